@@ -0,0 +1,175 @@
+//! Streak alarms: user-configured patterns checked against the round history
+//! after every hand, raising a banner (and optionally the terminal bell).
+
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmRule {
+    /// `length` of the same outcome in a row. Ties neither extend nor break a
+    /// player or banker streak.
+    Consecutive { outcome: u8, length: usize },
+    /// At least `count` of `outcome` within the last `window` hands.
+    WithinWindow {
+        outcome: u8,
+        count: usize,
+        window: usize,
+    },
+}
+
+fn outcome_name(outcome: u8) -> &'static str {
+    match outcome {
+        1 => "Player",
+        2 => "Banker",
+        _ => "Tie",
+    }
+}
+
+impl AlarmRule {
+    /// Parses `OUTCOME:N` (N in a row) or `OUTCOME:COUNT/WINDOW`, where
+    /// OUTCOME is player, banker, or tie (or p/b/t).
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let err = || {
+            format!(
+                "invalid alarm '{}', expected e.g. banker:6 or tie:3/10",
+                spec
+            )
+        };
+        let (outcome, rest) = spec.split_once(':').ok_or_else(err)?;
+        let outcome = match outcome.to_ascii_lowercase().as_str() {
+            "p" | "player" => 1,
+            "b" | "banker" => 2,
+            "t" | "tie" => 3,
+            _ => return Err(err()),
+        };
+        let rule = match rest.split_once('/') {
+            Some((count, window)) => AlarmRule::WithinWindow {
+                outcome,
+                count: count.parse().map_err(|_| err())?,
+                window: window.parse().map_err(|_| err())?,
+            },
+            None => AlarmRule::Consecutive {
+                outcome,
+                length: rest.parse().map_err(|_| err())?,
+            },
+        };
+        match rule {
+            AlarmRule::Consecutive { length: 0, .. } => Err(err()),
+            AlarmRule::WithinWindow { count, window, .. } if count == 0 || count > window => {
+                Err(err())
+            }
+            _ => Ok(rule),
+        }
+    }
+
+    fn outcome(&self) -> u8 {
+        match *self {
+            AlarmRule::Consecutive { outcome, .. } | AlarmRule::WithinWindow { outcome, .. } => {
+                outcome
+            }
+        }
+    }
+
+    /// Whether the latest hand in `history` (winners, oldest first) completes
+    /// or extends the pattern.
+    pub fn is_triggered(&self, history: &[u8]) -> bool {
+        if history.last() != Some(&self.outcome()) {
+            return false;
+        }
+        match *self {
+            AlarmRule::Consecutive { outcome, length } => {
+                let streak = history
+                    .iter()
+                    .rev()
+                    .filter(|&&w| outcome == 3 || w != 3)
+                    .take_while(|&&w| w == outcome)
+                    .count();
+                streak >= length
+            }
+            AlarmRule::WithinWindow {
+                outcome,
+                count,
+                window,
+            } => {
+                let hits = history
+                    .iter()
+                    .rev()
+                    .take(window)
+                    .filter(|&&w| w == outcome)
+                    .count();
+                hits >= count
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match *self {
+            AlarmRule::Consecutive { outcome, length } => {
+                format!("{} {}s in a row", length, outcome_name(outcome))
+            }
+            AlarmRule::WithinWindow {
+                outcome,
+                count,
+                window,
+            } => {
+                format!("{} {}s in {} hands", count, outcome_name(outcome), window)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreakAlarms {
+    pub rules: Vec<AlarmRule>,
+    pub bell: bool,
+}
+
+impl StreakAlarms {
+    pub fn new(rules: Vec<AlarmRule>, bell: bool) -> Self {
+        Self { rules, bell }
+    }
+
+    /// 6 bankers or players in a row, or 3 ties in 10 hands.
+    pub fn defaults() -> Self {
+        Self::new(
+            vec![
+                AlarmRule::Consecutive {
+                    outcome: 2,
+                    length: 6,
+                },
+                AlarmRule::Consecutive {
+                    outcome: 1,
+                    length: 6,
+                },
+                AlarmRule::WithinWindow {
+                    outcome: 3,
+                    count: 3,
+                    window: 10,
+                },
+            ],
+            true,
+        )
+    }
+
+    /// Banner text for every rule the latest hand triggered, if any.
+    pub fn check(&self, history: &[u8]) -> Option<String> {
+        let fired: Vec<String> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.is_triggered(history))
+            .map(AlarmRule::describe)
+            .collect();
+        if fired.is_empty() {
+            None
+        } else {
+            Some(format!("STREAK ALERT: {}", fired.join(", ")))
+        }
+    }
+
+    pub fn ring_bell(&self) {
+        if self.bell {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+}
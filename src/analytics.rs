@@ -0,0 +1,843 @@
+//! Theoretical odds computed by exact enumeration of every deal under an
+//! infinite-deck model (each rank 1/13, or 1/9 in Super Pan 9's stripped
+//! decks, with court cards told apart for Three Card Baccarat), evaluated
+//! through the engine's own payout code so the figures always match the
+//! active pay tables. Draws left to the player are made the way the
+//! tableau makes them. A shoe's odds are worked out exactly instead: every
+//! deal is enumerated card by card from what is left in it, each card
+//! coming out of the shoe as it is drawn, so the figures are the true
+//! chances of the next hand rather than an estimate.
+
+use crate::baccarat::{
+    BaccaratGame, BetSlip, BetType, BonusBetType, BonusBets, Card, Deck, DrawRule, GameMode,
+    HEARTS, Hand, Money, OverUnderRule, PayoutTable, SPADES,
+};
+use crate::three_card;
+use std::collections::BTreeMap;
+
+/// Chance of drawing each rank, ace to king.
+pub type RankWeights = [f64; 13];
+
+/// Rank weights of a fresh deck at a `mode` table.
+pub fn full_deck(mode: GameMode) -> RankWeights {
+    let top_rank = mode.top_rank();
+    std::array::from_fn(|i| {
+        if i < top_rank as usize {
+            1.0 / top_rank as f64
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Fewest cards a shoe's odds are worked out from: enough for any deal.
+const EXACT_MIN_CARDS: u32 = 6;
+
+/// Ranks standing in for each baccarat value, with their weights; the
+/// ten-valued ranks share one representative.
+fn value_ranks(weights: &RankWeights) -> Vec<(u8, f64)> {
+    let mut ranks: Vec<(u8, f64)> = (1..=9)
+        .map(|rank| (rank, weights[rank as usize - 1]))
+        .collect();
+    ranks.insert(0, (10, weights[9..].iter().sum()));
+    ranks.retain(|&(_, weight)| weight > 0.0);
+    ranks
+}
+
+/// Whether a hand under `rule` draws, an optional draw going the way the
+/// tableau (`tableau_draws`) would.
+fn draws(rule: DrawRule, tableau_draws: bool) -> bool {
+    match rule {
+        DrawRule::Draw => true,
+        DrawRule::Stand => false,
+        DrawRule::Optional => tableau_draws,
+    }
+}
+
+/// Stake used when asking the engine for payouts, large enough that cent
+/// rounding is negligible.
+const STAKE: Money = Money::from_dollars(10_000);
+
+/// Side-bet stake.
+const BONUS_STAKE: Money = Money::from_dollars(100);
+
+/// Side bets settled on card values alone. Pair bets depend on rank and suit,
+/// which the value-only enumeration does not model; Dragon Tiger deals are
+/// enumerated card by card, so its suited tie is priced here too.
+const VALUE_SIDE_BETS: [BonusBetType; 12] = [
+    BonusBetType::PlayerDragon,
+    BonusBetType::BankerDragon,
+    BonusBetType::Lucky6,
+    BonusBetType::Over,
+    BonusBetType::Under,
+    BonusBetType::EitherNatural,
+    BonusBetType::SuperSix,
+    BonusBetType::Big,
+    BonusBetType::Small,
+    BonusBetType::MonkeySix,
+    BonusBetType::MonkeyTie,
+    BonusBetType::SuitedTie,
+];
+
+/// Per-hand chance of each pair bet winning. Pairs depend only on the first
+/// two cards of each hand, so these follow from one rank (or rank and suit)
+/// matching: 1/13, or 1/52 for a suited pair.
+const PAIR_HIT_RATES: [(BonusBetType, f64); 4] = [
+    (BonusBetType::PlayerPair, 1.0 / 13.0),
+    (BonusBetType::BankerPair, 1.0 / 13.0),
+    (
+        BonusBetType::EitherPair,
+        1.0 - (12.0 / 13.0) * (12.0 / 13.0),
+    ),
+    (
+        BonusBetType::PerfectPair,
+        1.0 - (51.0 / 52.0) * (51.0 / 52.0),
+    ),
+];
+
+/// `PAIR_HIT_RATES` for cards drawn at `weights`: two cards match in rank
+/// with chance Σw², and in suit as well a quarter as often.
+fn pair_hit_rates(weights: &RankWeights) -> [(BonusBetType, f64); 4] {
+    let pair: f64 = weights.iter().map(|weight| weight * weight).sum();
+    let suited = pair / 4.0;
+    [
+        (BonusBetType::PlayerPair, pair),
+        (BonusBetType::BankerPair, pair),
+        (BonusBetType::EitherPair, 1.0 - (1.0 - pair) * (1.0 - pair)),
+        (
+            BonusBetType::PerfectPair,
+            1.0 - (1.0 - suited) * (1.0 - suited),
+        ),
+    ]
+}
+
+/// Chance that a hand's first two cards pair, and that both hands' do, for
+/// cards dealt without replacement from `counts` of each kind.
+fn pair_chances(counts: &[f64]) -> (f64, f64) {
+    let n: f64 = counts.iter().sum();
+    let pairs = |count: f64| (count * (count - 1.0)).max(0.0);
+    let one: f64 = counts.iter().map(|&count| pairs(count)).sum::<f64>() / (n * (n - 1.0));
+    let mut both = 0.0;
+    for (i, &first) in counts.iter().enumerate() {
+        for (j, &second) in counts.iter().enumerate() {
+            both += pairs(first) * pairs(if i == j { second - 2.0 } else { second });
+        }
+    }
+    (one, both / (n * (n - 1.0) * (n - 2.0) * (n - 3.0)))
+}
+
+/// `PAIR_HIT_RATES` for the next hand from a shoe holding `counts` of each
+/// rank. The shoe keeps ranks only, so suits are taken to be spread evenly
+/// within each rank for the Perfect Pair.
+fn exact_pair_hit_rates(counts: &[u32; 13]) -> [(BonusBetType, f64); 4] {
+    let ranks: Vec<f64> = counts.iter().map(|&count| count as f64).collect();
+    let suited: Vec<f64> = ranks.iter().flat_map(|&count| [count / 4.0; 4]).collect();
+    let (pair, both) = pair_chances(&ranks);
+    let (suited_pair, both_suited) = pair_chances(&suited);
+    [
+        (BonusBetType::PlayerPair, pair),
+        (BonusBetType::BankerPair, pair),
+        (BonusBetType::EitherPair, 2.0 * pair - both),
+        (BonusBetType::PerfectPair, 2.0 * suited_pair - both_suited),
+    ]
+}
+
+/// Three Card Baccarat hands grouped by how they rank, each as one
+/// representative hand with the chance of being dealt a hand of that rank.
+/// Tens and court cards are kept apart, as only court cards make 3 Faces.
+fn three_card_hands(weights: &RankWeights) -> Vec<([Card; 3], f64)> {
+    let ranks: Vec<(u8, f64)> = (1..=10)
+        .map(|rank| (rank, weights[rank as usize - 1]))
+        .chain([(13, weights[10..].iter().sum())])
+        .filter(|&(_, weight)| weight > 0.0)
+        .collect();
+    let mut hands: BTreeMap<_, ([Card; 3], f64)> = BTreeMap::new();
+    for &(r1, w1) in &ranks {
+        for &(r2, w2) in &ranks {
+            for &(r3, w3) in &ranks {
+                let hand = [r1, r2, r3].map(|rank| Card::new(SPADES, rank));
+                hands
+                    .entry(three_card::strength(&hand))
+                    .or_insert((hand, 0.0))
+                    .1 += w1 * w2 * w3;
+            }
+        }
+    }
+    hands.into_values().collect()
+}
+
+/// Calls `visit(player_hand, banker_hand, probability)` for every distinct
+/// completed deal at a `mode` table, following the engine's drawing rules.
+pub fn for_each_deal(mode: GameMode, visit: impl FnMut(&[Card], &[Card], f64)) {
+    for_each_weighted_deal(mode, &full_deck(mode), visit);
+}
+
+/// `for_each_deal` with every card drawn at `weights`.
+fn for_each_weighted_deal(
+    mode: GameMode,
+    weights: &RankWeights,
+    mut visit: impl FnMut(&[Card], &[Card], f64),
+) {
+    if mode == GameMode::ThreeCard {
+        let hands = three_card_hands(weights);
+        for (player, wp) in &hands {
+            for (banker, wb) in &hands {
+                visit(player, banker, wp * wb);
+            }
+        }
+        return;
+    }
+    if mode == GameMode::DragonTiger {
+        // Each suit takes a quarter of its rank's weight.
+        let deck = Deck::new();
+        let weight = |card: &Card| weights[card.rank as usize - 1] / 4.0;
+        for dragon in deck.cards() {
+            for tiger in deck.cards() {
+                visit(
+                    std::slice::from_ref(dragon),
+                    std::slice::from_ref(tiger),
+                    weight(dragon) * weight(tiger),
+                );
+            }
+        }
+        return;
+    }
+    let card = |rank: u8| Card::new(SPADES, rank);
+    let value_ranks = value_ranks(weights);
+    for &(p1, wp1) in &value_ranks {
+        for &(b1, wb1) in &value_ranks {
+            for &(p2, wp2) in &value_ranks {
+                for &(b2, wb2) in &value_ranks {
+                    let p = wp1 * wb1 * wp2 * wb2;
+                    let mut player = vec![card(p1), card(p2)];
+                    let mut banker = vec![card(b1), card(b2)];
+                    let player_score = Hand::points(&player);
+                    let banker_score = Hand::points(&banker);
+
+                    if player_score >= mode.natural_minimum()
+                        || banker_score >= mode.natural_minimum()
+                    {
+                        visit(&player, &banker, p);
+                        continue;
+                    }
+
+                    let banker_draws = |third: Option<u8>| {
+                        draws(
+                            mode.banker_draw(banker_score, third),
+                            BaccaratGame::banker_should_draw(banker_score, third),
+                        )
+                    };
+                    if draws(
+                        mode.player_draw(player_score),
+                        BaccaratGame::player_should_draw(player_score),
+                    ) {
+                        for &(p3, wp3) in &value_ranks {
+                            player.push(card(p3));
+                            if banker_draws(Some(card(p3).baccarat_value())) {
+                                for &(b3, wb3) in &value_ranks {
+                                    banker.push(card(b3));
+                                    visit(&player, &banker, p * wp3 * wb3);
+                                    banker.pop();
+                                }
+                            } else {
+                                visit(&player, &banker, p * wp3);
+                            }
+                            player.pop();
+                        }
+                    } else if banker_draws(None) {
+                        for &(b3, wb3) in &value_ranks {
+                            banker.push(card(b3));
+                            visit(&player, &banker, p * wb3);
+                            banker.pop();
+                        }
+                    } else {
+                        visit(&player, &banker, p);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The cards left in a shoe by baccarat value while deals are enumerated
+/// from it, ten-valued ranks counted together.
+struct ValueShoe {
+    counts: [u32; 10],
+    total: u32,
+}
+
+impl ValueShoe {
+    fn new(counts: &[u32; 13]) -> Self {
+        let mut values = [0; 10];
+        for (rank, &count) in (1..=13).zip(counts) {
+            values[if rank < 10 { rank } else { 0 }] += count;
+        }
+        Self {
+            counts: values,
+            total: counts.iter().sum(),
+        }
+    }
+
+    /// Draws a card of `value`, returning the chance it was the one to come
+    /// out; `None` if there are none left.
+    fn take(&mut self, value: usize) -> Option<f64> {
+        if self.counts[value] == 0 {
+            return None;
+        }
+        let chance = self.counts[value] as f64 / self.total as f64;
+        self.counts[value] -= 1;
+        self.total -= 1;
+        Some(chance)
+    }
+
+    fn put_back(&mut self, value: usize) {
+        self.counts[value] += 1;
+        self.total += 1;
+    }
+}
+
+/// A card standing in for baccarat value `value`.
+fn value_card(value: usize) -> Card {
+    Card::new(SPADES, if value == 0 { 10 } else { value as u8 })
+}
+
+/// Ways to deal `hand` (classes, in order) from `counts` of each class, as
+/// a count of card combinations.
+fn combinations(counts: &[u32], hand: &[usize]) -> f64 {
+    let mut taken = vec![0u32; counts.len()];
+    let mut ways = 1.0;
+    for &class in hand {
+        let left = counts[class].saturating_sub(taken[class]);
+        taken[class] += 1;
+        ways *= left as f64 / taken[class] as f64;
+    }
+    ways
+}
+
+/// `for_each_deal` for the next hand from a shoe holding `counts` of each
+/// rank, every card taken out of the shoe as it is dealt. Three Card
+/// Baccarat hands are enumerated as sets of ranks, and Dragon Tiger's cards
+/// take a quarter of their rank's count for each suit.
+fn for_each_exact_deal(
+    mode: GameMode,
+    counts: &[u32; 13],
+    mut visit: impl FnMut(&[Card], &[Card], f64),
+) {
+    let total: u32 = counts.iter().sum();
+    if mode == GameMode::ThreeCard {
+        // Tens and court cards are kept apart, as only court cards make 3 Faces.
+        let mut classes = [0u32; 11];
+        classes[..10].copy_from_slice(&counts[..10]);
+        classes[10] = counts[10..].iter().sum();
+        let rank = |class: usize| if class == 10 { 13 } else { class as u8 + 1 };
+        let mut hands = Vec::new();
+        for i in 0..11 {
+            for j in i..11 {
+                for k in j..11 {
+                    hands.push([i, j, k]);
+                }
+            }
+        }
+        let n = total as f64;
+        let deals = n * (n - 1.0) * (n - 2.0) / 6.0 * (n - 3.0) * (n - 4.0) * (n - 5.0) / 6.0;
+        for player in &hands {
+            let player_ways = combinations(&classes, player);
+            if player_ways == 0.0 {
+                continue;
+            }
+            let mut left = classes;
+            for &class in player {
+                left[class] -= 1;
+            }
+            let player_cards = player.map(|class| Card::new(SPADES, rank(class)));
+            for banker in &hands {
+                let ways = player_ways * combinations(&left, banker);
+                if ways > 0.0 {
+                    visit(
+                        &player_cards,
+                        &banker.map(|class| Card::new(SPADES, rank(class))),
+                        ways / deals,
+                    );
+                }
+            }
+        }
+        return;
+    }
+    if mode == GameMode::DragonTiger {
+        let deck = Deck::new();
+        let per_suit = |card: &Card| counts[card.rank as usize - 1] as f64 / 4.0;
+        let pairs = total as f64 * (total as f64 - 1.0);
+        for dragon in deck.cards() {
+            for tiger in deck.cards() {
+                let left = per_suit(tiger) - if dragon == tiger { 1.0 } else { 0.0 };
+                let p = per_suit(dragon) * left.max(0.0) / pairs;
+                if p > 0.0 {
+                    visit(std::slice::from_ref(dragon), std::slice::from_ref(tiger), p);
+                }
+            }
+        }
+        return;
+    }
+    let mut shoe = ValueShoe::new(counts);
+    for p1 in 0..10 {
+        let Some(wp1) = shoe.take(p1) else {
+            continue;
+        };
+        for b1 in 0..10 {
+            let Some(wb1) = shoe.take(b1) else {
+                continue;
+            };
+            for p2 in 0..10 {
+                let Some(wp2) = shoe.take(p2) else {
+                    continue;
+                };
+                for b2 in 0..10 {
+                    let Some(wb2) = shoe.take(b2) else {
+                        continue;
+                    };
+                    let p = wp1 * wb1 * wp2 * wb2;
+                    let mut player = vec![value_card(p1), value_card(p2)];
+                    let mut banker = vec![value_card(b1), value_card(b2)];
+                    let player_score = Hand::points(&player);
+                    let banker_score = Hand::points(&banker);
+                    let banker_draws = |third: Option<u8>| {
+                        draws(
+                            mode.banker_draw(banker_score, third),
+                            BaccaratGame::banker_should_draw(banker_score, third),
+                        )
+                    };
+
+                    if player_score >= mode.natural_minimum()
+                        || banker_score >= mode.natural_minimum()
+                    {
+                        visit(&player, &banker, p);
+                    } else if draws(
+                        mode.player_draw(player_score),
+                        BaccaratGame::player_should_draw(player_score),
+                    ) {
+                        for p3 in 0..10 {
+                            let Some(wp3) = shoe.take(p3) else {
+                                continue;
+                            };
+                            player.push(value_card(p3));
+                            if banker_draws(Some(p3 as u8)) {
+                                for b3 in 0..10 {
+                                    let Some(wb3) = shoe.take(b3) else {
+                                        continue;
+                                    };
+                                    banker.push(value_card(b3));
+                                    visit(&player, &banker, p * wp3 * wb3);
+                                    banker.pop();
+                                    shoe.put_back(b3);
+                                }
+                            } else {
+                                visit(&player, &banker, p * wp3);
+                            }
+                            player.pop();
+                            shoe.put_back(p3);
+                        }
+                    } else if banker_draws(None) {
+                        for b3 in 0..10 {
+                            let Some(wb3) = shoe.take(b3) else {
+                                continue;
+                            };
+                            banker.push(value_card(b3));
+                            visit(&player, &banker, p * wb3);
+                            banker.pop();
+                            shoe.put_back(b3);
+                        }
+                    } else {
+                        visit(&player, &banker, p);
+                    }
+                    shoe.put_back(b2);
+                }
+                shoe.put_back(p2);
+            }
+            shoe.put_back(b1);
+        }
+        shoe.put_back(p1);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModeOdds {
+    pub mode: GameMode,
+    /// House edge per main bet, as a fraction of the stake.
+    pub house_edges: Vec<(BetType, f64)>,
+    /// Every side bet offered, with its house edge where the enumeration can
+    /// price it.
+    pub side_bets: Vec<(BonusBetType, Option<f64>)>,
+}
+
+impl ModeOdds {
+    pub fn house_edge(&self, bet: BetType) -> Option<f64> {
+        self.house_edges
+            .iter()
+            .find(|(name, _)| *name == bet)
+            .map(|(_, edge)| *edge)
+    }
+}
+
+/// House edges for every main bet of every mode.
+pub fn compare_modes() -> Vec<ModeOdds> {
+    let side_bets: Vec<BonusBets> = VALUE_SIDE_BETS
+        .iter()
+        .map(|&name| {
+            let mut bets = BonusBets::new();
+            bets.set(name, BONUS_STAKE);
+            bets
+        })
+        .collect();
+
+    GameMode::ALL
+        .iter()
+        .map(|&mode| {
+            let mut game = BaccaratGame::with_mode(mode);
+            let mut returns = vec![0.0; mode.main_bets().len()];
+            let mut side = vec![0.0; VALUE_SIDE_BETS.len()];
+            for_each_deal(mode, |player, banker, p| {
+                game.set_hands(player, banker);
+                for (bet, expected) in mode.main_bets().iter().zip(returns.iter_mut()) {
+                    *expected += p * game.calculate_main_bet_payout(*bet, STAKE).as_dollars();
+                }
+                for (bets, expected) in side_bets.iter().zip(side.iter_mut()) {
+                    *expected += p * bets.calculate_payouts(&game).as_dollars();
+                }
+            });
+            ModeOdds {
+                mode,
+                house_edges: mode
+                    .main_bets()
+                    .iter()
+                    .zip(returns)
+                    .map(|(&bet, expected)| (bet, 1.0 - expected / STAKE.as_dollars()))
+                    .collect(),
+                side_bets: BonusBetType::ALL
+                    .iter()
+                    .filter(|&&name| mode.offers_side_bet(name))
+                    .map(|&name| {
+                        let edge = VALUE_SIDE_BETS
+                            .iter()
+                            .position(|&bet| bet == name)
+                            .map(|i| 1.0 - side[i] / BONUS_STAKE.as_dollars());
+                        (name, edge)
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// House edge of every bet open at a table, as a fraction of the stake.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableEdges {
+    pub main_bets: Vec<(BetType, f64)>,
+    /// Every side bet offered, in `BonusBetType::ALL` order.
+    pub side_bets: Vec<(BonusBetType, f64)>,
+}
+
+impl TableEdges {
+    pub fn side_bet(&self, bet: BonusBetType) -> Option<f64> {
+        self.side_bets
+            .iter()
+            .find(|(name, _)| *name == bet)
+            .map(|(_, edge)| *edge)
+    }
+}
+
+/// Everything the house edges at a table depend on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeInputs {
+    pub mode: GameMode,
+    pub over_under: OverUnderRule,
+    pub payout_table: PayoutTable,
+    /// What is left in the shoe, rank by rank; `None` for a table with no
+    /// shoe to count, priced as a fresh deck.
+    pub counts: Option<[u32; 13]>,
+}
+
+impl EdgeInputs {
+    pub fn house_edges(&self) -> TableEdges {
+        let exact = self.counts.and_then(|counts| {
+            exact_odds(
+                self.mode,
+                self.over_under,
+                self.payout_table.clone(),
+                &counts,
+            )
+        });
+        match exact {
+            Some(odds) => odds.edges,
+            None => house_edges(
+                self.mode,
+                self.over_under,
+                self.payout_table.clone(),
+                &full_deck(self.mode),
+            ),
+        }
+    }
+}
+
+/// The chances of the next hand and what every bet open is worth on it.
+#[derive(Debug, Clone)]
+pub struct ShoeOdds {
+    /// Player, banker, and tie.
+    pub outcomes: [f64; 3],
+    pub edges: TableEdges,
+    /// Chance of each side bet offered paying, in `BonusBetType::ALL` order.
+    pub side_bet_hits: Vec<(BonusBetType, f64)>,
+}
+
+/// The odds at a `mode` table with the given over/under line and pay
+/// table, dealing at `weights`, e.g. `full_deck`. Pair bets are priced from
+/// their hit rates at those weights.
+pub fn deck_odds(
+    mode: GameMode,
+    over_under: OverUnderRule,
+    payout_table: PayoutTable,
+    weights: &RankWeights,
+) -> ShoeOdds {
+    deal_odds(
+        mode,
+        over_under,
+        payout_table,
+        pair_hit_rates(weights),
+        |visit| for_each_weighted_deal(mode, weights, visit),
+    )
+}
+
+/// The house edges of `deck_odds`.
+pub fn house_edges(
+    mode: GameMode,
+    over_under: OverUnderRule,
+    payout_table: PayoutTable,
+    weights: &RankWeights,
+) -> TableEdges {
+    deck_odds(mode, over_under, payout_table, weights).edges
+}
+
+/// The exact odds of the next hand from a shoe holding `counts` of each
+/// rank, at a `mode` table with the given over/under line and pay table;
+/// `None` if there are too few cards left to deal a hand.
+pub fn exact_odds(
+    mode: GameMode,
+    over_under: OverUnderRule,
+    payout_table: PayoutTable,
+    counts: &[u32; 13],
+) -> Option<ShoeOdds> {
+    if counts.iter().sum::<u32>() < EXACT_MIN_CARDS {
+        return None;
+    }
+    Some(deal_odds(
+        mode,
+        over_under,
+        payout_table,
+        exact_pair_hit_rates(counts),
+        |visit| for_each_exact_deal(mode, counts, visit),
+    ))
+}
+
+/// Settles every deal `deals` visits and adds up the outcomes and what each
+/// bet returned, pricing the pair bets from `pair_rates`.
+fn deal_odds(
+    mode: GameMode,
+    over_under: OverUnderRule,
+    payout_table: PayoutTable,
+    pair_rates: [(BonusBetType, f64); 4],
+    deals: impl FnOnce(&mut dyn FnMut(&[Card], &[Card], f64)),
+) -> ShoeOdds {
+    let mut game = BaccaratGame::with_mode(mode);
+    game.over_under = over_under;
+    game.payout_table = payout_table;
+    let value_bets: Vec<(BonusBetType, BonusBets)> = VALUE_SIDE_BETS
+        .iter()
+        .filter(|&&name| mode.offers_side_bet(name))
+        .map(|&name| {
+            let mut bets = BonusBets::new();
+            bets.set(name, BONUS_STAKE);
+            (name, bets)
+        })
+        .collect();
+    let mut outcomes = [0.0; 3];
+    let mut main_returns = vec![0.0; mode.main_bets().len()];
+    let mut side_returns = vec![0.0; value_bets.len()];
+    let mut side_hits = vec![0.0; value_bets.len()];
+    deals(&mut |player, banker, p| {
+        game.set_hands(player, banker);
+        if let winner @ 1..=3 = game.state.winner {
+            outcomes[winner as usize - 1] += p;
+        }
+        for (bet, expected) in mode.main_bets().iter().zip(main_returns.iter_mut()) {
+            *expected += p * game.calculate_main_bet_payout(*bet, STAKE).as_dollars();
+        }
+        for (((_, bets), expected), hits) in value_bets
+            .iter()
+            .zip(side_returns.iter_mut())
+            .zip(side_hits.iter_mut())
+        {
+            let paid = bets.calculate_payouts(&game).as_dollars();
+            *expected += p * paid;
+            if paid > 0.0 {
+                *hits += p;
+            }
+        }
+    });
+    let mut side_bets: Vec<(BonusBetType, f64)> = value_bets
+        .iter()
+        .zip(side_returns)
+        .map(|((name, _), expected)| (*name, 1.0 - expected / BONUS_STAKE.as_dollars()))
+        .collect();
+    let mut side_bet_hits: Vec<(BonusBetType, f64)> = value_bets
+        .iter()
+        .map(|(name, _)| *name)
+        .zip(side_hits)
+        .collect();
+
+    // A suited pair of aces in both hands wins every pair bet.
+    let pair = [Card::new(HEARTS, 1), Card::new(HEARTS, 1)];
+    game.set_hands(&pair, &pair);
+    for (name, rate) in pair_rates {
+        if !mode.offers_side_bet(name) {
+            continue;
+        }
+        let mut single = BonusBets::new();
+        single.set(name, BONUS_STAKE);
+        let win = single.calculate_payouts(&game).as_dollars();
+        side_bets.push((name, 1.0 - rate * win / BONUS_STAKE.as_dollars()));
+        side_bet_hits.push((name, rate));
+    }
+    let order = |name: &BonusBetType| BonusBetType::ALL.iter().position(|bet| bet == name);
+    side_bets.sort_by_key(|(name, _)| order(name));
+    side_bet_hits.sort_by_key(|(name, _)| order(name));
+
+    ShoeOdds {
+        outcomes,
+        edges: TableEdges {
+            main_bets: mode
+                .main_bets()
+                .iter()
+                .zip(main_returns)
+                .map(|(&bet, expected)| (bet, 1.0 - expected / STAKE.as_dollars()))
+                .collect(),
+            side_bets,
+        },
+        side_bet_hits,
+    }
+}
+
+/// Per-hand probability of each outcome and of each side bet paying.
+#[derive(Debug, Clone)]
+pub struct HitRates {
+    /// Player, banker, and tie.
+    pub outcomes: [f64; 3],
+    pub side_bets: Vec<(BonusBetType, f64)>,
+}
+
+impl HitRates {
+    pub fn side_bet(&self, name: BonusBetType) -> Option<f64> {
+        self.side_bets
+            .iter()
+            .find(|(bet, _)| *bet == name)
+            .map(|(_, rate)| *rate)
+    }
+}
+
+/// Theoretical hit rates for a table in `mode` using the given over/under
+/// line.
+pub fn hit_rates(mode: GameMode, over_under: OverUnderRule) -> HitRates {
+    let side_bets: Vec<BonusBets> = VALUE_SIDE_BETS
+        .iter()
+        .map(|&name| {
+            let mut bets = BonusBets::new();
+            bets.set(name, Money::from_dollars(1));
+            bets
+        })
+        .collect();
+    let mut game = BaccaratGame::with_mode(mode);
+    game.over_under = over_under;
+    let mut outcomes = [0.0; 3];
+    let mut side_hits = vec![0.0; VALUE_SIDE_BETS.len()];
+
+    for_each_deal(mode, |player, banker, p| {
+        game.set_hands(player, banker);
+        if let winner @ 1..=3 = game.state.winner {
+            outcomes[winner as usize - 1] += p;
+        }
+        for (bets, hits) in side_bets.iter().zip(side_hits.iter_mut()) {
+            if bets.calculate_payouts(&game) > Money::ZERO {
+                *hits += p;
+            }
+        }
+    });
+
+    HitRates {
+        outcomes,
+        side_bets: BonusBetType::ALL
+            .iter()
+            .filter(|&&name| mode.offers_side_bet(name))
+            .filter_map(|&name| {
+                let rate = match VALUE_SIDE_BETS.iter().position(|&bet| bet == name) {
+                    Some(i) => side_hits[i],
+                    None => PAIR_HIT_RATES.iter().find(|(bet, _)| *bet == name)?.1,
+                };
+                Some((name, rate))
+            })
+            .collect(),
+    }
+}
+
+/// Mean and variance of one hand's net result for a bet layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BetOutlook {
+    pub mean: f64,
+    pub variance: f64,
+}
+
+/// Net result per hand of a bet slip at a table with the given over/under
+/// line and pay table. Pair bets are priced from their hit rates and treated
+/// as independent of the rest of the layout.
+pub fn bet_outlook(
+    mode: GameMode,
+    over_under: OverUnderRule,
+    payout_table: PayoutTable,
+    slip: &BetSlip,
+) -> BetOutlook {
+    let mut game = BaccaratGame::with_mode(mode);
+    game.over_under = over_under;
+    game.payout_table = payout_table;
+
+    let bonus_bets = &slip.bonus_bets;
+    let mut value_bets = *bonus_bets;
+    for (name, _) in PAIR_HIT_RATES {
+        value_bets.set(name, Money::ZERO);
+    }
+    let stake = (slip.total_main_bet() + value_bets.total_bet()).as_dollars();
+    let (mut mean, mut second_moment) = (0.0, 0.0);
+    for_each_deal(mode, |player, banker, p| {
+        game.set_hands(player, banker);
+        let main: Money = slip
+            .main_bets()
+            .map(|(bet, amount)| game.calculate_main_bet_payout(bet, amount))
+            .sum();
+        let net = (main + value_bets.calculate_payouts(&game)).as_dollars() - stake;
+        mean += p * net;
+        second_moment += p * net * net;
+    });
+    let mut variance = second_moment - mean * mean;
+
+    // A suited pair of aces in both hands wins every pair bet.
+    let pair = [Card::new(HEARTS, 1), Card::new(HEARTS, 1)];
+    game.set_hands(&pair, &pair);
+    for (name, rate) in PAIR_HIT_RATES {
+        let staked = bonus_bets.get(name);
+        if staked == Money::ZERO {
+            continue;
+        }
+        let mut single = BonusBets::new();
+        single.set(name, staked);
+        let win = single.calculate_payouts(&game).as_dollars();
+        mean += rate * win - staked.as_dollars();
+        variance += rate * (1.0 - rate) * win * win;
+    }
+    BetOutlook { mean, variance }
+}
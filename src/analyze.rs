@@ -0,0 +1,126 @@
+//! `analyze` subcommand: the exact odds of the next hand from a shoe, fresh
+//! or with cards already dealt from it. Every deal is enumerated from the
+//! cards left, so the chances and house edges are exact rather than
+//! estimated, and they are set beside the infinite-deck figures to show how
+//! far the shoe has moved them.
+
+use crate::analytics::{self, ShoeOdds};
+use crate::baccarat::{BaccaratGame, Card, GameMode, OverUnderRule, Side};
+use crate::card_renderer::CardRenderer;
+use std::io::{self, Write};
+use std::time::Instant;
+
+fn percent(value: f64) -> String {
+    format!("{:.4}%", value * 100.0)
+}
+
+/// Takes `dealt` out of a fresh `decks`-deck shoe for a `mode` table and
+/// writes the exact odds of the next hand from what is left to `out`.
+pub fn run(
+    out: &mut impl Write,
+    mode: GameMode,
+    decks: usize,
+    dealt: &[Card],
+) -> Result<(), String> {
+    let mut counts = [0u32; 13];
+    for count in &mut counts[..mode.top_rank() as usize] {
+        *count = decks as u32 * 4;
+    }
+    for card in dealt {
+        let count = &mut counts[card.rank as usize - 1];
+        if *count == 0 {
+            return Err(format!(
+                "the shoe has no {} left to take out",
+                CardRenderer::rank_symbol(card.rank)
+            ));
+        }
+        *count -= 1;
+    }
+    let payout_table = BaccaratGame::with_mode(mode).payout_table;
+    let over_under = OverUnderRule::standard();
+
+    let started = Instant::now();
+    let exact = analytics::exact_odds(mode, over_under, payout_table.clone(), &counts)
+        .ok_or_else(|| "too few cards left in the shoe to deal a hand".to_string())?;
+    let elapsed = started.elapsed().as_secs_f64();
+    let infinite =
+        analytics::deck_odds(mode, over_under, payout_table, &analytics::full_deck(mode));
+
+    writeln!(
+        out,
+        "{:?}, {} decks: {} cards left after {} dealt. Exact odds of the next hand ({:.2}s):",
+        mode,
+        decks,
+        counts.iter().sum::<u32>(),
+        dealt.len(),
+        elapsed
+    )
+    .and_then(|_| write_odds(out, mode, &exact, &infinite))
+    .map_err(|e| e.to_string())
+}
+
+fn write_odds(
+    out: &mut impl Write,
+    mode: GameMode,
+    exact: &ShoeOdds,
+    infinite: &ShoeOdds,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "\n{:<14} {:>12} {:>14}",
+        "Outcome", "Chance", "Infinite deck"
+    )?;
+    let outcomes = [
+        mode.side_name(Side::Player),
+        mode.side_name(Side::Banker),
+        "Tie",
+    ];
+    for ((name, chance), deck) in outcomes.iter().zip(exact.outcomes).zip(infinite.outcomes) {
+        writeln!(
+            out,
+            "{:<14} {:>12} {:>14}",
+            name,
+            percent(chance),
+            percent(deck)
+        )?;
+    }
+
+    writeln!(
+        out,
+        "\n{:<14} {:>12} {:>14}",
+        "Bet", "House edge", "Infinite deck"
+    )?;
+    for (&(bet, edge), &(_, deck)) in exact.edges.main_bets.iter().zip(&infinite.edges.main_bets) {
+        writeln!(
+            out,
+            "{:<14} {:>12} {:>14}",
+            mode.bet_label(bet),
+            percent(edge),
+            percent(deck)
+        )?;
+    }
+
+    if exact.edges.side_bets.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        out,
+        "\n{:<14} {:>12} {:>12} {:>14}",
+        "Side bet", "Hit rate", "House edge", "Infinite deck"
+    )?;
+    for (&(bet, edge), &(_, hits)) in exact.edges.side_bets.iter().zip(&exact.side_bet_hits) {
+        let deck = infinite
+            .edges
+            .side_bet(bet)
+            .map_or("-".to_string(), percent);
+        writeln!(
+            out,
+            "{:<14} {:>12} {:>12} {:>14}",
+            bet.to_string(),
+            percent(hits),
+            percent(edge),
+            deck
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,812 @@
+//! `serve --api`: the engine over HTTP, for web frontends and bots. Each
+//! session is a private table with its own shoe and bankroll, played with
+//! the same `GameSession` the terminal tables keep for their players: bets
+//! are laid out, then each deal locks them in, plays the hand and settles
+//! it. Requests and responses are JSON, typed by the structs below, and
+//! `GET /` describes every route. Session ids are random, so one client
+//! can't reach another's table by counting, and browsers on other origins
+//! are only let in when `--api-origin` names theirs.
+
+use crate::baccarat::{
+    BaccaratGame, BetType, BonusBetType, BonusBets, GameMode, GameSession, Money, ProcedureProfile,
+};
+use crate::protocol;
+use crate::settlement::SettlementReport;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// Where `--api` listens when given no address.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+/// Sessions open at once; the oldest is closed to make room.
+const MAX_SESSIONS: usize = 64;
+/// Hands each session remembers for `history`.
+const HISTORY_LIMIT: usize = 1000;
+/// Largest request body taken.
+const MAX_BODY: usize = 64 * 1024;
+/// Longest request line or header taken.
+const MAX_LINE: usize = 8 * 1024;
+/// Most headers taken on one request.
+const MAX_HEADERS: usize = 64;
+/// Requests answered at once; past this, connections are turned away.
+const MAX_CONNECTIONS: usize = 32;
+/// A client that sends nothing for this long is hung up on.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// A client that takes nothing for this long is hung up on.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `POST /sessions`: a new table. Anything left out is the server's.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NewSession {
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Dollars to start with.
+    #[serde(default)]
+    pub balance: Option<i32>,
+    /// Shuffle every shoe from this seed, to deal the same cards again.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// `PUT /sessions/{id}/bet`: a main bet for the next deal, alongside any
+/// others already down. An amount of 0 takes that bet down; side bets are
+/// named as `simulate` names them, e.g. `player_pair`, and replace any
+/// already down.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceBet {
+    pub bet: String,
+    pub amount: i32,
+    #[serde(default)]
+    pub side_bets: BTreeMap<String, i32>,
+}
+
+/// A bet as it stands on the layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BetView {
+    pub bet: String,
+    pub amount: Money,
+}
+
+/// How one bet on a dealt hand came out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettledView {
+    pub bet: String,
+    pub stake: Money,
+    /// Returned, stake included.
+    pub payout: Money,
+    /// "won", "lost", "push" or "rides".
+    pub outcome: String,
+}
+
+/// `POST /sessions/{id}/deal`: a hand, dealt and settled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandView {
+    pub round: u32,
+    pub shoe: u32,
+    pub player_cards: Vec<String>,
+    pub banker_cards: Vec<String>,
+    pub player_score: u8,
+    pub banker_score: u8,
+    /// 1 player, 2 banker, 3 tie.
+    pub winner: u8,
+    /// Why the bets didn't play, if they didn't.
+    pub sat_out: Option<String>,
+    pub bets: Vec<SettledView>,
+    pub net: Money,
+    pub balance: Money,
+}
+
+/// `GET /sessions/{id}`: the table as it stands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Random, and the only way to reach the session.
+    pub id: String,
+    pub mode: String,
+    pub decks: usize,
+    pub min_bet: i32,
+    pub max_bet: i32,
+    pub balance: Money,
+    /// The main bets the next deal plays.
+    pub bets: Vec<BetView>,
+    pub side_bets: Vec<BetView>,
+    /// The player and banker bets were barged by a tie and stay as they are
+    /// for the next deal.
+    pub bet_rides: bool,
+    pub rounds_played: u32,
+    pub shoe: u32,
+    /// Cards dealt from this shoe, and the shoe's size.
+    pub cards_dealt: usize,
+    pub shoe_size: usize,
+    pub last_hand: Option<HandView>,
+}
+
+/// `GET /sessions/{id}/history`: the hands dealt, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub hands: Vec<HandView>,
+}
+
+/// One route, as `GET /` lists it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub method: String,
+    pub path: String,
+    /// The body's type, if it takes one.
+    pub request: Option<String>,
+    pub response: String,
+}
+
+/// What every failed request answers with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub error: String,
+}
+
+fn endpoints() -> Vec<Endpoint> {
+    [
+        ("GET", "/", None, "[Endpoint]"),
+        ("POST", "/sessions", Some("NewSession"), "SessionState"),
+        ("GET", "/sessions/{id}", None, "SessionState"),
+        ("DELETE", "/sessions/{id}", None, "none"),
+        (
+            "PUT",
+            "/sessions/{id}/bet",
+            Some("PlaceBet"),
+            "SessionState",
+        ),
+        ("POST", "/sessions/{id}/deal", None, "HandView"),
+        ("GET", "/sessions/{id}/history", None, "History"),
+    ]
+    .into_iter()
+    .map(|(method, path, request, response)| Endpoint {
+        method: method.to_string(),
+        path: path.to_string(),
+        request: request.map(String::from),
+        response: response.to_string(),
+    })
+    .collect()
+}
+
+/// A private table: its shoe and the player's place at it.
+struct Session {
+    /// How many sessions were opened before this one, to close the oldest.
+    opened: u64,
+    game: BaccaratGame,
+    player: GameSession,
+    decks: usize,
+    shoe: u32,
+    history: Vec<HandView>,
+}
+
+impl Session {
+    fn state(&self, id: &str) -> SessionState {
+        let mode = self.game.mode;
+        let side_bets = BonusBetType::ALL
+            .into_iter()
+            .filter(|&bet| self.player.slip.bonus_bets.get(bet) > Money::ZERO)
+            .map(|bet| BetView {
+                bet: bet.to_string(),
+                amount: self.player.slip.bonus_bets.get(bet),
+            })
+            .collect();
+        let (cards_dealt, shoe_size) = self.game.card_source.penetration().unwrap_or((0, 0));
+        SessionState {
+            id: id.to_string(),
+            mode: format!("{:?}", mode),
+            decks: self.decks,
+            min_bet: self.game.limits.min_bet,
+            max_bet: self.game.limits.max_bet,
+            balance: self.player.balance,
+            bets: self
+                .player
+                .slip
+                .main_bets()
+                .map(|(bet, amount)| BetView {
+                    bet: bet.to_string(),
+                    amount,
+                })
+                .collect(),
+            side_bets,
+            bet_rides: self.player.main_bet_rides(),
+            rounds_played: self.player.rounds_played,
+            shoe: self.shoe,
+            cards_dealt,
+            shoe_size,
+            last_hand: self.history.last().cloned(),
+        }
+    }
+
+    /// Lays out the next deal's bets, leaving the layout as it was if the
+    /// table won't take them.
+    fn place_bet(&mut self, request: &PlaceBet) -> Result<(), String> {
+        let mode = self.game.mode;
+        let bet: BetType = request.bet.parse()?;
+        if !mode.main_bets().contains(&bet) {
+            return Err(format!("{:?} tables don't take {}", mode, bet));
+        }
+        if request.amount < 0 {
+            return Err("bets can't be negative".to_string());
+        }
+        let mut side_bets = BonusBets::new();
+        for (name, &amount) in &request.side_bets {
+            let side_bet: BonusBetType = name.parse()?;
+            if !mode.offers_side_bet(side_bet) {
+                return Err(format!("{:?} tables don't deal {}", mode, side_bet));
+            }
+            if amount < 0 {
+                return Err(format!("the {} bet can't be negative", side_bet));
+            }
+            side_bets.set(side_bet, Money::from_dollars(amount));
+        }
+        let before = self.player.slip;
+        self.player
+            .place_main_bet(bet, Money::from_dollars(request.amount))?;
+        self.player.slip.bonus_bets = side_bets;
+        if self.player.slip.total_main_bet() > Money::ZERO
+            && let Some(reason) = self.player.sit_out_reason(&self.game)
+        {
+            self.player.slip = before;
+            return Err(reason);
+        }
+        Ok(())
+    }
+
+    /// Deals a hand, playing the bets laid out if the table takes them.
+    fn deal(&mut self) -> Result<HandView, String> {
+        if self.game.prepare_next_round() {
+            self.shoe += 1;
+        }
+        let sat_out = self.player.lock_bets(&self.game).err();
+        self.game
+            .play_round_unattended()
+            .map_err(|e| e.to_string())?;
+        let report = self.player.settle(&self.game).cloned().unwrap_or_default();
+        let hand = HandView {
+            round: self.history.last().map_or(1, |hand| hand.round + 1),
+            shoe: self.shoe,
+            player_cards: protocol::card_labels(&self.game.player_hand),
+            banker_cards: protocol::card_labels(&self.game.banker_hand),
+            player_score: self.game.state.player_score,
+            banker_score: self.game.state.banker_score,
+            winner: self.game.state.winner,
+            sat_out,
+            bets: settled_views(&report),
+            net: report.net(),
+            balance: self.player.balance,
+        };
+        if self.history.len() >= HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.history.push(hand.clone());
+        Ok(hand)
+    }
+}
+
+fn settled_views(report: &SettlementReport) -> Vec<SettledView> {
+    report
+        .bets
+        .iter()
+        .map(|bet| SettledView {
+            bet: bet.wager.to_string(),
+            stake: bet.stake,
+            payout: bet.payout,
+            outcome: bet.outcome.to_string(),
+        })
+        .collect()
+}
+
+/// What the sessions start from when a request leaves it out.
+#[derive(Debug, Clone)]
+pub struct ApiOptions {
+    pub bind: String,
+    pub mode: GameMode,
+    pub procedure: ProcedureProfile,
+    pub balance: Money,
+    /// The origin browsers may call from (`*` for any); without one, pages
+    /// on other origins can't read the answers.
+    pub allow_origin: Option<String>,
+}
+
+struct Sessions {
+    options: ApiOptions,
+    open: HashMap<String, Session>,
+    /// Sessions opened so far.
+    opened: u64,
+}
+
+impl Sessions {
+    fn open(&mut self, request: NewSession) -> Result<SessionState, String> {
+        let mode = match &request.mode {
+            Some(mode) => mode.parse()?,
+            None => self.options.mode,
+        };
+        let balance = match request.balance {
+            Some(dollars) if dollars > 0 => Money::from_dollars(dollars),
+            Some(_) => return Err("the balance must be positive".to_string()),
+            None => self.options.balance,
+        };
+        let procedure = self.options.procedure.clone();
+        let decks = procedure.decks;
+        let game = match request.seed {
+            Some(seed) => BaccaratGame::seeded(mode, procedure, seed),
+            None => BaccaratGame::with_procedure(mode, procedure),
+        };
+        if self.open.len() >= MAX_SESSIONS
+            && let Some(oldest) = self
+                .open
+                .iter()
+                .min_by_key(|(_, session)| session.opened)
+                .map(|(id, _)| id.clone())
+        {
+            self.open.remove(&oldest);
+        }
+        let id = loop {
+            let id = format!("{:032x}", rand::random::<u128>());
+            if !self.open.contains_key(&id) {
+                break id;
+            }
+        };
+        let player = GameSession::new(balance);
+        let session = Session {
+            opened: self.opened,
+            game,
+            player,
+            decks,
+            shoe: 1,
+            history: Vec::new(),
+        };
+        self.opened += 1;
+        let state = session.state(&id);
+        self.open.insert(id, session);
+        Ok(state)
+    }
+}
+
+/// A response: the status and its JSON body, if any.
+type Reply = (u16, Option<String>);
+
+fn json<T: Serialize>(status: u16, body: &T) -> Reply {
+    match serde_json::to_string(body) {
+        Ok(body) => (status, Some(body)),
+        Err(e) => error(500, e.to_string()),
+    }
+}
+
+fn error(status: u16, message: impl Into<String>) -> Reply {
+    json(
+        status,
+        &ApiError {
+            error: message.into(),
+        },
+    )
+}
+
+fn parse_body<T: for<'de> Deserialize<'de> + Default>(body: &[u8]) -> Result<T, String> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(body).map_err(|e| format!("unreadable body: {}", e))
+}
+
+fn route(sessions: &Mutex<Sessions>, method: &str, path: &str, body: &[u8]) -> Reply {
+    let mut sessions = lock(sessions);
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    match (method, segments.as_slice()) {
+        ("GET", []) => json(200, &endpoints()),
+        ("POST", ["sessions"]) => {
+            match parse_body::<NewSession>(body).and_then(|request| sessions.open(request)) {
+                Ok(state) => json(201, &state),
+                Err(e) => error(400, e),
+            }
+        }
+        ("DELETE", ["sessions", id]) => match sessions.open.remove(*id) {
+            Some(_) => (204, None),
+            None => error(404, format!("no session {}", id)),
+        },
+        (method, ["sessions", id, rest @ ..]) => {
+            let Some(session) = sessions.open.get_mut(*id) else {
+                return error(404, format!("no session {}", id));
+            };
+            match (method, rest) {
+                ("GET", []) => json(200, &session.state(id)),
+                ("PUT", ["bet"]) => {
+                    let placed = serde_json::from_slice::<PlaceBet>(body)
+                        .map_err(|e| format!("unreadable body: {}", e))
+                        .and_then(|request| session.place_bet(&request));
+                    match placed {
+                        Ok(()) => json(200, &session.state(id)),
+                        Err(e) => error(400, e),
+                    }
+                }
+                ("POST", ["deal"]) => match session.deal() {
+                    Ok(hand) => json(200, &hand),
+                    Err(e) => error(500, e),
+                },
+                ("GET", ["history"]) => json(
+                    200,
+                    &History {
+                        hands: session.history.clone(),
+                    },
+                ),
+                (_, [] | ["bet"] | ["deal"] | ["history"]) => {
+                    error(405, format!("{} not allowed here", method))
+                }
+                _ => error(404, format!("no route {}", path)),
+            }
+        }
+        _ => error(404, format!("no route {} {}", method, path)),
+    }
+}
+
+fn lock(sessions: &Mutex<Sessions>) -> MutexGuard<'_, Sessions> {
+    sessions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// A request's method, path and body length, as its head gives them.
+struct Head {
+    method: String,
+    path: String,
+    length: usize,
+}
+
+/// Reads a line of up to `MAX_LINE` bytes; `None` if it runs on past that.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE as u64 + 1).read_line(&mut line)?;
+    Ok((line.len() <= MAX_LINE).then_some(line))
+}
+
+/// What a request that couldn't be read is answered with.
+fn unreadable(e: io::Error) -> Reply {
+    match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            error(408, "the request took too long to send")
+        }
+        _ => error(400, format!("unreadable request: {}", e)),
+    }
+}
+
+/// Reads the request line and headers, or says why it won't.
+fn read_head(reader: &mut impl BufRead) -> Result<Head, Reply> {
+    let too_large = || {
+        error(
+            431,
+            format!(
+                "lines run to {} bytes and requests to {} headers",
+                MAX_LINE, MAX_HEADERS
+            ),
+        )
+    };
+    let request_line = read_line(reader)
+        .map_err(unreadable)?
+        .ok_or_else(too_large)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+    let path = target.split('?').next().unwrap_or_default();
+
+    let mut length = 0;
+    for count in 0.. {
+        let header = read_line(reader)
+            .map_err(unreadable)?
+            .ok_or_else(too_large)?;
+        if header.trim().is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Err(too_large());
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value
+                .trim()
+                .parse()
+                .map_err(|_| error(400, "unreadable Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            // Chunked bodies aren't read, and guessing at one would take
+            // its chunk sizes for JSON.
+            return Err(error(501, "send bodies with a Content-Length"));
+        }
+    }
+    Ok(Head {
+        method: method.to_string(),
+        path: path.to_string(),
+        length,
+    })
+}
+
+/// Reads one request and works out the answer.
+fn answer(reader: &mut impl BufRead, sessions: &Mutex<Sessions>) -> Reply {
+    let head = match read_head(reader) {
+        Ok(head) => head,
+        Err(reply) => return reply,
+    };
+    if head.length > MAX_BODY {
+        return error(413, format!("bodies run to {} bytes", MAX_BODY));
+    }
+    if head.method == "OPTIONS" {
+        // A browser asking whether it may call from another origin.
+        return (204, None);
+    }
+    let mut body = vec![0; head.length];
+    match reader.read_exact(&mut body) {
+        Ok(()) => route(sessions, &head.method, &head.path, &body),
+        Err(e) => unreadable(e),
+    }
+}
+
+/// Writes `reply`, letting `allow_origin` read it if there is one.
+fn respond(
+    stream: &mut TcpStream,
+    (status, body): Reply,
+    allow_origin: Option<&str>,
+) -> io::Result<()> {
+    let body = body.unwrap_or_default();
+    let mut response = format!("HTTP/1.1 {} {}\r\n", status, reason(status));
+    if let Some(origin) = allow_origin {
+        response.push_str(&format!("Access-Control-Allow-Origin: {}\r\n", origin));
+        response.push_str("Access-Control-Allow-Methods: GET, POST, PUT, DELETE, OPTIONS\r\n");
+        response.push_str("Access-Control-Allow-Headers: Content-Type\r\n");
+    }
+    response.push_str(&format!(
+        "Connection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    ));
+    if !body.is_empty() {
+        response.push_str("Content-Type: application/json\r\n");
+    }
+    response.push_str("\r\n");
+    response.push_str(&body);
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+/// Reads one request, answers it and hangs up.
+fn handle(
+    mut stream: TcpStream,
+    sessions: &Mutex<Sessions>,
+    allow_origin: Option<&str>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let reply = answer(&mut BufReader::new(stream.try_clone()?), sessions);
+    respond(&mut stream, reply, allow_origin)
+}
+
+/// One of the `MAX_CONNECTIONS` requests being answered, given back when
+/// dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(active: &Arc<AtomicUsize>) -> Option<Self> {
+        let taken = active.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+            (n < MAX_CONNECTIONS).then_some(n + 1)
+        });
+        taken.ok().map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Binds the API's address; the listener is served with `run`.
+pub fn bind(options: &ApiOptions) -> Result<TcpListener, String> {
+    TcpListener::bind(&options.bind).map_err(|e| format!("{}: {}", options.bind, e))
+}
+
+/// Answers requests on `listener` until the process is stopped.
+pub fn run(listener: TcpListener, options: ApiOptions) {
+    let allow_origin = options.allow_origin.clone();
+    let sessions = Arc::new(Mutex::new(Sessions {
+        options,
+        open: HashMap::new(),
+        opened: 0,
+    }));
+    let active = Arc::new(AtomicUsize::new(0));
+    for mut stream in listener.incoming().flatten() {
+        let Some(slot) = Slot::take(&active) else {
+            // Told without waiting on the client, so the listener keeps up.
+            let _ = stream.set_nonblocking(true);
+            let _ = respond(
+                &mut stream,
+                error(503, "too many requests at once; try again"),
+                allow_origin.as_deref(),
+            );
+            continue;
+        };
+        let sessions = Arc::clone(&sessions);
+        let allow_origin = allow_origin.clone();
+        thread::spawn(move || {
+            let _ = handle(stream, &sessions, allow_origin.as_deref());
+            drop(slot);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sessions() -> Mutex<Sessions> {
+        Mutex::new(Sessions {
+            options: ApiOptions {
+                bind: DEFAULT_ADDRESS.to_string(),
+                mode: GameMode::Classic,
+                procedure: ProcedureProfile::standard(8),
+                balance: Money::from_dollars(1000),
+                allow_origin: None,
+            },
+            open: HashMap::new(),
+            opened: 0,
+        })
+    }
+
+    /// Answers `method path` with `body`, as a client would send it.
+    fn send(sessions: &Mutex<Sessions>, method: &str, path: &str, body: &str) -> Reply {
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        );
+        answer(&mut request.as_bytes(), sessions)
+    }
+
+    fn body<T: for<'de> Deserialize<'de>>((status, body): Reply, expected: u16) -> T {
+        assert_eq!(status, expected, "{:?}", body);
+        serde_json::from_str(&body.unwrap()).unwrap()
+    }
+
+    fn open(sessions: &Mutex<Sessions>) -> SessionState {
+        body(send(sessions, "POST", "/sessions", r#"{"seed":7}"#), 201)
+    }
+
+    #[test]
+    fn the_index_lists_every_route() {
+        let endpoints: Vec<Endpoint> = body(send(&sessions(), "GET", "/", ""), 200);
+        assert_eq!(endpoints, super::endpoints());
+    }
+
+    #[test]
+    fn a_session_bets_deals_and_closes() {
+        let sessions = sessions();
+        let state = open(&sessions);
+        assert_eq!(state.balance, Money::from_dollars(1000));
+        let path = format!("/sessions/{}", state.id);
+
+        let bet = r#"{"bet":"banker","amount":25,"side_bets":{"player_pair":5}}"#;
+        let state: SessionState = body(send(&sessions, "PUT", &format!("{}/bet", path), bet), 200);
+        assert_eq!(state.bets[0].amount, Money::from_dollars(25));
+        assert_eq!(state.side_bets[0].bet, "player_pair");
+
+        let hand: HandView = body(send(&sessions, "POST", &format!("{}/deal", path), ""), 200);
+        assert_eq!(hand.sat_out, None);
+        assert_eq!(hand.balance, Money::from_dollars(1000) + hand.net);
+        let history: History = body(
+            send(&sessions, "GET", &format!("{}/history", path), ""),
+            200,
+        );
+        assert_eq!(history.hands, [hand]);
+
+        assert_eq!(send(&sessions, "DELETE", &path, ""), (204, None));
+        assert_eq!(send(&sessions, "GET", &path, "").0, 404);
+    }
+
+    #[test]
+    fn seeded_sessions_deal_the_same_hands() {
+        let sessions = sessions();
+        let deal = |id: &str| -> HandView {
+            body(
+                send(&sessions, "POST", &format!("/sessions/{}/deal", id), ""),
+                200,
+            )
+        };
+        let (first, second) = (open(&sessions), open(&sessions));
+        assert_ne!(first.id, second.id);
+        for _ in 0..5 {
+            assert_eq!(deal(&first.id).player_cards, deal(&second.id).player_cards);
+        }
+    }
+
+    #[test]
+    fn bad_requests_say_what_is_wrong() {
+        let sessions = sessions();
+        let path = format!("/sessions/{}", open(&sessions).id);
+        let bet = format!("{}/bet", path);
+
+        assert_eq!(send(&sessions, "GET", "/tables", "").0, 404);
+        assert_eq!(send(&sessions, "GET", "/sessions/unknown", "").0, 404);
+        assert_eq!(send(&sessions, "POST", &path, "").0, 405);
+        assert_eq!(send(&sessions, "PUT", &bet, "{").0, 400);
+        assert_eq!(
+            send(&sessions, "PUT", &bet, r#"{"bet":"dragon","amount":25}"#).0,
+            400
+        );
+        assert_eq!(
+            send(&sessions, "PUT", &bet, r#"{"bet":"banker","amount":-5}"#).0,
+            400
+        );
+        let error: ApiError = body(
+            send(
+                &sessions,
+                "PUT",
+                &bet,
+                r#"{"bet":"banker","amount":5000000}"#,
+            ),
+            400,
+        );
+        assert!(!error.error.is_empty());
+        assert_eq!(
+            send(&sessions, "POST", "/sessions", r#"{"balance":0}"#).0,
+            400
+        );
+    }
+
+    #[test]
+    fn requests_that_cannot_be_read_are_refused() {
+        let sessions = sessions();
+        let answer_to = |request: &str| answer(&mut request.as_bytes(), &sessions).0;
+
+        let chunked =
+            "POST /sessions HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n2\r\n{}\r\n0\r\n\r\n";
+        assert_eq!(answer_to(chunked), 501);
+        let too_long = format!(
+            "POST /sessions HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        assert_eq!(answer_to(&too_long), 413);
+        assert_eq!(
+            answer_to("POST /sessions HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}"),
+            400
+        );
+        assert_eq!(
+            answer_to("POST /sessions HTTP/1.1\r\nContent-Length: ten\r\n\r\n"),
+            400
+        );
+        let long_header = format!("GET / HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(MAX_LINE));
+        assert_eq!(answer_to(&long_header), 431);
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-Pad: a\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert_eq!(answer_to(&many_headers), 431);
+        assert_eq!(answer_to("OPTIONS /sessions HTTP/1.1\r\n\r\n"), 204);
+    }
+}
@@ -1,29 +1,889 @@
+//! The baccarat engine: cards, shoes and dealing procedures, the drawing
+//! rules, and settlement of main and side bets for every game mode.
+
+use crate::dragon_tiger;
+use crate::settlement::{BetOutcome, SettledBet, SettlementReport, Wager};
+use crate::three_card;
 use bytemuck::{Pod, Zeroable};
+use rand::rngs::{OsRng, StdRng};
+use rand::{RngCore, SeedableRng, TryRngCore};
 use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
 
+/// Card suit, one of `HEARTS`, `DIAMONDS`, `CLUBS`, or `SPADES`.
 pub type Suit = u8;
 pub const HEARTS: u8 = 0;
 pub const DIAMONDS: u8 = 1;
 pub const CLUBS: u8 = 2;
 pub const SPADES: u8 = 3;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Chip denominations offered on the number keys.
+pub const CHIP_VALUES: [i32; 5] = [10, 50, 100, 500, 1000];
+/// Main bet limits at a standard table.
+pub const TABLE_MIN_BET: i32 = 10;
+pub const TABLE_MAX_BET: i32 = 5000;
+/// Side bet limits, and the most riding on one hand, at a standard table.
+pub const TABLE_MIN_SIDE_BET: i32 = 1;
+pub const TABLE_MAX_SIDE_BET: i32 = 100;
+pub const TABLE_MAX_TOTAL_BET: i32 = 6000;
+/// Decks in the shoe dealt at the interactive tables.
+pub const SHOE_DECKS: usize = 8;
+/// Share of a standard shoe dealt before the cut card comes out.
+pub const DEFAULT_PENETRATION_PCT: u8 = 90;
+/// Cards dealt in an average baccarat hand, for counting hands left in a shoe.
+const AVERAGE_CARDS_PER_HAND: f64 = 4.94;
+/// Bankroll a player sits down with at the interactive tables.
+pub const STARTING_BALANCE: Money = Money::from_dollars(1000);
+
+/// An amount of money in whole cents, so banker commission and fractional
+/// returns are exact. Arithmetic panics on overflow instead of wrapping;
+/// amounts from outside the engine are held to `MAX_INPUT` so it never
+/// does. Saved files hold it in dollars: a whole number, or a decimal when
+/// there are cents.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Pod, Zeroable)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+    /// The most, either way, an amount read from a file, a request or a
+    /// script may be: a trillion dollars, which leaves every payout
+    /// multiple and running total far inside `i64` cents.
+    pub const MAX_INPUT: Money = Money(100_000_000_000_000);
+
+    pub const fn from_dollars(dollars: i32) -> Self {
+        Money(dollars as i64 * 100)
+    }
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// `cents` as an amount, if it is within `MAX_INPUT` either way.
+    pub fn from_input_cents(cents: i64) -> Option<Money> {
+        (cents.unsigned_abs() <= Self::MAX_INPUT.0 as u64).then_some(Money(cents))
+    }
+
+    /// Whole dollars, dropping any cents.
+    pub fn whole_dollars(self) -> i64 {
+        self.0 / 100
+    }
+
+    /// The amount in dollars, for ratios and charts.
+    pub fn as_dollars(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Option<Money> {
+        self.0.checked_mul(factor).map(Money)
+    }
+
+    /// `pct` percent of the amount, rounded down to the cent: a banker win
+    /// paying 95% commission-adjusted is `stake.percent(195)`.
+    pub fn percent(self, pct: i64) -> Money {
+        Money(self.checked_mul(pct).expect("money overflow").0 / 100)
+    }
+}
+
+impl std::ops::Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        self.checked_add(other).expect("money overflow")
+    }
+}
+
+impl std::ops::Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        self.checked_sub(other).expect("money overflow")
+    }
+}
+
+impl std::ops::Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, factor: i64) -> Money {
+        self.checked_mul(factor).expect("money overflow")
+    }
+}
+
+impl std::ops::Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(self.0.checked_neg().expect("money overflow"))
+    }
+}
+
+impl std::ops::AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl std::iter::Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, |total, amount| total + amount)
+    }
+}
+
+/// Dollars, with cents only when there are any: `1000`, `19.50`, `-2.50`.
+/// Honors `+`, width, and alignment flags.
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sign = if self.0 < 0 {
+            "-"
+        } else if f.sign_plus() {
+            "+"
+        } else {
+            ""
+        };
+        let cents = self.0.unsigned_abs();
+        let text = if cents.is_multiple_of(100) {
+            format!("{}{}", sign, cents / 100)
+        } else {
+            format!("{}{}.{:02}", sign, cents / 100, cents % 100)
+        };
+        f.pad(&text)
+    }
+}
+
+impl serde::Serialize for Money {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % 100 == 0 {
+            serializer.serialize_i64(self.0 / 100)
+        } else {
+            serializer.serialize_f64(self.as_dollars())
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Money {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DollarsVisitor;
+
+        impl serde::de::Visitor<'_> for DollarsVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an amount in dollars")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, dollars: i64) -> Result<Money, E> {
+                dollars
+                    .checked_mul(100)
+                    .and_then(Money::from_input_cents)
+                    .ok_or_else(|| E::custom("amount out of range"))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, dollars: u64) -> Result<Money, E> {
+                i64::try_from(dollars)
+                    .map_err(|_| E::custom("amount out of range"))
+                    .and_then(|dollars| self.visit_i64(dollars))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, dollars: f64) -> Result<Money, E> {
+                let cents = (dollars * 100.0).round();
+                if cents.is_finite() && cents.abs() <= Money::MAX_INPUT.0 as f64 {
+                    Ok(Money(cents as i64))
+                } else {
+                    Err(E::custom("amount out of range"))
+                }
+            }
+        }
+
+        deserializer.deserialize_any(DollarsVisitor)
+    }
+}
+
+/// Smallest and largest bets a table accepts, in whole dollars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TableLimits {
+    /// Per main bet.
+    pub min_bet: i32,
+    pub max_bet: i32,
+    /// Per side bet.
+    pub min_side_bet: i32,
+    pub max_side_bet: i32,
+    /// Main and side bets together on one hand.
+    pub max_total: i32,
+}
+
+impl TableLimits {
+    pub const STANDARD: TableLimits = TableLimits {
+        min_bet: TABLE_MIN_BET,
+        max_bet: TABLE_MAX_BET,
+        min_side_bet: TABLE_MIN_SIDE_BET,
+        max_side_bet: TABLE_MAX_SIDE_BET,
+        max_total: TABLE_MAX_TOTAL_BET,
+    };
+
+    /// Brings `bet` inside the limits.
+    pub fn clamp(&self, bet: i32) -> i32 {
+        bet.clamp(self.min_bet, self.max_bet)
+    }
+
+    /// Brings a side bet `stake` inside the limits.
+    pub fn clamp_side(&self, stake: i32) -> Money {
+        Money::from_dollars(stake.clamp(self.min_side_bet, self.max_side_bet))
+    }
+
+    /// Steps a main bet up or down by one chip, staying within the table
+    /// limits rather than wrapping around.
+    pub fn step(&self, current: i32, chip: i32, increase: bool) -> i32 {
+        self.clamp(if increase {
+            current + chip
+        } else {
+            current - chip
+        })
+    }
+
+    /// Every way `slip` breaks these limits; empty when the table takes it.
+    fn check_side_bet(&self, bet: Wager, amount: Money, violations: &mut Vec<LimitViolation>) {
+        let dollars = Money::from_dollars;
+        if amount < dollars(self.min_side_bet) {
+            violations.push(LimitViolation::SideBelowMin {
+                bet,
+                amount,
+                min: dollars(self.min_side_bet),
+            });
+        } else if amount > dollars(self.max_side_bet) {
+            violations.push(LimitViolation::SideAboveMax {
+                bet,
+                amount,
+                max: dollars(self.max_side_bet),
+            });
+        }
+    }
+
+    pub fn violations(&self, slip: &BetSlip) -> Vec<LimitViolation> {
+        let dollars = Money::from_dollars;
+        let mut violations = Vec::new();
+        if slip.total_main_bet() == Money::ZERO {
+            violations.push(LimitViolation::NoMainBet);
+        }
+        for (bet, amount) in slip.main_bets() {
+            if amount < dollars(self.min_bet) {
+                violations.push(LimitViolation::MainBelowMin {
+                    bet,
+                    amount,
+                    min: dollars(self.min_bet),
+                });
+            } else if amount > dollars(self.max_bet) {
+                violations.push(LimitViolation::MainAboveMax {
+                    bet,
+                    amount,
+                    max: dollars(self.max_bet),
+                });
+            }
+        }
+        for bet in BonusBetType::ALL {
+            let amount = slip.bonus_bets.get(bet);
+            if amount == Money::ZERO {
+                continue;
+            }
+            self.check_side_bet(Wager::Side(bet), amount, &mut violations);
+        }
+        for (score, stake) in slip.bonus_bets.tie_score_bets() {
+            self.check_side_bet(Wager::TieScore(score), stake, &mut violations);
+        }
+        if slip.total_bet() > dollars(self.max_total) {
+            violations.push(LimitViolation::TotalAboveMax {
+                total: slip.total_bet(),
+                max: dollars(self.max_total),
+            });
+        }
+        violations
+    }
+}
+
+/// One way a bet slip breaks the table limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitViolation {
+    NoMainBet,
+    MainBelowMin {
+        bet: BetType,
+        amount: Money,
+        min: Money,
+    },
+    MainAboveMax {
+        bet: BetType,
+        amount: Money,
+        max: Money,
+    },
+    SideBelowMin {
+        bet: Wager,
+        amount: Money,
+        min: Money,
+    },
+    SideAboveMax {
+        bet: Wager,
+        amount: Money,
+        max: Money,
+    },
+    TotalAboveMax {
+        total: Money,
+        max: Money,
+    },
+}
+
+impl std::fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LimitViolation::NoMainBet => write!(f, "no main bet placed"),
+            LimitViolation::MainBelowMin { bet, amount, min } => {
+                write!(f, "{} bet ${} is under the ${} minimum", bet, amount, min)
+            }
+            LimitViolation::MainAboveMax { bet, amount, max } => {
+                write!(f, "{} bet ${} is over the ${} maximum", bet, amount, max)
+            }
+            LimitViolation::SideBelowMin { bet, amount, min } => {
+                write!(
+                    f,
+                    "{} bet ${} is under the ${} side bet minimum",
+                    bet, amount, min
+                )
+            }
+            LimitViolation::SideAboveMax { bet, amount, max } => {
+                write!(
+                    f,
+                    "{} bet ${} is over the ${} side bet maximum",
+                    bet, amount, max
+                )
+            }
+            LimitViolation::TotalAboveMax { total, max } => {
+                write!(f, "${} in bets is over the ${} table maximum", total, max)
+            }
+        }
+    }
+}
+
+/// Table variant, which decides main bet payouts and the pace of play.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameMode {
     Classic,
     NoCommission,
     Speed,
     EzBaccarat,
+    /// Card-room game dealt from 36-card decks: only a two-card 9 is a
+    /// natural, and each hand chooses whether to draw on 4 to 6.
+    SuperPan9,
+    /// The bank is held by a player rather than the house: the player may
+    /// draw or stand on 5, and the banker has a choice where the tableau
+    /// leaves one.
+    CheminDeFer,
+    /// A different game on the same shoe: one card each to the Dragon (the
+    /// player's seat) and the Tiger (the banker's), and the higher card
+    /// wins.
+    DragonTiger,
+    /// Three Card Baccarat, as in Hong Kong: three cards each and no
+    /// drawing; three court cards are the best hand, and a tie takes the
+    /// player and banker bets.
+    ThreeCard,
+}
+
+impl GameMode {
+    /// Every mode, in the order the UIs cycle through them.
+    pub const ALL: [GameMode; 8] = [
+        GameMode::Classic,
+        GameMode::NoCommission,
+        GameMode::Speed,
+        GameMode::EzBaccarat,
+        GameMode::SuperPan9,
+        GameMode::CheminDeFer,
+        GameMode::DragonTiger,
+        GameMode::ThreeCard,
+    ];
+
+    /// Main-line bets offered at this mode's table.
+    pub fn main_bets(&self) -> &'static [BetType] {
+        match self {
+            GameMode::EzBaccarat => &BetType::ALL,
+            _ => &[BetType::Player, BetType::Banker, BetType::Tie],
+        }
+    }
+
+    /// Whether this mode's table deals `side_bet`; the Monkey bets are EZ
+    /// Baccarat's, Dragon Tiger deals only its suited tie, and Three Card
+    /// Baccarat has none.
+    pub fn offers_side_bet(&self, side_bet: BonusBetType) -> bool {
+        match side_bet {
+            BonusBetType::SuitedTie => *self == GameMode::DragonTiger,
+            _ if self.fixed_hand_size().is_some() => false,
+            BonusBetType::MonkeySix | BonusBetType::MonkeyTie => *self == GameMode::EzBaccarat,
+            _ => true,
+        }
+    }
+
+    /// Whether this mode's table takes bets on a tie at a particular score.
+    pub fn offers_tie_scores(&self) -> bool {
+        self.fixed_hand_size().is_none()
+    }
+
+    /// Drops any side bets this mode does not deal.
+    pub fn restrict(&self, mut bets: BonusBets) -> BonusBets {
+        for side_bet in BonusBetType::ALL {
+            if !self.offers_side_bet(side_bet) {
+                bets.set(side_bet, Money::ZERO);
+            }
+        }
+        if !self.offers_tie_scores() {
+            bets.tie_score_bets = [Money::ZERO; 10];
+        }
+        bets
+    }
+
+    /// Cards each side is dealt where nobody draws: one at Dragon Tiger,
+    /// three at Three Card Baccarat; `None` where the drawing rules decide.
+    pub fn fixed_hand_size(&self) -> Option<usize> {
+        match self {
+            GameMode::DragonTiger => Some(1),
+            GameMode::ThreeCard => Some(3),
+            _ => None,
+        }
+    }
+
+    /// What the table calls `side`'s hand: Dragon and Tiger at Dragon
+    /// Tiger.
+    pub fn side_name(&self, side: Side) -> &'static str {
+        match (self, side) {
+            (GameMode::DragonTiger, Side::Player) => "Dragon",
+            (GameMode::DragonTiger, Side::Banker) => "Tiger",
+            (_, Side::Player) => "Player",
+            (_, Side::Banker) => "Banker",
+        }
+    }
+
+    /// A main bet as the table labels it, e.g. "Tiger" for a banker bet at
+    /// Dragon Tiger.
+    pub fn bet_label(&self, bet: BetType) -> &'static str {
+        match bet {
+            BetType::Player => self.side_name(Side::Player),
+            BetType::Banker => self.side_name(Side::Banker),
+            BetType::Tie => "Tie",
+            BetType::Dragon7 => "Dragon7",
+            BetType::Panda8 => "Panda8",
+        }
+    }
+
+    /// Highest rank in each deck: Super Pan 9 takes the tens and court cards
+    /// out.
+    pub fn top_rank(&self) -> u8 {
+        match self {
+            GameMode::SuperPan9 => 9,
+            _ => 13,
+        }
+    }
+
+    /// Lowest two-card total that is a natural and ends the hand.
+    pub fn natural_minimum(&self) -> u8 {
+        match self {
+            GameMode::SuperPan9 => 9,
+            _ => 8,
+        }
+    }
+
+    /// Whether a hand can stop at `GamePhase::DecisionRequired`.
+    pub fn has_draw_decisions(&self) -> bool {
+        matches!(self, GameMode::SuperPan9 | GameMode::CheminDeFer)
+    }
+
+    /// The player's third card on `score`.
+    pub fn player_draw(&self, score: u8) -> DrawRule {
+        match (self, score) {
+            (GameMode::SuperPan9, 0..=3) => DrawRule::Draw,
+            (GameMode::SuperPan9, 4..=6) => DrawRule::Optional,
+            (GameMode::SuperPan9, _) => DrawRule::Stand,
+            (GameMode::CheminDeFer, 5) => DrawRule::Optional,
+            _ => DrawRule::tableau(BaccaratGame::player_should_draw(score)),
+        }
+    }
+
+    /// The banker's third card on `score`; `player_third_value` is `None`
+    /// when the player stood.
+    pub fn banker_draw(&self, score: u8, player_third_value: Option<u8>) -> DrawRule {
+        match (self, score, player_third_value) {
+            (GameMode::SuperPan9, 0..=3, _) => DrawRule::Draw,
+            (GameMode::SuperPan9, 4..=6, _) => DrawRule::Optional,
+            (GameMode::SuperPan9, _, _) => DrawRule::Stand,
+            (GameMode::CheminDeFer, 3, Some(9))
+            | (GameMode::CheminDeFer, 4, Some(1))
+            | (GameMode::CheminDeFer, 5, Some(4)) => DrawRule::Optional,
+            _ => DrawRule::tableau(BaccaratGame::banker_should_draw(score, player_third_value)),
+        }
+    }
+
+    /// Speed tables deal on a fixed cadence whether or not anyone is ready.
+    pub fn auto_deal_interval(&self) -> Option<Duration> {
+        match self {
+            GameMode::Speed => Some(Duration::from_secs(12)),
+            _ => None,
+        }
+    }
+
+    /// The standard pay table at this mode's table.
+    pub fn payout_table(&self) -> PayoutTable {
+        let standard = PayoutTable::default();
+        match self {
+            GameMode::Classic | GameMode::SuperPan9 | GameMode::CheminDeFer => standard,
+            GameMode::NoCommission => PayoutTable {
+                banker_pct: 200,
+                banker_six_pct: 150,
+                big_pct: 150,
+                ..standard
+            },
+            GameMode::Speed => PayoutTable {
+                banker_pct: 200,
+                banker_six_pct: 200,
+                tie: 8,
+                big_pct: 150,
+                ..standard
+            },
+            GameMode::EzBaccarat => PayoutTable {
+                banker_pct: 200,
+                banker_six_pct: 200,
+                dragon_7: 41,
+                panda_8: 26,
+                big_pct: 150,
+                monkey_6: 12,
+                monkey_tie: 150,
+                ..standard
+            },
+            GameMode::DragonTiger => PayoutTable {
+                banker_pct: 200,
+                banker_six_pct: 200,
+                suited_tie: 50,
+                ..standard
+            },
+            GameMode::ThreeCard => PayoutTable {
+                banker_pct: 200,
+                banker_six_pct: 200,
+                // 25 to 1, the stake included like every main bet.
+                tie: 26,
+                ..standard
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "classic" => Ok(GameMode::Classic),
+            "nocommission" | "nc" => Ok(GameMode::NoCommission),
+            "speed" => Ok(GameMode::Speed),
+            "ez" | "ezbaccarat" => Ok(GameMode::EzBaccarat),
+            "superpan9" | "pan9" => Ok(GameMode::SuperPan9),
+            "chemindefer" | "chemmy" => Ok(GameMode::CheminDeFer),
+            "dragontiger" | "dt" => Ok(GameMode::DragonTiger),
+            "threecard" | "3card" | "hongkong" => Ok(GameMode::ThreeCard),
+            _ => Err(format!("unknown game mode '{}'", s)),
+        }
+    }
+}
+
+/// What the drawing rules say about a hand's third card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawRule {
+    Draw,
+    Stand,
+    /// Whoever holds the hand chooses.
+    Optional,
+}
+
+impl DrawRule {
+    fn tableau(draws: bool) -> Self {
+        if draws {
+            DrawRule::Draw
+        } else {
+            DrawRule::Stand
+        }
+    }
+}
+
+/// What each bet pays on a win. Main bets give the whole return as a
+/// multiple of the stake (`player: 2` is even money); side bets give their
+/// odds to 1, and the stake comes back on top. Every mode has its own
+/// standard table (`GameMode::payout_table`); rules bundles and events
+/// override single entries by name.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PayoutTable {
+    pub player: i32,
+    /// Banker's return per 100 staked; 195 takes a 5% commission.
+    pub banker_pct: i32,
+    /// Banker's return per 100 staked when it wins with a 6.
+    pub banker_six_pct: i32,
+    pub tie: i32,
+    /// EZ Baccarat bets, 40 and 25 to 1 at the standard table; 0 where the
+    /// mode does not offer them.
+    pub dragon_7: i32,
+    pub panda_8: i32,
+    pub player_pair: i32,
+    pub banker_pair: i32,
+    pub either_pair: i32,
+    pub perfect_pair: i32,
+    pub lucky_6: i32,
+    pub lucky_6_three_card: i32,
+    /// Dragon bonus by victory margin, from 4 points up to 9.
+    pub dragon: [i32; 6],
+    /// Dragon bonus on a natural win, whatever the margin.
+    pub dragon_natural: i32,
+    pub natural_8: i32,
+    pub natural_9: i32,
+    pub natural_both: i32,
+    /// Super Six: banker wins with 6, however many cards.
+    pub super_6: i32,
+    /// Big (five or six cards dealt) and Small (four cards), as returns per
+    /// 100 staked; commission-free tables pay Big a little less.
+    pub big_pct: i32,
+    pub small_pct: i32,
+    /// Tie on each score from 0 to 9.
+    pub tie_scores: [i32; 10],
+    /// EZ Baccarat Monkey bets: banker wins with a three-card 6, or the hand
+    /// ties at 6 with the banker on three cards; 0 at other tables.
+    pub monkey_6: i32,
+    pub monkey_tie: i32,
+    /// Dragon Tiger: both cards the same rank and suit; 0 at baccarat
+    /// tables.
+    pub suited_tie: i32,
+}
+
+impl Default for PayoutTable {
+    /// The classic table.
+    fn default() -> Self {
+        Self {
+            player: 2,
+            banker_pct: 195,
+            banker_six_pct: 195,
+            tie: 9,
+            dragon_7: 0,
+            panda_8: 0,
+            player_pair: 11,
+            banker_pair: 11,
+            either_pair: 5,
+            perfect_pair: 25,
+            lucky_6: 12,
+            lucky_6_three_card: 20,
+            dragon: [1, 2, 4, 6, 10, 30],
+            dragon_natural: 1,
+            natural_8: 2,
+            natural_9: 3,
+            natural_both: 5,
+            super_6: 12,
+            big_pct: 154,
+            small_pct: 250,
+            tie_scores: [150, 215, 225, 200, 120, 110, 45, 45, 80, 80],
+            monkey_6: 0,
+            monkey_tie: 0,
+            suited_tie: 0,
+        }
+    }
+}
+
+impl PayoutTable {
+    fn entry_mut(&mut self, name: &str) -> Option<&mut i32> {
+        Some(match name {
+            "player" => &mut self.player,
+            "banker_pct" => &mut self.banker_pct,
+            "banker_six_pct" => &mut self.banker_six_pct,
+            "tie" => &mut self.tie,
+            "dragon_7" => &mut self.dragon_7,
+            "panda_8" => &mut self.panda_8,
+            "player_pair" => &mut self.player_pair,
+            "banker_pair" => &mut self.banker_pair,
+            "either_pair" => &mut self.either_pair,
+            "perfect_pair" => &mut self.perfect_pair,
+            "lucky_6" => &mut self.lucky_6,
+            "lucky_6_three_card" => &mut self.lucky_6_three_card,
+            "dragon_natural" => &mut self.dragon_natural,
+            "natural_8" => &mut self.natural_8,
+            "natural_9" => &mut self.natural_9,
+            "natural_both" => &mut self.natural_both,
+            "super_6" => &mut self.super_6,
+            "big_pct" => &mut self.big_pct,
+            "small_pct" => &mut self.small_pct,
+            "monkey_6" => &mut self.monkey_6,
+            "monkey_tie" => &mut self.monkey_tie,
+            "suited_tie" => &mut self.suited_tie,
+            _ if name.starts_with("tie_") => {
+                let score: usize = name.strip_prefix("tie_")?.parse().ok()?;
+                self.tie_scores.get_mut(score)?
+            }
+            _ => {
+                let margin: usize = name.strip_prefix("dragon_")?.parse().ok()?;
+                self.dragon.get_mut(margin.checked_sub(4)?)?
+            }
+        })
+    }
+
+    /// Replaces one entry by name (`tie`, `banker_pct`, `dragon_9`, `tie_7`,
+    /// ...).
+    pub fn set(&mut self, name: &str, ratio: i32) -> Result<(), String> {
+        if ratio < 0 {
+            return Err(format!("negative payout {} for {}", ratio, name));
+        }
+        let entry = self
+            .entry_mut(name)
+            .ok_or_else(|| format!("unknown pay table entry '{}'", name))?;
+        *entry = ratio;
+        Ok(())
+    }
+
+    /// This table with every override applied.
+    pub fn with_overrides(mut self, overrides: &HashMap<String, i32>) -> Result<Self, String> {
+        for (name, ratio) in overrides {
+            self.set(name, *ratio)?;
+        }
+        Ok(self)
+    }
+
+    /// Dragon bonus for winning by `margin` points; nothing under 4.
+    pub fn dragon_ratio(&self, margin: u8) -> i32 {
+        match margin {
+            4..=9 => self.dragon[margin as usize - 4],
+            _ => 0,
+        }
+    }
+
+    /// Specific-tie bet on a tie at `score`.
+    pub fn tie_score_ratio(&self, score: u8) -> i32 {
+        self.tie_scores.get(score as usize).copied().unwrap_or(0)
+    }
+}
+
+/// What happens to player and banker bets when the hand ties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieRule {
+    /// The stake comes back, as at most tables.
+    PushMainBets,
+    /// The stake is lost with the hand.
+    LoseMainBets,
+    /// The stake stays on the layout and must ride, unchanged, on the next
+    /// hand.
+    BargeToNextHand,
 }
 
+impl TieRule {
+    /// The name `FromStr` takes.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TieRule::PushMainBets => "push",
+            TieRule::LoseMainBets => "lose",
+            TieRule::BargeToNextHand => "barge",
+        }
+    }
+}
+
+impl std::str::FromStr for TieRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "push" => Ok(TieRule::PushMainBets),
+            "lose" => Ok(TieRule::LoseMainBets),
+            "barge" => Ok(TieRule::BargeToNextHand),
+            _ => Err(format!(
+                "unknown tie rule '{}', expected push, lose, or barge",
+                s
+            )),
+        }
+    }
+}
+
+/// A main-line bet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BetType {
+    Player,
+    Banker,
+    Tie,
+    /// EZ Baccarat: banker wins with a three-card 7.
+    Dragon7,
+    /// EZ Baccarat: player wins with a three-card 8.
+    Panda8,
+}
+
+impl BetType {
+    pub const ALL: [BetType; 5] = [
+        BetType::Player,
+        BetType::Banker,
+        BetType::Tie,
+        BetType::Dragon7,
+        BetType::Panda8,
+    ];
+
+    /// Lowercase name used in saved files and on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BetType::Player => "player",
+            BetType::Banker => "banker",
+            BetType::Tie => "tie",
+            BetType::Dragon7 => "dragon7",
+            BetType::Panda8 => "panda8",
+        }
+    }
+}
+
+impl std::fmt::Display for BetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for BetType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|bet| bet.name() == name)
+            .ok_or_else(|| format!("unknown bet '{}'", s))
+    }
+}
+
+/// A playing card; `rank` runs from 1 (ace) to 13 (king).
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable, serde::Serialize, serde::Deserialize,
+)]
 pub struct Card {
     pub suit: Suit,
     pub rank: u8,
 }
 
+/// Scores and result of the current hand.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable, serde::Serialize, serde::Deserialize,
+)]
 pub struct GameState {
     pub player_score: u8,
     pub banker_score: u8,
@@ -36,109 +896,740 @@ impl Card {
         Self { suit, rank }
     }
 
-    pub fn baccarat_value(&self) -> u8 {
-        match self.rank {
-            1..=9 => self.rank,
-            _ => 0, // Face cards and tens count as zero in Baccarat
-        }
-    }
+    /// Whether this is a real card: one of the four suits, ace to king.
+    pub fn is_valid(&self) -> bool {
+        self.suit < 4 && (1..=13).contains(&self.rank)
+    }
+
+    /// Point value: aces count one, tens and face cards zero.
+    pub fn baccarat_value(&self) -> u8 {
+        match self.rank {
+            1..=9 => self.rank,
+            _ => 0, // Face cards and tens count as zero in Baccarat
+        }
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        Self {
+            player_score: 0,
+            banker_score: 0,
+            round_complete: 0,
+            winner: 0,
+        }
+    }
+}
+
+/// The cards dealt to one side, in dealing order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total of `cards` modulo ten.
+    pub fn points(cards: &[Card]) -> u8 {
+        let total: u8 = cards.iter().map(|card| card.baccarat_value()).sum();
+        total % 10
+    }
+
+    pub fn score(&self) -> u8 {
+        Self::points(&self.cards)
+    }
+
+    /// A two-card 8 or 9.
+    pub fn is_natural(&self) -> bool {
+        self.cards.len() == 2 && BaccaratGame::is_natural_total(self.score())
+    }
+
+    /// The first two cards share a rank.
+    pub fn is_pair(&self) -> bool {
+        matches!(&self.cards[..], [first, second, ..] if first.rank == second.rank)
+    }
+
+    /// The first two cards share a rank and a suit.
+    pub fn is_suited_pair(&self) -> bool {
+        self.is_pair() && self.cards[0].suit == self.cards[1].suit
+    }
+
+    /// The drawn card, if the hand took one.
+    pub fn third_card(&self) -> Option<Card> {
+        self.cards.get(2).copied()
+    }
+
+    pub fn push(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
+}
+
+impl From<&[Card]> for Hand {
+    fn from(cards: &[Card]) -> Self {
+        Self {
+            cards: cards.to_vec(),
+        }
+    }
+}
+
+impl std::ops::Deref for Hand {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+impl<'a> IntoIterator for &'a Hand {
+    type Item = &'a Card;
+    type IntoIter = std::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
+/// The generator a deck or shoe shuffles from. Any `rand` generator will
+/// do; a seeded one deals the same cards every time it is run.
+pub type ShuffleRng = Box<dyn RngCore + Send>;
+
+/// A generator seeded from `seed`.
+pub fn seeded_rng(seed: u64) -> ShuffleRng {
+    Box::new(StdRng::seed_from_u64(seed))
+}
+
+/// The operating system's cryptographically secure generator, read afresh
+/// for every draw.
+pub fn os_rng() -> ShuffleRng {
+    Box::new(OsRng.unwrap_err())
+}
+
+/// Which generator a table shuffles its shoes from when they aren't dealt
+/// from a fixed seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RngChoice {
+    /// Each shoe drawn from a committed server seed and the player's client
+    /// seed, to be checked once it is revealed.
+    #[default]
+    ProvablyFair,
+    /// The operating system's secure generator (`getrandom`).
+    Os,
+    /// `rand`'s thread generator.
+    Thread,
 }
 
-impl GameState {
-    pub fn new() -> Self {
-        Self {
-            player_score: 0,
-            banker_score: 0,
-            round_complete: 0,
-            winner: 0,
+impl RngChoice {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RngChoice::ProvablyFair => "fair",
+            RngChoice::Os => "os",
+            RngChoice::Thread => "thread",
         }
     }
+}
 
-    pub fn calculate_hand_score(cards: &[Card]) -> u8 {
-        let total: u8 = cards.iter().map(|card| card.baccarat_value()).sum();
-        total % 10
+impl std::str::FromStr for RngChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fair" | "provablyfair" => Ok(RngChoice::ProvablyFair),
+            "os" | "osrng" => Ok(RngChoice::Os),
+            "thread" => Ok(RngChoice::Thread),
+            _ => Err(format!(
+                "unknown generator '{}', expected fair, os or thread",
+                s
+            )),
+        }
     }
 }
 
+/// A single deck: 52 cards, or fewer when stripped.
 pub struct Deck {
     cards: Vec<Card>,
+    top_rank: u8,
+    /// Shuffles this deck; `None` draws from the thread's generator.
+    rng: Option<ShuffleRng>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Deck {
     pub fn new() -> Self {
-        let mut cards = Vec::with_capacity(52);
+        Self::stripped(13)
+    }
+
+    /// A deck holding only the ranks up to `top_rank`, e.g. 9 for the
+    /// 36-card decks of Super Pan 9.
+    pub fn stripped(top_rank: u8) -> Self {
+        let mut cards = Vec::with_capacity(4 * top_rank as usize);
         for suit in 0..4 {
-            for rank in 1..=13 {
+            for rank in 1..=top_rank {
                 cards.push(Card::new(suit, rank));
             }
         }
-        Self { cards }
+        Self {
+            cards,
+            top_rank,
+            rng: None,
+        }
+    }
+
+    /// Shuffles from `rng` from now on instead of the thread's generator.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    /// A deck holding exactly `cards`, dealt first to last until it is
+    /// shuffled.
+    pub fn from_cards(mut cards: Vec<Card>) -> Self {
+        cards.reverse();
+        Self {
+            cards,
+            top_rank: 13,
+            rng: None,
+        }
+    }
+
+    /// Cards in the full deck.
+    pub fn size(&self) -> usize {
+        4 * self.top_rank as usize
     }
 
     pub fn shuffle(&mut self) {
         use rand::seq::SliceRandom;
-        let mut rng = rand::rng();
-        self.cards.shuffle(&mut rng);
+        match &mut self.rng {
+            Some(rng) => self.cards.shuffle(rng.as_mut()),
+            None => self.cards.shuffle(&mut rand::rng()),
+        }
+    }
+
+    /// Shuffles from `rng` this once, for a table that keeps one generator
+    /// for its decks and everything else it draws.
+    pub fn shuffle_with(&mut self, rng: &mut dyn RngCore) {
+        use rand::seq::SliceRandom;
+        self.cards.shuffle(rng);
+    }
+
+    /// Puts every card back, in order, ready to shuffle.
+    fn restock(&mut self) {
+        self.cards = Self::stripped(self.top_rank).cards;
     }
 
     pub fn deal(&mut self) -> Option<Card> {
         self.cards.pop()
     }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+/// How many cards are burned after each shuffle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnRule {
+    Fixed(usize),
+    /// Turn the first card face up and burn as many more as its value, a
+    /// ten-valued card counting as ten.
+    ByFirstCard,
+}
+
+/// When the dealt cards go back into the shoe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReshufflePolicy {
+    /// Deal down to the cut card, then shuffle the whole shoe.
+    CutCard,
+    /// Continuous shuffling machine: each hand's cards go straight back in.
+    Continuous,
+}
+
+/// A house's dealing procedure, so the shoe can behave like a particular
+/// casino's table.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ProcedureProfile {
+    pub name: String,
+    pub decks: usize,
+    pub burn: BurnRule,
+    /// Cards left behind the cut card.
+    pub cut_card_depth: usize,
+    pub reshuffle: ReshufflePolicy,
+}
+
+impl ProcedureProfile {
+    /// Built-in profile names accepted by `parse`.
+    pub const NAMES: [&'static str; 4] = ["standard", "macau", "midi", "csm"];
+
+    /// No burn, cut card a tenth of the way from the back (at least 15 cards).
+    pub fn standard(decks: usize) -> Self {
+        Self {
+            name: "standard".to_string(),
+            decks,
+            burn: BurnRule::Fixed(0),
+            cut_card_depth: ShoeConfig::new(decks).cut_card_depth().max(15),
+            reshuffle: ReshufflePolicy::CutCard,
+        }
+    }
+
+    /// One of the named profiles in `NAMES`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let profile = match name {
+            "standard" => Self::standard(SHOE_DECKS),
+            // Eight decks, burn by the first card, cut card about a third of a
+            // deck from the back.
+            "macau" => Self {
+                name: name.to_string(),
+                decks: 8,
+                burn: BurnRule::ByFirstCard,
+                cut_card_depth: 16,
+                reshuffle: ReshufflePolicy::CutCard,
+            },
+            // Six-deck midi-baccarat shoe with one burn card.
+            "midi" => Self {
+                name: name.to_string(),
+                decks: 6,
+                burn: BurnRule::Fixed(1),
+                cut_card_depth: 14,
+                reshuffle: ReshufflePolicy::CutCard,
+            },
+            "csm" => Self {
+                name: name.to_string(),
+                decks: 8,
+                burn: BurnRule::Fixed(0),
+                cut_card_depth: 0,
+                reshuffle: ReshufflePolicy::Continuous,
+            },
+            _ => return None,
+        };
+        Some(profile)
+    }
+
+    /// A built-in name, or `key=value` pairs applied on top of the standard
+    /// profile: `decks=6,burn=3,cut=20,shuffle=continuous` (`burn=first`
+    /// burns by the first card, `shuffle=cut` deals to the cut card).
+    /// `penetration=75,offset=-10` places the cut card by share of the shoe
+    /// instead of `cut`, as in `ShoeConfig`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if let Some(profile) = Self::builtin(spec) {
+            return Ok(profile);
+        }
+        let err = |detail: &str| {
+            format!(
+                "invalid procedure '{}': {} (built-ins: {})",
+                spec,
+                detail,
+                Self::NAMES.join(", ")
+            )
+        };
+        let mut profile = Self::standard(SHOE_DECKS);
+        profile.name = "custom".to_string();
+        let mut cut_given = false;
+        let mut penetration = None;
+        let mut offset = 0;
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| err("expected key=value"))?;
+            let number = || {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| err(&format!("bad number for {}", key)))
+            };
+            match key {
+                "decks" => profile.decks = number()?,
+                "burn" if value == "first" => profile.burn = BurnRule::ByFirstCard,
+                "burn" => profile.burn = BurnRule::Fixed(number()?),
+                "cut" => {
+                    profile.cut_card_depth = number()?;
+                    cut_given = true;
+                }
+                "penetration" => match value.trim_end_matches('%').parse::<u8>() {
+                    Ok(pct) if (1..=100).contains(&pct) => penetration = Some(pct),
+                    _ => return Err(err("penetration must be 1-100")),
+                },
+                "offset" => match value.parse::<i32>() {
+                    Ok(cards) if cards.unsigned_abs() as usize <= 52 * 8 => offset = cards,
+                    _ => return Err(err("offset must be a card count within the shoe")),
+                },
+                "shuffle" => {
+                    profile.reshuffle = match value {
+                        "cut" | "cutcard" => ReshufflePolicy::CutCard,
+                        "continuous" | "csm" => ReshufflePolicy::Continuous,
+                        _ => return Err(err("shuffle must be cut or continuous")),
+                    }
+                }
+                _ => return Err(err(&format!("unknown key '{}'", key))),
+            }
+        }
+        if !(1..=8).contains(&profile.decks) {
+            return Err(err("decks must be 1-8"));
+        }
+        if cut_given && (penetration.is_some() || offset != 0) {
+            return Err(err("give either cut or penetration/offset"));
+        }
+        if let Some(pct) = penetration {
+            profile.cut_card_depth = ShoeConfig::new(profile.decks)
+                .penetration_pct(pct)
+                .cut_card_offset(offset)
+                .cut_card_depth();
+        } else if !cut_given {
+            let standard = Self::standard(profile.decks).cut_card_depth;
+            profile.cut_card_depth = standard.saturating_add_signed(-(offset as isize));
+        }
+        // A hand can take six cards; keep at least one full hand in front of
+        // the cut card after the burn.
+        if profile.cut_card_depth.saturating_add(16) > 52 * profile.decks {
+            return Err(err("cut card is too deep for the shoe"));
+        }
+        Ok(profile)
+    }
+}
+
+/// Shoe size and cut card placement, for modelling a particular table's
+/// penetration: `ShoeConfig::new(6).penetration_pct(75).cut_card_offset(-8)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ShoeConfig {
+    pub num_decks: usize,
+    /// Share of the shoe dealt before the cut card comes out.
+    pub penetration_pct: u8,
+    /// Cards the dealer places the cut card past the penetration point;
+    /// negative places it earlier.
+    pub cut_card_offset: i32,
+}
+
+impl ShoeConfig {
+    pub fn new(num_decks: usize) -> Self {
+        Self {
+            num_decks,
+            penetration_pct: DEFAULT_PENETRATION_PCT,
+            cut_card_offset: 0,
+        }
+    }
+
+    pub fn penetration_pct(mut self, penetration_pct: u8) -> Self {
+        self.penetration_pct = penetration_pct.min(100);
+        self
+    }
+
+    pub fn cut_card_offset(mut self, cut_card_offset: i32) -> Self {
+        self.cut_card_offset = cut_card_offset;
+        self
+    }
+
+    /// Cards left behind the cut card, always leaving a full hand in front
+    /// of it.
+    pub fn cut_card_depth(&self) -> usize {
+        let size = 52 * self.num_decks;
+        let depth = size * (100 - self.penetration_pct.min(100) as usize) / 100;
+        depth
+            .saturating_add_signed((self.cut_card_offset as isize).saturating_neg())
+            .min(size.saturating_sub(16))
+    }
+
+    /// The standard procedure with this shoe's decks and cut card.
+    pub fn procedure(&self) -> ProcedureProfile {
+        ProcedureProfile {
+            name: "custom".to_string(),
+            cut_card_depth: self.cut_card_depth(),
+            ..ProcedureProfile::standard(self.num_decks)
+        }
+    }
 }
 
+/// Several decks shuffled together and dealt down to the cut card.
 pub struct Shoe {
     cards: Vec<Card>,
-    num_decks: usize,
-    cut_card_position: usize,
+    procedure: ProcedureProfile,
     cards_dealt: usize,
+    /// Cards burned since the shuffle, in the order they came out; `None`
+    /// until the burn procedure has run.
+    burned: Option<Vec<Card>>,
+    /// Built from a fixed card order, which is never shuffled or burned.
+    stacked: bool,
+    /// Highest rank in each deck.
+    top_rank: u8,
+    /// Shuffles this shoe and every reshuffle after it; `None` draws from
+    /// the thread's generator.
+    rng: Option<ShuffleRng>,
 }
 
 impl Shoe {
+    /// A standard shoe of `num_decks` decks.
     pub fn new(num_decks: usize) -> Self {
-        let mut cards = Vec::with_capacity(52 * num_decks);
-        for _ in 0..num_decks {
+        Self::with_procedure(ProcedureProfile::standard(num_decks))
+    }
+
+    /// A standard shoe with the config's decks and cut card placement.
+    pub fn with_config(config: ShoeConfig) -> Self {
+        Self::with_procedure(config.procedure())
+    }
+
+    /// A shoe holding exactly `cards`, dealt first to last with no burn and
+    /// no cut card. It cannot be reshuffled, so once the cards run out the
+    /// shoe is empty.
+    pub fn from_cards(mut cards: Vec<Card>) -> Self {
+        cards.reverse();
+        let procedure = ProcedureProfile {
+            name: "stacked".to_string(),
+            decks: cards.len().div_ceil(52),
+            burn: BurnRule::Fixed(0),
+            cut_card_depth: 0,
+            reshuffle: ReshufflePolicy::CutCard,
+        };
+        Self {
+            cards,
+            procedure,
+            cards_dealt: 0,
+            burned: Some(Vec::new()),
+            stacked: true,
+            top_rank: 13,
+            rng: None,
+        }
+    }
+
+    /// A shoe picked up part way through, e.g. from a saved session:
+    /// `to_come` in dealing order, `cards_dealt` since the shuffle, and the
+    /// cards already burned, or `None` if the burn has still to run.
+    pub fn resume(
+        procedure: ProcedureProfile,
+        top_rank: u8,
+        mut to_come: Vec<Card>,
+        cards_dealt: usize,
+        burned: Option<Vec<Card>>,
+    ) -> Self {
+        to_come.reverse();
+        Self {
+            cards: to_come,
+            procedure,
+            cards_dealt,
+            burned,
+            stacked: false,
+            top_rank,
+            rng: None,
+        }
+    }
+
+    /// A freshly shuffled shoe. The procedure's burn runs before the first
+    /// card is dealt.
+    pub fn with_procedure(procedure: ProcedureProfile) -> Self {
+        Self::stripped(procedure, 13)
+    }
+
+    /// A freshly shuffled shoe of decks holding only the ranks up to
+    /// `top_rank`.
+    pub fn stripped(procedure: ProcedureProfile, top_rank: u8) -> Self {
+        Self::shuffled(procedure, top_rank, None)
+    }
+
+    /// Like `stripped`, but every shuffle comes from `seed`, so the same
+    /// seed deals the same cards shoe after shoe.
+    pub fn seeded(procedure: ProcedureProfile, top_rank: u8, seed: u64) -> Self {
+        Self::with_rng(procedure, top_rank, seeded_rng(seed))
+    }
+
+    /// Like `stripped`, but every shuffle comes from `rng`.
+    pub fn with_rng(procedure: ProcedureProfile, top_rank: u8, rng: ShuffleRng) -> Self {
+        Self::shuffled(procedure, top_rank, Some(rng))
+    }
+
+    /// Shuffles from `rng` from the next reshuffle on, and puts it back
+    /// into any cards returned to a continuous shuffler.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    fn shuffled(procedure: ProcedureProfile, top_rank: u8, mut rng: Option<ShuffleRng>) -> Self {
+        let mut cards = Vec::with_capacity(4 * top_rank as usize * procedure.decks);
+        for _ in 0..procedure.decks {
             for suit in 0..4 {
-                for rank in 1..=13 {
+                for rank in 1..=top_rank {
                     cards.push(Card::new(suit, rank));
                 }
             }
         }
-        
+
         use rand::seq::SliceRandom;
-        let mut rng = rand::rng();
-        cards.shuffle(&mut rng);
-        
-        let cut_card_position = cards.len() - (cards.len() / 10).max(15);
-        
+        match &mut rng {
+            Some(rng) => cards.shuffle(rng.as_mut()),
+            None => cards.shuffle(&mut rand::rng()),
+        }
+
         Self {
             cards,
-            num_decks,
-            cut_card_position,
+            procedure,
             cards_dealt: 0,
+            burned: None,
+            stacked: false,
+            top_rank,
+            rng,
+        }
+    }
+
+    /// Burns cards off the top of a freshly shuffled shoe as the procedure
+    /// says: under `BurnRule::ByFirstCard` the first card is turned face up
+    /// and as many more burned as its value. Runs once per shuffle; later
+    /// calls return the same cards.
+    pub fn burn_procedure(&mut self) -> &[Card] {
+        let cards = &mut self.cards;
+        self.burned.get_or_insert_with(|| {
+            let count = match self.procedure.burn {
+                BurnRule::Fixed(count) => count,
+                BurnRule::ByFirstCard => match cards.last() {
+                    Some(card) if card.baccarat_value() == 0 => 11,
+                    Some(card) => 1 + card.baccarat_value() as usize,
+                    None => 0,
+                },
+            };
+            let mut burned = cards.split_off(cards.len().saturating_sub(count));
+            burned.reverse();
+            burned
+        })
+    }
+
+    /// Cards burned since the shuffle, or `None` before the burn.
+    pub fn burned(&self) -> Option<&[Card]> {
+        self.burned.as_deref()
+    }
+
+    /// The card turned face up by the burn, if the procedure shows one.
+    pub fn burn_reveal(&self) -> Option<Card> {
+        match self.procedure.burn {
+            BurnRule::ByFirstCard => self.burned()?.first().copied(),
+            BurnRule::Fixed(_) => None,
         }
     }
-    
+
     pub fn deal(&mut self) -> Option<Card> {
+        self.burn_procedure();
         if self.cards.is_empty() {
             return None;
         }
         self.cards_dealt += 1;
         self.cards.pop()
     }
-    
+
+    /// Whether the cut card has come out.
     pub fn needs_reshuffle(&self) -> bool {
-        self.cards.len() <= (52 * self.num_decks) - self.cut_card_position
+        match self.procedure.reshuffle {
+            ReshufflePolicy::CutCard => self.cards.len() <= self.procedure.cut_card_depth,
+            ReshufflePolicy::Continuous => false,
+        }
+    }
+
+    /// Roughly how many more hands come out before the cut card, or `None`
+    /// for a continuous shuffler, which has none.
+    pub fn hands_until_cut_card(&self) -> Option<usize> {
+        match self.procedure.reshuffle {
+            ReshufflePolicy::CutCard => {
+                let cards = self
+                    .cards
+                    .len()
+                    .saturating_sub(self.procedure.cut_card_depth);
+                Some((cards as f64 / AVERAGE_CARDS_PER_HAND) as usize)
+            }
+            ReshufflePolicy::Continuous => None,
+        }
     }
-    
+
+    /// Rebuilds the full shoe, freshly shuffled and burned. A stacked shoe
+    /// keeps its remaining cards.
     pub fn reshuffle(&mut self) {
-        *self = Self::new(self.num_decks);
+        if !self.stacked {
+            *self = Self::shuffled(self.procedure.clone(), self.top_rank, self.rng.take());
+        }
     }
-    
+
+    /// Puts a finished hand's cards back at random positions when the shoe
+    /// is a continuous shuffler; otherwise they go to the discard tray.
+    pub fn return_cards(&mut self, returned: &[Card]) {
+        if self.procedure.reshuffle != ReshufflePolicy::Continuous {
+            return;
+        }
+        use rand::Rng;
+        for &card in returned {
+            let positions = 0..=self.cards.len();
+            let position = match &mut self.rng {
+                Some(rng) => rng.random_range(positions),
+                None => rand::rng().random_range(positions),
+            };
+            self.cards.insert(position, card);
+        }
+    }
+
     pub fn cards_remaining(&self) -> usize {
         self.cards.len()
     }
+
+    /// Whether this is a `from_cards` shoe, which never reshuffles.
+    pub fn is_stacked(&self) -> bool {
+        self.stacked
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The cards still to deal, next first.
+    pub fn cards_to_come(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter().rev()
+    }
+
+    /// How many of each rank, ace to king, are still to be dealt; burned
+    /// cards are gone.
+    pub fn composition(&self) -> [u32; 13] {
+        rank_counts(&self.cards)
+    }
+
+    pub fn cards_dealt(&self) -> usize {
+        self.cards_dealt
+    }
+
+    /// Cards in the full shoe, before any burn.
+    pub fn size(&self) -> usize {
+        if self.stacked {
+            self.cards_dealt + self.cards.len()
+        } else {
+            4 * self.top_rank as usize * self.procedure.decks
+        }
+    }
+}
+
+/// How many of each rank, ace to king; a card with no such rank isn't
+/// counted.
+fn rank_counts(cards: &[Card]) -> [u32; 13] {
+    let mut counts = [0; 13];
+    for card in cards {
+        if let Some(count) = (card.rank as usize)
+            .checked_sub(1)
+            .and_then(|index| counts.get_mut(index))
+        {
+            *count += 1;
+        }
+    }
+    counts
 }
 
+/// Where a game's cards come from.
 pub enum CardSource {
     SingleDeck(Deck),
     Shoe(Shoe),
@@ -151,130 +1642,590 @@ impl CardSource {
             CardSource::Shoe(shoe) => shoe.deal(),
         }
     }
-    
+
+    /// Whether the source is too low to deal another full hand.
     pub fn needs_reshuffle(&self) -> bool {
         match self {
             CardSource::SingleDeck(deck) => deck.cards.len() < 6,
             CardSource::Shoe(shoe) => shoe.needs_reshuffle(),
         }
     }
-    
+
+    /// Cards dealt since the last shuffle and the full pack size; `None` for
+    /// a stacked shoe, which has no fixed size.
+    pub fn penetration(&self) -> Option<(usize, usize)> {
+        match self {
+            CardSource::SingleDeck(deck) => Some((deck.size() - deck.cards.len(), deck.size())),
+            CardSource::Shoe(shoe) if shoe.is_stacked() => None,
+            CardSource::Shoe(shoe) => Some((shoe.cards_dealt(), shoe.size())),
+        }
+    }
+
+    /// How many of each rank, ace to king, a shuffled deck or shoe has
+    /// still to deal; `None` for a stacked shoe, dealt in a fixed order.
+    pub fn composition(&self) -> Option<[u32; 13]> {
+        match self {
+            CardSource::SingleDeck(deck) => Some(rank_counts(deck.cards())),
+            CardSource::Shoe(shoe) if shoe.is_stacked() => None,
+            CardSource::Shoe(shoe) => Some(shoe.composition()),
+        }
+    }
+
+    /// Roughly how many hands are left before the cut card; `None` unless
+    /// the cards come from a shoe dealt to a cut card.
+    pub fn hands_until_cut_card(&self) -> Option<usize> {
+        match self {
+            CardSource::Shoe(shoe) if !shoe.is_stacked() => shoe.hands_until_cut_card(),
+            _ => None,
+        }
+    }
+
     pub fn reshuffle(&mut self) {
         match self {
             CardSource::SingleDeck(deck) => {
-                *deck = Deck::new();
+                deck.restock();
                 deck.shuffle();
             }
             CardSource::Shoe(shoe) => shoe.reshuffle(),
         }
     }
-}
+}
+
+/// Why a hand could not be dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameError {
+    /// The shoe or deck ran dry mid-hand and the table is set not to
+    /// reshuffle.
+    ShoeEmpty,
+    /// A stacked shoe, e.g. a recorded one, ran out; there is nothing to
+    /// reshuffle.
+    SequenceExhausted,
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameError::ShoeEmpty => write!(f, "the shoe ran out of cards mid-hand"),
+            GameError::SequenceExhausted => write!(f, "the card sequence ran out mid-hand"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// What `play_round` does when the card source runs dry mid-hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyShoePolicy {
+    /// Void the partial hand, reshuffle, and deal it again once.
+    ReshuffleAndRetry,
+    /// Void the partial hand and return the error.
+    Fail,
+}
+
+/// Where a hand stands, named for the card most recently dealt.
+/// `BaccaratGame::advance` moves it along one card at a time:
+/// `Betting → DealPlayer1 → DealBanker1 → DealPlayer2 → DealBanker2`, then
+/// `PlayerThird` and `BankerThird` when the drawing rules call for them,
+/// then `Settled`. Where a mode leaves a draw to the hand's holder, the hand
+/// waits at `DecisionRequired` until `BaccaratGame::decide` answers. Dragon
+/// Tiger settles after `DealBanker1`, and Three Card Baccarat always deals
+/// both third cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamePhase {
+    /// No cards dealt yet.
+    Betting,
+    DealPlayer1,
+    DealBanker1,
+    DealPlayer2,
+    DealBanker2,
+    PlayerThird,
+    BankerThird,
+    /// Waiting on the holder of this side's hand to draw or stand.
+    DecisionRequired(Side),
+    /// The hand is complete and the winner decided.
+    Settled,
+}
+
+/// One of the two hands on the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Player,
+    Banker,
+}
+
+/// Something that happened at the table, sent to every subscriber as it
+/// happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// One of the opening cards: four in baccarat, every card at tables
+    /// where nobody draws.
+    CardDealt { side: Side, card: Card },
+    /// A hand ended on a two-card 8 or 9; the score of each natural.
+    NaturalDetected {
+        player: Option<u8>,
+        banker: Option<u8>,
+    },
+    /// A third card drawn under the tableau.
+    ThirdCardDrawn { side: Side, card: Card },
+    /// The hand is complete. `winner` is 1 for player, 2 for banker, 3 for
+    /// a tie.
+    RoundSettled {
+        winner: u8,
+        player_score: u8,
+        banker_score: u8,
+    },
+    /// The cards were shuffled, between hands or after voiding one that
+    /// ran dry.
+    ShoeReshuffled,
+    /// Cards burned from a fresh shoe before its first hand; `revealed` is
+    /// the card turned face up to set the count, if the procedure shows one.
+    CardsBurned {
+        revealed: Option<Card>,
+        count: usize,
+    },
+}
+
+/// One table: its card source, the hands in play, and the bets riding on
+/// them.
+pub struct BaccaratGame {
+    pub card_source: CardSource,
+    pub empty_shoe_policy: EmptyShoePolicy,
+    pub player_hand: Hand,
+    pub banker_hand: Hand,
+    pub state: GameState,
+    phase: GamePhase,
+    subscribers: Vec<Sender<GameEvent>>,
+    pub mode: GameMode,
+    pub bonus_bets: BonusBets,
+    /// Pay table for this table's mode, with any house or event overrides.
+    pub payout_table: PayoutTable,
+    pub over_under: OverUnderRule,
+    pub tie_rule: TieRule,
+    pub limits: TableLimits,
+}
+
+impl Default for BaccaratGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BaccaratGame {
+    /// A classic table dealing from a single deck.
+    pub fn new() -> Self {
+        Self::with_mode(GameMode::Classic)
+    }
+
+    /// A table in `mode` dealing from a single deck.
+    pub fn with_mode(mode: GameMode) -> Self {
+        let mut deck = Deck::stripped(mode.top_rank());
+        deck.shuffle();
+        Self::with_card_source(mode, CardSource::SingleDeck(deck))
+    }
+
+    /// A table dealing from a standard shoe of `num_decks` decks.
+    pub fn with_shoe(mode: GameMode, num_decks: usize) -> Self {
+        Self::with_procedure(mode, ProcedureProfile::standard(num_decks))
+    }
+
+    /// A table dealing from a shoe run by `procedure`.
+    pub fn with_procedure(mode: GameMode, procedure: ProcedureProfile) -> Self {
+        Self::with_card_source(
+            mode,
+            CardSource::Shoe(Shoe::stripped(procedure, mode.top_rank())),
+        )
+    }
+
+    /// Like `with_procedure`, but the shoe's shuffles all come from `seed`.
+    pub fn seeded(mode: GameMode, procedure: ProcedureProfile, seed: u64) -> Self {
+        Self::with_rng(mode, procedure, seeded_rng(seed))
+    }
+
+    /// Like `with_procedure`, but the shoe's shuffles all come from `rng`.
+    pub fn with_rng(mode: GameMode, procedure: ProcedureProfile, rng: ShuffleRng) -> Self {
+        Self::with_card_source(
+            mode,
+            CardSource::Shoe(Shoe::with_rng(procedure, mode.top_rank(), rng)),
+        )
+    }
+
+    /// A table dealing from `card_source` as it stands, e.g. a stacked
+    /// `Shoe::from_cards` for replaying a known hand.
+    pub fn with_card_source(mode: GameMode, card_source: CardSource) -> Self {
+        Self {
+            card_source,
+            empty_shoe_policy: EmptyShoePolicy::ReshuffleAndRetry,
+            player_hand: Hand::new(),
+            banker_hand: Hand::new(),
+            state: GameState::new(),
+            phase: GamePhase::Betting,
+            subscribers: Vec::new(),
+            mode,
+            bonus_bets: BonusBets::new(),
+            payout_table: mode.payout_table(),
+            over_under: OverUnderRule::standard(),
+            tie_rule: TieRule::PushMainBets,
+            limits: TableLimits::STANDARD,
+        }
+    }
+
+    /// A table dealing `cards` in order, e.g. a recorded real shoe.
+    pub fn with_card_sequence(mode: GameMode, cards: Vec<Card>) -> Self {
+        Self::with_card_source(mode, CardSource::Shoe(Shoe::from_cards(cards)))
+    }
+
+    /// Clears the previous hand so the same card source can deal again,
+    /// reshuffling first when the cut card has come out. Returns whether a
+    /// reshuffle happened.
+    pub fn prepare_next_round(&mut self) -> bool {
+        if let CardSource::Shoe(shoe) = &mut self.card_source {
+            shoe.return_cards(&self.player_hand);
+            shoe.return_cards(&self.banker_hand);
+        }
+        self.void_hand();
+        if self.card_source.needs_reshuffle() {
+            self.reshuffle();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// A stream of everything that happens at this table from now on. Any
+    /// number of receivers can listen; dropped ones are forgotten.
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event).is_ok());
+    }
+
+    fn reshuffle(&mut self) {
+        self.card_source.reshuffle();
+        self.emit(GameEvent::ShoeReshuffled);
+    }
+
+    /// The next card from the source.
+    pub fn deal(&mut self) -> Result<Card, GameError> {
+        if let CardSource::Shoe(shoe) = &mut self.card_source
+            && shoe.burned().is_none()
+        {
+            let count = shoe.burn_procedure().len();
+            let revealed = shoe.burn_reveal();
+            if count > 0 {
+                self.emit(GameEvent::CardsBurned { revealed, count });
+            }
+        }
+        self.card_source.deal().ok_or(match &self.card_source {
+            CardSource::Shoe(shoe) if shoe.is_stacked() => GameError::SequenceExhausted,
+            _ => GameError::ShoeEmpty,
+        })
+    }
+
+    pub fn phase(&self) -> GamePhase {
+        self.phase
+    }
+
+    fn update_scores(&mut self) {
+        self.state.player_score = self.hand_score(&self.player_hand);
+        self.state.banker_score = self.hand_score(&self.banker_hand);
+    }
+
+    /// Baccarat points, or the card's own value at Dragon Tiger.
+    fn hand_score(&self, hand: &Hand) -> u8 {
+        if self.mode == GameMode::DragonTiger {
+            hand.first().map_or(0, dragon_tiger::card_value)
+        } else {
+            hand.score()
+        }
+    }
+
+    /// Deals the rest of the hand under the drawing rules and settles the
+    /// winner; from `Betting` that is the whole hand. Stops early at
+    /// `DecisionRequired` until `decide` answers it, and does nothing once
+    /// the hand is settled.
+    pub fn play_round(&mut self) -> Result<(), GameError> {
+        while !matches!(
+            self.phase,
+            GamePhase::Settled | GamePhase::DecisionRequired(_)
+        ) {
+            self.advance()?;
+        }
+        Ok(())
+    }
 
-pub struct BaccaratGame {
-    pub card_source: CardSource,
-    pub player_hand: Vec<Card>,
-    pub banker_hand: Vec<Card>,
-    pub state: GameState,
-    pub mode: GameMode,
-    pub bonus_bets: BonusBets,
-}
+    /// Deals the whole hand like `play_round`, answering every draw
+    /// decision with `house_draws`, for hands played with nobody to ask.
+    pub fn play_round_unattended(&mut self) -> Result<(), GameError> {
+        self.play_round()?;
+        while matches!(self.phase, GamePhase::DecisionRequired(_)) {
+            self.decide(self.house_draws())?;
+            self.play_round()?;
+        }
+        Ok(())
+    }
 
-impl BaccaratGame {
-    pub fn new() -> Self {
-        Self::with_mode(GameMode::Classic)
+    /// Deals the next card the drawing rules call for, or settles the hand
+    /// once no more are due, and returns the new phase. A hand waiting at
+    /// `DecisionRequired` stays there.
+    pub fn advance(&mut self) -> Result<GamePhase, GameError> {
+        self.recovering(Self::step)
     }
 
-    pub fn with_mode(mode: GameMode) -> Self {
-        let mut deck = Deck::new();
-        deck.shuffle();
+    /// Answers the draw decision the hand is waiting on: `draw` deals the
+    /// deciding side a third card, otherwise it stands. Returns the new
+    /// phase; does nothing unless a decision is pending.
+    pub fn decide(&mut self, draw: bool) -> Result<GamePhase, GameError> {
+        self.recovering(|game| {
+            let GamePhase::DecisionRequired(side) = game.phase else {
+                return Ok(game.phase);
+            };
+            let next = match (side, draw) {
+                (Side::Player, true) => GamePhase::PlayerThird,
+                (Side::Player, false) => game.banker_turn(),
+                (Side::Banker, true) => GamePhase::BankerThird,
+                (Side::Banker, false) => GamePhase::Settled,
+            };
+            game.enter(next)
+        })
+    }
 
-        Self {
-            card_source: CardSource::SingleDeck(deck),
-            player_hand: Vec::new(),
-            banker_hand: Vec::new(),
-            state: GameState::new(),
-            mode,
-            bonus_bets: BonusBets::new(),
+    /// What the Punto Banco tableau does at the pending decision; `false`
+    /// when there is none.
+    pub fn house_draws(&self) -> bool {
+        match self.phase {
+            GamePhase::DecisionRequired(Side::Player) => {
+                Self::player_should_draw(self.state.player_score)
+            }
+            GamePhase::DecisionRequired(Side::Banker) => {
+                let player_third_value = self
+                    .player_hand
+                    .third_card()
+                    .map(|card| card.baccarat_value());
+                Self::banker_should_draw(self.state.banker_score, player_third_value)
+            }
+            _ => false,
         }
     }
-    
-    pub fn with_shoe(mode: GameMode, num_decks: usize) -> Self {
-        Self {
-            card_source: CardSource::Shoe(Shoe::new(num_decks)),
-            player_hand: Vec::new(),
-            banker_hand: Vec::new(),
-            state: GameState::new(),
-            mode,
-            bonus_bets: BonusBets::new(),
+
+    /// Runs one move of the hand. If the card source runs dry mid-hand, the
+    /// partial hand is voided and, under
+    /// `EmptyShoePolicy::ReshuffleAndRetry`, dealt again from a fresh
+    /// shuffle.
+    fn recovering(
+        &mut self,
+        step: impl FnOnce(&mut Self) -> Result<GamePhase, GameError>,
+    ) -> Result<GamePhase, GameError> {
+        let result = match step(self) {
+            Err(GameError::ShoeEmpty)
+                if self.empty_shoe_policy == EmptyShoePolicy::ReshuffleAndRetry =>
+            {
+                self.void_hand();
+                self.reshuffle();
+                self.step()
+            }
+            result => result,
+        };
+        if result.is_err() {
+            self.void_hand();
         }
+        result
     }
 
-    pub fn deal_initial_cards(&mut self) {
-        self.player_hand.push(self.card_source.deal().unwrap());
-        self.banker_hand.push(self.card_source.deal().unwrap());
-        self.player_hand.push(self.card_source.deal().unwrap());
-        self.banker_hand.push(self.card_source.deal().unwrap());
-        self.update_scores();
+    fn step(&mut self) -> Result<GamePhase, GameError> {
+        let next = match self.phase {
+            GamePhase::Betting => GamePhase::DealPlayer1,
+            GamePhase::DealPlayer1 => GamePhase::DealBanker1,
+            GamePhase::DealBanker1 if self.mode.fixed_hand_size() == Some(1) => GamePhase::Settled,
+            GamePhase::DealBanker1 => GamePhase::DealPlayer2,
+            GamePhase::DealPlayer2 => GamePhase::DealBanker2,
+            GamePhase::DealBanker2 if self.mode.fixed_hand_size().is_some() => {
+                GamePhase::PlayerThird
+            }
+            GamePhase::DealBanker2
+                if self.player_natural().is_some() || self.banker_natural().is_some() =>
+            {
+                GamePhase::Settled
+            }
+            GamePhase::DealBanker2 => match self.mode.player_draw(self.state.player_score) {
+                DrawRule::Draw => GamePhase::PlayerThird,
+                DrawRule::Optional => GamePhase::DecisionRequired(Side::Player),
+                DrawRule::Stand => self.banker_turn(),
+            },
+            GamePhase::PlayerThird if self.mode.fixed_hand_size().is_some() => {
+                GamePhase::BankerThird
+            }
+            GamePhase::PlayerThird => self.banker_turn(),
+            GamePhase::DecisionRequired(_) => return Ok(self.phase),
+            GamePhase::BankerThird | GamePhase::Settled => GamePhase::Settled,
+        };
+        self.enter(next)
     }
 
-    fn update_scores(&mut self) {
-        self.state.player_score = GameState::calculate_hand_score(&self.player_hand);
-        self.state.banker_score = GameState::calculate_hand_score(&self.banker_hand);
+    /// The banker's move once the player's hand is done.
+    fn banker_turn(&self) -> GamePhase {
+        let player_third_value = self
+            .player_hand
+            .third_card()
+            .map(|card| card.baccarat_value());
+        match self
+            .mode
+            .banker_draw(self.state.banker_score, player_third_value)
+        {
+            DrawRule::Draw => GamePhase::BankerThird,
+            DrawRule::Optional => GamePhase::DecisionRequired(Side::Banker),
+            DrawRule::Stand => GamePhase::Settled,
+        }
     }
 
-    pub fn play_round(&mut self) {
-        self.deal_initial_cards();
-        if self.state.player_score >= 8 || self.state.banker_score >= 8 {
-            self.determine_winner();
-            return;
+    /// Deals the card `next` is named for, or settles the hand, and moves
+    /// the hand on to `next`.
+    fn enter(&mut self, next: GamePhase) -> Result<GamePhase, GameError> {
+        let event = match next {
+            GamePhase::DealPlayer1 | GamePhase::DealPlayer2 | GamePhase::PlayerThird => {
+                let card = self.deal()?;
+                self.player_hand.push(card);
+                Some(self.card_event(next, Side::Player, card))
+            }
+            GamePhase::DealBanker1 | GamePhase::DealBanker2 | GamePhase::BankerThird => {
+                let card = self.deal()?;
+                self.banker_hand.push(card);
+                Some(self.card_event(next, Side::Banker, card))
+            }
+            GamePhase::Settled if self.phase != GamePhase::Settled => {
+                if self.player_natural().is_some() || self.banker_natural().is_some() {
+                    self.emit(GameEvent::NaturalDetected {
+                        player: self.player_natural(),
+                        banker: self.banker_natural(),
+                    });
+                }
+                self.determine_winner();
+                Some(GameEvent::RoundSettled {
+                    winner: self.state.winner,
+                    player_score: self.state.player_score,
+                    banker_score: self.state.banker_score,
+                })
+            }
+            GamePhase::Settled | GamePhase::Betting | GamePhase::DecisionRequired(_) => None,
+        };
+        self.update_scores();
+        self.phase = next;
+        if let Some(event) = event {
+            self.emit(event);
         }
+        Ok(next)
+    }
 
-        let player_third_card = if self.state.player_score <= 5 {
-            let card = self.card_source.deal().unwrap();
-            self.player_hand.push(card);
-            self.update_scores();
-            Some(card.baccarat_value())
-        } else {
-            None
-        };
+    fn card_event(&self, phase: GamePhase, side: Side, card: Card) -> GameEvent {
+        match phase {
+            GamePhase::PlayerThird | GamePhase::BankerThird
+                if self.mode.fixed_hand_size().is_none() =>
+            {
+                GameEvent::ThirdCardDrawn { side, card }
+            }
+            _ => GameEvent::CardDealt { side, card },
+        }
+    }
 
-        self.banker_draw_logic(player_third_card);
+    /// Discards the hand, dealt or partly dealt, ready for the next.
+    fn void_hand(&mut self) {
+        self.player_hand.clear();
+        self.banker_hand.clear();
+        self.state = GameState::new();
+        self.phase = GamePhase::Betting;
+    }
 
+    /// Replaces both hands with already-completed ones and settles the
+    /// winner, for analysing outcomes without dealing them.
+    pub fn set_hands(&mut self, player_hand: &[Card], banker_hand: &[Card]) {
+        self.player_hand = Hand::from(player_hand);
+        self.banker_hand = Hand::from(banker_hand);
+        self.update_scores();
         self.determine_winner();
+        self.phase = GamePhase::Settled;
+    }
+
+    /// A two-card 8 or 9 ends the hand: neither side draws.
+    pub fn is_natural_total(score: u8) -> bool {
+        score >= 8
     }
 
-    fn banker_draw_logic(&mut self, player_third_value: Option<u8>) {
-        let should_draw = match self.state.banker_score {
+    /// Player draws on 0-5 and stands on 6-7.
+    pub fn player_should_draw(player_score: u8) -> bool {
+        player_score <= 5
+    }
+
+    /// Banker's tableau. `player_third_value` is `None` when the player stood,
+    /// in which case the banker draws on 0-5 like the player.
+    pub fn banker_should_draw(banker_score: u8, player_third_value: Option<u8>) -> bool {
+        let Some(third) = player_third_value else {
+            return banker_score <= 5;
+        };
+        match banker_score {
             0..=2 => true,
-            3 => player_third_value != Some(8),
-            4 => matches!(player_third_value, Some(2..=7)),
-            5 => matches!(player_third_value, Some(4..=7)),
-            6 => matches!(player_third_value, Some(6 | 7)),
+            3 => third != 8,
+            4 => matches!(third, 2..=7),
+            5 => matches!(third, 4..=7),
+            6 => matches!(third, 6 | 7),
             _ => false,
-        };
-
-        if should_draw {
-            self.banker_hand.push(self.card_source.deal().unwrap());
-            self.update_scores();
         }
     }
 
     fn determine_winner(&mut self) {
         self.state.round_complete = 1;
 
-        self.state.winner = if self.state.player_score > self.state.banker_score {
-            1 // Player wins
-        } else if self.state.banker_score > self.state.player_score {
-            2 // Banker wins
+        let order = if self.mode == GameMode::ThreeCard {
+            three_card::strength(&self.player_hand).cmp(&three_card::strength(&self.banker_hand))
         } else {
-            3 // Tie
+            self.state.player_score.cmp(&self.state.banker_score)
+        };
+        self.state.winner = match order {
+            std::cmp::Ordering::Greater => 1, // Player wins
+            std::cmp::Ordering::Less => 2,    // Banker wins
+            std::cmp::Ordering::Equal => 3,   // Tie
         };
     }
 
+    /// The player's first two cards share a rank.
     pub fn is_player_pair(&self) -> bool {
-        self.player_hand.len() >= 2 && self.player_hand[0].rank == self.player_hand[1].rank
+        self.player_hand.is_pair()
+    }
+
+    /// The two-card total if `hand` is a natural at this table's mode.
+    fn natural(&self, hand: &Hand) -> Option<u8> {
+        (self.mode.fixed_hand_size().is_none()
+            && hand.len() == 2
+            && hand.score() >= self.mode.natural_minimum())
+        .then(|| hand.score())
+    }
+
+    /// The player's two-card 8 or 9 (9 only at Super Pan 9), if the hand is
+    /// a natural.
+    pub fn player_natural(&self) -> Option<u8> {
+        self.natural(&self.player_hand)
+    }
+
+    /// The banker's two-card 8 or 9 (9 only at Super Pan 9), if the hand is
+    /// a natural.
+    pub fn banker_natural(&self) -> Option<u8> {
+        self.natural(&self.banker_hand)
     }
 
+    /// Player points plus banker points, the figure over/under bets settle on.
+    pub fn combined_points(&self) -> u8 {
+        self.state.player_score + self.state.banker_score
+    }
+
+    /// Points the winning hand won by; zero on a tie.
     pub fn victory_margin(&self) -> u8 {
         if self.state.winner == 0 || self.state.winner == 3 {
             0
@@ -285,300 +2236,889 @@ impl BaccaratGame {
         }
     }
 
+    /// The banker's first two cards share a rank.
     pub fn is_banker_pair(&self) -> bool {
-        self.banker_hand.len() >= 2 && self.banker_hand[0].rank == self.banker_hand[1].rank
+        self.banker_hand.is_pair()
     }
 
     pub fn is_either_pair(&self) -> bool {
         self.is_player_pair() || self.is_banker_pair()
     }
 
+    /// Either hand opens with two cards of the same rank and suit.
     pub fn is_perfect_pair(&self) -> bool {
-        let player_perfect = self.player_hand.len() >= 2 
-            && self.player_hand[0].rank == self.player_hand[1].rank 
-            && self.player_hand[0].suit == self.player_hand[1].suit;
-        
-        let banker_perfect = self.banker_hand.len() >= 2 
-            && self.banker_hand[0].rank == self.banker_hand[1].rank 
-            && self.banker_hand[0].suit == self.banker_hand[1].suit;
-        
-        player_perfect || banker_perfect
+        self.player_hand.is_suited_pair() || self.banker_hand.is_suited_pair()
     }
 
-    pub fn calculate_main_bet_payout(&self, bet_type: &str, bet_amount: i32) -> i32 {
-        match self.mode {
-            GameMode::Classic => self.classic_payout(bet_type, bet_amount),
-            GameMode::NoCommission => self.no_commission_payout(bet_type, bet_amount),
-            GameMode::Speed => self.speed_payout(bet_type, bet_amount),
-            GameMode::EzBaccarat => self.ez_baccarat_payout(bet_type, bet_amount),
+    /// Amount returned on a main bet (stake included), zero if it lost.
+    /// Bets outside the mode's `main_bets` never win.
+    pub fn calculate_main_bet_payout(&self, bet_type: BetType, bet_amount: Money) -> Money {
+        let table = &self.payout_table;
+        match (bet_type, self.state.winner) {
+            (BetType::Player, 1) => bet_amount * table.player as i64,
+            (BetType::Banker, 2) if self.mode == GameMode::EzBaccarat && self.is_dragon_7() => {
+                bet_amount
+            }
+            (BetType::Banker, 2) if self.state.banker_score == 6 => {
+                bet_amount.percent(table.banker_six_pct as i64)
+            }
+            (BetType::Banker, 2) => bet_amount.percent(table.banker_pct as i64),
+            (BetType::Tie, 3) => bet_amount * table.tie as i64,
+            (BetType::Player | BetType::Banker, 3)
+                if self.tie_rule == TieRule::LoseMainBets || self.mode == GameMode::ThreeCard =>
+            {
+                Money::ZERO
+            }
+            (BetType::Player | BetType::Banker, 3) if self.mode == GameMode::DragonTiger => {
+                dragon_tiger::tie_return(bet_amount)
+            }
+            (BetType::Player | BetType::Banker, 3) => bet_amount,
+            (BetType::Dragon7, 2) if self.is_dragon_7() => bet_amount * table.dragon_7 as i64,
+            (BetType::Panda8, 1) if self.is_panda_8() => bet_amount * table.panda_8 as i64,
+            _ => Money::ZERO,
         }
     }
 
-    fn classic_payout(&self, bet_type: &str, bet_amount: i32) -> i32 {
-        match (bet_type, self.state.winner) {
-            ("player", 1) => bet_amount * 2,
-            ("banker", 2) => (bet_amount as f32 * 1.95) as i32,
-            ("tie", 3) => bet_amount * 9,
-            _ => 0,
-        }
+    /// Whether a `bet` on the finished hand stays on the layout for the next
+    /// one, under `TieRule::BargeToNextHand`. Dragon Tiger and Three Card
+    /// Baccarat settle ties by their own rules.
+    pub fn main_bet_rides(&self, bet: BetType) -> bool {
+        self.tie_rule == TieRule::BargeToNextHand
+            && self.mode.fixed_hand_size().is_none()
+            && self.state.winner == 3
+            && matches!(bet, BetType::Player | BetType::Banker)
     }
 
-    fn no_commission_payout(&self, bet_type: &str, bet_amount: i32) -> i32 {
-        match (bet_type, self.state.winner) {
-            ("player", 1) => bet_amount * 2,
-            ("banker", 2) => {
-                if self.state.banker_score == 6 {
-                    (bet_amount as f32 * 1.5) as i32
-                } else {
-                    bet_amount * 2
-                }
-            }
-            ("tie", 3) => bet_amount * 9,
-            _ => 0,
-        }
+    /// Banker wins with a three-card 7 (EZ Baccarat).
+    pub fn is_dragon_7(&self) -> bool {
+        self.state.winner == 2 && self.state.banker_score == 7 && self.banker_hand.len() == 3
     }
 
-    fn speed_payout(&self, bet_type: &str, bet_amount: i32) -> i32 {
-        match (bet_type, self.state.winner) {
-            ("player", 1) => bet_amount * 2,
-            ("banker", 2) => bet_amount * 2,
-            ("tie", 3) => bet_amount * 8,
-            _ => 0,
-        }
+    /// Player wins with a three-card 8 (EZ Baccarat).
+    pub fn is_panda_8(&self) -> bool {
+        self.state.winner == 1 && self.state.player_score == 8 && self.player_hand.len() == 3
     }
 
-    fn ez_baccarat_payout(&self, bet_type: &str, bet_amount: i32) -> i32 {
-        match (bet_type, self.state.winner) {
-            ("player", 1) => bet_amount * 2,
-            ("banker", 2) => {
-                if self.banker_hand.len() == 3 
-                    && self.state.banker_score == 7 
-                    && self.banker_hand.iter().all(|c| c.baccarat_value() == 0 || c.baccarat_value() >= 10) {
-                    bet_amount
-                } else {
-                    bet_amount * 2
-                }
-            }
-            ("tie", 3) => bet_amount * 9,
-            ("dragon7", 2) if self.is_dragon_7() => bet_amount * 40,
-            ("panda8", 1) if self.is_panda_8() => bet_amount * 25,
-            _ => 0,
-        }
+    /// Banker wins with a three-card 6 (EZ Baccarat Monkey 6).
+    pub fn is_monkey_6(&self) -> bool {
+        self.state.winner == 2 && self.state.banker_score == 6 && self.banker_hand.len() == 3
     }
 
-    pub fn is_dragon_7(&self) -> bool {
-        self.state.winner == 2 
-            && self.state.banker_score == 7 
-            && self.banker_hand.len() == 3
+    /// The hand ties at 6 with the banker on three cards (EZ Baccarat
+    /// Monkey Tie).
+    pub fn is_monkey_tie(&self) -> bool {
+        self.state.winner == 3 && self.state.banker_score == 6 && self.banker_hand.len() == 3
     }
 
-    pub fn is_panda_8(&self) -> bool {
-        self.state.winner == 1 
-            && self.state.player_score == 8 
-            && self.player_hand.len() == 3
+    /// Dragon and Tiger tie on the same card, rank and suit (Dragon Tiger
+    /// suited tie).
+    pub fn is_suited_tie(&self) -> bool {
+        match (self.player_hand.first(), self.banker_hand.first()) {
+            (Some(dragon), Some(tiger)) => {
+                self.mode == GameMode::DragonTiger && dragon_tiger::is_suited_tie(dragon, tiger)
+            }
+            _ => false,
+        }
+    }
+
+    /// `side`'s hand is three court cards at a Three Card Baccarat table.
+    pub fn is_three_faces(&self, side: Side) -> bool {
+        let hand = match side {
+            Side::Player => &self.player_hand,
+            Side::Banker => &self.banker_hand,
+        };
+        self.mode == GameMode::ThreeCard && three_card::is_three_faces(hand)
     }
 
     pub fn set_bonus_bets(&mut self, bets: BonusBets) {
         self.bonus_bets = bets;
     }
 
-    pub fn total_payout(&self, main_bet_type: &str, main_bet_amount: i32) -> i32 {
+    /// Checks `slip` against the table limits, listing every violation.
+    pub fn validate_bet_slip(&self, slip: &BetSlip) -> Result<(), Vec<LimitViolation>> {
+        let violations = self.limits.violations(slip);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Pays out `slip` on the finished hand, bet by bet.
+    pub fn settle(&self, slip: &BetSlip) -> SettlementReport {
+        let main = slip.main_bets().map(|(bet, amount)| {
+            let mut settled = SettledBet::new(
+                Wager::Main(bet),
+                amount,
+                self.calculate_main_bet_payout(bet, amount),
+            );
+            if self.main_bet_rides(bet) {
+                settled.outcome = BetOutcome::Rides;
+            }
+            settled
+        });
+        let side = BonusBetType::ALL
+            .into_iter()
+            .filter(|&bet| slip.bonus_bets.get(bet) > Money::ZERO)
+            .map(|bet| {
+                let stake = slip.bonus_bets.get(bet);
+                SettledBet::new(Wager::Side(bet), stake, slip.bonus_bets.payout(bet, self))
+            });
+        let tie_scores = slip.bonus_bets.tie_score_bets().map(|(score, stake)| {
+            SettledBet::new(
+                Wager::TieScore(score),
+                stake,
+                slip.bonus_bets.tie_score_payout(score, self),
+            )
+        });
+        SettlementReport {
+            bets: main.chain(side).chain(tie_scores).collect(),
+        }
+    }
+
+    /// Main bet payout plus every bonus bet's payout.
+    pub fn total_payout(&self, main_bet_type: BetType, main_bet_amount: Money) -> Money {
         let main_payout = self.calculate_main_bet_payout(main_bet_type, main_bet_amount);
         let bonus_payout = self.bonus_bets.calculate_payouts(self);
         main_payout + bonus_payout
     }
 }
 
+/// Over/under side bet on the combined final points of both hands (0-18).
+/// The line always sits on a half point, so the bet never pushes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OverUnderRule {
+    /// Whole part of the line: 9 means "9.5", so over wins on 10 or more.
+    pub line: u8,
+    /// Amount returned per 100 staked on a winning over/under bet.
+    pub over_return_pct: i32,
+    pub under_return_pct: i32,
+}
+
+impl OverUnderRule {
+    /// Over 9.5 wins about 57.1% of hands and under about 42.9%; these
+    /// returns keep the house edge near 3%.
+    pub fn standard() -> Self {
+        Self {
+            line: 9,
+            over_return_pct: 170,
+            under_return_pct: 225,
+        }
+    }
+
+    /// Parses a half-point line such as "9.5".
+    pub fn parse_line(line: &str) -> Result<u8, String> {
+        match line.trim().strip_suffix(".5").map(str::parse::<u8>) {
+            Some(Ok(whole)) if whole < 18 => Ok(whole),
+            _ => Err(format!(
+                "invalid over/under line '{}', expected e.g. 9.5",
+                line
+            )),
+        }
+    }
+
+    /// Whether `points` settles the over bet as a win.
+    pub fn is_over(&self, points: u8) -> bool {
+        points > self.line
+    }
+}
+
+/// A side bet, settled alongside the main bet.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BonusBetType {
+    PlayerPair,
+    BankerPair,
+    EitherPair,
+    PerfectPair,
+    PlayerDragon,
+    BankerDragon,
+    #[serde(rename = "lucky_6")]
+    Lucky6,
+    Over,
+    Under,
+    EitherNatural,
+    #[serde(rename = "super_6")]
+    SuperSix,
+    Big,
+    Small,
+    #[serde(rename = "monkey_6")]
+    MonkeySix,
+    MonkeyTie,
+    SuitedTie,
+}
+
+impl BonusBetType {
+    pub const ALL: [BonusBetType; 16] = [
+        BonusBetType::PlayerPair,
+        BonusBetType::BankerPair,
+        BonusBetType::EitherPair,
+        BonusBetType::PerfectPair,
+        BonusBetType::PlayerDragon,
+        BonusBetType::BankerDragon,
+        BonusBetType::Lucky6,
+        BonusBetType::Over,
+        BonusBetType::Under,
+        BonusBetType::EitherNatural,
+        BonusBetType::SuperSix,
+        BonusBetType::Big,
+        BonusBetType::Small,
+        BonusBetType::MonkeySix,
+        BonusBetType::MonkeyTie,
+        BonusBetType::SuitedTie,
+    ];
+
+    /// snake_case name used in saved files, rules bundles, and pay tables.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BonusBetType::PlayerPair => "player_pair",
+            BonusBetType::BankerPair => "banker_pair",
+            BonusBetType::EitherPair => "either_pair",
+            BonusBetType::PerfectPair => "perfect_pair",
+            BonusBetType::PlayerDragon => "player_dragon",
+            BonusBetType::BankerDragon => "banker_dragon",
+            BonusBetType::Lucky6 => "lucky_6",
+            BonusBetType::Over => "over",
+            BonusBetType::Under => "under",
+            BonusBetType::EitherNatural => "either_natural",
+            BonusBetType::SuperSix => "super_6",
+            BonusBetType::Big => "big",
+            BonusBetType::Small => "small",
+            BonusBetType::MonkeySix => "monkey_6",
+            BonusBetType::MonkeyTie => "monkey_tie",
+            BonusBetType::SuitedTie => "suited_tie",
+        }
+    }
+}
+
+impl std::fmt::Display for BonusBetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for BonusBetType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|bet| bet.name() == name)
+            .ok_or_else(|| format!("unknown side bet '{}'", s))
+    }
+}
+
+/// Side bet stakes, by bet; zero means not placed.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable, serde::Serialize, serde::Deserialize,
+)]
 pub struct BonusBets {
-    pub player_pair: u8,
-    pub banker_pair: u8,
-    pub either_pair: u8,
-    pub perfect_pair: u8,
-    pub player_dragon: u8,
-    pub banker_dragon: u8,
-    pub lucky_6: u8,
+    pub player_pair: Money,
+    pub banker_pair: Money,
+    pub either_pair: Money,
+    pub perfect_pair: Money,
+    pub player_dragon: Money,
+    pub banker_dragon: Money,
+    pub lucky_6: Money,
+    pub over: Money,
+    pub under: Money,
+    pub either_natural: Money,
+    pub super_6: Money,
+    pub big: Money,
+    pub small: Money,
+    pub monkey_6: Money,
+    pub monkey_tie: Money,
+    pub suited_tie: Money,
+    /// Stake on a tie at each score from 0 to 9.
+    pub tie_score_bets: [Money; 10],
+}
+
+impl Default for BonusBets {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BonusBets {
     pub fn new() -> Self {
         Self {
-            player_pair: 0,
-            banker_pair: 0,
-            either_pair: 0,
-            perfect_pair: 0,
-            player_dragon: 0,
-            banker_dragon: 0,
-            lucky_6: 0,
+            player_pair: Money::ZERO,
+            banker_pair: Money::ZERO,
+            either_pair: Money::ZERO,
+            perfect_pair: Money::ZERO,
+            player_dragon: Money::ZERO,
+            banker_dragon: Money::ZERO,
+            lucky_6: Money::ZERO,
+            over: Money::ZERO,
+            under: Money::ZERO,
+            either_natural: Money::ZERO,
+            super_6: Money::ZERO,
+            big: Money::ZERO,
+            small: Money::ZERO,
+            monkey_6: Money::ZERO,
+            monkey_tie: Money::ZERO,
+            suited_tie: Money::ZERO,
+            tie_score_bets: [Money::ZERO; 10],
         }
     }
-    pub fn calculate_payouts(&self, game: &BaccaratGame) -> i32 {
-        let mut total_payout = 0;
+    /// Total returned on the winning side bets for the finished hand.
+    pub fn calculate_payouts(&self, game: &BaccaratGame) -> Money {
+        let side: Money = BonusBetType::ALL
+            .iter()
+            .map(|&bet| self.payout(bet, game))
+            .sum();
+        let tie_scores: Money = self
+            .tie_score_bets()
+            .map(|(score, _)| self.tie_score_payout(score, game))
+            .sum();
+        side + tie_scores
+    }
 
-        if self.player_pair > 0 && game.is_player_pair() {
-            total_payout += (self.player_pair as i32) * 11;
-        }
+    /// Placed specific-tie bets as (score, stake).
+    pub fn tie_score_bets(&self) -> impl Iterator<Item = (u8, Money)> + '_ {
+        (0..)
+            .zip(self.tie_score_bets)
+            .filter(|&(_, stake)| stake > Money::ZERO)
+    }
 
-        if self.banker_pair > 0 && game.is_banker_pair() {
-            total_payout += (self.banker_pair as i32) * 11;
+    /// Amount returned on the bet that the hand ties at `score`.
+    pub fn tie_score_payout(&self, score: u8, game: &BaccaratGame) -> Money {
+        let stake = self
+            .tie_score_bets
+            .get(score as usize)
+            .copied()
+            .unwrap_or(Money::ZERO);
+        if game.state.winner == 3
+            && game.state.player_score == score
+            && game.mode.offers_tie_scores()
+        {
+            Self::to_one(stake, game.payout_table.tie_score_ratio(score))
+        } else {
+            Money::ZERO
         }
+    }
 
-        if self.either_pair > 0 && game.is_either_pair() {
-            total_payout += (self.either_pair as i32) * 5;
+    /// Amount returned on one side bet for the finished hand. Bets the
+    /// mode does not offer never win.
+    pub fn payout(&self, bet_type: BonusBetType, game: &BaccaratGame) -> Money {
+        let stake = self.get(bet_type);
+        if stake == Money::ZERO || !game.mode.offers_side_bet(bet_type) {
+            return Money::ZERO;
+        }
+        let table = &game.payout_table;
+        let won = match bet_type {
+            BonusBetType::PlayerPair => game.is_player_pair(),
+            BonusBetType::BankerPair => game.is_banker_pair(),
+            BonusBetType::EitherPair => game.is_either_pair(),
+            BonusBetType::PerfectPair => game.is_perfect_pair(),
+            BonusBetType::PlayerDragon | BonusBetType::BankerDragon => true,
+            BonusBetType::Lucky6 => game.state.winner == 2 && game.state.banker_score == 6,
+            BonusBetType::Over => game.over_under.is_over(game.combined_points()),
+            BonusBetType::Under => !game.over_under.is_over(game.combined_points()),
+            BonusBetType::EitherNatural => true,
+            BonusBetType::SuperSix => game.state.winner == 2 && game.state.banker_score == 6,
+            BonusBetType::Big => game.player_hand.len() + game.banker_hand.len() >= 5,
+            BonusBetType::Small => game.player_hand.len() + game.banker_hand.len() == 4,
+            BonusBetType::MonkeySix => game.is_monkey_6(),
+            BonusBetType::MonkeyTie => game.is_monkey_tie(),
+            BonusBetType::SuitedTie => game.is_suited_tie(),
+        };
+        if !won {
+            return Money::ZERO;
         }
+        match bet_type {
+            BonusBetType::PlayerPair => Self::to_one(stake, table.player_pair),
+            BonusBetType::BankerPair => Self::to_one(stake, table.banker_pair),
+            BonusBetType::EitherPair => Self::to_one(stake, table.either_pair),
+            BonusBetType::PerfectPair => Self::to_one(stake, table.perfect_pair),
+            BonusBetType::PlayerDragon => Self::dragon_payout(stake, Side::Player, game),
+            BonusBetType::BankerDragon => Self::dragon_payout(stake, Side::Banker, game),
+            BonusBetType::Lucky6 if game.banker_hand.len() == 3 => {
+                Self::to_one(stake, table.lucky_6_three_card)
+            }
+            BonusBetType::Lucky6 => Self::to_one(stake, table.lucky_6),
+            BonusBetType::Over => stake.percent(game.over_under.over_return_pct as i64),
+            BonusBetType::Under => stake.percent(game.over_under.under_return_pct as i64),
+            BonusBetType::EitherNatural => Self::to_one(stake, Self::natural_ratio(game)),
+            BonusBetType::SuperSix => Self::to_one(stake, table.super_6),
+            BonusBetType::Big => stake.percent(table.big_pct as i64),
+            BonusBetType::Small => stake.percent(table.small_pct as i64),
+            BonusBetType::MonkeySix => Self::to_one(stake, table.monkey_6),
+            BonusBetType::MonkeyTie => Self::to_one(stake, table.monkey_tie),
+            BonusBetType::SuitedTie => Self::to_one(stake, table.suited_tie),
+        }
+    }
 
-        if self.perfect_pair > 0 && game.is_perfect_pair() {
-            total_payout += (self.perfect_pair as i32) * 25;
+    /// Return on a winning side bet paying `ratio` to 1: the winnings plus
+    /// the stake back, or nothing where the table pays 0.
+    fn to_one(stake: Money, ratio: i32) -> Money {
+        match ratio {
+            0 => Money::ZERO,
+            ratio => stake + stake * ratio as i64,
         }
+    }
 
-        if self.player_dragon > 0 && game.state.winner == 1 {
-            let margin = game.victory_margin();
-            let payout_ratio = match margin {
-                9 => 30,
-                8 => 10,
-                7 => 6,
-                6 => 4,
-                5 => 2,
-                4 => 1,
-                _ => 0,
-            };
-            if payout_ratio > 0 {
-                total_payout += (self.player_dragon as i32) * payout_ratio;
-            }
+    /// Dragon Bonus on `side`: a natural win pays the natural rate whatever
+    /// the margin, a natural tie pushes, and any other win pays by margin
+    /// from 4 points up.
+    fn dragon_payout(stake: Money, side: Side, game: &BaccaratGame) -> Money {
+        let (natural, winner) = match side {
+            Side::Player => (game.player_natural(), 1),
+            Side::Banker => (game.banker_natural(), 2),
+        };
+        let table = &game.payout_table;
+        match game.state.winner {
+            3 if natural.is_some() => stake,
+            w if w == winner && natural.is_some() => Self::to_one(stake, table.dragon_natural),
+            w if w == winner => Self::to_one(stake, table.dragon_ratio(game.victory_margin())),
+            _ => Money::ZERO,
         }
+    }
 
-        if self.banker_dragon > 0 && game.state.winner == 2 {
-            let margin = game.victory_margin();
-            let payout_ratio = match margin {
-                9 => 30,
-                8 => 10,
-                7 => 6,
-                6 => 4,
-                5 => 2,
-                4 => 1,
-                _ => 0,
-            };
-            if payout_ratio > 0 {
-                total_payout += (self.banker_dragon as i32) * payout_ratio;
-            }
+    /// Either-hand natural: both hands natural pays best, then a natural 9,
+    /// then a natural 8.
+    fn natural_ratio(game: &BaccaratGame) -> i32 {
+        match (game.player_natural(), game.banker_natural()) {
+            (Some(_), Some(_)) => game.payout_table.natural_both,
+            (Some(9), None) | (None, Some(9)) => game.payout_table.natural_9,
+            (Some(_), None) | (None, Some(_)) => game.payout_table.natural_8,
+            (None, None) => 0,
         }
+    }
 
-        if self.lucky_6 > 0 && game.state.winner == 2 && game.state.banker_score == 6 {
-            let payout_ratio = if game.banker_hand.len() == 3 { 20 } else { 12 };
-            total_payout += (self.lucky_6 as i32) * payout_ratio;
+    /// Sets the stake on `bet_type`.
+    pub fn set(&mut self, bet_type: BonusBetType, amount: Money) {
+        *self.stake_mut(bet_type) = amount;
+    }
+
+    /// The stake on `bet_type`.
+    pub fn get(&self, bet_type: BonusBetType) -> Money {
+        match bet_type {
+            BonusBetType::PlayerPair => self.player_pair,
+            BonusBetType::BankerPair => self.banker_pair,
+            BonusBetType::EitherPair => self.either_pair,
+            BonusBetType::PerfectPair => self.perfect_pair,
+            BonusBetType::PlayerDragon => self.player_dragon,
+            BonusBetType::BankerDragon => self.banker_dragon,
+            BonusBetType::Lucky6 => self.lucky_6,
+            BonusBetType::Over => self.over,
+            BonusBetType::Under => self.under,
+            BonusBetType::EitherNatural => self.either_natural,
+            BonusBetType::SuperSix => self.super_6,
+            BonusBetType::Big => self.big,
+            BonusBetType::Small => self.small,
+            BonusBetType::MonkeySix => self.monkey_6,
+            BonusBetType::MonkeyTie => self.monkey_tie,
+            BonusBetType::SuitedTie => self.suited_tie,
         }
+    }
 
-        total_payout
+    fn stake_mut(&mut self, bet_type: BonusBetType) -> &mut Money {
+        match bet_type {
+            BonusBetType::PlayerPair => &mut self.player_pair,
+            BonusBetType::BankerPair => &mut self.banker_pair,
+            BonusBetType::EitherPair => &mut self.either_pair,
+            BonusBetType::PerfectPair => &mut self.perfect_pair,
+            BonusBetType::PlayerDragon => &mut self.player_dragon,
+            BonusBetType::BankerDragon => &mut self.banker_dragon,
+            BonusBetType::Lucky6 => &mut self.lucky_6,
+            BonusBetType::Over => &mut self.over,
+            BonusBetType::Under => &mut self.under,
+            BonusBetType::EitherNatural => &mut self.either_natural,
+            BonusBetType::SuperSix => &mut self.super_6,
+            BonusBetType::Big => &mut self.big,
+            BonusBetType::Small => &mut self.small,
+            BonusBetType::MonkeySix => &mut self.monkey_6,
+            BonusBetType::MonkeyTie => &mut self.monkey_tie,
+            BonusBetType::SuitedTie => &mut self.suited_tie,
+        }
     }
 
-    pub fn total_bet(&self) -> i32 {
-        (self.player_pair
-            + self.banker_pair
-            + self.either_pair
-            + self.perfect_pair
-            + self.player_dragon
-            + self.banker_dragon
-            + self.lucky_6) as i32
+    /// Sum of every side bet stake.
+    pub fn total_bet(&self) -> Money {
+        BonusBetType::ALL
+            .iter()
+            .map(|&bet| self.get(bet))
+            .chain(self.tie_score_bets)
+            .sum()
     }
 }
 
-pub struct BettingRound {
-    pub main_bet_type: String,
-    pub main_bet_amount: i32,
+/// Every bet one player has riding on a hand: any of the main bets at once,
+/// as real layouts allow, plus side bets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct BetSlip {
+    /// Stake on each main bet, in `BetType::ALL` order; zero means not
+    /// placed.
+    main_bets: [Money; BetType::ALL.len()],
     pub bonus_bets: BonusBets,
-    pub balance: i32,
-    pub round_stats: RoundStatistics,
 }
 
+impl BetSlip {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One main bet plus side bets, as the tables take them.
+    pub fn single(bet_type: BetType, amount: Money, bonus_bets: BonusBets) -> Self {
+        let mut slip = Self {
+            bonus_bets,
+            ..Self::default()
+        };
+        slip.set_main_bet(bet_type, amount);
+        slip
+    }
+
+    pub fn main_bet(&self, bet_type: BetType) -> Money {
+        self.main_bets[bet_type as usize]
+    }
+
+    /// Sets the stake on `bet_type`; zero takes the bet down.
+    pub fn set_main_bet(&mut self, bet_type: BetType, amount: Money) {
+        self.main_bets[bet_type as usize] = amount;
+    }
+
+    /// Main bets placed, in `BetType::ALL` order.
+    pub fn main_bets(&self) -> impl Iterator<Item = (BetType, Money)> + '_ {
+        BetType::ALL
+            .into_iter()
+            .map(|bet| (bet, self.main_bet(bet)))
+            .filter(|&(_, amount)| amount > Money::ZERO)
+    }
+
+    pub fn total_main_bet(&self) -> Money {
+        self.main_bets.iter().copied().sum()
+    }
+
+    /// Every bet a slip holds a stake for: the main bets, the side bets,
+    /// then the ties on each score.
+    pub fn wagers() -> impl Iterator<Item = Wager> {
+        let main = BetType::ALL.into_iter().map(Wager::Main);
+        let side = BonusBetType::ALL.into_iter().map(Wager::Side);
+        main.chain(side).chain((0..10).map(Wager::TieScore))
+    }
+
+    /// Stake on `wager`; zero for bets from other games.
+    pub fn stake(&self, wager: Wager) -> Money {
+        match wager {
+            Wager::Main(bet) => self.main_bet(bet),
+            Wager::Side(bet) => self.bonus_bets.get(bet),
+            Wager::TieScore(score) => self
+                .bonus_bets
+                .tie_score_bets
+                .get(score as usize)
+                .copied()
+                .unwrap_or(Money::ZERO),
+            _ => Money::ZERO,
+        }
+    }
+
+    /// Sets the stake on `wager`; `None` for a bet the slip can't hold.
+    pub fn set_stake(&mut self, wager: Wager, stake: Money) -> Option<()> {
+        match wager {
+            Wager::Main(bet) => self.set_main_bet(bet, stake),
+            Wager::Side(bet) => self.bonus_bets.set(bet, stake),
+            Wager::TieScore(score) => {
+                *self.bonus_bets.tie_score_bets.get_mut(score as usize)? = stake
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    pub fn total_bet(&self) -> Money {
+        self.total_main_bet() + self.bonus_bets.total_bet()
+    }
+}
+
+/// Running totals across the rounds a `GameSession` has settled.
 pub struct RoundStatistics {
     pub hands_played: u32,
-    pub amount_wagered: i32,
-    pub amount_won: i32,
-    pub bonus_hits: HashMap<String, u32>,
+    pub amount_wagered: Money,
+    pub amount_won: Money,
+    pub bonus_hits: HashMap<BonusBetType, u32>,
+    /// Money through each kind of bet, in the order they were first placed.
+    pub by_bet: Vec<(Wager, BetPerformance)>,
+}
+
+/// What one kind of bet has taken and given back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BetPerformance {
+    /// Hands it was on.
+    pub bets: u32,
+    pub wagered: Money,
+    /// Amount returned, stakes included.
+    pub won: Money,
+}
+
+impl BetPerformance {
+    pub fn net(&self) -> Money {
+        self.won - self.wagered
+    }
+
+    /// Net result per amount wagered, e.g. -0.0124 for a bet losing 1.24%.
+    pub fn roi(&self) -> Option<f64> {
+        (self.wagered > Money::ZERO).then(|| self.net().as_dollars() / self.wagered.as_dollars())
+    }
+}
+
+impl Default for RoundStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RoundStatistics {
     pub fn new() -> Self {
         Self {
             hands_played: 0,
-            amount_wagered: 0,
-            amount_won: 0,
+            amount_wagered: Money::ZERO,
+            amount_won: Money::ZERO,
             bonus_hits: HashMap::new(),
+            by_bet: Vec::new(),
+        }
+    }
+
+    /// Adds a settled hand to the totals, overall and bet by bet.
+    pub fn record_settlement(&mut self, report: &SettlementReport) {
+        self.hands_played += 1;
+        self.amount_wagered += report.total_bet();
+        self.amount_won += report.total_payout();
+        for bet in &report.bets {
+            let index = match self
+                .by_bet
+                .iter()
+                .position(|(wager, _)| *wager == bet.wager)
+            {
+                Some(index) => index,
+                None => {
+                    self.by_bet.push((bet.wager, BetPerformance::default()));
+                    self.by_bet.len() - 1
+                }
+            };
+            let performance = &mut self.by_bet[index].1;
+            performance.bets += 1;
+            performance.wagered += bet.stake;
+            performance.won += bet.payout;
+            if let (Wager::Side(side), BetOutcome::Win) = (bet.wager, bet.outcome) {
+                self.record_bonus_hit(side);
+            }
         }
     }
-    
-    pub fn record_bonus_hit(&mut self, bonus_type: &str) {
-        *self.bonus_hits.entry(bonus_type.to_string()).or_insert(0) += 1;
+
+    pub fn record_bonus_hit(&mut self, bonus_type: BonusBetType) {
+        *self.bonus_hits.entry(bonus_type).or_insert(0) += 1;
     }
 }
 
-impl BettingRound {
-    pub fn new(balance: i32) -> Self {
+/// One player's place at an interactive table: bankroll, the bet slip for
+/// the next hand, running totals, and the last settlement. Frontends keep
+/// one per player, forward bet input to it, and hand it each finished hand.
+pub struct GameSession {
+    pub balance: Money,
+    /// Bets laid out for the next hand: any of the main bets at once, plus
+    /// side bets.
+    pub slip: BetSlip,
+    /// The main bet chip steps go on; the slip may hold others alongside.
+    pub selected_bet: BetType,
+    pub rounds_played: u32,
+    pub total_wagered: Money,
+    pub total_won: Money,
+    /// Wagered and won bet by bet.
+    pub round_stats: RoundStatistics,
+    /// Last hand bet by bet, or `None` if the player sat it out.
+    pub last_settlement: Option<SettlementReport>,
+    /// Bets from a hand whose player and banker bets were barged by a tie;
+    /// those two go back on the next deal as they were.
+    riding: Option<BetSlip>,
+    /// Bets locked in for the hand being dealt; `None` when sitting out.
+    in_play: Option<BetSlip>,
+}
+
+impl GameSession {
+    pub fn new(balance: Money) -> Self {
         Self {
-            main_bet_type: String::new(),
-            main_bet_amount: 0,
-            bonus_bets: BonusBets::new(),
             balance,
+            slip: BetSlip::new(),
+            selected_bet: BetType::Player,
+            rounds_played: 0,
+            total_wagered: Money::ZERO,
+            total_won: Money::ZERO,
             round_stats: RoundStatistics::new(),
+            last_settlement: None,
+            riding: None,
+            in_play: None,
         }
     }
-    
-    pub fn place_main_bet(&mut self, bet_type: &str, amount: i32) -> Result<(), &str> {
-        if amount > self.balance {
-            return Err("Insufficient balance");
+
+    pub fn total_bet(&self) -> Money {
+        self.slip.total_bet()
+    }
+
+    /// Stake on one main bet, in whole dollars.
+    pub fn main_bet(&self, bet_type: BetType) -> i32 {
+        self.slip.main_bet(bet_type).whole_dollars() as i32
+    }
+
+    /// Places `amount` on a main bet alongside any others already down,
+    /// if the balance covers them all; zero takes the bet down. A player
+    /// or banker bet riding from a tie can't be changed.
+    pub fn place_main_bet(&mut self, bet_type: BetType, amount: Money) -> Result<(), String> {
+        if amount < Money::ZERO {
+            return Err("Bet amount can't be negative".to_string());
+        }
+        if self.main_bet_rides() && matches!(bet_type, BetType::Player | BetType::Banker) {
+            return Err(format!("The {} bet rides from the tie", bet_type));
         }
-        
-        if amount <= 0 {
-            return Err("Bet amount must be positive");
+        let total = self.slip.total_bet() - self.slip.main_bet(bet_type) + amount;
+        if total > self.balance {
+            return Err(format!(
+                "${} in bets is more than the ${} balance",
+                total, self.balance
+            ));
         }
-        
-        if !["player", "banker", "tie"].contains(&bet_type) {
-            return Err("Invalid bet type");
+        self.slip.set_main_bet(bet_type, amount);
+        Ok(())
+    }
+
+    /// Places `amount` on a side bet, if the balance covers it on top of the
+    /// other bets; zero takes it down.
+    pub fn place_bonus_bet(&mut self, bet_type: BonusBetType, amount: Money) -> Result<(), String> {
+        if amount < Money::ZERO {
+            return Err("Bet amount can't be negative".to_string());
+        }
+        let total = self.slip.total_bet() - self.slip.bonus_bets.get(bet_type) + amount;
+        if total > self.balance {
+            return Err(format!(
+                "${} in bets is more than the ${} balance",
+                total, self.balance
+            ));
         }
-        
-        self.main_bet_type = bet_type.to_string();
-        self.main_bet_amount = amount;
+        self.slip.bonus_bets.set(bet_type, amount);
         Ok(())
     }
-    
-    pub fn place_bonus_bet(&mut self, bet_type: &str, amount: u8) -> Result<(), &str> {
-        let total_bet = self.main_bet_amount + self.bonus_bets.total_bet() + amount as i32;
-        
-        if total_bet > self.balance {
-            return Err("Insufficient balance for bonus bet");
+
+    /// Makes `bet_type` the bet chip steps go on, putting a first `chip`
+    /// down on it if it has nothing yet.
+    pub fn select_main_bet(&mut self, bet_type: BetType, chip: i32, limits: &TableLimits) {
+        self.selected_bet = bet_type;
+        if self.slip.main_bet(bet_type) == Money::ZERO {
+            self.slip
+                .set_main_bet(bet_type, Money::from_dollars(limits.clamp(chip)));
         }
-        
-        match bet_type {
-            "player_pair" => self.bonus_bets.player_pair = amount,
-            "banker_pair" => self.bonus_bets.banker_pair = amount,
-            "either_pair" => self.bonus_bets.either_pair = amount,
-            "perfect_pair" => self.bonus_bets.perfect_pair = amount,
-            "player_dragon" => self.bonus_bets.player_dragon = amount,
-            "banker_dragon" => self.bonus_bets.banker_dragon = amount,
-            "lucky_6" => self.bonus_bets.lucky_6 = amount,
-            _ => return Err("Invalid bonus bet type"),
-        }
-        
+    }
+
+    /// Adds or takes off one `chip` on `bet_type`, staying within the table
+    /// limits; taking a chip off a bet at the minimum takes the bet down.
+    pub fn step_main_bet(
+        &mut self,
+        bet_type: BetType,
+        chip: i32,
+        increase: bool,
+        limits: &TableLimits,
+    ) {
+        let current = self.main_bet(bet_type);
+        let amount = match current {
+            0 if !increase => 0,
+            current if !increase && current <= limits.min_bet => 0,
+            current => limits.step(current, chip, increase),
+        };
+        self.selected_bet = bet_type;
+        self.slip
+            .set_main_bet(bet_type, Money::from_dollars(amount));
+    }
+
+    /// Sets the selected main bet to one `chip`, within the table limits,
+    /// as picking up a chip does.
+    pub fn stake_selected_bet(&mut self, chip: i32, limits: &TableLimits) {
+        self.slip
+            .set_main_bet(self.selected_bet, Money::from_dollars(limits.clamp(chip)));
+    }
+
+    /// Replaces every main bet with `amount` on `bet_type`, keeping the
+    /// side bets, as a strategy calling one bet a hand lays it out.
+    pub fn bet_only(&mut self, bet_type: BetType, amount: Money) {
+        self.slip = BetSlip::single(bet_type, amount, self.slip.bonus_bets);
+        self.selected_bet = bet_type;
+    }
+
+    /// Why the player can't be dealt in: no main bet, a layout outside the
+    /// table limits, or bets the balance can't cover. `None` if they play.
+    pub fn sit_out_reason(&self, game: &BaccaratGame) -> Option<String> {
+        if let Err(violations) = game.validate_bet_slip(&self.slip) {
+            let reasons: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            return Some(reasons.join(", "));
+        }
+        (self.total_bet() > self.balance).then(|| {
+            format!(
+                "${} in bets is more than the ${} balance",
+                self.total_bet(),
+                self.balance
+            )
+        })
+    }
+
+    /// Locks in the slip for the next hand, first putting back the player
+    /// and banker bets barged by a tie. Returns why the player sits the
+    /// hand out instead.
+    pub fn lock_bets(&mut self, game: &BaccaratGame) -> Result<(), String> {
+        if let Some(riding) = self.riding.take() {
+            for bet in [BetType::Player, BetType::Banker] {
+                self.slip.set_main_bet(bet, riding.main_bet(bet));
+            }
+        }
+        self.in_play = None;
+        if let Some(reason) = self.sit_out_reason(game) {
+            return Err(reason);
+        }
+        self.in_play = Some(self.slip);
         Ok(())
     }
-    
-    pub fn settle_round(&mut self, game: &BaccaratGame) -> i32 {
-        let total_bet = self.main_bet_amount + self.bonus_bets.total_bet();
-        let payout = game.total_payout(&self.main_bet_type, self.main_bet_amount);
-        
+
+    /// Settles the locked bets on the finished hand and updates the
+    /// bankroll and totals; `None` if the player sat the hand out.
+    pub fn settle(&mut self, game: &BaccaratGame) -> Option<&SettlementReport> {
+        let Some(slip) = self.in_play.take() else {
+            self.last_settlement = None;
+            return None;
+        };
+        let report = game.settle(&slip);
+        let total_bet = report.total_bet();
+        let payout = report.total_payout();
+        self.rounds_played += 1;
+        self.total_wagered += total_bet;
+        self.total_won += payout;
         self.balance = self.balance - total_bet + payout;
-        self.round_stats.hands_played += 1;
-        self.round_stats.amount_wagered += total_bet;
-        self.round_stats.amount_won += payout;
-        
-        if game.is_player_pair() && self.bonus_bets.player_pair > 0 {
-            self.round_stats.record_bonus_hit("player_pair");
-        }
-        if game.is_banker_pair() && self.bonus_bets.banker_pair > 0 {
-            self.round_stats.record_bonus_hit("banker_pair");
-        }
-        
-        payout
+        self.riding = report
+            .bets
+            .iter()
+            .any(|bet| bet.outcome == BetOutcome::Rides)
+            .then_some(slip);
+        self.round_stats.record_settlement(&report);
+        self.last_settlement = Some(report);
+        self.last_settlement.as_ref()
+    }
+
+    /// Whether the player and banker bets were barged by a tie and ride on
+    /// the next hand.
+    pub fn main_bet_rides(&self) -> bool {
+        self.riding.is_some()
+    }
+
+    pub fn last_net(&self) -> Option<Money> {
+        self.last_settlement.as_ref().map(SettlementReport::net)
+    }
+
+    /// Amount returned per 100 wagered so far.
+    pub fn win_rate(&self) -> f32 {
+        if self.total_wagered == Money::ZERO {
+            0.0
+        } else {
+            (self.total_won.as_dollars() / self.total_wagered.as_dollars()) as f32 * 100.0
+        }
     }
 }
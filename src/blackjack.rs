@@ -0,0 +1,513 @@
+//! Blackjack, dealt from the same cards and shoe as the baccarat tables. One
+//! player box against the dealer: hit, stand, double, split and late
+//! surrender, insurance when the dealer shows an ace, and a dealer who peeks
+//! for blackjack before anyone acts. Stakes are tracked here; the caller
+//! takes them from the bankroll and pays back what `returned` says.
+
+use crate::baccarat::{Card, Money, Shoe};
+
+/// Decks in the blackjack shoe.
+pub const BLACKJACK_DECKS: usize = 6;
+
+/// House rules that vary from table to table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlackjackRules {
+    /// The dealer draws to a soft 17 (H17) instead of standing on it (S17).
+    pub dealer_hits_soft_17: bool,
+    /// Late surrender: half the stake back for folding the first two cards.
+    pub surrender: bool,
+    /// Split hands may double down.
+    pub double_after_split: bool,
+    /// Most hands a box can be split into.
+    pub max_hands: usize,
+}
+
+impl Default for BlackjackRules {
+    fn default() -> Self {
+        Self {
+            dealer_hits_soft_17: true,
+            surrender: true,
+            double_after_split: true,
+            max_hands: 4,
+        }
+    }
+}
+
+impl BlackjackRules {
+    /// Whether the dealer takes another card on `hand`.
+    pub fn dealer_hits(&self, hand: &Hand) -> bool {
+        match hand.total() {
+            17 => hand.is_soft() && self.dealer_hits_soft_17,
+            total => total < 17,
+        }
+    }
+}
+
+/// Point value: aces count one here (`Hand::total` decides when they count
+/// eleven), tens and face cards ten.
+pub fn card_value(card: &Card) -> u8 {
+    card.rank.min(10)
+}
+
+/// A blackjack hand, the player's or the dealer's.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total with every ace counted as one.
+    pub fn hard_total(&self) -> u8 {
+        self.cards.iter().map(card_value).sum()
+    }
+
+    /// An ace is counting eleven without busting the hand.
+    pub fn is_soft(&self) -> bool {
+        self.cards.iter().any(|card| card.rank == 1) && self.hard_total() <= 11
+    }
+
+    /// The best total: the hard total, plus ten for an ace when it fits.
+    pub fn total(&self) -> u8 {
+        if self.is_soft() {
+            self.hard_total() + 10
+        } else {
+            self.hard_total()
+        }
+    }
+
+    /// 21 on the first two cards.
+    pub fn is_blackjack(&self) -> bool {
+        self.cards.len() == 2 && self.total() == 21
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.hard_total() > 21
+    }
+
+    /// Two cards of the same value, which may be split.
+    pub fn is_pair(&self) -> bool {
+        matches!(&self.cards[..], [first, second] if card_value(first) == card_value(second))
+    }
+
+    pub fn push(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+}
+
+impl std::ops::Deref for Hand {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+/// How a player hand came out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// A two-card 21 against a dealer without one; pays 3:2.
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    /// Folded for half the stake back.
+    Surrender,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Outcome::Blackjack => "Blackjack",
+            Outcome::Win => "Win",
+            Outcome::Push => "Push",
+            Outcome::Lose => "Lose",
+            Outcome::Surrender => "Surrender",
+        })
+    }
+}
+
+/// One of the player's hands with its stake; splitting makes more.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerHand {
+    pub hand: Hand,
+    pub stake: Money,
+    pub doubled: bool,
+    /// Made by a split, so a two-card 21 is not a blackjack.
+    pub split: bool,
+    pub surrendered: bool,
+    /// No more cards or decisions for this hand.
+    pub finished: bool,
+}
+
+impl PlayerHand {
+    fn new(stake: Money) -> Self {
+        Self {
+            hand: Hand::new(),
+            stake,
+            doubled: false,
+            split: false,
+            surrendered: false,
+            finished: false,
+        }
+    }
+
+    pub fn is_blackjack(&self) -> bool {
+        !self.split && self.hand.is_blackjack()
+    }
+
+    pub fn outcome(&self, dealer: &Hand) -> Outcome {
+        if self.surrendered {
+            return Outcome::Surrender;
+        }
+        match (self.is_blackjack(), dealer.is_blackjack()) {
+            (true, true) => return Outcome::Push,
+            (true, false) => return Outcome::Blackjack,
+            (false, true) => return Outcome::Lose,
+            (false, false) => {}
+        }
+        if self.hand.is_bust() {
+            return Outcome::Lose;
+        }
+        if dealer.is_bust() {
+            return Outcome::Win;
+        }
+        match self.hand.total().cmp(&dealer.total()) {
+            std::cmp::Ordering::Greater => Outcome::Win,
+            std::cmp::Ordering::Equal => Outcome::Push,
+            std::cmp::Ordering::Less => Outcome::Lose,
+        }
+    }
+
+    /// What the hand pays back, stake included.
+    pub fn returned(&self, dealer: &Hand) -> Money {
+        match self.outcome(dealer) {
+            Outcome::Blackjack => self.stake.percent(250),
+            Outcome::Win => self.stake * 2,
+            Outcome::Push => self.stake,
+            Outcome::Lose => Money::ZERO,
+            Outcome::Surrender => self.stake.percent(50),
+        }
+    }
+}
+
+/// A player decision on the hand in play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Hit,
+    Stand,
+    /// Double the stake, take one card, and stand.
+    Double,
+    /// Split a pair into two hands, staking the same again on the second.
+    Split,
+    Surrender,
+}
+
+impl Action {
+    pub const ALL: [Action; 5] = [
+        Action::Hit,
+        Action::Stand,
+        Action::Double,
+        Action::Split,
+        Action::Surrender,
+    ];
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Action::Hit => "Hit",
+            Action::Stand => "Stand",
+            Action::Double => "Double",
+            Action::Split => "Split",
+            Action::Surrender => "Surrender",
+        })
+    }
+}
+
+/// Where the round stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Waiting for a stake and the deal.
+    Betting,
+    /// The dealer shows an ace and asks for insurance before peeking.
+    Insurance,
+    /// The player acts on `BlackjackGame::active`.
+    PlayerTurn,
+    /// The dealer has played and every hand is paid.
+    Settled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackError {
+    /// The shoe ran out of cards mid-round.
+    ShoeEmpty,
+    /// A round is already being dealt.
+    RoundInProgress,
+    /// Insurance is only offered while the dealer's ace is showing.
+    NoInsurance,
+    /// The action is not open to the hand in play.
+    NotAllowed(Action),
+}
+
+impl std::fmt::Display for BlackjackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BlackjackError::ShoeEmpty => write!(f, "the shoe ran out of cards mid-round"),
+            BlackjackError::RoundInProgress => write!(f, "a round is already in progress"),
+            BlackjackError::NoInsurance => write!(f, "insurance is not on offer"),
+            BlackjackError::NotAllowed(action) => {
+                write!(f, "{} is not allowed on this hand", action)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BlackjackError {}
+
+/// A blackjack table: the shoe, the dealer, and the player's hands.
+pub struct BlackjackGame {
+    pub rules: BlackjackRules,
+    shoe: Shoe,
+    pub hands: Vec<PlayerHand>,
+    /// Index into `hands` of the hand being played.
+    pub active: usize,
+    pub dealer: Hand,
+    /// Insurance taken, half the original stake, or zero.
+    pub insurance: Money,
+    pub phase: Phase,
+}
+
+impl BlackjackGame {
+    pub fn new(rules: BlackjackRules, num_decks: usize) -> Self {
+        Self::with_shoe(rules, Shoe::new(num_decks))
+    }
+
+    /// A table dealing from `shoe`, such as a seeded or stacked one.
+    pub fn with_shoe(rules: BlackjackRules, shoe: Shoe) -> Self {
+        Self {
+            rules,
+            shoe,
+            hands: Vec::new(),
+            active: 0,
+            dealer: Hand::new(),
+            insurance: Money::ZERO,
+            phase: Phase::Betting,
+        }
+    }
+
+    fn draw(&mut self) -> Result<Card, BlackjackError> {
+        self.shoe.deal().ok_or(BlackjackError::ShoeEmpty)
+    }
+
+    /// The dealer's face-up card.
+    pub fn upcard(&self) -> Option<Card> {
+        self.dealer.first().copied()
+    }
+
+    /// A round is dealt and waiting on the player.
+    pub fn in_progress(&self) -> bool {
+        matches!(self.phase, Phase::Insurance | Phase::PlayerTurn)
+    }
+
+    /// Whether the hole card is face up yet.
+    pub fn hole_card_shown(&self) -> bool {
+        self.phase == Phase::Settled
+    }
+
+    /// Deals a new round on `stake`, reshuffling first once the cut card
+    /// is out. A dealer ace stops for insurance; otherwise the dealer peeks
+    /// and a blackjack on either side settles the round at once.
+    pub fn deal(&mut self, stake: Money) -> Result<(), BlackjackError> {
+        if self.in_progress() {
+            return Err(BlackjackError::RoundInProgress);
+        }
+        self.phase = Phase::Betting;
+        if self.shoe.needs_reshuffle() {
+            self.shoe.reshuffle();
+        }
+        self.hands = vec![PlayerHand::new(stake)];
+        self.active = 0;
+        self.dealer = Hand::new();
+        self.insurance = Money::ZERO;
+        for _ in 0..2 {
+            let card = self.draw()?;
+            self.hands[0].hand.push(card);
+            let card = self.draw()?;
+            self.dealer.push(card);
+        }
+        if self.upcard().is_some_and(|card| card.rank == 1) {
+            self.phase = Phase::Insurance;
+            Ok(())
+        } else {
+            self.peek()
+        }
+    }
+
+    /// What insurance would cost: half the original stake.
+    pub fn insurance_cost(&self) -> Money {
+        self.hands
+            .first()
+            .map_or(Money::ZERO, |hand| hand.stake.percent(50))
+    }
+
+    /// Takes or declines insurance, then the dealer peeks.
+    pub fn insure(&mut self, take: bool) -> Result<(), BlackjackError> {
+        if self.phase != Phase::Insurance {
+            return Err(BlackjackError::NoInsurance);
+        }
+        if take {
+            self.insurance = self.insurance_cost();
+        }
+        self.peek()
+    }
+
+    fn peek(&mut self) -> Result<(), BlackjackError> {
+        if self.dealer.is_blackjack() || self.hands[0].is_blackjack() {
+            self.hands[0].finished = true;
+            self.phase = Phase::Settled;
+            return Ok(());
+        }
+        self.phase = Phase::PlayerTurn;
+        Ok(())
+    }
+
+    /// Whether `action` is open to the hand in play.
+    pub fn can(&self, action: Action) -> bool {
+        if self.phase != Phase::PlayerTurn {
+            return false;
+        }
+        let Some(current) = self.hands.get(self.active) else {
+            return false;
+        };
+        let first_decision = current.hand.len() == 2;
+        match action {
+            Action::Hit | Action::Stand => true,
+            Action::Double => first_decision && (!current.split || self.rules.double_after_split),
+            Action::Split => current.hand.is_pair() && self.hands.len() < self.rules.max_hands,
+            Action::Surrender => self.rules.surrender && first_decision && self.hands.len() == 1,
+        }
+    }
+
+    pub fn available_actions(&self) -> Vec<Action> {
+        Action::ALL
+            .into_iter()
+            .filter(|&action| self.can(action))
+            .collect()
+    }
+
+    /// What `action` adds to the stakes on the table: the hand's stake
+    /// again to double or split, nothing otherwise.
+    pub fn cost(&self, action: Action) -> Money {
+        match action {
+            Action::Double | Action::Split => self
+                .hands
+                .get(self.active)
+                .map_or(Money::ZERO, |hand| hand.stake),
+            _ => Money::ZERO,
+        }
+    }
+
+    /// Plays `action` on the hand in play. When the last hand is finished
+    /// the dealer plays out and the round settles.
+    pub fn act(&mut self, action: Action) -> Result<(), BlackjackError> {
+        if !self.can(action) {
+            return Err(BlackjackError::NotAllowed(action));
+        }
+        let index = self.active;
+        match action {
+            Action::Hit => {
+                let card = self.draw()?;
+                let current = &mut self.hands[index];
+                current.hand.push(card);
+                current.finished = current.hand.total() >= 21;
+            }
+            Action::Stand => self.hands[index].finished = true,
+            Action::Double => {
+                let card = self.draw()?;
+                let current = &mut self.hands[index];
+                current.stake = current.stake * 2;
+                current.doubled = true;
+                current.hand.push(card);
+                current.finished = true;
+            }
+            Action::Split => {
+                let first = self.hands[index].hand[0];
+                let second = self.hands[index].hand[1];
+                let aces = first.rank == 1;
+                let stake = self.hands[index].stake;
+                for (offset, card) in [first, second].into_iter().enumerate() {
+                    let mut split = PlayerHand::new(stake);
+                    split.split = true;
+                    split.hand.push(card);
+                    split.hand.push(self.draw()?);
+                    // Split aces get one card each.
+                    split.finished = aces || split.hand.total() == 21;
+                    if offset == 0 {
+                        self.hands[index] = split;
+                    } else {
+                        self.hands.insert(index + 1, split);
+                    }
+                }
+            }
+            Action::Surrender => {
+                let current = &mut self.hands[index];
+                current.surrendered = true;
+                current.finished = true;
+            }
+        }
+        self.advance()
+    }
+
+    /// Moves to the next unfinished hand, or plays the dealer's hand and
+    /// settles once none are left.
+    fn advance(&mut self) -> Result<(), BlackjackError> {
+        while self
+            .hands
+            .get(self.active)
+            .is_some_and(|hand| hand.finished)
+        {
+            self.active += 1;
+        }
+        if self.active < self.hands.len() {
+            return Ok(());
+        }
+        self.active = self.hands.len() - 1;
+        let live = self
+            .hands
+            .iter()
+            .any(|hand| !hand.surrendered && !hand.hand.is_bust());
+        while live && self.rules.dealer_hits(&self.dealer) {
+            let card = self.draw()?;
+            self.dealer.push(card);
+        }
+        self.phase = Phase::Settled;
+        Ok(())
+    }
+
+    /// Every stake on the table, insurance included.
+    pub fn wagered(&self) -> Money {
+        self.hands.iter().map(|hand| hand.stake).sum::<Money>() + self.insurance
+    }
+
+    /// What the settled round pays back, stakes included; zero until it is
+    /// settled. Insurance pays 2:1 when the dealer has blackjack.
+    pub fn returned(&self) -> Money {
+        if self.phase != Phase::Settled {
+            return Money::ZERO;
+        }
+        let insurance = if self.dealer.is_blackjack() {
+            self.insurance * 3
+        } else {
+            Money::ZERO
+        };
+        self.hands
+            .iter()
+            .map(|hand| hand.returned(&self.dealer))
+            .sum::<Money>()
+            + insurance
+    }
+}
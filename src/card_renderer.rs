@@ -1,18 +1,78 @@
-use crate::baccarat::{Card, HEARTS, DIAMONDS, CLUBS, SPADES};
+//! Card art for ratatui front ends: full, fanned, face-down, and mini
+//! cards, whole hands laid out to fit a panel, and a spinning roulette wheel.
+
+use crate::baccarat::{BaccaratGame, CLUBS, Card, DIAMONDS, HEARTS, SPADES, Side};
+use crate::blackjack;
+use crate::roulette::{self, PocketColor, Wheel};
+use crate::theme::Theme;
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
 };
+use std::time::Duration;
+
+/// Columns taken by the card art from `render_card`.
+const CARD_WIDTH: usize = 11;
+const CARD_HEIGHT: usize = 7;
+/// Narrowest sliver of an overlapped card that still shows its rank and suit
+/// corner ("│ 10").
+const MIN_CARD_SLIVER: usize = 4;
+
+/// What a hand is worth, as shown beside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandScore {
+    Points(u8),
+    /// Three court cards at Three Card Baccarat, which beats any total.
+    ThreeFaces,
+    /// A blackjack total with an ace counting eleven.
+    Soft(u8),
+    /// A label in place of a score, such as a poker hand's category.
+    Named(&'static str),
+}
+
+impl HandScore {
+    /// `side`'s hand at the table as it stands.
+    pub fn of(game: &BaccaratGame, side: Side) -> Self {
+        if game.is_three_faces(side) {
+            return HandScore::ThreeFaces;
+        }
+        match side {
+            Side::Player => HandScore::Points(game.state.player_score),
+            Side::Banker => HandScore::Points(game.state.banker_score),
+        }
+    }
+
+    /// A blackjack hand's best total.
+    pub fn of_blackjack(hand: &blackjack::Hand) -> Self {
+        if hand.is_soft() {
+            HandScore::Soft(hand.total())
+        } else {
+            HandScore::Points(hand.total())
+        }
+    }
+}
+
+impl std::fmt::Display for HandScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HandScore::Points(points) => write!(f, "Score: {}", points),
+            HandScore::ThreeFaces => f.write_str("3 Faces"),
+            HandScore::Soft(total) => write!(f, "Score: soft {}", total),
+            HandScore::Named(name) => f.write_str(name),
+        }
+    }
+}
 
+/// Renders cards as lines of box-drawing text.
 pub struct CardRenderer;
 
 impl CardRenderer {
+    /// Full card art, 11 columns by 7 rows.
     pub fn render_card(card: &Card) -> Vec<String> {
         let rank = Self::rank_symbol(card.rank);
         let suit = Self::suit_symbol(card.suit);
-        let _color = Self::suit_color(card.suit);
-        
+
         // ASCII art representation of a card
         vec![
             "┌─────────┐".to_string(),
@@ -24,50 +84,102 @@ impl CardRenderer {
             "└─────────┘".to_string(),
         ]
     }
-    
+
+    /// Card art for a card partly covered by the next one in a fan: rank and
+    /// suit together in the visible corner, and no bottom-right rank to be
+    /// cut in half.
+    pub fn render_fanned_card(card: &Card) -> Vec<String> {
+        let mut lines = Self::render_card(card);
+        lines[1] = format!(
+            "│{:<2}{}      │",
+            Self::rank_symbol(card.rank),
+            Self::suit_symbol(card.suit)
+        );
+        lines[5] = "│         │".to_string();
+        lines
+    }
+
+    /// A face-down card.
     pub fn render_card_back() -> Vec<String> {
-        vec![
-            "┌─────────┐".to_string(),
-            "│░░░░░░░░░│".to_string(),
-            "│░░░░░░░░░│".to_string(),
-            "│░░░░░░░░░│".to_string(),
-            "│░░░░░░░░░│".to_string(),
-            "│░░░░░░░░░│".to_string(),
-            "└─────────┘".to_string(),
-        ]
+        Self::render_card_back_with('░')
+    }
+
+    /// A face-down card whose back is filled with `fill`.
+    pub fn render_card_back_with(fill: char) -> Vec<String> {
+        let middle = format!("│{}│", fill.to_string().repeat(9));
+        let mut lines = vec!["┌─────────┐".to_string()];
+        lines.extend(std::iter::repeat_n(middle, 5));
+        lines.push("└─────────┘".to_string());
+        lines
     }
-    
+
+    /// A card part-way through a squeeze: still face down but for its suit
+    /// in the corner and, across the middle, what its peeled edges give
+    /// away.
+    pub fn render_peeled_card(suit: &str, hint: &str, fill: char) -> Vec<String> {
+        let mut lines = Self::render_card_back_with(fill);
+        if !suit.is_empty() {
+            lines[1] = format!("│{}{}│", suit, fill.to_string().repeat(8));
+        }
+        if !hint.is_empty() {
+            lines[3] = format!("│{:^9}│", hint);
+        }
+        lines
+    }
+
+    /// Card art side by side, a column apart, each card in its own style.
+    pub fn card_row(cards: &[(Vec<String>, Style)]) -> Vec<Line<'static>> {
+        (0..CARD_HEIGHT)
+            .map(|row| {
+                let spans = cards.iter().enumerate().flat_map(|(i, (art, style))| {
+                    let gap = (i > 0).then(|| Span::raw(" "));
+                    gap.into_iter()
+                        .chain([Span::styled(art[row].clone(), *style)])
+                });
+                Line::from(spans.collect::<Vec<_>>())
+            })
+            .collect()
+    }
+
+    /// One-line form such as `[10♥]`.
     pub fn render_mini_card(card: &Card) -> String {
         let rank = Self::rank_symbol(card.rank);
         let suit = Self::suit_symbol(card.suit);
         format!("[{}{}]", rank, suit)
     }
-    
-    fn rank_symbol(rank: u8) -> &'static str {
+
+    /// Rank and suit as text, e.g. "10♥".
+    pub fn label(card: &Card) -> String {
+        format!(
+            "{}{}",
+            Self::rank_symbol(card.rank),
+            Self::suit_symbol(card.suit)
+        )
+    }
+
+    pub fn rank_symbol(rank: u8) -> &'static str {
         match rank {
             1 => "A",
             10 => "10",
             11 => "J",
             12 => "Q",
             13 => "K",
-            n if n <= 9 => {
-                match n {
-                    2 => "2",
-                    3 => "3",
-                    4 => "4",
-                    5 => "5",
-                    6 => "6",
-                    7 => "7",
-                    8 => "8",
-                    9 => "9",
-                    _ => "?",
-                }
-            }
+            n if n <= 9 => match n {
+                2 => "2",
+                3 => "3",
+                4 => "4",
+                5 => "5",
+                6 => "6",
+                7 => "7",
+                8 => "8",
+                9 => "9",
+                _ => "?",
+            },
             _ => "?",
         }
     }
-    
-    fn suit_symbol(suit: u8) -> &'static str {
+
+    pub fn suit_symbol(suit: u8) -> &'static str {
         match suit {
             HEARTS => "♥",
             DIAMONDS => "♦",
@@ -76,64 +188,219 @@ impl CardRenderer {
             _ => "?",
         }
     }
-    
-    fn suit_color(suit: u8) -> Color {
+
+    /// The color `theme` draws a suit in.
+    pub fn suit_color(suit: u8, theme: &Theme) -> Color {
         match suit {
-            HEARTS | DIAMONDS => Color::Red,
-            CLUBS | SPADES => Color::White,
-            _ => Color::Gray,
+            HEARTS | DIAMONDS => theme.red_suit,
+            CLUBS | SPADES => theme.black_suit,
+            _ => theme.muted,
         }
     }
-    
-    pub fn create_card_widget(card: &Card) -> Paragraph<'static> {
+
+    /// A single card, colored by suit.
+    pub fn create_card_widget(card: &Card, theme: &Theme) -> Paragraph<'static> {
         let lines = Self::render_card(card);
-        let color = Self::suit_color(card.suit);
-        
+        let color = Self::suit_color(card.suit, theme);
+
         let text = Text::from(
             lines
                 .into_iter()
                 .map(|line| Line::from(vec![Span::styled(line, Style::default().fg(color))]))
-                .collect::<Vec<_>>()
+                .collect::<Vec<_>>(),
         );
-        
+
         Paragraph::new(text)
     }
-    
-    pub fn create_hand_display(cards: &[Card], title: String, score: u8) -> Paragraph<'static> {
-        let mut lines = vec![
-            Line::from(vec![
-                Span::styled(title, Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" "),
-                Span::styled(format!("(Score: {})", score), Style::default().fg(Color::Yellow)),
-            ])
-        ];
-        
+
+    /// A hand with every card face up.
+    pub fn create_hand_display(
+        cards: &[Card],
+        title: String,
+        score: u8,
+        theme: &Theme,
+    ) -> Paragraph<'static> {
+        Self::create_partial_hand_display(
+            cards,
+            title,
+            HandScore::Points(score),
+            cards.len(),
+            '░',
+            u16::MAX,
+            theme,
+        )
+    }
+
+    /// How many columns of each card but the last fit in `inner_width`:
+    /// a full card plus a gap when there is room, otherwise a fanned sliver.
+    /// `None` when even the narrowest fan won't fit.
+    fn card_step(count: usize, inner_width: usize) -> Option<usize> {
+        if count <= 1 {
+            return (inner_width >= CARD_WIDTH).then_some(CARD_WIDTH + 1);
+        }
+        let step = (inner_width.saturating_sub(CARD_WIDTH) / (count - 1)).min(CARD_WIDTH + 1);
+        (step >= MIN_CARD_SLIVER).then_some(step)
+    }
+
+    /// Hand display where only the first `revealed` cards are face up; the
+    /// rest show a card back drawn with `back_fill`. Cards overlap when the
+    /// hand is wider than `width`, and shrink to one-line mini cards when
+    /// even that won't fit. A 3 Faces hand is labelled as such rather than
+    /// by its points. Face-up cards take their suit's color from `theme`.
+    pub fn create_partial_hand_display(
+        cards: &[Card],
+        title: String,
+        score: HandScore,
+        revealed: usize,
+        back_fill: char,
+        width: u16,
+        theme: &Theme,
+    ) -> Paragraph<'static> {
+        let score_text = if revealed >= cards.len() {
+            format!("({})", score)
+        } else {
+            "(Score: ?)".to_string()
+        };
+        let mut lines = vec![Line::from(vec![
+            Span::styled(title, Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" "),
+            Span::styled(score_text, Style::default().fg(theme.highlight)),
+        ])];
+        let card_style = |i: usize, card: &Card| match i < revealed {
+            true => Style::default().fg(Self::suit_color(card.suit, theme)),
+            false => Style::default().fg(theme.card_back),
+        };
+
+        let inner_width = (width as usize).saturating_sub(2);
         if cards.is_empty() {
             lines.push(Line::from("No cards dealt"));
-        } else {
+        } else if let Some(step) = Self::card_step(cards.len(), inner_width) {
             // Add card representations horizontally
-            let card_lines: Vec<Vec<String>> = cards.iter()
-                .map(|c| Self::render_card(c))
+            let fanned = step <= CARD_WIDTH;
+            let card_lines: Vec<Vec<String>> = cards
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if i >= revealed {
+                        Self::render_card_back_with(back_fill)
+                    } else if fanned && i + 1 < cards.len() {
+                        Self::render_fanned_card(c)
+                    } else {
+                        Self::render_card(c)
+                    }
+                })
                 .collect();
-            
-            // Merge card lines horizontally
-            for row in 0..7 {
-                let mut row_text = String::new();
+
+            // Merge card lines horizontally; every card but the last is cut to
+            // `step` columns, leaving its top-left corner visible
+            for row in 0..CARD_HEIGHT {
+                let mut spans = Vec::with_capacity(card_lines.len());
                 for (i, card_art) in card_lines.iter().enumerate() {
-                    if i > 0 {
-                        row_text.push(' ');
-                    }
-                    row_text.push_str(&card_art[row]);
+                    let text = if i + 1 == card_lines.len() {
+                        card_art[row].clone()
+                    } else {
+                        let mut cut: String = card_art[row].chars().take(step).collect();
+                        if step > CARD_WIDTH {
+                            cut.push(' ');
+                        }
+                        cut
+                    };
+                    spans.push(Span::styled(text, card_style(i, &cards[i])));
                 }
-                lines.push(Line::from(row_text));
+                lines.push(Line::from(spans));
             }
+        } else {
+            let minis: Vec<Span> = cards
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let text = if i < revealed {
+                        Self::render_mini_card(c)
+                    } else {
+                        format!("[{}{}]", back_fill, back_fill)
+                    };
+                    Span::styled(text, card_style(i, c))
+                })
+                .collect();
+            lines.push(Line::from(minis));
         }
-        
-        Paragraph::new(lines)
-            .block(Block::default().borders(Borders::ALL))
+
+        Paragraph::new(lines).block(theme.panel())
+    }
+
+    /// The wheel as a strip of pockets centered under the ball, with the
+    /// result named once `spin` has stopped. With no spin yet the strip
+    /// rests at 0. Red pockets are drawn in `theme`'s red suit color and
+    /// green ones in its felt.
+    pub fn create_wheel_display(
+        wheel: Wheel,
+        spin: Option<&WheelAnimation>,
+        width: u16,
+        theme: &Theme,
+    ) -> Paragraph<'static> {
+        let order = wheel.order();
+        let current = spin.map_or(0, WheelAnimation::current);
+        let status = match spin {
+            Some(spin) if spin.is_complete() => {
+                let color = match roulette::color(spin.result) {
+                    PocketColor::Red => "red",
+                    PocketColor::Black => "black",
+                    PocketColor::Green => "green",
+                };
+                format!("({} {})", roulette::pocket_name(spin.result), color)
+            }
+            Some(_) => "(No more bets)".to_string(),
+            None => "(Place your bets)".to_string(),
+        };
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("{} WHEEL", wheel.name().to_uppercase()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(status, Style::default().fg(theme.highlight)),
+        ])];
+
+        // An odd number of pockets, so one sits in the middle under the ball
+        let inner_width = (width as usize).saturating_sub(2);
+        let shown = ((inner_width / POCKET_WIDTH).saturating_sub(1) | 1).min(order.len());
+        let at = order
+            .iter()
+            .position(|&pocket| pocket == current)
+            .unwrap_or(0);
+        let first = at + order.len() - shown / 2;
+        let pad = " ".repeat(shown / 2 * POCKET_WIDTH + POCKET_WIDTH / 2);
+        let strip: Vec<Span> = (first..first + shown)
+            .map(|index| {
+                let pocket = order[index % order.len()];
+                let background = match roulette::color(pocket) {
+                    PocketColor::Red => theme.red_suit,
+                    PocketColor::Black => Color::Black,
+                    PocketColor::Green => theme.felt,
+                };
+                let mut style = Style::default().fg(Color::White).bg(background);
+                if pocket == current {
+                    style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                }
+                Span::styled(
+                    format!(
+                        "{:^width$}",
+                        roulette::pocket_name(pocket),
+                        width = POCKET_WIDTH
+                    ),
+                    style,
+                )
+            })
+            .collect();
+        lines.push(Line::from(format!("{}▼", pad)));
+        lines.push(Line::from(strip));
+        lines.push(Line::from(format!("{}▲", pad)));
+
+        Paragraph::new(lines).block(theme.panel())
     }
 }
 
+/// A card being dealt, face down until its turn to be revealed.
 #[derive(Debug, Clone)]
 pub struct CardAnimation {
     pub card: Card,
@@ -149,11 +416,11 @@ impl CardAnimation {
             position,
         }
     }
-    
+
     pub fn reveal(&mut self) {
         self.revealed = true;
     }
-    
+
     pub fn render(&self) -> Vec<String> {
         if self.revealed {
             CardRenderer::render_card(&self.card)
@@ -161,4 +428,53 @@ impl CardAnimation {
             CardRenderer::render_card_back()
         }
     }
-}
\ No newline at end of file
+}
+/// Columns per pocket in the wheel strip (" 17 ").
+const POCKET_WIDTH: usize = 4;
+/// Laps the wheel makes before the ball settles.
+const WHEEL_LAPS: usize = 2;
+
+/// A roulette wheel spinning down to its result: a strip of pockets in
+/// wheel order slides under the ball, slowing over the last lap.
+#[derive(Debug, Clone)]
+pub struct WheelAnimation {
+    pub wheel: Wheel,
+    pub result: u8,
+    /// Pockets still to pass under the ball.
+    pub remaining: usize,
+}
+
+impl WheelAnimation {
+    pub fn new(wheel: Wheel, result: u8) -> Self {
+        Self {
+            wheel,
+            result,
+            remaining: wheel.order().len() * WHEEL_LAPS,
+        }
+    }
+
+    /// Moves the wheel on one pocket.
+    pub fn step(&mut self) {
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Time until the next step: quick at first, slowing as the result
+    /// comes round.
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(10 + 400 / (self.remaining as u64 + 1))
+    }
+
+    /// The pocket under the ball.
+    pub fn current(&self) -> u8 {
+        let order = self.wheel.order();
+        let at = order
+            .iter()
+            .position(|&pocket| pocket == self.result)
+            .unwrap_or(0);
+        order[(at + order.len() - self.remaining % order.len()) % order.len()]
+    }
+}
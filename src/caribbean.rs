@@ -0,0 +1,192 @@
+//! Caribbean Stud Poker: five cards each against the dealer, with one of
+//! the dealer's face up. The player folds the ante or raises twice it. The
+//! dealer needs ace-king or better to qualify; a dealer who doesn't pays the
+//! ante even money and hands the raise back. Against a qualifying dealer the
+//! better hand wins, the ante at even money and the raise on the player's
+//! hand, from 1:1 for ace-king or a pair up to 100:1 for a royal flush. The
+//! $1 jackpot bet pays on the player's hand alone, a royal or straight
+//! flush drawing on the progressive meter, and is lost on a fold.
+
+use crate::baccarat::{Card, Deck, Money};
+use crate::poker::{Category, HandValue, evaluate};
+pub use crate::poker::{Jackpot, JackpotAward, PokerBet, PokerError, Progressive};
+use crate::settlement::{SettledBet, SettlementReport, Wager};
+
+pub const HAND_SIZE: usize = 5;
+/// The jackpot bet, the same every hand.
+pub const JACKPOT_BET: Money = Money::from_dollars(1);
+/// Where the meter starts, and goes back to once it is hit.
+pub const JACKPOT_SEED: Money = Money::from_dollars(10_000);
+/// Share of each jackpot bet that goes on the meter.
+pub const JACKPOT_SHARE_PCT: i64 = 25;
+
+/// What the raise wins per unit on the player's hand.
+pub fn raise_pays(hand: &HandValue) -> i64 {
+    match hand.category {
+        Category::StraightFlush if hand.is_royal() => 100,
+        Category::StraightFlush => 50,
+        Category::FourOfAKind => 20,
+        Category::FullHouse => 7,
+        Category::Flush => 5,
+        Category::Straight => 4,
+        Category::ThreeOfAKind => 3,
+        Category::TwoPair => 2,
+        _ => 1,
+    }
+}
+
+/// What the jackpot bet collects on the player's hand, if anything.
+pub fn jackpot_award(hand: &HandValue) -> Option<JackpotAward> {
+    match hand.category {
+        Category::StraightFlush if hand.is_royal() => Some(JackpotAward::Meter(100)),
+        Category::StraightFlush => Some(JackpotAward::Meter(10)),
+        Category::FourOfAKind => Some(JackpotAward::Fixed(Money::from_dollars(500))),
+        Category::FullHouse => Some(JackpotAward::Fixed(Money::from_dollars(100))),
+        Category::Flush => Some(JackpotAward::Fixed(Money::from_dollars(50))),
+        _ => None,
+    }
+}
+
+/// Ace-king high or better.
+pub fn qualifies(hand: &HandValue) -> bool {
+    hand.category > Category::HighCard || hand.ranks[..2] == [14, 13]
+}
+
+/// A Caribbean Stud table: both hands and the bets on them.
+pub struct CaribbeanGame {
+    deck: Deck,
+    pub player: Vec<Card>,
+    pub dealer: Vec<Card>,
+    pub ante: Money,
+    /// Twice the ante once the player raises.
+    pub raise: Money,
+    pub jackpot_bet: Money,
+    /// The hand is dealt and waiting for the player to fold or raise.
+    deciding: bool,
+    pub folded: bool,
+}
+
+impl Default for CaribbeanGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CaribbeanGame {
+    pub fn new() -> Self {
+        Self {
+            deck: Deck::new(),
+            player: Vec::new(),
+            dealer: Vec::new(),
+            ante: Money::ZERO,
+            raise: Money::ZERO,
+            jackpot_bet: Money::ZERO,
+            deciding: false,
+            folded: false,
+        }
+    }
+
+    /// The player still has to fold or raise.
+    pub fn in_progress(&self) -> bool {
+        self.deciding
+    }
+
+    /// Takes the ante, and the jackpot bet if `jackpot_bet`, and deals from
+    /// a fresh deck. The dealer's first card is the one face up.
+    pub fn deal(
+        &mut self,
+        ante: Money,
+        jackpot_bet: bool,
+        jackpot: &mut dyn Jackpot,
+    ) -> Result<(), PokerError> {
+        if self.deciding {
+            return Err(PokerError::HandInProgress);
+        }
+        self.deck = Deck::new();
+        self.deck.shuffle();
+        self.player = (0..HAND_SIZE).filter_map(|_| self.deck.deal()).collect();
+        self.dealer = (0..HAND_SIZE).filter_map(|_| self.deck.deal()).collect();
+        self.ante = ante;
+        self.raise = Money::ZERO;
+        self.jackpot_bet = if jackpot_bet {
+            JACKPOT_BET
+        } else {
+            Money::ZERO
+        };
+        if jackpot_bet {
+            jackpot.contribute(JACKPOT_BET);
+        }
+        self.folded = false;
+        self.deciding = true;
+        Ok(())
+    }
+
+    pub fn player_hand(&self) -> HandValue {
+        evaluate(&self.player)
+    }
+
+    pub fn dealer_hand(&self) -> HandValue {
+        evaluate(&self.dealer)
+    }
+
+    /// Raises twice the ante or folds, turns the dealer's hand over and
+    /// settles every bet. Payouts include the stake.
+    pub fn decide(
+        &mut self,
+        raise: bool,
+        jackpot: &mut dyn Jackpot,
+    ) -> Result<SettlementReport, PokerError> {
+        if !self.deciding {
+            return Err(PokerError::NoHand);
+        }
+        self.deciding = false;
+        if raise {
+            self.raise = self.ante * 2;
+        } else {
+            self.folded = true;
+        }
+        Ok(self.settle(jackpot))
+    }
+
+    fn settle(&self, jackpot: &mut dyn Jackpot) -> SettlementReport {
+        let player = self.player_hand();
+        let (ante, raise) = if self.folded {
+            (Money::ZERO, Money::ZERO)
+        } else {
+            let dealer = self.dealer_hand();
+            if !qualifies(&dealer) {
+                (self.ante * 2, self.raise)
+            } else {
+                match player.cmp(&dealer) {
+                    std::cmp::Ordering::Greater => {
+                        (self.ante * 2, self.raise * (raise_pays(&player) + 1))
+                    }
+                    std::cmp::Ordering::Equal => (self.ante, self.raise),
+                    std::cmp::Ordering::Less => (Money::ZERO, Money::ZERO),
+                }
+            }
+        };
+        let mut bets = vec![SettledBet::new(
+            Wager::Poker(PokerBet::Ante),
+            self.ante,
+            ante,
+        )];
+        if !self.folded {
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Raise),
+                self.raise,
+                raise,
+            ));
+        }
+        if self.jackpot_bet > Money::ZERO {
+            let award = jackpot_award(&player).filter(|_| !self.folded);
+            let won = award.map_or(Money::ZERO, |award| jackpot.pay(award));
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Jackpot),
+                self.jackpot_bet,
+                won,
+            ));
+        }
+        SettlementReport { bets }
+    }
+}
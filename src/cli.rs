@@ -0,0 +1,415 @@
+//! The command line: the tables by default, or one of the subcommands.
+//! Flags given without a subcommand are `play`'s, so `--ratatui --game
+//! blackjack` works as it always has.
+
+use crate::alarms::AlarmRule;
+use crate::api;
+use crate::baccarat::{BetType, Card, GameMode, OverUnderRule, ProcedureProfile, RngChoice};
+use crate::holdem;
+use crate::lobby::Game;
+use crate::playback;
+use crate::profiles;
+use crate::protocol;
+use crate::provably_fair::ServerSeed;
+use crate::selftest;
+use crate::server::TableSpec;
+use crate::shoe_import;
+use crate::storage::DEFAULT_PROFILE;
+use crate::strategy::StrategyKind;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "terminal_casino",
+    version,
+    about = "Baccarat and a casino's worth of other tables in the terminal"
+)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Open the tables (the default)
+    Play(PlayArgs),
+    /// Deal hands with no one watching and report how a bet, or a betting
+    /// strategy, did
+    Simulate(SimulateArgs),
+    /// Print a profile's lifetime statistics and achievements
+    Stats {
+        #[arg(long, default_value = DEFAULT_PROFILE, value_parser = profiles::parse_name)]
+        profile: String,
+    },
+    /// List the profiles with their bankrolls and achievements
+    Profiles,
+    /// Play back a session's replay file, or replay a recorded shoe (a CSV
+    /// of cards) and report what every bet paid
+    #[command(alias = "import")]
+    Replay {
+        /// A replay file (.toml) from `replays` in a profile, or a shoe
+        file: PathBuf,
+        /// Game mode to score the hands under [default: classic]
+        #[arg(long)]
+        mode: Option<GameMode>,
+        /// The mode as `import` used to take it, after the file
+        #[arg(hide = true, conflicts_with = "mode")]
+        legacy_mode: Option<GameMode>,
+        /// Play back this many times as fast as a hand every two seconds
+        #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+        speed: f64,
+        /// Wait on each hand for the arrow keys instead of playing on
+        #[arg(long)]
+        step: bool,
+        /// Start from this hand
+        #[arg(long, default_value_t = 1, value_parser = parse_count)]
+        hand: usize,
+        /// How to report a shoe: text, or json with a line per hand
+        #[arg(long, default_value = "text", value_parser = parse_output)]
+        output: Output,
+    },
+    /// Compare profiles, or single sessions as PROFILE:N, side by side
+    Compare {
+        #[arg(required = true, value_name = "PROFILE[:SESSION]")]
+        profiles: Vec<String>,
+    },
+    /// Check that the shuffle is unbiased
+    Selftest {
+        #[arg(default_value_t = selftest::DEFAULT_SHUFFLES, value_parser = parse_count)]
+        shuffles: usize,
+    },
+    /// Check the drawing decisions against the official tableau
+    Tableau,
+    /// Work out the exact odds of the next hand from a shoe, fresh or with
+    /// cards already dealt from it
+    Analyze {
+        /// Game mode [default: the table's]
+        #[arg(long)]
+        mode: Option<GameMode>,
+        /// Decks in the shoe [default: the table's]
+        #[arg(long, value_parser = parse_decks)]
+        decks: Option<usize>,
+        /// Cards dealt so far, to take out of the shoe, e.g. A,5,5,K
+        #[arg(long, value_delimiter = ',', value_parser = shoe_import::parse_card)]
+        dealt: Vec<Card>,
+        /// A CSV of cards dealt so far, as `replay` reads them
+        #[arg(long)]
+        dealt_file: Option<PathBuf>,
+    },
+    /// Check a profile's provably fair shoes against the hashes published
+    /// for them, or deal one shoe out from its seeds
+    Verify {
+        #[arg(long, default_value = DEFAULT_PROFILE, value_parser = profiles::parse_name)]
+        profile: String,
+        /// A revealed server seed, to check that one shoe
+        #[arg(long, value_parser = ServerSeed::parse, requires = "client_seed", conflicts_with = "profile")]
+        server_seed: Option<ServerSeed>,
+        /// The client seed the shoe was mixed with
+        #[arg(long, requires = "server_seed")]
+        client_seed: Option<String>,
+        /// The hash published before the shoe was dealt
+        #[arg(long, requires = "server_seed")]
+        hash: Option<String>,
+        /// The shoe's game mode [default: classic]
+        #[arg(long, requires = "server_seed")]
+        mode: Option<GameMode>,
+        /// Decks in the shoe [default: 8]
+        #[arg(long, value_parser = parse_decks, requires = "server_seed")]
+        decks: Option<usize>,
+    },
+    /// Watch a flat bet's bankroll converge on the house edge, live
+    Ticker {
+        #[arg(default_value = "banker")]
+        bet: BetType,
+        /// Shuffle every shoe from this seed, to run the same rounds again
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Host a baccarat table that players join over the network, betting
+    /// against the same shoe
+    Serve(ServeArgs),
+    /// Sit down at, or watch, a table hosted with `serve`
+    Join {
+        /// The host, as HOST or HOST:PORT
+        #[arg(default_value = protocol::DEFAULT_ADDRESS, value_parser = protocol::parse_address)]
+        address: String,
+        /// The name the table knows you by
+        #[arg(long, default_value = DEFAULT_PROFILE, value_parser = profiles::parse_name)]
+        name: String,
+        /// The table to join; with several and none named, they are listed
+        /// to choose from
+        #[arg(long)]
+        table: Option<String>,
+        /// Watch instead of playing
+        #[arg(long)]
+        watch: bool,
+        /// Print the server's tables and exit
+        #[arg(long, conflicts_with_all = ["table", "watch"])]
+        list: bool,
+    },
+    /// Play heads-up with one other player, no server between you, the
+    /// cards drawn by mental shuffle so neither can know or steer them
+    /// (experimental)
+    Peer {
+        /// Wait for the other player here, as HOST:PORT or a port
+        #[arg(long, value_parser = protocol::parse_address, required_unless_present = "connect")]
+        listen: Option<String>,
+        /// Connect to the other player waiting there
+        #[arg(long, value_parser = protocol::parse_address, conflicts_with = "listen")]
+        connect: Option<String>,
+        /// The name the other player knows you by
+        #[arg(long, default_value = DEFAULT_PROFILE, value_parser = profiles::parse_name)]
+        name: String,
+        /// Game mode, when listening [default: the table's]
+        #[arg(long, conflicts_with = "connect")]
+        mode: Option<GameMode>,
+        /// Decks in the shoe, when listening [default: the table's]
+        #[arg(long, value_parser = parse_decks, conflicts_with = "connect")]
+        decks: Option<usize>,
+        /// What you sit down with, in dollars [default: the table's]
+        #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+        balance: Option<i32>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Address to listen on; 0.0.0.0 takes players from the whole network
+    #[arg(long, default_value = protocol::DEFAULT_ADDRESS, value_parser = protocol::parse_address)]
+    pub bind: String,
+    /// A table to run, as NAME or NAME:MODE; repeatable [default: one
+    /// table, main]
+    #[arg(long = "table", value_name = "NAME[:MODE]", value_parser = TableSpec::parse)]
+    pub tables: Vec<TableSpec>,
+    /// Game mode of tables not given one [default: the table's]
+    #[arg(long)]
+    pub mode: Option<GameMode>,
+    /// Decks in the shoe [default: the table's]
+    #[arg(long, value_parser = parse_decks, conflicts_with = "procedure")]
+    pub decks: Option<usize>,
+    #[arg(long, value_parser = ProcedureProfile::parse)]
+    pub procedure: Option<ProcedureProfile>,
+    /// What every player sits down with, in dollars [default: the table's]
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    pub balance: Option<i32>,
+    /// Seconds the betting window stays open each round
+    #[arg(long, default_value_t = 15, value_parser = clap::value_parser!(u64).range(1..=300))]
+    pub betting_secs: u64,
+    /// Seconds each hand stays up before betting opens again
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..=60))]
+    pub reveal_secs: u64,
+    /// Keep dealing when nobody is at a table, like a live-dealer stream
+    #[arg(long)]
+    pub continuous: bool,
+    /// Also answer HTTP requests here: private tables driven over JSON,
+    /// for web frontends and bots
+    #[arg(long, value_name = "ADDRESS", num_args = 0..=1, default_missing_value = api::DEFAULT_ADDRESS, value_parser = protocol::parse_address)]
+    pub api: Option<String>,
+    /// Let pages from this origin call the API from a browser, e.g.
+    /// http://localhost:3000, or * for any
+    #[arg(long, value_name = "ORIGIN", requires = "api")]
+    pub api_origin: Option<String>,
+    /// Shuffle every shoe from this seed, to deal the same cards again
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+/// How the tables open. Flags override the `--rules` bundle, which
+/// overrides the `[table]` defaults in `config.toml`.
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    /// Use the ratatui interface, with the lobby and every table
+    #[arg(long)]
+    pub ratatui: bool,
+    /// Open this table instead of the lobby
+    #[arg(long)]
+    pub game: Option<Game>,
+    /// Baccarat game mode, e.g. classic, ez, dragontiger
+    #[arg(long)]
+    pub mode: Option<GameMode>,
+    /// Decks in the baccarat shoe
+    #[arg(long, value_parser = parse_decks, conflicts_with = "procedure")]
+    pub decks: Option<usize>,
+    /// Starting balance in dollars
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    pub balance: Option<i32>,
+    /// Shuffle every baccarat shoe from this seed, to deal the same cards again
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Shuffle from fair (provably fair seeds), os (the system's secure
+    /// generator) or thread (rand's)
+    #[arg(long, conflicts_with = "seed")]
+    pub rng: Option<RngChoice>,
+    /// Your seed for the provably fair shuffle; random if not given
+    #[arg(long, conflicts_with = "seed")]
+    pub client_seed: Option<String>,
+    /// House-rules bundle
+    #[arg(long)]
+    pub rules: Option<String>,
+    /// Dealing procedure: a house name or KEY=VALUE settings
+    #[arg(long, value_parser = ProcedureProfile::parse)]
+    pub procedure: Option<ProcedureProfile>,
+    /// Over/under line, e.g. 8.5
+    #[arg(long, value_parser = OverUnderRule::parse_line)]
+    pub ou_line: Option<u8>,
+    /// Over/under returns per 100, OVER:UNDER, e.g. 170:225
+    #[arg(long, value_parser = parse_ou_pay)]
+    pub ou_pay: Option<(i32, i32)>,
+    /// Play as this profile, creating it if need be; without it the
+    /// profiles are listed to choose from
+    #[arg(long, value_parser = profiles::parse_name)]
+    pub profile: Option<String>,
+    /// Target balance to play towards
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    pub goal: Option<i32>,
+    /// Hot-seat players, comma separated
+    #[arg(long, value_delimiter = ',')]
+    pub players: Vec<String>,
+    /// Two players side by side at one keyboard
+    #[arg(long)]
+    pub split: bool,
+    /// Start the attract loop straight away
+    #[arg(long)]
+    pub demo: bool,
+    /// Let a betting strategy play the baccarat table, e.g. martingale or
+    /// a .rhai script; any key hands it back
+    #[arg(long, value_name = "STRATEGY", conflicts_with = "demo")]
+    pub autoplay: Option<StrategyKind>,
+    /// Bots at the hold'em table
+    #[arg(long, value_parser = parse_opponents)]
+    pub opponents: Option<usize>,
+    /// Streak alarm, e.g. banker:5 or tie:2/8; repeatable
+    #[arg(long = "alarm", value_name = "RULE", value_parser = AlarmRule::parse)]
+    pub alarms: Vec<AlarmRule>,
+    /// Alarms without the terminal bell
+    #[arg(long)]
+    pub no_bell: bool,
+    /// Continue the last session without asking
+    #[arg(long = "continue", conflicts_with = "new")]
+    pub resume: bool,
+    /// Start afresh without asking
+    #[arg(long)]
+    pub new: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    #[arg(long, default_value_t = 100_000, value_parser = parse_hands)]
+    pub hands: u32,
+    /// The main bet to back
+    #[arg(long, default_value = "banker")]
+    pub bet: BetType,
+    /// Stake on every hand, or the strategy's base stake, in dollars
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i32).range(1..))]
+    pub unit: i32,
+    /// How to size the bets: flat, martingale, paroli, fibonacci, 1-3-2-4,
+    /// banker, or a .rhai strategy script
+    #[arg(long, default_value = "flat")]
+    pub strategy: StrategyKind,
+    #[arg(long)]
+    pub mode: Option<GameMode>,
+    #[arg(long, value_parser = parse_decks, conflicts_with = "procedure")]
+    pub decks: Option<usize>,
+    #[arg(long, value_parser = ProcedureProfile::parse)]
+    pub procedure: Option<ProcedureProfile>,
+    /// Bet from a bankroll of this many dollars, stopping if it can't cover
+    /// a stake
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    pub balance: Option<i32>,
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Threads to deal on [default: one per core]
+    #[arg(long, value_parser = parse_count)]
+    pub threads: Option<usize>,
+    /// Play this many sessions of up to --hands hands from the --balance
+    /// bankroll and report the risk of ruin
+    #[arg(long, value_parser = parse_count, requires = "balance")]
+    pub sessions: Option<usize>,
+    /// How to report: text, or json with a line per hand then the summary
+    /// (with --sessions, the risk-of-ruin report)
+    #[arg(long, default_value = "text", value_parser = parse_output)]
+    pub output: Output,
+    /// Stream every hand to stdout as binary frames (state, then player
+    /// and banker cards) instead of reporting
+    #[arg(long, conflicts_with_all = ["sessions", "output"])]
+    pub binary: bool,
+}
+
+/// How a subcommand that plays by itself reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Output {
+    #[default]
+    Text,
+    /// Machine-readable: one JSON object per line.
+    Json,
+}
+
+fn parse_count(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!(
+            "invalid count '{}', expected a number above 0",
+            value
+        )),
+    }
+}
+
+/// A count of hands the simulator tallies in a `u32`.
+fn parse_hands(value: &str) -> Result<u32, String> {
+    u32::try_from(parse_count(value)?)
+        .map_err(|_| format!("invalid count '{}', expected at most {}", value, u32::MAX))
+}
+
+fn parse_output(value: &str) -> Result<Output, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "text" => Ok(Output::Text),
+        "json" => Ok(Output::Json),
+        _ => Err(format!("invalid output '{}', expected text or json", value)),
+    }
+}
+
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed <= playback::MAX_SPEED => Ok(speed),
+        _ => Err(format!(
+            "invalid speed '{}', expected above 0 and at most {}",
+            value,
+            playback::MAX_SPEED
+        )),
+    }
+}
+
+fn parse_decks(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(n @ 1..=8) => Ok(n),
+        _ => Err(format!("invalid decks '{}', expected 1-8", value)),
+    }
+}
+
+fn parse_opponents(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(n) if (1..=holdem::MAX_OPPONENTS).contains(&n) => Ok(n),
+        _ => Err(format!(
+            "invalid opponents '{}', expected 1 to {}",
+            value,
+            holdem::MAX_OPPONENTS
+        )),
+    }
+}
+
+fn parse_ou_pay(value: &str) -> Result<(i32, i32), String> {
+    let parsed = value
+        .split_once(':')
+        .and_then(|(over, under)| Some((over.parse::<i32>().ok()?, under.parse::<i32>().ok()?)));
+    match parsed {
+        Some((over, under)) if over > 0 && under > 0 => Ok((over, under)),
+        _ => Err(format!(
+            "invalid '{}', expected OVER:UNDER returns per 100, e.g. 170:225",
+            value
+        )),
+    }
+}
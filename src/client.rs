@@ -0,0 +1,1164 @@
+//! `join`: a seat at a table hosted by `serve`, or a place to watch one
+//! from. When the server runs several tables, a browser lists them to pick
+//! from. The server deals; this end shows what it says as it says it: the
+//! betting clock, the squeeze as the biggest bettor peels their cards, each
+//! hand turned over a card at a time and then settled, and who is seated.
+//! If the connection drops it keeps trying to get back
+//! to the same table under the same name.
+
+use crate::baccarat::{BetType, Card, GameMode, Money, Side, TableLimits};
+use crate::card_renderer::{CardRenderer, HandScore};
+use crate::protocol::{
+    self, ClientMessage, PlayerResult, PlayerView, Role, ServerMessage, TableSummary,
+};
+use crate::scoreboard::{Bead, BeadPlate, BeadRoad};
+use crate::theme::Theme;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table},
+};
+use std::io::{self, BufRead, BufReader};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the server to pick up and answer the join.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time between cards as a hand is turned over.
+const REVEAL_STEP: Duration = Duration::from_millis(600);
+/// Wait between attempts to get back to a server that went away.
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+/// Width of the players sidebar.
+const SIDEBAR_WIDTH: u16 = 34;
+
+/// A live connection: the stream bets go out on, and the server's messages
+/// stamped with when they came in.
+struct Connection {
+    stream: TcpStream,
+    messages: Receiver<(Instant, ServerMessage)>,
+}
+
+/// What the server says on sitting down.
+struct Seat {
+    name: String,
+    table: String,
+    role: Role,
+    token: Option<String>,
+    balance: Money,
+    mode: GameMode,
+    limits: TableLimits,
+}
+
+/// Reads the server's next message, skipping blank lines.
+fn read_message(lines: &mut impl BufRead) -> Result<Option<ServerMessage>, String> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if lines.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            return serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| format!("unreadable message: {}", e));
+        }
+    }
+}
+
+/// Dials `address`, sends `message` and waits for the answer, returning
+/// the stream and its reader for what follows.
+fn ask(
+    address: &str,
+    message: &ClientMessage,
+) -> Result<(TcpStream, BufReader<TcpStream>, ServerMessage), String> {
+    let socket = address
+        .to_socket_addrs()
+        .map_err(|e| format!("{}: {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("{}: no such host", address))?;
+    let mut stream = TcpStream::connect_timeout(&socket, JOIN_TIMEOUT)
+        .map_err(|e| format!("{}: {}", address, e))?;
+    let mut reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+    protocol::send(&mut stream, message).map_err(|e| e.to_string())?;
+
+    stream
+        .set_read_timeout(Some(JOIN_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let answer = read_message(&mut reader)
+        .map_err(|e| format!("{}: {}", address, e))?
+        .ok_or_else(|| format!("{}: closed the connection", address))?;
+    stream.set_read_timeout(None).map_err(|e| e.to_string())?;
+    Ok((stream, reader, answer))
+}
+
+/// The tables the server at `address` runs.
+pub fn list_tables(address: &str) -> Result<Vec<TableSummary>, String> {
+    match ask(address, &ClientMessage::ListTables)? {
+        (_, _, ServerMessage::Tables { tables }) => Ok(tables),
+        (_, _, ServerMessage::Rejected { reason }) => Err(reason),
+        (_, _, other) => Err(format!("{}: expected the tables, got {:?}", address, other)),
+    }
+}
+
+impl Connection {
+    /// Dials `address` and joins `table` (the server's first if `None`)
+    /// as `name`, sitting back down with `token` if there is one, failing
+    /// if the server turns the seat down.
+    fn open(
+        address: &str,
+        name: &str,
+        table: Option<&str>,
+        role: Role,
+        token: Option<&str>,
+    ) -> Result<(Self, Seat), String> {
+        let join = ClientMessage::Join {
+            name: name.to_string(),
+            table: table.map(String::from),
+            role,
+            version: protocol::PROTOCOL_VERSION,
+            token: token.map(String::from),
+        };
+        let (stream, mut reader, welcome) = ask(address, &join)?;
+        let seat = match welcome {
+            ServerMessage::Welcome {
+                name,
+                table,
+                role,
+                token,
+                balance,
+                mode,
+                min_bet,
+                max_bet,
+                ..
+            } => Seat {
+                name,
+                table,
+                role,
+                token,
+                balance,
+                mode: mode.parse().unwrap_or(GameMode::Classic),
+                limits: TableLimits {
+                    min_bet,
+                    max_bet,
+                    ..TableLimits::STANDARD
+                },
+            },
+            ServerMessage::Rejected { reason } => return Err(reason),
+            other => return Err(format!("{}: expected a welcome, got {:?}", address, other)),
+        };
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(Some(message)) = read_message(&mut reader) {
+                if sender.send((Instant::now(), message)).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok((Self { stream, messages }, seat))
+    }
+}
+
+enum Link {
+    Up(Connection),
+    /// Lost, and trying to get back; `reason` is why the last try failed.
+    Down {
+        reason: String,
+        attempts: u32,
+        retry_at: Instant,
+    },
+}
+
+/// A squeezed card, as far as it has been peeled.
+#[derive(Default)]
+struct PeeledCard {
+    step: u8,
+    suit: String,
+    hint: String,
+    /// The card, once it is face up.
+    card: Option<Card>,
+}
+
+/// The squeeze between betting closing and the hand being shown.
+struct SqueezeView {
+    round: u32,
+    squeezer: String,
+    side: Side,
+    cards: Vec<PeeledCard>,
+    /// The other side's cards, face up.
+    shown: Vec<Card>,
+    closes_at: Instant,
+    /// The card the squeezer's keys peel.
+    selected: usize,
+}
+
+/// A hand the server dealt, turned over a card at a time from `dealt_at`.
+struct DealtHand {
+    round: u32,
+    player: Vec<Card>,
+    banker: Vec<Card>,
+    player_score: u8,
+    banker_score: u8,
+    winner: u8,
+    results: Vec<PlayerResult>,
+    dealt_at: Instant,
+    /// Cards already seen in the squeeze, which start face up.
+    squeezed: usize,
+}
+
+impl DealtHand {
+    /// Cards face up so far, player's and banker's. The deal alternates
+    /// between the sides, player first, as at the table.
+    fn revealed(&self) -> (usize, usize) {
+        let mut order = Vec::new();
+        for i in 0..self.player.len().max(self.banker.len()) {
+            if i < self.player.len() {
+                order.push(Side::Player);
+            }
+            if i < self.banker.len() {
+                order.push(Side::Banker);
+            }
+        }
+        let shown = (self.dealt_at.elapsed().as_millis() / REVEAL_STEP.as_millis()) as usize
+            + self.squeezed.max(1);
+        let shown = &order[..shown.min(order.len())];
+        let player = shown.iter().filter(|&&side| side == Side::Player).count();
+        (player, shown.len() - player)
+    }
+
+    fn face_up(&self) -> bool {
+        self.revealed() == (self.player.len(), self.banker.len())
+    }
+}
+
+pub struct ClientUI {
+    address: String,
+    name: String,
+    table: String,
+    role: Role,
+    /// From the last welcome, to sit back down with after a drop.
+    token: Option<String>,
+    link: Link,
+    mode: GameMode,
+    limits: TableLimits,
+    chips: [i32; 5],
+    players: Vec<PlayerView>,
+    spectators: usize,
+    round: u32,
+    shoe: u32,
+    /// Cards dealt from the shoe, and its size.
+    penetration: (usize, usize),
+    /// When betting closes; `None` while the hand is dealt and shown.
+    closes_at: Option<Instant>,
+    squeeze: Option<SqueezeView>,
+    last_hand: Option<DealtHand>,
+    /// This shoe's hands, for the bead plate.
+    beads: Vec<Bead>,
+    /// The bet being made up, sent with SPACE.
+    bet: BetType,
+    selected_chip: i32,
+    stake: i32,
+    status: Option<String>,
+    theme: Theme,
+}
+
+impl ClientUI {
+    /// Dials `address` and joins `table` as `name`, to play or to watch,
+    /// failing if the server can't be reached or turns the seat down.
+    pub fn connect(
+        address: &str,
+        name: &str,
+        table: Option<&str>,
+        role: Role,
+        chips: [i32; 5],
+        theme: Theme,
+    ) -> Result<Self, String> {
+        let (connection, seat) = Connection::open(address, name, table, role, None)?;
+        let mut client = Self {
+            address: address.to_string(),
+            name: String::new(),
+            table: String::new(),
+            role,
+            token: None,
+            link: Link::Up(connection),
+            mode: GameMode::Classic,
+            limits: TableLimits::STANDARD,
+            chips,
+            players: Vec::new(),
+            spectators: 0,
+            round: 0,
+            shoe: 1,
+            penetration: (0, 0),
+            closes_at: None,
+            squeeze: None,
+            last_hand: None,
+            beads: Vec::new(),
+            bet: BetType::Banker,
+            selected_chip: chips[0],
+            stake: chips[0],
+            status: None,
+            theme,
+        };
+        client.sit(seat);
+        Ok(client)
+    }
+
+    fn sit(&mut self, seat: Seat) {
+        self.players = match seat.role {
+            Role::Player => vec![PlayerView {
+                name: seat.name.clone(),
+                balance: seat.balance,
+                bet: None,
+                stake: Money::ZERO,
+            }],
+            Role::Spectator => Vec::new(),
+        };
+        self.name = seat.name;
+        self.table = seat.table;
+        self.role = seat.role;
+        self.token = seat.token;
+        self.mode = seat.mode;
+        self.limits = seat.limits;
+        self.stake = self.limits.clamp(self.stake);
+        if !self.mode.main_bets().contains(&self.bet) {
+            self.bet = BetType::Banker;
+        }
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let res = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        res
+    }
+
+    fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        loop {
+            self.receive();
+            self.reconnect();
+            terminal.draw(|f| self.ui(f))?;
+
+            if event::poll(Duration::from_millis(50))?
+                && let Event::Key(key) = event::read()?
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Left | KeyCode::Right if self.has_squeeze() => {
+                        self.select_squeezed(key.code == KeyCode::Right)
+                    }
+                    KeyCode::Up | KeyCode::Char(' ') if self.has_squeeze() => self.peel(),
+                    _ if self.role == Role::Spectator => {}
+                    KeyCode::Char('p') => self.choose(BetType::Player),
+                    KeyCode::Char('b') => self.choose(BetType::Banker),
+                    KeyCode::Char('t') => self.choose(BetType::Tie),
+                    KeyCode::Tab => {
+                        let bets = self.mode.main_bets();
+                        let next = bets
+                            .iter()
+                            .position(|&bet| bet == self.bet)
+                            .map_or(0, |i| i + 1);
+                        self.choose(bets[next % bets.len()]);
+                    }
+                    KeyCode::Char(digit @ '1'..='5') => {
+                        self.selected_chip = self.chips[digit as usize - '1' as usize];
+                        self.stake = self.limits.clamp(self.selected_chip);
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        self.stake = self.limits.step(self.stake, self.selected_chip, true);
+                    }
+                    KeyCode::Char('-') => {
+                        self.stake = self.limits.step(self.stake, self.selected_chip, false)
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => self.send(ClientMessage::Bet {
+                        bet: self.bet.to_string(),
+                        amount: self.stake,
+                    }),
+                    KeyCode::Char('x') => self.send(ClientMessage::Bet {
+                        bet: self.bet.to_string(),
+                        amount: 0,
+                    }),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn choose(&mut self, bet: BetType) {
+        if self.mode.main_bets().contains(&bet) {
+            self.bet = bet;
+        }
+    }
+
+    /// Whether the cards being squeezed are this player's to peel.
+    fn has_squeeze(&self) -> bool {
+        self.role == Role::Player
+            && self
+                .squeeze
+                .as_ref()
+                .is_some_and(|squeeze| squeeze.squeezer == self.name)
+    }
+
+    fn select_squeezed(&mut self, next: bool) {
+        if let Some(squeeze) = &mut self.squeeze {
+            let last = squeeze.cards.len().saturating_sub(1);
+            squeeze.selected = if next {
+                (squeeze.selected + 1).min(last)
+            } else {
+                squeeze.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    fn peel(&mut self) {
+        let Some(card) = self.squeeze.as_ref().map(|squeeze| squeeze.selected) else {
+            return;
+        };
+        let Link::Up(connection) = &mut self.link else {
+            return;
+        };
+        if let Err(e) = protocol::send(&mut connection.stream, &ClientMessage::Peel { card }) {
+            self.lose_link(e.to_string());
+        }
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        let Link::Up(connection) = &mut self.link else {
+            self.status = Some("Not connected; bet again once back at the table".to_string());
+            return;
+        };
+        if self.closes_at.is_none() {
+            self.status = Some("Betting is closed; wait for the next round".to_string());
+            return;
+        }
+        match protocol::send(&mut connection.stream, &message) {
+            Ok(()) => self.status = None,
+            Err(e) => self.lose_link(e.to_string()),
+        }
+    }
+
+    fn lose_link(&mut self, reason: String) {
+        self.link = Link::Down {
+            reason,
+            attempts: 0,
+            retry_at: Instant::now() + RECONNECT_DELAY,
+        };
+        self.closes_at = None;
+        self.squeeze = None;
+        self.status = None;
+    }
+
+    /// Tries to sit back down, once the wait since the last try is up.
+    fn reconnect(&mut self) {
+        let Link::Down {
+            attempts, retry_at, ..
+        } = &self.link
+        else {
+            return;
+        };
+        if Instant::now() < *retry_at {
+            return;
+        }
+        let attempts = attempts + 1;
+        match Connection::open(
+            &self.address,
+            &self.name,
+            Some(&self.table),
+            self.role,
+            self.token.as_deref(),
+        ) {
+            Ok((connection, seat)) => {
+                self.sit(seat);
+                self.link = Link::Up(connection);
+                self.status = Some("Back at the table".to_string());
+            }
+            Err(reason) => {
+                self.link = Link::Down {
+                    reason,
+                    attempts,
+                    retry_at: Instant::now() + RECONNECT_DELAY,
+                }
+            }
+        }
+    }
+
+    /// Takes in whatever the server has sent, holding it back while a hand
+    /// is still being turned over so the balances don't give the result
+    /// away.
+    fn receive(&mut self) {
+        loop {
+            if self.last_hand.as_ref().is_some_and(|hand| !hand.face_up()) {
+                return;
+            }
+            let Link::Up(connection) = &self.link else {
+                return;
+            };
+            match connection.messages.try_recv() {
+                Ok((received, message)) => self.apply(received, message),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.lose_link("the server closed the connection".to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, received: Instant, message: ServerMessage) {
+        match message {
+            ServerMessage::Welcome { .. } => {}
+            ServerMessage::Table {
+                players,
+                spectators,
+            } => {
+                self.players = players;
+                self.spectators = spectators;
+            }
+            ServerMessage::Tables { .. } => {}
+            ServerMessage::BettingOpen {
+                round,
+                shoe,
+                closes_in_ms,
+                cards_dealt,
+                shoe_size,
+            } => {
+                self.round = round;
+                self.shoe = shoe;
+                self.penetration = (cards_dealt, shoe_size);
+                self.closes_at = Some(received + Duration::from_millis(closes_in_ms));
+            }
+            ServerMessage::Squeeze {
+                round,
+                squeezer,
+                side,
+                cards,
+                shown,
+                closes_in_ms,
+            } => {
+                self.closes_at = None;
+                self.squeeze = Some(SqueezeView {
+                    round,
+                    squeezer,
+                    side,
+                    cards: (0..cards).map(|_| PeeledCard::default()).collect(),
+                    shown: protocol::parse_cards(&shown).unwrap_or_default(),
+                    closes_at: received + Duration::from_millis(closes_in_ms),
+                    selected: 0,
+                });
+            }
+            ServerMessage::Peeled {
+                card,
+                step,
+                suit,
+                hint,
+                label,
+            } => {
+                let Some(squeeze) = &mut self.squeeze else {
+                    return;
+                };
+                let Some(peeled) = squeeze.cards.get_mut(card) else {
+                    return;
+                };
+                *peeled = PeeledCard {
+                    step,
+                    suit,
+                    hint,
+                    card: label.and_then(|label| protocol::parse_cards(&[label]).ok()?.pop()),
+                };
+                // A card turned face up hands the keys on to the next one.
+                if peeled.step >= protocol::PEEL_STEPS
+                    && squeeze.selected == card
+                    && let Some(next) = squeeze
+                        .cards
+                        .iter()
+                        .position(|card| card.step < protocol::PEEL_STEPS)
+                {
+                    squeeze.selected = next;
+                }
+            }
+            ServerMessage::Hand {
+                round,
+                shoe,
+                player_cards,
+                banker_cards,
+                player_score,
+                banker_score,
+                winner,
+                results,
+            } => {
+                self.closes_at = None;
+                let squeeze = self.squeeze.take().filter(|squeeze| squeeze.round == round);
+                let squeezed =
+                    squeeze.map_or(0, |squeeze| squeeze.cards.len() + squeeze.shown.len());
+                let (Ok(player), Ok(banker)) = (
+                    protocol::parse_cards(&player_cards),
+                    protocol::parse_cards(&banker_cards),
+                ) else {
+                    self.status = Some(format!("Round {}'s cards could not be read", round));
+                    return;
+                };
+                if shoe != self.shoe {
+                    self.shoe = shoe;
+                    self.beads.clear();
+                }
+                let pair = |cards: &[Card]| matches!(cards, [first, second, ..] if first.rank == second.rank);
+                self.beads.push(Bead {
+                    winner,
+                    player_pair: pair(&player),
+                    banker_pair: pair(&banker),
+                });
+                self.last_hand = Some(DealtHand {
+                    round,
+                    player,
+                    banker,
+                    player_score,
+                    banker_score,
+                    winner,
+                    results,
+                    dealt_at: Instant::now(),
+                    squeezed,
+                });
+            }
+            ServerMessage::Sync(snapshot) => {
+                self.round = snapshot.round;
+                self.shoe = snapshot.shoe;
+                self.penetration = (snapshot.cards_dealt, snapshot.shoe_size);
+                self.beads = snapshot.beads;
+                self.players = snapshot.players;
+                self.spectators = snapshot.spectators;
+                if let Some(bet) = snapshot.bet.and_then(|bet| bet.parse().ok()) {
+                    self.bet = bet;
+                    self.stake = snapshot.stake.whole_dollars() as i32;
+                    self.status =
+                        Some(format!("Your ${} on {} is still down", snapshot.stake, bet));
+                }
+            }
+            ServerMessage::Shuffled { shoe } => {
+                self.shoe = shoe;
+                self.beads.clear();
+                self.status = Some(format!("New shoe: shoe {}", shoe));
+            }
+            ServerMessage::Rejected { reason } => {
+                self.status = Some(format!("Bet refused: {}", reason))
+            }
+        }
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .margin(1)
+            .constraints([Constraint::Min(40), Constraint::Length(SIDEBAR_WIDTH)])
+            .split(f.area());
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(10),
+                Constraint::Min(4),
+                Constraint::Length(8),
+                Constraint::Length(2),
+            ])
+            .split(columns[0]);
+
+        let dealing = self.last_hand.as_ref().filter(|hand| !hand.face_up());
+        let clock = match (&self.link, self.closes_at, dealing, &self.squeeze) {
+            (Link::Down { attempts: 0, .. }, _, _, _) => Span::styled(
+                "connection lost, reconnecting",
+                Style::default().fg(self.theme.alert),
+            ),
+            (
+                Link::Down {
+                    attempts, reason, ..
+                },
+                _,
+                _,
+                _,
+            ) => Span::styled(
+                format!("reconnecting (try {}: {})", attempts, reason),
+                Style::default().fg(self.theme.alert),
+            ),
+            (Link::Up(_), Some(at), _, _) => Span::styled(
+                format!(
+                    "Bets close in {}s",
+                    at.saturating_duration_since(Instant::now())
+                        .as_secs_f64()
+                        .ceil() as u64
+                ),
+                Style::default()
+                    .fg(self.theme.win)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (Link::Up(_), None, _, Some(squeeze)) => Span::styled(
+                format!(
+                    "{} squeezing, {}s left",
+                    squeeze.squeezer,
+                    squeeze
+                        .closes_at
+                        .saturating_duration_since(Instant::now())
+                        .as_secs_f64()
+                        .ceil() as u64
+                ),
+                Style::default()
+                    .fg(self.theme.side_bet)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (Link::Up(_), None, Some(hand), None) => Span::styled(
+                format!("dealing round {}", hand.round),
+                Style::default().fg(self.theme.highlight),
+            ),
+            (Link::Up(_), None, None, None) if self.round == 0 => Span::styled(
+                "waiting for the first round",
+                Style::default().fg(self.theme.highlight),
+            ),
+            (Link::Up(_), None, None, None) => {
+                Span::styled("no more bets", Style::default().fg(self.theme.highlight))
+            }
+        };
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    "TABLE {} AT {}  |  {:?}  |  round {}, shoe {}{}  |  ",
+                    self.table.to_uppercase(),
+                    self.address,
+                    self.mode,
+                    self.round,
+                    self.shoe,
+                    match self.penetration {
+                        (_, 0) => String::new(),
+                        (dealt, size) => format!(" ({}/{} cards)", dealt, size),
+                    }
+                ),
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            clock,
+        ]))
+        .block(self.theme.panel());
+        f.render_widget(title, chunks[0]);
+
+        self.render_hands(f, chunks[1]);
+        self.render_settlement(f, chunks[2]);
+
+        let plate = BeadPlate::from_beads(self.beads.clone());
+        f.render_widget(
+            BeadRoad::new(&plate).theme(&self.theme).block(
+                self.theme
+                    .panel()
+                    .title(format!("Bead Plate - Shoe {}", self.shoe)),
+            ),
+            chunks[3],
+        );
+
+        let slip = match self.role {
+            Role::Player => format!(
+                "Your bet: ${} on {}  (chip ${})",
+                self.stake, self.bet, self.selected_chip
+            ),
+            Role::Spectator => format!("Watching as {}", self.name),
+        };
+        let status = match &self.status {
+            Some(status) => Line::from(vec![
+                Span::raw(slip),
+                Span::raw("  -  "),
+                Span::styled(status.clone(), Style::default().fg(self.theme.highlight)),
+            ]),
+            None => Line::from(slip),
+        };
+        let help = match self.role {
+            _ if self.has_squeeze() => "Your squeeze: [←/→] Card  [↑/SPACE] Peel  [Q/ESC] Leave",
+            Role::Player => {
+                "[P/B/T] Bet  [TAB] Next bet  [1-5] Chip  [+/-] Adjust  [SPACE] Place  [X] Take down  [Q/ESC] Leave"
+            }
+            Role::Spectator => "[Q/ESC] Leave",
+        };
+        let help = Line::from(Span::styled(help, Style::default().fg(self.theme.muted)));
+        f.render_widget(Paragraph::new(vec![status, help]), chunks[4]);
+
+        self.render_players(f, columns[1]);
+    }
+
+    fn render_hands(&self, f: &mut Frame, area: Rect) {
+        if let Some(squeeze) = &self.squeeze {
+            self.render_squeeze(f, area, squeeze);
+            return;
+        }
+        let Some(hand) = &self.last_hand else {
+            f.render_widget(
+                Paragraph::new("No hand dealt yet").block(self.theme.panel()),
+                area,
+            );
+            return;
+        };
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let (player_shown, banker_shown) = hand.revealed();
+        for (side, cards, score, shown, column) in [
+            (
+                Side::Player,
+                &hand.player,
+                hand.player_score,
+                player_shown,
+                columns[0],
+            ),
+            (
+                Side::Banker,
+                &hand.banker,
+                hand.banker_score,
+                banker_shown,
+                columns[1],
+            ),
+        ] {
+            f.render_widget(
+                CardRenderer::create_partial_hand_display(
+                    cards,
+                    self.mode.side_name(side).to_uppercase(),
+                    HandScore::Points(score),
+                    shown,
+                    '░',
+                    column.width,
+                    &self.theme,
+                ),
+                column,
+            );
+        }
+    }
+
+    /// The squeezed side's cards as far as they are peeled, the one the
+    /// squeezer's keys peel picked out, beside the other side's face up.
+    fn render_squeeze(&self, f: &mut Frame, area: Rect, squeeze: &SqueezeView) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let squeezed: Vec<_> = squeeze
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, peeled)| {
+                let (art, color) = match &peeled.card {
+                    Some(card) => (
+                        CardRenderer::render_card(card),
+                        CardRenderer::suit_color(card.suit, &self.theme),
+                    ),
+                    None => (
+                        CardRenderer::render_peeled_card(&peeled.suit, &peeled.hint, '░'),
+                        self.theme.card_back,
+                    ),
+                };
+                let style = if self.has_squeeze() && i == squeeze.selected {
+                    Style::default()
+                        .fg(self.theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(color)
+                };
+                (art, style)
+            })
+            .collect();
+        let shown: Vec<_> = squeeze
+            .shown
+            .iter()
+            .map(|card| {
+                (
+                    CardRenderer::render_card(card),
+                    Style::default().fg(CardRenderer::suit_color(card.suit, &self.theme)),
+                )
+            })
+            .collect();
+        // Player on the left, as when the hand is shown.
+        let (other, squeezed_column, shown_column) = match squeeze.side {
+            Side::Player => (Side::Banker, columns[0], columns[1]),
+            Side::Banker => (Side::Player, columns[1], columns[0]),
+        };
+        for (side, cards, note, column) in [
+            (
+                squeeze.side,
+                squeezed,
+                format!("squeezed by {}", squeeze.squeezer),
+                squeezed_column,
+            ),
+            (other, shown, "(Score: ?)".to_string(), shown_column),
+        ] {
+            let mut lines = vec![Line::from(vec![
+                Span::styled(
+                    self.mode.side_name(side).to_uppercase(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(note, Style::default().fg(self.theme.highlight)),
+            ])];
+            lines.extend(CardRenderer::card_row(&cards));
+            f.render_widget(Paragraph::new(lines).block(self.theme.panel()), column);
+        }
+    }
+
+    /// The last hand's result and what each bet on it paid, once it is all
+    /// face up.
+    fn render_settlement(&self, f: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        if let Some(squeeze) = &self.squeeze {
+            let side = self.mode.side_name(squeeze.side).to_uppercase();
+            lines.push(Line::from(format!(
+                "Round {}: {} squeezes the {} cards",
+                squeeze.round, squeeze.squeezer, side
+            )));
+        }
+        match &self.last_hand {
+            _ if self.squeeze.is_some() => {}
+            Some(hand) if hand.face_up() => {
+                let result = match hand.winner {
+                    1 => "Player wins",
+                    2 => "Banker wins",
+                    _ => "Tie",
+                };
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "Round {}: {} {}-{}",
+                        hand.round, result, hand.player_score, hand.banker_score
+                    ),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+                if hand.results.is_empty() {
+                    lines.push(Line::from("  no bets"));
+                }
+                for result in &hand.results {
+                    let color = if result.net > Money::ZERO {
+                        self.theme.win
+                    } else if result.net < Money::ZERO {
+                        self.theme.loss
+                    } else {
+                        self.theme.muted
+                    };
+                    lines.push(Line::from(vec![
+                        Span::raw(format!(
+                            "  {:<16} ${:>7} on {:<8} ",
+                            result.name, result.stake, result.bet
+                        )),
+                        Span::styled(format!("{:>+9}", result.net), Style::default().fg(color)),
+                    ]));
+                }
+            }
+            Some(hand) => lines.push(Line::from(format!("Round {}: dealing...", hand.round))),
+            None => {}
+        }
+        f.render_widget(
+            Paragraph::new(lines).block(self.theme.panel().title("Last Hand")),
+            area,
+        );
+    }
+
+    /// Everyone seated, their balances and the bets they have down.
+    fn render_players(&self, f: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        for player in &self.players {
+            let style = if player.name == self.name {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<18}", player.name), style),
+                Span::raw(format!("${:>10}", player.balance)),
+            ]));
+            lines.push(match &player.bet {
+                Some(bet) => Line::from(format!("  ${} on {}", player.stake, bet)),
+                None => Line::from(Span::styled(
+                    "  no bet",
+                    Style::default().fg(self.theme.muted),
+                )),
+            });
+        }
+        f.render_widget(
+            Paragraph::new(lines).block(self.theme.panel().title(format!(
+                "Players ({}), {} watching",
+                self.players.len(),
+                self.spectators
+            ))),
+            area,
+        );
+    }
+}
+
+/// One line per table, for `join --list`.
+pub fn print_tables(tables: &[TableSummary]) {
+    println!(
+        "{:<16} {:<14} {:>7} {:>9} {:>7} {:>5}",
+        "TABLE", "MODE", "PLAYERS", "WATCHING", "ROUND", "SHOE"
+    );
+    for table in tables {
+        println!(
+            "{:<16} {:<14} {:>7} {:>9} {:>7} {:>5}",
+            table.name,
+            table.mode,
+            format!("{}/{}", table.players, table.seats),
+            table.spectators,
+            table.round,
+            table.shoe
+        );
+    }
+}
+
+/// The server's tables, to pick one to play at or watch.
+pub struct TableBrowser {
+    address: String,
+    tables: Vec<TableSummary>,
+    selected: usize,
+    /// What [ENTER] joins as.
+    role: Role,
+    status: Option<String>,
+    theme: Theme,
+}
+
+impl TableBrowser {
+    pub fn new(address: &str, tables: Vec<TableSummary>, role: Role, theme: Theme) -> Self {
+        Self {
+            address: address.to_string(),
+            tables,
+            selected: 0,
+            role,
+            status: None,
+            theme,
+        }
+    }
+
+    /// Shows the tables until one is picked, returning its name and how to
+    /// join it, or `None` if the player quits.
+    pub fn run(&mut self) -> io::Result<Option<(String, Role)>> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let res = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        res
+    }
+
+    fn run_app<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> io::Result<Option<(String, Role)>> {
+        loop {
+            terminal.draw(|f| self.ui(f))?;
+            if let Event::Key(key) = event::read()? {
+                let picked = |browser: &Self, role| {
+                    browser
+                        .tables
+                        .get(browser.selected)
+                        .map(|table| (table.name.clone(), role))
+                };
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                    KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        self.selected = (self.selected + 1).min(self.tables.len().saturating_sub(1))
+                    }
+                    KeyCode::Enter => return Ok(picked(self, self.role)),
+                    KeyCode::Char('w') => return Ok(picked(self, Role::Spectator)),
+                    KeyCode::Char('r') => match list_tables(&self.address) {
+                        Ok(tables) => {
+                            self.tables = tables;
+                            self.selected = self.selected.min(self.tables.len().saturating_sub(1));
+                            self.status = None;
+                        }
+                        Err(e) => self.status = Some(e),
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(4), Constraint::Length(1)])
+            .split(f.area());
+
+        let header = ["Table", "Mode", "Players", "Watching", "Round", "Shoe"];
+        let rows: Vec<Row> = self
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(i, table)| {
+                let row = Row::new(vec![
+                    Cell::from(table.name.clone()),
+                    Cell::from(table.mode.clone()),
+                    Cell::from(format!("{}/{}", table.players, table.seats)),
+                    Cell::from(table.spectators.to_string()),
+                    Cell::from(table.round.to_string()),
+                    Cell::from(table.shoe.to_string()),
+                ]);
+                if i == self.selected {
+                    row.style(
+                        Style::default()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::REVERSED),
+                    )
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(18),
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(5),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(
+                self.theme
+                    .panel()
+                    .title(format!("Tables at {}", self.address)),
+            );
+        f.render_widget(table, chunks[0]);
+
+        let enter = match self.role {
+            Role::Player => "Sit down",
+            Role::Spectator => "Watch",
+        };
+        let help = format!(
+            "[↑/↓] Choose  [ENTER] {}  [W] Watch  [R] Refresh  [Q/ESC] Quit",
+            enter
+        );
+        let line = match &self.status {
+            Some(status) => Line::from(vec![
+                Span::styled(status.clone(), Style::default().fg(self.theme.alert)),
+                Span::raw("  "),
+                Span::raw(help),
+            ]),
+            None => Line::from(Span::styled(help, Style::default().fg(self.theme.muted))),
+        };
+        f.render_widget(Paragraph::new(line), chunks[1]);
+    }
+}
@@ -0,0 +1,235 @@
+//! `compare` subcommand: side-by-side report of several profiles, or of
+//! single saved sessions, built from their session records.
+
+use crate::baccarat::Money;
+use crate::integrity::Integrity;
+use crate::sessions::{SessionLog, SessionRecord};
+use std::io::{self, Write};
+
+const CHART_WIDTH: usize = 60;
+const CHART_HEIGHT: usize = 12;
+const COLUMN_WIDTH: usize = 16;
+
+/// One column of the report: a profile's sessions, or one of them.
+pub struct Contender {
+    label: String,
+    sessions: Vec<SessionRecord>,
+    integrity: Integrity,
+}
+
+impl Contender {
+    /// Loads `PROFILE` (every session) or `PROFILE:N` (its Nth session,
+    /// counting from 1).
+    pub fn load(spec: &str) -> Result<Self, String> {
+        let (profile, index) = match spec.rsplit_once(':') {
+            Some((profile, n)) => {
+                let n: usize = n
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid session number in '{}'", spec))?;
+                (profile, Some(n))
+            }
+            None => (spec, None),
+        };
+        let log = SessionLog::new(profile);
+        let mut sessions = log.load()?;
+        if sessions.is_empty() {
+            return Err(format!("profile '{}' has no recorded sessions", profile));
+        }
+        let label = match index {
+            Some(n) if n <= sessions.len() => {
+                sessions = vec![sessions.swap_remove(n - 1)];
+                format!("{} #{}", profile, n)
+            }
+            Some(n) => {
+                return Err(format!(
+                    "profile '{}' has {} sessions, no session {}",
+                    profile,
+                    sessions.len(),
+                    n
+                ));
+            }
+            None => profile.to_string(),
+        };
+        Ok(Self {
+            label,
+            sessions,
+            integrity: log.integrity(),
+        })
+    }
+
+    fn rounds(&self) -> usize {
+        self.sessions.iter().map(SessionRecord::rounds).sum()
+    }
+
+    fn wagered(&self) -> Money {
+        self.sessions.iter().map(|s| s.wagered).sum()
+    }
+
+    fn returned(&self) -> Money {
+        self.sessions.iter().map(|s| s.returned).sum()
+    }
+
+    fn rtp(&self) -> Option<f64> {
+        let wagered = self.wagered();
+        (wagered > Money::ZERO).then(|| self.returned().as_dollars() / wagered.as_dollars() * 100.0)
+    }
+
+    /// The bet placed in the most rounds.
+    fn favorite_bet(&self) -> Option<String> {
+        let mut counts = std::collections::BTreeMap::new();
+        for session in &self.sessions {
+            for (bet, count) in &session.bets {
+                *counts.entry(bet.as_str()).or_insert(0) += count;
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .map(|(bet, _)| bet.to_string())
+    }
+
+    /// Running net result over every round in whole dollars, sessions laid
+    /// end to end.
+    fn curve(&self) -> Vec<i32> {
+        let mut offset = Money::ZERO;
+        let mut curve = Vec::with_capacity(self.rounds());
+        for session in &self.sessions {
+            curve.extend(session.bankroll.iter().map(|&balance| {
+                (offset + balance - session.starting_balance).whole_dollars() as i32
+            }));
+            offset += session.net();
+        }
+        curve
+    }
+}
+
+fn marker(index: usize) -> char {
+    (b'A' + (index % 26) as u8) as char
+}
+
+/// Plots every curve on shared axes, one letter per curve; `*` marks where
+/// two cross and `·` the break-even line.
+fn overlay_chart(curves: &[Vec<i32>]) -> Vec<String> {
+    let longest = curves.iter().map(Vec::len).max().unwrap_or(0);
+    if longest == 0 {
+        return Vec::new();
+    }
+    let values = || curves.iter().flatten().copied().chain([0]);
+    let high = values().max().unwrap_or(0);
+    let low = values().min().unwrap_or(0);
+    let span = (high - low).max(1) as f64;
+    let row_of =
+        |value: i32| ((high - value) as f64 / span * (CHART_HEIGHT - 1) as f64).round() as usize;
+
+    let columns = CHART_WIDTH.min(longest);
+    let mut grid = vec![vec![' '; columns]; CHART_HEIGHT];
+    grid[row_of(0)].fill('·');
+    for (index, curve) in curves.iter().enumerate() {
+        for (column, round) in (0..columns)
+            .map(|column| column * longest / columns)
+            .enumerate()
+        {
+            let Some(&value) = curve.get(round) else {
+                break;
+            };
+            let cell = &mut grid[row_of(value)][column];
+            *cell = if cell.is_ascii_uppercase() && *cell != marker(index) {
+                '*'
+            } else {
+                marker(index)
+            };
+        }
+    }
+
+    grid.into_iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let label = match row {
+                0 => format!("{:+}", high),
+                r if r == CHART_HEIGHT - 1 => format!("{:+}", low),
+                _ => String::new(),
+            };
+            format!("{:>8} |{}", label, cells.into_iter().collect::<String>())
+        })
+        .collect()
+}
+
+/// Loads every contender and writes the report to `out`.
+pub fn run(out: &mut impl Write, specs: &[String]) -> Result<(), String> {
+    let contenders = specs
+        .iter()
+        .map(|spec| Contender::load(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+    write_report(out, &contenders).map_err(|e| e.to_string())
+}
+
+fn write_report(out: &mut impl Write, contenders: &[Contender]) -> io::Result<()> {
+    let header: String = contenders
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            format!(
+                "{:>width$}",
+                format!("{}: {}", marker(index), c.label),
+                width = COLUMN_WIDTH
+            )
+        })
+        .collect();
+    writeln!(out, "{:<20}{}", "", header)?;
+    let mut row = |name: &str, cell: &dyn Fn(&Contender) -> String| {
+        let cells: String = contenders
+            .iter()
+            .map(|c| format!("{:>width$}", cell(c), width = COLUMN_WIDTH))
+            .collect();
+        writeln!(out, "{:<20}{}", name, cells)
+    };
+    row("Sessions", &|c| c.sessions.len().to_string())?;
+    row("Rounds", &|c| c.rounds().to_string())?;
+    row("Wagered", &|c| c.wagered().to_string())?;
+    row("Returned", &|c| c.returned().to_string())?;
+    row("RTP", &|c| {
+        c.rtp()
+            .map_or("-".to_string(), |rtp| format!("{:.1}%", rtp))
+    })?;
+    row("Net", &|c| format!("{:+}", c.returned() - c.wagered()))?;
+    row("Favorite bet", &|c| {
+        c.favorite_bet().unwrap_or_else(|| "-".to_string())
+    })?;
+    row("Longest win run", &|c| {
+        c.sessions
+            .iter()
+            .map(|s| s.longest_win_streak)
+            .max()
+            .unwrap_or(0)
+            .to_string()
+    })?;
+    row("Longest loss run", &|c| {
+        c.sessions
+            .iter()
+            .map(|s| s.longest_loss_streak)
+            .max()
+            .unwrap_or(0)
+            .to_string()
+    })?;
+    row("Save file", &|c| c.integrity.label().to_string())?;
+    if contenders
+        .iter()
+        .any(|c| c.integrity == Integrity::Tampered)
+    {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "Warning: a session file was edited outside the game; its figures can't be trusted."
+        )?;
+    }
+
+    let curves: Vec<Vec<i32>> = contenders.iter().map(Contender::curve).collect();
+    writeln!(out)?;
+    writeln!(out, "Net result by round played:")?;
+    for line in overlay_chart(&curves) {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,600 @@
+//! User settings: how a fresh table opens (starting balance, game mode,
+//! decks, chips), key bindings, animation speed, autoplay and blackjack
+//! house rules in `config.toml`, colors in its `[theme]` or in `theme.toml`, both in the
+//! config directory. The ratatui table watches the files while it is open,
+//! so edits apply live; the table defaults wait for the next launch.
+
+use crate::baccarat::{CHIP_VALUES, GameMode, Money, RngChoice, SHOE_DECKS, STARTING_BALANCE};
+use crate::blackjack::BlackjackRules;
+use crate::storage;
+use crate::strategy::StrategyKind;
+use crate::theme::Theme;
+use crossterm::event::KeyCode;
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the files' modification times are checked.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_ANIMATION_SPEED: f64 = 10.0;
+/// Bounds on the pause between autoplay hands, in seconds.
+pub const MIN_AUTOPLAY_DELAY: f64 = 0.1;
+pub const MAX_AUTOPLAY_DELAY: f64 = 10.0;
+
+/// Table commands that can be bound to a different key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Player,
+    Banker,
+    Tie,
+    Deal,
+    Mode,
+    SavePreset,
+    Natural,
+    SuperSix,
+    Big,
+    Small,
+    MonkeySix,
+    MonkeyTie,
+    SuitedTie,
+    TieScores,
+    Stats,
+    Odds,
+    Significance,
+    Count,
+    History,
+    Export,
+    Games,
+    Autoplay,
+    Quit,
+}
+
+impl Action {
+    const ALL: [Action; 23] = [
+        Action::Player,
+        Action::Banker,
+        Action::Tie,
+        Action::Deal,
+        Action::Mode,
+        Action::SavePreset,
+        Action::Natural,
+        Action::SuperSix,
+        Action::Big,
+        Action::Small,
+        Action::MonkeySix,
+        Action::MonkeyTie,
+        Action::SuitedTie,
+        Action::TieScores,
+        Action::Stats,
+        Action::Odds,
+        Action::Significance,
+        Action::Count,
+        Action::History,
+        Action::Export,
+        Action::Games,
+        Action::Autoplay,
+        Action::Quit,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Action::Player => "player",
+            Action::Banker => "banker",
+            Action::Tie => "tie",
+            Action::Deal => "deal",
+            Action::Mode => "mode",
+            Action::SavePreset => "save_preset",
+            Action::Natural => "natural",
+            Action::SuperSix => "super_6",
+            Action::Big => "big",
+            Action::Small => "small",
+            Action::MonkeySix => "monkey_6",
+            Action::MonkeyTie => "monkey_tie",
+            Action::SuitedTie => "suited_tie",
+            Action::TieScores => "tie_scores",
+            Action::Stats => "stats",
+            Action::Odds => "odds",
+            Action::Significance => "significance",
+            Action::Count => "count",
+            Action::History => "history",
+            Action::Export => "export",
+            Action::Games => "games",
+            Action::Autoplay => "autoplay",
+            Action::Quit => "quit",
+        }
+    }
+
+    fn default_key(&self) -> char {
+        match self {
+            Action::Player => 'p',
+            Action::Banker => 'b',
+            Action::Tie => 't',
+            Action::Deal => ' ',
+            Action::Mode => 'm',
+            Action::SavePreset => 'v',
+            Action::Natural => 'n',
+            Action::SuperSix => '6',
+            Action::Big => 'g',
+            Action::Small => 'l',
+            Action::MonkeySix => 'k',
+            Action::MonkeyTie => 'j',
+            Action::SuitedTie => 'u',
+            Action::TieScores => 'y',
+            Action::Stats => 's',
+            Action::Odds => 'o',
+            Action::Significance => 'i',
+            Action::Count => 'r',
+            Action::History => 'h',
+            Action::Export => 'f',
+            Action::Games => 'e',
+            Action::Autoplay => 'a',
+            Action::Quit => 'q',
+        }
+    }
+}
+
+/// Keys with fixed meanings (chips and bet steps), which no action may take.
+const RESERVED_KEYS: &str = "12345+=-";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    keys: [char; Action::ALL.len()],
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.map(|action| action.default_key()),
+        }
+    }
+}
+
+impl Keymap {
+    /// Applies `[keys]` overrides (`player = "z"`, `deal = "space"`) on top
+    /// of the defaults, rejecting keys bound twice.
+    fn with_overrides(overrides: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        for (name, key) in overrides {
+            let index = Action::ALL
+                .iter()
+                .position(|action| action.name() == name)
+                .ok_or_else(|| format!("unknown action '{}' in [keys]", name))?;
+            keymap.keys[index] = match key.to_lowercase().as_str() {
+                "space" => ' ',
+                _ => {
+                    let mut chars = key.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) if !c.is_control() => c,
+                        _ => {
+                            return Err(format!(
+                                "invalid key '{}' for {}, expected one character or \"space\"",
+                                key, name
+                            ));
+                        }
+                    }
+                }
+            };
+            if RESERVED_KEYS.contains(keymap.keys[index]) {
+                return Err(format!("'{}' is reserved for chips and bet steps", key));
+            }
+        }
+        for (index, key) in keymap.keys.iter().enumerate() {
+            if let Some(other) = keymap.keys[index + 1..].iter().position(|k| k == key) {
+                return Err(format!(
+                    "'{}' is bound to both {} and {}",
+                    key_label(*key),
+                    Action::ALL[index].name(),
+                    Action::ALL[index + 1 + other].name()
+                ));
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        let KeyCode::Char(c) = code else {
+            return None;
+        };
+        self.keys
+            .iter()
+            .position(|&key| key == c)
+            .map(|index| Action::ALL[index])
+    }
+
+    /// The key for `action` as shown in the controls, e.g. "P" or "SPACE".
+    pub fn label(&self, action: Action) -> String {
+        key_label(self.keys[action as usize])
+    }
+}
+
+fn key_label(key: char) -> String {
+    match key {
+        ' ' => "SPACE".to_string(),
+        c => c.to_uppercase().to_string(),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: Option<String>,
+    felt: Option<String>,
+    border: Option<String>,
+    red_suit: Option<String>,
+    black_suit: Option<String>,
+    card_back: Option<String>,
+    chips: Option<Vec<String>>,
+    player: Option<String>,
+    banker: Option<String>,
+    tie: Option<String>,
+    win: Option<String>,
+    loss: Option<String>,
+    accent: Option<String>,
+    balance: Option<String>,
+    bet: Option<String>,
+    side_bet: Option<String>,
+    alert: Option<String>,
+    highlight: Option<String>,
+    muted: Option<String>,
+}
+
+fn parse_color(value: &str, name: &str) -> Result<Color, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid color '{}' for {}", value, name))
+}
+
+impl ThemeFile {
+    /// The theme this file names, or `base` if it names none, with the
+    /// colors it sets laid over it.
+    fn lay_over(self, base: Theme) -> Result<Theme, String> {
+        let mut theme = match &self.name {
+            Some(name) => Theme::named(name).ok_or_else(|| {
+                let names: Vec<&str> = Theme::BUILT_IN.iter().map(|theme| theme.name).collect();
+                format!(
+                    "unknown theme '{}', expected one of {}",
+                    name,
+                    names.join(", ")
+                )
+            })?,
+            None => base,
+        };
+        for (name, value, slot) in [
+            ("felt", self.felt, &mut theme.felt),
+            ("border", self.border, &mut theme.border),
+            ("red_suit", self.red_suit, &mut theme.red_suit),
+            ("black_suit", self.black_suit, &mut theme.black_suit),
+            ("card_back", self.card_back, &mut theme.card_back),
+            ("player", self.player, &mut theme.player),
+            ("banker", self.banker, &mut theme.banker),
+            ("tie", self.tie, &mut theme.tie),
+            ("win", self.win, &mut theme.win),
+            ("loss", self.loss, &mut theme.loss),
+            ("accent", self.accent, &mut theme.accent),
+            ("balance", self.balance, &mut theme.balance),
+            ("bet", self.bet, &mut theme.bet),
+            ("side_bet", self.side_bet, &mut theme.side_bet),
+            ("alert", self.alert, &mut theme.alert),
+            ("highlight", self.highlight, &mut theme.highlight),
+            ("muted", self.muted, &mut theme.muted),
+        ] {
+            if let Some(value) = value {
+                *slot = parse_color(&value, name)?;
+            }
+        }
+        if let Some(chips) = self.chips {
+            if chips.len() != theme.chips.len() {
+                return Err(format!(
+                    "chips needs {} colors, smallest chip first",
+                    theme.chips.len()
+                ));
+            }
+            for (slot, value) in theme.chips.iter_mut().zip(&chips) {
+                *slot = parse_color(value, "chips")?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+/// How a fresh baccarat table opens, before any rules bundle or flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableDefaults {
+    pub starting_balance: Money,
+    pub mode: GameMode,
+    pub decks: usize,
+    /// The chips under keys 1-5, smallest first.
+    pub chips: [i32; 5],
+    /// What the baccarat shoes shuffle from.
+    pub rng: RngChoice,
+}
+
+impl Default for TableDefaults {
+    fn default() -> Self {
+        Self {
+            starting_balance: STARTING_BALANCE,
+            mode: GameMode::Classic,
+            decks: SHOE_DECKS,
+            chips: CHIP_VALUES,
+            rng: RngChoice::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TableSection {
+    starting_balance: Option<i32>,
+    mode: Option<String>,
+    decks: Option<usize>,
+    chips: Option<Vec<i32>>,
+    rng: Option<String>,
+}
+
+impl TableDefaults {
+    fn from_section(section: TableSection) -> Result<Self, String> {
+        let mut table = Self::default();
+        if let Some(balance) = section.starting_balance {
+            if balance <= 0 {
+                return Err("starting balance must be above 0".to_string());
+            }
+            table.starting_balance = Money::from_dollars(balance);
+        }
+        if let Some(mode) = section.mode {
+            table.mode = mode.parse()?;
+        }
+        if let Some(decks) = section.decks {
+            if !(1..=8).contains(&decks) {
+                return Err("decks must be 1-8".to_string());
+            }
+            table.decks = decks;
+        }
+        if let Some(chips) = section.chips {
+            table.chips = chips
+                .try_into()
+                .ok()
+                .filter(|chips: &[i32; 5]| {
+                    chips[0] > 0 && chips.windows(2).all(|pair| pair[0] < pair[1])
+                })
+                .ok_or("chips must be five amounts above 0, smallest first")?;
+        }
+        if let Some(rng) = section.rng {
+            table.rng = rng.parse()?;
+        }
+        Ok(table)
+    }
+}
+
+/// How autoplay bets, how fast, and when it stops of its own accord.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoplaySettings {
+    pub strategy: StrategyKind,
+    /// Pause between hands once the last is face up, in seconds.
+    pub delay: f64,
+    /// Stop after this many hands.
+    pub hands: Option<u32>,
+    /// Stop once the seat is this far up, or down, since autoplay started.
+    pub profit_target: Option<Money>,
+    pub loss_limit: Option<Money>,
+}
+
+impl Default for AutoplaySettings {
+    fn default() -> Self {
+        Self {
+            strategy: StrategyKind::Flat,
+            delay: 0.8,
+            hands: None,
+            profit_target: None,
+            loss_limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AutoplaySection {
+    strategy: Option<String>,
+    delay: Option<f64>,
+    hands: Option<u32>,
+    profit_target: Option<i32>,
+    loss_limit: Option<i32>,
+}
+
+impl AutoplaySettings {
+    fn from_section(section: AutoplaySection) -> Result<Self, String> {
+        let mut autoplay = Self::default();
+        if let Some(strategy) = section.strategy {
+            autoplay.strategy = strategy.parse()?;
+        }
+        if let Some(delay) = section.delay {
+            if !(MIN_AUTOPLAY_DELAY..=MAX_AUTOPLAY_DELAY).contains(&delay) {
+                return Err(format!(
+                    "autoplay delay must be {} to {} seconds",
+                    MIN_AUTOPLAY_DELAY, MAX_AUTOPLAY_DELAY
+                ));
+            }
+            autoplay.delay = delay;
+        }
+        if section.hands == Some(0) {
+            return Err("autoplay hands must be above 0".to_string());
+        }
+        autoplay.hands = section.hands;
+        let amount = |value: Option<i32>, name: &str| match value {
+            Some(dollars) if dollars <= 0 => Err(format!("autoplay {} must be above 0", name)),
+            value => Ok(value.map(Money::from_dollars)),
+        };
+        autoplay.profit_target = amount(section.profit_target, "profit_target")?;
+        autoplay.loss_limit = amount(section.loss_limit, "loss_limit")?;
+        Ok(autoplay)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    table: TableSection,
+    #[serde(default)]
+    keys: BTreeMap<String, String>,
+    #[serde(default)]
+    animation: AnimationSection,
+    #[serde(default)]
+    autoplay: AutoplaySection,
+    #[serde(default)]
+    blackjack: BlackjackSection,
+    #[serde(default)]
+    theme: ThemeFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnimationSection {
+    speed: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlackjackSection {
+    dealer_hits_soft_17: Option<bool>,
+    surrender: Option<bool>,
+    double_after_split: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub table: TableDefaults,
+    pub keys: Keymap,
+    /// Multiplier on the card reveal rate; 2.0 deals twice as fast.
+    pub animation_speed: f64,
+    pub autoplay: AutoplaySettings,
+    pub theme: Theme,
+    pub blackjack: BlackjackRules,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            table: TableDefaults::default(),
+            keys: Keymap::default(),
+            animation_speed: 1.0,
+            autoplay: AutoplaySettings::default(),
+            theme: Theme::default(),
+            blackjack: BlackjackRules::default(),
+        }
+    }
+}
+
+/// A settings file in the config directory, or in the data directory where
+/// earlier versions kept it, if only that one exists.
+fn settings_path(name: &str) -> PathBuf {
+    let path = storage::config_dir().join(name);
+    let legacy = storage::data_dir().join(name);
+    if !path.exists() && legacy.exists() {
+        legacy
+    } else {
+        path
+    }
+}
+
+fn config_path() -> PathBuf {
+    settings_path("config.toml")
+}
+
+fn theme_path() -> PathBuf {
+    settings_path("theme.toml")
+}
+
+/// Reads a settings file; a missing one means all defaults.
+fn read_toml<T: Default + for<'de> Deserialize<'de>>(path: &PathBuf) -> Result<T, String> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    match std::fs::read_to_string(path) {
+        Ok(source) => toml::from_str(&source).map_err(|e| format!("{}: {}", name, e.message())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("{}: {}", name, e)),
+    }
+}
+
+impl Config {
+    /// Loads both files, naming the file and the problem if either is
+    /// invalid.
+    pub fn load() -> Result<Self, String> {
+        let file: ConfigFile = read_toml(&config_path())?;
+        let table =
+            TableDefaults::from_section(file.table).map_err(|e| format!("config.toml: {}", e))?;
+        let keys = Keymap::with_overrides(&file.keys).map_err(|e| format!("config.toml: {}", e))?;
+        let animation_speed = file.animation.speed.unwrap_or(1.0);
+        if !(animation_speed > 0.0 && animation_speed <= MAX_ANIMATION_SPEED) {
+            return Err(format!(
+                "config.toml: animation speed must be above 0 and at most {}",
+                MAX_ANIMATION_SPEED
+            ));
+        }
+        let autoplay = AutoplaySettings::from_section(file.autoplay)
+            .map_err(|e| format!("config.toml: {}", e))?;
+        let defaults = BlackjackRules::default();
+        let blackjack = BlackjackRules {
+            dealer_hits_soft_17: file
+                .blackjack
+                .dealer_hits_soft_17
+                .unwrap_or(defaults.dealer_hits_soft_17),
+            surrender: file.blackjack.surrender.unwrap_or(defaults.surrender),
+            double_after_split: file
+                .blackjack
+                .double_after_split
+                .unwrap_or(defaults.double_after_split),
+            ..defaults
+        };
+        let theme = file
+            .theme
+            .lay_over(Theme::default())
+            .map_err(|e| format!("config.toml: {}", e))?;
+        let theme = read_toml::<ThemeFile>(&theme_path())?
+            .lay_over(theme)
+            .map_err(|e| format!("theme.toml: {}", e))?;
+        Ok(Self {
+            table,
+            keys,
+            animation_speed,
+            autoplay,
+            theme,
+            blackjack,
+        })
+    }
+}
+
+/// Notices when either settings file is created, edited, or removed.
+pub struct ConfigWatcher {
+    stamps: [Option<SystemTime>; 2],
+    next_check: Instant,
+}
+
+fn stamps() -> [Option<SystemTime>; 2] {
+    [config_path(), theme_path()]
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Self {
+        Self {
+            stamps: stamps(),
+            next_check: Instant::now() + WATCH_INTERVAL,
+        }
+    }
+
+    /// The freshly loaded settings (or why they could not be loaded) if a
+    /// file changed since the last call.
+    pub fn poll(&mut self) -> Option<Result<Config, String>> {
+        if Instant::now() < self.next_check {
+            return None;
+        }
+        self.next_check = Instant::now() + WATCH_INTERVAL;
+        let current = stamps();
+        if current == self.stamps {
+            return None;
+        }
+        self.stamps = current;
+        Some(Config::load())
+    }
+}
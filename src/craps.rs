@@ -0,0 +1,443 @@
+//! Craps: the come-out roll and the point, pass and don't pass with odds,
+//! come bets that travel to their own points, the field, and place bets.
+//! Stakes are checked against the same `TableLimits` as baccarat; the
+//! caller takes them from the bankroll and pays back what each roll's
+//! resolutions return.
+
+use crate::baccarat::{Money, ShuffleRng, TableLimits};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Totals that become the point on a come-out roll.
+pub const POINTS: [u8; 6] = [4, 5, 6, 8, 9, 10];
+
+/// Free odds allowed behind a pass or come bet, as a multiple of the flat
+/// bet: 3x on 4 and 10, 4x on 5 and 9, 5x on 6 and 8.
+fn odds_multiple(point: u8) -> i64 {
+    match point {
+        4 | 10 => 3,
+        5 | 9 => 4,
+        _ => 5,
+    }
+}
+
+/// Laying 6x the flat bet wins the same 3-4-5x a taker could win.
+const LAY_MULTIPLE: i64 = 6;
+
+/// What `stake` wins at `num:den`.
+fn winnings(stake: Money, num: i64, den: i64) -> Money {
+    Money::from_cents(stake.cents() * num / den)
+}
+
+/// Two dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roll {
+    pub dice: [u8; 2],
+}
+
+impl Roll {
+    pub fn new(first: u8, second: u8) -> Self {
+        Self {
+            dice: [first, second],
+        }
+    }
+
+    /// Two dice thrown with `rng`.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(rng.random_range(1..=6), rng.random_range(1..=6))
+    }
+
+    pub fn total(&self) -> u8 {
+        self.dice[0] + self.dice[1]
+    }
+
+    /// Both dice the same, e.g. a hard 8.
+    pub fn is_hard(&self) -> bool {
+        self.dice[0] == self.dice[1]
+    }
+}
+
+/// A spot on the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bet {
+    PassLine,
+    PassOdds,
+    DontPass,
+    DontPassOdds,
+    /// A come bet waiting for its first roll.
+    Come,
+    /// A come bet moved to its point.
+    ComePoint(u8),
+    ComeOdds(u8),
+    /// One roll: 2, 3, 4, 9, 10, 11 or 12.
+    Field,
+    Place(u8),
+}
+
+impl std::fmt::Display for Bet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Bet::PassLine => write!(f, "Pass Line"),
+            Bet::PassOdds => write!(f, "Pass Odds"),
+            Bet::DontPass => write!(f, "Don't Pass"),
+            Bet::DontPassOdds => write!(f, "Don't Pass Odds"),
+            Bet::Come => write!(f, "Come"),
+            Bet::ComePoint(point) => write!(f, "Come {}", point),
+            Bet::ComeOdds(point) => write!(f, "Come {} Odds", point),
+            Bet::Field => write!(f, "Field"),
+            Bet::Place(number) => write!(f, "Place {}", number),
+        }
+    }
+}
+
+/// How a bet fared on a roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Won this much on top of the stake.
+    Won(Money),
+    Lost,
+    /// Neither won nor lost, e.g. don't pass on a 12.
+    Push,
+    /// A come bet moved to this point.
+    Moved(u8),
+}
+
+/// One bet's result on a roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    pub bet: Bet,
+    pub stake: Money,
+    pub outcome: Outcome,
+    /// What goes back to the bankroll: the winnings, plus the stake when
+    /// the bet comes down. A winning place bet stays up.
+    pub returned: Money,
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.outcome {
+            Outcome::Won(amount) => write!(f, "{} wins ${}", self.bet, amount),
+            Outcome::Lost => write!(f, "{} loses ${}", self.bet, self.stake),
+            Outcome::Push => write!(f, "{} pushes", self.bet),
+            Outcome::Moved(point) => write!(f, "Come moves to {}", point),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrapsError {
+    /// Pass and don't pass are made on the come-out roll.
+    PointIsOn,
+    /// Come bets and pass or don't pass odds need a point.
+    NoPoint,
+    /// Odds go behind a flat bet that is not there.
+    NoFlatBet(Bet),
+    /// Not one of the point numbers.
+    NotAPoint(u8),
+    /// More odds than 3-4-5x (6x laid) allows.
+    OddsAboveMax(Money),
+    BelowMin(Money),
+    AboveMax(Money),
+    /// Line and come bets stay up until they are decided.
+    ContractBet(Bet),
+}
+
+impl std::fmt::Display for CrapsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CrapsError::PointIsOn => write!(f, "line bets are made on the come-out roll"),
+            CrapsError::NoPoint => write!(f, "there is no point yet"),
+            CrapsError::NoFlatBet(bet) => write!(f, "odds need a {} bet", bet),
+            CrapsError::NotAPoint(number) => write!(f, "{} is not a point number", number),
+            CrapsError::OddsAboveMax(max) => write!(f, "odds are limited to ${} here", max),
+            CrapsError::BelowMin(min) => write!(f, "the table minimum is ${}", min),
+            CrapsError::AboveMax(max) => write!(f, "the table maximum is ${}", max),
+            CrapsError::ContractBet(bet) => write!(f, "{} stays up until it is decided", bet),
+        }
+    }
+}
+
+impl std::error::Error for CrapsError {}
+
+/// A craps table: the point and the bets on the layout.
+pub struct CrapsGame {
+    limits: TableLimits,
+    /// `None` on the come-out roll.
+    pub point: Option<u8>,
+    bets: BTreeMap<Bet, Money>,
+    /// Rolls since the table opened, oldest first.
+    pub rolls: Vec<Roll>,
+    /// Where the dice come from; the thread's generator when unset.
+    rng: Option<ShuffleRng>,
+}
+
+impl CrapsGame {
+    pub fn new(limits: TableLimits) -> Self {
+        Self {
+            limits,
+            point: None,
+            bets: BTreeMap::new(),
+            rolls: Vec::new(),
+            rng: None,
+        }
+    }
+
+    /// Throws the dice with `rng` from now on; a seeded one rolls the same
+    /// numbers every time.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    pub fn bets(&self) -> impl Iterator<Item = (Bet, Money)> + '_ {
+        self.bets.iter().map(|(&bet, &stake)| (bet, stake))
+    }
+
+    pub fn stake(&self, bet: Bet) -> Money {
+        self.bets.get(&bet).copied().unwrap_or_default()
+    }
+
+    pub fn total_on_table(&self) -> Money {
+        self.bets.values().copied().sum()
+    }
+
+    /// The flat bet `bet`'s odds go behind, and the point they are on.
+    fn odds_base(&self, bet: Bet) -> Option<(Bet, u8)> {
+        match bet {
+            Bet::PassOdds => Some((Bet::PassLine, self.point?)),
+            Bet::DontPassOdds => Some((Bet::DontPass, self.point?)),
+            Bet::ComeOdds(point) => Some((Bet::ComePoint(point), point)),
+            _ => None,
+        }
+    }
+
+    /// Most odds `bet` may hold with the flat bet as it is.
+    pub fn max_odds(&self, bet: Bet) -> Money {
+        let Some((flat, point)) = self.odds_base(bet) else {
+            return Money::ZERO;
+        };
+        let multiple = if bet == Bet::DontPassOdds {
+            LAY_MULTIPLE
+        } else {
+            odds_multiple(point)
+        };
+        self.stake(flat) * multiple
+    }
+
+    /// Adds `stake` to `bet`, checking when it may be made and the limits.
+    /// Odds are limited by the flat bet instead of the table maximum.
+    pub fn place(&mut self, bet: Bet, stake: Money) -> Result<(), CrapsError> {
+        match bet {
+            Bet::PassLine | Bet::DontPass if self.point.is_some() => {
+                return Err(CrapsError::PointIsOn);
+            }
+            Bet::Come if self.point.is_none() => return Err(CrapsError::NoPoint),
+            Bet::Place(number) | Bet::ComePoint(number) | Bet::ComeOdds(number)
+                if !POINTS.contains(&number) =>
+            {
+                return Err(CrapsError::NotAPoint(number));
+            }
+            Bet::ComePoint(_) => return Err(CrapsError::ContractBet(bet)),
+            _ => {}
+        }
+        let total = self.stake(bet) + stake;
+        if matches!(bet, Bet::PassOdds | Bet::DontPassOdds | Bet::ComeOdds(_)) {
+            let Some((flat, _)) = self.odds_base(bet) else {
+                return Err(CrapsError::NoPoint);
+            };
+            if self.stake(flat) == Money::ZERO {
+                return Err(CrapsError::NoFlatBet(flat));
+            }
+            if total > self.max_odds(bet) {
+                return Err(CrapsError::OddsAboveMax(self.max_odds(bet)));
+            }
+        } else if total < Money::from_dollars(self.limits.min_bet) {
+            return Err(CrapsError::BelowMin(Money::from_dollars(
+                self.limits.min_bet,
+            )));
+        } else if total > Money::from_dollars(self.limits.max_bet) {
+            return Err(CrapsError::AboveMax(Money::from_dollars(
+                self.limits.max_bet,
+            )));
+        }
+        self.bets.insert(bet, total);
+        Ok(())
+    }
+
+    /// Takes a place, field or odds bet down and returns its stake. Line
+    /// and come bets stay up until they are decided.
+    pub fn take_down(&mut self, bet: Bet) -> Result<Money, CrapsError> {
+        if matches!(
+            bet,
+            Bet::PassLine | Bet::DontPass | Bet::Come | Bet::ComePoint(_)
+        ) {
+            return Err(CrapsError::ContractBet(bet));
+        }
+        Ok(self.bets.remove(&bet).unwrap_or_default())
+    }
+
+    /// Rolls the dice and settles the layout.
+    pub fn roll(&mut self) -> (Roll, Vec<Resolution>) {
+        let roll = match &mut self.rng {
+            Some(rng) => Roll::random(rng),
+            None => Roll::random(&mut rand::rng()),
+        };
+        (roll, self.resolve(roll))
+    }
+
+    /// Settles every bet the roll decides, moves come bets to their
+    /// points, and turns the point on or off.
+    pub fn resolve(&mut self, roll: Roll) -> Vec<Resolution> {
+        self.rolls.push(roll);
+        let total = roll.total();
+        let mut resolutions = Vec::new();
+        // Decides `bet`, if it is up, with an outcome worked out from its stake.
+        let mut settle =
+            |bets: &mut BTreeMap<Bet, Money>, bet: Bet, outcome: &dyn Fn(Money) -> Outcome| {
+                let Some(&stake) = bets.get(&bet) else {
+                    return;
+                };
+                let outcome = outcome(stake);
+                // A winning place bet and don't pass barred on a 12 stay up.
+                let stays = matches!(
+                    (bet, outcome),
+                    (Bet::Place(_), Outcome::Won(_)) | (Bet::DontPass, Outcome::Push)
+                );
+                let returned = match outcome {
+                    Outcome::Won(amount) if stays => amount,
+                    Outcome::Won(amount) => stake + amount,
+                    Outcome::Push if stays => Money::ZERO,
+                    Outcome::Push => stake,
+                    Outcome::Lost | Outcome::Moved(_) => Money::ZERO,
+                };
+                if !stays {
+                    bets.remove(&bet);
+                }
+                resolutions.push(Resolution {
+                    bet,
+                    stake,
+                    outcome,
+                    returned,
+                });
+            };
+        let even = |stake| Outcome::Won(stake);
+        let lost = |_| Outcome::Lost;
+        let push = |_| Outcome::Push;
+
+        // The field is decided every roll.
+        let field = match total {
+            2 => 2,
+            12 => 3,
+            3 | 4 | 9 | 10 | 11 => 1,
+            _ => 0,
+        };
+        settle(&mut self.bets, Bet::Field, &|stake| {
+            if field > 0 {
+                Outcome::Won(stake * field)
+            } else {
+                Outcome::Lost
+            }
+        });
+
+        // Place bets and come odds are off on the come-out roll.
+        let point_on = self.point.is_some();
+        for number in POINTS {
+            if point_on && total == number {
+                let (num, den) = match number {
+                    4 | 10 => (9, 5),
+                    5 | 9 => (7, 5),
+                    _ => (7, 6),
+                };
+                settle(&mut self.bets, Bet::Place(number), &|stake| {
+                    Outcome::Won(winnings(stake, num, den))
+                });
+            } else if point_on && total == 7 {
+                settle(&mut self.bets, Bet::Place(number), &lost);
+            }
+
+            let (flat, odds) = (Bet::ComePoint(number), Bet::ComeOdds(number));
+            if total == number {
+                settle(&mut self.bets, flat, &even);
+                if point_on {
+                    settle(&mut self.bets, odds, &|stake| {
+                        Outcome::Won(true_odds(stake, number))
+                    });
+                } else {
+                    settle(&mut self.bets, odds, &push);
+                }
+            } else if total == 7 {
+                settle(&mut self.bets, flat, &lost);
+                settle(&mut self.bets, odds, if point_on { &lost } else { &push });
+            }
+        }
+
+        // The come bet in the box plays like a pass line bet on a come-out.
+        match total {
+            7 | 11 => settle(&mut self.bets, Bet::Come, &even),
+            2 | 3 | 12 => settle(&mut self.bets, Bet::Come, &lost),
+            point => {
+                let come = self.stake(Bet::Come);
+                settle(&mut self.bets, Bet::Come, &|_| Outcome::Moved(point));
+                if come > Money::ZERO {
+                    *self.bets.entry(Bet::ComePoint(point)).or_default() += come;
+                }
+            }
+        }
+
+        match self.point {
+            None => match total {
+                7 | 11 => {
+                    settle(&mut self.bets, Bet::PassLine, &even);
+                    settle(&mut self.bets, Bet::DontPass, &lost);
+                }
+                2 | 3 => {
+                    settle(&mut self.bets, Bet::PassLine, &lost);
+                    settle(&mut self.bets, Bet::DontPass, &even);
+                }
+                12 => {
+                    settle(&mut self.bets, Bet::PassLine, &lost);
+                    settle(&mut self.bets, Bet::DontPass, &push);
+                }
+                point => self.point = Some(point),
+            },
+            Some(point) if total == point => {
+                settle(&mut self.bets, Bet::PassLine, &even);
+                settle(&mut self.bets, Bet::PassOdds, &|stake| {
+                    Outcome::Won(true_odds(stake, point))
+                });
+                settle(&mut self.bets, Bet::DontPass, &lost);
+                settle(&mut self.bets, Bet::DontPassOdds, &lost);
+                self.point = None;
+            }
+            Some(point) if total == 7 => {
+                settle(&mut self.bets, Bet::PassLine, &lost);
+                settle(&mut self.bets, Bet::PassOdds, &lost);
+                settle(&mut self.bets, Bet::DontPass, &even);
+                settle(&mut self.bets, Bet::DontPassOdds, &|stake| {
+                    Outcome::Won(lay_odds(stake, point))
+                });
+                self.point = None;
+            }
+            Some(_) => {}
+        }
+        resolutions
+    }
+}
+
+/// What odds taken on `point` win: 2:1 on 4 and 10, 3:2 on 5 and 9, 6:5 on
+/// 6 and 8.
+fn true_odds(stake: Money, point: u8) -> Money {
+    match point {
+        4 | 10 => winnings(stake, 2, 1),
+        5 | 9 => winnings(stake, 3, 2),
+        _ => winnings(stake, 6, 5),
+    }
+}
+
+/// What odds laid against `point` win, the reverse of `true_odds`.
+fn lay_odds(stake: Money, point: u8) -> Money {
+    match point {
+        4 | 10 => winnings(stake, 1, 2),
+        5 | 9 => winnings(stake, 2, 3),
+        _ => winnings(stake, 5, 6),
+    }
+}
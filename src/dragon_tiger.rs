@@ -0,0 +1,22 @@
+//! Dragon Tiger: one card to each side from the baccarat shoe, and the
+//! higher card wins. It runs on the baccarat engine as
+//! `GameMode::DragonTiger`, with the Dragon in the player's seat and the
+//! Tiger in the banker's, so sessions, bet slips and both tables handle it
+//! like any other mode.
+
+use crate::baccarat::{Card, Money};
+
+/// Card points: aces are low at 1 and kings high at 13; suits do not count.
+pub fn card_value(card: &Card) -> u8 {
+    card.rank
+}
+
+/// Both cards share a rank and a suit, which the suited tie bet pays on.
+pub fn is_suited_tie(dragon: &Card, tiger: &Card) -> bool {
+    dragon == tiger
+}
+
+/// What a Dragon or Tiger bet returns when the cards tie: half the stake.
+pub fn tie_return(stake: Money) -> Money {
+    stake.percent(50)
+}
@@ -0,0 +1,168 @@
+use crate::baccarat::PayoutTable;
+use crate::storage;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Events shipped with the binary; `TERMINAL_CASINO_EVENTS` points at a
+/// replacement file so new events can be published without a rebuild.
+const BUILTIN_EVENTS: &str = include_str!("../data/events.toml");
+pub const EVENTS_PATH_VAR: &str = "TERMINAL_CASINO_EVENTS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl CalendarDate {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_unix(secs)
+    }
+
+    /// The UTC date of a Unix timestamp.
+    pub fn from_unix(secs: u64) -> Self {
+        Self::from_days_since_epoch((secs / 86_400) as i64)
+    }
+
+    // Howard Hinnant's civil_from_days, valid for the proleptic Gregorian calendar.
+    fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl std::fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Either a recurring "MM-DD" or a one-off "YYYY-MM-DD" date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventDate {
+    Yearly(u8, u8),
+    Once(CalendarDate),
+}
+
+impl EventDate {
+    fn parse(s: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = s.trim().split('-').collect();
+        let num = |p: &str| {
+            p.parse::<i32>()
+                .map_err(|_| format!("invalid date '{}'", s))
+        };
+        let (year, month, day) = match parts.as_slice() {
+            [m, d] => (None, num(m)?, num(d)?),
+            [y, m, d] => (Some(num(y)?), num(m)?, num(d)?),
+            _ => {
+                return Err(format!(
+                    "invalid date '{}', expected MM-DD or YYYY-MM-DD",
+                    s
+                ));
+            }
+        };
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(format!("invalid date '{}'", s));
+        }
+        Ok(match year {
+            Some(year) => EventDate::Once(CalendarDate {
+                year,
+                month: month as u8,
+                day: day as u8,
+            }),
+            None => EventDate::Yearly(month as u8, day as u8),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeasonalEvent {
+    pub name: String,
+    start: String,
+    end: String,
+    #[serde(default)]
+    pub banner: Option<String>,
+    #[serde(default = "default_card_back")]
+    pub card_back: char,
+    /// Bonus bet pay ratios replacing the standard table while the event runs.
+    #[serde(default)]
+    pub boosts: HashMap<String, i32>,
+}
+
+fn default_card_back() -> char {
+    '░'
+}
+
+impl SeasonalEvent {
+    pub fn is_active_on(&self, date: CalendarDate) -> bool {
+        let (Ok(start), Ok(end)) = (EventDate::parse(&self.start), EventDate::parse(&self.end))
+        else {
+            return false;
+        };
+        match (start, end) {
+            (EventDate::Once(start), EventDate::Once(end)) => start <= date && date <= end,
+            (EventDate::Yearly(sm, sd), EventDate::Yearly(em, ed)) => {
+                let today = (date.month, date.day);
+                if (sm, sd) <= (em, ed) {
+                    (sm, sd) <= today && today <= (em, ed)
+                } else {
+                    // Range wraps over the new year, e.g. 12-18 .. 01-02.
+                    today >= (sm, sd) || today <= (em, ed)
+                }
+            }
+            _ => false,
+        }
+    }
+
+    pub fn banner_text(&self) -> &str {
+        self.banner.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventCalendar {
+    #[serde(default, rename = "event")]
+    pub events: Vec<SeasonalEvent>,
+}
+
+impl EventCalendar {
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_EVENTS).unwrap_or_default()
+    }
+
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let calendar: Self = toml::from_str(source).map_err(|e| e.to_string())?;
+        for event in &calendar.events {
+            EventDate::parse(&event.start)?;
+            EventDate::parse(&event.end)?;
+            PayoutTable::default()
+                .with_overrides(&event.boosts)
+                .map_err(|e| format!("event '{}': {}", event.name, e))?;
+        }
+        Ok(calendar)
+    }
+
+    /// Loads the calendar named by `TERMINAL_CASINO_EVENTS`, falling back to
+    /// the built-in events when the variable is unset.
+    pub fn load() -> Result<Self, String> {
+        storage::load_or_builtin(EVENTS_PATH_VAR, Self::parse, Self::builtin)
+    }
+
+    pub fn active_on(&self, date: CalendarDate) -> Option<&SeasonalEvent> {
+        self.events.iter().find(|event| event.is_active_on(date))
+    }
+}
@@ -0,0 +1,252 @@
+//! The table's side of provably fair shoes (see `provably_fair`): a server
+//! seed committed to before each shoe, revealed once the shoe is done, and
+//! every revealed shoe appended to the profile's `fairness.toml` for
+//! `verify` to check.
+
+use crate::baccarat::{GameMode, ShuffleRng};
+use crate::card_renderer::CardRenderer;
+use crate::events::CalendarDate;
+use crate::integrity;
+use crate::provably_fair::{self, ServerSeed};
+use crate::resume;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Cards of each shoe the log check prints, to hold against the hands.
+const FIRST_CARDS: usize = 12;
+/// Cards per line when a whole shoe is printed.
+const CARDS_PER_LINE: usize = 13;
+
+/// One shoe, committed to and revealed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FairShoe {
+    /// Unix timestamps of the commitment and the reveal.
+    pub committed_at: u64,
+    pub revealed_at: u64,
+    /// The game mode's name; it says which ranks each deck holds.
+    pub mode: String,
+    pub decks: usize,
+    /// SHA-256 of the server seed, shown before the shoe was dealt.
+    pub hash: String,
+    pub client_seed: String,
+    pub server_seed: String,
+}
+
+impl FairShoe {
+    /// Whether the revealed seed hashes to the commitment.
+    pub fn check(&self) -> Result<bool, String> {
+        Ok(ServerSeed::parse(&self.server_seed)?.commitment() == self.hash)
+    }
+
+    /// The shoe the seeds deal, in the order it came out.
+    fn order(&self) -> Result<Vec<String>, String> {
+        let seed = ServerSeed::parse(&self.server_seed)?;
+        let mode: GameMode = self.mode.parse()?;
+        let cards =
+            provably_fair::shoe_order(&seed, &self.client_seed, self.decks, mode.top_rank());
+        Ok(cards.iter().map(CardRenderer::label).collect())
+    }
+}
+
+/// The shoe in play: its secret seed and what was published for it.
+struct Commitment {
+    seed: ServerSeed,
+    committed_at: u64,
+    mode: GameMode,
+    decks: usize,
+}
+
+pub struct Fairness {
+    client_seed: String,
+    current: Option<Commitment>,
+    /// Shoes revealed since the log was last written.
+    revealed: Vec<FairShoe>,
+}
+
+impl Fairness {
+    /// Shoes mixed with `client_seed`, or one picked at random.
+    pub fn new(client_seed: Option<String>) -> Self {
+        Self {
+            client_seed: client_seed.unwrap_or_else(provably_fair::random_client_seed),
+            current: None,
+            revealed: Vec::new(),
+        }
+    }
+
+    pub fn client_seed(&self) -> &str {
+        &self.client_seed
+    }
+
+    /// The hash published for the shoe in play, if it was committed to.
+    pub fn commitment(&self) -> Option<String> {
+        self.current
+            .as_ref()
+            .map(|current| current.seed.commitment())
+    }
+
+    /// Reveals the shoe in play and commits to a fresh server seed for the
+    /// next, returning the generator that shuffles it.
+    pub fn next_shoe(&mut self, mode: GameMode, decks: usize) -> ShuffleRng {
+        self.reveal();
+        let seed = ServerSeed::generate();
+        let rng = provably_fair::shuffle_rng(&seed, &self.client_seed);
+        self.current = Some(Commitment {
+            seed,
+            committed_at: resume::now(),
+            mode,
+            decks,
+        });
+        rng
+    }
+
+    /// Reveals the shoe in play, e.g. once its cut card is out or the
+    /// table is left; a shoe that was never committed to has nothing to
+    /// reveal.
+    pub fn reveal(&mut self) -> Option<&FairShoe> {
+        let current = self.current.take()?;
+        self.revealed.push(FairShoe {
+            committed_at: current.committed_at,
+            revealed_at: resume::now(),
+            mode: format!("{:?}", current.mode),
+            decks: current.decks,
+            hash: current.seed.commitment(),
+            client_seed: self.client_seed.clone(),
+            server_seed: current.seed.as_str().to_string(),
+        });
+        self.revealed.last()
+    }
+
+    /// Appends the shoes revealed so far to the profile's log.
+    pub fn save(&mut self, profile: &str) -> io::Result<()> {
+        for shoe in std::mem::take(&mut self.revealed) {
+            let record =
+                toml::to_string(&FairShoeRecord { shoe: [&shoe] }).map_err(io::Error::other)?;
+            integrity::append_signed(&log_path(profile), &record)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FairShoeRecord<'a> {
+    shoe: [&'a FairShoe; 1],
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FairnessFile {
+    #[serde(default)]
+    shoe: Vec<FairShoe>,
+}
+
+fn log_path(profile: &str) -> PathBuf {
+    storage::profile_dir(profile).join("fairness.toml")
+}
+
+fn load(profile: &str) -> Result<Vec<FairShoe>, String> {
+    let path = log_path(profile);
+    let source = match std::fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    let file: FairnessFile =
+        toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(file.shoe)
+}
+
+/// `verify`: checks every revealed shoe in the profile's log, writing the
+/// first cards each dealt to `out`. Returns whether every seed matched its
+/// hash.
+pub fn verify_log(out: &mut impl Write, profile: &str) -> Result<bool, String> {
+    let shoes = load(profile)?;
+    write_log(out, profile, &shoes).map_err(|e| e.to_string())
+}
+
+fn write_log(out: &mut impl Write, profile: &str, shoes: &[FairShoe]) -> io::Result<bool> {
+    if shoes.is_empty() {
+        writeln!(out, "No revealed shoes for {} yet.", profile)?;
+        return Ok(true);
+    }
+    writeln!(
+        out,
+        "Provably fair shoes for {} (fairness.toml {}):",
+        profile,
+        integrity::verify(&log_path(profile)).label()
+    )?;
+    let mut failed = 0;
+    for (i, shoe) in shoes.iter().enumerate() {
+        let verdict = match shoe.check() {
+            Ok(true) => "hash ok",
+            Ok(false) => "HASH MISMATCH",
+            Err(_) => "SEED UNREADABLE",
+        };
+        if verdict != "hash ok" {
+            failed += 1;
+        }
+        let first = shoe.order().map_or_else(
+            |e| e,
+            |cards| cards[..FIRST_CARDS.min(cards.len())].join(" "),
+        );
+        writeln!(
+            out,
+            "{:>4}. {}  {}, {} decks  {}  first cards: {}",
+            i + 1,
+            CalendarDate::from_unix(shoe.committed_at),
+            shoe.mode,
+            shoe.decks,
+            verdict,
+            first
+        )?;
+    }
+    match failed {
+        0 => writeln!(
+            out,
+            "{} shoes, every server seed matches the hash published for it.",
+            shoes.len()
+        )?,
+        n => writeln!(out, "{} shoes, {} failed.", shoes.len(), n)?,
+    }
+    writeln!(
+        out,
+        "Any one can be dealt out in full with verify --server-seed S --client-seed C --mode M --decks N."
+    )?;
+    Ok(failed == 0)
+}
+
+/// `verify --server-seed`: checks one shoe's seed against the published
+/// hash, if given, and writes the whole shoe in dealing order to `out`.
+pub fn verify_seeds(
+    out: &mut impl Write,
+    server_seed: &ServerSeed,
+    client_seed: &str,
+    hash: Option<&str>,
+    mode: GameMode,
+    decks: usize,
+) -> io::Result<bool> {
+    let commitment = server_seed.commitment();
+    writeln!(out, "Server seed hashes to {}", commitment)?;
+    let matched = match hash {
+        Some(hash) if hash.trim().eq_ignore_ascii_case(&commitment) => {
+            writeln!(out, "Published hash       {}  matches", hash.trim())?;
+            true
+        }
+        Some(hash) => {
+            writeln!(out, "Published hash       {}  DOES NOT MATCH", hash.trim())?;
+            false
+        }
+        None => true,
+    };
+    let cards = provably_fair::shoe_order(server_seed, client_seed, decks, mode.top_rank());
+    writeln!(
+        out,
+        "\n{:?}, {} decks, client seed {}, in dealing order with the burn first:",
+        mode, decks, client_seed
+    )?;
+    for line in cards.chunks(CARDS_PER_LINE) {
+        let labels: Vec<String> = line.iter().map(CardRenderer::label).collect();
+        writeln!(out, "  {}", labels.join(" "))?;
+    }
+    Ok(matched)
+}
@@ -0,0 +1,157 @@
+//! Bankroll goal: progress toward a target balance, how long the current
+//! bets would take to get there, and whether they realistically can.
+
+use crate::analytics::{self, BetOutlook};
+use crate::baccarat::{BetSlip, GameMode, Money, OverUnderRule, PayoutTable};
+
+/// Below this chance of reaching the goal before going broke, the goal is
+/// flagged as out of reach.
+pub const OUT_OF_REACH_CHANCE: f64 = 0.05;
+
+/// A seat's bets and the table they are placed at; the outlook is only
+/// recomputed when this changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetLayout {
+    pub mode: GameMode,
+    pub over_under: OverUnderRule,
+    pub payout_table: PayoutTable,
+    pub slip: BetSlip,
+}
+
+#[derive(Debug, Clone)]
+pub struct BankrollGoal {
+    target: Money,
+    outlook: Option<(BetLayout, BetOutlook)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProjection {
+    pub target: Money,
+    pub balance: Money,
+    /// Expected net result per hand with the current bets.
+    pub expected_per_hand: f64,
+    /// Chance of reaching the target before the bankroll runs out.
+    pub reach_chance: f64,
+    /// Hands needed on average, if the bets are expected to win.
+    pub hands_to_goal: Option<f64>,
+    pub hours_to_goal: Option<f64>,
+}
+
+impl BankrollGoal {
+    pub fn new(target: i32) -> Self {
+        Self {
+            target: Money::from_dollars(target),
+            outlook: None,
+        }
+    }
+
+    /// Projects the goal from `balance` with `layout`, at `hands_per_hour`
+    /// (zero while the pace is not known yet).
+    pub fn project(
+        &mut self,
+        layout: BetLayout,
+        balance: Money,
+        hands_per_hour: f64,
+    ) -> GoalProjection {
+        let outlook = match &self.outlook {
+            Some((cached, outlook)) if *cached == layout => *outlook,
+            _ => {
+                let outlook = analytics::bet_outlook(
+                    layout.mode,
+                    layout.over_under,
+                    layout.payout_table.clone(),
+                    &layout.slip,
+                );
+                self.outlook = Some((layout, outlook));
+                outlook
+            }
+        };
+
+        let gap = (self.target - balance).as_dollars();
+        let hands_to_goal = if gap <= 0.0 {
+            Some(0.0)
+        } else {
+            (outlook.mean > 0.0).then(|| gap / outlook.mean)
+        };
+        GoalProjection {
+            target: self.target,
+            balance,
+            expected_per_hand: outlook.mean,
+            reach_chance: reach_chance(balance.as_dollars(), self.target.as_dollars(), outlook),
+            hands_to_goal,
+            hours_to_goal: hands_to_goal
+                .filter(|_| hands_per_hour > 0.0)
+                .map(|hands| hands / hands_per_hour),
+        }
+    }
+}
+
+/// Chance a random walk with the outlook's drift and variance climbs from
+/// `balance` to `target` before falling to zero (diffusion approximation).
+fn reach_chance(balance: f64, target: f64, outlook: BetOutlook) -> f64 {
+    if balance >= target {
+        return 1.0;
+    }
+    if balance <= 0.0 || outlook.variance <= 0.0 {
+        return 0.0;
+    }
+    let k = -2.0 * outlook.mean / outlook.variance;
+    if k.abs() < 1e-12 {
+        return balance / target;
+    }
+    // For a losing layout e^(k * target) overflows long before the ratio
+    // stops being representable.
+    if k * target > 700.0 {
+        return (k * (balance - target)).exp();
+    }
+    ((k * balance).exp_m1() / (k * target).exp_m1()).clamp(0.0, 1.0)
+}
+
+impl GoalProjection {
+    pub fn progress(&self) -> f64 {
+        if self.target <= Money::ZERO {
+            return 1.0;
+        }
+        (self.balance.as_dollars() / self.target.as_dollars()).clamp(0.0, 1.0)
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.balance >= self.target
+    }
+
+    pub fn is_out_of_reach(&self) -> bool {
+        !self.is_reached() && self.reach_chance < OUT_OF_REACH_CHANCE
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Goal: ${} of ${} ({:.0}%)",
+            self.balance,
+            self.target,
+            self.progress() * 100.0
+        )];
+        if self.is_reached() {
+            lines.push("Goal reached!".to_string());
+            return lines;
+        }
+        lines.push(match (self.hands_to_goal, self.hours_to_goal) {
+            (Some(hands), Some(hours)) => format!(
+                "About {:.0} hands to go (~{:.1} h at this pace)",
+                hands, hours
+            ),
+            (Some(hands), None) => format!("About {:.0} hands to go", hands),
+            _ => format!(
+                "Not expected: these bets average {:+.2} per hand",
+                self.expected_per_hand
+            ),
+        });
+        lines.push(format!(
+            "Chance of reaching it before going broke: {:.1}%",
+            self.reach_chance * 100.0
+        ));
+        if self.is_out_of_reach() {
+            lines.push("Warning: goal is statistically out of reach with these bets".to_string());
+        }
+        lines
+    }
+}
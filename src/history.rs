@@ -0,0 +1,587 @@
+//! Shoe history store: a summary record for every finished shoe, appended to
+//! `profiles/<name>/shoes.toml`, and the hand-by-hand log of the session.
+
+use crate::baccarat::{BaccaratGame, Card, GameMode, Money, Side};
+use crate::card_renderer::CardRenderer;
+use crate::integrity;
+use crate::roads::{ShoeRoads, winner_letter};
+use crate::settlement::{BetOutcome, SettledBet};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShoeSummary {
+    pub shoe_number: u32,
+    /// Unix timestamp of the last hand.
+    pub finished_at: u64,
+    /// One letter per hand in dealing order, e.g. "BBPPTB".
+    pub outcomes: String,
+    pub player_wins: u32,
+    pub banker_wins: u32,
+    pub ties: u32,
+    pub cards_dealt: u32,
+    /// Fraction of the shoe dealt before the cut card came out.
+    pub penetration: f64,
+    pub side_bet_hits: u32,
+    /// Player's net result over the shoe.
+    pub net: Money,
+}
+
+impl ShoeSummary {
+    pub fn hands(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn recap_lines(&self) -> Vec<String> {
+        vec![
+            format!("Shoe {} complete: {} hands", self.shoe_number, self.hands()),
+            format!(
+                "Player {} | Banker {} | Tie {}",
+                self.player_wins, self.banker_wins, self.ties
+            ),
+            format!(
+                "Penetration: {:.0}% ({} cards)",
+                self.penetration * 100.0,
+                self.cards_dealt
+            ),
+            format!("Side bet hits: {}", self.side_bet_hits),
+            format!("Net result: {:+}", self.net),
+            format!("Outcomes: {}", self.outcomes),
+        ]
+    }
+}
+
+/// Money results for the shoe in progress; the outcomes come from the roads.
+#[derive(Debug, Clone, Default)]
+pub struct ShoeLedger {
+    net: Money,
+    side_bet_hits: u32,
+}
+
+impl ShoeLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_round(&mut self, net: Money, side_bet_hit: bool) {
+        self.net += net;
+        if side_bet_hit {
+            self.side_bet_hits += 1;
+        }
+    }
+
+    /// Closes the shoe: builds its summary and starts a fresh ledger.
+    /// `penetration` is cards dealt and shoe size.
+    pub fn finish(&mut self, roads: &ShoeRoads, penetration: (usize, usize)) -> ShoeSummary {
+        let winners = roads.winners();
+        let count = |outcome: u8| winners.iter().filter(|&&w| w == outcome).count() as u32;
+        let (dealt, size) = penetration;
+        let summary = ShoeSummary {
+            shoe_number: roads.shoe_number(),
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            outcomes: winners.iter().map(|&w| winner_letter(w)).collect(),
+            player_wins: count(1),
+            banker_wins: count(2),
+            ties: count(3),
+            cards_dealt: dealt as u32,
+            penetration: if size > 0 {
+                dealt as f64 / size as f64
+            } else {
+                0.0
+            },
+            side_bet_hits: self.side_bet_hits,
+            net: self.net,
+        };
+        *self = Self::new();
+        summary
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ShoeRecord<'a> {
+    shoe: [&'a ShoeSummary; 1],
+}
+
+#[derive(Debug, Clone)]
+pub struct ShoeHistory {
+    profile: String,
+}
+
+impl ShoeHistory {
+    pub fn new(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        storage::profile_dir(&self.profile).join("shoes.toml")
+    }
+
+    /// Appends one `[[shoe]]` table, so the file stays valid TOML and is never
+    /// rewritten, and re-signs it.
+    pub fn append(&self, summary: &ShoeSummary) -> io::Result<()> {
+        let record = toml::to_string(&ShoeRecord { shoe: [summary] }).map_err(io::Error::other)?;
+        integrity::append_signed(&self.path(), &record)
+    }
+}
+
+/// One seat's bets on a hand, settled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatBets {
+    pub seat: String,
+    pub bets: Vec<SettledBet>,
+}
+
+/// Everything about one hand: the cards, how it came out, and what every
+/// seat had on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandRecord {
+    /// Hand number in the session, from 1.
+    pub number: u32,
+    pub shoe: u32,
+    /// Unix timestamp of the settlement.
+    pub dealt_at: u64,
+    pub mode: GameMode,
+    pub player_cards: Vec<Card>,
+    pub banker_cards: Vec<Card>,
+    pub player_score: u8,
+    pub banker_score: u8,
+    /// 1 for player, 2 for banker, 3 for a tie.
+    pub winner: u8,
+    /// Either hand was a natural.
+    pub natural: bool,
+    pub seats: Vec<SeatBets>,
+    /// What the shoe was shuffled from: "fair", "os", "thread" or
+    /// "seed N".
+    pub shuffle: String,
+}
+
+impl HandRecord {
+    /// Records the hand `game` has just settled, dealt from a shoe shuffled
+    /// from `shuffle`.
+    pub fn new(
+        number: u32,
+        shoe: u32,
+        game: &BaccaratGame,
+        seats: Vec<SeatBets>,
+        shuffle: String,
+    ) -> Self {
+        Self {
+            number,
+            shoe,
+            dealt_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            mode: game.mode,
+            player_cards: game.player_hand.to_vec(),
+            banker_cards: game.banker_hand.to_vec(),
+            player_score: game.state.player_score,
+            banker_score: game.state.banker_score,
+            winner: game.state.winner,
+            natural: game.player_natural().is_some() || game.banker_natural().is_some(),
+            seats,
+            shuffle,
+        }
+    }
+
+    /// Either hand opened with two cards of the same rank.
+    pub fn is_pair(&self) -> bool {
+        [&self.player_cards, &self.banker_cards]
+            .iter()
+            .any(|cards| matches!(&cards[..], [first, second, ..] if first.rank == second.rank))
+    }
+
+    /// The hand as the history panel shows it: a heading, the cards, then a
+    /// line per seat that had money down.
+    pub fn lines(&self) -> Vec<String> {
+        let (player, banker) = (
+            self.mode.side_name(Side::Player),
+            self.mode.side_name(Side::Banker),
+        );
+        let result = match self.winner {
+            1 => format!(
+                "{} wins {}-{}",
+                player, self.player_score, self.banker_score
+            ),
+            2 => format!(
+                "{} wins {}-{}",
+                banker, self.banker_score, self.player_score
+            ),
+            _ => format!("Tie at {}", self.player_score),
+        };
+        let secs = self.dealt_at % 86_400;
+        let mut lines = vec![
+            format!(
+                "Hand {} (shoe {}, {} shuffle) at {:02}:{:02}:{:02} UTC: {}",
+                self.number,
+                self.shoe,
+                self.shuffle,
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60,
+                result
+            ),
+            format!(
+                "  {} {} = {}   {} {} = {}",
+                player,
+                cards_text(&self.player_cards),
+                self.player_score,
+                banker,
+                cards_text(&self.banker_cards),
+                self.banker_score
+            ),
+        ];
+        for seat in &self.seats {
+            let bets: Vec<String> = seat.bets.iter().map(bet_text).collect();
+            let net = seat
+                .bets
+                .iter()
+                .map(|bet| bet.payout - bet.stake)
+                .sum::<Money>();
+            lines.push(format!(
+                "  {}: {}  net {:+}",
+                seat.seat,
+                bets.join(", "),
+                net
+            ));
+        }
+        lines
+    }
+
+    /// The hand as one line of JSON, as the history exports it.
+    pub fn json(&self) -> String {
+        let cards = |cards: &[Card]| {
+            let cards: Vec<String> = cards
+                .iter()
+                .map(|card| json_string(&CardRenderer::label(card)))
+                .collect();
+            format!("[{}]", cards.join(", "))
+        };
+        let seats: Vec<String> = self
+            .seats
+            .iter()
+            .map(|seat| {
+                let bets: Vec<String> = seat
+                    .bets
+                    .iter()
+                    .map(|bet| {
+                        format!(
+                            "{{\"bet\": {}, \"stake\": {}, \"payout\": {}, \"outcome\": {}}}",
+                            json_string(&bet.wager.to_string()),
+                            bet.stake,
+                            bet.payout,
+                            json_string(&bet.outcome.to_string())
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"seat\": {}, \"bets\": [{}]}}",
+                    json_string(&seat.seat),
+                    bets.join(", ")
+                )
+            })
+            .collect();
+        format!(
+            "{{\"hand\": {}, \"shoe\": {}, \"dealt_at\": {}, \"mode\": {}, \"shuffle\": {}, \"player_cards\": {}, \"banker_cards\": {}, \
+             \"player_score\": {}, \"banker_score\": {}, \"outcome\": {}, \"natural\": {}, \"seats\": [{}]}}",
+            self.number,
+            self.shoe,
+            self.dealt_at,
+            json_string(&format!("{:?}", self.mode)),
+            json_string(&self.shuffle),
+            cards(&self.player_cards),
+            cards(&self.banker_cards),
+            self.player_score,
+            self.banker_score,
+            json_string(outcome_name(self.winner)),
+            self.natural,
+            seats.join(", ")
+        )
+    }
+}
+
+fn cards_text(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(CardRenderer::label)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "banker $100 won $195", "tie $10 lost".
+fn bet_text(bet: &SettledBet) -> String {
+    match bet.outcome {
+        BetOutcome::Win => format!("{} ${} won ${}", bet.wager, bet.stake, bet.payout),
+        BetOutcome::Lose => format!("{} ${} lost", bet.wager, bet.stake),
+        BetOutcome::Push => format!("{} ${} pushed", bet.wager, bet.stake),
+        BetOutcome::Rides => format!("{} ${} rides", bet.wager, bet.stake),
+    }
+}
+
+/// Streaks and trends over a run of hands, built up from the hand records.
+/// As with the streak alarms, ties neither extend nor break a player or
+/// banker streak; a tie streak is ties in a row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trends {
+    pub hands: u32,
+    /// Player, banker, and tie wins.
+    pub wins: [u32; 3],
+    /// Longest player, banker, and tie streaks.
+    pub longest: [u32; 3],
+    /// Decisions that went the other way from the one before.
+    pub chops: u32,
+    /// Decisions that went the same way as the one before.
+    pub repeats: u32,
+    /// Hands where either side was dealt a natural.
+    pub naturals: u32,
+    /// Hands where either side opened with a pair.
+    pub pairs: u32,
+    /// The player or banker streak in progress.
+    decision_streak: Option<(u8, u32)>,
+    ties_in_a_row: u32,
+}
+
+impl Trends {
+    pub fn record(&mut self, hand: &HandRecord) {
+        let winner = hand.winner;
+        if !(1..=3).contains(&winner) {
+            return;
+        }
+        self.hands += 1;
+        self.wins[winner as usize - 1] += 1;
+        self.naturals += hand.natural as u32;
+        self.pairs += hand.is_pair() as u32;
+        let length = if winner == 3 {
+            self.ties_in_a_row += 1;
+            self.ties_in_a_row
+        } else {
+            self.ties_in_a_row = 0;
+            let length = match self.decision_streak {
+                Some((side, length)) if side == winner => {
+                    self.repeats += 1;
+                    length + 1
+                }
+                Some(_) => {
+                    self.chops += 1;
+                    1
+                }
+                None => 1,
+            };
+            self.decision_streak = Some((winner, length));
+            length
+        };
+        let longest = &mut self.longest[winner as usize - 1];
+        *longest = (*longest).max(length);
+    }
+
+    /// The outcome of the latest hand and how many in a row it has run.
+    pub fn current_streak(&self) -> Option<(u8, u32)> {
+        match self.ties_in_a_row {
+            0 => self.decision_streak,
+            ties => Some((3, ties)),
+        }
+    }
+
+    /// Share of decisions that chopped to the other side, once there have
+    /// been two.
+    pub fn chop_ratio(&self) -> Option<f64> {
+        let changes = self.chops + self.repeats;
+        (changes > 0).then(|| self.chops as f64 / changes as f64)
+    }
+
+    pub fn natural_rate(&self) -> Option<f64> {
+        (self.hands > 0).then(|| self.naturals as f64 / self.hands as f64)
+    }
+
+    /// "Streak: Banker x3 | Longest Player 5, Banker 7, Tie 2", then the
+    /// chop and natural figures, for the statistics panels.
+    pub fn lines(&self, mode: GameMode) -> Vec<String> {
+        let (player, banker) = (mode.side_name(Side::Player), mode.side_name(Side::Banker));
+        let current = match self.current_streak() {
+            Some((1, length)) => format!("{} x{}", player, length),
+            Some((2, length)) => format!("{} x{}", banker, length),
+            Some((_, length)) => format!("Tie x{}", length),
+            None => "-".to_string(),
+        };
+        let percent = |rate: Option<f64>| {
+            rate.map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0))
+        };
+        vec![
+            format!(
+                "Streak: {} | Longest {} {}, {} {}, Tie {}",
+                current, player, self.longest[0], banker, self.longest[1], self.longest[2]
+            ),
+            format!(
+                "Chops: {} | Repeats: {} | Chop rate: {}",
+                self.chops,
+                self.repeats,
+                percent(self.chop_ratio())
+            ),
+            format!(
+                "Naturals: {} ({}) | Pairs: {}",
+                self.naturals,
+                percent(self.natural_rate()),
+                self.pairs
+            ),
+        ]
+    }
+}
+
+/// Every hand of the session, oldest first. Kept in memory only; the shoe
+/// summaries are what gets saved.
+#[derive(Debug, Clone, Default)]
+pub struct HandHistory {
+    hands: Vec<HandRecord>,
+}
+
+impl HandHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, hand: HandRecord) {
+        self.hands.push(hand);
+    }
+
+    pub fn hands(&self) -> &[HandRecord] {
+        &self.hands
+    }
+
+    /// Every hand's lines, oldest first.
+    pub fn lines(&self) -> Vec<String> {
+        self.hands.iter().flat_map(HandRecord::lines).collect()
+    }
+
+    /// Writes every hand to `path`, oldest first: a CSV row or a JSON object
+    /// per hand with its cards, scores, outcome, and each seat's bets and
+    /// payouts, for analysis in a spreadsheet. Amounts are in dollars.
+    pub fn export(&self, path: &Path, format: Format) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = match format {
+            Format::Csv => self.csv(),
+            Format::Json => self.json(),
+        };
+        std::fs::write(path, contents)
+    }
+
+    /// One row per hand; the bets of every seat share a column, and the
+    /// amounts after it are totals over the seats.
+    fn csv(&self) -> String {
+        let mut out = String::from(
+            "hand,shoe,dealt_at,mode,shuffle,player_cards,banker_cards,player_score,banker_score,outcome,natural,bets,wagered,paid,net\n",
+        );
+        for hand in &self.hands {
+            let bets: Vec<String> = hand
+                .seats
+                .iter()
+                .map(|seat| {
+                    let bets: Vec<String> = seat.bets.iter().map(bet_text).collect();
+                    format!("{}: {}", seat.seat, bets.join(", "))
+                })
+                .collect();
+            let settled = || hand.seats.iter().flat_map(|seat| &seat.bets);
+            let wagered: Money = settled().map(|bet| bet.stake).sum();
+            let paid: Money = settled().map(|bet| bet.payout).sum();
+            let fields = [
+                hand.number.to_string(),
+                hand.shoe.to_string(),
+                hand.dealt_at.to_string(),
+                format!("{:?}", hand.mode),
+                hand.shuffle.clone(),
+                cards_text(&hand.player_cards),
+                cards_text(&hand.banker_cards),
+                hand.player_score.to_string(),
+                hand.banker_score.to_string(),
+                outcome_name(hand.winner).to_string(),
+                hand.natural.to_string(),
+                bets.join("; "),
+                wagered.to_string(),
+                paid.to_string(),
+                (paid - wagered).to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// An array of hands, each seat's bets listed one by one.
+    fn json(&self) -> String {
+        let mut out = String::from("[");
+        for (index, hand) in self.hands.iter().enumerate() {
+            out.push_str(if index == 0 { "\n  " } else { ",\n  " });
+            out.push_str(&hand.json());
+        }
+        out.push_str(if self.hands.is_empty() {
+            "]\n"
+        } else {
+            "\n]\n"
+        });
+        out
+    }
+}
+
+/// File formats the hand history exports to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// The winning side by its bet name: "player", "banker" or "tie".
+pub fn outcome_name(winner: u8) -> &'static str {
+    match winner {
+        1 => "player",
+        2 => "banker",
+        _ => "tie",
+    }
+}
+
+/// Quotes a field holding a comma, a quote or a line break, doubling its
+/// quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// `text` as a JSON string literal.
+pub fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
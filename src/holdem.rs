@@ -0,0 +1,612 @@
+//! No-limit Texas Hold'em against simple bots: the best five of seven cards,
+//! valued by the shared poker evaluator, blinds and the four betting rounds, side pots for all-in players, and a
+//! bot that bets on its hand strength and the pot odds. Seat 0 is the human
+//! player; the table plays every other seat with `play_bots`.
+
+use crate::baccarat::{Card, Deck, Money, ShuffleRng};
+use crate::poker::high_rank;
+pub use crate::poker::{Category, HandValue, evaluate};
+use rand::Rng;
+
+/// Most bots at the table.
+pub const MAX_OPPONENTS: usize = 5;
+pub const SMALL_BLIND: Money = Money::from_dollars(1);
+pub const BIG_BLIND: Money = Money::from_dollars(2);
+/// Chips every seat sits down with: 100 big blinds.
+pub const BUY_IN: Money = Money::from_dollars(200);
+
+const BOT_NAMES: [&str; MAX_OPPONENTS] = ["Ada", "Bo", "Cy", "Dee", "Eli"];
+
+/// How far the hand has been dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    /// The hand is over, won at a showdown or by the last player left in.
+    Showdown,
+}
+
+impl Street {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Street::Preflop => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+            Street::Showdown => "Showdown",
+        }
+    }
+}
+
+/// A player decision when it is their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// Raise (or bet, when nobody has) to this total for the street.
+    Raise(Money),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seat {
+    pub name: String,
+    pub bot: bool,
+    /// Chips in front of the player, not counting bets already made.
+    pub chips: Money,
+    pub hole: Vec<Card>,
+    /// Put in on the current street.
+    pub street_bet: Money,
+    /// Put in over the whole hand.
+    pub committed: Money,
+    pub folded: bool,
+}
+
+impl Seat {
+    fn new(name: &str, bot: bool, chips: Money) -> Self {
+        Self {
+            name: name.to_string(),
+            bot,
+            chips,
+            hole: Vec::new(),
+            street_bet: Money::ZERO,
+            committed: Money::ZERO,
+            folded: true,
+        }
+    }
+
+    /// Dealt into the current hand and not folded.
+    pub fn in_hand(&self) -> bool {
+        !self.folded && !self.hole.is_empty()
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        self.in_hand() && self.chips == Money::ZERO
+    }
+
+    /// Still to make decisions this hand.
+    fn can_act(&self) -> bool {
+        self.in_hand() && self.chips > Money::ZERO
+    }
+
+    /// Moves up to `amount` from the stack into the pot; returns what went in.
+    fn put_in(&mut self, amount: Money) -> Money {
+        let amount = amount.min(self.chips);
+        self.chips -= amount;
+        self.street_bet += amount;
+        self.committed += amount;
+        amount
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldemError {
+    /// A hand is already being played.
+    HandInProgress,
+    /// Fewer than two seats have chips.
+    NotEnoughPlayers,
+    /// It is nobody's turn, or the action is not open to the player whose
+    /// turn it is.
+    NotAllowed(Action),
+    DeckEmpty,
+}
+
+impl std::fmt::Display for HoldemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HoldemError::HandInProgress => write!(f, "a hand is already in progress"),
+            HoldemError::NotEnoughPlayers => write!(f, "at least two players need chips to deal"),
+            HoldemError::NotAllowed(action) => write!(f, "{:?} is not allowed now", action),
+            HoldemError::DeckEmpty => write!(f, "the deck ran out of cards"),
+        }
+    }
+}
+
+impl std::error::Error for HoldemError {}
+
+/// A hold'em table: the seats, the deck, the board, and whose turn it is.
+pub struct HoldemGame {
+    pub seats: Vec<Seat>,
+    deck: Deck,
+    pub board: Vec<Card>,
+    pub street: Street,
+    /// Dealer button; the blinds sit to its left.
+    pub button: usize,
+    /// Seat whose turn it is, or `None` between hands.
+    pub to_act: Option<usize>,
+    /// Highest bet on the current street.
+    current_bet: Money,
+    /// Size of the last full raise, which the next raise must at least
+    /// match.
+    last_raise: Money,
+    /// Seats that have acted since the last full raise; they may call a
+    /// short all-in raise but not raise again.
+    acted: Vec<bool>,
+    /// Shuffles the deck and steadies the bots' nerves; `None` draws from
+    /// the thread's generator.
+    rng: Option<ShuffleRng>,
+    /// What each seat won in the last hand.
+    pub winnings: Vec<Money>,
+    /// The last hand went to a showdown, so hole cards are face up.
+    pub showdown: bool,
+    /// Actions and results of the current hand, oldest first.
+    pub log: Vec<String>,
+}
+
+impl HoldemGame {
+    /// A table of the player `name`, staked with `chips`, and `opponents`
+    /// bots (1 to `MAX_OPPONENTS`) sitting with `BUY_IN` each.
+    pub fn new(name: &str, opponents: usize, chips: Money) -> Self {
+        let mut game = Self {
+            seats: vec![Seat::new(name, false, chips)],
+            deck: Deck::new(),
+            board: Vec::new(),
+            street: Street::Showdown,
+            button: 0,
+            to_act: None,
+            current_bet: Money::ZERO,
+            last_raise: BIG_BLIND,
+            acted: Vec::new(),
+            rng: None,
+            winnings: Vec::new(),
+            showdown: false,
+            log: Vec::new(),
+        };
+        game.set_opponents(opponents);
+        game
+    }
+
+    /// Deals and plays the bots from `rng` from the next hand on; a seeded
+    /// one plays the same hands every time.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    pub fn opponents(&self) -> usize {
+        self.seats.len() - 1
+    }
+
+    /// Seats or unseats bots between hands so `opponents` (clamped to
+    /// 1..=`MAX_OPPONENTS`) remain.
+    pub fn set_opponents(&mut self, opponents: usize) {
+        if self.in_progress() {
+            return;
+        }
+        let opponents = opponents.clamp(1, MAX_OPPONENTS);
+        self.seats.truncate(opponents + 1);
+        while self.seats.len() <= opponents {
+            let name = BOT_NAMES[self.seats.len() - 1];
+            self.seats.push(Seat::new(name, true, BUY_IN));
+        }
+        self.button %= self.seats.len();
+        self.winnings = vec![Money::ZERO; self.seats.len()];
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.to_act.is_some()
+    }
+
+    pub fn pot(&self) -> Money {
+        self.seats.iter().map(|seat| seat.committed).sum()
+    }
+
+    /// What `seat` must put in to stay in.
+    pub fn to_call(&self, seat: usize) -> Money {
+        (self.current_bet - self.seats[seat].street_bet).min(self.seats[seat].chips)
+    }
+
+    /// The smallest legal raise total, or the whole stack when that is less.
+    pub fn min_raise_to(&self, seat: usize) -> Money {
+        (self.current_bet + self.last_raise).min(self.max_raise_to(seat))
+    }
+
+    /// Everything `seat` has: their stack plus this street's bet.
+    pub fn max_raise_to(&self, seat: usize) -> Money {
+        self.seats[seat].street_bet + self.seats[seat].chips
+    }
+
+    /// Whether the player to act may take `action`.
+    pub fn can(&self, action: Action) -> bool {
+        let Some(index) = self.to_act else {
+            return false;
+        };
+        let seat = &self.seats[index];
+        match action {
+            Action::Fold => true,
+            Action::Check => seat.street_bet == self.current_bet,
+            Action::Call => seat.street_bet < self.current_bet,
+            Action::Raise(to) => {
+                let max = self.max_raise_to(index);
+                !self.acted[index]
+                    && max > self.current_bet
+                    && to <= max
+                    && to >= self.min_raise_to(index)
+            }
+        }
+    }
+
+    /// The best hand `seat` can make with the board, from the flop on.
+    pub fn best_hand(&self, seat: usize) -> Option<HandValue> {
+        if self.board.len() < 3 || self.seats[seat].hole.is_empty() {
+            return None;
+        }
+        let cards: Vec<Card> = self.seats[seat]
+            .hole
+            .iter()
+            .chain(&self.board)
+            .copied()
+            .collect();
+        Some(evaluate(&cards))
+    }
+
+    fn draw(&mut self) -> Result<Card, HoldemError> {
+        self.deck.deal().ok_or(HoldemError::DeckEmpty)
+    }
+
+    /// The next seat after `from`, going round the table, that satisfies
+    /// `wanted`.
+    fn next_seat(&self, from: usize, wanted: impl Fn(&Seat) -> bool) -> Option<usize> {
+        (1..=self.seats.len())
+            .map(|step| (from + step) % self.seats.len())
+            .find(|&index| wanted(&self.seats[index]))
+    }
+
+    /// Moves the button, restakes any bot that has gone broke, posts the
+    /// blinds and deals two cards to everyone with chips.
+    pub fn start_hand(&mut self) -> Result<(), HoldemError> {
+        if self.in_progress() {
+            return Err(HoldemError::HandInProgress);
+        }
+        self.log.clear();
+        for seat in &mut self.seats {
+            if seat.bot && seat.chips == Money::ZERO {
+                seat.chips = BUY_IN;
+                self.log
+                    .push(format!("{} buys in for ${}", seat.name, BUY_IN));
+            }
+        }
+        if self
+            .seats
+            .iter()
+            .filter(|seat| seat.chips > Money::ZERO)
+            .count()
+            < 2
+        {
+            return Err(HoldemError::NotEnoughPlayers);
+        }
+        self.deck = Deck::new();
+        match &mut self.rng {
+            Some(rng) => self.deck.shuffle_with(rng.as_mut()),
+            None => self.deck.shuffle(),
+        }
+        self.board.clear();
+        self.street = Street::Preflop;
+        self.showdown = false;
+        self.winnings = vec![Money::ZERO; self.seats.len()];
+        self.acted = vec![false; self.seats.len()];
+        for seat in &mut self.seats {
+            seat.hole.clear();
+            seat.street_bet = Money::ZERO;
+            seat.committed = Money::ZERO;
+            seat.folded = seat.chips == Money::ZERO;
+        }
+        self.button = self
+            .next_seat(self.button, |seat| !seat.folded)
+            .unwrap_or(0);
+        for _ in 0..2 {
+            for index in 0..self.seats.len() {
+                if !self.seats[index].folded {
+                    let card = self.draw()?;
+                    self.seats[index].hole.push(card);
+                }
+            }
+        }
+
+        // Heads up, the button posts the small blind.
+        let live = self.seats.iter().filter(|seat| seat.in_hand()).count();
+        let small = if live == 2 {
+            self.button
+        } else {
+            self.next_seat(self.button, Seat::in_hand).unwrap_or(0)
+        };
+        let big = self.next_seat(small, Seat::in_hand).unwrap_or(0);
+        for (index, blind, name) in [(small, SMALL_BLIND, "small"), (big, BIG_BLIND, "big")] {
+            let posted = self.seats[index].put_in(blind);
+            self.log.push(format!(
+                "{} posts the {} blind ${}",
+                self.seats[index].name, name, posted
+            ));
+        }
+        self.current_bet = BIG_BLIND;
+        self.last_raise = BIG_BLIND;
+        self.to_act = Some(big);
+        self.pass_turn(big)
+    }
+
+    /// Plays `action` for the seat whose turn it is.
+    pub fn act(&mut self, action: Action) -> Result<(), HoldemError> {
+        let Some(index) = self.to_act.filter(|_| self.can(action)) else {
+            return Err(HoldemError::NotAllowed(action));
+        };
+        let name = self.seats[index].name.clone();
+        match action {
+            Action::Fold => {
+                self.seats[index].folded = true;
+                self.log.push(format!("{} folds", name));
+            }
+            Action::Check => self.log.push(format!("{} checks", name)),
+            Action::Call => {
+                let call = self.current_bet - self.seats[index].street_bet;
+                let paid = self.seats[index].put_in(call);
+                self.log.push(format!(
+                    "{} calls ${}{}",
+                    name,
+                    paid,
+                    if self.seats[index].chips == Money::ZERO {
+                        " and is all in"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+            Action::Raise(to) => {
+                let verb = if self.current_bet == Money::ZERO {
+                    "bets"
+                } else {
+                    "raises to"
+                };
+                let raise = to - self.current_bet;
+                let amount = to - self.seats[index].street_bet;
+                self.seats[index].put_in(amount);
+                // Only a full raise reopens the betting; an all-in short of
+                // one leaves those who have acted to call or fold.
+                if raise >= self.last_raise {
+                    self.last_raise = raise;
+                    self.acted.iter_mut().for_each(|acted| *acted = false);
+                }
+                self.current_bet = to;
+                self.log.push(format!(
+                    "{} {} ${}{}",
+                    name,
+                    verb,
+                    to,
+                    if self.seats[index].chips == Money::ZERO {
+                        ", all in"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+        }
+        self.acted[index] = true;
+        self.pass_turn(index)
+    }
+
+    /// Hands the turn to the next seat still to act after `from`, or ends
+    /// the street (and the hand, when one player is left) if nobody is.
+    fn pass_turn(&mut self, from: usize) -> Result<(), HoldemError> {
+        if self.seats.iter().filter(|seat| seat.in_hand()).count() == 1 {
+            self.award();
+            return Ok(());
+        }
+        let current_bet = self.current_bet;
+        let acted = self.acted.clone();
+        let next = (1..=self.seats.len())
+            .map(|step| (from + step) % self.seats.len())
+            .find(|&index| {
+                let seat = &self.seats[index];
+                seat.can_act() && (!acted[index] || seat.street_bet < current_bet)
+            });
+        if let Some(next) = next {
+            self.to_act = Some(next);
+            return Ok(());
+        }
+        self.next_street()
+    }
+
+    /// Deals the next board cards and opens the betting left of the button,
+    /// running the board out when at most one player can still bet.
+    fn next_street(&mut self) -> Result<(), HoldemError> {
+        loop {
+            let (street, cards) = match self.street {
+                Street::Preflop => (Street::Flop, 3),
+                Street::Flop => (Street::Turn, 1),
+                Street::Turn => (Street::River, 1),
+                Street::River | Street::Showdown => {
+                    self.showdown = true;
+                    self.award();
+                    return Ok(());
+                }
+            };
+            for _ in 0..cards {
+                let card = self.draw()?;
+                self.board.push(card);
+            }
+            self.street = street;
+            for seat in &mut self.seats {
+                seat.street_bet = Money::ZERO;
+            }
+            self.current_bet = Money::ZERO;
+            self.last_raise = BIG_BLIND;
+            self.acted.iter_mut().for_each(|acted| *acted = false);
+            if self.seats.iter().filter(|seat| seat.can_act()).count() > 1 {
+                self.to_act = self.next_seat(self.button, Seat::can_act);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Splits the pot, side pots first, among the best hands still in and
+    /// ends the hand. Odd cents go to the first winner left of the button.
+    fn award(&mut self) {
+        self.to_act = None;
+        let values: Vec<Option<HandValue>> = (0..self.seats.len())
+            .map(|index| {
+                if self.seats[index].in_hand() {
+                    self.best_hand(index)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut levels: Vec<Money> = self
+            .seats
+            .iter()
+            .filter(|seat| seat.in_hand())
+            .map(|seat| seat.committed)
+            .collect();
+        levels.sort();
+        levels.dedup();
+        // Bets beyond what anyone still in could match go to the deepest stack.
+        if let Some(top) = levels.last_mut() {
+            *top = self
+                .seats
+                .iter()
+                .map(|seat| seat.committed)
+                .max()
+                .unwrap_or(*top);
+        }
+        let order: Vec<usize> = (1..=self.seats.len())
+            .map(|step| (self.button + step) % self.seats.len())
+            .collect();
+        let mut previous = Money::ZERO;
+        for level in levels {
+            let pot: Money = self
+                .seats
+                .iter()
+                .map(|seat| seat.committed.min(level) - seat.committed.min(previous))
+                .sum();
+            let contenders: Vec<usize> = order
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    self.seats[index].in_hand() && self.seats[index].committed > previous
+                })
+                .collect();
+            let best = contenders
+                .iter()
+                .map(|&index| values[index])
+                .max()
+                .flatten();
+            let winners: Vec<usize> = contenders
+                .into_iter()
+                .filter(|&index| values[index] == best)
+                .collect();
+            let share = pot.cents() / winners.len().max(1) as i64;
+            let mut odd = pot.cents() - share * winners.len() as i64;
+            for &index in &winners {
+                let amount = Money::from_cents(share + if odd > 0 { 1 } else { 0 });
+                odd -= 1;
+                self.seats[index].chips += amount;
+                self.winnings[index] += amount;
+            }
+            previous = level;
+        }
+        for (index, seat) in self.seats.iter().enumerate() {
+            if self.winnings[index] > Money::ZERO {
+                let hand = match values[index] {
+                    Some(value) if self.showdown => format!(" with {}", value.category),
+                    _ => String::new(),
+                };
+                self.log.push(format!(
+                    "{} wins ${}{}",
+                    seat.name, self.winnings[index], hand
+                ));
+            }
+        }
+        self.street = Street::Showdown;
+    }
+
+    /// Plays every bot turn until it is the player's turn or the hand ends.
+    pub fn play_bots(&mut self) -> Result<(), HoldemError> {
+        while let Some(index) = self.to_act.filter(|&index| self.seats[index].bot) {
+            let action = self.bot_action(index);
+            self.act(action)?;
+        }
+        Ok(())
+    }
+
+    /// A bot's decision: raise with strong hands, call when the hand is
+    /// worth the price, otherwise check or fold, with a little randomness.
+    fn bot_action(&mut self, index: usize) -> Action {
+        let noise = match &mut self.rng {
+            Some(rng) => rng.random_range(-0.1..0.1),
+            None => rand::rng().random_range(-0.1..0.1),
+        };
+        let strength = self.strength(index) + noise;
+        let to_call = self.to_call(index);
+        let pot = self.pot();
+        let can_raise = !self.acted[index] && self.max_raise_to(index) > self.current_bet;
+        if strength > 0.75 && can_raise {
+            // Half the pot, in whole dollars.
+            let size =
+                Money::from_cents(pot.percent(50).whole_dollars() * 100).max(self.last_raise);
+            let to = (self.current_bet + size).min(self.max_raise_to(index));
+            return Action::Raise(to.max(self.min_raise_to(index)));
+        }
+        if to_call == Money::ZERO {
+            return Action::Check;
+        }
+        let pot_odds = to_call.as_dollars() / (pot + to_call).as_dollars();
+        if strength > pot_odds + 0.15 || (to_call <= BIG_BLIND && strength > 0.3) {
+            Action::Call
+        } else {
+            Action::Fold
+        }
+    }
+
+    /// Rough hand strength from 0 to 1: the hole cards before the flop,
+    /// the made hand after.
+    fn strength(&self, index: usize) -> f64 {
+        let hole = &self.seats[index].hole;
+        let Some(value) = self.best_hand(index) else {
+            let (high, low) = match hole[..] {
+                [a, b] => (
+                    high_rank(&a).max(high_rank(&b)),
+                    high_rank(&a).min(high_rank(&b)),
+                ),
+                _ => return 0.0,
+            };
+            let mut strength = (high + low) as f64 / 40.0;
+            if high == low {
+                strength += 0.3;
+            }
+            if hole[0].suit == hole[1].suit {
+                strength += 0.05;
+            }
+            if high - low == 1 {
+                strength += 0.05;
+            }
+            return strength.min(1.0);
+        };
+        match value.category {
+            Category::HighCard => 0.15 + value.ranks[0] as f64 / 100.0,
+            Category::OnePair => 0.35 + value.ranks[0] as f64 / 50.0,
+            Category::TwoPair => 0.7,
+            Category::ThreeOfAKind => 0.8,
+            _ => 0.95,
+        }
+    }
+}
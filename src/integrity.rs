@@ -0,0 +1,185 @@
+//! Tamper evidence for save files: each file the game writes gets an
+//! HMAC-SHA256 sidecar (`<file>.sig`), so editing the file by hand shows up
+//! as a failed check instead of going unnoticed. What a save must keep but
+//! the player shouldn't read, such as the cards left in a shoe, is sealed
+//! under the same key.
+
+use crate::provably_fair::{hex, parse_hex};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Optional passphrase mixed into the signing key, so only someone who knows
+/// it can re-sign an edited file.
+pub const PASSPHRASE_VAR: &str = "TERMINAL_CASINO_PASSPHRASE";
+/// The key without a passphrase. It is public, so what it signs is only
+/// checksummed: it catches careless edits, not forged signatures.
+const DEFAULT_KEY: &str = "terminal_casino save file";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrity {
+    /// The signature matches the contents.
+    Verified,
+    /// No signature: written before signing existed, or the sidecar was
+    /// removed.
+    Unsigned,
+    /// The contents changed since the game last wrote them, or the
+    /// passphrase differs.
+    Tampered,
+}
+
+impl Integrity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Integrity::Verified => "ok",
+            Integrity::Unsigned => "unsigned",
+            Integrity::Tampered => "edited",
+        }
+    }
+}
+
+fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sig");
+    path.with_file_name(name)
+}
+
+/// Whether `path` is a signature sidecar rather than a save file.
+pub fn is_signature(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "sig")
+}
+
+fn mac() -> Hmac<Sha256> {
+    let key = std::env::var(PASSPHRASE_VAR).unwrap_or_else(|_| DEFAULT_KEY.to_string());
+    Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length")
+}
+
+fn digest(contents: &[u8]) -> String {
+    let mut mac = mac();
+    mac.update(contents);
+    hex(&mac.finalize().into_bytes())
+}
+
+/// `text` XORed with a keystream drawn from the signing key and `nonce`;
+/// running it twice gives `text` back.
+fn keystream_xor(nonce: &[u8], text: &[u8]) -> Vec<u8> {
+    let blocks = text.chunks(32).enumerate().flat_map(|(block, chunk)| {
+        let mut mac = mac();
+        mac.update(b"keystream:");
+        mac.update(nonce);
+        mac.update(&(block as u64).to_le_bytes());
+        let pad = mac.finalize().into_bytes();
+        chunk
+            .iter()
+            .zip(pad)
+            .map(|(byte, pad)| byte ^ pad)
+            .collect::<Vec<_>>()
+    });
+    blocks.collect()
+}
+
+/// Bytes of the random nonce a sealed text starts with.
+const NONCE_BYTES: usize = 16;
+/// Bytes of the tag a sealed text ends with.
+const TAG_BYTES: usize = 32;
+
+/// The tag over a sealed text's nonce and ciphertext, which `unseal`
+/// checks before it decrypts anything.
+fn seal_tag(nonce: &[u8], ciphertext: &[u8]) -> Hmac<Sha256> {
+    let mut mac = mac();
+    mac.update(b"sealed:");
+    mac.update(nonce);
+    mac.update(ciphertext);
+    mac
+}
+
+/// Hides `text` from anyone reading the save it is written into, as hex:
+/// the nonce, the ciphertext, then a tag over both.
+pub fn seal(text: &str) -> String {
+    let nonce: [u8; NONCE_BYTES] = rand::random();
+    let ciphertext = keystream_xor(&nonce, text.as_bytes());
+    let tag = seal_tag(&nonce, &ciphertext).finalize().into_bytes();
+    format!("{}{}{}", hex(&nonce), hex(&ciphertext), hex(&tag))
+}
+
+/// The text `seal` hid, once its tag shows it was sealed under this key
+/// and hasn't been changed since.
+pub fn unseal(sealed: &str) -> Result<String, String> {
+    let bytes = parse_hex(sealed)
+        .filter(|bytes| bytes.len() >= NONCE_BYTES + TAG_BYTES)
+        .ok_or("unreadable sealed text")?;
+    let (nonce, rest) = bytes.split_at(NONCE_BYTES);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_BYTES);
+    seal_tag(nonce, ciphertext)
+        .verify_slice(tag)
+        .map_err(|_| "sealed text was edited, or sealed under another passphrase")?;
+    String::from_utf8(keystream_xor(nonce, ciphertext))
+        .map_err(|_| "unreadable sealed text".to_string())
+}
+
+fn sign(path: &Path) -> io::Result<()> {
+    let contents = std::fs::read(path)?;
+    std::fs::write(signature_path(path), digest(&contents) + "\n")
+}
+
+/// Checks a save file against its signature. A file that does not exist
+/// yet has nothing to tamper with and counts as verified.
+pub fn verify(path: &Path) -> Integrity {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(_) => return Integrity::Verified,
+    };
+    match std::fs::read_to_string(signature_path(path)) {
+        Ok(signature) if signature.trim() == digest(&contents) => Integrity::Verified,
+        Ok(_) => Integrity::Tampered,
+        Err(_) => Integrity::Unsigned,
+    }
+}
+
+/// Replaces the file's contents and signs them.
+pub fn write_signed(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, contents)?;
+    sign(path)
+}
+
+/// Appends to the file and re-signs it, but only if it was intact: an
+/// edited or unsigned file keeps failing the check rather than having the
+/// edit signed over.
+pub fn append_signed(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let intact = verify(path) == Integrity::Verified;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", text)?;
+    if intact {
+        sign(path)?;
+    }
+    Ok(())
+}
+
+/// Every save file in a profile that fails its check, with the reason.
+pub fn check_profile(profile: &str) -> Vec<(String, Integrity)> {
+    let dir = crate::storage::profile_dir(profile);
+    let mut flagged: Vec<(String, Integrity)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && !is_signature(path))
+        .filter_map(|path| {
+            let status = verify(&path);
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            (status != Integrity::Verified).then_some((name, status))
+        })
+        .collect();
+    flagged.sort_by(|a, b| a.0.cmp(&b.0));
+    flagged
+}
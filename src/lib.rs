@@ -0,0 +1,58 @@
+//! The game engines behind the terminal casino. The `terminal_casino`
+//! binary is one consumer; anything else can drive a table with the same
+//! rules.
+//!
+//! - `baccarat`: shoes and dealing procedures, the drawing rules for every
+//!   mode, and main and side bet payouts, with `dragon_tiger` and
+//!   `three_card` for the modes that compare cards differently.
+//! - `settlement`: the report every table's bets are paid out as.
+//! - `analytics`: exact house edges for every bet.
+//! - `blackjack`, `holdem`, `war`, `caribbean` and `three_card_poker`:
+//!   other card games dealt from the same cards.
+//! - `craps`, `roulette` and `sicbo`: the dice and wheel games.
+//! - `slots`: slot machines loaded from TOML.
+//! - `strategy` and `script`: betting strategies, built in or scripted in
+//!   Rhai.
+//! - `provably_fair` and `mental_shuffle`: shoes nobody has to take on
+//!   trust.
+//! - `wire`: compact binary frames for game state and cards.
+//! - `test_support`: fixed deals for regression tests of the drawing rules.
+//! - `card_renderer` and `theme`: card art and color themes for ratatui
+//!   front ends.
+//!
+//! ```
+//! use terminal_casino::baccarat::{BaccaratGame, BetType, GameMode, GameSession, Money};
+//!
+//! let mut game = BaccaratGame::with_shoe(GameMode::Classic, 8);
+//! let mut session = GameSession::new(Money::from_dollars(1000));
+//! session.place_main_bet(BetType::Banker, Money::from_dollars(100)).unwrap();
+//! session.place_main_bet(BetType::Tie, Money::from_dollars(10)).unwrap();
+//! session.lock_bets(&game).unwrap();
+//! game.play_round().unwrap();
+//! let report = session.settle(&game).unwrap();
+//! println!("winner {} paid {}", game.state.winner, report.total_payout());
+//! ```
+
+pub mod analytics;
+pub mod baccarat;
+pub mod blackjack;
+pub mod card_renderer;
+pub mod caribbean;
+pub mod craps;
+pub mod dragon_tiger;
+pub mod holdem;
+pub mod mental_shuffle;
+mod poker;
+pub mod provably_fair;
+pub mod roulette;
+pub mod script;
+pub mod settlement;
+pub mod sicbo;
+pub mod slots;
+pub mod strategy;
+pub mod test_support;
+pub mod theme;
+pub mod three_card;
+pub mod three_card_poker;
+pub mod war;
+pub mod wire;
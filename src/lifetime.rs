@@ -0,0 +1,237 @@
+//! Lifetime statistics: every baccarat hand a profile has played, totalled
+//! per game mode and per bet, kept in `profiles/<name>/lifetime.toml` and
+//! saved when the table closes. The `stats` subcommand prints them.
+
+use crate::baccarat::{BetPerformance, GameMode, Money};
+use crate::history::HandRecord;
+use crate::integrity;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Hands at one kind of table and what every bet on them did.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModeTotals {
+    pub hands: u32,
+    pub player_wins: u32,
+    pub banker_wins: u32,
+    pub ties: u32,
+    pub naturals: u32,
+    pub pairs: u32,
+    /// Keyed by bet name, as `Wager` prints it, over every seat.
+    #[serde(default)]
+    pub bets: BTreeMap<String, BetPerformance>,
+}
+
+impl ModeTotals {
+    pub fn record(&mut self, hand: &HandRecord) {
+        self.hands += 1;
+        match hand.winner {
+            1 => self.player_wins += 1,
+            2 => self.banker_wins += 1,
+            _ => self.ties += 1,
+        }
+        self.naturals += hand.natural as u32;
+        self.pairs += hand.is_pair() as u32;
+        for bet in hand.seats.iter().flat_map(|seat| &seat.bets) {
+            let totals = self.bets.entry(bet.wager.to_string()).or_default();
+            totals.bets += 1;
+            totals.wagered += bet.stake;
+            totals.won += bet.payout;
+        }
+    }
+
+    fn add(&mut self, other: &ModeTotals) {
+        self.hands += other.hands;
+        self.player_wins += other.player_wins;
+        self.banker_wins += other.banker_wins;
+        self.ties += other.ties;
+        self.naturals += other.naturals;
+        self.pairs += other.pairs;
+        for (name, performance) in &other.bets {
+            let totals = self.bets.entry(name.clone()).or_default();
+            totals.bets += performance.bets;
+            totals.wagered += performance.wagered;
+            totals.won += performance.won;
+        }
+    }
+
+    pub fn net(&self) -> Money {
+        self.bets.values().map(BetPerformance::net).sum()
+    }
+
+    /// "P 45.9% | B 45.6% | T 8.5%  over 1234 hands", and the naturals and
+    /// pairs under it.
+    pub fn lines(&self) -> Vec<String> {
+        let share = |count: u32| 100.0 * count as f64 / self.hands.max(1) as f64;
+        vec![
+            format!(
+                "P {:.1}% | B {:.1}% | T {:.1}%  over {} hands",
+                share(self.player_wins),
+                share(self.banker_wins),
+                share(self.ties),
+                self.hands
+            ),
+            format!(
+                "Naturals {} ({:.1}%) | Pairs {} ({:.1}%)",
+                self.naturals,
+                share(self.naturals),
+                self.pairs,
+                share(self.pairs)
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LifetimeFile {
+    /// Keyed by the mode's name, e.g. "Classic".
+    #[serde(default)]
+    modes: BTreeMap<String, ModeTotals>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LifetimeStats {
+    profile: String,
+    modes: BTreeMap<String, ModeTotals>,
+    /// A hand has been recorded since the totals were loaded.
+    unsaved: bool,
+}
+
+impl LifetimeStats {
+    fn path(profile: &str) -> PathBuf {
+        storage::profile_dir(profile).join("lifetime.toml")
+    }
+
+    pub fn empty(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            modes: BTreeMap::new(),
+            unsaved: false,
+        }
+    }
+
+    /// Loads the profile's totals; a profile that has never played has none.
+    pub fn load(profile: &str) -> Result<Self, String> {
+        let path = Self::path(profile);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::empty(profile)),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let file: LifetimeFile =
+            toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self {
+            profile: profile.to_string(),
+            modes: file.modes,
+            unsaved: false,
+        })
+    }
+
+    /// Writes the totals back if a hand has been recorded since they were
+    /// loaded.
+    pub fn save(&self) -> io::Result<()> {
+        if !self.unsaved {
+            return Ok(());
+        }
+        let file = LifetimeFile {
+            modes: self.modes.clone(),
+        };
+        let contents = toml::to_string(&file).map_err(io::Error::other)?;
+        integrity::write_signed(&Self::path(&self.profile), &contents)
+    }
+
+    pub fn record(&mut self, hand: &HandRecord) {
+        self.modes
+            .entry(format!("{:?}", hand.mode))
+            .or_default()
+            .record(hand);
+        self.unsaved = true;
+    }
+
+    /// Totals at `mode` tables; empty if the profile has never played one.
+    pub fn mode(&self, mode: GameMode) -> ModeTotals {
+        self.modes
+            .get(&format!("{:?}", mode))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Totals over every mode.
+    pub fn total(&self) -> ModeTotals {
+        let mut total = ModeTotals::default();
+        for totals in self.modes.values() {
+            total.add(totals);
+        }
+        total
+    }
+
+    /// Modes the profile has played at least one hand of.
+    pub fn modes_played(&self) -> usize {
+        self.modes
+            .values()
+            .filter(|totals| totals.hands > 0)
+            .count()
+    }
+}
+
+/// Writes a mode's heading, its outcome lines, and a row per bet.
+fn write_totals(out: &mut impl Write, heading: &str, totals: &ModeTotals) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}: {} hands | Net: {:+}",
+        heading,
+        totals.hands,
+        totals.net()
+    )?;
+    for line in totals.lines() {
+        writeln!(out, "  {}", line)?;
+    }
+    for (name, performance) in &totals.bets {
+        writeln!(
+            out,
+            "  {:<14} {:>6} bets {:>10} wagered {:>10} won {:>+10}  ROI {}",
+            name,
+            performance.bets,
+            performance.wagered,
+            performance.won,
+            performance.net(),
+            performance
+                .roi()
+                .map_or("-".to_string(), |roi| format!("{:+.2}%", roi * 100.0))
+        )?;
+    }
+    Ok(())
+}
+
+/// Loads the profile's lifetime totals and writes them to `out`, every mode
+/// it has played and then all of them together.
+pub fn run(out: &mut impl Write, profile: &str) -> Result<(), String> {
+    let lifetime = LifetimeStats::load(profile)?;
+    write_lifetime(out, profile, &lifetime).map_err(|e| e.to_string())
+}
+
+fn write_lifetime(out: &mut impl Write, profile: &str, lifetime: &LifetimeStats) -> io::Result<()> {
+    let played: Vec<(&String, &ModeTotals)> = lifetime
+        .modes
+        .iter()
+        .filter(|(_, totals)| totals.hands > 0)
+        .collect();
+    if played.is_empty() {
+        writeln!(out, "Profile '{}' has no lifetime statistics yet.", profile)?;
+        return Ok(());
+    }
+    writeln!(out, "Lifetime statistics for profile '{}'", profile)?;
+    for (mode, totals) in played {
+        writeln!(out)?;
+        write_totals(out, &format!("{} tables", mode), totals)?;
+    }
+    writeln!(out)?;
+    write_totals(
+        out,
+        &format!("All modes ({} played)", lifetime.modes_played()),
+        &lifetime.total(),
+    )
+}
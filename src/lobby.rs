@@ -0,0 +1,271 @@
+//! The lobby: every table on the floor and the `CasinoGame` trait the
+//! ratatui UI seats the player through. Baccarat is the host's own table,
+//! with its shoe, roads and hot-seat play; every other game is a
+//! `CasinoGame` opened from the registry. All of them draw on the active
+//! seat's bankroll, so whatever is won at one table is carried to the next.
+
+use crate::baccarat::{Money, TableLimits};
+use crate::config::{Action, Config};
+use crate::tables::{
+    BlackjackTable, CaribbeanTable, CrapsTable, HoldemTable, RouletteTable, SicBoTable, SlotsTable,
+    ThreeCardPokerTable, WarTable,
+};
+use crossterm::event::KeyCode;
+use ratatui::{Frame, layout::Rect};
+
+/// Tables in the lobby.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Game {
+    Baccarat,
+    Blackjack,
+    Holdem,
+    Craps,
+    Roulette,
+    War,
+    SicBo,
+    Slots,
+    CaribbeanStud,
+    ThreeCardPoker,
+}
+
+impl Game {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Game::Baccarat => "Baccarat",
+            Game::Blackjack => "Blackjack",
+            Game::Holdem => "Texas Hold'em",
+            Game::Craps => "Craps",
+            Game::Roulette => "Roulette",
+            Game::War => "Casino War",
+            Game::SicBo => "Sic Bo",
+            Game::Slots => "Slots",
+            Game::CaribbeanStud => "Caribbean Stud",
+            Game::ThreeCardPoker => "Three Card Poker",
+        }
+    }
+}
+
+impl std::str::FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "baccarat" => Ok(Game::Baccarat),
+            "blackjack" | "21" => Ok(Game::Blackjack),
+            "holdem" | "hold'em" | "poker" => Ok(Game::Holdem),
+            "craps" | "dice" => Ok(Game::Craps),
+            "roulette" => Ok(Game::Roulette),
+            "war" | "casino war" => Ok(Game::War),
+            "sicbo" | "sic bo" => Ok(Game::SicBo),
+            "slots" | "slot" => Ok(Game::Slots),
+            "caribbean" | "caribbean stud" | "stud" => Ok(Game::CaribbeanStud),
+            "3cp" | "three card poker" | "threecardpoker" => Ok(Game::ThreeCardPoker),
+            _ => Err(format!(
+                "unknown game '{}', expected baccarat, blackjack, holdem, craps, roulette, war, sicbo, slots, caribbean or 3cp",
+                s
+            )),
+        }
+    }
+}
+
+/// What the host does after a table has handled a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableAction {
+    Stay,
+    /// Back to the lobby; the table stays open until another is chosen.
+    Lobby,
+}
+
+/// What a table may read and change while it handles a key or a tick: the
+/// active seat's bankroll and chip, the house limits, and the status line.
+pub struct TableContext<'a> {
+    /// Name of the player in the active seat.
+    pub player: &'a str,
+    pub balance: &'a mut Money,
+    pub selected_chip: &'a mut i32,
+    pub limits: TableLimits,
+    pub config: &'a Config,
+    pub status: &'a mut Option<String>,
+    /// Bots at a hold'em table when it opens.
+    pub opponents: usize,
+    /// Deals a table's cards from this seed, to play the same hands again.
+    pub seed: Option<u64>,
+}
+
+impl TableContext<'_> {
+    pub fn say(&mut self, message: impl Into<String>) {
+        *self.status = Some(message.into());
+    }
+
+    pub fn is_deal(&self, code: KeyCode) -> bool {
+        self.config.keys.action(code) == Some(Action::Deal)
+    }
+
+    /// Why the seat can't bet with `chip`, if it can't.
+    pub fn chip_unavailable(&self, chip: i32) -> Option<String> {
+        if chip > self.limits.max_bet {
+            Some(format!(
+                "${} chip is over the ${} table maximum",
+                chip, self.limits.max_bet
+            ))
+        } else if Money::from_dollars(chip) > *self.balance {
+            Some(format!(
+                "${} chip is more than the ${} balance",
+                chip, self.balance
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Picks up the chip under key `1`-`5`, or says why it can't be used.
+    /// Returns the chip once it is selected.
+    pub fn select_chip(&mut self, key: char) -> Option<i32> {
+        let chip = self.config.table.chips[key as usize - '1' as usize];
+        if let Some(reason) = self.chip_unavailable(chip) {
+            self.say(reason);
+            return None;
+        }
+        *self.selected_chip = chip;
+        Some(chip)
+    }
+}
+
+/// What a table may read while it draws itself.
+pub struct TableView<'a> {
+    pub balance: Money,
+    pub selected_chip: i32,
+    pub limits: TableLimits,
+    pub config: &'a Config,
+    pub status: Option<&'a str>,
+    /// Fill for face-down cards, from the seasonal event if one is on.
+    pub card_back: char,
+}
+
+impl TableView<'_> {
+    pub fn key(&self, action: Action) -> String {
+        self.config.keys.label(action)
+    }
+
+    /// `name`, followed by the status line when there is one.
+    pub fn panel_title(&self, name: &str) -> String {
+        match self.status {
+            Some(message) => format!("{} — {}", name, message),
+            None => name.to_string(),
+        }
+    }
+}
+
+/// A table the lobby can seat the player at. Stakes come out of the
+/// bankroll in the `TableContext` as they are made and winnings go back
+/// into it as bets settle, so nothing is owed when the player leaves
+/// except what `settle` hands back.
+pub trait CasinoGame {
+    fn game(&self) -> Game;
+
+    fn name(&self) -> &'static str {
+        self.game().name()
+    }
+
+    /// The title bar while seated here.
+    fn title(&self, view: &TableView) -> String;
+
+    /// Draws the table into the cards, info and controls areas of the
+    /// screen, `chunks[1]` to `chunks[3]`.
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView);
+
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction;
+
+    /// Moves animations on and pays out what they were holding back; called
+    /// once a frame.
+    fn tick(&mut self, _ctx: &mut TableContext) {}
+
+    /// Keys are being typed into the table, so the quit key is text.
+    fn typing(&self) -> bool {
+        false
+    }
+
+    /// Why the player can't leave yet, while a hand or spin is unfinished
+    /// or stakes are on the felt.
+    fn busy(&self) -> Option<String>;
+
+    /// Closes the table, returning what the player still has on it: chips
+    /// in play or bets not yet played.
+    fn settle(&mut self) -> Money {
+        Money::ZERO
+    }
+}
+
+pub type OpenTable = fn(&mut TableContext) -> Result<Box<dyn CasinoGame>, String>;
+
+/// A line in the lobby.
+pub struct TableEntry {
+    pub game: Game,
+    /// Opens a fresh table, or `None` for baccarat, which the host plays
+    /// itself: a `CasinoGame` is lent one seat's bankroll through its
+    /// `TableContext`, while hot-seat and split baccarat settle every
+    /// seat's bets on each hand.
+    pub open: Option<OpenTable>,
+}
+
+/// Every table, in lobby order; keys `1` to `9` and then `0` pick them.
+pub const TABLES: [TableEntry; 10] = [
+    TableEntry {
+        game: Game::Baccarat,
+        open: None,
+    },
+    TableEntry {
+        game: Game::Blackjack,
+        open: Some(BlackjackTable::open),
+    },
+    TableEntry {
+        game: Game::Holdem,
+        open: Some(HoldemTable::open),
+    },
+    TableEntry {
+        game: Game::Craps,
+        open: Some(CrapsTable::open),
+    },
+    TableEntry {
+        game: Game::Roulette,
+        open: Some(RouletteTable::open),
+    },
+    TableEntry {
+        game: Game::War,
+        open: Some(WarTable::open),
+    },
+    TableEntry {
+        game: Game::SicBo,
+        open: Some(SicBoTable::open),
+    },
+    TableEntry {
+        game: Game::Slots,
+        open: Some(SlotsTable::open),
+    },
+    TableEntry {
+        game: Game::CaribbeanStud,
+        open: Some(CaribbeanTable::open),
+    },
+    TableEntry {
+        game: Game::ThreeCardPoker,
+        open: Some(ThreeCardPokerTable::open),
+    },
+];
+
+pub fn entry(game: Game) -> &'static TableEntry {
+    TABLES
+        .iter()
+        .find(|entry| entry.game == game)
+        .expect("every game is in the lobby")
+}
+
+/// The key that picks the table at `index` in the lobby.
+pub fn menu_key(index: usize) -> char {
+    char::from_digit(((index + 1) % 10) as u32, 10).unwrap_or('?')
+}
+
+/// The table picked by `key`, `0` being the tenth.
+pub fn from_menu_key(key: char) -> Option<Game> {
+    let digit = key.to_digit(10)? as usize;
+    TABLES.get((digit + 9) % 10).map(|entry| entry.game)
+}
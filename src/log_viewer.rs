@@ -0,0 +1,177 @@
+//! Browser for the saved history files (shoe and session records, presets)
+//! under every profile: list them, open one, scroll, and search.
+
+use crate::integrity::{self, Integrity};
+use crate::storage;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+pub struct SavedFile {
+    /// `profile/file.toml`.
+    pub label: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub integrity: Integrity,
+}
+
+pub struct OpenFile {
+    pub label: String,
+    pub lines: Vec<String>,
+    /// First line shown.
+    pub scroll: usize,
+    pub query: String,
+    /// Lines containing the query, in order.
+    pub matches: Vec<usize>,
+    current_match: Option<usize>,
+}
+
+impl OpenFile {
+    /// Scrolls by `delta` lines, keeping at least one line on screen.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let last = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn scroll_to_end(&mut self) {
+        self.scroll = self.lines.len().saturating_sub(1);
+    }
+
+    /// Finds every line containing `query`, ignoring case, and jumps to the
+    /// first match at or after the current position.
+    pub fn search(&mut self, query: &str) {
+        self.query = query.to_string();
+        let needle = query.to_lowercase();
+        self.matches = if needle.is_empty() {
+            Vec::new()
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.to_lowercase().contains(&needle))
+                .map(|(index, _)| index)
+                .collect()
+        };
+        self.current_match = self
+            .matches
+            .iter()
+            .position(|&line| line >= self.scroll)
+            .or((!self.matches.is_empty()).then_some(0));
+        self.jump_to_match();
+    }
+
+    /// Moves to the next match (or previous, going backwards), wrapping
+    /// around the file.
+    pub fn next_match(&mut self, forward: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.current_match = Some(match self.current_match {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None => 0,
+        });
+        self.jump_to_match();
+    }
+
+    fn jump_to_match(&mut self) {
+        if let Some(line) = self.current_match.map(|index| self.matches[index]) {
+            self.scroll = line;
+        }
+    }
+
+    /// "match 2 of 7", or why there is none.
+    pub fn match_status(&self) -> Option<String> {
+        if self.query.is_empty() {
+            return None;
+        }
+        Some(match self.current_match {
+            Some(index) => format!(
+                "'{}': match {} of {}",
+                self.query,
+                index + 1,
+                self.matches.len()
+            ),
+            None => format!("'{}': no matches", self.query),
+        })
+    }
+
+    pub fn is_match(&self, line: usize) -> bool {
+        self.matches.binary_search(&line).is_ok()
+    }
+}
+
+pub struct LogViewer {
+    pub files: Vec<SavedFile>,
+    pub selected: usize,
+    pub open: Option<OpenFile>,
+    /// Search text being typed, while the search prompt is up.
+    pub search_input: Option<String>,
+}
+
+impl LogViewer {
+    /// Lists every file in every profile directory, newest first.
+    pub fn load() -> Self {
+        let mut files = Vec::new();
+        let profiles = storage::data_dir().join("profiles");
+        for profile in std::fs::read_dir(&profiles).into_iter().flatten().flatten() {
+            for entry in std::fs::read_dir(profile.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if !metadata.is_file() || integrity::is_signature(&entry.path()) {
+                    continue;
+                }
+                files.push(SavedFile {
+                    label: format!(
+                        "{}/{}",
+                        profile.file_name().to_string_lossy(),
+                        entry.file_name().to_string_lossy()
+                    ),
+                    path: entry.path(),
+                    size: metadata.len(),
+                    modified: metadata.modified().ok(),
+                    integrity: integrity::verify(&entry.path()),
+                });
+            }
+        }
+        files.sort_by(|a, b| {
+            b.modified
+                .cmp(&a.modified)
+                .then_with(|| a.label.cmp(&b.label))
+        });
+        Self {
+            files,
+            selected: 0,
+            open: None,
+            search_input: None,
+        }
+    }
+
+    pub fn select_by(&mut self, delta: isize) {
+        let last = self.files.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn open_selected(&mut self) -> Result<(), String> {
+        let Some(file) = self.files.get(self.selected) else {
+            return Ok(());
+        };
+        let source =
+            std::fs::read_to_string(&file.path).map_err(|e| format!("{}: {}", file.label, e))?;
+        self.open = Some(OpenFile {
+            label: file.label.clone(),
+            lines: source.lines().map(String::from).collect(),
+            scroll: 0,
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+        });
+        Ok(())
+    }
+}
@@ -1,27 +1,564 @@
-mod baccarat;
-mod card_renderer;
+mod alarms;
+mod analyze;
+mod api;
+mod cli;
+mod client;
+mod compare;
+mod config;
+mod events;
+mod fairness;
+mod goal;
+mod history;
+mod integrity;
+mod lifetime;
+mod log_viewer;
+mod pace;
+mod peer;
+mod playback;
+mod presets;
+mod profiles;
+mod protocol;
+mod replay;
+mod resume;
+mod roads;
+mod ruin;
+mod rules;
+mod scoreboard;
+mod selftest;
+mod server;
+mod sessions;
+mod shoe_import;
+mod significance;
+mod simulate;
+mod stats;
+mod storage;
+mod tableau;
+mod ticker;
+
+use terminal_casino::{
+    analytics, baccarat, blackjack, card_renderer, caribbean, craps, holdem, mental_shuffle,
+    provably_fair, roulette, settlement, sicbo, slots, strategy, test_support, theme,
+    three_card_poker, war, wire,
+};
 
 mod ui;
 use ui::TerminalUI;
 
+mod lobby;
+mod tables;
 mod tui;
+use lobby::Game;
 use tui::RatatuiUI;
 
-use std::env;
+use alarms::StreakAlarms;
+use baccarat::{Card, GameMode, Money, ProcedureProfile};
+use clap::Parser;
+use cli::{Cli, Command, Output, PlayArgs, ServeArgs, SimulateArgs};
+use config::Config;
+use events::{CalendarDate, EventCalendar};
+use playback::PlaybackUI;
+use profiles::Profile;
+use protocol::Role;
+use replay::Replay;
+use resume::SavedSession;
+use rules::{RulesBook, RulesBundle};
+use simulate::Simulation;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+/// Asks on the terminal whether to pick up the saved table; anything but
+/// "n" is yes. With no terminal to ask on, the table starts afresh.
+fn ask_continue(saved: &SavedSession) -> bool {
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    print!("Continue last session ({})? [Y/n] ", saved.summary());
+    let _ = std::io::stdout().flush();
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    !answer.trim().eq_ignore_ascii_case("n")
+}
+
+/// Prints a subcommand's error and exits with a failure.
+fn exit_on_error(result: Result<(), String>) {
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Whether a check that can't be completed passed, printing why it couldn't
+/// and counting it as a failure.
+fn report_failure<E: std::fmt::Display>(result: Result<bool, E>) -> bool {
+    result.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        false
+    })
+}
+
+/// The settings from `config.toml` and `theme.toml`, or the built-in ones
+/// if the files can't be read.
+fn settings() -> Config {
+    match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: settings not loaded ({}), using defaults", e);
+            Config::default()
+        }
+    }
+}
+
+fn simulate(args: SimulateArgs) {
+    let table = settings().table;
+    let simulation = Simulation {
+        mode: args.mode.unwrap_or(table.mode),
+        procedure: args
+            .procedure
+            .unwrap_or_else(|| ProcedureProfile::standard(args.decks.unwrap_or(table.decks))),
+        bet: args.bet,
+        unit: Money::from_dollars(args.unit),
+        strategy: args.strategy,
+        bankroll: args.balance.map(Money::from_dollars),
+        hands: args.hands,
+        seed: args.seed,
+        threads: args
+            .threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |cores| cores.get())),
+        json: args.output == Output::Json,
+        binary: args.binary,
+    };
+    if simulation.binary && std::io::stdout().is_terminal() {
+        exit_on_error(Err(
+            "binary frames are for a pipe or a file; redirect stdout".to_string(),
+        ));
+    }
+    match args.sessions {
+        Some(sessions) => exit_on_error(ruin::run(
+            &mut std::io::stdout().lock(),
+            &simulation,
+            sessions,
+        )),
+        None => exit_on_error(simulate::run(
+            &mut std::io::BufWriter::new(std::io::stdout().lock()),
+            &simulation,
+        )),
+    }
+}
+
+fn serve(args: ServeArgs) -> Result<(), String> {
+    let table = settings().table;
+    server::run(server::ServeOptions {
+        bind: args.bind,
+        tables: args.tables,
+        mode: args.mode.unwrap_or(table.mode),
+        procedure: args
+            .procedure
+            .unwrap_or_else(|| ProcedureProfile::standard(args.decks.unwrap_or(table.decks))),
+        balance: args
+            .balance
+            .map_or(table.starting_balance, Money::from_dollars),
+        betting_secs: args.betting_secs,
+        reveal_secs: args.reveal_secs,
+        continuous: args.continuous,
+        api: args.api,
+        api_origin: args.api_origin,
+        seed: args.seed,
+    })
+}
+
+fn join(
+    address: &str,
+    name: &str,
+    table: Option<String>,
+    watch: bool,
+    list: bool,
+) -> Result<(), String> {
+    let role = if watch { Role::Spectator } else { Role::Player };
+    let settings = settings();
+    let (table, role) = match table {
+        Some(table) => (Some(table), role),
+        None => {
+            let tables = client::list_tables(address)?;
+            if list {
+                client::print_tables(&tables);
+                return Ok(());
+            }
+            if tables.len() < 2 {
+                (None, role)
+            } else {
+                match client::TableBrowser::new(address, tables, role, settings.theme)
+                    .run()
+                    .map_err(|e| e.to_string())?
+                {
+                    Some((table, role)) => (Some(table), role),
+                    None => return Ok(()),
+                }
+            }
+        }
+    };
+    let mut client = client::ClientUI::connect(
+        address,
+        name,
+        table.as_deref(),
+        role,
+        settings.table.chips,
+        settings.theme,
+    )?;
+    client.run().map_err(|e| e.to_string())
+}
+
+fn analyze_shoe(
+    mode: Option<GameMode>,
+    decks: Option<usize>,
+    mut dealt: Vec<Card>,
+    dealt_file: Option<&Path>,
+) -> Result<(), String> {
+    let table = settings().table;
+    if let Some(path) = dealt_file {
+        let source =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        dealt.extend(
+            shoe_import::parse_shoe_csv(&source)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+        );
+    }
+    analyze::run(
+        &mut std::io::stdout().lock(),
+        mode.unwrap_or(table.mode),
+        decks.unwrap_or(table.decks),
+        &dealt,
+    )
+}
+
+fn play_back(path: &Path, start: usize, speed: f64, step: bool) -> Result<(), String> {
+    let (replay, integrity) = Replay::load(path)?;
+    let file = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    PlaybackUI::new(
+        replay,
+        integrity,
+        file,
+        start,
+        speed,
+        step,
+        settings().theme,
+    )
+    .run()
+    .map_err(|e| e.to_string())
+}
 
 #[tokio::main]
 async fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() > 1 && args[1] == "--ratatui" {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Selftest { shuffles }) => {
+            let passed = selftest::run(&mut std::io::stdout().lock(), shuffles);
+            std::process::exit(if report_failure(passed) { 0 } else { 1 });
+        }
+        Some(Command::Tableau) => {
+            let matched = tableau::run(&mut std::io::stdout().lock());
+            std::process::exit(if report_failure(matched) { 0 } else { 1 });
+        }
+        Some(Command::Analyze {
+            mode,
+            decks,
+            dealt,
+            dealt_file,
+        }) => {
+            exit_on_error(analyze_shoe(mode, decks, dealt, dealt_file.as_deref()));
+        }
+        Some(Command::Verify {
+            profile,
+            server_seed,
+            client_seed,
+            hash,
+            mode,
+            decks,
+        }) => {
+            let mut out = std::io::stdout().lock();
+            let passed = match (server_seed, client_seed) {
+                (Some(server_seed), Some(client_seed)) => fairness::verify_seeds(
+                    &mut out,
+                    &server_seed,
+                    &client_seed,
+                    hash.as_deref(),
+                    mode.unwrap_or(GameMode::Classic),
+                    decks.unwrap_or(baccarat::SHOE_DECKS),
+                )
+                .map_err(|e| e.to_string()),
+                _ => fairness::verify_log(&mut out, &profile),
+            };
+            std::process::exit(if report_failure(passed) { 0 } else { 1 });
+        }
+        Some(Command::Replay {
+            file,
+            mode,
+            legacy_mode,
+            speed,
+            step,
+            hand,
+            output,
+        }) => {
+            if file
+                .extension()
+                .is_some_and(|extension| extension == "toml")
+            {
+                if output == Output::Json {
+                    exit_on_error(Err(
+                        "--output json is for recorded shoes; a replay file plays back on screen"
+                            .to_string(),
+                    ));
+                }
+                exit_on_error(play_back(&file, hand - 1, speed, step));
+            } else {
+                let mode = legacy_mode.or(mode).unwrap_or(GameMode::Classic);
+                exit_on_error(shoe_import::run(
+                    &mut std::io::stdout().lock(),
+                    &file,
+                    mode,
+                    output == Output::Json,
+                ));
+            }
+        }
+        Some(Command::Compare { profiles }) => {
+            exit_on_error(compare::run(&mut std::io::stdout().lock(), &profiles))
+        }
+        Some(Command::Stats { profile }) => {
+            exit_on_error(lifetime::run(&mut std::io::stdout().lock(), &profile));
+            exit_on_error(
+                Profile::load(&profile).map(|profile| profiles::print_achievements(&profile)),
+            );
+        }
+        Some(Command::Profiles) => exit_on_error(profiles::run()),
+        Some(Command::Simulate(args)) => simulate(args),
+        Some(Command::Ticker { bet, seed }) => {
+            let mut ticker =
+                ticker::TickerUI::new(baccarat::GameMode::Classic, bet, seed, settings().theme);
+            if let Err(e) = ticker.run() {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Some(Command::Serve(args)) => exit_on_error(serve(args)),
+        Some(Command::Join {
+            address,
+            name,
+            table,
+            watch,
+            list,
+        }) => exit_on_error(join(&address, &name, table, watch, list)),
+        Some(Command::Peer {
+            listen,
+            connect,
+            name,
+            mode,
+            decks,
+            balance,
+        }) => {
+            let table = settings().table;
+            exit_on_error(peer::run(peer::PeerOptions {
+                listen,
+                connect,
+                name,
+                mode: mode.unwrap_or(table.mode),
+                decks: decks.unwrap_or(table.decks),
+                balance: balance.map_or(table.starting_balance, Money::from_dollars),
+            }))
+        }
+        Some(Command::Play(args)) => play(args).await,
+        None => play(cli.play).await,
+    }
+}
+
+async fn play(args: PlayArgs) {
+    let calendar = EventCalendar::load().unwrap_or_else(|e| {
+        eprintln!(
+            "Warning: could not load events ({}), using built-in calendar",
+            e
+        );
+        EventCalendar::builtin()
+    });
+    let event = calendar.active_on(CalendarDate::today()).cloned();
+
+    let mut alarms = StreakAlarms::defaults();
+    if !args.alarms.is_empty() {
+        alarms.rules = args.alarms.clone();
+    }
+    if args.no_bell {
+        alarms.bell = false;
+    }
+
+    let profile_name = match &args.profile {
+        Some(name) => name.clone(),
+        None if args.demo => storage::DEFAULT_PROFILE.to_string(),
+        None => profiles::choose(),
+    };
+    let profile = Profile::load_or_ask(&profile_name).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    // The profile's bankroll and preferred mode stand in for the settings
+    // file's; flags override both.
+    let (mut config, config_error) = match Config::load() {
+        Ok(config) => (config, None),
+        Err(e) => (Config::default(), Some(e)),
+    };
+    config.table.starting_balance = profile.bankroll_or(config.table.starting_balance);
+    if let Some(mode) = args.mode.or(profile.preferences().mode()) {
+        config.table.mode = mode;
+    }
+    if let Some(decks) = args.decks {
+        config.table.decks = decks;
+    }
+    if let Some(balance) = args.balance {
+        config.table.starting_balance = Money::from_dollars(balance);
+    }
+    if let Some(rng) = args.rng {
+        config.table.rng = rng;
+    }
+
+    let rules_book = match RulesBook::load() {
+        Ok(book) => book,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let rules_name = args.rules.as_deref().unwrap_or(rules::DEFAULT_BUNDLE);
+    let mut rules = match rules_book.find(rules_name) {
+        Some(bundle) => bundle.clone(),
+        None if rules_name == rules::DEFAULT_BUNDLE => RulesBundle::classic(),
+        None => {
+            eprintln!(
+                "Error: unknown rules '{}', expected one of: {}",
+                rules_name,
+                rules_book.names().join(", ")
+            );
+            std::process::exit(2);
+        }
+    };
+
+    // Without a bundle named, the table opens as the settings file says.
+    // Individual flags override either.
+    if args.rules.is_none() || args.mode.is_some() {
+        rules.mode = config.table.mode;
+    }
+    if args.rules.is_none() || args.decks.is_some() {
+        rules.procedure = ProcedureProfile::standard(config.table.decks);
+    }
+    if let Some(line) = args.ou_line {
+        rules.over_under.line = line;
+    }
+    if let Some((over, under)) = args.ou_pay {
+        rules.over_under.over_return_pct = over;
+        rules.over_under.under_return_pct = under;
+    }
+    if let Some(procedure) = &args.procedure {
+        rules.procedure = procedure.clone();
+    }
+
+    let players: Vec<String> = args
+        .players
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+
+    if args.ratatui {
         // Use the new ratatui interface
-        let mut app = RatatuiUI::new();
+        let mut app = RatatuiUI::new(config, config_error);
+        app.set_event(event);
+        app.set_alarms(alarms);
+        if let Some(seed) = args.seed {
+            app.set_seed(seed);
+        }
+        if let Some(client_seed) = args.client_seed.clone() {
+            app.set_client_seed(client_seed);
+        }
+        app.set_rules(rules);
+        app.set_players(&players);
+        app.set_profile(profile);
+        // Offer the table saved on the last quit, unless told to start afresh.
+        let mut resumed = false;
+        if !args.demo && !args.new {
+            match SavedSession::load(&profile_name) {
+                Ok(Some(saved)) if args.resume || ask_continue(&saved) => {
+                    match app.resume(&saved) {
+                        Ok(()) => resumed = true,
+                        Err(e) => eprintln!(
+                            "Warning: last session not continued ({}), starting a new one",
+                            e
+                        ),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Warning: last session not continued, {}", e),
+            }
+        }
+        if let Some(target) = args.goal {
+            app.set_goal(target);
+        }
+        if args.split {
+            app.set_split_view();
+        }
+        if args.demo {
+            app.set_demo();
+        }
+        if let Some(opponents) = args.opponents {
+            app.set_opponents(opponents);
+        }
+        match args.game {
+            Some(game) => app.set_game(game),
+            // Side-by-side play, the attract loop and autoplay are baccarat's own
+            None if !args.split && !args.demo && args.autoplay.is_none() && !resumed => {
+                app.open_lobby()
+            }
+            None => {}
+        }
+        if let Some(strategy) = args.autoplay {
+            if args.game.is_some_and(|game| game != Game::Baccarat) {
+                eprintln!("Warning: autoplay bets at the baccarat table; ignoring --autoplay");
+            } else {
+                app.set_autoplay(strategy);
+            }
+        }
         if let Err(e) = app.run().await {
             eprintln!("Error: {}", e);
         }
     } else {
         // Use the original crossterm interface
-        let mut terminal = TerminalUI::new();
+        if !players.is_empty() {
+            eprintln!("Warning: hot-seat play needs --ratatui; starting a single-player table");
+        }
+        if args.demo {
+            eprintln!("Warning: demo mode needs --ratatui; ignoring --demo");
+        }
+        if args.autoplay.is_some() {
+            eprintln!("Warning: autoplay needs --ratatui; ignoring --autoplay");
+        }
+        if args.game.is_some_and(|game| game != Game::Baccarat) {
+            eprintln!("Warning: the lobby needs --ratatui; starting a baccarat table");
+        }
+        let mut terminal = TerminalUI::new(config, config_error);
+        terminal.set_event(event);
+        terminal.set_alarms(alarms);
+        if let Some(seed) = args.seed {
+            terminal.set_seed(seed);
+        }
+        if let Some(client_seed) = args.client_seed.clone() {
+            terminal.set_client_seed(client_seed);
+        }
+        terminal.set_rules(rules);
+        terminal.set_profile(profile);
+        if let Some(target) = args.goal {
+            terminal.set_goal(target);
+        }
         if let Err(e) = terminal.run() {
             eprintln!("Error: {}", e);
         }
@@ -0,0 +1,143 @@
+//! Mental shuffling for two players who don't trust each other, and no
+//! house to trust either. Nobody shuffles the shoe up front: it sits in a
+//! fixed order, and each hand's cards are drawn from what is left of it
+//! by a generator keyed with a secret from each player. Before a hand both
+//! publish the SHA-256 of a fresh secret, then lock in their bets, then
+//! reveal. The key is the two secrets XORed together, which comes out the
+//! same whichever way round they are taken, so both sides work out the
+//! same cards, and neither can steer them: each committed to their secret
+//! before seeing the other's. Every reveal is checked against its
+//! commitment, and the hand can be dealt again from the two secrets.
+//!
+//! What the XOR can't stop is an abort: whoever reveals second knows the
+//! key, and so the hand, before the other does, and could refuse to reveal
+//! whenever it would lose. The cards stay fair but that player's results
+//! would not, so `peer` holds a bet left down by a player who stops there
+//! as lost, which is never better than playing the hand out.
+
+use crate::baccarat::{BaccaratGame, Card, GameMode};
+use crate::provably_fair::{FairRng, ServerSeed, hex, parse_hex};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Bytes in each player's secret.
+const SECRET_BYTES: usize = 32;
+/// Cards drawn for each hand, enough for any mode's longest.
+const HAND_CARDS: usize = 6;
+/// Cards left when the shoe is replaced, as the cut card would.
+const CUT_CARDS: usize = 52;
+
+/// One player's contribution to a hand's cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secret([u8; SECRET_BYTES]);
+
+impl Secret {
+    pub fn generate() -> Self {
+        Self(rand::rng().random())
+    }
+
+    /// A revealed secret as written: 64 hex digits.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        parse_hex(text)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Self)
+            .ok_or_else(|| {
+                format!(
+                    "invalid secret '{}', expected {} hex digits",
+                    text,
+                    2 * SECRET_BYTES
+                )
+            })
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex(&self.0)
+    }
+
+    /// The SHA-256 hash published before the secret is revealed, as hex.
+    pub fn commitment(&self) -> String {
+        hex(&Sha256::digest(self.to_hex().as_bytes()))
+    }
+
+    /// Whether this is the secret behind `commitment`.
+    pub fn matches(&self, commitment: &str) -> bool {
+        self.commitment().eq_ignore_ascii_case(commitment.trim())
+    }
+
+    /// The key both secrets make together, the same in either order.
+    pub fn combine(&self, other: &Secret) -> ServerSeed {
+        let mut key = [0; SECRET_BYTES];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = self.0[i] ^ other.0[i];
+        }
+        ServerSeed::parse(&hex(&key)).expect("64 hex digits")
+    }
+}
+
+/// The shoe two players deal from, drawn hand by hand with their combined
+/// secrets.
+#[derive(Debug, Clone)]
+pub struct MentalShoe {
+    mode: GameMode,
+    decks: usize,
+    /// Cards still to come, in no particular order until drawn.
+    cards: Vec<Card>,
+    /// Shoes dealt, counting this one.
+    pub shoe: u32,
+}
+
+impl MentalShoe {
+    pub fn new(mode: GameMode, decks: usize) -> Self {
+        let mut shoe = Self {
+            mode,
+            decks,
+            cards: Vec::new(),
+            shoe: 0,
+        };
+        shoe.refill();
+        shoe
+    }
+
+    /// Every card of a fresh shoe, in a fixed order both sides agree on.
+    fn refill(&mut self) {
+        self.cards.clear();
+        let top_rank = self.mode.top_rank();
+        for _ in 0..self.decks {
+            for suit in 0..4 {
+                self.cards
+                    .extend((1..=top_rank).map(|rank| Card::new(suit, rank)));
+            }
+        }
+        self.shoe += 1;
+    }
+
+    pub fn cards_left(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Whether the next hand comes from a fresh shoe.
+    pub fn needs_reshuffle(&self) -> bool {
+        self.cards.len() < CUT_CARDS.min(self.decks * 52 / 2)
+    }
+
+    /// Deals hand number `hand` with the key the two secrets make: draws
+    /// its cards at random from what is left, plays the hand, and puts
+    /// back the cards it didn't use.
+    pub fn deal(&mut self, hand: u32, key: &ServerSeed) -> Result<BaccaratGame, String> {
+        if self.needs_reshuffle() {
+            self.refill();
+        }
+        let mut rng = FairRng::new(key, &format!("hand:{}", hand));
+        let mut drawn = Vec::with_capacity(HAND_CARDS);
+        for _ in 0..HAND_CARDS.min(self.cards.len()) {
+            let index = rng.random_range(0..self.cards.len());
+            drawn.push(self.cards.swap_remove(index));
+        }
+        let mut game = BaccaratGame::with_card_sequence(self.mode, drawn.clone());
+        game.play_round_unattended().map_err(|e| e.to_string())?;
+        let used = game.player_hand.len() + game.banker_hand.len();
+        self.cards.extend_from_slice(&drawn[used..]);
+        Ok(game)
+    }
+}
@@ -0,0 +1,98 @@
+//! Wall-clock pace of a session: hands per hour, time per round, and how long
+//! the player takes to decide between rounds.
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct PaceTracker {
+    session_start: Instant,
+    /// When the table last became ready for bets.
+    ready_since: Instant,
+    round_start: Option<Instant>,
+    rounds: u32,
+    total_round_time: Duration,
+    total_decision_time: Duration,
+    last_round_time: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaceSummary {
+    pub rounds: u32,
+    pub session_secs: f64,
+    pub hands_per_hour: f64,
+    pub avg_round_secs: f64,
+    pub avg_decision_secs: f64,
+}
+
+impl PaceTracker {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            session_start: now,
+            ready_since: now,
+            round_start: None,
+            rounds: 0,
+            total_round_time: Duration::ZERO,
+            total_decision_time: Duration::ZERO,
+            last_round_time: None,
+        }
+    }
+
+    /// The deal key was pressed: closes the decision interval.
+    pub fn round_started(&mut self) {
+        let now = Instant::now();
+        self.total_decision_time += now.duration_since(self.ready_since);
+        self.round_start = Some(now);
+    }
+
+    /// The round is fully revealed and settled; the table is ready again.
+    pub fn round_finished(&mut self) {
+        let now = Instant::now();
+        if let Some(start) = self.round_start.take() {
+            let round_time = now.duration_since(start);
+            self.total_round_time += round_time;
+            self.last_round_time = Some(round_time);
+            self.rounds += 1;
+        }
+        self.ready_since = now;
+    }
+
+    pub fn is_round_in_progress(&self) -> bool {
+        self.round_start.is_some()
+    }
+
+    pub fn last_round_time(&self) -> Option<Duration> {
+        self.last_round_time
+    }
+
+    pub fn summary(&self) -> PaceSummary {
+        let session_secs = self.session_start.elapsed().as_secs_f64();
+        let per_round = |total: Duration| {
+            if self.rounds == 0 {
+                0.0
+            } else {
+                total.as_secs_f64() / self.rounds as f64
+            }
+        };
+        PaceSummary {
+            rounds: self.rounds,
+            session_secs,
+            hands_per_hour: if session_secs > 0.0 {
+                self.rounds as f64 * 3600.0 / session_secs
+            } else {
+                0.0
+            },
+            avg_round_secs: per_round(self.total_round_time),
+            avg_decision_secs: per_round(self.total_decision_time),
+        }
+    }
+}
+
+impl PaceSummary {
+    pub fn describe(&self) -> String {
+        format!(
+            "{:.0} hands/hr | {:.1}s per round | {:.1}s avg decision",
+            self.hands_per_hour, self.avg_round_secs, self.avg_decision_secs
+        )
+    }
+}
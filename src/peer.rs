@@ -0,0 +1,350 @@
+//! `peer`: two players at one baccarat table with no server between them,
+//! the cards drawn by mental shuffling (see `mental_shuffle`) so neither
+//! can know or steer them. One player listens and the other connects; the
+//! one listening sets the mode and the shoe. Each hand runs in three
+//! exchanges, one JSON line each way: commitments to fresh secrets, then
+//! bets, then the secrets. Both ends then deal the same hand, check it
+//! against the other's commitment, and settle both players' bets against
+//! the rules, each player keeping their own bankroll. Experimental.
+
+use crate::baccarat::{BetType, GameMode, Money, TableLimits};
+use crate::mental_shuffle::{MentalShoe, Secret};
+use crate::protocol;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+/// What this build of `peer` speaks; both ends must match.
+const PEER_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PeerMessage {
+    /// Sent by both on connecting; the listener's mode and decks are the
+    /// table's.
+    Hello {
+        version: u32,
+        name: String,
+        mode: String,
+        decks: usize,
+        balance: Money,
+    },
+    /// The SHA-256 of the secret for `hand`.
+    Commit { hand: u32, commitment: String },
+    /// The bet for `hand`, or none to sit it out.
+    Bet {
+        hand: u32,
+        bet: Option<String>,
+        amount: i32,
+    },
+    /// The secret behind the commitment.
+    Reveal { hand: u32, secret: String },
+    /// Leaving the table.
+    Bye,
+}
+
+/// How a `peer` table is set up.
+pub struct PeerOptions {
+    /// Listen here for the other player, or connect there.
+    pub listen: Option<String>,
+    pub connect: Option<String>,
+    pub name: String,
+    pub mode: GameMode,
+    pub decks: usize,
+    pub balance: Money,
+}
+
+/// The line to the other player.
+struct Link {
+    stream: TcpStream,
+    lines: io::Lines<BufReader<TcpStream>>,
+}
+
+impl Link {
+    fn send(&mut self, message: &PeerMessage) -> Result<(), String> {
+        protocol::send(&mut self.stream, message).map_err(|e| format!("the other player: {}", e))
+    }
+
+    /// The next message, or `None` if the other player is gone.
+    fn receive(&mut self) -> Result<Option<PeerMessage>, String> {
+        match self.lines.next() {
+            Some(Ok(line)) => serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| format!("unreadable message: {}", e)),
+            Some(Err(_)) | None => Ok(None),
+        }
+    }
+}
+
+/// A player at the table: either end.
+struct Player {
+    name: String,
+    balance: Money,
+    bet: Option<(BetType, Money)>,
+}
+
+/// Takes the bet `player` left down on a hand they stopped before it could
+/// be dealt. Whoever reveals second has seen both secrets, and so the
+/// cards, before the other has; losing the stake by walking away leaves
+/// nothing to gain over playing the hand out.
+fn forfeit(player: &mut Player, why: &str) {
+    if let Some((bet, stake)) = player.bet.take() {
+        player.balance -= stake;
+        println!(
+            "{} {}; their ${} on {} is forfeit, balance ${}",
+            player.name, why, stake, bet, player.balance
+        );
+    }
+}
+
+/// Asks for this hand's bet until one the table takes is given: `None` to
+/// sit out, or `Err` to leave.
+fn ask_bet(
+    mode: GameMode,
+    limits: &TableLimits,
+    balance: Money,
+    hand: u32,
+) -> Result<Option<(BetType, Money)>, ()> {
+    let stdin = io::stdin();
+    loop {
+        print!(
+            "Hand {}: bet as BET AMOUNT (e.g. banker 25), ENTER to sit out, Q to leave: ",
+            hand
+        );
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Err(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let reply = match words.as_slice() {
+            [] => return Ok(None),
+            [quit] if quit.eq_ignore_ascii_case("q") => return Err(()),
+            [bet, amount] => match (
+                bet.parse::<BetType>(),
+                amount.trim_start_matches('$').parse::<i32>(),
+            ) {
+                (Ok(bet), _) if !mode.main_bets().contains(&bet) => {
+                    format!("{:?} tables don't take {}", mode, bet)
+                }
+                (Ok(_), Ok(amount)) if !(limits.min_bet..=limits.max_bet).contains(&amount) => {
+                    format!("bets run from ${} to ${}", limits.min_bet, limits.max_bet)
+                }
+                (Ok(_), Ok(amount)) if Money::from_dollars(amount) > balance => {
+                    format!("only ${} to bet with", balance)
+                }
+                (Ok(bet), Ok(amount)) => return Ok(Some((bet, Money::from_dollars(amount)))),
+                (Err(e), _) => e,
+                (_, Err(_)) => format!("'{}' is not an amount", amount),
+            },
+            _ => "give a bet and an amount".to_string(),
+        };
+        println!("  {}", reply);
+    }
+}
+
+fn connect(options: &PeerOptions) -> Result<Link, String> {
+    let stream = match (&options.listen, &options.connect) {
+        (Some(address), _) => {
+            let listener = TcpListener::bind(address).map_err(|e| format!("{}: {}", address, e))?;
+            let address = listener.local_addr().map_err(|e| e.to_string())?;
+            println!(
+                "Waiting for the other player; they connect with: terminal_casino peer --connect {}",
+                address
+            );
+            listener.accept().map_err(|e| e.to_string())?.0
+        }
+        (None, Some(address)) => {
+            TcpStream::connect(address).map_err(|e| format!("{}: {}", address, e))?
+        }
+        (None, None) => return Err("listen for the other player or connect to them".to_string()),
+    };
+    let lines = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?).lines();
+    Ok(Link { stream, lines })
+}
+
+/// Plays hands with the other player until either leaves.
+pub fn run(options: PeerOptions) -> Result<(), String> {
+    let mut link = connect(&options)?;
+    link.send(&PeerMessage::Hello {
+        version: PEER_VERSION,
+        name: options.name.clone(),
+        mode: format!("{:?}", options.mode),
+        decks: options.decks,
+        balance: options.balance,
+    })?;
+    let (them, mode, decks) = match link.receive()? {
+        Some(PeerMessage::Hello { version, .. }) if version != PEER_VERSION => {
+            return Err(format!(
+                "the other player speaks version {} and this end {}",
+                version, PEER_VERSION
+            ));
+        }
+        Some(PeerMessage::Hello {
+            name,
+            mode,
+            decks,
+            balance,
+            ..
+        }) => {
+            let them = Player {
+                name,
+                balance,
+                bet: None,
+            };
+            // The listener's table is the one played.
+            match options.listen {
+                Some(_) => (them, options.mode, options.decks),
+                None => (them, mode.parse()?, decks),
+            }
+        }
+        Some(other) => return Err(format!("expected a hello, got {:?}", other)),
+        None => return Err("the other player hung up".to_string()),
+    };
+    let mut players = [
+        Player {
+            name: options.name.clone(),
+            balance: options.balance,
+            bet: None,
+        },
+        them,
+    ];
+    let limits = TableLimits::STANDARD;
+    let mut shoe = MentalShoe::new(mode, decks);
+    println!(
+        "{} vs {}: {:?}, {} decks, cards drawn by mental shuffle",
+        players[0].name, players[1].name, mode, decks
+    );
+
+    for hand in 1.. {
+        let secret = Secret::generate();
+        link.send(&PeerMessage::Commit {
+            hand,
+            commitment: secret.commitment(),
+        })?;
+        let commitment = match link.receive()? {
+            Some(PeerMessage::Commit {
+                hand: theirs,
+                commitment,
+            }) if theirs == hand => commitment,
+            Some(PeerMessage::Bye) | None => break,
+            Some(other) => {
+                return Err(format!(
+                    "expected hand {}'s commitment, got {:?}",
+                    hand, other
+                ));
+            }
+        };
+
+        let Ok(bet) = ask_bet(mode, &limits, players[0].balance, hand) else {
+            let _ = link.send(&PeerMessage::Bye);
+            break;
+        };
+        players[0].bet = bet;
+        link.send(&PeerMessage::Bet {
+            hand,
+            bet: bet.map(|(bet, _)| bet.to_string()),
+            amount: bet.map_or(0, |(_, stake)| stake.whole_dollars() as i32),
+        })?;
+        players[1].bet = match link.receive()? {
+            Some(PeerMessage::Bet {
+                hand: theirs,
+                bet,
+                amount,
+            }) if theirs == hand => match bet {
+                Some(bet) => Some((bet.parse()?, Money::from_dollars(amount))),
+                None => None,
+            },
+            Some(PeerMessage::Bye) | None => break,
+            Some(other) => return Err(format!("expected hand {}'s bet, got {:?}", hand, other)),
+        };
+        if let Some((bet, stake)) = players[1].bet {
+            let stake_dollars = stake.whole_dollars() as i32;
+            if !mode.main_bets().contains(&bet)
+                || !(limits.min_bet..=limits.max_bet).contains(&stake_dollars)
+                || stake > players[1].balance
+            {
+                return Err(format!(
+                    "{}'s ${} on {} is not a bet this table takes; stopping",
+                    players[1].name, stake, bet
+                ));
+            }
+        }
+
+        link.send(&PeerMessage::Reveal {
+            hand,
+            secret: secret.to_hex(),
+        })?;
+        let theirs = match link.receive()? {
+            Some(PeerMessage::Reveal {
+                hand: theirs,
+                secret,
+            }) if theirs == hand => Secret::parse(&secret)
+                .ok()
+                .filter(|secret| secret.matches(&commitment)),
+            Some(PeerMessage::Bye) | None => {
+                forfeit(&mut players[1], "left without revealing");
+                break;
+            }
+            Some(other) => {
+                forfeit(&mut players[1], "didn't reveal");
+                return Err(format!("expected hand {}'s secret, got {:?}", hand, other));
+            }
+        };
+        let Some(theirs) = theirs else {
+            forfeit(&mut players[1], "revealed the wrong secret");
+            return Err(format!(
+                "{}'s secret for hand {} doesn't match their commitment; stopping",
+                players[1].name, hand
+            ));
+        };
+
+        let shoe_before = shoe.shoe;
+        let key = secret.combine(&theirs);
+        let game = shoe.deal(hand, &key)?;
+        if shoe.shoe != shoe_before {
+            println!("New shoe: shoe {}", shoe.shoe);
+        }
+        let result = match game.state.winner {
+            1 => "Player wins",
+            2 => "Banker wins",
+            _ => "Tie",
+        };
+        println!(
+            "  Player {} ({})  Banker {} ({})  {} {}-{}",
+            protocol::card_labels(&game.player_hand).join(" "),
+            game.state.player_score,
+            protocol::card_labels(&game.banker_hand).join(" "),
+            game.state.banker_score,
+            result,
+            game.state.player_score,
+            game.state.banker_score
+        );
+        for player in &mut players {
+            match player.bet.take() {
+                Some((bet, stake)) => {
+                    let net = game.calculate_main_bet_payout(bet, stake) - stake;
+                    player.balance += net;
+                    println!(
+                        "  {:<16} {:<16} {:>+9}  balance ${}",
+                        player.name,
+                        format!("${} on {}", stake, bet),
+                        net,
+                        player.balance
+                    );
+                }
+                None => println!(
+                    "  {:<16} {:<16} {:>9}  balance ${}",
+                    player.name, "sat out", "", player.balance
+                ),
+            }
+        }
+        println!(
+            "  key {} ({} cards left in the shoe)",
+            key.as_str(),
+            shoe.cards_left()
+        );
+    }
+    println!("Left the table with ${}", players[0].balance);
+    Ok(())
+}
@@ -0,0 +1,385 @@
+//! Replay playback: a recorded session dealt again hand by hand, each
+//! seat's bets beside what the engine pays on the same cards, so a payout
+//! that doesn't add up stands out. Plays on its own at a set pace or steps
+//! through under the player's hand.
+
+use crate::baccarat::{Card, GameMode, Money, Side};
+use crate::card_renderer::{CardRenderer, HandScore};
+use crate::events::CalendarDate;
+use crate::integrity::Integrity;
+use crate::replay::{Recheck, Replay, ReplayHand};
+use crate::scoreboard::{Bead, BeadPlate, BeadRoad};
+use crate::theme::Theme;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+/// Time each hand stays up at speed 1.
+const BASE_INTERVAL: Duration = Duration::from_millis(2000);
+/// Fastest and slowest the speed keys go.
+const MIN_SPEED: f64 = 0.25;
+pub const MAX_SPEED: f64 = 20.0;
+
+pub struct PlaybackUI {
+    replay: Replay,
+    integrity: Integrity,
+    /// The file's name, for the title.
+    file: String,
+    /// The hand on screen.
+    index: usize,
+    playing: bool,
+    /// Hands go by `speed` times as fast as at speed 1.
+    speed: f64,
+    next_step: Instant,
+    /// Each hand's bets re-settled by the engine, seat by seat.
+    rechecks: Vec<Vec<Vec<Recheck>>>,
+    theme: Theme,
+}
+
+impl PlaybackUI {
+    /// Opens on hand `start` (0 for the first), playing from there unless
+    /// `step` says to wait for the arrow keys.
+    pub fn new(
+        replay: Replay,
+        integrity: Integrity,
+        file: String,
+        start: usize,
+        speed: f64,
+        step: bool,
+        theme: Theme,
+    ) -> Self {
+        let rechecks = replay
+            .hands
+            .iter()
+            .map(|hand| replay.recheck(hand))
+            .collect();
+        let index = start.min(replay.hands.len() - 1);
+        Self {
+            replay,
+            integrity,
+            file,
+            index,
+            playing: !step,
+            speed: speed.clamp(MIN_SPEED, MAX_SPEED),
+            next_step: Instant::now(),
+            rechecks,
+            theme,
+        }
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        self.next_step = Instant::now() + self.interval();
+        let res = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        res
+    }
+
+    fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        loop {
+            if self.playing && Instant::now() >= self.next_step {
+                self.advance();
+            }
+            terminal.draw(|f| self.ui(f))?;
+
+            if event::poll(Duration::from_millis(50))?
+                && let Event::Key(key) = event::read()?
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => self.toggle_play(),
+                    KeyCode::Right | KeyCode::Char('n') => self.step_to(self.index + 1),
+                    KeyCode::Left | KeyCode::Char('p') => {
+                        self.step_to(self.index.saturating_sub(1))
+                    }
+                    KeyCode::Home => self.step_to(0),
+                    KeyCode::End => self.step_to(self.replay.hands.len() - 1),
+                    KeyCode::Char('d') => self.next_dispute(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        self.speed = (self.speed * 2.0).min(MAX_SPEED)
+                    }
+                    KeyCode::Char('-') => self.speed = (self.speed / 2.0).max(MIN_SPEED),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        BASE_INTERVAL.div_f64(self.speed)
+    }
+
+    /// Moves on a hand while playing, stopping on the last.
+    fn advance(&mut self) {
+        if self.index + 1 < self.replay.hands.len() {
+            self.index += 1;
+            self.next_step = Instant::now() + self.interval();
+        } else {
+            self.playing = false;
+        }
+    }
+
+    /// Plays or pauses; playing from the last hand starts over.
+    fn toggle_play(&mut self) {
+        self.playing = !self.playing;
+        if self.playing && self.index + 1 == self.replay.hands.len() {
+            self.index = 0;
+        }
+        self.next_step = Instant::now() + self.interval();
+    }
+
+    /// Shows hand `index` and pauses there.
+    fn step_to(&mut self, index: usize) {
+        self.index = index.min(self.replay.hands.len() - 1);
+        self.playing = false;
+    }
+
+    fn has_dispute(&self, index: usize) -> bool {
+        self.rechecks[index]
+            .iter()
+            .flatten()
+            .any(|check| matches!(check, Recheck::Differs(_)))
+    }
+
+    /// Jumps to the next hand a payout doesn't match on, if there is one.
+    fn next_dispute(&mut self) {
+        if let Some(index) =
+            (self.index + 1..self.replay.hands.len()).find(|&i| self.has_dispute(i))
+        {
+            self.step_to(index);
+        }
+    }
+
+    fn hand(&self) -> &ReplayHand {
+        &self.replay.hands[self.index]
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(10),
+                Constraint::Min(6),
+                Constraint::Length(8),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+
+        let disputes = (0..self.replay.hands.len())
+            .filter(|&i| self.has_dispute(i))
+            .count();
+        let check = match disputes {
+            0 => Span::styled("every payout matches", Style::default().fg(self.theme.win)),
+            1 => Span::styled(
+                "1 hand paid differently",
+                Style::default().fg(self.theme.loss),
+            ),
+            n => Span::styled(
+                format!("{} hands paid differently", n),
+                Style::default().fg(self.theme.loss),
+            ),
+        };
+        let signature = match self.integrity {
+            Integrity::Verified => Span::styled("signed", Style::default().fg(self.theme.win)),
+            status => Span::styled(
+                format!("file {}", status.label()),
+                Style::default().fg(self.theme.loss),
+            ),
+        };
+        let seed = self
+            .replay
+            .seed
+            .map_or("random shuffle".to_string(), |seed| {
+                format!("seed {}", seed)
+            });
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    "REPLAY - {}  |  {} on {}  |  {} rules, {}  |  ",
+                    self.file,
+                    self.replay.profile,
+                    CalendarDate::from_unix(self.replay.recorded_at),
+                    self.replay.rules.name,
+                    seed
+                ),
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            check,
+            Span::raw("  |  "),
+            signature,
+        ]))
+        .block(self.theme.panel());
+        f.render_widget(title, chunks[0]);
+
+        self.render_hands(f, chunks[1]);
+        self.render_bets(f, chunks[2]);
+
+        let shoe = self.hand().shoe;
+        let beads: Vec<Bead> = self.replay.hands[..=self.index]
+            .iter()
+            .filter(|hand| hand.shoe == shoe)
+            .filter_map(bead)
+            .collect();
+        let plate = BeadPlate::from_beads(beads);
+        f.render_widget(
+            BeadRoad::new(&plate).theme(&self.theme).block(
+                self.theme
+                    .panel()
+                    .title(format!("Bead Plate - Shoe {}", shoe)),
+            ),
+            chunks[3],
+        );
+
+        let state = if self.playing {
+            format!("PLAYING x{}", self.speed)
+        } else {
+            "PAUSED".to_string()
+        };
+        let help = Paragraph::new(format!(
+            "{}  [SPACE] Play/Pause  [←/→] Step  [HOME/END] First/Last  [D] Next disputed hand  [+/-] Speed  [Q/ESC] Quit",
+            state
+        ))
+        .style(Style::default().fg(self.theme.muted));
+        f.render_widget(help, chunks[4]);
+    }
+
+    fn render_hands(&self, f: &mut Frame, area: Rect) {
+        let hand = self.hand();
+        let mode = hand.mode.parse::<GameMode>().unwrap_or(GameMode::Classic);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let Ok((player, banker)) = hand.cards() else {
+            f.render_widget(
+                Paragraph::new("The hand's cards could not be read").block(self.theme.panel()),
+                area,
+            );
+            return;
+        };
+        for (side, cards, score, column) in [
+            (Side::Player, &player, hand.player_score, columns[0]),
+            (Side::Banker, &banker, hand.banker_score, columns[1]),
+        ] {
+            f.render_widget(
+                CardRenderer::create_partial_hand_display(
+                    cards,
+                    mode.side_name(side).to_uppercase(),
+                    HandScore::Points(score),
+                    cards.len(),
+                    '░',
+                    column.width,
+                    &self.theme,
+                ),
+                column,
+            );
+        }
+    }
+
+    fn render_bets(&self, f: &mut Frame, area: Rect) {
+        let hand = self.hand();
+        let result = match hand.winner {
+            1 => "Player wins",
+            2 => "Banker wins",
+            _ => "Tie",
+        };
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "Hand {} of {} (shoe {}, {} mode): {} {}-{}",
+                    self.index + 1,
+                    self.replay.hands.len(),
+                    hand.shoe,
+                    hand.mode,
+                    result,
+                    hand.player_score,
+                    hand.banker_score
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for (seat, checks) in hand.seats.iter().zip(&self.rechecks[self.index]) {
+            lines.push(Line::from(format!(
+                "{}  (balance after: ${})",
+                seat.name, seat.balance
+            )));
+            if seat.bets.is_empty() {
+                lines.push(Line::from("  no bets"));
+            }
+            for (bet, check) in seat.bets.iter().zip(checks) {
+                let verdict = match check {
+                    Recheck::Matches => {
+                        Span::styled("matches", Style::default().fg(self.theme.win))
+                    }
+                    Recheck::Differs(paid) => Span::styled(
+                        format!("engine pays ${}", paid),
+                        Style::default()
+                            .fg(self.theme.loss)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Recheck::Unchecked => {
+                        Span::styled("not checked", Style::default().fg(self.theme.muted))
+                    }
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(format!(
+                        "  {:<14} ${:>8} staked  ${:>8} paid  {:>+9}  ",
+                        bet.bet,
+                        bet.stake,
+                        bet.payout,
+                        bet.payout - bet.stake
+                    )),
+                    verdict,
+                ]));
+            }
+        }
+        let net: Money = hand
+            .seats
+            .iter()
+            .flat_map(|seat| &seat.bets)
+            .map(|bet| bet.payout - bet.stake)
+            .sum();
+        let title = format!("Bets  (net {:+})", net);
+        f.render_widget(
+            Paragraph::new(lines).block(self.theme.panel().title(title)),
+            area,
+        );
+    }
+}
+
+/// The hand as the bead plate shows it, pairs included.
+fn bead(hand: &ReplayHand) -> Option<Bead> {
+    let (player, banker) = hand.cards().ok()?;
+    let pair = |cards: &[Card]| matches!(cards, [first, second, ..] if first.rank == second.rank);
+    Some(Bead {
+        winner: hand.winner,
+        player_pair: pair(&player),
+        banker_pair: pair(&banker),
+    })
+}
@@ -0,0 +1,349 @@
+//! Poker hands for the poker tables: the five-card evaluator that values
+//! hold'em's best five of seven and a Caribbean Stud hand, the three-card
+//! ranking Three Card Poker plays by, and the progressive jackpot the table
+//! games' jackpot bets feed. The games re-export what their callers need.
+
+use crate::baccarat::{Card, Money};
+
+/// Hand categories, weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::HighCard => "High Card",
+            Category::OnePair => "Pair",
+            Category::TwoPair => "Two Pair",
+            Category::ThreeOfAKind => "Three of a Kind",
+            Category::Straight => "Straight",
+            Category::Flush => "Flush",
+            Category::FullHouse => "Full House",
+            Category::FourOfAKind => "Four of a Kind",
+            Category::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The best five cards a player can make. Values compare by category,
+/// then by the ranks that break ties within it (aces high at 14), so the
+/// greater value wins and equal values split the pot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandValue {
+    pub category: Category,
+    pub ranks: [u8; 5],
+}
+
+impl HandValue {
+    /// Ace-high straight flush.
+    pub fn is_royal(&self) -> bool {
+        self.category == Category::StraightFlush && self.ranks[0] == 14
+    }
+
+    /// The category's name, or "Royal Flush".
+    pub fn name(&self) -> &'static str {
+        if self.is_royal() {
+            "Royal Flush"
+        } else {
+            self.category.name()
+        }
+    }
+}
+
+/// Rank with aces high, 2 to 14.
+pub(crate) fn high_rank(card: &Card) -> u8 {
+    if card.rank == 1 { 14 } else { card.rank }
+}
+
+/// The top rank of the highest straight in `mask` (bit `r` set for each
+/// rank `r` held, bit 1 standing in for an ace played low).
+fn straight_high(mask: u16) -> Option<u8> {
+    (5..=14u8)
+        .rev()
+        .find(|&high| (high - 4..=high).all(|rank| mask & (1 << rank) != 0))
+}
+
+/// The highest five ranks in `mask`, padded with zeros.
+fn top_ranks(mask: u16, count: usize) -> [u8; 5] {
+    let mut ranks = [0; 5];
+    for (slot, rank) in ranks.iter_mut().zip(
+        (2..=14u8)
+            .rev()
+            .filter(|&rank| mask & (1 << rank) != 0)
+            .take(count),
+    ) {
+        *slot = rank;
+    }
+    ranks
+}
+
+/// Values the best five-card hand among `cards`, which may hold five to
+/// seven cards.
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    let mut counts = [0u8; 15];
+    let mut suits = [0u16; 4];
+    let mut held = 0u16;
+    for card in cards {
+        let rank = high_rank(card);
+        counts[rank as usize] += 1;
+        held |= 1 << rank;
+        suits[card.suit as usize % 4] |= 1 << rank;
+    }
+    let with_low_ace = |mask: u16| {
+        if mask & (1 << 14) != 0 {
+            mask | 1 << 1
+        } else {
+            mask
+        }
+    };
+    let value = |category, ranks| HandValue { category, ranks };
+
+    let flush = suits.iter().copied().find(|mask| mask.count_ones() >= 5);
+    if let Some(high) = flush.and_then(|mask| straight_high(with_low_ace(mask))) {
+        return value(Category::StraightFlush, [high, 0, 0, 0, 0]);
+    }
+
+    // Ranks grouped by how many of each are held, bigger groups first.
+    let mut groups: Vec<(u8, u8)> = (2..=14u8)
+        .rev()
+        .filter(|&rank| counts[rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    groups.sort_by(|a, b| b.cmp(a));
+    let kickers = |used: &[u8], count: usize| {
+        let mask = used.iter().fold(held, |mask, &rank| mask & !(1 << rank));
+        top_ranks(mask, count)
+    };
+
+    match groups[..] {
+        [(4, quads), ..] => {
+            return value(
+                Category::FourOfAKind,
+                [quads, kickers(&[quads], 1)[0], 0, 0, 0],
+            );
+        }
+        [(3, trips), (2.., pair), ..] => return value(Category::FullHouse, [trips, pair, 0, 0, 0]),
+        _ => {}
+    }
+    if let Some(mask) = flush {
+        return value(Category::Flush, top_ranks(mask, 5));
+    }
+    if let Some(high) = straight_high(with_low_ace(held)) {
+        return value(Category::Straight, [high, 0, 0, 0, 0]);
+    }
+    match groups[..] {
+        [(3, trips), ..] => {
+            let [first, second, ..] = kickers(&[trips], 2);
+            value(Category::ThreeOfAKind, [trips, first, second, 0, 0])
+        }
+        [(2, high), (2, low), ..] => value(
+            Category::TwoPair,
+            [high, low, kickers(&[high, low], 1)[0], 0, 0],
+        ),
+        [(2, pair), ..] => {
+            let [first, second, third, ..] = kickers(&[pair], 3);
+            value(Category::OnePair, [pair, first, second, third, 0])
+        }
+        _ => value(Category::HighCard, top_ranks(held, 5)),
+    }
+}
+
+/// A three-card hand. With three cards a straight is rarer than a flush and
+/// three of a kind rarer than either, so categories compare in that order
+/// before the ranks break ties. A-2-3 is the lowest straight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreeCardValue {
+    pub category: Category,
+    pub ranks: [u8; 3],
+}
+
+impl ThreeCardValue {
+    fn strength(&self) -> u8 {
+        match self.category {
+            Category::HighCard => 0,
+            Category::OnePair => 1,
+            Category::Flush => 2,
+            Category::Straight => 3,
+            Category::ThreeOfAKind => 4,
+            _ => 5,
+        }
+    }
+
+    /// Ace, king and queen of one suit.
+    pub fn is_mini_royal(&self) -> bool {
+        self.category == Category::StraightFlush && self.ranks[0] == 14
+    }
+
+    /// The category's name, or "Mini Royal".
+    pub fn name(&self) -> &'static str {
+        if self.is_mini_royal() {
+            "Mini Royal"
+        } else {
+            self.category.name()
+        }
+    }
+}
+
+impl Ord for ThreeCardValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.strength(), self.ranks).cmp(&(other.strength(), other.ranks))
+    }
+}
+
+impl PartialOrd for ThreeCardValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Values the first three of `cards`.
+pub fn evaluate_three(cards: &[Card]) -> ThreeCardValue {
+    let mut ranks = [0u8; 3];
+    for (slot, card) in ranks.iter_mut().zip(cards) {
+        *slot = high_rank(card);
+    }
+    ranks.sort_unstable_by(|a, b| b.cmp(a));
+    let [high, middle, low] = ranks;
+    let flush = cards.len() >= 3 && cards[1..3].iter().all(|card| card.suit == cards[0].suit);
+    let straight = if ranks == [14, 3, 2] {
+        Some([3, 2, 1])
+    } else if high == middle + 1 && middle == low + 1 {
+        Some(ranks)
+    } else {
+        None
+    };
+    let value = |category, ranks| ThreeCardValue { category, ranks };
+    match straight {
+        Some(straight) if flush => value(Category::StraightFlush, straight),
+        _ if high == low => value(Category::ThreeOfAKind, ranks),
+        Some(straight) => value(Category::Straight, straight),
+        None if flush => value(Category::Flush, ranks),
+        None if high == middle => value(Category::OnePair, [high, low, 0]),
+        None if middle == low => value(Category::OnePair, [middle, high, 0]),
+        None => value(Category::HighCard, ranks),
+    }
+}
+
+/// The bets on a poker table game.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PokerBet {
+    Ante,
+    /// Caribbean Stud's raise, twice the ante.
+    Raise,
+    /// Three Card Poker's play bet, matching the ante.
+    Play,
+    PairPlus,
+    Jackpot,
+}
+
+impl std::fmt::Display for PokerBet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            PokerBet::Ante => "ante",
+            PokerBet::Raise => "raise",
+            PokerBet::Play => "play",
+            PokerBet::PairPlus => "pair plus",
+            PokerBet::Jackpot => "jackpot",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PokerError {
+    /// The last hand is still waiting on the player.
+    HandInProgress,
+    /// No hand is waiting on the player.
+    NoHand,
+}
+
+impl std::fmt::Display for PokerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PokerError::HandInProgress => write!(f, "a hand is already in progress"),
+            PokerError::NoHand => write!(f, "no hand is waiting on a decision"),
+        }
+    }
+}
+
+impl std::error::Error for PokerError {}
+
+/// What a winning jackpot bet collects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JackpotAward {
+    /// This percentage of the meter.
+    Meter(i64),
+    /// A set amount, paid by the house rather than off the meter.
+    Fixed(Money),
+}
+
+/// A progressive jackpot: jackpot bets feed it and winning hands draw on
+/// it. Tables take one as `&mut dyn Jackpot`, so a meter shared between
+/// tables or kept outside the game can stand in for `Progressive`.
+pub trait Jackpot {
+    fn meter(&self) -> Money;
+
+    /// Adds the meter's share of a jackpot bet.
+    fn contribute(&mut self, stake: Money);
+
+    /// Pays `award` and returns what it came to.
+    fn pay(&mut self, award: JackpotAward) -> Money;
+}
+
+/// A meter kept with the table: it starts at a seed, grows by a share of
+/// every jackpot bet, and goes back to the seed once it is hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progressive {
+    meter: Money,
+    seed: Money,
+    share_pct: i64,
+}
+
+impl Progressive {
+    pub fn new(seed: Money, share_pct: i64) -> Self {
+        Self {
+            meter: seed,
+            seed,
+            share_pct,
+        }
+    }
+}
+
+impl Jackpot for Progressive {
+    fn meter(&self) -> Money {
+        self.meter
+    }
+
+    fn contribute(&mut self, stake: Money) {
+        self.meter += stake.percent(self.share_pct);
+    }
+
+    fn pay(&mut self, award: JackpotAward) -> Money {
+        match award {
+            JackpotAward::Meter(pct) => {
+                let amount = self.meter.percent(pct);
+                self.meter = (self.meter - amount).max(self.seed);
+                amount
+            }
+            JackpotAward::Fixed(amount) => amount,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Saved bet layouts (every main and side bet) recalled with one key,
+//! stored per profile.
+
+use crate::baccarat::{BetSlip, BetType, BonusBetType, Money};
+use crate::integrity;
+use crate::settlement::Wager;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+pub const PRESET_SLOTS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BetPreset {
+    /// Stake on each main bet placed, by bet name.
+    #[serde(default)]
+    pub main_bets: BTreeMap<String, Money>,
+    #[serde(default)]
+    pub bonus_bets: BTreeMap<String, Money>,
+    /// The one main bet of a layout saved before several could be placed
+    /// at once.
+    #[serde(default, skip_serializing)]
+    bet_type: Option<String>,
+    #[serde(default, skip_serializing)]
+    amount: Option<i32>,
+}
+
+impl BetPreset {
+    pub fn new(slip: &BetSlip) -> Self {
+        let bonus_bets = &slip.bonus_bets;
+        Self {
+            main_bets: slip
+                .main_bets()
+                .map(|(bet, amount)| (bet.to_string(), amount))
+                .collect(),
+            bonus_bets: BonusBetType::ALL
+                .iter()
+                .filter(|&&name| bonus_bets.get(name) > Money::ZERO)
+                .map(|&name| (name.to_string(), bonus_bets.get(name)))
+                .chain(
+                    bonus_bets
+                        .tie_score_bets()
+                        .map(|(score, stake)| (Wager::TieScore(score).to_string(), stake)),
+                )
+                .collect(),
+            bet_type: None,
+            amount: None,
+        }
+    }
+
+    /// The saved bets; names the game doesn't know are skipped.
+    pub fn slip(&self) -> BetSlip {
+        let mut slip = BetSlip::new();
+        for (name, &amount) in &self.main_bets {
+            if let Ok(bet) = name.parse::<BetType>() {
+                slip.set_main_bet(bet, amount);
+            }
+        }
+        if let (Some(Ok(bet)), Some(amount)) = (
+            self.bet_type.as_deref().map(str::parse::<BetType>),
+            self.amount,
+        ) {
+            slip.set_main_bet(bet, Money::from_dollars(amount));
+        }
+        for (name, &amount) in &self.bonus_bets {
+            if let Ok(bet) = name.parse() {
+                slip.bonus_bets.set(bet, amount);
+            } else if let Some(stake) = name
+                .strip_prefix("tie_on_")
+                .and_then(|score| score.parse::<usize>().ok())
+                .and_then(|score| slip.bonus_bets.tie_score_bets.get_mut(score))
+            {
+                *stake = amount;
+            }
+        }
+        slip
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    slots: BTreeMap<String, BetPreset>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PresetBook {
+    profile: String,
+    slots: [Option<BetPreset>; PRESET_SLOTS],
+}
+
+impl PresetBook {
+    fn path(profile: &str) -> PathBuf {
+        storage::profile_dir(profile).join("presets.toml")
+    }
+
+    pub fn empty(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            slots: Default::default(),
+        }
+    }
+
+    /// Loads the profile's presets; a missing file is an empty book.
+    pub fn load(profile: &str) -> Result<Self, String> {
+        let mut book = Self::empty(profile);
+        let path = Self::path(profile);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(book),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let file: PresetFile =
+            toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for (slot, preset) in file.slots {
+            if let Ok(n @ 1..=PRESET_SLOTS) = slot.parse::<usize>() {
+                book.slots[n - 1] = Some(preset);
+            }
+        }
+        Ok(book)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let file = PresetFile {
+            slots: self
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(i, preset)| preset.clone().map(|p| ((i + 1).to_string(), p)))
+                .collect(),
+        };
+        let contents = toml::to_string(&file).map_err(io::Error::other)?;
+        integrity::write_signed(&Self::path(&self.profile), &contents)
+    }
+
+    /// Slot numbers are 1-based, matching the keys.
+    pub fn get(&self, slot: usize) -> Option<&BetPreset> {
+        self.slots.get(slot.checked_sub(1)?)?.as_ref()
+    }
+
+    pub fn store(&mut self, slot: usize, preset: BetPreset) -> io::Result<()> {
+        if let Some(entry) = slot.checked_sub(1).and_then(|i| self.slots.get_mut(i)) {
+            *entry = Some(preset);
+        }
+        self.save()
+    }
+}
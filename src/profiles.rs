@@ -0,0 +1,486 @@
+//! Named profiles, so players sharing a machine (or experiments run side by
+//! side) keep separate bankrolls. Everything a profile owns lives under
+//! `profiles/<name>/`; `profile.toml` there holds what follows the player
+//! from one launch to the next: the baccarat bankroll, the achievements
+//! earned, and the preferred game mode and chip.
+
+use crate::baccarat::{BetType, BonusBetType, GameMode, Money, TABLE_MIN_BET};
+use crate::events::CalendarDate;
+use crate::history::HandRecord;
+use crate::integrity::{self, Integrity};
+use crate::lifetime::LifetimeStats;
+use crate::resume;
+use crate::settlement::{BetOutcome, Wager};
+use crate::storage::{self, DEFAULT_PROFILE};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+/// Longest profile name; names become directory names.
+const MAX_NAME_LEN: usize = 32;
+
+/// Checks a profile name: letters, digits, `-` and `_`, as the directory
+/// holding the profile is named after it.
+pub fn parse_name(name: &str) -> Result<String, String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "invalid profile name '{}', expected up to {} letters, digits, '-' or '_'",
+            name, MAX_NAME_LEN
+        ))
+    }
+}
+
+/// Milestones a profile can earn at the baccarat table, each once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstHand,
+    Regular,
+    Veteran,
+    NaturalWinner,
+    CalledTheTie,
+    PairHunter,
+    DragonSlayer,
+    PandaKeeper,
+    BigWin,
+    DoubleUp,
+}
+
+/// Net win on one hand that earns `BigWin`.
+const BIG_WIN: Money = Money::from_dollars(500);
+
+impl Achievement {
+    pub const ALL: [Achievement; 10] = [
+        Achievement::FirstHand,
+        Achievement::Regular,
+        Achievement::Veteran,
+        Achievement::NaturalWinner,
+        Achievement::CalledTheTie,
+        Achievement::PairHunter,
+        Achievement::DragonSlayer,
+        Achievement::PandaKeeper,
+        Achievement::BigWin,
+        Achievement::DoubleUp,
+    ];
+
+    /// The key it is saved under.
+    fn id(&self) -> &'static str {
+        match self {
+            Achievement::FirstHand => "first_hand",
+            Achievement::Regular => "regular",
+            Achievement::Veteran => "veteran",
+            Achievement::NaturalWinner => "natural_winner",
+            Achievement::CalledTheTie => "called_the_tie",
+            Achievement::PairHunter => "pair_hunter",
+            Achievement::DragonSlayer => "dragon_slayer",
+            Achievement::PandaKeeper => "panda_keeper",
+            Achievement::BigWin => "big_win",
+            Achievement::DoubleUp => "double_up",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstHand => "First Hand",
+            Achievement::Regular => "Regular",
+            Achievement::Veteran => "Veteran",
+            Achievement::NaturalWinner => "Natural Winner",
+            Achievement::CalledTheTie => "Called the Tie",
+            Achievement::PairHunter => "Pair Hunter",
+            Achievement::DragonSlayer => "Dragon Slayer",
+            Achievement::PandaKeeper => "Panda Keeper",
+            Achievement::BigWin => "Big Win",
+            Achievement::DoubleUp => "Double Up",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstHand => "bet on a hand of baccarat",
+            Achievement::Regular => "play 100 hands",
+            Achievement::Veteran => "play 1000 hands",
+            Achievement::NaturalWinner => "win a main bet on a natural",
+            Achievement::CalledTheTie => "win a tie bet",
+            Achievement::PairHunter => "win a pair side bet",
+            Achievement::DragonSlayer => "win a Dragon 7 bet",
+            Achievement::PandaKeeper => "win a Panda 8 bet",
+            Achievement::BigWin => "win $500 or more on one hand",
+            Achievement::DoubleUp => "double the bankroll a session started with",
+        }
+    }
+
+    /// Whether the hand just settled earns it. `hands` is the profile's
+    /// lifetime count with this hand, `balance` what the seat now holds and
+    /// `starting` what it sat down with.
+    fn earned(&self, hand: &HandRecord, hands: u32, balance: Money, starting: Money) -> bool {
+        let bets = || hand.seats.iter().flat_map(|seat| &seat.bets);
+        let won = |matches: &dyn Fn(Wager) -> bool| {
+            bets().any(|bet| bet.outcome == BetOutcome::Win && matches(bet.wager))
+        };
+        match self {
+            Achievement::FirstHand => bets().next().is_some(),
+            Achievement::Regular => hands >= 100,
+            Achievement::Veteran => hands >= 1000,
+            Achievement::NaturalWinner => {
+                hand.natural
+                    && won(&|wager| matches!(wager, Wager::Main(BetType::Player | BetType::Banker)))
+            }
+            Achievement::CalledTheTie => {
+                won(&|wager| matches!(wager, Wager::Main(BetType::Tie) | Wager::TieScore(_)))
+            }
+            Achievement::PairHunter => won(&|wager| {
+                matches!(
+                    wager,
+                    Wager::Side(
+                        BonusBetType::PlayerPair
+                            | BonusBetType::BankerPair
+                            | BonusBetType::EitherPair
+                            | BonusBetType::PerfectPair
+                    )
+                )
+            }),
+            Achievement::DragonSlayer => won(&|wager| wager == Wager::Main(BetType::Dragon7)),
+            Achievement::PandaKeeper => won(&|wager| wager == Wager::Main(BetType::Panda8)),
+            Achievement::BigWin => hand.seats.iter().any(|seat| {
+                seat.bets
+                    .iter()
+                    .map(|bet| bet.payout - bet.stake)
+                    .sum::<Money>()
+                    >= BIG_WIN
+            }),
+            Achievement::DoubleUp => starting > Money::ZERO && balance >= starting * 2,
+        }
+    }
+}
+
+/// What the player last chose at the table, to open with next time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Preferences {
+    /// The game mode's name, e.g. "EzBaccarat".
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// The chip selected, in dollars.
+    #[serde(default)]
+    pub chip: Option<i32>,
+}
+
+impl Preferences {
+    /// The preferred mode, if one is set and still exists.
+    pub fn mode(&self) -> Option<GameMode> {
+        self.mode.as_deref().and_then(|mode| mode.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProfileFile {
+    created_at: u64,
+    #[serde(default)]
+    last_played: u64,
+    /// The single seat's balance when the table last closed.
+    #[serde(default)]
+    bankroll: Option<Money>,
+    /// Achievement keys and when each was earned.
+    #[serde(default)]
+    achievements: BTreeMap<String, u64>,
+    #[serde(default)]
+    preferences: Preferences,
+}
+
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    file: ProfileFile,
+    /// There was no `profile.toml` to load.
+    pub is_new: bool,
+}
+
+impl Profile {
+    fn path(name: &str) -> PathBuf {
+        storage::profile_dir(name).join("profile.toml")
+    }
+
+    /// A profile with nothing saved yet; `save` creates it.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            file: ProfileFile {
+                created_at: resume::now(),
+                ..ProfileFile::default()
+            },
+            is_new: true,
+        }
+    }
+
+    /// Loads the profile, or a new one if it has no `profile.toml` yet
+    /// (including one made before profiles kept one). A file that fails its
+    /// check is refused, so an edited bankroll can't be played.
+    pub fn load(name: &str) -> Result<Self, String> {
+        let path = Self::path(name);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new(name)),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        match integrity::verify(&path) {
+            Integrity::Verified => {}
+            status => {
+                return Err(format!(
+                    "{}: failed its check ({})",
+                    path.display(),
+                    status.label()
+                ));
+            }
+        }
+        let file = toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self {
+            name: name.to_string(),
+            file,
+            is_new: false,
+        })
+    }
+
+    /// Loads the profile to play. One that fails its check can't be played
+    /// as it stands, since its bankroll and achievements can no longer be
+    /// trusted: the player is warned and asked whether to start it afresh.
+    /// Declining, or having no terminal to ask on, leaves the file alone.
+    pub fn load_or_ask(name: &str) -> Result<Self, String> {
+        let path = Self::path(name);
+        let status = integrity::verify(&path);
+        if status == Integrity::Verified {
+            return Self::load(name);
+        }
+        let refused = format!("{}: failed its check ({})", path.display(), status.label());
+        eprintln!(
+            "Warning: {} failed its check ({}). It was changed outside the game, or signed \
+             under another {}.",
+            path.display(),
+            status.label(),
+            integrity::PASSPHRASE_VAR
+        );
+        if !io::stdin().is_terminal() {
+            return Err(refused);
+        }
+        loop {
+            eprint!("[r]eset the profile, or [q]uit? ");
+            let mut answer = String::new();
+            if io::stdin().lock().read_line(&mut answer).unwrap_or(0) == 0 {
+                return Err(refused);
+            }
+            match answer.trim() {
+                "r" | "R" => return Ok(Self::new(name)),
+                "q" | "Q" => return Err(refused),
+                _ => {}
+            }
+        }
+    }
+
+    /// Writes the profile, stamping it as played now.
+    pub fn save(&mut self) -> io::Result<()> {
+        self.file.last_played = resume::now();
+        let contents = toml::to_string(&self.file).map_err(io::Error::other)?;
+        integrity::write_signed(&Self::path(&self.name), &contents)?;
+        self.is_new = false;
+        Ok(())
+    }
+
+    /// The balance to sit down with: the bankroll left last time, or
+    /// `default` for a new profile or one that went broke.
+    pub fn bankroll_or(&self, default: Money) -> Money {
+        self.file
+            .bankroll
+            .filter(|&bankroll| bankroll >= Money::from_dollars(TABLE_MIN_BET))
+            .unwrap_or(default)
+    }
+
+    pub fn preferences(&self) -> &Preferences {
+        &self.file.preferences
+    }
+
+    /// Remembers the seat's balance and choices for the next launch.
+    pub fn remember(&mut self, bankroll: Money, mode: GameMode, chip: i32) {
+        self.file.bankroll = Some(bankroll);
+        self.file.preferences = Preferences {
+            mode: Some(format!("{:?}", mode)),
+            chip: Some(chip),
+        };
+    }
+
+    /// Awards whatever the settled hand earns that the profile doesn't have
+    /// yet, and returns it.
+    pub fn record(
+        &mut self,
+        hand: &HandRecord,
+        hands: u32,
+        balance: Money,
+        starting: Money,
+    ) -> Vec<Achievement> {
+        let earned: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|achievement| !self.file.achievements.contains_key(achievement.id()))
+            .filter(|achievement| achievement.earned(hand, hands, balance, starting))
+            .collect();
+        for achievement in &earned {
+            self.file
+                .achievements
+                .insert(achievement.id().to_string(), hand.dealt_at);
+        }
+        earned
+    }
+
+    /// The achievements earned, with when, in the order `ALL` lists them.
+    pub fn achievements(&self) -> Vec<(Achievement, u64)> {
+        Achievement::ALL
+            .into_iter()
+            .filter_map(|achievement| {
+                Some((achievement, *self.file.achievements.get(achievement.id())?))
+            })
+            .collect()
+    }
+
+    /// "Achievement unlocked: Big Win (win $500 or more on one hand)" for
+    /// the status line.
+    pub fn announce(earned: &[Achievement]) -> Option<String> {
+        let names: Vec<String> = earned
+            .iter()
+            .map(|achievement| format!("{} ({})", achievement.title(), achievement.description()))
+            .collect();
+        match names.len() {
+            0 => None,
+            1 => Some(format!("Achievement unlocked: {}", names[0])),
+            _ => Some(format!("Achievements unlocked: {}", names.join(", "))),
+        }
+    }
+
+    /// "alice  $1230  412 hands  3/10 achievements  last played 2026-10-16"
+    fn summary(&self) -> String {
+        let hands = LifetimeStats::load(&self.name).map_or(0, |lifetime| lifetime.total().hands);
+        let bankroll = self
+            .file
+            .bankroll
+            .map_or("-".to_string(), |bankroll| format!("${}", bankroll));
+        let last_played = match self.file.last_played {
+            0 => "never played".to_string(),
+            at => format!("last played {}", CalendarDate::from_unix(at)),
+        };
+        format!(
+            "{:<16} {:>10}  {:>6} hands  {}/{} achievements  {}",
+            self.name,
+            bankroll,
+            hands,
+            self.file.achievements.len(),
+            Achievement::ALL.len(),
+            last_played
+        )
+    }
+
+    /// Every profile on disk, most recently played first.
+    pub fn all() -> Vec<Profile> {
+        let Ok(entries) = std::fs::read_dir(storage::data_dir().join("profiles")) else {
+            return Vec::new();
+        };
+        let mut profiles: Vec<Profile> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| parse_name(&entry.file_name().to_string_lossy()).ok())
+            .map(|name| Profile::load(&name).unwrap_or_else(|_| Profile::new(&name)))
+            .collect();
+        profiles.sort_by(|a, b| {
+            b.file
+                .last_played
+                .cmp(&a.file.last_played)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        profiles
+    }
+}
+
+/// Asks on the terminal which profile to play as: a number from the list,
+/// or a new name to create one. [ENTER] takes the most recently played.
+/// With no terminal, or no profiles yet, it is the default profile.
+pub fn choose() -> String {
+    let profiles = Profile::all();
+    if profiles.is_empty() || !io::stdin().is_terminal() {
+        return DEFAULT_PROFILE.to_string();
+    }
+    println!("Profiles:");
+    for (i, profile) in profiles.iter().enumerate() {
+        println!("  {}) {}", i + 1, profile.summary());
+    }
+    loop {
+        print!(
+            "Play as [1-{}, or a new name; ENTER for {}]: ",
+            profiles.len(),
+            profiles[0].name
+        );
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        if io::stdin().lock().read_line(&mut answer).unwrap_or(0) == 0 {
+            return profiles[0].name.clone();
+        }
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return profiles[0].name.clone();
+        }
+        if let Ok(n) = answer.parse::<usize>() {
+            match profiles.get(n.wrapping_sub(1)) {
+                Some(profile) => return profile.name.clone(),
+                None => {
+                    println!("No profile {}", n);
+                    continue;
+                }
+            }
+        }
+        match parse_name(answer) {
+            Ok(name) => {
+                if !profiles.iter().any(|profile| profile.name == name) {
+                    println!("Creating profile '{}'", name);
+                }
+                return name;
+            }
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+/// The `profiles` subcommand: every profile with its bankroll, hands and
+/// achievements.
+pub fn run() -> Result<(), String> {
+    let profiles = Profile::all();
+    if profiles.is_empty() {
+        println!("No profiles yet; `--profile NAME` creates one.");
+        return Ok(());
+    }
+    for profile in &profiles {
+        println!("{}", profile.summary());
+    }
+    Ok(())
+}
+
+/// Prints the achievements under the `stats` subcommand's totals.
+pub fn print_achievements(profile: &Profile) {
+    let earned = profile.achievements();
+    println!();
+    println!("Achievements ({}/{})", earned.len(), Achievement::ALL.len());
+    for achievement in Achievement::ALL {
+        match earned.iter().find(|(a, _)| *a == achievement) {
+            Some((_, at)) => println!(
+                "  [x] {:<15} {} ({})",
+                achievement.title(),
+                achievement.description(),
+                CalendarDate::from_unix(*at)
+            ),
+            None => println!(
+                "  [ ] {:<15} {}",
+                achievement.title(),
+                achievement.description()
+            ),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! What `serve` and `join` say to each other: one JSON message per line
+//! over TCP, so a table can be watched with `nc` or bridged to anything
+//! that speaks lines. A client can ask which tables the server runs, then
+//! joins one by name, to play or to watch. Players bet while the betting
+//! window is open; the server tells every client about the table, the
+//! window, and each hand as it is dealt. Joining, or asking, brings a
+//! snapshot of the table, so a client that drops out mid-shoe picks up
+//! where it left off; a player's seat is held for the resume token their
+//! welcome carried, so no one else can sit down under their name. When there are bets down, the
+//! biggest bettor squeezes their side's cards first, peeling them a step
+//! at a time for the whole table.
+
+use crate::baccarat::{Card, Money, Side};
+use crate::card_renderer::CardRenderer;
+use crate::scoreboard::Bead;
+use crate::shoe_import::parse_card;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+/// The protocol this build speaks. A client sends it with its join and the
+/// server answers with its own; one that leaves it out is taken to speak
+/// the first.
+pub const PROTOCOL_VERSION: u32 = 2;
+/// Port `serve` listens on and `join` dials unless told otherwise.
+pub const DEFAULT_PORT: u16 = 7878;
+/// Where `serve` listens and `join` dials by default: this machine only.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+/// Peels it takes to turn a squeezed card face up.
+pub const PEEL_STEPS: u8 = 3;
+
+/// An address as given on the command line, with the default port added
+/// when there isn't one: `192.168.1.20` dials `192.168.1.20:7878`, and a
+/// bare port is one on this machine.
+pub fn parse_address(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("empty address".to_string());
+    }
+    if let Ok(port) = value.parse::<u16>() {
+        return Ok(format!("127.0.0.1:{}", port));
+    }
+    let has_port = value
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    Ok(if has_port {
+        value.to_string()
+    } else {
+        format!("{}:{}", value, DEFAULT_PORT)
+    })
+}
+
+/// Whether a connection plays at its table or only watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    Player,
+    /// Sees the cards, the roads and the seats, but can't bet.
+    Spectator,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Asks for the tables the server runs; allowed before joining.
+    ListTables,
+    /// Sits down, or stands by to watch; the table is the server's first
+    /// if not named.
+    Join {
+        name: String,
+        #[serde(default)]
+        table: Option<String>,
+        #[serde(default)]
+        role: Role,
+        #[serde(default = "first_version")]
+        version: u32,
+        /// The token from the welcome of a player sitting back down.
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// Asks for a fresh snapshot of the table.
+    Sync,
+    /// Puts `amount` dollars on a main bet ("player", "banker", "tie"),
+    /// replacing any bet already down; 0 takes it down.
+    Bet { bet: String, amount: i32 },
+    /// Peels the squeezed card at `card` (0 or 1) back a step; only the
+    /// squeezer may.
+    Peel { card: usize },
+}
+
+/// A seat as everyone at the table sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub name: String,
+    pub balance: Money,
+    /// The bet down this round, if any.
+    pub bet: Option<String>,
+    pub stake: Money,
+}
+
+/// A table as the server lists it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSummary {
+    pub name: String,
+    pub mode: String,
+    pub decks: usize,
+    pub players: usize,
+    pub seats: usize,
+    pub spectators: usize,
+    /// Rounds dealt so far.
+    pub round: u32,
+    pub shoe: u32,
+}
+
+/// The table as `sync` gives it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The round being bet on or dealt, or the last one dealt.
+    pub round: u32,
+    pub shoe: u32,
+    pub cards_dealt: usize,
+    pub shoe_size: usize,
+    pub beads: Vec<Bead>,
+    pub balance: Money,
+    /// This client's bet down this round, if any.
+    pub bet: Option<String>,
+    pub stake: Money,
+    pub players: Vec<PlayerView>,
+    pub spectators: usize,
+}
+
+/// How a seat's bet came out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerResult {
+    pub name: String,
+    pub bet: String,
+    pub stake: Money,
+    pub net: Money,
+    pub balance: Money,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// The join was accepted. A spectator's balance is 0, and only a
+    /// player gets a token, to send with their join to sit back down.
+    Welcome {
+        version: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+        name: String,
+        table: String,
+        role: Role,
+        balance: Money,
+        mode: String,
+        decks: usize,
+        betting_secs: u64,
+        min_bet: i32,
+        max_bet: i32,
+    },
+    /// Who is seated, their balances and bets, and how many are watching;
+    /// sent whenever it changes.
+    Table {
+        players: Vec<PlayerView>,
+        #[serde(default)]
+        spectators: usize,
+    },
+    /// The answer to `list_tables`.
+    Tables { tables: Vec<TableSummary> },
+    /// Bets are taken until the window closes.
+    BettingOpen {
+        round: u32,
+        shoe: u32,
+        closes_in_ms: u64,
+        /// Cards dealt from the shoe so far, and its size.
+        #[serde(default)]
+        cards_dealt: usize,
+        #[serde(default)]
+        shoe_size: usize,
+    },
+    /// Betting closed and `squeezer`, the biggest bettor, has the first
+    /// `cards` cards of their side to peel; the other side's are `shown`
+    /// face up. The hand follows once they are all peeled, or when the
+    /// squeeze times out.
+    Squeeze {
+        round: u32,
+        squeezer: String,
+        side: Side,
+        cards: usize,
+        shown: Vec<String>,
+        closes_in_ms: u64,
+    },
+    /// A squeezed card peeled back to `step` of `PEEL_STEPS`: its suit
+    /// and what its pips give away, and the card itself once face up.
+    Peeled {
+        card: usize,
+        step: u8,
+        suit: String,
+        hint: String,
+        #[serde(default)]
+        label: Option<String>,
+    },
+    /// The round's hand, dealt and settled.
+    Hand {
+        round: u32,
+        shoe: u32,
+        player_cards: Vec<String>,
+        banker_cards: Vec<String>,
+        player_score: u8,
+        banker_score: u8,
+        /// 1 player, 2 banker, 3 tie.
+        winner: u8,
+        results: Vec<PlayerResult>,
+    },
+    /// Everything a client needs to pick up where the table is, sent on
+    /// joining and when asked: the shoe and how far into it the dealing
+    /// is, its hands so far for the roads, the seats, and this client's
+    /// balance and any bet of theirs still down.
+    Sync(Snapshot),
+    /// The shoe ran out and a fresh one was shuffled.
+    Shuffled { shoe: u32 },
+    /// The last message couldn't be acted on.
+    Rejected { reason: String },
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+/// Cards as they go over the wire: "10♥", "K♠".
+pub fn card_labels(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(CardRenderer::label).collect()
+}
+
+/// A squeezed card `step` peels in: the first shows how its pips run
+/// along the edge (one column for an ace to three, two for a four to ten,
+/// none for a picture), the second the other edge, and the last the card.
+pub fn peeled(index: usize, card: &Card, step: u8) -> ServerMessage {
+    let hint = match (step, card.rank) {
+        (0, _) => "",
+        (1, 1..=3) => "A to 3",
+        (1, 4..=10) => "4 to 10",
+        (_, 11..=13) => "picture",
+        (2, 1) => "A",
+        (2, 2..=3) => "2 or 3",
+        (2, 4..=5) => "4 or 5",
+        (2, 6..=8) => "6 to 8",
+        (2, _) => "9 or 10",
+        _ => "",
+    };
+    ServerMessage::Peeled {
+        card: index,
+        step,
+        suit: if step > 0 {
+            CardRenderer::suit_symbol(card.suit).to_string()
+        } else {
+            String::new()
+        },
+        hint: hint.to_string(),
+        label: (step >= PEEL_STEPS).then(|| CardRenderer::label(card)),
+    }
+}
+
+pub fn parse_cards(labels: &[String]) -> Result<Vec<Card>, String> {
+    labels.iter().map(|label| parse_card(label)).collect()
+}
+
+/// Writes `message` as one line.
+pub fn send<T: Serialize>(out: &mut impl Write, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message).map_err(io::Error::other)?;
+    line.push(b'\n');
+    out.write_all(&line)?;
+    out.flush()
+}
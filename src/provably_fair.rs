@@ -0,0 +1,149 @@
+//! Provably fair shoes. Before a shoe is shuffled the house picks a secret
+//! server seed and publishes its SHA-256 hash; the player brings a client
+//! seed of their own. The shuffle is drawn from HMAC-SHA256 keyed with the
+//! server seed over the client seed, so neither side alone decides the
+//! cards. Once the shoe is done the server seed is revealed: hashing it
+//! must give the published commitment, and `shoe_order` deals the same
+//! shoe again from the two seeds.
+
+use crate::baccarat::{Card, ProcedureProfile, Shoe, ShuffleRng};
+use hmac::{Hmac, Mac};
+use rand::{Rng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// Bytes of randomness in a server seed.
+const SERVER_SEED_BYTES: usize = 32;
+/// Bytes of randomness in a client seed picked for the player.
+const CLIENT_SEED_BYTES: usize = 8;
+
+/// `bytes` as lowercase hex, two digits a byte.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The bytes `hex` wrote, or `None` if `text` isn't an even run of hex
+/// digits.
+pub fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// The house's secret for one shoe: 32 random bytes, written as hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSeed(String);
+
+impl ServerSeed {
+    pub fn generate() -> Self {
+        Self(hex(&rand::rng().random::<[u8; SERVER_SEED_BYTES]>()))
+    }
+
+    /// A revealed seed as written, checked to be 64 hex digits.
+    pub fn parse(seed: &str) -> Result<Self, String> {
+        let seed = seed.trim().to_ascii_lowercase();
+        if parse_hex(&seed).is_none_or(|bytes| bytes.len() != SERVER_SEED_BYTES) {
+            return Err(format!(
+                "invalid server seed '{}', expected {} hex digits",
+                seed,
+                2 * SERVER_SEED_BYTES
+            ));
+        }
+        Ok(Self(seed))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The SHA-256 hash published before the shoe is dealt, as hex.
+    pub fn commitment(&self) -> String {
+        hex(&Sha256::digest(self.0.as_bytes()))
+    }
+}
+
+/// A client seed for a player who hasn't picked one.
+pub fn random_client_seed() -> String {
+    hex(&rand::rng().random::<[u8; CLIENT_SEED_BYTES]>())
+}
+
+/// The generator a provably fair shoe shuffles from: block `n` of its
+/// output is HMAC-SHA256(server seed, "<client seed>:<n>"), counting from
+/// 0, so the same two seeds always give the same stream.
+pub struct FairRng {
+    mac: Hmac<Sha256>,
+    client_seed: String,
+    counter: u64,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl FairRng {
+    pub fn new(server_seed: &ServerSeed, client_seed: &str) -> Self {
+        Self {
+            mac: Hmac::<Sha256>::new_from_slice(server_seed.as_str().as_bytes())
+                .expect("HMAC accepts keys of any length"),
+            client_seed: client_seed.to_string(),
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+
+    fn refill(&mut self) {
+        let mut mac = self.mac.clone();
+        mac.update(format!("{}:{}", self.client_seed, self.counter).as_bytes());
+        self.block = mac.finalize().into_bytes().into();
+        self.counter += 1;
+        self.used = 0;
+    }
+}
+
+impl RngCore for FairRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for byte in dst {
+            if self.used == self.block.len() {
+                self.refill();
+            }
+            *byte = self.block[self.used];
+            self.used += 1;
+        }
+    }
+}
+
+/// The generator for a shoe dealt from the two seeds.
+pub fn shuffle_rng(server_seed: &ServerSeed, client_seed: &str) -> ShuffleRng {
+    Box::new(FairRng::new(server_seed, client_seed))
+}
+
+/// Every card of the shoe the two seeds shuffle, in the order it comes out
+/// of the shoe, burn cards first. A continuous shuffler's shoe starts in
+/// this order; the cards put back after each hand move it on from there.
+pub fn shoe_order(
+    server_seed: &ServerSeed,
+    client_seed: &str,
+    decks: usize,
+    top_rank: u8,
+) -> Vec<Card> {
+    let shoe = Shoe::with_rng(
+        ProcedureProfile::standard(decks),
+        top_rank,
+        shuffle_rng(server_seed, client_seed),
+    );
+    shoe.cards_to_come().copied().collect()
+}
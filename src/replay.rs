@@ -0,0 +1,283 @@
+//! Session replays: every baccarat hand of a session (the cards as dealt,
+//! every seat's bets, what each paid and the balance it left) with the seed
+//! the shoes were shuffled from and the rules they were paid under. Written
+//! to `profiles/<name>/replays/<time>.toml` when the table closes; `replay
+//! FILE.toml` plays one back, re-settling every bet through the engine so a
+//! disputed payout can be checked.
+
+use crate::baccarat::{BaccaratGame, BetSlip, Card, GameMode, Money, OverUnderRule, TieRule};
+use crate::card_renderer::CardRenderer;
+use crate::history::HandRecord;
+use crate::integrity::{self, Integrity};
+use crate::resume::{self, parse_wager};
+use crate::rules::RulesBundle;
+use crate::shoe_import::parse_card;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Bumped when a field changes meaning, so an old file is refused rather
+/// than misread.
+const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayBet {
+    /// The bet's name, as `Wager` prints it.
+    pub bet: String,
+    pub stake: Money,
+    /// Amount returned, stake included.
+    pub payout: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySeat {
+    pub name: String,
+    /// Balance once the hand was paid.
+    pub balance: Money,
+    #[serde(default)]
+    pub bets: Vec<ReplayBet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayHand {
+    pub number: u32,
+    pub shoe: u32,
+    pub dealt_at: u64,
+    /// The game mode's name, e.g. "EzBaccarat".
+    pub mode: String,
+    /// What the shoe was shuffled from, as the hand history records it.
+    #[serde(default)]
+    pub shuffle: String,
+    /// Cards in the order each side was dealt them, as the table shows
+    /// them ("K♠").
+    pub player: Vec<String>,
+    pub banker: Vec<String>,
+    pub player_score: u8,
+    pub banker_score: u8,
+    /// 1 for player, 2 for banker, 3 for a tie.
+    pub winner: u8,
+    pub seats: Vec<ReplaySeat>,
+}
+
+impl ReplayHand {
+    /// The hand `record` describes, with each seat's balance after it.
+    pub fn new(record: &HandRecord, balances: &[(String, Money)]) -> Self {
+        let labels = |cards: &[Card]| cards.iter().map(CardRenderer::label).collect();
+        Self {
+            number: record.number,
+            shoe: record.shoe,
+            dealt_at: record.dealt_at,
+            mode: format!("{:?}", record.mode),
+            shuffle: record.shuffle.clone(),
+            player: labels(&record.player_cards),
+            banker: labels(&record.banker_cards),
+            player_score: record.player_score,
+            banker_score: record.banker_score,
+            winner: record.winner,
+            seats: balances
+                .iter()
+                .map(|(name, balance)| ReplaySeat {
+                    name: name.clone(),
+                    balance: *balance,
+                    bets: record
+                        .seats
+                        .iter()
+                        .filter(|seat| &seat.seat == name)
+                        .flat_map(|seat| &seat.bets)
+                        .map(|bet| ReplayBet {
+                            bet: bet.wager.to_string(),
+                            stake: bet.stake,
+                            payout: bet.payout,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn cards(&self) -> Result<(Vec<Card>, Vec<Card>), String> {
+        let parse = |cards: &[String]| {
+            cards
+                .iter()
+                .map(|card| parse_card(card))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok((parse(&self.player)?, parse(&self.banker)?))
+    }
+}
+
+/// The house rules the hands were paid under.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayRules {
+    /// The rules bundle's name.
+    pub name: String,
+    /// The dealing procedure's name.
+    pub procedure: String,
+    /// Pay table entries that differ from the mode's standard table, the
+    /// bundle's and any seasonal event's.
+    #[serde(default)]
+    pub pay_table: BTreeMap<String, i32>,
+    /// Over/under line, as the whole part of the half point.
+    pub over_under_line: u8,
+    pub over_return_pct: i32,
+    pub under_return_pct: i32,
+    /// "push", "lose" or "barge".
+    pub tie_rule: String,
+}
+
+impl ReplayRules {
+    pub fn new(rules: &RulesBundle, event_boosts: Option<&HashMap<String, i32>>) -> Self {
+        Self {
+            name: rules.name.clone(),
+            procedure: rules.procedure.name.clone(),
+            pay_table: rules
+                .pay_table
+                .iter()
+                .chain(event_boosts.into_iter().flatten())
+                .map(|(name, ratio)| (name.clone(), *ratio))
+                .collect(),
+            over_under_line: rules.over_under.line,
+            over_return_pct: rules.over_under.over_return_pct,
+            under_return_pct: rules.over_under.under_return_pct,
+            tie_rule: rules.tie_rule.name().to_string(),
+        }
+    }
+
+    /// A table in `mode` that pays as these rules did.
+    fn table(&self, mode: GameMode) -> Result<BaccaratGame, String> {
+        let mut game = BaccaratGame::with_mode(mode);
+        for (name, ratio) in &self.pay_table {
+            game.payout_table.set(name, *ratio)?;
+        }
+        game.over_under = OverUnderRule {
+            line: self.over_under_line,
+            over_return_pct: self.over_return_pct,
+            under_return_pct: self.under_return_pct,
+        };
+        game.tie_rule = self.tie_rule.parse::<TieRule>()?;
+        Ok(game)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub profile: String,
+    /// Unix timestamp of the save.
+    pub recorded_at: u64,
+    /// The seed every shoe was shuffled from; `None` for a random shuffle.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub rules: ReplayRules,
+    pub hands: Vec<ReplayHand>,
+}
+
+/// How a recorded payout compares with what the engine pays on the same
+/// cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recheck {
+    Matches,
+    /// The engine pays this instead.
+    Differs(Money),
+    /// The bet, or the hand's cards, could not be read.
+    Unchecked,
+}
+
+impl Replay {
+    pub fn new(
+        profile: &str,
+        seed: Option<u64>,
+        rules: ReplayRules,
+        hands: Vec<ReplayHand>,
+    ) -> Self {
+        Self {
+            version: VERSION,
+            profile: profile.to_string(),
+            recorded_at: resume::now(),
+            seed,
+            rules,
+            hands,
+        }
+    }
+
+    /// Writes the replay under the profile's `replays` directory, named for
+    /// the time of the save, and returns where.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = storage::profile_dir(&self.profile)
+            .join("replays")
+            .join(format!("session-{}.toml", self.recorded_at));
+        let contents = toml::to_string(self).map_err(io::Error::other)?;
+        integrity::write_signed(&path, &contents)?;
+        Ok(path)
+    }
+
+    /// Reads a replay and whether it passes its check; one edited since it
+    /// was recorded still loads, so it can be looked at, but is flagged.
+    pub fn load(path: &Path) -> Result<(Self, Integrity), String> {
+        let source =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let replay: Self =
+            toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e.message()))?;
+        if replay.version != VERSION {
+            return Err(format!(
+                "{}: replay version {} is not supported, expected {}",
+                path.display(),
+                replay.version,
+                VERSION
+            ));
+        }
+        if replay.hands.is_empty() {
+            return Err(format!("{}: no hands to replay", path.display()));
+        }
+        Ok((replay, integrity::verify(path)))
+    }
+
+    /// Settles every recorded bet again on the hand's cards, seat by seat
+    /// and bet by bet in the order they were recorded.
+    pub fn recheck(&self, hand: &ReplayHand) -> Vec<Vec<Recheck>> {
+        let unchecked = || {
+            hand.seats
+                .iter()
+                .map(|seat| vec![Recheck::Unchecked; seat.bets.len()])
+                .collect()
+        };
+        let Ok(mode) = hand.mode.parse::<GameMode>() else {
+            return unchecked();
+        };
+        let (Ok(mut game), Ok((player, banker))) = (self.rules.table(mode), hand.cards()) else {
+            return unchecked();
+        };
+        game.set_hands(&player, &banker);
+        if game.state.winner != hand.winner {
+            return unchecked();
+        }
+        hand.seats
+            .iter()
+            .map(|seat| {
+                seat.bets
+                    .iter()
+                    .map(|bet| match slip_for(&bet.bet, bet.stake) {
+                        Some(slip) => {
+                            let paid = game.settle(&slip).total_payout();
+                            if paid == bet.payout {
+                                Recheck::Matches
+                            } else {
+                                Recheck::Differs(paid)
+                            }
+                        }
+                        None => Recheck::Unchecked,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A slip holding just the named bet, if it is one baccarat deals.
+fn slip_for(bet: &str, stake: Money) -> Option<BetSlip> {
+    let mut slip = BetSlip::new();
+    slip.set_stake(parse_wager(bet)?, stake)?;
+    Some(slip)
+}
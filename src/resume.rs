@@ -0,0 +1,258 @@
+//! Save and resume: the baccarat table as the player left it (each seat's
+//! bankroll, bets and totals, the shoe card by card, the roads and trends,
+//! the game mode), written to `profiles/<name>/table.toml` on quit so the
+//! next launch can carry on from it. The cards still to come are sealed, so
+//! reading the file doesn't tell the player what is next.
+
+use crate::baccarat::{BetPerformance, Card, GameSession, Money, Shoe};
+use crate::card_renderer::CardRenderer;
+use crate::history::Trends;
+use crate::integrity::{self, Integrity};
+use crate::presets::BetPreset;
+use crate::scoreboard::Bead;
+use crate::settlement::Wager;
+use crate::shoe_import::parse_card;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A shoe part way through, cards written as the table shows them ("K♠").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedShoe {
+    /// Cards still to deal, next first, sealed (see `integrity::seal`).
+    #[serde(default)]
+    pub sealed: String,
+    /// Cards still to deal in the clear, as saves before sealing wrote them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to_come: Vec<String>,
+    /// Cards dealt since the shuffle.
+    pub dealt: usize,
+    /// Cards burned since the shuffle; absent if the burn has still to run.
+    #[serde(default)]
+    pub burned: Option<Vec<String>>,
+}
+
+impl SavedShoe {
+    pub fn new(shoe: &Shoe) -> Self {
+        let to_come: Vec<String> = shoe.cards_to_come().map(CardRenderer::label).collect();
+        Self {
+            sealed: integrity::seal(&to_come.join(" ")),
+            to_come: Vec::new(),
+            dealt: shoe.cards_dealt(),
+            burned: shoe
+                .burned()
+                .map(|cards| cards.iter().map(CardRenderer::label).collect()),
+        }
+    }
+
+    /// The cards to come and the cards burned, as `Shoe::resume` takes them.
+    pub fn cards(&self) -> Result<(Vec<Card>, Option<Vec<Card>>), String> {
+        let parse = |cards: &[String]| {
+            cards
+                .iter()
+                .map(|card| parse_card(card))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let to_come = match self.sealed.as_str() {
+            "" => self.to_come.clone(),
+            sealed => integrity::unseal(sealed)?
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+        };
+        Ok((
+            parse(&to_come)?,
+            self.burned.as_deref().map(parse).transpose()?,
+        ))
+    }
+}
+
+/// One kind of bet's totals, keyed by its name as `Wager` prints it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedBet {
+    pub name: String,
+    pub bets: u32,
+    pub wagered: Money,
+    pub won: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSeat {
+    pub name: String,
+    pub balance: Money,
+    pub selected_chip: i32,
+    /// The bets laid out for the next hand.
+    pub layout: BetPreset,
+    pub rounds_played: u32,
+    pub total_wagered: Money,
+    pub total_won: Money,
+    #[serde(default)]
+    pub bets: Vec<SavedBet>,
+    /// Side bet wins, keyed by bet name.
+    #[serde(default)]
+    pub bonus_hits: BTreeMap<String, u32>,
+}
+
+impl SavedSeat {
+    pub fn new(name: &str, session: &GameSession, selected_chip: i32) -> Self {
+        let stats = &session.round_stats;
+        Self {
+            name: name.to_string(),
+            balance: session.balance,
+            selected_chip,
+            layout: BetPreset::new(&session.slip),
+            rounds_played: session.rounds_played,
+            total_wagered: session.total_wagered,
+            total_won: session.total_won,
+            bets: stats
+                .by_bet
+                .iter()
+                .map(|(wager, performance)| SavedBet {
+                    name: wager.to_string(),
+                    bets: performance.bets,
+                    wagered: performance.wagered,
+                    won: performance.won,
+                })
+                .collect(),
+            bonus_hits: stats
+                .bonus_hits
+                .iter()
+                .map(|(bet, &hits)| (bet.to_string(), hits))
+                .collect(),
+        }
+    }
+
+    /// The seat's session as it was saved; bets the game no longer knows
+    /// are dropped.
+    pub fn session(&self) -> GameSession {
+        let mut session = GameSession::new(self.balance);
+        session.slip = self.layout.slip();
+        if let Some((bet, _)) = session.slip.main_bets().next() {
+            session.selected_bet = bet;
+        }
+        session.rounds_played = self.rounds_played;
+        session.total_wagered = self.total_wagered;
+        session.total_won = self.total_won;
+        let stats = &mut session.round_stats;
+        stats.hands_played = self.rounds_played;
+        stats.amount_wagered = self.total_wagered;
+        stats.amount_won = self.total_won;
+        stats.by_bet = self
+            .bets
+            .iter()
+            .filter_map(|bet| {
+                let performance = BetPerformance {
+                    bets: bet.bets,
+                    wagered: bet.wagered,
+                    won: bet.won,
+                };
+                Some((parse_wager(&bet.name)?, performance))
+            })
+            .collect();
+        stats.bonus_hits = self
+            .bonus_hits
+            .iter()
+            .filter_map(|(name, &hits)| Some((name.parse().ok()?, hits)))
+            .collect();
+        session
+    }
+}
+
+/// A baccarat bet from its printed name: a main bet, a side bet, or
+/// `tie_on_N`.
+pub fn parse_wager(name: &str) -> Option<Wager> {
+    if let Ok(bet) = name.parse() {
+        return Some(Wager::Main(bet));
+    }
+    if let Ok(bet) = name.parse() {
+        return Some(Wager::Side(bet));
+    }
+    name.strip_prefix("tie_on_")?
+        .parse()
+        .ok()
+        .map(Wager::TieScore)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// Unix timestamp of the save.
+    pub saved_at: u64,
+    /// The game mode's name, e.g. "EzBaccarat".
+    pub mode: String,
+    /// The dealing procedure the shoe came from; the shoe is only picked up
+    /// again under the same one.
+    pub procedure: String,
+    pub shoe_number: u32,
+    pub rounds_played: u32,
+    pub active_seat: usize,
+    /// `None` when the table was dealing from a single deck or a recorded
+    /// shoe, which start afresh.
+    #[serde(default)]
+    pub shoe: Option<SavedShoe>,
+    /// The shoe's hands so far, which rebuild the roads.
+    #[serde(default)]
+    pub beads: Vec<Bead>,
+    pub trends: Trends,
+    pub seats: Vec<SavedSeat>,
+}
+
+impl SavedSession {
+    fn path(profile: &str) -> PathBuf {
+        storage::profile_dir(profile).join("table.toml")
+    }
+
+    /// The profile's saved table, or `None` if there isn't one. A file that
+    /// fails its check is refused rather than played on.
+    pub fn load(profile: &str) -> Result<Option<Self>, String> {
+        let path = Self::path(profile);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        match integrity::verify(&path) {
+            Integrity::Verified => {}
+            status => {
+                return Err(format!(
+                    "{}: failed its check ({})",
+                    path.display(),
+                    status.label()
+                ));
+            }
+        }
+        toml::from_str(&source)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Writes the table out in place of any earlier save.
+    pub fn save(&self, profile: &str) -> io::Result<()> {
+        let contents = toml::to_string(self).map_err(io::Error::other)?;
+        integrity::write_signed(&Self::path(profile), &contents)
+    }
+
+    /// "EzBaccarat, 42 hands, Alice $1230 and Bob $870" for the startup
+    /// prompt.
+    pub fn summary(&self) -> String {
+        let seats: Vec<String> = self
+            .seats
+            .iter()
+            .map(|seat| format!("{} ${}", seat.name, seat.balance))
+            .collect();
+        format!(
+            "{}, {} hands, {}",
+            self.mode,
+            self.rounds_played,
+            seats.join(" and ")
+        )
+    }
+}
@@ -0,0 +1,119 @@
+//! Scoreboard roads for the current shoe. Roads accumulate hand by hand and
+//! start over whenever the shoe is reshuffled.
+
+use crate::baccarat::GameEvent;
+use crate::scoreboard::{self, Bead, BeadPlate, Roadmap};
+
+const ROAD_ROWS: usize = scoreboard::ROWS;
+
+pub fn winner_letter(winner: u8) -> char {
+    match winner {
+        1 => 'P',
+        2 => 'B',
+        _ => 'T',
+    }
+}
+
+/// Bead plate: one cell per hand, filled top to bottom then left to right.
+pub fn bead_plate(winners: &[u8]) -> Vec<String> {
+    let mut rows = vec![String::new(); ROAD_ROWS];
+    for (i, &winner) in winners.iter().enumerate() {
+        rows[i % ROAD_ROWS].push(winner_letter(winner));
+        rows[i % ROAD_ROWS].push(' ');
+    }
+    rows
+}
+
+/// Big road: a new column for each change between Player and Banker, streaks
+/// run down and turn right along the bottom ("dragon tail"). Ties are counted
+/// on the preceding cell and shown as a lowercase letter.
+pub fn big_road(winners: &[u8]) -> Vec<String> {
+    let road = Roadmap::from_winners(winners);
+    (0..ROAD_ROWS)
+        .map(|row| {
+            (0..road.width())
+                .map(|column| match road.cell(column, row) {
+                    Some(cell) if cell.ties == 0 => format!("{} ", winner_letter(cell.winner)),
+                    Some(cell) => format!("{} ", winner_letter(cell.winner).to_ascii_lowercase()),
+                    None => ". ".to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Keeps only the most recent `columns` columns of a road so it fits on
+/// screen.
+pub fn last_columns(road: Vec<String>, columns: usize) -> Vec<String> {
+    road.into_iter()
+        .map(|row| {
+            let skip = row.len().saturating_sub(columns * 2);
+            row[skip..].to_string()
+        })
+        .collect()
+}
+
+/// Outcomes of every hand dealt from the current shoe.
+#[derive(Debug, Clone)]
+pub struct ShoeRoads {
+    shoe_number: u32,
+    winners: Vec<u8>,
+    beads: BeadPlate,
+}
+
+impl ShoeRoads {
+    pub fn new() -> Self {
+        Self {
+            shoe_number: 1,
+            winners: Vec::new(),
+            beads: BeadPlate::new(),
+        }
+    }
+
+    /// Shoe `shoe_number` with `beads` already dealt from it, e.g. from a
+    /// saved session.
+    pub fn resume(shoe_number: u32, beads: Vec<Bead>) -> Self {
+        Self {
+            shoe_number,
+            winners: beads.iter().map(|bead| bead.winner).collect(),
+            beads: BeadPlate::from_beads(beads),
+        }
+    }
+
+    pub fn record(&mut self, winner: u8) {
+        self.winners.push(winner);
+    }
+
+    /// Clears the roads for a freshly shuffled shoe.
+    pub fn new_shoe(&mut self) {
+        self.shoe_number += 1;
+        self.winners.clear();
+        self.beads.clear();
+    }
+
+    /// Feeds the bead plate, which reads pairs off the cards as they are
+    /// dealt.
+    pub fn observe(&mut self, event: &GameEvent) {
+        self.beads.observe(event);
+    }
+
+    pub fn shoe_number(&self) -> u32 {
+        self.shoe_number
+    }
+
+    pub fn winners(&self) -> &[u8] {
+        &self.winners
+    }
+
+    pub fn big_road(&self) -> Vec<String> {
+        big_road(&self.winners)
+    }
+
+    pub fn roadmap(&self) -> Roadmap {
+        Roadmap::from_winners(&self.winners)
+    }
+
+    pub fn bead_plate(&self) -> &BeadPlate {
+        &self.beads
+    }
+}
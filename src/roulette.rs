@@ -0,0 +1,453 @@
+//! Roulette on a single-zero European wheel or a double-zero American one.
+//! Inside bets cover one to six numbers on the layout (straight, split,
+//! street, corner, six line); outside bets cover red or black, odd or even,
+//! 1-18 or 19-36, a dozen, or a column, and lose to the zeros. A spin
+//! settles into the same `SettlementReport` as a baccarat hand.
+
+use crate::baccarat::{Money, ShuffleRng, TableLimits};
+use crate::settlement::{SettledBet, SettlementReport, Wager};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// The American wheel's 00 pocket.
+pub const DOUBLE_ZERO: u8 = 37;
+
+const RED: [u8; 18] = [
+    1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36,
+];
+
+/// Pockets clockwise from 0.
+const EUROPEAN_ORDER: [u8; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33, 1, 20,
+    14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
+];
+const AMERICAN_ORDER: [u8; 38] = [
+    0,
+    28,
+    9,
+    26,
+    30,
+    11,
+    7,
+    20,
+    32,
+    17,
+    5,
+    22,
+    34,
+    15,
+    3,
+    24,
+    36,
+    13,
+    1,
+    DOUBLE_ZERO,
+    27,
+    10,
+    25,
+    29,
+    12,
+    8,
+    19,
+    31,
+    18,
+    6,
+    21,
+    33,
+    16,
+    4,
+    23,
+    35,
+    14,
+    2,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wheel {
+    /// 37 pockets, one zero.
+    European,
+    /// 38 pockets, 0 and 00.
+    American,
+}
+
+impl Wheel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Wheel::European => "European",
+            Wheel::American => "American",
+        }
+    }
+
+    /// Pockets in the order they sit around the wheel, from 0.
+    pub fn order(&self) -> &'static [u8] {
+        match self {
+            Wheel::European => &EUROPEAN_ORDER,
+            Wheel::American => &AMERICAN_ORDER,
+        }
+    }
+
+    /// The house's share of every bet: the zeros' pockets out of all of them.
+    pub fn house_edge(&self) -> f64 {
+        let pockets = self.order().len() as f64;
+        (pockets - 36.0) / pockets
+    }
+
+    /// The pocket the ball lands in, drawn from `rng`.
+    pub fn spin<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        let order = self.order();
+        order[rng.random_range(0..order.len())]
+    }
+}
+
+impl std::str::FromStr for Wheel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "european" | "single" | "0" => Ok(Wheel::European),
+            "american" | "double" | "00" => Ok(Wheel::American),
+            _ => Err(format!(
+                "unknown wheel '{}', expected european or american",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PocketColor {
+    Red,
+    Black,
+    Green,
+}
+
+pub fn color(pocket: u8) -> PocketColor {
+    if pocket == 0 || pocket == DOUBLE_ZERO {
+        PocketColor::Green
+    } else if RED.contains(&pocket) {
+        PocketColor::Red
+    } else {
+        PocketColor::Black
+    }
+}
+
+/// "17", "0" or "00".
+pub fn pocket_name(pocket: u8) -> String {
+    if pocket == DOUBLE_ZERO {
+        "00".to_string()
+    } else {
+        pocket.to_string()
+    }
+}
+
+/// A bet on the layout. Groups of numbers are named by their lowest.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Bet {
+    Straight(u8),
+    /// Two numbers side by side, the lower first; 0 and 00 split with the
+    /// numbers next to them.
+    Split(u8, u8),
+    /// A row of three.
+    Street(u8),
+    /// Four numbers meeting at a corner.
+    Corner(u8),
+    /// Two rows, six numbers.
+    SixLine(u8),
+    Red,
+    Black,
+    Odd,
+    Even,
+    /// 1-18.
+    Low,
+    /// 19-36.
+    High,
+    /// 1-12, 13-24 or 25-36.
+    Dozen(u8),
+    /// A column of the layout: 1 holds 1, 4, 7 ... 34.
+    Column(u8),
+}
+
+impl Bet {
+    /// Pockets the bet wins on.
+    pub fn numbers(&self) -> Vec<u8> {
+        match *self {
+            Bet::Straight(number) => vec![number],
+            Bet::Split(first, second) => vec![first, second],
+            Bet::Street(first) => (first..first + 3).collect(),
+            Bet::Corner(first) => vec![first, first + 1, first + 3, first + 4],
+            Bet::SixLine(first) => (first..first + 6).collect(),
+            Bet::Red => RED.to_vec(),
+            Bet::Black => (1..=36).filter(|number| !RED.contains(number)).collect(),
+            Bet::Odd => (1..=36).filter(|number| number % 2 == 1).collect(),
+            Bet::Even => (1..=36).filter(|number| number % 2 == 0).collect(),
+            Bet::Low => (1..=18).collect(),
+            Bet::High => (19..=36).collect(),
+            Bet::Dozen(dozen) => (dozen * 12 - 11..=dozen * 12).collect(),
+            Bet::Column(column) => (column..=36).step_by(3).collect(),
+        }
+    }
+
+    pub fn covers(&self, pocket: u8) -> bool {
+        self.numbers().contains(&pocket)
+    }
+
+    /// Winnings per unit staked: 35 to 1 on a single number down to 1 to 1
+    /// on the even-money bets.
+    pub fn pays(&self) -> i64 {
+        36 / self.numbers().len() as i64 - 1
+    }
+
+    /// Whether the bet is on `wheel`'s layout.
+    pub fn is_on(&self, wheel: Wheel) -> bool {
+        let number = |n: u8| (1..=36).contains(&n);
+        let american = wheel == Wheel::American;
+        match *self {
+            Bet::Straight(n) => n <= 36 || (n == DOUBLE_ZERO && american),
+            Bet::Split(0, n) => {
+                if american {
+                    matches!(n, 1 | 2 | DOUBLE_ZERO)
+                } else {
+                    matches!(n, 1..=3)
+                }
+            }
+            Bet::Split(n, DOUBLE_ZERO) => american && matches!(n, 2 | 3),
+            Bet::Split(first, second) => {
+                number(first)
+                    && (second == first + 3 && second <= 36
+                        || second == first + 1 && first % 3 != 0)
+            }
+            Bet::Street(first) => number(first) && first % 3 == 1,
+            Bet::Corner(first) => number(first) && first <= 32 && first % 3 != 0,
+            Bet::SixLine(first) => number(first) && first <= 31 && first % 3 == 1,
+            Bet::Dozen(n) | Bet::Column(n) => (1..=3).contains(&n),
+            Bet::Red | Bet::Black | Bet::Odd | Bet::Even | Bet::Low | Bet::High => true,
+        }
+    }
+}
+
+impl std::fmt::Display for Bet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Bet::Straight(number) => f.write_str(&pocket_name(number)),
+            Bet::Split(first, second) => {
+                write!(f, "{}/{}", pocket_name(first), pocket_name(second))
+            }
+            Bet::Street(first) => write!(f, "{}-{}", first, first + 2),
+            Bet::Corner(first) => write!(f, "{}/{}/{}/{}", first, first + 1, first + 3, first + 4),
+            Bet::SixLine(first) => write!(f, "{}-{}", first, first + 5),
+            Bet::Red => f.write_str("red"),
+            Bet::Black => f.write_str("black"),
+            Bet::Odd => f.write_str("odd"),
+            Bet::Even => f.write_str("even"),
+            Bet::Low => f.write_str("1-18"),
+            Bet::High => f.write_str("19-36"),
+            Bet::Dozen(dozen) => write!(f, "dozen {}", dozen),
+            Bet::Column(column) => write!(f, "column {}", column),
+        }
+    }
+}
+
+/// Parses what `Display` writes: "17", "00", "17/20", "13-15", "13-18",
+/// "13/14/16/17", "red", "1-18", "dozen 2", "column 3" and so on.
+impl std::str::FromStr for Bet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_lowercase();
+        let unknown = || format!("'{}' is not a roulette bet", s.trim());
+        let outside = match text.as_str() {
+            "red" => Some(Bet::Red),
+            "black" => Some(Bet::Black),
+            "odd" => Some(Bet::Odd),
+            "even" => Some(Bet::Even),
+            "1-18" | "low" => Some(Bet::Low),
+            "19-36" | "high" => Some(Bet::High),
+            _ => None,
+        };
+        if let Some(bet) = outside {
+            return Ok(bet);
+        }
+        if let Some((kind, n)) = text.split_once(' ') {
+            let n = n.trim().parse::<u8>().map_err(|_| unknown())?;
+            return match kind {
+                "dozen" => Ok(Bet::Dozen(n)),
+                "column" => Ok(Bet::Column(n)),
+                _ => Err(unknown()),
+            };
+        }
+
+        let pocket = |part: &str| match part {
+            "00" => Ok(DOUBLE_ZERO),
+            _ => part.parse::<u8>().map_err(|_| unknown()),
+        };
+        let mut numbers = Vec::new();
+        if let Some((low, high)) = text.split_once('-') {
+            numbers.extend(pocket(low)?..=pocket(high)?);
+        } else {
+            for part in text.split('/') {
+                numbers.push(pocket(part)?);
+            }
+        }
+        numbers.sort_unstable();
+        let first = *numbers.first().ok_or_else(unknown)?;
+        let bet = match numbers.len() {
+            1 => Bet::Straight(first),
+            2 => Bet::Split(first, numbers[1]),
+            3 => Bet::Street(first),
+            4 => Bet::Corner(first),
+            6 => Bet::SixLine(first),
+            _ => return Err(unknown()),
+        };
+        if bet.numbers() == numbers {
+            Ok(bet)
+        } else {
+            Err(unknown())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouletteError {
+    /// The bet isn't on this wheel's layout, e.g. 00 on a European wheel.
+    NotOnLayout(Bet, Wheel),
+    BelowMin(Money),
+    AboveMax(Money),
+    /// All bets on the spin together above the table maximum.
+    OverTableMax(Money),
+    /// The wheel can't be changed with bets down.
+    BetsDown,
+    NoBets,
+}
+
+impl std::fmt::Display for RouletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RouletteError::NotOnLayout(bet, wheel) => {
+                write!(f, "{} is not on the {} layout", bet, wheel.name())
+            }
+            RouletteError::BelowMin(min) => write!(f, "the table minimum is ${}", min),
+            RouletteError::AboveMax(max) => write!(f, "the table maximum is ${}", max),
+            RouletteError::OverTableMax(max) => {
+                write!(f, "bets on one spin are limited to ${}", max)
+            }
+            RouletteError::BetsDown => write!(f, "take the bets down first"),
+            RouletteError::NoBets => write!(f, "no bets on the layout"),
+        }
+    }
+}
+
+impl std::error::Error for RouletteError {}
+
+/// A roulette table: the wheel and the bets on the layout.
+pub struct RouletteGame {
+    wheel: Wheel,
+    limits: TableLimits,
+    bets: BTreeMap<Bet, Money>,
+    /// Winning pockets, oldest first.
+    pub history: Vec<u8>,
+    /// Where spins come from; the thread's generator when unset.
+    rng: Option<ShuffleRng>,
+}
+
+impl RouletteGame {
+    pub fn new(wheel: Wheel, limits: TableLimits) -> Self {
+        Self {
+            wheel,
+            limits,
+            bets: BTreeMap::new(),
+            history: Vec::new(),
+            rng: None,
+        }
+    }
+
+    /// Spins the wheel from `rng` from now on; a seeded one lands in the
+    /// same pockets every time.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    pub fn wheel(&self) -> Wheel {
+        self.wheel
+    }
+
+    /// Changes the wheel, between spins with nothing on the layout.
+    pub fn set_wheel(&mut self, wheel: Wheel) -> Result<(), RouletteError> {
+        if !self.bets.is_empty() {
+            return Err(RouletteError::BetsDown);
+        }
+        self.wheel = wheel;
+        Ok(())
+    }
+
+    pub fn bets(&self) -> impl Iterator<Item = (Bet, Money)> + '_ {
+        self.bets.iter().map(|(&bet, &stake)| (bet, stake))
+    }
+
+    pub fn stake(&self, bet: Bet) -> Money {
+        self.bets.get(&bet).copied().unwrap_or_default()
+    }
+
+    pub fn total_bet(&self) -> Money {
+        self.bets.values().copied().sum()
+    }
+
+    /// Adds `stake` to `bet`, checking the layout and the table limits.
+    pub fn place(&mut self, bet: Bet, stake: Money) -> Result<(), RouletteError> {
+        if !bet.is_on(self.wheel) {
+            return Err(RouletteError::NotOnLayout(bet, self.wheel));
+        }
+        let dollars = Money::from_dollars;
+        let total = self.stake(bet) + stake;
+        if total < dollars(self.limits.min_bet) {
+            return Err(RouletteError::BelowMin(dollars(self.limits.min_bet)));
+        }
+        if total > dollars(self.limits.max_bet) {
+            return Err(RouletteError::AboveMax(dollars(self.limits.max_bet)));
+        }
+        if self.total_bet() + stake > dollars(self.limits.max_total) {
+            return Err(RouletteError::OverTableMax(dollars(self.limits.max_total)));
+        }
+        self.bets.insert(bet, total);
+        Ok(())
+    }
+
+    /// Takes every bet down and returns what was on the layout.
+    pub fn clear(&mut self) -> Money {
+        let total = self.total_bet();
+        self.bets.clear();
+        total
+    }
+
+    /// Spins the wheel and settles the layout.
+    pub fn spin(&mut self) -> Result<(u8, SettlementReport), RouletteError> {
+        if self.bets.is_empty() {
+            return Err(RouletteError::NoBets);
+        }
+        let pocket = match &mut self.rng {
+            Some(rng) => self.wheel.spin(rng),
+            None => self.wheel.spin(&mut rand::rng()),
+        };
+        Ok((pocket, self.settle(pocket)))
+    }
+
+    /// Settles every bet against `pocket` and clears the layout. Payouts
+    /// include the stake, as at baccarat.
+    pub fn settle(&mut self, pocket: u8) -> SettlementReport {
+        self.history.push(pocket);
+        let bets = std::mem::take(&mut self.bets)
+            .into_iter()
+            .map(|(bet, stake)| {
+                let payout = if bet.covers(pocket) {
+                    stake * (bet.pays() + 1)
+                } else {
+                    Money::ZERO
+                };
+                SettledBet::new(Wager::Roulette(bet), stake, payout)
+            })
+            .collect();
+        SettlementReport { bets }
+    }
+}
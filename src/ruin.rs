@@ -0,0 +1,305 @@
+//! `simulate --sessions`: plays the same bankroll, strategy and mode as
+//! many separate sessions and reports how they ended: the risk of ruin, how
+//! long a session lasts, how far the bankroll falls on the way, and where it
+//! finishes, each with a 95% confidence interval or percentiles. A session
+//! is ruined once its bankroll can't cover the stake the strategy asks for.
+
+use crate::baccarat::Money;
+use crate::history::json_string;
+use crate::simulate::{self, Simulation};
+use crate::stats::{percentile, wilson_interval};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::thread;
+
+/// Standard errors either side of a mean or rate for 95% confidence.
+const Z_95: f64 = 1.96;
+/// Percentiles of the final bankroll, low to high.
+const BANKROLL_PERCENTILES: [u32; 5] = [5, 25, 50, 75, 95];
+/// Percentiles of the worst drawdown, the bad end last.
+const DRAWDOWN_PERCENTILES: [u32; 5] = [50, 75, 90, 95, 99];
+
+/// How one session ended.
+#[derive(Debug, Clone, Copy)]
+struct Session {
+    hands: u32,
+    ruined: bool,
+    bankroll: Money,
+    drawdown: Money,
+}
+
+/// The sessions, summed up.
+struct Report {
+    sessions: usize,
+    ruined: u32,
+    ruin_interval: (f64, f64),
+    /// Mean hands per session and its margin of error, the median, and the
+    /// mean among ruined sessions only.
+    length: (f64, f64),
+    median_length: u32,
+    ruined_length: Option<f64>,
+    /// Mean final bankroll in dollars and its margin of error.
+    bankroll: (f64, f64),
+    bankrolls: Vec<(u32, Money)>,
+    mean_drawdown: f64,
+    drawdowns: Vec<(u32, Money)>,
+}
+
+/// Mean of `values` and the half-width of its 95% confidence interval.
+fn mean_interval(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0).max(1.0);
+    (mean, Z_95 * (variance / n).sqrt())
+}
+
+impl Report {
+    fn new(sessions: &[Session]) -> Self {
+        let ruined: Vec<&Session> = sessions.iter().filter(|session| session.ruined).collect();
+        let lengths: Vec<f64> = sessions
+            .iter()
+            .map(|session| session.hands as f64)
+            .collect();
+        let mut sorted_lengths: Vec<u32> = sessions.iter().map(|session| session.hands).collect();
+        sorted_lengths.sort_unstable();
+        let finals: Vec<f64> = sessions
+            .iter()
+            .map(|session| session.bankroll.as_dollars())
+            .collect();
+        let mut bankrolls: Vec<Money> = sessions.iter().map(|session| session.bankroll).collect();
+        bankrolls.sort_unstable();
+        let mut drawdowns: Vec<Money> = sessions.iter().map(|session| session.drawdown).collect();
+        drawdowns.sort_unstable();
+        Self {
+            sessions: sessions.len(),
+            ruined: ruined.len() as u32,
+            ruin_interval: wilson_interval(ruined.len() as u32, sessions.len() as u32, Z_95),
+            length: mean_interval(&lengths),
+            median_length: percentile(&sorted_lengths, 50),
+            ruined_length: (!ruined.is_empty()).then(|| {
+                ruined
+                    .iter()
+                    .map(|session| session.hands as f64)
+                    .sum::<f64>()
+                    / ruined.len() as f64
+            }),
+            bankroll: mean_interval(&finals),
+            bankrolls: BANKROLL_PERCENTILES
+                .iter()
+                .map(|&pct| (pct, percentile(&bankrolls, pct)))
+                .collect(),
+            mean_drawdown: drawdowns
+                .iter()
+                .map(|drawdown| drawdown.as_dollars())
+                .sum::<f64>()
+                / sessions.len() as f64,
+            drawdowns: DRAWDOWN_PERCENTILES
+                .iter()
+                .map(|&pct| (pct, percentile(&drawdowns, pct)))
+                .collect(),
+        }
+    }
+
+    fn rate(&self) -> f64 {
+        self.ruined as f64 / self.sessions as f64
+    }
+
+    fn write(
+        &self,
+        out: &mut impl Write,
+        simulation: &Simulation,
+        bankroll: Money,
+    ) -> io::Result<()> {
+        let seed = simulation
+            .seed
+            .map_or(String::new(), |seed| format!(", seed {}", seed));
+        writeln!(
+            out,
+            "{} sessions of up to {} hands from ${}, {} ({:?} mode, {} decks{})",
+            self.sessions,
+            simulation.hands,
+            bankroll,
+            simulation.betting(),
+            simulation.mode,
+            simulation.procedure.decks,
+            seed
+        )?;
+        writeln!(
+            out,
+            "Risk of ruin {:.2}% ({} sessions), 95% confidence {:.2}% to {:.2}%",
+            self.rate() * 100.0,
+            self.ruined,
+            self.ruin_interval.0 * 100.0,
+            self.ruin_interval.1 * 100.0
+        )?;
+        let ruined_length = self.ruined_length.map_or(String::new(), |hands| {
+            format!(" | ruined sessions lasted {:.1} on average", hands)
+        });
+        writeln!(
+            out,
+            "Session length: mean {:.1} hands ± {:.1} | median {}{}",
+            self.length.0, self.length.1, self.median_length, ruined_length
+        )?;
+        let bankrolls: Vec<String> = self
+            .bankrolls
+            .iter()
+            .map(|(pct, money)| format!("{}th ${}", pct, money))
+            .collect();
+        writeln!(
+            out,
+            "Final bankroll: mean ${:.2} ± ${:.2} | {}",
+            self.bankroll.0,
+            self.bankroll.1,
+            bankrolls.join(" | ")
+        )?;
+        let drawdowns: Vec<String> = self
+            .drawdowns
+            .iter()
+            .map(|(pct, money)| format!("{}th ${}", pct, money))
+            .collect();
+        writeln!(
+            out,
+            "Largest drawdown: mean ${:.2} | {}",
+            self.mean_drawdown,
+            drawdowns.join(" | ")
+        )
+    }
+
+    fn json(&self, simulation: &Simulation, bankroll: Money) -> String {
+        let percentiles = |values: &[(u32, Money)]| {
+            let fields: Vec<String> = values
+                .iter()
+                .map(|(pct, money)| format!("\"p{}\": {:.2}", pct, money.as_dollars()))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        };
+        let mut out = String::from("{\n");
+        let _ = writeln!(out, "  \"sessions\": {},", self.sessions);
+        let _ = writeln!(out, "  \"hands_per_session\": {},", simulation.hands);
+        let _ = writeln!(out, "  \"bankroll\": {:.2},", bankroll.as_dollars());
+        let _ = writeln!(
+            out,
+            "  \"strategy\": {},",
+            json_string(simulation.strategy.name())
+        );
+        let _ = writeln!(
+            out,
+            "  \"bet\": {},",
+            json_string(&simulation.main_bet().to_string())
+        );
+        let _ = writeln!(out, "  \"unit\": {:.2},", simulation.unit.as_dollars());
+        let _ = writeln!(
+            out,
+            "  \"mode\": {},",
+            json_string(&format!("{:?}", simulation.mode))
+        );
+        let _ = writeln!(out, "  \"decks\": {},", simulation.procedure.decks);
+        let _ = writeln!(
+            out,
+            "  \"seed\": {},",
+            simulation
+                .seed
+                .map_or("null".to_string(), |seed| seed.to_string())
+        );
+        let _ = writeln!(
+            out,
+            "  \"risk_of_ruin\": {{\"rate\": {:.6}, \"ruined\": {}, \"ci95\": [{:.6}, {:.6}]}},",
+            self.rate(),
+            self.ruined,
+            self.ruin_interval.0,
+            self.ruin_interval.1
+        );
+        let _ = writeln!(
+            out,
+            "  \"session_length\": {{\"mean\": {:.3}, \"ci95\": [{:.3}, {:.3}], \"median\": {}, \"mean_when_ruined\": {}}},",
+            self.length.0,
+            self.length.0 - self.length.1,
+            self.length.0 + self.length.1,
+            self.median_length,
+            self.ruined_length
+                .map_or("null".to_string(), |hands| format!("{:.3}", hands))
+        );
+        let _ = writeln!(
+            out,
+            "  \"final_bankroll\": {{\"mean\": {:.2}, \"ci95\": [{:.2}, {:.2}], \"percentiles\": {}}},",
+            self.bankroll.0,
+            self.bankroll.0 - self.bankroll.1,
+            self.bankroll.0 + self.bankroll.1,
+            percentiles(&self.bankrolls)
+        );
+        let _ = writeln!(
+            out,
+            "  \"largest_drawdown\": {{\"mean\": {:.2}, \"percentiles\": {}}}",
+            self.mean_drawdown,
+            percentiles(&self.drawdowns)
+        );
+        out.push('}');
+        out
+    }
+}
+
+/// Plays session `i`, shuffling from stream `i` of the seed.
+fn play(simulation: &Simulation, i: u32) -> Result<Session, String> {
+    let tally = simulate::deal(
+        simulation,
+        simulation.hands,
+        simulate::stream_seed(simulation.seed, i),
+        None,
+    )?;
+    Ok(Session {
+        hands: tally.dealt,
+        ruined: tally.dealt < simulation.hands,
+        bankroll: tally.bankroll.unwrap_or(Money::ZERO),
+        drawdown: tally.drawdown,
+    })
+}
+
+/// Plays `sessions` sessions from the simulation's bankroll, spread over
+/// its threads, and writes the report, or the same as JSON, to `out`.
+pub fn run(out: &mut impl Write, simulation: &Simulation, sessions: usize) -> Result<(), String> {
+    let Some(bankroll) = simulation.bankroll else {
+        return Err("risk of ruin needs a bankroll (--balance)".to_string());
+    };
+    if !simulation.mode.main_bets().contains(&simulation.bet) {
+        return Err(format!(
+            "{} bets are not dealt at {:?} tables",
+            simulation.bet, simulation.mode
+        ));
+    }
+    let threads = simulation.threads.clamp(1, sessions) as u32;
+    let sessions = sessions as u32;
+    let mut played = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| {
+                scope.spawn(move || {
+                    (t..sessions)
+                        .step_by(threads as usize)
+                        .map(|i| play(simulation, i))
+                        .collect::<Result<Vec<Session>, String>>()
+                })
+            })
+            .collect();
+        let mut played = Vec::with_capacity(sessions as usize);
+        for worker in workers {
+            played.extend(
+                worker
+                    .join()
+                    .map_err(|_| "a simulation thread panicked".to_string())??,
+            );
+        }
+        Ok::<_, String>(played)
+    })?;
+    played.sort_by_key(|session| (session.hands, session.bankroll));
+
+    let report = Report::new(&played);
+    if simulation.json {
+        writeln!(out, "{}", report.json(simulation, bankroll))
+    } else {
+        report.write(out, simulation, bankroll)
+    }
+    .map_err(|e| e.to_string())
+}
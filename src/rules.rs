@@ -0,0 +1,267 @@
+//! House-rules bundles: a named table setup (game mode, pay table, limits,
+//! side bets, and dealing procedure) chosen when the table opens.
+
+use crate::baccarat::{
+    BetSlip, BonusBetType, BonusBets, GameMode, Money, OverUnderRule, PayoutTable,
+    ProcedureProfile, TableLimits, TieRule,
+};
+use crate::storage;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Bundles shipped with the binary; `TERMINAL_CASINO_RULES` points at a
+/// replacement file.
+const BUILTIN_RULES: &str = include_str!("../data/rules.toml");
+pub const RULES_PATH_VAR: &str = "TERMINAL_CASINO_RULES";
+pub const DEFAULT_BUNDLE: &str = "Classic";
+
+#[derive(Debug, Clone, Deserialize)]
+struct OverUnderSpec {
+    line: String,
+    over: i32,
+    under: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BundleSpec {
+    name: String,
+    mode: String,
+    #[serde(default = "default_procedure")]
+    procedure: String,
+    #[serde(default = "default_min_bet")]
+    min_bet: i32,
+    #[serde(default = "default_max_bet")]
+    max_bet: i32,
+    #[serde(default = "default_min_side_bet")]
+    min_side_bet: i32,
+    #[serde(default = "default_max_side_bet")]
+    max_side_bet: i32,
+    #[serde(default = "default_max_total")]
+    max_total: i32,
+    #[serde(default)]
+    side_bets: Option<Vec<String>>,
+    #[serde(default)]
+    pay_table: HashMap<String, i32>,
+    #[serde(default)]
+    over_under: Option<OverUnderSpec>,
+    #[serde(default)]
+    tie_rule: Option<String>,
+}
+
+fn default_procedure() -> String {
+    "standard".to_string()
+}
+
+fn default_min_bet() -> i32 {
+    TableLimits::STANDARD.min_bet
+}
+
+fn default_max_bet() -> i32 {
+    TableLimits::STANDARD.max_bet
+}
+
+fn default_min_side_bet() -> i32 {
+    TableLimits::STANDARD.min_side_bet
+}
+
+fn default_max_side_bet() -> i32 {
+    TableLimits::STANDARD.max_side_bet
+}
+
+fn default_max_total() -> i32 {
+    TableLimits::STANDARD.max_total
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulesBundle {
+    pub name: String,
+    pub mode: GameMode,
+    pub procedure: ProcedureProfile,
+    pub limits: TableLimits,
+    pub side_bets: Vec<BonusBetType>,
+    /// Entries replacing the mode's standard pay table.
+    pub pay_table: HashMap<String, i32>,
+    pub over_under: OverUnderRule,
+    pub tie_rule: TieRule,
+}
+
+impl RulesBundle {
+    fn from_spec(spec: BundleSpec) -> Result<Self, String> {
+        let err = |detail: String| format!("bundle '{}': {}", spec.name, detail);
+        let mode: GameMode = spec.mode.parse().map_err(err)?;
+        let procedure = ProcedureProfile::parse(&spec.procedure).map_err(err)?;
+        if spec.min_bet <= 0 || spec.min_bet > spec.max_bet {
+            return Err(err(format!(
+                "invalid limits {}-{}",
+                spec.min_bet, spec.max_bet
+            )));
+        }
+        if spec.min_side_bet <= 0 || spec.min_side_bet > spec.max_side_bet {
+            return Err(err(format!(
+                "invalid side bet limits {}-{}",
+                spec.min_side_bet, spec.max_side_bet
+            )));
+        }
+        if spec.max_total < spec.min_bet {
+            return Err(err(format!(
+                "max_total {} is under the minimum bet",
+                spec.max_total
+            )));
+        }
+        let side_bets = match &spec.side_bets {
+            None => BonusBetType::ALL.to_vec(),
+            Some(names) => names
+                .iter()
+                .map(|name| name.parse().map_err(err))
+                .collect::<Result<_, _>>()?,
+        };
+        let mut over_under = OverUnderRule::standard();
+        if let Some(ou) = &spec.over_under {
+            over_under.line = OverUnderRule::parse_line(&ou.line).map_err(err)?;
+            over_under.over_return_pct = ou.over;
+            over_under.under_return_pct = ou.under;
+        }
+        mode.payout_table()
+            .with_overrides(&spec.pay_table)
+            .map_err(err)?;
+        let tie_rule = match &spec.tie_rule {
+            Some(rule) => rule.parse().map_err(err)?,
+            None => TieRule::PushMainBets,
+        };
+        Ok(Self {
+            mode,
+            procedure,
+            limits: TableLimits {
+                min_bet: spec.min_bet,
+                max_bet: spec.max_bet,
+                min_side_bet: spec.min_side_bet,
+                max_side_bet: spec.max_side_bet,
+                max_total: spec.max_total,
+            },
+            side_bets,
+            over_under,
+            tie_rule,
+            pay_table: spec.pay_table,
+            name: spec.name,
+        })
+    }
+
+    /// The plain table: classic rules, standard shoe and limits, every side
+    /// bet at standard pay.
+    pub fn classic() -> Self {
+        Self {
+            name: DEFAULT_BUNDLE.to_string(),
+            mode: GameMode::Classic,
+            procedure: ProcedureProfile::standard(crate::baccarat::SHOE_DECKS),
+            limits: TableLimits::STANDARD,
+            side_bets: BonusBetType::ALL.to_vec(),
+            pay_table: HashMap::new(),
+            over_under: OverUnderRule::standard(),
+            tie_rule: TieRule::PushMainBets,
+        }
+    }
+
+    pub fn offers(&self, side_bet: BonusBetType) -> bool {
+        self.side_bets.contains(&side_bet)
+    }
+
+    /// Drops any side bets this table does not offer.
+    pub fn restrict(&self, mut bets: BonusBets) -> BonusBets {
+        for name in BonusBetType::ALL {
+            if !self.offers(name) {
+                bets.set(name, Money::ZERO);
+            }
+        }
+        bets
+    }
+
+    /// `slip` as a `mode` table under these rules takes it: main bets the
+    /// mode doesn't deal and side bets not offered come off, and the other
+    /// main bets are brought inside the limits.
+    pub fn restrict_slip(&self, mode: GameMode, slip: BetSlip) -> BetSlip {
+        let mut restricted = BetSlip::new();
+        for (bet, amount) in slip
+            .main_bets()
+            .filter(|(bet, _)| mode.main_bets().contains(bet))
+        {
+            let dollars = self.limits.clamp(amount.whole_dollars() as i32);
+            restricted.set_main_bet(bet, Money::from_dollars(dollars));
+        }
+        restricted.bonus_bets = mode.restrict(self.restrict(slip.bonus_bets));
+        restricted
+    }
+
+    /// The standard pay table for `mode` with the bundle's overrides and
+    /// then any event boosts laid over it.
+    pub fn payout_table(
+        &self,
+        mode: GameMode,
+        event_boosts: Option<&HashMap<String, i32>>,
+    ) -> PayoutTable {
+        let mut table = mode.payout_table();
+        for (name, ratio) in self
+            .pay_table
+            .iter()
+            .chain(event_boosts.into_iter().flatten())
+        {
+            // Names were checked when the bundle and the event calendar loaded.
+            let _ = table.set(name, *ratio);
+        }
+        table
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default, rename = "bundle")]
+    bundles: Vec<BundleSpec>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RulesBook {
+    pub bundles: Vec<RulesBundle>,
+}
+
+impl RulesBook {
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_RULES).unwrap_or_default()
+    }
+
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let file: RulesFile = toml::from_str(source).map_err(|e| e.to_string())?;
+        let bundles = file
+            .bundles
+            .into_iter()
+            .map(RulesBundle::from_spec)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { bundles })
+    }
+
+    /// Loads the bundles named by `TERMINAL_CASINO_RULES`, falling back to
+    /// the built-in bundles when the variable is unset.
+    pub fn load() -> Result<Self, String> {
+        storage::load_or_builtin(RULES_PATH_VAR, Self::parse, Self::builtin)
+    }
+
+    /// Looks a bundle up ignoring case, spaces, and punctuation, so
+    /// `macau-ez` finds "Macau EZ".
+    pub fn find(&self, name: &str) -> Option<&RulesBundle> {
+        let key = |s: &str| {
+            s.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+        let wanted = key(name);
+        self.bundles
+            .iter()
+            .find(|bundle| key(&bundle.name) == wanted)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.bundles
+            .iter()
+            .map(|bundle| bundle.name.as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,328 @@
+//! The Big Road, the scoreboard baccarat players read a shoe from. Each
+//! column is a streak of one side's wins, top to bottom; a streak deeper
+//! than the board turns right along the bottom row (the "dragon tail"), and
+//! ties don't take a cell but are marked on the hand before them. The bead
+//! plate beside it keeps every hand in its own cell, ties and pairs included.
+
+use crate::baccarat::{Card, GameEvent, Side};
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Widget},
+};
+use serde::{Deserialize, Serialize};
+
+/// Cells down each column of the road.
+pub const ROWS: usize = 6;
+/// Screen columns a cell takes: the hand, then its ties.
+const CELL_WIDTH: u16 = 2;
+/// Screen columns a bead takes: banker pair, winner, player pair.
+const BEAD_WIDTH: u16 = 3;
+
+/// A decided hand on the road.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoadCell {
+    /// 1 for Player, 2 for Banker, as `BaccaratGame` reports the winner.
+    pub winner: u8,
+    /// Ties dealt after this hand and before the next decision. Ties before
+    /// the shoe's first decision are marked on its first cell.
+    pub ties: u32,
+}
+
+/// The Big Road grid for one shoe, built up hand by hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Roadmap {
+    /// Left to right, each `ROWS` cells top to bottom.
+    columns: Vec<[Option<RoadCell>; ROWS]>,
+    /// Where the last decided hand went.
+    last: Option<(usize, usize)>,
+    /// Column the current streak started in; a dragon tail runs on past it.
+    streak_column: usize,
+    /// Ties dealt before the first decision.
+    leading_ties: u32,
+}
+
+impl Roadmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The road for a shoe's winners so far, in the order they were dealt.
+    pub fn from_winners(winners: &[u8]) -> Self {
+        let mut road = Self::new();
+        for &winner in winners {
+            road.record(winner);
+        }
+        road
+    }
+
+    /// Adds a hand: a new cell for a Player or Banker win, a mark on the
+    /// last cell for a tie.
+    pub fn record(&mut self, winner: u8) {
+        if winner == 3 {
+            match self.last {
+                Some((column, row)) => {
+                    if let Some(cell) = &mut self.columns[column][row] {
+                        cell.ties += 1;
+                    }
+                }
+                None => self.leading_ties += 1,
+            }
+            return;
+        }
+        let (column, row, ties) = match self
+            .last
+            .and_then(|(column, row)| Some((column, row, self.cell(column, row)?)))
+        {
+            None => (0, 0, std::mem::take(&mut self.leading_ties)),
+            Some((column, row, last)) if last.winner == winner => {
+                if row + 1 < ROWS && self.cell(column, row + 1).is_none() {
+                    (column, row + 1, 0)
+                } else {
+                    (column + 1, row, 0)
+                }
+            }
+            Some(_) => {
+                self.streak_column += 1;
+                (self.streak_column, 0, 0)
+            }
+        };
+        if self.columns.len() <= column {
+            self.columns.resize(column + 1, [None; ROWS]);
+        }
+        self.columns[column][row] = Some(RoadCell { winner, ties });
+        self.last = Some((column, row));
+    }
+
+    /// The hand at `column`, `row`, if one has been placed there.
+    pub fn cell(&self, column: usize, row: usize) -> Option<RoadCell> {
+        self.columns
+            .get(column)
+            .and_then(|cells| cells.get(row).copied().flatten())
+    }
+
+    /// Columns in use, dragon tails included.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+}
+
+/// The road in colour: a ● for each win in the theme's Banker or Player
+/// color, with the ties after it in the tie color beside it, and muted
+/// dots for the empty cells. Only the latest columns that fit are shown.
+pub struct BigRoad<'a> {
+    road: &'a Roadmap,
+    block: Option<Block<'a>>,
+    theme: Theme,
+}
+
+impl<'a> BigRoad<'a> {
+    pub fn new(road: &'a Roadmap) -> Self {
+        Self {
+            road,
+            block: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl Widget for BigRoad<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        let shown = (area.width / CELL_WIDTH) as usize;
+        let first = self.road.width().saturating_sub(shown);
+        for (x, column) in (first..first + shown).enumerate() {
+            let left = area.x + x as u16 * CELL_WIDTH;
+            for row in 0..ROWS.min(area.height as usize) {
+                let y = area.y + row as u16;
+                let Some(cell) = self.road.cell(column, row) else {
+                    buf.set_string(left, y, "·", Style::default().fg(self.theme.muted));
+                    continue;
+                };
+                let color = if cell.winner == 2 {
+                    self.theme.banker
+                } else {
+                    self.theme.player
+                };
+                buf.set_string(left, y, "●", Style::default().fg(color));
+                let ties = match cell.ties {
+                    0 => continue,
+                    1..=9 => cell.ties.to_string(),
+                    _ => "+".to_string(),
+                };
+                buf.set_string(left + 1, y, ties, Style::default().fg(self.theme.tie));
+            }
+        }
+    }
+}
+
+/// A hand on the bead plate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bead {
+    /// 1 for Player, 2 for Banker, 3 for a tie.
+    pub winner: u8,
+    /// The player's first two cards shared a rank.
+    pub player_pair: bool,
+    /// The banker's first two cards shared a rank.
+    pub banker_pair: bool,
+}
+
+/// Every hand of the shoe in the order it was dealt, read off the table's
+/// `GameEvent` stream: the opening cards say whether either side was
+/// paired, the settlement gives the winner, and a reshuffle starts a new
+/// plate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BeadPlate {
+    beads: Vec<Bead>,
+    /// Opening cards of the hand being dealt.
+    player: Vec<Card>,
+    banker: Vec<Card>,
+}
+
+impl BeadPlate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::CardDealt {
+                side: Side::Player,
+                card,
+            } => self.player.push(card),
+            GameEvent::CardDealt {
+                side: Side::Banker,
+                card,
+            } => self.banker.push(card),
+            GameEvent::RoundSettled { winner, .. } => {
+                self.beads.push(Bead {
+                    winner,
+                    player_pair: is_pair(&self.player),
+                    banker_pair: is_pair(&self.banker),
+                });
+                self.player.clear();
+                self.banker.clear();
+            }
+            GameEvent::ShoeReshuffled => self.clear(),
+            GameEvent::NaturalDetected { .. }
+            | GameEvent::ThirdCardDrawn { .. }
+            | GameEvent::CardsBurned { .. } => {}
+        }
+    }
+
+    /// A plate already holding `beads`, e.g. from a saved session.
+    pub fn from_beads(beads: Vec<Bead>) -> Self {
+        Self {
+            beads,
+            ..Self::default()
+        }
+    }
+
+    /// Empties the plate for a new shoe, dropping any hand half dealt.
+    pub fn clear(&mut self) {
+        self.beads.clear();
+        self.player.clear();
+        self.banker.clear();
+    }
+
+    pub fn beads(&self) -> &[Bead] {
+        &self.beads
+    }
+}
+
+fn is_pair(cards: &[Card]) -> bool {
+    matches!(cards, [first, second, ..] if first.rank == second.rank)
+}
+
+/// The bead plate in colour: each hand's winner as a letter in the
+/// theme's color for it, filled top to bottom and then left to right, with
+/// a Banker colored dot before it for a banker pair and a Player colored
+/// one after it for a player pair. Only the latest columns that fit are
+/// shown.
+pub struct BeadRoad<'a> {
+    plate: &'a BeadPlate,
+    block: Option<Block<'a>>,
+    theme: Theme,
+}
+
+impl<'a> BeadRoad<'a> {
+    pub fn new(plate: &'a BeadPlate) -> Self {
+        Self {
+            plate,
+            block: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl Widget for BeadRoad<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        let beads = self.plate.beads();
+        let shown = (area.width / BEAD_WIDTH) as usize;
+        let first = beads.len().div_ceil(ROWS).saturating_sub(shown);
+        for x in 0..shown {
+            let left = area.x + x as u16 * BEAD_WIDTH;
+            for row in 0..ROWS.min(area.height as usize) {
+                let y = area.y + row as u16;
+                let Some(bead) = beads.get((first + x) * ROWS + row) else {
+                    buf.set_string(left + 1, y, "·", Style::default().fg(self.theme.muted));
+                    continue;
+                };
+                let (letter, color) = match bead.winner {
+                    1 => ("P", self.theme.player),
+                    2 => ("B", self.theme.banker),
+                    _ => ("T", self.theme.tie),
+                };
+                buf.set_string(
+                    left + 1,
+                    y,
+                    letter,
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                );
+                if bead.banker_pair {
+                    buf.set_string(left, y, "•", Style::default().fg(self.theme.banker));
+                }
+                if bead.player_pair {
+                    buf.set_string(left + 2, y, "•", Style::default().fg(self.theme.player));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Betting strategies written in Rhai and loaded at runtime, so a system
+//! can be tried at the table's autoplay or in `simulate` without
+//! rebuilding. A script defines `on_round_end(history)`, called before
+//! every hand with how the session has gone, and returns the next bet:
+//!
+//! ```text
+//! // Double the stake after a loss, back to one unit after a win.
+//! fn on_round_end(history) {
+//!     if this.units == () || history.last >= 0.0 { this.units = 1; }
+//!     else { this.units *= 2; }
+//!     #{ bet: "banker", units: this.units }
+//! }
+//! ```
+//!
+//! `history` is a map of `shoe` (the winners so far this shoe, "P", "B"
+//! or "T"), `last` (the last main bet's net in dollars, 0.0 before the
+//! first hand), `net` (the session's net), `hands`, `bankroll` (dollars,
+//! or `()` without one), `unit` and `bet` (the base stake and the bet asked
+//! for). `this` is a map the script keeps between hands. The bet is a bet
+//! name for one unit, or a map of `bet` with `units` or `stake` (dollars).
+
+use crate::baccarat::{BetType, Money};
+use crate::strategy::{Decision, Strategy};
+use rhai::{AST, Array, CallFnOptions, Dynamic, Engine, Map, Scope};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The function a strategy script defines.
+const ENTRY_POINT: &str = "on_round_end";
+/// Operations one call may take before it is stopped, so a script that
+/// loops forever can't hang the table.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A compiled strategy script.
+#[derive(Debug)]
+pub struct StrategyScript {
+    pub path: PathBuf,
+    /// The file name without `.rhai`, to show in reports.
+    pub name: String,
+    ast: AST,
+}
+
+impl PartialEq for StrategyScript {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+/// An engine with the limits scripts run under. `print` and `debug` are
+/// dropped: they would write over the table.
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine
+}
+
+impl StrategyScript {
+    /// Reads and compiles the script at `path`, checking it defines
+    /// `on_round_end(history)`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let ast = engine()
+            .compile(&source)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == ENTRY_POINT && f.params.len() == 1)
+        {
+            return Err(format!(
+                "{}: no {}(history) function",
+                path.display(),
+                ENTRY_POINT
+            ));
+        }
+        let name = path.file_stem().map_or("script".to_string(), |stem| {
+            stem.to_string_lossy().into_owned()
+        });
+        Ok(Self {
+            path: path.to_path_buf(),
+            name,
+            ast,
+        })
+    }
+
+    /// A fresh run of the script betting `bet` from a base stake of `unit`.
+    pub fn build(self: &Arc<Self>, bet: BetType, unit: Money) -> ScriptStrategy {
+        ScriptStrategy {
+            script: Arc::clone(self),
+            engine: engine(),
+            state: Dynamic::from_map(Map::new()),
+            bet,
+            unit,
+            last: Money::ZERO,
+            net: Money::ZERO,
+            hands: 0,
+        }
+    }
+}
+
+/// One run of a script, with the state it keeps between hands.
+pub struct ScriptStrategy {
+    script: Arc<StrategyScript>,
+    engine: Engine,
+    /// `this` in the script.
+    state: Dynamic,
+    bet: BetType,
+    unit: Money,
+    last: Money,
+    net: Money,
+    hands: u32,
+}
+
+/// Dollars as Rhai reads them, from an integer or a float.
+fn dollars(value: &Dynamic) -> Option<f64> {
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|n| n as f64))
+}
+
+impl ScriptStrategy {
+    fn history(&self, shoe: &[u8], bankroll: Option<Money>) -> Map {
+        let winners: Array = shoe
+            .iter()
+            .map(|&winner| {
+                Dynamic::from(match winner {
+                    1 => "P",
+                    2 => "B",
+                    _ => "T",
+                })
+            })
+            .collect();
+        let mut history = Map::new();
+        history.insert("shoe".into(), winners.into());
+        history.insert("last".into(), self.last.as_dollars().into());
+        history.insert("net".into(), self.net.as_dollars().into());
+        history.insert("hands".into(), (self.hands as i64).into());
+        history.insert(
+            "bankroll".into(),
+            bankroll.map_or(Dynamic::UNIT, |money| money.as_dollars().into()),
+        );
+        history.insert("unit".into(), self.unit.as_dollars().into());
+        history.insert("bet".into(), self.bet.to_string().into());
+        history
+    }
+
+    /// Reads the bet the script returned.
+    fn decision(&self, value: Dynamic) -> Result<Decision, String> {
+        let (bet, stake) = if let Ok(name) = value.clone().into_string() {
+            (name.parse()?, self.unit.as_dollars())
+        } else if let Some(map) = value.try_cast::<Map>() {
+            let bet = match map.get("bet") {
+                Some(name) => name
+                    .clone()
+                    .into_string()
+                    .map_err(|_| "bet must be a name".to_string())?
+                    .parse()?,
+                None => self.bet,
+            };
+            let stake = match (map.get("units"), map.get("stake")) {
+                (Some(units), None) => {
+                    dollars(units).ok_or("units must be a number")? * self.unit.as_dollars()
+                }
+                (None, Some(stake)) => dollars(stake).ok_or("stake must be a number")?,
+                (None, None) => self.unit.as_dollars(),
+                (Some(_), Some(_)) => return Err("give units or stake, not both".to_string()),
+            };
+            (bet, stake)
+        } else {
+            return Err(format!("{} must return a bet name or a map", ENTRY_POINT));
+        };
+        let cents = (stake * 100.0).round();
+        if !(1.0..=Money::MAX_INPUT.cents() as f64).contains(&cents) {
+            return Err(format!(
+                "stake must be above 0 and at most {}, not {}",
+                Money::MAX_INPUT,
+                stake
+            ));
+        }
+        Ok(Decision {
+            bet,
+            stake: Money::from_cents(cents as i64),
+        })
+    }
+}
+
+impl Strategy for ScriptStrategy {
+    fn next_bet(&mut self, shoe: &[u8], bankroll: Option<Money>) -> Result<Decision, String> {
+        let history = self.history(shoe, bankroll);
+        let options = CallFnOptions::new().bind_this_ptr(&mut self.state);
+        let value = self
+            .engine
+            .call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &self.script.ast,
+                ENTRY_POINT,
+                (history,),
+            )
+            .map_err(|e| format!("{}: {}", self.script.name, e))?;
+        self.decision(value)
+            .map_err(|e| format!("{}: {}", self.script.name, e))
+    }
+
+    fn settle(&mut self, net: Money) {
+        self.last = net;
+        self.net += net;
+        self.hands += 1;
+    }
+}
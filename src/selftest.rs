@@ -0,0 +1,137 @@
+//! `selftest` subcommand: statistical checks that the shuffle is unbiased.
+
+use crate::baccarat::{Deck, Shoe};
+use crate::stats::{chi_square, chi_square_p_value};
+use std::io::{self, Write};
+
+pub const DEFAULT_SHUFFLES: usize = 100_000;
+
+/// Reject when p falls outside [ALPHA, 1 - ALPHA]: too biased or suspiciously
+/// uniform.
+const ALPHA: f64 = 0.001;
+
+pub struct TestResult {
+    pub name: &'static str,
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl TestResult {
+    fn new(name: &'static str, observed: &[f64], expected: &[f64], df: f64) -> Self {
+        let statistic = chi_square(observed, expected);
+        Self {
+            name,
+            statistic,
+            df,
+            p_value: chi_square_p_value(statistic, df),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.p_value > ALPHA && self.p_value < 1.0 - ALPHA
+    }
+}
+
+/// Card-at-position frequencies over many single-deck shuffles: every card
+/// should land in every position equally often.
+fn position_test(shuffles: usize) -> TestResult {
+    let mut counts = vec![0f64; 52 * 52];
+    for _ in 0..shuffles {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        for (position, card) in deck.cards().iter().enumerate() {
+            let identity = (card.suit as usize) * 13 + (card.rank as usize - 1);
+            counts[position * 52 + identity] += 1.0;
+        }
+    }
+    let expected = vec![shuffles as f64 / 52.0; 52 * 52];
+    TestResult::new("Card position (52x52)", &counts, &expected, 51.0 * 51.0)
+}
+
+/// Rank of the first cards out of an 8-deck shoe, as dealt in a real game.
+fn shoe_rank_test(shuffles: usize) -> TestResult {
+    const DEALT: usize = 6;
+    let mut counts = [0f64; 13];
+    let shoes = (shuffles / 10).max(1);
+    for _ in 0..shoes {
+        let mut shoe = Shoe::new(8);
+        for _ in 0..DEALT {
+            if let Some(card) = shoe.deal() {
+                counts[card.rank as usize - 1] += 1.0;
+            }
+        }
+    }
+    let expected = [(shoes * DEALT) as f64 / 13.0; 13];
+    TestResult::new("Shoe rank frequency (8 decks)", &counts, &expected, 12.0)
+}
+
+/// Serial test on consecutive rank pairs within a shuffled deck.
+fn serial_test(shuffles: usize) -> TestResult {
+    let mut counts = vec![0f64; 13 * 13];
+    for _ in 0..shuffles {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        for pair in deck.cards().windows(2) {
+            counts[(pair[0].rank as usize - 1) * 13 + (pair[1].rank as usize - 1)] += 1.0;
+        }
+    }
+    let pairs = (shuffles * 51) as f64;
+    let expected: Vec<f64> = (0..13 * 13)
+        .map(|cell| {
+            let same_rank = cell / 13 == cell % 13;
+            let p = if same_rank {
+                4.0 / 52.0 * 3.0 / 51.0
+            } else {
+                4.0 / 52.0 * 4.0 / 51.0
+            };
+            pairs * p
+        })
+        .collect();
+    TestResult::new("Serial rank pairs (13x13)", &counts, &expected, 168.0)
+}
+
+/// Runs every test, writes the report to `out`, and returns whether all
+/// passed.
+pub fn run(out: &mut impl Write, shuffles: usize) -> io::Result<bool> {
+    writeln!(
+        out,
+        "RNG self-test: {} shuffles, alpha = {}",
+        shuffles, ALPHA
+    )?;
+    writeln!(
+        out,
+        "{:<32} {:>12} {:>8} {:>10}  Result",
+        "Test", "Chi-square", "df", "p-value"
+    )?;
+
+    let results = [
+        position_test(shuffles),
+        shoe_rank_test(shuffles),
+        serial_test(shuffles),
+    ];
+    for result in &results {
+        writeln!(
+            out,
+            "{:<32} {:>12.2} {:>8} {:>10.4}  {}",
+            result.name,
+            result.statistic,
+            result.df,
+            result.p_value,
+            if result.passed() { "PASS" } else { "FAIL" }
+        )?;
+    }
+
+    let all_passed = results.iter().all(TestResult::passed);
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{}",
+        if all_passed {
+            "All tests passed."
+        } else {
+            "One or more tests FAILED."
+        }
+    )?;
+    Ok(all_passed)
+}
@@ -0,0 +1,859 @@
+//! `serve`: baccarat tables shared over the network. Clients list the
+//! tables, then join one with `join` (or anything that speaks the line
+//! protocol in `protocol`), to play or only to watch. Players bet while the
+//! betting window is open, and everyone at a table sees the same hand
+//! dealt. Each connection gets a thread that reads its messages, and each
+//! table a dealer thread that opens the window, deals and settles in turn
+//! on a fixed clock while anyone is there, or all the time when the tables
+//! run continuously, like a live-dealer stream. The dealer keeps the
+//! shoe's bead plate so that whoever sits down mid-shoe sees its history. Between dealing and settling, the biggest bettor
+//! gets the squeeze: they peel their side's first cards for the table, a
+//! step at a time, before the hand is shown.
+
+use crate::api::{self, ApiOptions};
+use crate::baccarat::{BaccaratGame, BetType, Card, GameMode, Money, ProcedureProfile, Side};
+use crate::profiles;
+use crate::protocol::{
+    self, ClientMessage, PlayerResult, PlayerView, Role, ServerMessage, Snapshot, TableSummary,
+};
+use crate::scoreboard::Bead;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A client that can't take a message in this long is dropped, so one
+/// stalled connection can't hold up the table.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// How often an empty table looks for company.
+const IDLE_POLL: Duration = Duration::from_millis(200);
+/// How long the squeezer has to peel before the cards are turned for them.
+const SQUEEZE_TIME: Duration = Duration::from_secs(20);
+/// How often the dealer checks on the squeeze.
+const SQUEEZE_POLL: Duration = Duration::from_millis(50);
+/// Cards the squeezer peels, at most.
+const SQUEEZED_CARDS: usize = 2;
+/// Seats at each table.
+pub const MAX_PLAYERS: usize = 8;
+/// Spectators each table takes.
+pub const MAX_SPECTATORS: usize = 256;
+/// The table's name when `serve` isn't given any.
+pub const DEFAULT_TABLE: &str = "main";
+
+/// A table to run, as `--table NAME[:MODE]` gives it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSpec {
+    pub name: String,
+    /// The mode `serve` was given if `None`.
+    pub mode: Option<GameMode>,
+}
+
+impl TableSpec {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (name, mode) = match value.split_once(':') {
+            Some((name, mode)) => (name, Some(mode.parse()?)),
+            None => (value, None),
+        };
+        Ok(Self {
+            name: profiles::parse_name(name)?,
+            mode,
+        })
+    }
+}
+
+/// How the shared tables are set up.
+pub struct ServeOptions {
+    pub bind: String,
+    pub tables: Vec<TableSpec>,
+    pub mode: GameMode,
+    pub procedure: ProcedureProfile,
+    /// What every player sits down with.
+    pub balance: Money,
+    pub betting_secs: u64,
+    /// How long each hand stays up before betting opens again.
+    pub reveal_secs: u64,
+    /// Deal even when nobody is at a table, so the shoe keeps moving.
+    pub continuous: bool,
+    /// Where to answer the HTTP API, if anywhere.
+    pub api: Option<String>,
+    /// The origin browsers may call the API from, if any.
+    pub api_origin: Option<String>,
+    pub seed: Option<u64>,
+}
+
+/// A connection at a table: a seated player, or a spectator.
+struct Seat {
+    id: u64,
+    name: String,
+    role: Role,
+    balance: Money,
+    bet: Option<(BetType, Money)>,
+    /// What a player must send to sit back down once they get up.
+    token: Option<String>,
+    /// Written only with the table locked, so messages never interleave.
+    stream: TcpStream,
+}
+
+/// A player who got up: their balance, kept for when they sit back down so
+/// a dropped connection doesn't cost them their winnings, and any bet they
+/// left down, which still plays. Their seat goes only to a join with their
+/// token.
+struct Departed {
+    balance: Money,
+    bet: Option<(BetType, Money)>,
+    token: String,
+}
+
+impl Departed {
+    fn of(seat: &Seat) -> Self {
+        Self {
+            balance: seat.balance,
+            bet: seat.bet,
+            token: seat.token.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// A fresh, unguessable token for a player to sit back down with.
+fn resume_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// The squeeze under way: whose it is, the cards they are peeling and how
+/// far each has come.
+struct Squeeze {
+    squeezer: u64,
+    name: String,
+    side: Side,
+    cards: Vec<Card>,
+    steps: Vec<u8>,
+    /// The other side's cards, face up.
+    shown: Vec<Card>,
+    closes_at: Instant,
+}
+
+impl Squeeze {
+    fn done(&self) -> bool {
+        self.steps.iter().all(|&step| step >= protocol::PEEL_STEPS)
+    }
+
+    /// The squeeze as announced, with the time it has left, and each card
+    /// as far as it is peeled: what someone arriving mid-squeeze is sent.
+    fn replay(&self, round: u32) -> Vec<ServerMessage> {
+        let announce = ServerMessage::Squeeze {
+            round,
+            squeezer: self.name.clone(),
+            side: self.side,
+            cards: self.cards.len(),
+            shown: protocol::card_labels(&self.shown),
+            closes_in_ms: self
+                .closes_at
+                .saturating_duration_since(Instant::now())
+                .as_millis() as u64,
+        };
+        let peeled = self
+            .cards
+            .iter()
+            .zip(&self.steps)
+            .enumerate()
+            .filter(|(_, (_, step))| **step > 0);
+        std::iter::once(announce)
+            .chain(peeled.map(|(i, (card, &step))| protocol::peeled(i, card, step)))
+            .collect()
+    }
+}
+
+/// Everything the connection threads and a table's dealer share.
+struct Table {
+    name: String,
+    mode: GameMode,
+    decks: usize,
+    balance: Money,
+    betting_secs: u64,
+    reveal_secs: u64,
+    continuous: bool,
+    min_bet: i32,
+    max_bet: i32,
+    seats: Vec<Seat>,
+    departed: HashMap<String, Departed>,
+    next_id: u64,
+    round: u32,
+    shoe: u32,
+    /// Cards dealt from the shoe so far, and its size.
+    cards_dealt: usize,
+    shoe_size: usize,
+    /// When betting closes; `None` while the hand is dealt and shown.
+    closes_at: Option<Instant>,
+    squeeze: Option<Squeeze>,
+    /// This shoe's hands, for the bead plate.
+    beads: Vec<Bead>,
+}
+
+impl Table {
+    fn players(&self) -> impl Iterator<Item = &Seat> {
+        self.seats.iter().filter(|seat| seat.role == Role::Player)
+    }
+
+    fn spectators(&self) -> usize {
+        self.seats
+            .iter()
+            .filter(|seat| seat.role == Role::Spectator)
+            .count()
+    }
+
+    fn views(&self) -> Vec<PlayerView> {
+        self.players()
+            .map(|player| PlayerView {
+                name: player.name.clone(),
+                balance: player.balance,
+                bet: player.bet.map(|(bet, _)| bet.to_string()),
+                stake: player.bet.map_or(Money::ZERO, |(_, stake)| stake),
+            })
+            .collect()
+    }
+
+    fn summary(&self) -> TableSummary {
+        TableSummary {
+            name: self.name.clone(),
+            mode: format!("{:?}", self.mode),
+            decks: self.decks,
+            players: self.players().count(),
+            seats: MAX_PLAYERS,
+            spectators: self.spectators(),
+            round: self.round,
+            shoe: self.shoe,
+        }
+    }
+
+    /// Sends `message` to everyone at the table, dropping those that can't
+    /// take it.
+    fn broadcast(&mut self, message: &ServerMessage) {
+        self.seats.retain_mut(|seat| {
+            let sent = protocol::send(&mut seat.stream, message).is_ok();
+            if !sent {
+                println!("[{}] {} dropped", self.name, seat.name);
+                if seat.role == Role::Player {
+                    self.departed.insert(seat.name.clone(), Departed::of(seat));
+                }
+                let _ = seat.stream.shutdown(Shutdown::Both);
+            }
+            sent
+        });
+    }
+
+    fn broadcast_table(&mut self) {
+        let message = ServerMessage::Table {
+            players: self.views(),
+            spectators: self.spectators(),
+        };
+        self.broadcast(&message);
+    }
+
+    fn send_to(&mut self, id: u64, message: &ServerMessage) {
+        if let Some(seat) = self.seats.iter_mut().find(|seat| seat.id == id) {
+            let _ = protocol::send(&mut seat.stream, message);
+        }
+    }
+
+    fn betting_open(&self) -> ServerMessage {
+        let closes_in = self.closes_at.map_or(Duration::ZERO, |at| {
+            at.saturating_duration_since(Instant::now())
+        });
+        ServerMessage::BettingOpen {
+            round: self.round,
+            shoe: self.shoe,
+            closes_in_ms: closes_in.as_millis() as u64,
+            cards_dealt: self.cards_dealt,
+            shoe_size: self.shoe_size,
+        }
+    }
+
+    /// The table as seat `id` should see it.
+    fn snapshot(&self, id: u64) -> ServerMessage {
+        let seat = self.seats.iter().find(|seat| seat.id == id);
+        let bet = seat.and_then(|seat| seat.bet);
+        ServerMessage::Sync(Snapshot {
+            round: self.round,
+            shoe: self.shoe,
+            cards_dealt: self.cards_dealt,
+            shoe_size: self.shoe_size,
+            beads: self.beads.clone(),
+            balance: seat.map_or(Money::ZERO, |seat| seat.balance),
+            bet: bet.map(|(bet, _)| bet.to_string()),
+            stake: bet.map_or(Money::ZERO, |(_, stake)| stake),
+            players: self.views(),
+            spectators: self.spectators(),
+        })
+    }
+
+    /// Brings seat `id` up to date: the snapshot, then the betting window
+    /// or the squeeze if either is under way.
+    fn sync(&mut self, id: u64) {
+        let snapshot = self.snapshot(id);
+        self.send_to(id, &snapshot);
+        // Someone arriving mid-window can still get a bet in.
+        if self.closes_at.is_some() {
+            let open = self.betting_open();
+            self.send_to(id, &open);
+        }
+        if let Some(squeeze) = &self.squeeze {
+            for message in squeeze.replay(self.round) {
+                self.send_to(id, &message);
+            }
+        }
+    }
+
+    /// Seats `name` to play or to watch, returning its id, or says why not.
+    /// A player sitting back down with the `token` from their last welcome
+    /// gets their balance back, and any bet they left down that is yet to
+    /// play; without it, the name stays theirs.
+    fn join(
+        &mut self,
+        name: &str,
+        role: Role,
+        version: u32,
+        token: Option<&str>,
+        stream: &TcpStream,
+    ) -> Result<u64, String> {
+        if version > protocol::PROTOCOL_VERSION {
+            return Err(format!(
+                "the server speaks protocol {} and the client {}; join with an older client",
+                protocol::PROTOCOL_VERSION,
+                version
+            ));
+        }
+        let name = profiles::parse_name(name)?;
+        let (balance, bet, token) = match role {
+            Role::Player => {
+                if self.players().any(|player| player.name == name) {
+                    return Err(format!("'{}' is already at the table", name));
+                }
+                if self.players().count() >= MAX_PLAYERS {
+                    return Err(format!(
+                        "the table is full ({} seats); join to watch instead",
+                        MAX_PLAYERS
+                    ));
+                }
+                if self
+                    .departed
+                    .get(&name)
+                    .is_some_and(|departed| token != Some(departed.token.as_str()))
+                {
+                    return Err(format!(
+                        "'{}' is held for the player who got up; sit back down with their resume token",
+                        name
+                    ));
+                }
+                let (balance, bet) = self
+                    .departed
+                    .remove(&name)
+                    .map_or((self.balance, None), |departed| {
+                        (departed.balance, departed.bet)
+                    });
+                (balance, bet, Some(resume_token()))
+            }
+            Role::Spectator => {
+                if self.spectators() >= MAX_SPECTATORS {
+                    return Err(format!(
+                        "the table has all the spectators it takes ({})",
+                        MAX_SPECTATORS
+                    ));
+                }
+                (Money::ZERO, None, None)
+            }
+        };
+        let stream = stream.try_clone().map_err(|e| e.to_string())?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.seats.push(Seat {
+            id,
+            name: name.clone(),
+            role,
+            balance,
+            bet,
+            token: token.clone(),
+            stream,
+        });
+        let welcome = ServerMessage::Welcome {
+            version: protocol::PROTOCOL_VERSION,
+            token,
+            name,
+            table: self.name.clone(),
+            role,
+            balance,
+            mode: format!("{:?}", self.mode),
+            decks: self.decks,
+            betting_secs: self.betting_secs,
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+        };
+        self.send_to(id, &welcome);
+        self.sync(id);
+        self.broadcast_table();
+        Ok(id)
+    }
+
+    fn leave(&mut self, id: u64) {
+        if let Some(index) = self.seats.iter().position(|seat| seat.id == id) {
+            let seat = self.seats.remove(index);
+            match seat.role {
+                Role::Player => {
+                    println!("[{}] {} left with ${}", self.name, seat.name, seat.balance);
+                    self.departed.insert(seat.name.clone(), Departed::of(&seat));
+                }
+                Role::Spectator => println!("[{}] {} stopped watching", self.name, seat.name),
+            }
+            self.broadcast_table();
+        }
+    }
+
+    /// Puts down, replaces or (with 0) takes down a player's bet.
+    fn place_bet(&mut self, id: u64, bet: &str, amount: i32) -> Result<(), String> {
+        let (mode, min_bet, max_bet, open) = (
+            self.mode,
+            self.min_bet,
+            self.max_bet,
+            self.closes_at.is_some(),
+        );
+        let seat = self
+            .seats
+            .iter_mut()
+            .find(|seat| seat.id == id)
+            .ok_or("not at the table")?;
+        if seat.role == Role::Spectator {
+            return Err("spectators can't bet".to_string());
+        }
+        if !open {
+            return Err("betting is closed".to_string());
+        }
+        if amount == 0 {
+            seat.bet = None;
+        } else {
+            let bet: BetType = bet.parse()?;
+            if !mode.main_bets().contains(&bet) {
+                return Err(format!("{:?} tables don't take {}", mode, bet));
+            }
+            if !(min_bet..=max_bet).contains(&amount) {
+                return Err(format!("bets run from ${} to ${}", min_bet, max_bet));
+            }
+            let stake = Money::from_dollars(amount);
+            if stake > seat.balance {
+                return Err(format!("only ${} to bet with", seat.balance));
+            }
+            seat.bet = Some((bet, stake));
+        }
+        self.broadcast_table();
+        Ok(())
+    }
+
+    /// Peels a squeezed card back a step for everyone to see.
+    fn peel(&mut self, id: u64, card: usize) -> Result<(), String> {
+        let squeeze = self.squeeze.as_mut().ok_or("nothing to squeeze")?;
+        if squeeze.squeezer != id {
+            return Err(format!("{} has the squeeze", squeeze.name));
+        }
+        let step = squeeze
+            .steps
+            .get_mut(card)
+            .ok_or(format!("no card {} to peel", card))?;
+        if *step < protocol::PEEL_STEPS {
+            *step += 1;
+            let peeled = protocol::peeled(card, &squeeze.cards[card], *step);
+            self.broadcast(&peeled);
+        }
+        Ok(())
+    }
+
+    /// The player with the most down on the player or banker side, the
+    /// first seated taking a tie, and the side they backed.
+    fn biggest_bettor(&self) -> Option<(u64, String, Side)> {
+        let mut biggest: Option<(&Seat, Money, Side)> = None;
+        for seat in self.players() {
+            let (side, stake) = match seat.bet {
+                Some((BetType::Player, stake)) => (Side::Player, stake),
+                Some((BetType::Banker, stake)) => (Side::Banker, stake),
+                _ => continue,
+            };
+            if biggest.is_none_or(|(_, most, _)| stake > most) {
+                biggest = Some((seat, stake, side));
+            }
+        }
+        biggest.map(|(seat, _, side)| (seat.id, seat.name.clone(), side))
+    }
+}
+
+/// Locks a table, carrying on past a connection thread that panicked.
+fn lock(table: &Mutex<Table>) -> MutexGuard<'_, Table> {
+    table
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn list(tables: &[Mutex<Table>]) -> ServerMessage {
+    ServerMessage::Tables {
+        tables: tables.iter().map(|table| lock(table).summary()).collect(),
+    }
+}
+
+/// Reads one client's messages until it goes away.
+fn serve_client(stream: TcpStream, tables: Arc<Vec<Mutex<Table>>>) {
+    let address = stream
+        .peer_addr()
+        .map_or("?".to_string(), |address| address.to_string());
+    let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let mut lines = BufReader::new(reader).lines();
+    let mut stream = stream;
+    let reject = |reason: String| ServerMessage::Rejected { reason };
+
+    // Until it joins a table, the connection is this thread's alone to
+    // write to.
+    let (table, id) = loop {
+        let Some(Ok(line)) = lines.next() else { return };
+        let outcome = match serde_json::from_str::<ClientMessage>(&line) {
+            Ok(ClientMessage::ListTables) => Ok(None),
+            Ok(ClientMessage::Join {
+                name,
+                table,
+                role,
+                version,
+                token,
+            }) => {
+                match tables.iter().find(|candidate| {
+                    table
+                        .as_ref()
+                        .is_none_or(|table| lock(candidate).name == *table)
+                }) {
+                    Some(found) => {
+                        let mut joined = lock(found);
+                        joined
+                            .join(&name, role, version, token.as_deref(), &stream)
+                            .map(|id| {
+                                let verb = if role == Role::Player {
+                                    "joined"
+                                } else {
+                                    "is watching"
+                                };
+                                println!("[{}] {} {} from {}", joined.name, name, verb, address);
+                                Some((found, id))
+                            })
+                    }
+                    None => Err(format!("no table called '{}'", table.unwrap_or_default())),
+                }
+            }
+            Ok(_) => Err("join a table first".to_string()),
+            Err(e) => Err(format!("unreadable message: {}", e)),
+        };
+        let reply = match outcome {
+            Ok(Some(joined)) => break joined,
+            Ok(None) => list(&tables),
+            Err(reason) => reject(reason),
+        };
+        if protocol::send(&mut stream, &reply).is_err() {
+            return;
+        }
+    };
+
+    for line in lines {
+        let Ok(line) = line else { break };
+        let message = serde_json::from_str::<ClientMessage>(&line);
+        // The listing locks every table in turn, so it is put together
+        // before this one is locked.
+        let listing = matches!(message, Ok(ClientMessage::ListTables)).then(|| list(&tables));
+        let mut table = lock(table);
+        let outcome = match message {
+            Ok(ClientMessage::Bet { bet, amount }) => table.place_bet(id, &bet, amount),
+            Ok(ClientMessage::Peel { card }) => table.peel(id, card),
+            Ok(ClientMessage::ListTables) => Ok(()),
+            Ok(ClientMessage::Sync) => {
+                table.sync(id);
+                Ok(())
+            }
+            Ok(ClientMessage::Join { .. }) => Err("already at a table".to_string()),
+            Err(e) => Err(format!("unreadable message: {}", e)),
+        };
+        if let Some(listing) = listing {
+            table.send_to(id, &listing);
+        }
+        if let Err(reason) = outcome {
+            table.send_to(id, &reject(reason));
+        }
+    }
+    lock(table).leave(id);
+}
+
+/// Closes betting and plays the hand, handing the biggest bettor the
+/// squeeze if there are bets down. Returns whether there is one.
+fn deal(table: &Mutex<Table>, game: &mut BaccaratGame) -> Result<bool, String> {
+    let mut table = lock(table);
+    table.closes_at = None;
+    game.play_round_unattended().map_err(|e| e.to_string())?;
+
+    let Some((squeezer, name, side)) = table.biggest_bettor() else {
+        return Ok(false);
+    };
+    let (squeezed, shown) = match side {
+        Side::Player => (&game.player_hand, &game.banker_hand),
+        Side::Banker => (&game.banker_hand, &game.player_hand),
+    };
+    let cards = squeezed[..squeezed.len().min(SQUEEZED_CARDS)].to_vec();
+    let shown = shown[..shown.len().min(SQUEEZED_CARDS)].to_vec();
+    println!(
+        "[{}] {} squeezes the {} cards",
+        table.name,
+        name,
+        table.mode.side_name(side).to_lowercase()
+    );
+    let squeeze = Squeeze {
+        squeezer,
+        name,
+        side,
+        steps: vec![0; cards.len()],
+        cards,
+        shown,
+        closes_at: Instant::now() + SQUEEZE_TIME,
+    };
+    for message in squeeze.replay(table.round) {
+        table.broadcast(&message);
+    }
+    table.squeeze = Some(squeeze);
+    Ok(true)
+}
+
+/// Waits for the squeezer to peel every card, to leave, or to run out of
+/// time.
+fn await_squeeze(table: &Mutex<Table>) {
+    loop {
+        thread::sleep(SQUEEZE_POLL);
+        let table = lock(table);
+        let Some(squeeze) = &table.squeeze else {
+            return;
+        };
+        if squeeze.done()
+            || Instant::now() >= squeeze.closes_at
+            || !table.seats.iter().any(|seat| seat.id == squeeze.squeezer)
+        {
+            return;
+        }
+    }
+}
+
+/// Settles the hand dealt: pays the players and tells everyone.
+fn settle(table: &Mutex<Table>, game: &BaccaratGame) {
+    let mut table = lock(table);
+    table.squeeze = None;
+    let mut results = Vec::new();
+    for seat in &mut table.seats {
+        let Some((bet, stake)) = seat.bet.take() else {
+            continue;
+        };
+        let net = game.calculate_main_bet_payout(bet, stake) - stake;
+        seat.balance += net;
+        results.push(PlayerResult {
+            name: seat.name.clone(),
+            bet: bet.to_string(),
+            stake,
+            net,
+            balance: seat.balance,
+        });
+    }
+    // Bets left down by players who got up play all the same.
+    for (name, departed) in &mut table.departed {
+        let Some((bet, stake)) = departed.bet.take() else {
+            continue;
+        };
+        let net = game.calculate_main_bet_payout(bet, stake) - stake;
+        departed.balance += net;
+        results.push(PlayerResult {
+            name: name.clone(),
+            bet: bet.to_string(),
+            stake,
+            net,
+            balance: departed.balance,
+        });
+    }
+    (table.cards_dealt, table.shoe_size) = game.card_source.penetration().unwrap_or((0, 0));
+    let pair = |cards: &[Card]| matches!(cards, [first, second, ..] if first.rank == second.rank);
+    table.beads.push(Bead {
+        winner: game.state.winner,
+        player_pair: pair(&game.player_hand),
+        banker_pair: pair(&game.banker_hand),
+    });
+    let hand = ServerMessage::Hand {
+        round: table.round,
+        shoe: table.shoe,
+        player_cards: protocol::card_labels(&game.player_hand),
+        banker_cards: protocol::card_labels(&game.banker_hand),
+        player_score: game.state.player_score,
+        banker_score: game.state.banker_score,
+        winner: game.state.winner,
+        results,
+    };
+    println!(
+        "[{}] Round {}: {} {}-{}",
+        table.name,
+        table.round,
+        match game.state.winner {
+            1 => "player wins",
+            2 => "banker wins",
+            _ => "tie",
+        },
+        game.state.player_score,
+        game.state.banker_score
+    );
+    table.broadcast(&hand);
+    table.broadcast_table();
+}
+
+/// Runs one table: a round at a time, each betting window and reveal on
+/// the clock whether or not anyone bets, while anyone is there to see it or
+/// all the time when the table runs continuously.
+fn run_table(table: &Mutex<Table>, mut game: BaccaratGame) -> Result<(), String> {
+    let (window, reveal, continuous) = {
+        let table = lock(table);
+        (
+            Duration::from_secs(table.betting_secs),
+            Duration::from_secs(table.reveal_secs),
+            table.continuous,
+        )
+    };
+    loop {
+        if !continuous && lock(table).seats.is_empty() {
+            thread::sleep(IDLE_POLL);
+            continue;
+        }
+        {
+            let mut table = lock(table);
+            if game.prepare_next_round() {
+                table.shoe += 1;
+                table.beads.clear();
+                (table.cards_dealt, table.shoe_size) =
+                    game.card_source.penetration().unwrap_or((0, 0));
+                let shuffled = ServerMessage::Shuffled { shoe: table.shoe };
+                table.broadcast(&shuffled);
+            }
+            table.round += 1;
+            table.closes_at = Some(Instant::now() + window);
+            let open = table.betting_open();
+            table.broadcast(&open);
+        }
+        thread::sleep(window);
+        if deal(table, &mut game)? {
+            await_squeeze(table);
+        }
+        settle(table, &game);
+        thread::sleep(reveal);
+    }
+}
+
+/// Hosts the tables until the process is stopped; returns only if the
+/// address can't be bound or a table fails.
+pub fn run(options: ServeOptions) -> Result<(), String> {
+    let mut specs = options.tables.clone();
+    if specs.is_empty() {
+        specs.push(TableSpec {
+            name: DEFAULT_TABLE.to_string(),
+            mode: None,
+        });
+    }
+    for (i, spec) in specs.iter().enumerate() {
+        if specs[..i].iter().any(|other| other.name == spec.name) {
+            return Err(format!("two tables called '{}'", spec.name));
+        }
+    }
+    let listener =
+        TcpListener::bind(&options.bind).map_err(|e| format!("{}: {}", options.bind, e))?;
+    let address = listener.local_addr().map_err(|e| e.to_string())?;
+
+    let mut games = Vec::new();
+    let mut tables = Vec::new();
+    for (i, spec) in specs.iter().enumerate() {
+        let mode = spec.mode.unwrap_or(options.mode);
+        // Each table gets its own shoe, from its own seed.
+        let game = match options.seed {
+            Some(seed) => {
+                BaccaratGame::seeded(mode, options.procedure.clone(), seed.wrapping_add(i as u64))
+            }
+            None => BaccaratGame::with_procedure(mode, options.procedure.clone()),
+        };
+        tables.push(Mutex::new(Table {
+            name: spec.name.clone(),
+            mode,
+            decks: options.procedure.decks,
+            balance: options.balance,
+            betting_secs: options.betting_secs,
+            reveal_secs: options.reveal_secs,
+            continuous: options.continuous,
+            min_bet: game.limits.min_bet,
+            max_bet: game.limits.max_bet,
+            seats: Vec::new(),
+            departed: HashMap::new(),
+            next_id: 0,
+            round: 0,
+            shoe: 1,
+            cards_dealt: 0,
+            shoe_size: game.card_source.penetration().map_or(0, |(_, size)| size),
+            closes_at: None,
+            squeeze: None,
+            beads: Vec::new(),
+        }));
+        games.push(game);
+        println!(
+            "Table {}: {:?}, {} decks",
+            spec.name, mode, options.procedure.decks
+        );
+    }
+    let tables = Arc::new(tables);
+    if let Some(bind) = &options.api {
+        let api = ApiOptions {
+            bind: bind.clone(),
+            mode: options.mode,
+            procedure: options.procedure.clone(),
+            balance: options.balance,
+            allow_origin: options.api_origin.clone(),
+        };
+        let listener = api::bind(&api)?;
+        println!(
+            "HTTP API on {}; GET / lists the routes",
+            listener.local_addr().map_err(|e| e.to_string())?
+        );
+        thread::spawn(move || api::run(listener, api));
+    }
+    println!(
+        "Serving on {} (${} to start, {}s to bet, {}s between hands{}); join with: terminal_casino join {}",
+        address,
+        options.balance,
+        options.betting_secs,
+        options.reveal_secs,
+        if options.continuous {
+            ", dealing continuously"
+        } else {
+            ""
+        },
+        address
+    );
+
+    let (failed, failure) = mpsc::channel();
+    for (i, game) in games.into_iter().enumerate() {
+        let tables = Arc::clone(&tables);
+        let failed = failed.clone();
+        thread::spawn(move || {
+            if let Err(e) = run_table(&tables[i], game) {
+                let _ = failed.send(format!("table {}: {}", lock(&tables[i]).name, e));
+            }
+        });
+    }
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let tables = Arc::clone(&tables);
+            thread::spawn(move || serve_client(stream, tables));
+        }
+    });
+    Err(failure
+        .recv()
+        .unwrap_or_else(|_| "every table stopped".to_string()))
+}
@@ -0,0 +1,185 @@
+//! Session records: one summary per player per table session, appended to
+//! `profiles/<name>/sessions.toml` when the table closes.
+
+use crate::baccarat::Money;
+use crate::integrity::{self, Integrity};
+use crate::settlement::SettlementReport;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub player: String,
+    /// Unix timestamps of the table opening and closing.
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub starting_balance: Money,
+    /// Balance after every round played.
+    pub bankroll: Vec<Money>,
+    pub wagered: Money,
+    pub returned: Money,
+    /// Rounds each bet was placed, keyed by bet name.
+    pub bets: BTreeMap<String, u32>,
+    pub longest_win_streak: u32,
+    pub longest_loss_streak: u32,
+}
+
+impl SessionRecord {
+    pub fn rounds(&self) -> usize {
+        self.bankroll.len()
+    }
+
+    pub fn net(&self) -> Money {
+        self.returned - self.wagered
+    }
+
+    /// The balance before the first round, then after every round.
+    pub fn balances(&self) -> impl Iterator<Item = Money> + '_ {
+        std::iter::once(self.starting_balance).chain(self.bankroll.iter().copied())
+    }
+
+    /// The deepest fall from a high point to a later balance.
+    pub fn max_drawdown(&self) -> Money {
+        let mut peak = self.starting_balance;
+        let mut deepest = Money::ZERO;
+        for balance in self.balances() {
+            peak = peak.max(balance);
+            deepest = deepest.max(peak - balance);
+        }
+        deepest
+    }
+}
+
+/// Builds a seat's session record round by round.
+#[derive(Debug, Clone)]
+pub struct SessionRecorder {
+    record: SessionRecord,
+    win_streak: u32,
+    loss_streak: u32,
+}
+
+impl SessionRecorder {
+    pub fn new(player: &str, balance: Money) -> Self {
+        Self {
+            record: SessionRecord {
+                player: player.to_string(),
+                started_at: now(),
+                finished_at: 0,
+                starting_balance: balance,
+                bankroll: Vec::new(),
+                wagered: Money::ZERO,
+                returned: Money::ZERO,
+                bets: BTreeMap::new(),
+                longest_win_streak: 0,
+                longest_loss_streak: 0,
+            },
+            win_streak: 0,
+            loss_streak: 0,
+        }
+    }
+
+    /// Records one settled round. Pushes leave both streaks as they were.
+    pub fn record_round(&mut self, report: &SettlementReport, balance: Money) {
+        let (wagered, returned) = (report.total_bet(), report.total_payout());
+        let record = &mut self.record;
+        record.bankroll.push(balance);
+        record.wagered += wagered;
+        record.returned += returned;
+        for bet in &report.bets {
+            *record.bets.entry(bet.wager.to_string()).or_default() += 1;
+        }
+        match returned.cmp(&wagered) {
+            std::cmp::Ordering::Greater => {
+                self.win_streak += 1;
+                self.loss_streak = 0;
+            }
+            std::cmp::Ordering::Less => {
+                self.loss_streak += 1;
+                self.win_streak = 0;
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+        record.longest_win_streak = record.longest_win_streak.max(self.win_streak);
+        record.longest_loss_streak = record.longest_loss_streak.max(self.loss_streak);
+    }
+
+    /// The session so far.
+    pub fn record(&self) -> &SessionRecord {
+        &self.record
+    }
+
+    /// The finished record, or `None` if no rounds were played.
+    pub fn finish(&self) -> Option<SessionRecord> {
+        if self.record.bankroll.is_empty() {
+            return None;
+        }
+        Some(SessionRecord {
+            finished_at: now(),
+            ..self.record.clone()
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SessionEntry<'a> {
+    session: [&'a SessionRecord; 1],
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SessionFile {
+    #[serde(default)]
+    session: Vec<SessionRecord>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionLog {
+    profile: String,
+}
+
+impl SessionLog {
+    pub fn new(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        storage::profile_dir(&self.profile).join("sessions.toml")
+    }
+
+    /// Appends one `[[session]]` table, so the file stays valid TOML and is
+    /// never rewritten, and re-signs it.
+    pub fn append(&self, record: &SessionRecord) -> io::Result<()> {
+        let entry =
+            toml::to_string(&SessionEntry { session: [record] }).map_err(io::Error::other)?;
+        integrity::append_signed(&self.path(), &entry)
+    }
+
+    pub fn integrity(&self) -> Integrity {
+        integrity::verify(&self.path())
+    }
+
+    /// Every recorded session, oldest first; a profile that has never
+    /// played has none.
+    pub fn load(&self) -> Result<Vec<SessionRecord>, String> {
+        let path = self.path();
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let file: SessionFile =
+            toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(file.session)
+    }
+}
@@ -0,0 +1,136 @@
+//! Settlement shared by every table: which bet a line is for, how it came
+//! out, and the report a finished hand, spin or roll pays out as.
+
+use crate::baccarat::{BetType, BonusBetType, Money};
+use crate::poker::PokerBet;
+use crate::roulette;
+use crate::sicbo;
+
+/// How a settled bet came out: paid more than its stake, returned exactly
+/// the stake, or returned less. A main bet barged by a tie is returned like
+/// a push but `Rides` on the next hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BetOutcome {
+    Win,
+    Lose,
+    Push,
+    Rides,
+}
+
+impl std::fmt::Display for BetOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            BetOutcome::Win => "won",
+            BetOutcome::Lose => "lost",
+            BetOutcome::Push => "push",
+            BetOutcome::Rides => "rides",
+        })
+    }
+}
+
+/// Which bet on the slip a settlement line is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wager {
+    Main(BetType),
+    Side(BonusBetType),
+    /// A tie at this score.
+    TieScore(u8),
+    /// A bet on the roulette layout.
+    Roulette(roulette::Bet),
+    /// A bet on the Sic Bo grid.
+    SicBo(sicbo::Bet),
+    /// A bet at Caribbean Stud or Three Card Poker.
+    Poker(PokerBet),
+}
+
+impl std::fmt::Display for Wager {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Wager::Main(bet) => bet.fmt(f),
+            Wager::Side(bet) => bet.fmt(f),
+            Wager::TieScore(score) => write!(f, "tie_on_{}", score),
+            Wager::Roulette(bet) => bet.fmt(f),
+            Wager::SicBo(bet) => bet.fmt(f),
+            Wager::Poker(bet) => bet.fmt(f),
+        }
+    }
+}
+
+/// One bet from the slip, settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SettledBet {
+    pub wager: Wager,
+    pub stake: Money,
+    /// Amount returned, stake included.
+    pub payout: Money,
+    pub outcome: BetOutcome,
+}
+
+impl SettledBet {
+    /// `payout` includes the stake; the outcome follows from comparing them.
+    pub fn new(wager: Wager, stake: Money, payout: Money) -> Self {
+        let outcome = match payout.cmp(&stake) {
+            std::cmp::Ordering::Greater => BetOutcome::Win,
+            std::cmp::Ordering::Equal => BetOutcome::Push,
+            std::cmp::Ordering::Less => BetOutcome::Lose,
+        };
+        Self {
+            wager,
+            stake,
+            payout,
+            outcome,
+        }
+    }
+
+    pub fn net(&self) -> Money {
+        self.payout - self.stake
+    }
+
+    /// "banker $10 won +9.50", "tie $5 lost -5", "player $10 push".
+    pub fn describe(&self) -> String {
+        match self.outcome {
+            BetOutcome::Push | BetOutcome::Rides => {
+                format!("{} ${} {}", self.wager, self.stake, self.outcome)
+            }
+            outcome => format!(
+                "{} ${} {} {:+}",
+                self.wager,
+                self.stake,
+                outcome,
+                self.net()
+            ),
+        }
+    }
+}
+
+/// A finished hand paid out bet by bet: the main bets first, then each side
+/// bet that was placed. A roulette spin or Sic Bo roll settles into one too.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct SettlementReport {
+    pub bets: Vec<SettledBet>,
+}
+
+impl SettlementReport {
+    pub fn total_bet(&self) -> Money {
+        self.bets.iter().map(|bet| bet.stake).sum()
+    }
+
+    pub fn total_payout(&self) -> Money {
+        self.bets.iter().map(|bet| bet.payout).sum()
+    }
+
+    pub fn net(&self) -> Money {
+        self.total_payout() - self.total_bet()
+    }
+
+    /// Amount returned on the side bets alone.
+    pub fn side_bet_payout(&self) -> Money {
+        self.bets
+            .iter()
+            .filter(|bet| matches!(bet.wager, Wager::Side(_) | Wager::TieScore(_)))
+            .map(|bet| bet.payout)
+            .sum()
+    }
+}
@@ -0,0 +1,323 @@
+//! `import` subcommand: replays a recorded shoe (a CSV of cards from a real
+//! table or another simulator) through the engine, reporting what every bet
+//! would have paid and drawing the resulting roads. As JSON, each round is
+//! a line of its own and the bets' totals a last line.
+
+use crate::baccarat::{
+    BaccaratGame, BonusBetType, BonusBets, CLUBS, Card, DIAMONDS, GameMode, HEARTS, Hand, Money,
+    SPADES,
+};
+use crate::card_renderer::CardRenderer;
+use crate::history::{json_string, outcome_name};
+use crate::roads::{bead_plate, big_road, winner_letter};
+use std::io::{self, Write};
+use std::path::Path;
+
+const UNIT_BET: Money = Money::from_dollars(10);
+
+/// Parses one card token: a rank (A, 2-10, T, J, Q, K) optionally followed by
+/// a suit letter or symbol. Cards without a suit are treated as spades.
+pub fn parse_card(token: &str) -> Result<Card, String> {
+    let token = token.trim();
+    let split = token
+        .char_indices()
+        .find(|(_, c)| {
+            !c.is_ascii_alphanumeric() || matches!(c.to_ascii_uppercase(), 'H' | 'D' | 'C' | 'S')
+        })
+        .map_or(token.len(), |(i, _)| i);
+    let (rank_str, suit_str) = token.split_at(split);
+    let rank = match rank_str.to_ascii_uppercase().as_str() {
+        "A" | "1" => 1,
+        "T" | "10" => 10,
+        "J" => 11,
+        "Q" => 12,
+        "K" => 13,
+        n => match n.parse::<u8>() {
+            Ok(n @ 2..=9) => n,
+            _ => return Err(format!("invalid card '{}'", token)),
+        },
+    };
+    let suit = match suit_str.to_ascii_uppercase().as_str() {
+        "H" | "♥" => HEARTS,
+        "D" | "♦" => DIAMONDS,
+        "C" | "♣" => CLUBS,
+        "S" | "♠" | "" => SPADES,
+        _ => return Err(format!("invalid suit in card '{}'", token)),
+    };
+    Ok(Card::new(suit, rank))
+}
+
+/// Reads cards in dealing order. Fields may be separated by commas,
+/// semicolons, or whitespace; `#` starts a comment, and a first line that
+/// does not parse as cards is taken to be a header.
+pub fn parse_shoe_csv(source: &str) -> Result<Vec<Card>, String> {
+    let mut cards = Vec::new();
+    let mut seen_data = false;
+    for (line_no, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line
+            .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+            .collect();
+        let parsed: Result<Vec<Card>, String> = tokens.iter().map(|t| parse_card(t)).collect();
+        match parsed {
+            Ok(line_cards) => cards.extend(line_cards),
+            Err(_) if !seen_data => {} // header row
+            Err(e) => return Err(format!("line {}: {}", line_no + 1, e)),
+        }
+        seen_data = true;
+    }
+    if cards.is_empty() {
+        return Err("no cards found".to_string());
+    }
+    Ok(cards)
+}
+
+pub struct ReplayedRound {
+    pub player_hand: Hand,
+    pub banker_hand: Hand,
+    pub player_score: u8,
+    pub banker_score: u8,
+    pub winner: u8,
+    /// Amount returned per bet name for a `UNIT_BET` stake.
+    pub returns: Vec<(&'static str, Money)>,
+}
+
+/// Plays every complete round the card sequence allows, once every card
+/// is checked to be one a `mode` shoe holds.
+pub fn replay(cards: Vec<Card>, mode: GameMode) -> Result<Vec<ReplayedRound>, String> {
+    if let Some((i, card)) = cards
+        .iter()
+        .enumerate()
+        .find(|(_, card)| !card.is_valid() || card.rank > mode.top_rank())
+    {
+        let name = if card.is_valid() {
+            card_text(card)
+        } else {
+            format!("rank {} suit {}", card.rank, card.suit)
+        };
+        return Err(format!(
+            "card {} ({}) is not in a {:?} shoe",
+            i + 1,
+            name,
+            mode
+        ));
+    }
+    let mut game = BaccaratGame::with_card_sequence(mode, cards);
+    let mut rounds = Vec::new();
+    loop {
+        game.prepare_next_round();
+        // The last hand stops short when the cards run out mid-hand.
+        if game.play_round_unattended().is_err() {
+            break;
+        }
+
+        let mut returns: Vec<(&'static str, Money)> = mode
+            .main_bets()
+            .iter()
+            .map(|&bet| (bet.name(), game.calculate_main_bet_payout(bet, UNIT_BET)))
+            .collect();
+        for name in BonusBetType::ALL {
+            let mut bets = BonusBets::new();
+            bets.set(name, UNIT_BET);
+            returns.push((name.name(), bets.calculate_payouts(&game)));
+        }
+
+        rounds.push(ReplayedRound {
+            player_hand: game.player_hand.clone(),
+            banker_hand: game.banker_hand.clone(),
+            player_score: game.state.player_score,
+            banker_score: game.state.banker_score,
+            winner: game.state.winner,
+            returns,
+        });
+    }
+    Ok(rounds)
+}
+
+fn card_text(card: &Card) -> String {
+    let rank = match card.rank {
+        1 => "A".to_string(),
+        11 => "J".to_string(),
+        12 => "Q".to_string(),
+        13 => "K".to_string(),
+        n => n.to_string(),
+    };
+    let suit = match card.suit {
+        HEARTS => "♥",
+        DIAMONDS => "♦",
+        CLUBS => "♣",
+        _ => "♠",
+    };
+    format!("{}{}", rank, suit)
+}
+
+/// What one bet took and returned over the shoe, at `UNIT_BET` a round.
+struct BetTotal {
+    name: &'static str,
+    paid: usize,
+    staked: Money,
+    returned: Money,
+}
+
+fn bet_totals(rounds: &[ReplayedRound]) -> Vec<BetTotal> {
+    let Some(first) = rounds.first() else {
+        return Vec::new();
+    };
+    first
+        .returns
+        .iter()
+        .enumerate()
+        .map(|(index, &(name, _))| BetTotal {
+            name,
+            paid: rounds
+                .iter()
+                .filter(|r| r.returns[index].1 > Money::ZERO)
+                .count(),
+            staked: UNIT_BET * rounds.len() as i64,
+            returned: rounds.iter().map(|r| r.returns[index].1).sum(),
+        })
+        .collect()
+}
+
+fn write_json(
+    out: &mut impl Write,
+    rounds: &[ReplayedRound],
+    card_count: usize,
+    mode: GameMode,
+) -> io::Result<()> {
+    let cards = |cards: &[Card]| {
+        let cards: Vec<String> = cards
+            .iter()
+            .map(|card| json_string(&CardRenderer::label(card)))
+            .collect();
+        format!("[{}]", cards.join(", "))
+    };
+    for (i, round) in rounds.iter().enumerate() {
+        let returns: Vec<String> = round
+            .returns
+            .iter()
+            .map(|(name, returned)| format!("{}: {:.2}", json_string(name), returned.as_dollars()))
+            .collect();
+        writeln!(
+            out,
+            "{{\"hand\": {}, \"player_cards\": {}, \"banker_cards\": {}, \"player_score\": {}, \"banker_score\": {}, \"outcome\": {}, \"returns\": {{{}}}}}",
+            i + 1,
+            cards(&round.player_hand),
+            cards(&round.banker_hand),
+            round.player_score,
+            round.banker_score,
+            json_string(outcome_name(round.winner)),
+            returns.join(", ")
+        )?;
+    }
+    let bets: Vec<String> = bet_totals(rounds)
+        .iter()
+        .map(|total| {
+            format!(
+                "{{\"bet\": {}, \"paid\": {}, \"staked\": {:.2}, \"returned\": {:.2}, \"net\": {:.2}, \"rtp\": {:.6}}}",
+                json_string(total.name),
+                total.paid,
+                total.staked.as_dollars(),
+                total.returned.as_dollars(),
+                (total.returned - total.staked).as_dollars(),
+                total.returned.as_dollars() / total.staked.as_dollars()
+            )
+        })
+        .collect();
+    writeln!(
+        out,
+        "{{\"summary\": {{\"rounds\": {}, \"cards\": {}, \"mode\": {}, \"unit\": {:.2}, \"bets\": [{}]}}}}",
+        rounds.len(),
+        card_count,
+        json_string(&format!("{:?}", mode)),
+        UNIT_BET.as_dollars(),
+        bets.join(", ")
+    )?;
+    Ok(())
+}
+
+/// Loads and replays a recorded shoe, then writes the report, or the rounds
+/// and totals as JSON, to `out`.
+pub fn run(out: &mut impl Write, path: &Path, mode: GameMode, json: bool) -> Result<(), String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let cards = parse_shoe_csv(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let card_count = cards.len();
+    let rounds = replay(cards, mode).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if json {
+        write_json(out, &rounds, card_count, mode)
+    } else {
+        write_report(out, &rounds, card_count, mode)
+    }
+    .map_err(|e| e.to_string())
+}
+
+fn write_report(
+    out: &mut impl Write,
+    rounds: &[ReplayedRound],
+    card_count: usize,
+    mode: GameMode,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "Replayed {} rounds from {} cards ({:?} mode, ${} per bet)",
+        rounds.len(),
+        card_count,
+        mode,
+        UNIT_BET
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{:>4}  {:<16} {:<16} {:>5}  Result",
+        "#", "Player", "Banker", "Score"
+    )?;
+    for (i, round) in rounds.iter().enumerate() {
+        let hand = |cards: &[Card]| cards.iter().map(card_text).collect::<Vec<_>>().join(" ");
+        writeln!(
+            out,
+            "{:>4}  {:<16} {:<16} {:>2}-{:<2}  {}",
+            i + 1,
+            hand(&round.player_hand),
+            hand(&round.banker_hand),
+            round.player_score,
+            round.banker_score,
+            winner_letter(round.winner)
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{:<14} {:>6} {:>9} {:>9} {:>9} {:>8}",
+        "Bet", "Paid", "Staked", "Returned", "Net", "RTP"
+    )?;
+    for total in bet_totals(rounds) {
+        writeln!(
+            out,
+            "{:<14} {:>6} {:>9} {:>9} {:>+9} {:>7.1}%",
+            total.name,
+            total.paid,
+            total.staked,
+            total.returned,
+            total.returned - total.staked,
+            total.returned.as_dollars() / total.staked.as_dollars() * 100.0
+        )?;
+    }
+
+    let winners: Vec<u8> = rounds.iter().map(|r| r.winner).collect();
+    writeln!(out)?;
+    writeln!(out, "Bead plate:")?;
+    for row in bead_plate(&winners) {
+        writeln!(out, "  {}", row)?;
+    }
+    writeln!(out)?;
+    writeln!(out, "Big road (lowercase = followed by a tie):")?;
+    for row in big_road(&winners) {
+        writeln!(out, "  {}", row)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,287 @@
+//! Sic Bo: three dice and a grid of bets on them, paid on the Macau table.
+//! Small (4-10) and big (11-17) pay even money but lose to any triple;
+//! totals pay from 6:1 on 9 to 12 up to 60:1 on 4 and 17; a specific double
+//! pays 10:1, a specific triple 180:1 and any triple 30:1; a two-number
+//! combination pays 5:1; a single number pays once for each die showing
+//! it. A roll settles into the same `SettlementReport` as a baccarat hand.
+
+use crate::baccarat::{Money, ShuffleRng, TableLimits};
+use crate::settlement::{SettledBet, SettlementReport, Wager};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Three dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dice(pub [u8; 3]);
+
+impl Dice {
+    /// Three dice thrown with `rng`.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Dice([
+            rng.random_range(1..=6),
+            rng.random_range(1..=6),
+            rng.random_range(1..=6),
+        ])
+    }
+
+    pub fn total(&self) -> u8 {
+        self.0.iter().sum()
+    }
+
+    /// Dice showing `face`.
+    pub fn count(&self, face: u8) -> usize {
+        self.0.iter().filter(|&&die| die == face).count()
+    }
+
+    pub fn is_triple(&self) -> bool {
+        self.0[0] == self.0[1] && self.0[1] == self.0[2]
+    }
+
+    /// Every roll of three dice, each equally likely.
+    pub fn all() -> impl Iterator<Item = Dice> {
+        (1..=6).flat_map(|a| (1..=6).flat_map(move |b| (1..=6).map(move |c| Dice([a, b, c]))))
+    }
+}
+
+/// A spot on the grid.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Bet {
+    /// Totals 4 to 10, barring triples.
+    Small,
+    /// Totals 11 to 17, barring triples.
+    Big,
+    /// The dice add up to this, 4 to 17.
+    Total(u8),
+    /// At least two dice show this face.
+    Double(u8),
+    /// All three dice show this face.
+    Triple(u8),
+    AnyTriple,
+    /// Both faces show, the lower first.
+    Combination(u8, u8),
+    /// Pays once for each die showing this face.
+    Single(u8),
+}
+
+impl Bet {
+    /// The grid row by row, as the table lays it out.
+    pub fn grid() -> Vec<Vec<Bet>> {
+        let faces = || 1..=6u8;
+        let combinations: Vec<Bet> = faces()
+            .flat_map(|a| (a + 1..=6).map(move |b| Bet::Combination(a, b)))
+            .collect();
+        vec![
+            vec![Bet::Small, Bet::AnyTriple, Bet::Big],
+            faces().map(Bet::Double).collect(),
+            faces().map(Bet::Triple).collect(),
+            (4..=10).map(Bet::Total).collect(),
+            (11..=17).map(Bet::Total).collect(),
+            combinations[..9].to_vec(),
+            combinations[9..].to_vec(),
+            faces().map(Bet::Single).collect(),
+        ]
+    }
+
+    /// What the total pays per unit.
+    fn total_pays(total: u8) -> i64 {
+        match total {
+            4 | 17 => 60,
+            5 | 16 => 30,
+            6 | 15 => 17,
+            7 | 14 => 12,
+            8 | 13 => 8,
+            _ => 6,
+        }
+    }
+
+    /// Winnings per unit staked on `dice`, or `None` when the bet loses.
+    pub fn pays(&self, dice: Dice) -> Option<i64> {
+        let total = dice.total();
+        match *self {
+            Bet::Small => (!dice.is_triple() && total <= 10).then_some(1),
+            Bet::Big => (!dice.is_triple() && total >= 11).then_some(1),
+            Bet::Total(wanted) => (total == wanted).then(|| Self::total_pays(wanted)),
+            Bet::Double(face) => (dice.count(face) >= 2).then_some(10),
+            Bet::Triple(face) => (dice.count(face) == 3).then_some(180),
+            Bet::AnyTriple => dice.is_triple().then_some(30),
+            Bet::Combination(a, b) => (dice.count(a) > 0 && dice.count(b) > 0).then_some(5),
+            Bet::Single(face) => match dice.count(face) {
+                0 => None,
+                count => Some(count as i64),
+            },
+        }
+    }
+
+    /// A short label for the pay table, such as "6:1" or "1-3:1" for a
+    /// single number.
+    pub fn odds(&self) -> String {
+        match *self {
+            Bet::Small | Bet::Big => "1:1".to_string(),
+            Bet::Total(total) => format!("{}:1", Self::total_pays(total)),
+            Bet::Double(_) => "10:1".to_string(),
+            Bet::Triple(_) => "180:1".to_string(),
+            Bet::AnyTriple => "30:1".to_string(),
+            Bet::Combination(..) => "5:1".to_string(),
+            Bet::Single(_) => "1-3:1".to_string(),
+        }
+    }
+
+    /// The house's share of each unit staked, worked out over all 216 rolls.
+    pub fn house_edge(&self) -> f64 {
+        let returned: i64 = Dice::all()
+            .map(|dice| self.pays(dice).map_or(0, |pays| pays + 1))
+            .sum();
+        1.0 - returned as f64 / 216.0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let face = |n: u8| (1..=6).contains(&n);
+        match *self {
+            Bet::Small | Bet::Big | Bet::AnyTriple => true,
+            Bet::Total(total) => (4..=17).contains(&total),
+            Bet::Double(n) | Bet::Triple(n) | Bet::Single(n) => face(n),
+            Bet::Combination(a, b) => face(a) && face(b) && a < b,
+        }
+    }
+}
+
+impl std::fmt::Display for Bet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Bet::Small => f.write_str("small"),
+            Bet::Big => f.write_str("big"),
+            Bet::Total(total) => write!(f, "total {}", total),
+            Bet::Double(face) => write!(f, "double {}", face),
+            Bet::Triple(face) => write!(f, "triple {}", face),
+            Bet::AnyTriple => f.write_str("any triple"),
+            Bet::Combination(a, b) => write!(f, "{}-{}", a, b),
+            Bet::Single(face) => write!(f, "single {}", face),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SicBoError {
+    NotOnGrid(Bet),
+    BelowMin(Money),
+    AboveMax(Money),
+    /// All bets on the roll together above the table maximum.
+    OverTableMax(Money),
+    NoBets,
+}
+
+impl std::fmt::Display for SicBoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SicBoError::NotOnGrid(bet) => write!(f, "{} is not on the grid", bet),
+            SicBoError::BelowMin(min) => write!(f, "the table minimum is ${}", min),
+            SicBoError::AboveMax(max) => write!(f, "the table maximum is ${}", max),
+            SicBoError::OverTableMax(max) => write!(f, "bets on one roll are limited to ${}", max),
+            SicBoError::NoBets => write!(f, "no bets on the grid"),
+        }
+    }
+}
+
+impl std::error::Error for SicBoError {}
+
+/// A Sic Bo table: the bets on the grid and the rolls so far.
+pub struct SicBoGame {
+    limits: TableLimits,
+    bets: BTreeMap<Bet, Money>,
+    /// Rolls since the table opened, oldest first.
+    pub history: Vec<Dice>,
+    /// Where the dice come from; the thread's generator when unset.
+    rng: Option<ShuffleRng>,
+}
+
+impl SicBoGame {
+    pub fn new(limits: TableLimits) -> Self {
+        Self {
+            limits,
+            bets: BTreeMap::new(),
+            history: Vec::new(),
+            rng: None,
+        }
+    }
+
+    /// Throws the dice with `rng` from now on; a seeded one rolls the same
+    /// dice every time.
+    pub fn set_rng(&mut self, rng: ShuffleRng) {
+        self.rng = Some(rng);
+    }
+
+    pub fn bets(&self) -> impl Iterator<Item = (Bet, Money)> + '_ {
+        self.bets.iter().map(|(&bet, &stake)| (bet, stake))
+    }
+
+    pub fn stake(&self, bet: Bet) -> Money {
+        self.bets.get(&bet).copied().unwrap_or_default()
+    }
+
+    pub fn total_bet(&self) -> Money {
+        self.bets.values().copied().sum()
+    }
+
+    /// Adds `stake` to `bet`, checking the table limits.
+    pub fn place(&mut self, bet: Bet, stake: Money) -> Result<(), SicBoError> {
+        if !bet.is_valid() {
+            return Err(SicBoError::NotOnGrid(bet));
+        }
+        let dollars = Money::from_dollars;
+        let total = self.stake(bet) + stake;
+        if total < dollars(self.limits.min_bet) {
+            return Err(SicBoError::BelowMin(dollars(self.limits.min_bet)));
+        }
+        if total > dollars(self.limits.max_bet) {
+            return Err(SicBoError::AboveMax(dollars(self.limits.max_bet)));
+        }
+        if self.total_bet() + stake > dollars(self.limits.max_total) {
+            return Err(SicBoError::OverTableMax(dollars(self.limits.max_total)));
+        }
+        self.bets.insert(bet, total);
+        Ok(())
+    }
+
+    /// Takes `bet` down and returns its stake.
+    pub fn take_down(&mut self, bet: Bet) -> Money {
+        self.bets.remove(&bet).unwrap_or_default()
+    }
+
+    /// Takes every bet down and returns what was on the grid.
+    pub fn clear(&mut self) -> Money {
+        let total = self.total_bet();
+        self.bets.clear();
+        total
+    }
+
+    /// Rolls the dice and settles the grid.
+    pub fn roll(&mut self) -> Result<(Dice, SettlementReport), SicBoError> {
+        if self.bets.is_empty() {
+            return Err(SicBoError::NoBets);
+        }
+        let dice = match &mut self.rng {
+            Some(rng) => Dice::random(rng),
+            None => Dice::random(&mut rand::rng()),
+        };
+        Ok((dice, self.settle(dice)))
+    }
+
+    /// Settles every bet against `dice` and clears the grid. Payouts
+    /// include the stake.
+    pub fn settle(&mut self, dice: Dice) -> SettlementReport {
+        self.history.push(dice);
+        let bets = std::mem::take(&mut self.bets)
+            .into_iter()
+            .map(|(bet, stake)| {
+                let payout = bet
+                    .pays(dice)
+                    .map_or(Money::ZERO, |pays| stake * (pays + 1));
+                SettledBet::new(Wager::SicBo(bet), stake, payout)
+            })
+            .collect();
+        SettlementReport { bets }
+    }
+}
@@ -0,0 +1,113 @@
+//! Significance checks on a session: observed outcome and side-bet
+//! frequencies tested against the theoretical rates, so a "hot table" can be
+//! told apart from noise.
+
+use crate::analytics::HitRates;
+use crate::baccarat::{BaccaratGame, BonusBetType, BonusBets, Money};
+use crate::stats::{binomial_p_value, chi_square, chi_square_p_value};
+
+/// p-values below this are flagged as unusual.
+pub const ALPHA: f64 = 0.05;
+
+const OUTCOME_NAMES: [&str; 3] = ["player", "banker", "tie"];
+
+/// Counts of every outcome and of every side bet that would have paid, over
+/// all hands dealt whether or not anyone bet on them.
+#[derive(Debug, Clone, Default)]
+pub struct SessionTally {
+    hands: u32,
+    outcomes: [u32; 3],
+    side_bet_hits: [u32; BonusBetType::ALL.len()],
+}
+
+impl SessionTally {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, game: &BaccaratGame) {
+        self.hands += 1;
+        if let winner @ 1..=3 = game.state.winner {
+            self.outcomes[winner as usize - 1] += 1;
+        }
+        for (name, hits) in BonusBetType::ALL
+            .into_iter()
+            .zip(self.side_bet_hits.iter_mut())
+        {
+            let mut bets = BonusBets::new();
+            bets.set(name, Money::from_dollars(1));
+            if bets.calculate_payouts(game) > Money::ZERO {
+                *hits += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignificanceTest {
+    pub name: &'static str,
+    /// Observed and expected share of hands.
+    pub observed: f64,
+    pub expected: f64,
+    pub p_value: f64,
+}
+
+impl SignificanceTest {
+    pub fn is_significant(&self) -> bool {
+        self.p_value < ALPHA
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        if self.is_significant() {
+            "unusual"
+        } else {
+            "noise"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignificanceReport {
+    pub hands: u32,
+    /// Chi-square test of the whole player/banker/tie split.
+    pub split_p_value: f64,
+    /// Binomial test for each outcome and side bet on its own.
+    pub tests: Vec<SignificanceTest>,
+}
+
+pub fn analyze(tally: &SessionTally, rates: &HitRates) -> SignificanceReport {
+    let hands = tally.hands.max(1) as f64;
+    let share = |count: u32| count as f64 / hands;
+
+    let observed: Vec<f64> = tally.outcomes.iter().map(|&count| count as f64).collect();
+    let expected: Vec<f64> = rates
+        .outcomes
+        .iter()
+        .map(|rate| rate * tally.hands as f64)
+        .collect();
+    let split_p_value = chi_square_p_value(chi_square(&observed, &expected), 2.0);
+
+    let outcomes = OUTCOME_NAMES
+        .iter()
+        .zip(tally.outcomes)
+        .zip(rates.outcomes)
+        .map(|((name, count), rate)| (*name, count, rate));
+    let side_bets = BonusBetType::ALL
+        .into_iter()
+        .zip(tally.side_bet_hits)
+        .filter_map(|(name, hits)| Some((name.name(), hits, rates.side_bet(name)?)));
+    let tests = outcomes
+        .chain(side_bets)
+        .map(|(name, hits, rate)| SignificanceTest {
+            name,
+            observed: share(hits),
+            expected: rate,
+            p_value: binomial_p_value(hits, tally.hands, rate),
+        })
+        .collect();
+    SignificanceReport {
+        hands: tally.hands,
+        split_p_value,
+        tests,
+    }
+}
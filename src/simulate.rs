@@ -0,0 +1,545 @@
+//! `simulate` subcommand: deals hands as fast as the engine goes with no
+//! one watching, betting one main bet by a strategy (flat unless told
+//! otherwise) from a bankroll, and reports how the bets and the shoe came
+//! out: the outcome split, the house edge with its margin of error, and the
+//! spread of the results. Without a bankroll the hands are split across
+//! threads, each dealing its own shoes and running its own strategy. As
+//! JSON, every hand is a line of its own as the hand history exports it,
+//! followed by a line with the report. As binary, every hand is three
+//! frames of the `wire` format and there is no report.
+
+use crate::baccarat::{BaccaratGame, BetType, GameMode, Money, ProcedureProfile, Side};
+use crate::history::{HandRecord, SeatBets, json_string};
+use crate::settlement::{SettledBet, Wager};
+use crate::strategy::{Decision, StrategyKind};
+use crate::wire;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Instant;
+
+/// Standard errors either side of the measured edge for 95% confidence.
+const Z_95: f64 = 1.96;
+/// Spaces the seeds of the threads' and sessions' generators apart (the
+/// 64-bit golden ratio), so no two of them shuffle alike.
+const STREAM_STEP: u64 = 0x9E37_79B9_7F4A_7C15;
+/// Results listed per hand; a progression's rarer ones are added up as
+/// "other".
+const RESULT_COLUMNS: usize = 12;
+
+/// What to deal and how to bet it.
+pub struct Simulation {
+    pub mode: GameMode,
+    pub procedure: ProcedureProfile,
+    pub bet: BetType,
+    /// Stake on every hand, or the strategy's base stake.
+    pub unit: Money,
+    pub strategy: StrategyKind,
+    /// Bankroll to bet from; the run stops once it can't cover the stake.
+    /// `None` bets without one.
+    pub bankroll: Option<Money>,
+    pub hands: u32,
+    /// Shuffles every shoe from this seed, so a run can be repeated with
+    /// the same number of threads.
+    pub seed: Option<u64>,
+    /// Threads to deal on. A bankroll is bet hand after hand, and JSON and
+    /// binary list the hands in order, so a run with any of them uses a
+    /// single thread.
+    pub threads: usize,
+    /// Report as JSON instead of text.
+    pub json: bool,
+    /// Stream the hands as binary frames (see `wire`) and report nothing.
+    pub binary: bool,
+}
+
+impl Simulation {
+    /// The main bet the strategy backs.
+    pub fn main_bet(&self) -> BetType {
+        match self.strategy {
+            StrategyKind::BankerOnly => BetType::Banker,
+            _ => self.bet,
+        }
+    }
+
+    /// How the shoes are shuffled, as the hand history records it.
+    fn shuffle(&self) -> String {
+        self.seed
+            .map_or("thread".to_string(), |seed| format!("seed {}", seed))
+    }
+
+    /// How the hands are bet, for the report's first line.
+    pub fn betting(&self) -> String {
+        match &self.strategy {
+            strategy if strategy.is_flat() => {
+                format!("${} on {} each hand", self.unit, self.main_bet())
+            }
+            strategy => format!("{} on {} from ${}", strategy.name(), self.bet, self.unit),
+        }
+    }
+}
+
+/// What a run, or one thread's share of it, dealt and paid.
+#[derive(Debug, Default)]
+pub struct Tally {
+    pub dealt: u32,
+    wagered: Money,
+    returned: Money,
+    outcomes: [u32; 3],
+    /// How often each net result per hand came up.
+    results: BTreeMap<Money, u32>,
+    largest_stake: Money,
+    /// The bankroll at the end, at its lowest, and at its highest.
+    pub bankroll: Option<Money>,
+    lowest: Option<Money>,
+    highest: Option<Money>,
+    /// The most the bankroll fell from a high before it recovered.
+    pub drawdown: Money,
+}
+
+impl Tally {
+    fn merge(&mut self, other: Tally) {
+        self.dealt += other.dealt;
+        self.wagered += other.wagered;
+        self.returned += other.returned;
+        for (total, count) in self.outcomes.iter_mut().zip(other.outcomes) {
+            *total += count;
+        }
+        for (net, count) in other.results {
+            *self.results.entry(net).or_default() += count;
+        }
+        self.largest_stake = self.largest_stake.max(other.largest_stake);
+    }
+}
+
+/// A hand `deal` has just settled.
+pub struct DealtHand<'a> {
+    /// From 1, and the shoe it came from.
+    pub number: u32,
+    pub shoe: u32,
+    pub game: &'a BaccaratGame,
+    pub decision: Decision,
+    pub payout: Money,
+}
+
+/// Something shown each hand as it is dealt.
+pub type Watch<'a> = &'a mut dyn FnMut(&DealtHand) -> Result<(), String>;
+
+/// Deals `hands` hands on a table of its own, its shoes shuffled from
+/// `seed` if given, stopping early if the bankroll can't cover the stake
+/// the strategy asks for. `watch` sees every hand.
+pub fn deal(
+    simulation: &Simulation,
+    hands: u32,
+    seed: Option<u64>,
+    mut watch: Option<Watch>,
+) -> Result<Tally, String> {
+    let procedure = simulation.procedure.clone();
+    let mut game = match seed {
+        Some(seed) => BaccaratGame::seeded(simulation.mode, procedure, seed),
+        None => BaccaratGame::with_procedure(simulation.mode, procedure),
+    };
+    let mut tally = Tally {
+        bankroll: simulation.bankroll,
+        lowest: simulation.bankroll,
+        highest: simulation.bankroll,
+        ..Tally::default()
+    };
+    let mut strategy = simulation.strategy.build(simulation.bet, simulation.unit);
+    let mut shoe = Vec::new();
+    let mut shoe_number = 1;
+    while tally.dealt < hands {
+        let decision = strategy.next_bet(&shoe, tally.bankroll)?;
+        if !simulation.mode.main_bets().contains(&decision.bet) {
+            return Err(format!(
+                "{} bets are not dealt at {:?} tables",
+                decision.bet, simulation.mode
+            ));
+        }
+        if tally
+            .bankroll
+            .is_some_and(|bankroll| bankroll < decision.stake)
+        {
+            break;
+        }
+        if game.prepare_next_round() {
+            shoe_number += 1;
+        }
+        game.play_round_unattended().map_err(|e| e.to_string())?;
+        let payout = game.calculate_main_bet_payout(decision.bet, decision.stake);
+        let net = payout - decision.stake;
+        strategy.settle(net);
+        if let Some(bankroll) = &mut tally.bankroll {
+            *bankroll += net;
+        }
+        tally.lowest = tally.lowest.min(tally.bankroll);
+        tally.highest = tally.highest.max(tally.bankroll);
+        if let (Some(highest), Some(bankroll)) = (tally.highest, tally.bankroll) {
+            tally.drawdown = tally.drawdown.max(highest - bankroll);
+        }
+        tally.wagered += decision.stake;
+        tally.returned += payout;
+        tally.largest_stake = tally.largest_stake.max(decision.stake);
+        tally.outcomes[game.state.winner as usize - 1] += 1;
+        *tally.results.entry(net).or_default() += 1;
+        tally.dealt += 1;
+        if let Some(watch) = watch.as_mut() {
+            watch(&DealtHand {
+                number: tally.dealt,
+                shoe: shoe_number,
+                game: &game,
+                decision,
+                payout,
+            })?;
+        }
+        shoe.push(game.state.winner);
+        if game.card_source.needs_reshuffle() {
+            shoe.clear();
+        }
+    }
+    Ok(tally)
+}
+
+/// The seed for stream `i` of a run seeded with `seed`: the seed moved on
+/// `i` steps, so stream 0 deals as a single-threaded run would.
+pub fn stream_seed(seed: Option<u64>, i: u32) -> Option<u64> {
+    seed.map(|seed| seed.wrapping_add(u64::from(i).wrapping_mul(STREAM_STEP)))
+}
+
+/// Splits the hands evenly over `threads` threads and adds up what they
+/// dealt, thread `i` shuffling from stream `i` of the seed.
+fn deal_in_parallel(simulation: &Simulation, threads: usize) -> Result<Tally, String> {
+    let threads = threads as u32;
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|i| {
+                let hands = simulation.hands / threads + u32::from(i < simulation.hands % threads);
+                let seed = stream_seed(simulation.seed, i);
+                scope.spawn(move || deal(simulation, hands, seed, None))
+            })
+            .collect();
+        let mut total = Tally::default();
+        for worker in workers {
+            total.merge(
+                worker
+                    .join()
+                    .map_err(|_| "a simulation thread panicked".to_string())??,
+            );
+        }
+        Ok(total)
+    })
+}
+
+/// The house edge as measured: the return to player and its margin of
+/// error in percent, and the mean and variance of a hand in units.
+struct Edge {
+    rtp: f64,
+    margin: f64,
+    mean: f64,
+    variance: f64,
+}
+
+impl Edge {
+    fn measure(simulation: &Simulation, tally: &Tally) -> Option<Self> {
+        if tally.wagered <= Money::ZERO {
+            return None;
+        }
+        let (mean, variance) = spread(&tally.results, simulation.unit);
+        // The edge is per dollar wagered, so its error is the spread of the
+        // total result over the units wagered; flat, that is one a hand.
+        let units_wagered = tally.wagered.as_dollars() / simulation.unit.as_dollars();
+        Some(Self {
+            rtp: tally.returned.as_dollars() / tally.wagered.as_dollars() * 100.0,
+            margin: Z_95 * (variance * tally.dealt as f64).sqrt() / units_wagered * 100.0,
+            mean,
+            variance,
+        })
+    }
+}
+
+/// Deals the hands and writes the report to `out`, or as JSON every hand and
+/// then the report. Binary frames go to `out` as they are dealt.
+pub fn run(out: &mut impl Write, simulation: &Simulation) -> Result<(), String> {
+    let mode = simulation.mode;
+    if !mode.main_bets().contains(&simulation.bet) {
+        return Err(format!(
+            "{} bets are not dealt at {:?} tables",
+            simulation.bet, mode
+        ));
+    }
+    let threads = match simulation.bankroll {
+        Some(_) => 1,
+        None if simulation.json || simulation.binary => 1,
+        None => simulation
+            .threads
+            .clamp(1, simulation.hands.max(1) as usize),
+    };
+    let started = Instant::now();
+    let tally = match threads {
+        1 if simulation.json => {
+            let mut print = |hand: &DealtHand| {
+                writeln!(out, "{}", hand_record(simulation, hand).json()).map_err(|e| e.to_string())
+            };
+            let tally = deal(
+                simulation,
+                simulation.hands,
+                simulation.seed,
+                Some(&mut print),
+            )?;
+            out.flush().map_err(|e| e.to_string())?;
+            tally
+        }
+        1 if simulation.binary => {
+            let mut stream = |hand: &DealtHand| {
+                wire::write_state(&mut *out, &hand.game.state)
+                    .and_then(|_| wire::write_cards(&mut *out, &hand.game.player_hand))
+                    .and_then(|_| wire::write_cards(&mut *out, &hand.game.banker_hand))
+                    .map_err(|e| e.to_string())
+            };
+            deal(
+                simulation,
+                simulation.hands,
+                simulation.seed,
+                Some(&mut stream),
+            )?;
+            return out.flush().map_err(|e| e.to_string());
+        }
+        1 => deal(simulation, simulation.hands, simulation.seed, None)?,
+        threads => deal_in_parallel(simulation, threads)?,
+    };
+    let elapsed = started.elapsed().as_secs_f64();
+    write_summary(out, simulation, &tally, threads, elapsed)
+        .and_then(|_| out.flush())
+        .map_err(|e| e.to_string())
+}
+
+fn write_summary(
+    out: &mut impl Write,
+    simulation: &Simulation,
+    tally: &Tally,
+    threads: usize,
+    elapsed: f64,
+) -> io::Result<()> {
+    let mode = simulation.mode;
+    let edge = Edge::measure(simulation, tally);
+    if simulation.json {
+        return writeln!(out, "{}", summary_json(simulation, tally, edge.as_ref()));
+    }
+    let dealt = tally.dealt;
+
+    let seed = simulation
+        .seed
+        .map_or(String::new(), |seed| format!(", seed {}", seed));
+    writeln!(
+        out,
+        "Simulated {} hands ({:?} mode, {} decks{}), {}",
+        dealt,
+        mode,
+        simulation.procedure.decks,
+        seed,
+        simulation.betting()
+    )?;
+    writeln!(
+        out,
+        "{:.2}s on {} thread{}, {:.0} hands a second",
+        elapsed,
+        threads,
+        if threads == 1 { "" } else { "s" },
+        dealt as f64 / elapsed.max(f64::EPSILON)
+    )?;
+    let share = |count: u32| 100.0 * count as f64 / dealt.max(1) as f64;
+    writeln!(
+        out,
+        "{} {:.2}% | {} {:.2}% | Tie {:.2}%",
+        mode.side_name(Side::Player),
+        share(tally.outcomes[0]),
+        mode.side_name(Side::Banker),
+        share(tally.outcomes[1]),
+        share(tally.outcomes[2])
+    )?;
+    let mut common: Vec<(Money, u32)> = tally
+        .results
+        .iter()
+        .map(|(&net, &count)| (net, count))
+        .collect();
+    common.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    let other: u32 = common
+        .iter()
+        .skip(RESULT_COLUMNS)
+        .map(|&(_, count)| count)
+        .sum();
+    common.truncate(RESULT_COLUMNS);
+    common.sort_by_key(|&(net, _)| std::cmp::Reverse(net));
+    let mut results_line: Vec<String> = common
+        .iter()
+        .map(|&(net, count)| format!("{:+} {:.2}%", net, share(count)))
+        .collect();
+    if other > 0 {
+        results_line.push(format!("other {:.2}%", share(other)));
+    }
+    writeln!(out, "Results per hand: {}", results_line.join(" | "))?;
+    writeln!(
+        out,
+        "Wagered ${} | Returned ${} | Net {:+}",
+        tally.wagered,
+        tally.returned,
+        tally.returned - tally.wagered
+    )?;
+    if !simulation.strategy.is_flat() {
+        writeln!(
+            out,
+            "Average stake ${:.2} | Largest stake ${}",
+            tally.wagered.as_dollars() / dealt.max(1) as f64,
+            tally.largest_stake
+        )?;
+    }
+    if let Some(edge) = edge {
+        writeln!(
+            out,
+            "RTP {:.2}% | House edge {:.2}% ± {:.2}% (95% confidence)",
+            edge.rtp,
+            100.0 - edge.rtp,
+            edge.margin
+        )?;
+        writeln!(
+            out,
+            "Per hand, in ${} units: mean {:+.4} | variance {:.4} | standard deviation {:.4}",
+            simulation.unit,
+            edge.mean,
+            edge.variance,
+            edge.variance.sqrt()
+        )?;
+    }
+    if let (Some(start), Some(end), Some(lowest)) =
+        (simulation.bankroll, tally.bankroll, tally.lowest)
+    {
+        writeln!(
+            out,
+            "Bankroll ${} -> ${} (lowest ${}, largest drawdown ${})",
+            start, end, lowest, tally.drawdown
+        )?;
+    }
+    if dealt < simulation.hands {
+        writeln!(
+            out,
+            "Stopped after {} of {} hands: the bankroll can't cover the stake",
+            dealt, simulation.hands
+        )?;
+    }
+    Ok(())
+}
+
+/// A dealt hand as the hand history records it, the strategy in the seat.
+fn hand_record(simulation: &Simulation, hand: &DealtHand) -> HandRecord {
+    let bet = SettledBet::new(
+        Wager::Main(hand.decision.bet),
+        hand.decision.stake,
+        hand.payout,
+    );
+    let seat = SeatBets {
+        seat: simulation.strategy.name().to_string(),
+        bets: vec![bet],
+    };
+    HandRecord::new(
+        hand.number,
+        hand.shoe,
+        hand.game,
+        vec![seat],
+        simulation.shuffle(),
+    )
+}
+
+/// The report as one line of JSON, under "summary".
+fn summary_json(simulation: &Simulation, tally: &Tally, edge: Option<&Edge>) -> String {
+    let null = || "null".to_string();
+    let mut out = String::from("{\"summary\": {");
+    let _ = write!(
+        out,
+        "\"hands\": {}, \"hands_asked\": {}",
+        tally.dealt, simulation.hands
+    );
+    let _ = write!(
+        out,
+        ", \"mode\": {}",
+        json_string(&format!("{:?}", simulation.mode))
+    );
+    let _ = write!(out, ", \"decks\": {}", simulation.procedure.decks);
+    let _ = write!(
+        out,
+        ", \"seed\": {}",
+        simulation.seed.map_or_else(null, |seed| seed.to_string())
+    );
+    let _ = write!(
+        out,
+        ", \"strategy\": {}",
+        json_string(simulation.strategy.name())
+    );
+    let _ = write!(
+        out,
+        ", \"bet\": {}",
+        json_string(&simulation.main_bet().to_string())
+    );
+    let _ = write!(out, ", \"unit\": {:.2}", simulation.unit.as_dollars());
+    let _ = write!(
+        out,
+        ", \"outcomes\": {{\"player\": {}, \"banker\": {}, \"tie\": {}}}",
+        tally.outcomes[0], tally.outcomes[1], tally.outcomes[2]
+    );
+    let _ = write!(
+        out,
+        ", \"wagered\": {:.2}, \"returned\": {:.2}, \"net\": {:.2}, \"largest_stake\": {:.2}",
+        tally.wagered.as_dollars(),
+        tally.returned.as_dollars(),
+        (tally.returned - tally.wagered).as_dollars(),
+        tally.largest_stake.as_dollars()
+    );
+    match edge {
+        Some(edge) => {
+            let _ = write!(
+                out,
+                ", \"rtp\": {:.6}, \"house_edge\": {:.6}, \"margin_95\": {:.6}, \"per_hand\": {{\"mean\": {:.6}, \"variance\": {:.6}}}",
+                edge.rtp / 100.0,
+                1.0 - edge.rtp / 100.0,
+                edge.margin / 100.0,
+                edge.mean,
+                edge.variance
+            );
+        }
+        None => out.push_str(
+            ", \"rtp\": null, \"house_edge\": null, \"margin_95\": null, \"per_hand\": null",
+        ),
+    }
+    let bankroll = match (simulation.bankroll, tally.bankroll, tally.lowest) {
+        (Some(start), Some(end), Some(lowest)) => format!(
+            "{{\"start\": {:.2}, \"end\": {:.2}, \"lowest\": {:.2}, \"largest_drawdown\": {:.2}}}",
+            start.as_dollars(),
+            end.as_dollars(),
+            lowest.as_dollars(),
+            tally.drawdown.as_dollars()
+        ),
+        _ => null(),
+    };
+    let _ = write!(
+        out,
+        ", \"bankroll\": {}, \"stopped_early\": {}}}}}",
+        bankroll,
+        tally.dealt < simulation.hands
+    );
+    out
+}
+
+/// Mean and sample variance of the net result per hand, in units.
+fn spread(results: &BTreeMap<Money, u32>, unit: Money) -> (f64, f64) {
+    let hands: f64 = results.values().map(|&count| count as f64).sum();
+    let units = |net: &Money| net.as_dollars() / unit.as_dollars();
+    let mean = results
+        .iter()
+        .map(|(net, &count)| units(net) * count as f64)
+        .sum::<f64>()
+        / hands;
+    let squares: f64 = results
+        .iter()
+        .map(|(net, &count)| (units(net) - mean).powi(2) * count as f64)
+        .sum();
+    (mean, squares / (hands - 1.0).max(1.0))
+}
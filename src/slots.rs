@@ -0,0 +1,359 @@
+//! Slot machines: a strip of symbols on each reel, paylines crossing the
+//! window the reels stop in, and a pay table for runs of one symbol from
+//! the left reel. Machines load from TOML like the house-rules bundles, so
+//! a strip or a pay can change without a rebuild, and the return to player
+//! is worked out exactly from the strips and the pay table.
+//!
+//! Each machine draws from its own generator. `SlotsGame::seeded` starts it
+//! from a fixed seed, so the same machine gives the same run of spins.
+
+use crate::baccarat::Money;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Machines shipped with the binary; `TERMINAL_CASINO_SLOTS` points at a
+/// replacement file.
+const BUILTIN_MACHINES: &str = include_str!("../data/slots.toml");
+pub const MACHINES_PATH_VAR: &str = "TERMINAL_CASINO_SLOTS";
+/// Bets per line offered at the machine, in whole dollars.
+pub const LINE_BETS: [i32; 6] = [1, 2, 5, 10, 25, 50];
+
+#[derive(Debug, Clone, Deserialize)]
+struct MachineSpec {
+    name: String,
+    #[serde(default = "default_rows")]
+    rows: usize,
+    reels: Vec<String>,
+    paylines: Vec<Vec<usize>>,
+    #[serde(default)]
+    pays: BTreeMap<String, Vec<i64>>,
+}
+
+fn default_rows() -> usize {
+    3
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Machine {
+    pub name: String,
+    /// Symbols showing on each reel.
+    pub rows: usize,
+    /// Each reel's strip, top to bottom, wrapping round.
+    pub reels: Vec<Vec<String>>,
+    /// The row each line crosses on each reel.
+    pub paylines: Vec<Vec<usize>>,
+    /// Pays per unit bet on a line for one to every reel of a symbol in a
+    /// row from the left.
+    pub pays: BTreeMap<String, Vec<i64>>,
+}
+
+impl Machine {
+    fn from_spec(spec: MachineSpec) -> Result<Self, String> {
+        let err = |detail: String| format!("machine '{}': {}", spec.name, detail);
+        if spec.rows == 0 {
+            return Err(err("rows must be at least 1".to_string()));
+        }
+        let reels: Vec<Vec<String>> = spec
+            .reels
+            .iter()
+            .map(|strip| strip.split_whitespace().map(str::to_string).collect())
+            .collect();
+        if reels.is_empty() {
+            return Err(err("no reels".to_string()));
+        }
+        if let Some(reel) = reels.iter().position(|strip| strip.len() < spec.rows) {
+            return Err(err(format!(
+                "reel {} has fewer stops than the {} rows showing",
+                reel + 1,
+                spec.rows
+            )));
+        }
+        if spec.paylines.is_empty() {
+            return Err(err("no paylines".to_string()));
+        }
+        for (line, rows) in spec.paylines.iter().enumerate() {
+            if rows.len() != reels.len() {
+                return Err(err(format!(
+                    "payline {} crosses {} reels, not {}",
+                    line + 1,
+                    rows.len(),
+                    reels.len()
+                )));
+            }
+            if let Some(row) = rows.iter().find(|&&row| row >= spec.rows) {
+                return Err(err(format!(
+                    "payline {} is on row {}, past the {} rows showing",
+                    line + 1,
+                    row,
+                    spec.rows
+                )));
+            }
+        }
+        for (symbol, pays) in &spec.pays {
+            if pays.len() != reels.len() {
+                return Err(err(format!(
+                    "{} has {} pays, one per reel is {}",
+                    symbol,
+                    pays.len(),
+                    reels.len()
+                )));
+            }
+            if pays.iter().any(|&pays| pays < 0) {
+                return Err(err(format!("{} has a negative pay", symbol)));
+            }
+            if !reels[0].contains(symbol) {
+                return Err(err(format!("{} is not on the first reel", symbol)));
+            }
+        }
+        Ok(Self {
+            rows: spec.rows,
+            reels,
+            paylines: spec.paylines,
+            pays: spec.pays,
+            name: spec.name,
+        })
+    }
+
+    /// The symbol `row` places below the top of the window when `reel`
+    /// stops at `stop`.
+    pub fn symbol(&self, reel: usize, stop: usize, row: usize) -> &str {
+        let strip = &self.reels[reel];
+        &strip[(stop + row) % strip.len()]
+    }
+
+    /// A stop on each reel, each equally likely.
+    pub fn spin_stops<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<usize> {
+        self.reels
+            .iter()
+            .map(|strip| rng.random_range(0..strip.len()))
+            .collect()
+    }
+
+    /// What `symbol` pays per unit for `count` in a row.
+    pub fn pays(&self, symbol: &str, count: usize) -> i64 {
+        self.pays
+            .get(symbol)
+            .and_then(|pays| pays.get(count.wrapping_sub(1)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The symbol running from the left reel along `line` and how many
+    /// reels it runs for, when the reels stop at `stops`.
+    pub fn line_run(&self, stops: &[usize], line: usize) -> (&str, usize) {
+        let rows = &self.paylines[line];
+        let first = self.symbol(0, stops[0], rows[0]);
+        let count = (0..self.reels.len())
+            .take_while(|&reel| self.symbol(reel, stops[reel], rows[reel]) == first)
+            .count();
+        (first, count)
+    }
+
+    /// The winning lines among the first `lines` when the reels stop at
+    /// `stops`.
+    pub fn line_wins(&self, stops: &[usize], lines: usize, line_bet: Money) -> Vec<LineWin> {
+        (0..lines.min(self.paylines.len()))
+            .filter_map(|line| {
+                let (symbol, count) = self.line_run(stops, line);
+                let pays = self.pays(symbol, count);
+                (pays > 0).then(|| LineWin {
+                    line,
+                    symbol: symbol.to_string(),
+                    count,
+                    payout: line_bet * pays,
+                })
+            })
+            .collect()
+    }
+
+    /// The share of each unit bet on a line that comes back, worked out from
+    /// the strips: a run of exactly `k` takes the symbol on the first `k`
+    /// reels and anything else on the next. Every row of a reel shows each
+    /// stop equally often, so every line, and any number of them, returns
+    /// the same.
+    pub fn rtp(&self) -> f64 {
+        let chance = |reel: usize, symbol: &str| {
+            let strip = &self.reels[reel];
+            strip.iter().filter(|&stop| stop == symbol).count() as f64 / strip.len() as f64
+        };
+        let reels = self.reels.len();
+        self.pays
+            .iter()
+            .map(|(symbol, pays)| {
+                let mut run = 1.0;
+                (0..reels)
+                    .map(|reel| {
+                        run *= chance(reel, symbol);
+                        let ends = if reel + 1 < reels {
+                            1.0 - chance(reel + 1, symbol)
+                        } else {
+                            1.0
+                        };
+                        run * ends * pays[reel] as f64
+                    })
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Symbols in the pay table, the best paying first.
+    pub fn paying_symbols(&self) -> Vec<&str> {
+        let mut symbols: Vec<&str> = self.pays.keys().map(String::as_str).collect();
+        symbols.sort_by_key(|symbol| {
+            std::cmp::Reverse(self.pays[*symbol].last().copied().unwrap_or(0))
+        });
+        symbols
+    }
+}
+
+/// A line that paid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineWin {
+    /// Index into the machine's paylines.
+    pub line: usize,
+    pub symbol: String,
+    /// Reels the symbol ran for from the left.
+    pub count: usize,
+    /// Winnings on the line; the line bet itself is not returned.
+    pub payout: Money,
+}
+
+/// One spin: where the reels stopped and what it paid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spin {
+    pub stops: Vec<usize>,
+    pub lines: usize,
+    pub line_bet: Money,
+    pub wins: Vec<LineWin>,
+}
+
+impl Spin {
+    /// The bet across every line played.
+    pub fn stake(&self) -> Money {
+        self.line_bet * self.lines as i64
+    }
+
+    pub fn payout(&self) -> Money {
+        self.wins.iter().map(|win| win.payout).sum()
+    }
+
+    pub fn net(&self) -> Money {
+        self.payout() - self.stake()
+    }
+
+    /// `line` paid on this spin.
+    pub fn won_on(&self, line: usize) -> bool {
+        self.wins.iter().any(|win| win.line == line)
+    }
+}
+
+/// A machine being played: how many lines and how much on each, and the
+/// generator the reels stop by.
+pub struct SlotsGame {
+    machine: Machine,
+    rng: StdRng,
+    lines: usize,
+    line_bet: Money,
+    /// Spins since the machine was started, oldest first.
+    pub history: Vec<Spin>,
+}
+
+impl SlotsGame {
+    /// Starts `machine` from a fresh seed, playing every line at the
+    /// lowest bet.
+    pub fn new(machine: Machine) -> Self {
+        Self::with_rng(machine, StdRng::from_rng(&mut rand::rng()))
+    }
+
+    /// Starts `machine` from `seed`, so the spins that follow are the same
+    /// every time.
+    pub fn seeded(machine: Machine, seed: u64) -> Self {
+        Self::with_rng(machine, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(machine: Machine, rng: StdRng) -> Self {
+        Self {
+            lines: machine.paylines.len(),
+            line_bet: Money::from_dollars(LINE_BETS[0]),
+            machine,
+            rng,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn machine(&self) -> &Machine {
+        &self.machine
+    }
+
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Plays the first `lines` paylines, at least one and at most all of them.
+    pub fn set_lines(&mut self, lines: usize) {
+        self.lines = lines.clamp(1, self.machine.paylines.len());
+    }
+
+    pub fn line_bet(&self) -> Money {
+        self.line_bet
+    }
+
+    pub fn set_line_bet(&mut self, line_bet: Money) {
+        self.line_bet = line_bet.max(Money::from_cents(1));
+    }
+
+    /// What the next spin costs.
+    pub fn total_bet(&self) -> Money {
+        self.line_bet * self.lines as i64
+    }
+
+    /// Spins the reels. The caller takes `total_bet` from the bankroll
+    /// first and pays the spin's `payout` back.
+    pub fn spin(&mut self) -> &Spin {
+        let stops = self.machine.spin_stops(&mut self.rng);
+        let wins = self.machine.line_wins(&stops, self.lines, self.line_bet);
+        self.history.push(Spin {
+            stops,
+            lines: self.lines,
+            line_bet: self.line_bet,
+            wins,
+        });
+        &self.history[self.history.len() - 1]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MachinesFile {
+    #[serde(default, rename = "machine")]
+    machines: Vec<MachineSpec>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MachineBook {
+    pub machines: Vec<Machine>,
+}
+
+impl MachineBook {
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_MACHINES).unwrap_or_default()
+    }
+
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let file: MachinesFile = toml::from_str(source).map_err(|e| e.to_string())?;
+        let machines = file
+            .machines
+            .into_iter()
+            .map(Machine::from_spec)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { machines })
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.machines
+            .iter()
+            .map(|machine| machine.name.as_str())
+            .collect()
+    }
+}
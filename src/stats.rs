@@ -0,0 +1,138 @@
+//! Small statistical helpers used by the self-test and analysis screens.
+
+/// Pearson chi-square statistic for observed counts against expected counts.
+pub fn chi_square(observed: &[f64], expected: &[f64]) -> f64 {
+    observed
+        .iter()
+        .zip(expected)
+        .filter(|(_, e)| **e > 0.0)
+        .map(|(o, e)| (o - e) * (o - e) / e)
+        .sum()
+}
+
+/// Probability of a chi-square statistic at least this large under the null
+/// hypothesis with `df` degrees of freedom.
+pub fn chi_square_p_value(statistic: f64, df: f64) -> f64 {
+    if statistic <= 0.0 {
+        return 1.0;
+    }
+    upper_regularized_gamma(df / 2.0, statistic / 2.0)
+}
+
+/// Two-sided exact binomial test: the probability, with `trials` draws at
+/// success rate `p`, of a count no more likely than `successes`.
+pub fn binomial_p_value(successes: u32, trials: u32, p: f64) -> f64 {
+    if trials == 0 || p <= 0.0 || p >= 1.0 {
+        return 1.0;
+    }
+    let n = trials as f64;
+    let ln_pmf = |k: u32| {
+        let k = k as f64;
+        ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+            + k * p.ln()
+            + (n - k) * (1.0 - p).ln()
+    };
+    // Tolerance so outcomes as likely as the observed one are not lost to
+    // rounding.
+    let threshold = ln_pmf(successes) + 1e-7;
+    let total: f64 = (0..=trials)
+        .map(ln_pmf)
+        .filter(|&ln_p| ln_p <= threshold)
+        .map(f64::exp)
+        .sum();
+    total.min(1.0)
+}
+
+/// Wilson score interval for a rate of `successes` in `trials`, `z` standard
+/// errors wide. Unlike the normal approximation it stays between 0 and 1
+/// when the rate is near either end.
+pub fn wilson_interval(successes: u32, trials: u32, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let rate = successes as f64 / n;
+    let z2 = z * z;
+    let centre = (rate + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half = z / (1.0 + z2 / n) * (rate * (1.0 - rate) / n + z2 / (4.0 * n * n)).sqrt();
+    ((centre - half).max(0.0), (centre + half).min(1.0))
+}
+
+/// The `pct`th percentile of `sorted` by nearest rank.
+pub fn percentile<T: Copy>(sorted: &[T], pct: u32) -> T {
+    let rank = (sorted.len() * pct as usize).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// Q(a, x) = Γ(a, x) / Γ(a), via series for small x and a continued fraction
+/// otherwise (Numerical Recipes, §6.2).
+pub fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    if x < a + 1.0 {
+        1.0 - lower_gamma_series(a, x)
+    } else {
+        upper_gamma_continued_fraction(a, x)
+    }
+}
+
+fn lower_gamma_series(a: f64, x: f64) -> f64 {
+    let mut sum = 1.0 / a;
+    let mut term = sum;
+    let mut n = a;
+    for _ in 0..10_000 {
+        n += 1.0;
+        term *= x / n;
+        sum += term;
+        if term.abs() < sum.abs() * 1e-15 {
+            break;
+        }
+    }
+    (sum.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn upper_gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..10_000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    (h.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// Lanczos approximation of ln Γ(x) for x > 0.
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for coeff in COEFFS {
+        y += 1.0;
+        series += coeff / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
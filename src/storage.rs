@@ -0,0 +1,51 @@
+//! On-disk locations for per-profile data.
+
+use std::path::PathBuf;
+
+pub const DATA_DIR_VAR: &str = "TERMINAL_CASINO_DATA";
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// `$TERMINAL_CASINO_DATA`, else `$XDG_DATA_HOME/terminal_casino`, else
+/// `~/.local/share/terminal_casino`.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("terminal_casino")
+}
+
+/// Where the settings files live: `$TERMINAL_CASINO_DATA` if set, alongside
+/// everything else, else `$XDG_CONFIG_HOME/terminal_casino`, else
+/// `~/.config/terminal_casino`.
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("terminal_casino")
+}
+
+/// Reads the file `var` names with `parse`, or returns `builtin()` when
+/// the variable is unset. Errors name the file.
+pub fn load_or_builtin<T>(
+    var: &str,
+    parse: impl FnOnce(&str) -> Result<T, String>,
+    builtin: impl FnOnce() -> T,
+) -> Result<T, String> {
+    let Ok(path) = std::env::var(var) else {
+        return Ok(builtin());
+    };
+    let source = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+    parse(&source).map_err(|e| format!("{}: {}", path, e))
+}
+
+pub fn profile_dir(profile: &str) -> PathBuf {
+    data_dir().join("profiles").join(profile)
+}
@@ -0,0 +1,259 @@
+//! Betting strategies: something that picks each hand's main bet and stake
+//! from the shoe so far and the bankroll, and hears how the bet came out.
+//! `simulate` runs one for as many hands as asked and the table's
+//! autoplay bets one hand after hand; the built-ins are the systems players
+//! ask about, and none of them moves the house edge. Anything else can be
+//! written as a script (see `script`).
+
+use crate::baccarat::{BetType, Money};
+use crate::script::StrategyScript;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Wins in a row a Paroli lets ride before it drops back to one unit.
+const PAROLI_WINS: usize = 3;
+/// The 1-3-2-4 system's stakes in units, moved on one step per win.
+const ONE_THREE_TWO_FOUR: [i64; 4] = [1, 3, 2, 4];
+
+/// One hand's bet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub bet: BetType,
+    pub stake: Money,
+}
+
+/// A betting system; each table it bets at has an instance of its own.
+pub trait Strategy: Send {
+    /// The next hand's bet, given the winners of the shoe so far (1 player,
+    /// 2 banker, 3 tie) and the bankroll, if there is one. It is up to the
+    /// caller to stop once the bankroll can't cover the stake. Only a
+    /// script can fail.
+    fn next_bet(&mut self, shoe: &[u8], bankroll: Option<Money>) -> Result<Decision, String>;
+
+    /// Tells the strategy what the last bet won or lost; a push is zero.
+    fn settle(&mut self, net: Money);
+}
+
+/// The same bet and stake every hand.
+pub struct Flat {
+    bet: BetType,
+    unit: Money,
+}
+
+impl Strategy for Flat {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        Ok(Decision {
+            bet: self.bet,
+            stake: self.unit,
+        })
+    }
+
+    fn settle(&mut self, _net: Money) {}
+}
+
+/// Doubles the stake after every loss and drops back to one unit after a
+/// win, to win one unit back with each run of losses.
+pub struct Martingale {
+    bet: BetType,
+    unit: Money,
+    stake: Money,
+}
+
+impl Strategy for Martingale {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        Ok(Decision {
+            bet: self.bet,
+            stake: self.stake,
+        })
+    }
+
+    fn settle(&mut self, net: Money) {
+        if net > Money::ZERO {
+            self.stake = self.unit;
+        } else if net < Money::ZERO {
+            self.stake = self
+                .stake
+                .checked_mul(2)
+                .filter(|&stake| stake <= Money::MAX_INPUT)
+                .unwrap_or(self.stake);
+        }
+    }
+}
+
+/// Doubles the stake after every win, up to three in a row, and drops back
+/// to one unit after a loss: the reverse Martingale.
+pub struct Paroli {
+    bet: BetType,
+    unit: Money,
+    wins: usize,
+}
+
+impl Strategy for Paroli {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        Ok(Decision {
+            bet: self.bet,
+            stake: self.unit * (1 << self.wins),
+        })
+    }
+
+    fn settle(&mut self, net: Money) {
+        if net > Money::ZERO {
+            self.wins = (self.wins + 1) % PAROLI_WINS;
+        } else if net < Money::ZERO {
+            self.wins = 0;
+        }
+    }
+}
+
+/// Stakes follow the Fibonacci numbers in units: one step on after a loss,
+/// two steps back after a win.
+pub struct Fibonacci {
+    bet: BetType,
+    unit: Money,
+    step: usize,
+}
+
+impl Fibonacci {
+    fn units(&self) -> i64 {
+        let (mut a, mut b) = (1i64, 1i64);
+        for _ in 0..self.step {
+            (a, b) = (b, a.saturating_add(b));
+        }
+        a
+    }
+}
+
+impl Strategy for Fibonacci {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        let stake = self
+            .unit
+            .checked_mul(self.units())
+            .filter(|&stake| stake <= Money::MAX_INPUT)
+            .unwrap_or(self.unit);
+        Ok(Decision {
+            bet: self.bet,
+            stake,
+        })
+    }
+
+    fn settle(&mut self, net: Money) {
+        if net > Money::ZERO {
+            self.step = self.step.saturating_sub(2);
+        } else if net < Money::ZERO {
+            self.step += 1;
+        }
+    }
+}
+
+/// Stakes of 1, 3, 2 and 4 units, one step on after each win, starting
+/// over after the fourth win or any loss.
+pub struct OneThreeTwoFour {
+    bet: BetType,
+    unit: Money,
+    step: usize,
+}
+
+impl Strategy for OneThreeTwoFour {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        Ok(Decision {
+            bet: self.bet,
+            stake: self.unit * ONE_THREE_TWO_FOUR[self.step],
+        })
+    }
+
+    fn settle(&mut self, net: Money) {
+        if net > Money::ZERO {
+            self.step = (self.step + 1) % ONE_THREE_TWO_FOUR.len();
+        } else if net < Money::ZERO {
+            self.step = 0;
+        }
+    }
+}
+
+/// One unit on the banker every hand, whatever bet was asked for: the
+/// lowest house edge on the layout.
+pub struct BankerOnly {
+    unit: Money,
+}
+
+impl Strategy for BankerOnly {
+    fn next_bet(&mut self, _shoe: &[u8], _bankroll: Option<Money>) -> Result<Decision, String> {
+        Ok(Decision {
+            bet: BetType::Banker,
+            stake: self.unit,
+        })
+    }
+
+    fn settle(&mut self, _net: Money) {}
+}
+
+/// The built-in strategies, by name, or a script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum StrategyKind {
+    #[default]
+    Flat,
+    Martingale,
+    Paroli,
+    Fibonacci,
+    OneThreeTwoFour,
+    BankerOnly,
+    Script(Arc<StrategyScript>),
+}
+
+impl StrategyKind {
+    pub fn name(&self) -> &str {
+        match self {
+            StrategyKind::Flat => "flat",
+            StrategyKind::Martingale => "martingale",
+            StrategyKind::Paroli => "paroli",
+            StrategyKind::Fibonacci => "fibonacci",
+            StrategyKind::OneThreeTwoFour => "1-3-2-4",
+            StrategyKind::BankerOnly => "banker",
+            StrategyKind::Script(script) => &script.name,
+        }
+    }
+
+    /// Whether every hand is bet with one unit, with no progression.
+    pub fn is_flat(&self) -> bool {
+        matches!(self, StrategyKind::Flat | StrategyKind::BankerOnly)
+    }
+
+    /// A fresh strategy betting `bet` from a base stake of `unit`.
+    pub fn build(&self, bet: BetType, unit: Money) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Flat => Box::new(Flat { bet, unit }),
+            StrategyKind::Martingale => Box::new(Martingale {
+                bet,
+                unit,
+                stake: unit,
+            }),
+            StrategyKind::Paroli => Box::new(Paroli { bet, unit, wins: 0 }),
+            StrategyKind::Fibonacci => Box::new(Fibonacci { bet, unit, step: 0 }),
+            StrategyKind::OneThreeTwoFour => Box::new(OneThreeTwoFour { bet, unit, step: 0 }),
+            StrategyKind::BankerOnly => Box::new(BankerOnly { unit }),
+            StrategyKind::Script(script) => Box::new(script.build(bet, unit)),
+        }
+    }
+}
+
+impl std::str::FromStr for StrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "flat" => Ok(StrategyKind::Flat),
+            "martingale" => Ok(StrategyKind::Martingale),
+            "paroli" => Ok(StrategyKind::Paroli),
+            "fibonacci" => Ok(StrategyKind::Fibonacci),
+            "1-3-2-4" | "1324" => Ok(StrategyKind::OneThreeTwoFour),
+            "banker" | "bankeronly" | "banker-only" => Ok(StrategyKind::BankerOnly),
+            _ if s.ends_with(".rhai") => Ok(StrategyKind::Script(Arc::new(StrategyScript::load(
+                Path::new(s),
+            )?))),
+            _ => Err(format!(
+                "unknown strategy '{}', expected flat, martingale, paroli, fibonacci, 1-3-2-4, banker or a .rhai script",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! `tableau` subcommand: checks the engine's drawing decisions against the
+//! official punto banco table for every reachable situation, dealing each
+//! one as a real hand.
+
+use crate::test_support::{self, OFFICIAL_BANKER_CHART, TableauCase};
+use std::io::{self, Write};
+
+/// Whether the banker draws when the engine deals `banker_total` against a
+/// player who stood on six, or who drew `third`.
+fn engine_banker_draws(banker_total: u8, third: Option<u8>) -> bool {
+    let case = TableauCase {
+        player_total: if third.is_some() { 0 } else { 6 },
+        banker_total,
+        third,
+    };
+    test_support::play_scripted_round(&case.cards())
+        .draws()
+        .banker
+}
+
+/// Writes the engine's banker chart, read off hands it deals, next to the
+/// official one to `out` and returns whether every combination matched. A
+/// cell is marked when any hand in it went against the chart.
+pub fn run(out: &mut impl Write) -> io::Result<bool> {
+    let checked = test_support::tableau_cases().len();
+    let mismatches = test_support::verify_tableau();
+
+    writeln!(
+        out,
+        "Banker drawing chart (D = draw, S = stand, ! = differs from official)"
+    )?;
+    writeln!(out, "{:<8} {:<30}   Official", "", "Engine")?;
+    writeln!(out, "{:<8} {:<30}   - 0123456789", "Banker", "- 0123456789")?;
+    for banker_total in 0..=7u8 {
+        let mut engine_row = String::new();
+        for column in 0..12 {
+            if column == 1 {
+                engine_row.push(' ');
+                continue;
+            }
+            let third = if column == 0 {
+                None
+            } else {
+                Some(column as u8 - 2)
+            };
+            let draws = engine_banker_draws(banker_total, third);
+            let official = OFFICIAL_BANKER_CHART[banker_total as usize].as_bytes()[column] == b'D';
+            let differs = draws != official
+                || mismatches.iter().any(|mismatch| {
+                    mismatch.case.banker_total == banker_total
+                        && mismatch.case.third == third
+                        && mismatch.engine.banker != mismatch.official.banker
+                });
+            engine_row.push(match (differs, draws) {
+                (true, _) => '!',
+                (false, true) => 'D',
+                (false, false) => 'S',
+            });
+        }
+        writeln!(
+            out,
+            "{:<8} {:<30}   {}",
+            banker_total, engine_row, OFFICIAL_BANKER_CHART[banker_total as usize]
+        )?;
+    }
+    writeln!(out)?;
+    writeln!(
+        out,
+        "Checked {} (player total, banker total, player third card) combinations.",
+        checked
+    )?;
+
+    if mismatches.is_empty() {
+        writeln!(out, "Engine matches the official punto banco table.")?;
+        return Ok(true);
+    }
+
+    writeln!(out, "MISMATCHES: {}", mismatches.len())?;
+    for mismatch in mismatches {
+        let case = mismatch.case;
+        let third = case.third.map_or("-".to_string(), |v| v.to_string());
+        writeln!(
+            out,
+            "  player {} banker {} third {}: engine P{} B{}, official P{} B{}",
+            case.player_total,
+            case.banker_total,
+            third,
+            draw_letter(mismatch.engine.player),
+            draw_letter(mismatch.engine.banker),
+            draw_letter(mismatch.official.player),
+            draw_letter(mismatch.official.banker),
+        )?;
+    }
+    Ok(false)
+}
+
+fn draw_letter(draws: bool) -> char {
+    if draws { 'D' } else { 'S' }
+}
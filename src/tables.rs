@@ -0,0 +1,2747 @@
+//! The tables besides baccarat, each a `CasinoGame` the lobby opens. The
+//! rules live in the library's game modules; this is how each table looks
+//! and what its keys do.
+
+use crate::baccarat::{self, Card, GamePhase, Money};
+use crate::blackjack::{self, BLACKJACK_DECKS, BlackjackGame, Phase};
+use crate::card_renderer::{CardRenderer, HandScore, WheelAnimation};
+use crate::caribbean::{self, CaribbeanGame, Jackpot, Progressive};
+use crate::config::Action;
+use crate::craps::{self, CrapsGame};
+use crate::holdem::{self, HoldemGame};
+use crate::lobby::{CasinoGame, Game, TableAction, TableContext, TableView};
+use crate::roulette::{self, PocketColor, RouletteGame, Wheel};
+use crate::settlement::SettlementReport;
+use crate::sicbo::{self, SicBoGame};
+use crate::slots::{self, MachineBook, SlotsGame};
+use crate::storage;
+use crate::theme::Theme;
+use crate::three_card_poker::{self, ThreeCardPokerGame};
+use crate::tui::CARD_REVEAL_INTERVAL;
+use crate::war::{self, WAR_DECKS, WarGame};
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+use std::time::{Duration, Instant};
+
+/// How long Sic Bo dice tumble before they land.
+const DICE_ROLL_TIME: Duration = Duration::from_millis(900);
+/// Slot reels stop one after another, this far apart, left to right.
+const REEL_STOP_INTERVAL: Duration = Duration::from_millis(400);
+/// Time for a turning reel to move one symbol.
+const REEL_STEP: Duration = Duration::from_millis(70);
+
+/// The blackjack table, dealt under the house rules in the settings.
+pub struct BlackjackTable {
+    game: BlackjackGame,
+    /// Stake for the next deal in whole dollars.
+    bet: i32,
+}
+
+impl BlackjackTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        Ok(Box::new(Self {
+            game: BlackjackGame::new(ctx.config.blackjack, BLACKJACK_DECKS),
+            bet: ctx.limits.clamp(*ctx.selected_chip),
+        }))
+    }
+
+    /// Takes the stake from the bankroll and deals, under the house rules
+    /// currently in the settings.
+    fn deal(&mut self, ctx: &mut TableContext) {
+        let stake = Money::from_dollars(self.bet);
+        if stake > *ctx.balance {
+            ctx.say(format!(
+                "${} bet is more than the ${} balance",
+                self.bet, ctx.balance
+            ));
+            return;
+        }
+        self.game.rules = ctx.config.blackjack;
+        *ctx.balance -= stake;
+        let result = self.game.deal(stake);
+        self.after_move(result, ctx);
+    }
+
+    fn insure(&mut self, take: bool, ctx: &mut TableContext) {
+        let cost = if take {
+            self.game.insurance_cost()
+        } else {
+            Money::ZERO
+        };
+        if cost > *ctx.balance {
+            ctx.say(format!("${} insurance is more than the balance", cost));
+            return;
+        }
+        *ctx.balance -= cost;
+        let result = self.game.insure(take);
+        self.after_move(result, ctx);
+    }
+
+    fn act(&mut self, action: blackjack::Action, ctx: &mut TableContext) {
+        if !self.game.can(action) {
+            ctx.say(format!("{} is not open to this hand", action));
+            return;
+        }
+        let cost = self.game.cost(action);
+        if cost > *ctx.balance {
+            ctx.say(format!(
+                "Not enough balance to {}",
+                action.to_string().to_lowercase()
+            ));
+            return;
+        }
+        *ctx.balance -= cost;
+        let result = self.game.act(action);
+        self.after_move(result, ctx);
+    }
+
+    /// Reports a failed move, or pays out once the round has settled.
+    fn after_move(
+        &mut self,
+        result: Result<(), blackjack::BlackjackError>,
+        ctx: &mut TableContext,
+    ) {
+        if let Err(e) = result {
+            ctx.say(format!("Error: {}", e));
+        }
+        if self.game.phase == Phase::Settled {
+            *ctx.balance += self.game.returned();
+        }
+    }
+}
+
+impl CasinoGame for BlackjackTable {
+    fn game(&self) -> Game {
+        Game::Blackjack
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        let rules = self.game.rules;
+        format!(
+            "BLACKJACK - {}  |  Blackjack pays 3:2{}  |  ${}-${}",
+            if rules.dealer_hits_soft_17 {
+                "Dealer hits soft 17"
+            } else {
+                "Dealer stands on all 17s"
+            },
+            if rules.surrender {
+                ", late surrender"
+            } else {
+                ""
+            },
+            view.limits.min_bet,
+            view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+
+        // Dealer on the left, then one panel per player hand
+        let mut constraints = vec![Constraint::Percentage(40)];
+        let hand_count = game.hands.len().max(1) as u32;
+        constraints.extend((0..hand_count).map(|_| Constraint::Ratio(3, 5 * hand_count)));
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(chunks[1]);
+        let dealer_shown = if game.hole_card_shown() {
+            game.dealer.len()
+        } else {
+            1
+        };
+        f.render_widget(
+            CardRenderer::create_partial_hand_display(
+                &game.dealer,
+                "DEALER".to_string(),
+                HandScore::of_blackjack(&game.dealer),
+                dealer_shown,
+                view.card_back,
+                panels[0].width,
+                theme,
+            ),
+            panels[0],
+        );
+        if game.hands.is_empty() {
+            f.render_widget(
+                CardRenderer::create_hand_display(&[], "YOU".to_string(), 0, theme),
+                panels[1],
+            );
+        }
+        for (index, hand) in game.hands.iter().enumerate() {
+            let mut title = if game.hands.len() > 1 {
+                format!("HAND {}", index + 1)
+            } else {
+                "YOU".to_string()
+            };
+            if game.phase == Phase::Settled {
+                title.push_str(&format!(" - {}", hand.outcome(&game.dealer)));
+            } else if game.phase == Phase::PlayerTurn && index == game.active {
+                title.push_str(" ◀");
+            }
+            f.render_widget(
+                CardRenderer::create_partial_hand_display(
+                    &hand.hand,
+                    title,
+                    HandScore::of_blackjack(&hand.hand),
+                    hand.hand.len(),
+                    view.card_back,
+                    panels[index + 1].width,
+                    theme,
+                ),
+                panels[index + 1],
+            );
+        }
+
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Bet: "),
+                Span::styled(
+                    format!("${}  (chip ${})", self.bet, view.selected_chip),
+                    Style::default().fg(theme.bet),
+                ),
+            ]),
+        ];
+        if game.phase != Phase::Betting {
+            let stakes: Vec<String> = game
+                .hands
+                .iter()
+                .map(|hand| {
+                    format!(
+                        "${}{}",
+                        hand.stake,
+                        if hand.doubled { " doubled" } else { "" }
+                    )
+                })
+                .collect();
+            info.push(Line::from(format!("In play: {}", stakes.join(", "))));
+        }
+        if game.insurance > Money::ZERO {
+            info.push(Line::from(vec![
+                Span::raw("Insurance: "),
+                Span::styled(
+                    format!("${}", game.insurance),
+                    Style::default().fg(theme.side_bet),
+                ),
+            ]));
+        }
+        if game.phase == Phase::Settled {
+            info.push(Line::from(format!(
+                "Last round {:+}: dealer {}",
+                game.returned() - game.wagered(),
+                if game.dealer.is_blackjack() {
+                    "blackjack".to_string()
+                } else if game.dealer.is_bust() {
+                    format!("busts on {}", game.dealer.total())
+                } else {
+                    format!("stands on {}", game.dealer.total())
+                }
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(info).block(theme.panel().title(view.panel_title("Blackjack"))),
+            chunks[2],
+        );
+
+        let moves = match game.phase {
+            Phase::Insurance => format!(
+                "Dealer shows an ace: [I] Insurance (${})  [N] No insurance",
+                game.insurance_cost()
+            ),
+            Phase::PlayerTurn => {
+                let moves: Vec<String> = game
+                    .available_actions()
+                    .into_iter()
+                    .map(|action| {
+                        format!(
+                            "[{}] {}",
+                            blackjack_key(action).to_ascii_uppercase(),
+                            action
+                        )
+                    })
+                    .collect();
+                moves.join("  ")
+            }
+            Phase::Betting | Phase::Settled => format!("[{}] Deal", view.key(Action::Deal)),
+        };
+        let controls = vec![
+            Line::from(moves),
+            Line::from("[1-5] Select chip  [+/-] Adjust"),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// Player decisions use fixed letters ([H]it, [S]tand, [D]ouble,
+    /// s[P]lit, su[R]render); the chip keys and deal key work as they do at
+    /// baccarat.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        match self.game.phase {
+            Phase::Insurance => match code {
+                KeyCode::Char('i') => self.insure(true, ctx),
+                KeyCode::Char('n') => self.insure(false, ctx),
+                _ => {}
+            },
+            Phase::PlayerTurn => {
+                if let Some(action) = blackjack::Action::ALL
+                    .into_iter()
+                    .find(|&action| KeyCode::Char(blackjack_key(action)) == code)
+                {
+                    self.act(action, ctx);
+                } else if code == KeyCode::Esc {
+                    ctx.say("Finish the hand first");
+                }
+            }
+            Phase::Betting | Phase::Settled => match code {
+                KeyCode::Esc => return TableAction::Lobby,
+                KeyCode::Char(c @ '1'..='5') => {
+                    if let Some(chip) = ctx.select_chip(c) {
+                        self.bet = ctx.limits.clamp(chip);
+                    }
+                }
+                KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                    self.bet = ctx.limits.step(self.bet, *ctx.selected_chip, c != '-')
+                }
+                _ if ctx.is_deal(code) => self.deal(ctx),
+                _ => {}
+            },
+        }
+        TableAction::Stay
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.game
+            .in_progress()
+            .then(|| format!("Finish the {} hand first", self.name()))
+    }
+}
+
+/// The hold'em table. The player's chips are bought in from the bankroll
+/// and go back to it on leaving.
+pub struct HoldemTable {
+    game: HoldemGame,
+    /// Raise total for the next raise, kept within the legal range when used.
+    raise_to: Money,
+}
+
+impl HoldemTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        let chips = (*ctx.balance).min(holdem::BUY_IN);
+        *ctx.balance -= chips;
+        let mut game = HoldemGame::new(ctx.player, ctx.opponents, chips);
+        if let Some(seed) = ctx.seed {
+            game.set_rng(baccarat::seeded_rng(seed));
+        }
+        Ok(Box::new(Self {
+            game,
+            raise_to: Money::ZERO,
+        }))
+    }
+
+    /// Deals the next hand and plays the bots up to the player's turn. A
+    /// player out of chips buys in again from the bankroll first.
+    fn deal(&mut self, ctx: &mut TableContext) {
+        if self.game.seats[0].chips == Money::ZERO {
+            let chips = (*ctx.balance).min(holdem::BUY_IN);
+            if chips == Money::ZERO {
+                ctx.say("No bankroll left to buy in");
+                return;
+            }
+            self.game.seats[0].chips = chips;
+            *ctx.balance -= chips;
+            ctx.say(format!("Bought in for ${}", chips));
+        }
+        if let Err(e) = self.game.start_hand().and_then(|_| self.game.play_bots()) {
+            ctx.say(format!("Error: {}", e));
+        }
+    }
+}
+
+impl CasinoGame for HoldemTable {
+    fn game(&self) -> Game {
+        Game::Holdem
+    }
+
+    fn title(&self, _view: &TableView) -> String {
+        format!(
+            "TEXAS HOLD'EM - No Limit ${}/${}  |  {} opponents",
+            holdem::SMALL_BLIND,
+            holdem::BIG_BLIND,
+            self.game.opponents()
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(chunks[1]);
+        let street = if game.board.is_empty() && !game.in_progress() {
+            "Waiting for the deal"
+        } else {
+            game.street.name()
+        };
+        f.render_widget(
+            CardRenderer::create_partial_hand_display(
+                &game.board,
+                "BOARD".to_string(),
+                HandScore::Named(street),
+                game.board.len(),
+                view.card_back,
+                panels[0].width,
+                theme,
+            ),
+            panels[0],
+        );
+        let hand = match game.best_hand(0) {
+            Some(value) => value.category.name(),
+            None => "Hole cards",
+        };
+        f.render_widget(
+            CardRenderer::create_partial_hand_display(
+                &game.seats[0].hole,
+                game.seats[0].name.to_uppercase(),
+                HandScore::Named(hand),
+                game.seats[0].hole.len(),
+                view.card_back,
+                panels[1].width,
+                theme,
+            ),
+            panels[1],
+        );
+
+        // One line per seat: stack, bet, and state, with bots' cards at a showdown
+        let mut info = vec![Line::from(vec![
+            Span::raw("Pot: "),
+            Span::styled(format!("${}", game.pot()), Style::default().fg(theme.bet)),
+            Span::raw(format!("  |  Bankroll ${}", view.balance)),
+        ])];
+        for (index, seat) in game.seats.iter().enumerate() {
+            let marker = if game.to_act == Some(index) {
+                "▶ "
+            } else {
+                "  "
+            };
+            let button = if index == game.button { " (D)" } else { "" };
+            let mut spans = vec![
+                Span::raw(format!("{}{}{}  ", marker, seat.name, button)),
+                Span::styled(
+                    format!("${}", seat.chips),
+                    Style::default().fg(theme.balance),
+                ),
+            ];
+            if seat.street_bet > Money::ZERO {
+                spans.push(Span::styled(
+                    format!("  bet ${}", seat.street_bet),
+                    Style::default().fg(theme.bet),
+                ));
+            }
+            if game.winnings[index] > Money::ZERO {
+                spans.push(Span::styled(
+                    format!("  won ${}", game.winnings[index]),
+                    Style::default().fg(theme.highlight),
+                ));
+            } else if seat.folded && !seat.hole.is_empty() {
+                spans.push(Span::styled("  folded", Style::default().fg(theme.muted)));
+            } else if seat.is_all_in() {
+                spans.push(Span::raw("  all in"));
+            }
+            if index > 0 && game.showdown && seat.in_hand() {
+                let cards: Vec<String> = seat
+                    .hole
+                    .iter()
+                    .map(CardRenderer::render_mini_card)
+                    .collect();
+                let hand = game
+                    .best_hand(index)
+                    .map_or("", |value| value.category.name());
+                spans.push(Span::raw(format!("  {} {}", cards.join(""), hand)));
+            }
+            info.push(Line::from(spans));
+        }
+        f.render_widget(
+            Paragraph::new(info).block(theme.panel().title(view.panel_title("Table"))),
+            chunks[2],
+        );
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(chunks[3]);
+        let controls = if game.to_act == Some(0) {
+            let call = game.to_call(0);
+            let raise_to = self
+                .raise_to
+                .clamp(game.min_raise_to(0), game.max_raise_to(0));
+            vec![
+                Line::from(format!(
+                    "[F] Fold  [C] {}  [R] Raise to ${}  [A] All in (${})",
+                    if call > Money::ZERO {
+                        format!("Call ${}", call)
+                    } else {
+                        "Check".to_string()
+                    },
+                    raise_to,
+                    game.max_raise_to(0)
+                )),
+                Line::from(format!("[+/-] Raise size by ${}", holdem::BIG_BLIND)),
+                Line::from(format!("[{}] Quit", view.key(Action::Quit))),
+            ]
+        } else {
+            vec![
+                Line::from(format!(
+                    "[{}] Deal  [+/-] Opponents ({})",
+                    view.key(Action::Deal),
+                    game.opponents()
+                )),
+                Line::from(format!(
+                    "Blinds ${}/${}, buy-in ${}",
+                    holdem::SMALL_BLIND,
+                    holdem::BIG_BLIND,
+                    holdem::BUY_IN
+                )),
+                Line::from(format!(
+                    "[ESC] Lobby (cash out)  [{}] Quit",
+                    view.key(Action::Quit)
+                )),
+            ]
+        };
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            rows[0],
+        );
+        let shown = rows[1].height.saturating_sub(2) as usize;
+        let log: Vec<Line> = game.log[game.log.len().saturating_sub(shown)..]
+            .iter()
+            .map(|entry| Line::from(entry.clone()))
+            .collect();
+        f.render_widget(
+            Paragraph::new(log).block(theme.panel().title("Hand")),
+            rows[1],
+        );
+    }
+
+    /// Fixed letters for the player's decisions ([F]old, [C]heck or call,
+    /// [R]aise, [A]ll in), the deal key between hands, and [+/-] for the
+    /// raise size or, between hands, the number of opponents.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        let game = &mut self.game;
+        if game.to_act != Some(0) {
+            match code {
+                KeyCode::Esc => return TableAction::Lobby,
+                KeyCode::Char('+' | '=') => game.set_opponents(game.opponents() + 1),
+                KeyCode::Char('-') => game.set_opponents(game.opponents() - 1),
+                _ if ctx.is_deal(code) => self.deal(ctx),
+                _ => {}
+            }
+            return TableAction::Stay;
+        }
+        let raise_to = self
+            .raise_to
+            .clamp(game.min_raise_to(0), game.max_raise_to(0));
+        let action = match code {
+            KeyCode::Char('f') => holdem::Action::Fold,
+            KeyCode::Char('c') if game.to_call(0) > Money::ZERO => holdem::Action::Call,
+            KeyCode::Char('c') => holdem::Action::Check,
+            KeyCode::Char('r') => holdem::Action::Raise(raise_to),
+            KeyCode::Char('a') => holdem::Action::Raise(game.max_raise_to(0)),
+            KeyCode::Char('+' | '=') => {
+                self.raise_to = raise_to + holdem::BIG_BLIND;
+                return TableAction::Stay;
+            }
+            KeyCode::Char('-') => {
+                self.raise_to = raise_to - holdem::BIG_BLIND;
+                return TableAction::Stay;
+            }
+            _ => return TableAction::Stay,
+        };
+        let result = game.act(action).and_then(|_| game.play_bots());
+        self.raise_to = Money::ZERO;
+        if let Err(e) = result {
+            ctx.say(format!("Can't do that: {}", e));
+        }
+        TableAction::Stay
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.game
+            .in_progress()
+            .then(|| format!("Finish the {} hand first", self.name()))
+    }
+
+    /// Cashes the player's chips out.
+    fn settle(&mut self) -> Money {
+        std::mem::take(&mut self.game.seats[0].chips)
+    }
+}
+
+/// The craps table. Each bet's stake comes out of the bankroll when it is
+/// made, and what a roll returns goes straight back.
+pub struct CrapsTable {
+    game: CrapsGame,
+    /// [A] or [O] was pressed and the number or line it goes on is next.
+    pending: Option<char>,
+    /// Bets the last roll decided.
+    last: Vec<craps::Resolution>,
+    /// One line per roll, oldest first.
+    log: Vec<String>,
+}
+
+impl CrapsTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        let mut game = CrapsGame::new(ctx.limits);
+        if let Some(seed) = ctx.seed {
+            game.set_rng(baccarat::seeded_rng(seed));
+        }
+        Ok(Box::new(Self {
+            game,
+            pending: None,
+            last: Vec::new(),
+            log: Vec::new(),
+        }))
+    }
+
+    /// Puts one chip on `bet`, out of the bankroll. Odds take at most what
+    /// is left under the 3-4-5x limit.
+    fn bet(&mut self, bet: craps::Bet, ctx: &mut TableContext) {
+        let mut stake = Money::from_dollars(ctx.limits.clamp(*ctx.selected_chip));
+        if matches!(
+            bet,
+            craps::Bet::PassOdds | craps::Bet::DontPassOdds | craps::Bet::ComeOdds(_)
+        ) {
+            let room = self.game.max_odds(bet) - self.game.stake(bet);
+            if room > Money::ZERO {
+                stake = stake.min(room);
+            }
+        }
+        if stake > *ctx.balance {
+            ctx.say(format!(
+                "${} bet is more than the ${} balance",
+                stake, ctx.balance
+            ));
+            return;
+        }
+        match self.game.place(bet, stake) {
+            Ok(()) => *ctx.balance -= stake,
+            Err(e) => ctx.say(format!("Can't bet: {}", e)),
+        }
+    }
+
+    /// Returns every place, field and odds bet to the bankroll.
+    fn take_down(&mut self, ctx: &mut TableContext) {
+        let bets: Vec<craps::Bet> = self
+            .game
+            .bets()
+            .map(|(bet, _)| bet)
+            .filter(|bet| {
+                matches!(
+                    bet,
+                    craps::Bet::Place(_)
+                        | craps::Bet::Field
+                        | craps::Bet::PassOdds
+                        | craps::Bet::DontPassOdds
+                        | craps::Bet::ComeOdds(_)
+                )
+            })
+            .collect();
+        let returned: Money = bets
+            .into_iter()
+            .filter_map(|bet| self.game.take_down(bet).ok())
+            .sum();
+        *ctx.balance += returned;
+        ctx.say(format!("${} taken down", returned));
+    }
+
+    /// Rolls, pays what the roll returns into the bankroll, and logs it.
+    fn roll(&mut self, ctx: &mut TableContext) {
+        let point = self.game.point;
+        let (roll, resolutions) = self.game.roll();
+        let total = roll.total();
+        let call = match (point, self.game.point) {
+            (None, Some(point)) => format!("point is {}", point),
+            (None, None) if matches!(total, 7 | 11) => "winner".to_string(),
+            (None, None) => "craps".to_string(),
+            (Some(_), None) if total == 7 => "seven out".to_string(),
+            (Some(_), None) => "point made".to_string(),
+            (Some(_), Some(_)) => "no decision".to_string(),
+        };
+        self.log.push(format!(
+            "{} ({}-{}): {}",
+            total, roll.dice[0], roll.dice[1], call
+        ));
+        let returned: Money = resolutions
+            .iter()
+            .map(|resolution| resolution.returned)
+            .sum();
+        self.last = resolutions;
+        *ctx.balance += returned;
+    }
+}
+
+impl CasinoGame for CrapsTable {
+    fn game(&self) -> Game {
+        Game::Craps
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        format!(
+            "CRAPS - {}  |  3-4-5x odds  |  ${}-${}",
+            self.game
+                .point
+                .map_or("Come-out roll".to_string(), |point| format!(
+                    "Point is {}",
+                    point
+                )),
+            view.limits.min_bet,
+            view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+
+        // Dice on the left, the layout on the right
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(24), Constraint::Min(0)])
+            .split(chunks[1]);
+        let mut dice = vec![Line::from("")];
+        match game.rolls.last() {
+            Some(roll) => {
+                let [first, second] = roll.dice.map(die_art);
+                dice.extend(
+                    first
+                        .iter()
+                        .zip(second.iter())
+                        .map(|(a, b)| Line::from(format!(" {} {}", a, b))),
+                );
+                let total = roll.total();
+                let caption = match total {
+                    7 => "Seven".to_string(),
+                    4 | 6 | 8 | 10 if roll.is_hard() => format!("Hard {}", total),
+                    _ => total.to_string(),
+                };
+                dice.push(
+                    Line::from(Span::styled(
+                        caption,
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .alignment(Alignment::Center),
+                );
+            }
+            None => dice.push(Line::from("No roll yet").alignment(Alignment::Center)),
+        }
+        f.render_widget(
+            Paragraph::new(dice).block(theme.panel().title("DICE")),
+            panels[0],
+        );
+
+        let stake = |bet| match game.stake(bet) {
+            stake if stake > Money::ZERO => format!("${}", stake),
+            _ => "·".to_string(),
+        };
+        let row = |label: &str, cell: &dyn Fn(u8) -> String| {
+            let mut spans = vec![Span::raw(format!("{:<11}", label))];
+            spans.extend(craps::POINTS.iter().map(|&number| {
+                let style = if game.point == Some(number) {
+                    Style::default().fg(theme.highlight)
+                } else {
+                    Style::default()
+                };
+                Span::styled(format!("{:^9}", cell(number)), style)
+            }));
+            Line::from(spans)
+        };
+        let odds = |bet| match game.stake(bet) {
+            odds if odds > Money::ZERO => format!("  odds ${}", odds),
+            _ => String::new(),
+        };
+        let felt = vec![
+            row("", &|number| number.to_string()),
+            row("Point", &|number| {
+                if game.point == Some(number) {
+                    "ON".to_string()
+                } else {
+                    String::new()
+                }
+            }),
+            row("Place", &|number| stake(craps::Bet::Place(number))),
+            row(
+                "Come",
+                &|number| match game.stake(craps::Bet::ComeOdds(number)) {
+                    odds if odds > Money::ZERO => {
+                        format!("{}+{}", stake(craps::Bet::ComePoint(number)), odds)
+                    }
+                    _ => stake(craps::Bet::ComePoint(number)),
+                },
+            ),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "Pass Line {}{}",
+                        stake(craps::Bet::PassLine),
+                        odds(craps::Bet::PassOdds)
+                    ),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw("    "),
+                Span::styled(
+                    format!(
+                        "Don't Pass {}{}",
+                        stake(craps::Bet::DontPass),
+                        odds(craps::Bet::DontPassOdds)
+                    ),
+                    Style::default().fg(theme.bet),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    format!("Come {}", stake(craps::Bet::Come)),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw("    "),
+                Span::styled(
+                    format!("Field {}", stake(craps::Bet::Field)),
+                    Style::default().fg(theme.side_bet),
+                ),
+                Span::styled(
+                    "  2 3 4 9 10 11 12, 2 pays double, 12 triple",
+                    Style::default().fg(theme.muted),
+                ),
+            ]),
+        ];
+        let title = match game.point {
+            Some(point) => format!("LAYOUT - point {}", point),
+            None => "LAYOUT - come-out".to_string(),
+        };
+        f.render_widget(
+            Paragraph::new(felt).block(theme.panel().title(title)),
+            panels[1],
+        );
+
+        let chip = Money::from_dollars(view.limits.clamp(view.selected_chip));
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+                Span::raw("  |  On the layout: "),
+                Span::styled(
+                    format!("${}", game.total_on_table()),
+                    Style::default().fg(theme.bet),
+                ),
+            ]),
+            Line::from(format!("Bet: ${}  (chip ${})", chip, view.selected_chip)),
+        ];
+        if !self.last.is_empty() {
+            let net: Money = self
+                .last
+                .iter()
+                .map(|resolution| match resolution.outcome {
+                    craps::Outcome::Won(amount) => amount,
+                    craps::Outcome::Lost => -resolution.stake,
+                    _ => Money::ZERO,
+                })
+                .sum();
+            info.push(Line::from(format!("Last roll {:+}", net)));
+        }
+        info.extend(self.last.iter().map(|resolution| {
+            let color = match resolution.outcome {
+                craps::Outcome::Won(_) => theme.highlight,
+                craps::Outcome::Lost => theme.alert,
+                _ => theme.muted,
+            };
+            Line::from(Span::styled(
+                format!("  {}", resolution),
+                Style::default().fg(color),
+            ))
+        }));
+        f.render_widget(
+            Paragraph::new(info).block(theme.panel().title(view.panel_title("Craps"))),
+            chunks[2],
+        );
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)])
+            .split(chunks[3]);
+        let bets = match self.pending {
+            Some('a') => "Place which number? [4] [5] [6] [8] [9] [0] for 10".to_string(),
+            Some(_) => "Odds behind? [P] Pass Line  [D] Don't Pass  [4-0] a come point".to_string(),
+            None => "[P] Pass Line  [D] Don't Pass  [C] Come  [F] Field  [A] Place".to_string(),
+        };
+        let controls = vec![
+            Line::from(bets),
+            Line::from("[O] Odds  [X] Take down place, field and odds"),
+            Line::from(format!(
+                "[1-5] Select chip  [{}] Roll",
+                view.key(Action::Deal)
+            )),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            rows[0],
+        );
+        let shown = rows[1].height.saturating_sub(2) as usize;
+        let log: Vec<Line> = self.log[self.log.len().saturating_sub(shown)..]
+            .iter()
+            .map(|entry| Line::from(entry.clone()))
+            .collect();
+        f.render_widget(
+            Paragraph::new(log).block(theme.panel().title("Rolls")),
+            rows[1],
+        );
+    }
+
+    /// Fixed letters for the line, come and field bets, [A] or [O] followed
+    /// by a number (or [P]/[D] for line odds) for place bets and odds, and
+    /// the deal key to roll.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        if let Some(prefix) = self.pending.take() {
+            let number = match code {
+                KeyCode::Char('0') => Some(10),
+                KeyCode::Char(c @ ('4' | '5' | '6' | '8' | '9')) => Some(c as u8 - b'0'),
+                _ => None,
+            };
+            let bet = match (prefix, code, number) {
+                ('a', _, Some(number)) => craps::Bet::Place(number),
+                ('o', KeyCode::Char('p'), _) => craps::Bet::PassOdds,
+                ('o', KeyCode::Char('d'), _) => craps::Bet::DontPassOdds,
+                ('o', _, Some(number)) => craps::Bet::ComeOdds(number),
+                _ => return TableAction::Stay,
+            };
+            self.bet(bet, ctx);
+            return TableAction::Stay;
+        }
+        let bet = match code {
+            KeyCode::Char('p') => craps::Bet::PassLine,
+            KeyCode::Char('d') => craps::Bet::DontPass,
+            KeyCode::Char('c') => craps::Bet::Come,
+            KeyCode::Char('f') => craps::Bet::Field,
+            KeyCode::Char(c @ ('a' | 'o')) => {
+                self.pending = Some(c);
+                return TableAction::Stay;
+            }
+            KeyCode::Char('x') => {
+                self.take_down(ctx);
+                return TableAction::Stay;
+            }
+            KeyCode::Char(c @ '1'..='5') => {
+                ctx.select_chip(c);
+                return TableAction::Stay;
+            }
+            KeyCode::Esc => {
+                if self.game.total_on_table() > Money::ZERO {
+                    ctx.say("Bets are still on the layout");
+                    return TableAction::Stay;
+                }
+                return TableAction::Lobby;
+            }
+            _ if ctx.is_deal(code) => {
+                self.roll(ctx);
+                return TableAction::Stay;
+            }
+            _ => return TableAction::Stay,
+        };
+        self.bet(bet, ctx);
+        TableAction::Stay
+    }
+
+    fn busy(&self) -> Option<String> {
+        (self.game.total_on_table() > Money::ZERO)
+            .then(|| "Bets are still on the craps layout".to_string())
+    }
+}
+
+/// The roulette table. Stakes come out of the bankroll as they go on the
+/// layout, and a spin's payouts go back once the ball stops.
+pub struct RouletteTable {
+    game: RouletteGame,
+    /// The wheel turning, or stopped on the last result.
+    spin: Option<WheelAnimation>,
+    next_step: Instant,
+    /// The last spin's bets, shown once the ball has stopped.
+    report: Option<SettlementReport>,
+    /// Bet being typed after [N], such as "17/20".
+    entry: Option<String>,
+    /// [D] or [C] was pressed and the dozen or column is next.
+    pending: Option<char>,
+}
+
+impl RouletteTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        let mut game = RouletteGame::new(Wheel::European, ctx.limits);
+        if let Some(seed) = ctx.seed {
+            game.set_rng(baccarat::seeded_rng(seed));
+        }
+        Ok(Box::new(Self {
+            game,
+            spin: None,
+            next_step: Instant::now(),
+            report: None,
+            entry: None,
+            pending: None,
+        }))
+    }
+
+    fn spinning(&self) -> bool {
+        self.spin.as_ref().is_some_and(|spin| !spin.is_complete())
+    }
+
+    /// Puts the selected chip on `bet`, out of the bankroll.
+    fn bet(&mut self, bet: roulette::Bet, ctx: &mut TableContext) {
+        let stake = Money::from_dollars(ctx.limits.clamp(*ctx.selected_chip));
+        if stake > *ctx.balance {
+            ctx.say(format!(
+                "${} bet is more than the ${} balance",
+                stake, ctx.balance
+            ));
+            return;
+        }
+        match self.game.place(bet, stake) {
+            Ok(()) => *ctx.balance -= stake,
+            Err(e) => ctx.say(format!("Can't bet: {}", e)),
+        }
+    }
+
+    /// Spins the wheel. The result is settled at once but kept back, with
+    /// its payout, until the animation stops on it.
+    fn spin(&mut self, ctx: &mut TableContext) {
+        match self.game.spin() {
+            Ok((pocket, report)) => {
+                self.spin = Some(WheelAnimation::new(self.game.wheel(), pocket));
+                self.next_step = Instant::now();
+                self.report = Some(report);
+            }
+            Err(e) => ctx.say(format!("Can't spin: {}", e)),
+        }
+    }
+}
+
+/// How a pocket's number is written on the layout: red pockets in the red
+/// suit color, and the zeros on the felt, as on the wheel.
+fn pocket_color(pocket: u8, theme: &Theme) -> Style {
+    match roulette::color(pocket) {
+        PocketColor::Red => Style::default().fg(theme.red_suit),
+        PocketColor::Black => Style::default(),
+        PocketColor::Green => Style::default().bg(theme.felt),
+    }
+}
+
+impl CasinoGame for RouletteTable {
+    fn game(&self) -> Game {
+        Game::Roulette
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        let wheel = self.game.wheel();
+        format!(
+            "ROULETTE - {} wheel  |  House edge {:.2}%  |  ${}-${}",
+            wheel.name(),
+            wheel.house_edge() * 100.0,
+            view.limits.min_bet,
+            view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let wheel = game.wheel();
+
+        // The wheel above the layout
+        let panels = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)])
+            .split(chunks[1]);
+        f.render_widget(
+            CardRenderer::create_wheel_display(wheel, self.spin.as_ref(), panels[0].width, theme),
+            panels[0],
+        );
+
+        // Three rows of twelve, 3 6 9 ... 36 along the top, with the zeros on
+        // the left; straight-up bets are underlined and the winner reversed
+        let winner = self
+            .spin
+            .as_ref()
+            .filter(|spin| spin.is_complete())
+            .map(|spin| spin.result);
+        let pocket_style = |pocket: u8| {
+            let mut style = pocket_color(pocket, theme);
+            if game.stake(roulette::Bet::Straight(pocket)) > Money::ZERO {
+                style = style.add_modifier(Modifier::UNDERLINED | Modifier::BOLD);
+            }
+            if winner == Some(pocket) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            style
+        };
+        let zeros: [Option<u8>; 3] = match wheel {
+            Wheel::European => [None, Some(0), None],
+            Wheel::American => [Some(0), None, Some(roulette::DOUBLE_ZERO)],
+        };
+        let mut felt: Vec<Line> = zeros
+            .iter()
+            .enumerate()
+            .map(|(row, zero)| {
+                let mut spans = vec![match zero {
+                    Some(pocket) => Span::styled(
+                        format!("{:^4}", roulette::pocket_name(*pocket)),
+                        pocket_style(*pocket),
+                    ),
+                    None => Span::raw("    "),
+                }];
+                spans.push(Span::raw("│"));
+                spans.extend((0..12).map(|street| {
+                    let pocket = street * 3 + 3 - row as u8;
+                    Span::styled(format!("{:^4}", pocket), pocket_style(pocket))
+                }));
+                spans.push(Span::styled(" │ 2:1", Style::default().fg(theme.muted)));
+                Line::from(spans)
+            })
+            .collect();
+        felt.push(Line::from(Span::styled(
+            "    │  1st 12         2nd 12         3rd 12      │  1-18  EVEN  RED  BLACK  ODD  19-36",
+            Style::default().fg(theme.muted),
+        )));
+        f.render_widget(
+            Paragraph::new(felt).block(theme.panel().title("LAYOUT")),
+            panels[1],
+        );
+
+        let chip = Money::from_dollars(view.limits.clamp(view.selected_chip));
+        let mut info = vec![Line::from(vec![
+            Span::raw("Balance: "),
+            Span::styled(
+                format!("${}", view.balance),
+                Style::default().fg(theme.balance),
+            ),
+            Span::raw("  |  On the layout: "),
+            Span::styled(
+                format!("${}", game.total_bet()),
+                Style::default().fg(theme.bet),
+            ),
+            Span::raw(format!("  |  Chip ${}", chip)),
+        ])];
+        let bets: Vec<String> = game
+            .bets()
+            .map(|(bet, stake)| format!("{} ${}", bet, stake))
+            .collect();
+        if !bets.is_empty() {
+            info.push(Line::from(Span::styled(
+                format!("Bets: {}", bets.join(", ")),
+                Style::default().fg(theme.bet),
+            )));
+        }
+        if let Some(report) = self.report.as_ref().filter(|_| winner.is_some()) {
+            info.push(Line::from(format!("Last spin {:+}", report.net())));
+            let lines: Vec<String> = report.bets.iter().map(|bet| bet.describe()).collect();
+            info.push(Line::from(lines.join(", ")));
+        }
+        let mut history = vec![Span::raw("History: ")];
+        let settled = game.history.len() - usize::from(winner.is_none() && self.spin.is_some());
+        history.extend(
+            game.history[..settled]
+                .iter()
+                .rev()
+                .take(20)
+                .map(|&pocket| {
+                    Span::styled(
+                        format!("{} ", roulette::pocket_name(pocket)),
+                        pocket_color(pocket, theme),
+                    )
+                }),
+        );
+        info.push(Line::from(history));
+        f.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(theme.panel().title(view.panel_title("Roulette"))),
+            chunks[2],
+        );
+
+        let first = match (&self.entry, self.pending) {
+            (Some(entry), _) => format!("Bet on: {}_  [ENTER] Place  [ESC] Cancel", entry),
+            (None, Some('d')) => "Which dozen? [1] 1-12  [2] 13-24  [3] 25-36".to_string(),
+            (None, Some(_)) => {
+                "Which column? [1] 1, 4 ... 34  [2] 2, 5 ... 35  [3] 3, 6 ... 36".to_string()
+            }
+            (None, None) => {
+                "[R] Red  [B] Black  [O] Odd  [E] Even  [L] 1-18  [H] 19-36  [D] Dozen  [C] Column"
+                    .to_string()
+            }
+        };
+        let controls = vec![
+            Line::from(first),
+            Line::from(
+                "[N] Numbers: 17, 0/2 (split), 13-15 (street), 13/14/16/17 (corner), 13-18 (six line)",
+            ),
+            Line::from(format!(
+                "[1-5] Select chip  [X] Clear bets  [W] {} wheel  [{}] Spin",
+                match wheel {
+                    Wheel::European => "American",
+                    Wheel::American => "European",
+                },
+                view.key(Action::Deal)
+            )),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// Fixed letters for the outside bets, [D] or [C] and a digit for a
+    /// dozen or column, [N] to type any inside bet, and the deal key to
+    /// spin. Nothing works while the wheel turns.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        if self.spinning() {
+            return TableAction::Stay;
+        }
+        *ctx.status = None;
+        if let Some(entry) = &mut self.entry {
+            match code {
+                KeyCode::Char(c) if c.is_ascii_digit() || c == '/' || c == '-' => entry.push(c),
+                KeyCode::Backspace => {
+                    entry.pop();
+                }
+                KeyCode::Esc => self.entry = None,
+                KeyCode::Enter => {
+                    let parsed = entry.parse::<roulette::Bet>();
+                    self.entry = None;
+                    match parsed {
+                        Ok(bet) => self.bet(bet, ctx),
+                        Err(e) => ctx.say(e),
+                    }
+                }
+                _ => {}
+            }
+            return TableAction::Stay;
+        }
+        if let Some(prefix) = self.pending.take() {
+            if let KeyCode::Char(c @ '1'..='3') = code {
+                let n = c as u8 - b'0';
+                self.bet(
+                    if prefix == 'd' {
+                        roulette::Bet::Dozen(n)
+                    } else {
+                        roulette::Bet::Column(n)
+                    },
+                    ctx,
+                );
+            }
+            return TableAction::Stay;
+        }
+        let bet = match code {
+            KeyCode::Char('r') => roulette::Bet::Red,
+            KeyCode::Char('b') => roulette::Bet::Black,
+            KeyCode::Char('o') => roulette::Bet::Odd,
+            KeyCode::Char('e') => roulette::Bet::Even,
+            KeyCode::Char('l') => roulette::Bet::Low,
+            KeyCode::Char('h') => roulette::Bet::High,
+            KeyCode::Char(c @ ('d' | 'c')) => {
+                self.pending = Some(c);
+                return TableAction::Stay;
+            }
+            KeyCode::Char('n') => {
+                self.entry = Some(String::new());
+                return TableAction::Stay;
+            }
+            KeyCode::Char('x') => {
+                *ctx.balance += self.game.clear();
+                return TableAction::Stay;
+            }
+            KeyCode::Char('w') => {
+                let wheel = match self.game.wheel() {
+                    Wheel::European => Wheel::American,
+                    Wheel::American => Wheel::European,
+                };
+                match self.game.set_wheel(wheel) {
+                    Ok(()) => self.spin = None,
+                    Err(e) => ctx.say(format!("Can't change wheels: {}", e)),
+                }
+                return TableAction::Stay;
+            }
+            KeyCode::Char(c @ '1'..='5') => {
+                ctx.select_chip(c);
+                return TableAction::Stay;
+            }
+            KeyCode::Esc => {
+                if self.game.total_bet() > Money::ZERO {
+                    ctx.say("Clear the bets with [X] first");
+                    return TableAction::Stay;
+                }
+                return TableAction::Lobby;
+            }
+            _ if ctx.is_deal(code) => {
+                self.spin(ctx);
+                return TableAction::Stay;
+            }
+            _ => return TableAction::Stay,
+        };
+        self.bet(bet, ctx);
+        TableAction::Stay
+    }
+
+    /// Turns the wheel on by every step that has come due since the last
+    /// frame, and pays the spin out once it stops.
+    fn tick(&mut self, ctx: &mut TableContext) {
+        let Some(spin) = self.spin.as_mut().filter(|spin| !spin.is_complete()) else {
+            return;
+        };
+        while !spin.is_complete() && Instant::now() >= self.next_step {
+            spin.step();
+            self.next_step += spin.interval();
+        }
+        if spin.is_complete() {
+            *ctx.balance += self
+                .report
+                .as_ref()
+                .map_or(Money::ZERO, |report| report.total_payout());
+        }
+    }
+
+    fn typing(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.spinning()
+            .then(|| "Wait for the wheel to stop".to_string())
+    }
+
+    /// Bets still on the layout go back to the bankroll.
+    fn settle(&mut self) -> Money {
+        self.game.clear()
+    }
+}
+
+/// The Casino War table. The ante and tie bet come out of the bankroll on
+/// the deal, the raise when the player goes to war.
+pub struct WarTable {
+    game: WarGame,
+    /// Ante for the next deal in whole dollars.
+    bet: i32,
+    /// Tie bet for the next deal, or zero.
+    tie_bet: Money,
+    /// When the next card comes out.
+    next_card: Instant,
+}
+
+impl WarTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        Ok(Box::new(Self {
+            game: WarGame::new(WAR_DECKS),
+            bet: ctx.limits.clamp(*ctx.selected_chip),
+            tie_bet: Money::ZERO,
+            next_card: Instant::now(),
+        }))
+    }
+
+    /// Takes the ante and tie bet from the bankroll and starts the deal.
+    fn deal(&mut self, ctx: &mut TableContext) {
+        let ante = Money::from_dollars(self.bet);
+        let stake = ante + self.tie_bet;
+        if stake > *ctx.balance {
+            ctx.say(format!(
+                "${} in bets is more than the ${} balance",
+                stake, ctx.balance
+            ));
+            return;
+        }
+        match self.game.deal(ante, self.tie_bet) {
+            Ok(()) => {
+                self.next_card = Instant::now();
+                *ctx.balance -= stake;
+            }
+            Err(e) => ctx.say(format!("Error: {}", e)),
+        }
+    }
+
+    /// Goes to war, raising the ante from the bankroll, or surrenders.
+    fn decide(&mut self, war: bool, ctx: &mut TableContext) {
+        let raise = if war { self.game.ante } else { Money::ZERO };
+        if raise > *ctx.balance {
+            ctx.say(format!("Not enough balance to raise ${}", raise));
+            return;
+        }
+        *ctx.balance -= raise;
+        self.next_card = Instant::now() + CARD_REVEAL_INTERVAL;
+        let result = self.game.decide(war);
+        self.after_card(result, ctx);
+    }
+
+    /// Reports a failed deal, or pays out once the hand has settled.
+    fn after_card(&mut self, result: Result<GamePhase, war::WarError>, ctx: &mut TableContext) {
+        match result {
+            Ok(GamePhase::Settled) => *ctx.balance += self.game.returned(),
+            Ok(_) => {}
+            Err(e) => ctx.say(format!("Hand not dealt: {}", e)),
+        }
+    }
+}
+
+impl CasinoGame for WarTable {
+    fn game(&self) -> Game {
+        Game::War
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        format!(
+            "CASINO WAR - {} decks  |  Tie bet pays 10:1  |  ${}-${}",
+            WAR_DECKS, view.limits.min_bet, view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        for (index, (cards, title)) in [(&game.player, "YOU"), (&game.dealer, "DEALER")]
+            .into_iter()
+            .enumerate()
+        {
+            let score = HandScore::Named(cards.last().map_or("No card", war::rank_name));
+            f.render_widget(
+                CardRenderer::create_partial_hand_display(
+                    cards,
+                    title.to_string(),
+                    score,
+                    cards.len(),
+                    view.card_back,
+                    panels[index].width,
+                    theme,
+                ),
+                panels[index],
+            );
+        }
+
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Ante: "),
+                Span::styled(
+                    format!("${}  (chip ${})", self.bet, view.selected_chip),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw("  Tie bet: "),
+                Span::styled(
+                    if self.tie_bet > Money::ZERO {
+                        format!("${}", self.tie_bet)
+                    } else {
+                        "off".to_string()
+                    },
+                    Style::default().fg(theme.side_bet),
+                ),
+            ]),
+        ];
+        if game.raise > Money::ZERO {
+            info.push(Line::from(format!("At war: raised ${}", game.raise)));
+        }
+        if !game.burned.is_empty() {
+            let burned: Vec<String> = game
+                .burned
+                .iter()
+                .map(CardRenderer::render_mini_card)
+                .collect();
+            info.push(Line::from(format!("Burned: {}", burned.join(""))));
+        }
+        if game.phase == GamePhase::Settled && !game.player.is_empty() {
+            let result = if game.surrendered {
+                "surrendered half the ante".to_string()
+            } else {
+                match (game.raise > Money::ZERO, game.player_wins()) {
+                    (true, Some(true))
+                        if game.player.last().map(war::rank)
+                            == game.dealer.last().map(war::rank) =>
+                    {
+                        "won the war on another tie, ante bonus paid".to_string()
+                    }
+                    (true, Some(true)) => "won the war".to_string(),
+                    (true, _) => "lost the war".to_string(),
+                    (false, Some(true)) => "high card wins".to_string(),
+                    (false, _) => "dealer's card is higher".to_string(),
+                }
+            };
+            let tie = if game.tie_bet > Money::ZERO && game.is_tie() {
+                ", tie bet pays 10:1"
+            } else {
+                ""
+            };
+            info.push(Line::from(format!(
+                "Last round {:+}: {}{}",
+                game.returned() - game.wagered(),
+                result,
+                tie
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(info).block(theme.panel().title(view.panel_title("Casino War"))),
+            chunks[2],
+        );
+
+        let moves = match game.phase {
+            GamePhase::DecisionRequired(_) => format!(
+                "A tie! [W] Go to war (raise ${})  [S] Surrender (take back ${})",
+                game.ante,
+                game.ante.percent(50)
+            ),
+            GamePhase::Settled => format!(
+                "[{}] Deal  [T] Tie bet {}",
+                view.key(Action::Deal),
+                if self.tie_bet > Money::ZERO {
+                    "off".to_string()
+                } else {
+                    format!("on (${})", view.limits.clamp_side(view.selected_chip))
+                }
+            ),
+            _ => "Dealing...".to_string(),
+        };
+        let controls = vec![
+            Line::from(moves),
+            Line::from("[1-5] Select chip  [+/-] Adjust"),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// [W]ar or [S]urrender on a tie; between hands the chip keys, [T] for
+    /// the tie bet, and the deal key.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        match self.game.phase {
+            GamePhase::DecisionRequired(_) => match code {
+                KeyCode::Char('w') => self.decide(true, ctx),
+                KeyCode::Char('s') => self.decide(false, ctx),
+                KeyCode::Esc => ctx.say("Go to war or surrender first"),
+                _ => {}
+            },
+            GamePhase::Settled => match code {
+                KeyCode::Esc => return TableAction::Lobby,
+                KeyCode::Char('t') => {
+                    self.tie_bet = if self.tie_bet > Money::ZERO {
+                        Money::ZERO
+                    } else {
+                        ctx.limits.clamp_side(*ctx.selected_chip)
+                    };
+                }
+                KeyCode::Char(c @ '1'..='5') => {
+                    if let Some(chip) = ctx.select_chip(c) {
+                        self.bet = ctx.limits.clamp(chip);
+                    }
+                }
+                KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                    self.bet = ctx.limits.step(self.bet, *ctx.selected_chip, c != '-')
+                }
+                _ if ctx.is_deal(code) => self.deal(ctx),
+                _ => {}
+            },
+            _ => {}
+        }
+        TableAction::Stay
+    }
+
+    /// Deals the next war card once its turn comes round.
+    fn tick(&mut self, ctx: &mut TableContext) {
+        let dealing =
+            self.game.in_progress() && !matches!(self.game.phase, GamePhase::DecisionRequired(_));
+        if !dealing || Instant::now() < self.next_card {
+            return;
+        }
+        self.next_card = Instant::now() + CARD_REVEAL_INTERVAL;
+        let result = self.game.advance();
+        self.after_card(result, ctx);
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.game
+            .in_progress()
+            .then(|| format!("Finish the {} hand first", self.name()))
+    }
+}
+
+/// The Sic Bo table. Stakes come out of the bankroll as they go on the
+/// grid, and a roll's payouts go back once the dice stop.
+pub struct SicBoTable {
+    game: SicBoGame,
+    /// Row and column of the grid cell under the cursor.
+    cursor: (usize, usize),
+    /// The last roll's bets, shown once the dice have stopped.
+    report: Option<SettlementReport>,
+    /// When the dice stop tumbling, while they still are.
+    rolling: Option<Instant>,
+}
+
+impl SicBoTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        let mut game = SicBoGame::new(ctx.limits);
+        if let Some(seed) = ctx.seed {
+            game.set_rng(baccarat::seeded_rng(seed));
+        }
+        Ok(Box::new(Self {
+            game,
+            cursor: (0, 0),
+            report: None,
+            rolling: None,
+        }))
+    }
+
+    fn bet_at_cursor(&self) -> sicbo::Bet {
+        let (row, column) = self.cursor;
+        sicbo::Bet::grid()[row][column]
+    }
+
+    /// Puts the selected chip on `bet`, out of the bankroll.
+    fn bet(&mut self, bet: sicbo::Bet, ctx: &mut TableContext) {
+        let stake = Money::from_dollars(ctx.limits.clamp(*ctx.selected_chip));
+        if stake > *ctx.balance {
+            ctx.say(format!(
+                "${} bet is more than the ${} balance",
+                stake, ctx.balance
+            ));
+            return;
+        }
+        match self.game.place(bet, stake) {
+            Ok(()) => *ctx.balance -= stake,
+            Err(e) => ctx.say(format!("Can't bet: {}", e)),
+        }
+    }
+}
+
+impl CasinoGame for SicBoTable {
+    fn game(&self) -> Game {
+        Game::SicBo
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        format!(
+            "SIC BO - Macau pay table  |  ${}-${}",
+            view.limits.min_bet, view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(33), Constraint::Min(0)])
+            .split(chunks[1]);
+
+        // The dice tumble until the roll is paid, then show where they landed
+        let shown = if self.rolling.is_some() {
+            Some(sicbo::Dice::random(&mut rand::rng()))
+        } else {
+            game.history.last().copied()
+        };
+        let mut dice = vec![Line::from("")];
+        match shown {
+            Some(shown) => {
+                let art = shown.0.map(die_art);
+                dice.extend((0..5).map(|row| {
+                    Line::from(format!(" {} {} {}", art[0][row], art[1][row], art[2][row]))
+                }));
+                let caption = if self.rolling.is_some() {
+                    "Rolling...".to_string()
+                } else if shown.is_triple() {
+                    format!("Triple {}", shown.0[0])
+                } else {
+                    format!(
+                        "{} - {}",
+                        shown.total(),
+                        if shown.total() <= 10 { "Small" } else { "Big" }
+                    )
+                };
+                dice.push(
+                    Line::from(Span::styled(
+                        caption,
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .alignment(Alignment::Center),
+                );
+            }
+            None => dice.push(Line::from("No roll yet").alignment(Alignment::Center)),
+        }
+        f.render_widget(
+            Paragraph::new(dice).block(theme.panel().title("DICE")),
+            panels[0],
+        );
+
+        // One line per row of the grid; the cursor cell is reversed, bets
+        // show their stake, and the last roll's winners are underlined
+        let last = game
+            .history
+            .last()
+            .copied()
+            .filter(|_| self.rolling.is_none());
+        let headers = [
+            "", "Doubles", "Triples", "Totals", "", "Combos", "", "Singles",
+        ];
+        let cell_width =
+            |cells: usize| ((panels[1].width as usize).saturating_sub(2 + 9) / cells).max(1);
+        let grid: Vec<Line> = sicbo::Bet::grid()
+            .iter()
+            .enumerate()
+            .map(|(row, bets)| {
+                let width = cell_width(bets.len());
+                let mut spans = vec![Span::styled(
+                    format!("{:<9}", headers[row]),
+                    Style::default().fg(theme.muted),
+                )];
+                spans.extend(bets.iter().enumerate().map(|(column, &bet)| {
+                    let label = match bet {
+                        sicbo::Bet::Small => "Small 4-10".to_string(),
+                        sicbo::Bet::Big => "Big 11-17".to_string(),
+                        sicbo::Bet::AnyTriple => "Any triple".to_string(),
+                        sicbo::Bet::Double(face) => format!("{} {}", face, face),
+                        sicbo::Bet::Triple(face) => format!("{} {} {}", face, face, face),
+                        sicbo::Bet::Total(total) | sicbo::Bet::Single(total) => total.to_string(),
+                        sicbo::Bet::Combination(a, b) => format!("{}-{}", a, b),
+                    };
+                    let stake = game.stake(bet);
+                    let mut style = Style::default();
+                    let text = if stake > Money::ZERO {
+                        style = style.fg(theme.bet).add_modifier(Modifier::BOLD);
+                        format!("{} ${}", label, stake)
+                    } else {
+                        label
+                    };
+                    if last.is_some_and(|dice| bet.pays(dice).is_some()) {
+                        style = style.add_modifier(Modifier::UNDERLINED);
+                    }
+                    if self.cursor == (row, column) {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    Span::styled(format!("{:^width$}", text, width = width), style)
+                }));
+                Line::from(spans)
+            })
+            .collect();
+        f.render_widget(
+            Paragraph::new(grid).block(theme.panel().title("GRID")),
+            panels[1],
+        );
+
+        let chip = Money::from_dollars(view.limits.clamp(view.selected_chip));
+        let cursor = self.bet_at_cursor();
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+                Span::raw("  |  On the grid: "),
+                Span::styled(
+                    format!("${}", game.total_bet()),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw(format!("  |  Chip ${}", chip)),
+            ]),
+            Line::from(format!(
+                "{} pays {}, house edge {:.2}%",
+                cursor,
+                cursor.odds(),
+                cursor.house_edge() * 100.0
+            )),
+        ];
+        if let Some(report) = self.report.as_ref().filter(|_| self.rolling.is_none()) {
+            info.push(Line::from(format!("Last roll {:+}", report.net())));
+            let lines: Vec<String> = report.bets.iter().map(|bet| bet.describe()).collect();
+            info.push(Line::from(lines.join(", ")));
+        }
+        let settled = game.history.len() - usize::from(self.rolling.is_some());
+        let history: Vec<String> = game.history[..settled]
+            .iter()
+            .rev()
+            .take(15)
+            .map(|dice| dice.total().to_string())
+            .collect();
+        info.push(Line::from(format!("History: {}", history.join(" "))));
+        f.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(theme.panel().title(view.panel_title("Sic Bo"))),
+            chunks[2],
+        );
+
+        let controls = vec![
+            Line::from(
+                "[←↑↓→] Move  [ENTER] Bet the chip  [BACKSPACE] Take the bet down  [X] Clear bets",
+            ),
+            Line::from(format!(
+                "[1-5] Select chip  [{}] Roll",
+                view.key(Action::Deal)
+            )),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// The arrows move around the grid, [ENTER] and [BACKSPACE] put a chip
+    /// on or take the bet off the cell under the cursor, and the deal key
+    /// rolls. Nothing works while the dice tumble.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        if self.rolling.is_some() {
+            return TableAction::Stay;
+        }
+        *ctx.status = None;
+        let grid = sicbo::Bet::grid();
+        let (row, column) = self.cursor;
+        match code {
+            KeyCode::Left => self.cursor.1 = column.saturating_sub(1),
+            KeyCode::Right => self.cursor.1 = (column + 1).min(grid[row].len() - 1),
+            KeyCode::Up | KeyCode::Down => {
+                let row = if code == KeyCode::Up {
+                    row.saturating_sub(1)
+                } else {
+                    (row + 1).min(grid.len() - 1)
+                };
+                // Keep roughly the same place across rows of different lengths
+                let column = column * grid[row].len() / grid[self.cursor.0].len();
+                self.cursor = (row, column.min(grid[row].len() - 1));
+            }
+            KeyCode::Enter => self.bet(self.bet_at_cursor(), ctx),
+            KeyCode::Backspace | KeyCode::Delete => {
+                *ctx.balance += self.game.take_down(self.bet_at_cursor())
+            }
+            KeyCode::Char('x') => *ctx.balance += self.game.clear(),
+            KeyCode::Char(c @ '1'..='5') => {
+                ctx.select_chip(c);
+            }
+            KeyCode::Esc => {
+                if self.game.total_bet() > Money::ZERO {
+                    ctx.say("Clear the bets with [X] first");
+                } else {
+                    return TableAction::Lobby;
+                }
+            }
+            _ if ctx.is_deal(code) => match self.game.roll() {
+                Ok((_, report)) => {
+                    self.report = Some(report);
+                    self.rolling = Some(Instant::now() + DICE_ROLL_TIME);
+                }
+                Err(e) => ctx.say(format!("Can't roll: {}", e)),
+            },
+            _ => {}
+        }
+        TableAction::Stay
+    }
+
+    /// Pays the roll out once the dice have stopped tumbling.
+    fn tick(&mut self, ctx: &mut TableContext) {
+        if self.rolling.is_none_or(|until| Instant::now() < until) {
+            return;
+        }
+        self.rolling = None;
+        *ctx.balance += self
+            .report
+            .as_ref()
+            .map_or(Money::ZERO, |report| report.total_payout());
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.rolling
+            .is_some()
+            .then(|| "Wait for the dice to stop".to_string())
+    }
+
+    /// Bets still on the grid go back to the bankroll.
+    fn settle(&mut self) -> Money {
+        self.game.clear()
+    }
+}
+
+/// The slots table. A spin's bet comes out of the bankroll when the reels
+/// start, and what it won goes back once the last reel stops.
+pub struct SlotsTable {
+    game: SlotsGame,
+    /// Every machine on the floor; [M] moves to the next.
+    machines: Vec<slots::Machine>,
+    machine: usize,
+    /// When the reels started, while they are still turning.
+    spinning: Option<Instant>,
+}
+
+impl SlotsTable {
+    /// Opens on the first machine in `TERMINAL_CASINO_SLOTS`, or the
+    /// built-in ones when that file can't be used.
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        let machines = match storage::load_or_builtin(
+            slots::MACHINES_PATH_VAR,
+            MachineBook::parse,
+            MachineBook::builtin,
+        ) {
+            Ok(book) => book.machines,
+            Err(e) => {
+                ctx.say(format!(
+                    "Slot machines not loaded ({}), using the built-in ones",
+                    e
+                ));
+                MachineBook::builtin().machines
+            }
+        };
+        let machine = machines
+            .first()
+            .cloned()
+            .ok_or("No slot machines are set up")?;
+        Ok(Box::new(Self {
+            game: SlotsGame::new(machine),
+            machines,
+            machine: 0,
+            spinning: None,
+        }))
+    }
+}
+
+impl CasinoGame for SlotsTable {
+    fn game(&self) -> Game {
+        Game::Slots
+    }
+
+    fn title(&self, _view: &TableView) -> String {
+        let machine = self.game.machine();
+        format!(
+            "SLOTS - {}  |  {} reels, {} lines  |  Return to player {:.2}%",
+            machine.name,
+            machine.reels.len(),
+            machine.paylines.len(),
+            machine.rtp() * 100.0
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let machine = game.machine();
+        let reels = machine.reels.len();
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(12 + 6 * reels as u16),
+            ])
+            .split(chunks[1]);
+
+        // Turning reels scroll down a symbol every REEL_STEP until their
+        // turn to stop; stopped reels show where the spin landed
+        let elapsed = self.spinning.map(|started| started.elapsed());
+        let last = game.history.last();
+        let stops: Vec<usize> = (0..reels)
+            .map(|reel| {
+                let landed = last.map_or(0, |spin| spin.stops[reel]);
+                match elapsed {
+                    Some(elapsed) if elapsed < REEL_STOP_INTERVAL * (reel as u32 + 1) => {
+                        let len = machine.reels[reel].len();
+                        let steps = (elapsed.as_millis() / REEL_STEP.as_millis()) as usize % len;
+                        (landed + len - steps) % len
+                    }
+                    _ => landed,
+                }
+            })
+            .collect();
+        let paying = machine.paying_symbols();
+        let winning = |reel: usize, row: usize| {
+            self.spinning.is_none()
+                && last.is_some_and(|spin| {
+                    spin.wins
+                        .iter()
+                        .any(|win| reel < win.count && machine.paylines[win.line][reel] == row)
+                })
+        };
+        let border = |left: &str, middle: &str, right: &str| {
+            Line::from(format!(
+                "{}{}{}",
+                left,
+                vec!["─".repeat(8); reels].join(middle),
+                right
+            ))
+        };
+        let mut window = vec![Line::from(""), border("┌", "┬", "┐")];
+        for row in 0..machine.rows {
+            let mut spans = vec![Span::raw("│")];
+            for (reel, &stop) in stops.iter().enumerate() {
+                let symbol = machine.symbol(reel, stop, row);
+                let mut style = match paying.iter().position(|&paid| paid == symbol) {
+                    Some(0) => Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                    Some(_) => Style::default(),
+                    None => Style::default().fg(theme.muted),
+                };
+                if winning(reel, row) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(Span::styled(format!("{:^8}", symbol), style));
+                spans.push(Span::raw("│"));
+            }
+            window.push(Line::from(spans));
+        }
+        window.push(border("└", "┴", "┘"));
+        f.render_widget(
+            Paragraph::new(window)
+                .alignment(Alignment::Center)
+                .block(theme.panel().title("REELS")),
+            panels[0],
+        );
+
+        let header: String = (1..=reels)
+            .map(|count| format!("{:>6}", format!("x{}", count)))
+            .collect();
+        let mut pays = vec![Line::from(Span::styled(
+            format!("{:<8}{}", "", header),
+            Style::default().fg(theme.muted),
+        ))];
+        pays.extend(paying.iter().map(|&symbol| {
+            let row: String = (1..=reels)
+                .map(|count| match machine.pays(symbol, count) {
+                    0 => format!("{:>6}", "-"),
+                    pays => format!("{:>6}", pays),
+                })
+                .collect();
+            Line::from(format!("{:<8}{}", symbol, row))
+        }));
+        f.render_widget(
+            Paragraph::new(pays).block(theme.panel().title("PAYS PER LINE BET")),
+            panels[1],
+        );
+
+        let mut info = vec![Line::from(vec![
+            Span::raw("Balance: "),
+            Span::styled(
+                format!("${}", view.balance),
+                Style::default().fg(theme.balance),
+            ),
+            Span::raw("  |  Bet: "),
+            Span::styled(
+                format!(
+                    "${} x {} lines = ${}",
+                    game.line_bet(),
+                    game.lines(),
+                    game.total_bet()
+                ),
+                Style::default().fg(theme.bet),
+            ),
+        ])];
+        match last {
+            _ if self.spinning.is_some() => info.push(Line::from("Spinning...")),
+            Some(spin) if spin.wins.is_empty() => {
+                info.push(Line::from(format!("Last spin {:+}", spin.net())))
+            }
+            Some(spin) => {
+                info.push(Line::from(Span::styled(
+                    format!("Last spin won ${} ({:+})", spin.payout(), spin.net()),
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                let wins: Vec<String> = spin
+                    .wins
+                    .iter()
+                    .map(|win| {
+                        format!(
+                            "line {}: {} {} pays ${}",
+                            win.line + 1,
+                            win.count,
+                            win.symbol,
+                            win.payout
+                        )
+                    })
+                    .collect();
+                info.push(Line::from(wins.join(", ")));
+            }
+            None => info.push(Line::from("No spins yet")),
+        }
+        let settled = &game.history[..game.history.len() - usize::from(self.spinning.is_some())];
+        let staked: Money = settled.iter().map(|spin| spin.stake()).sum();
+        let won: Money = settled.iter().map(|spin| spin.payout()).sum();
+        info.push(Line::from(format!(
+            "Spins: {}  |  Bet ${}  |  Won ${}  |  Net {:+}",
+            settled.len(),
+            staked,
+            won,
+            won - staked
+        )));
+        f.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(theme.panel().title(view.panel_title("Slots"))),
+            chunks[2],
+        );
+
+        let controls = vec![
+            Line::from(format!(
+                "[{}] Spin  [←/→] Line bet  [↑/↓] Lines played  [M] Next machine",
+                view.key(Action::Deal)
+            )),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// The arrows set the line bet and how many lines play, [M] moves to
+    /// the next machine, and the deal key spins. Nothing works while the
+    /// reels turn.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        if self.spinning.is_some() {
+            return TableAction::Stay;
+        }
+        *ctx.status = None;
+        let game = &mut self.game;
+        let bet = slots::LINE_BETS
+            .iter()
+            .position(|&bet| Money::from_dollars(bet) >= game.line_bet())
+            .unwrap_or(0);
+        match code {
+            KeyCode::Left => {
+                game.set_line_bet(Money::from_dollars(slots::LINE_BETS[bet.saturating_sub(1)]))
+            }
+            KeyCode::Right => game.set_line_bet(Money::from_dollars(
+                slots::LINE_BETS[(bet + 1).min(slots::LINE_BETS.len() - 1)],
+            )),
+            KeyCode::Up => game.set_lines(game.lines() + 1),
+            KeyCode::Down => game.set_lines(game.lines().saturating_sub(1)),
+            KeyCode::Char('m') => {
+                self.machine = (self.machine + 1) % self.machines.len();
+                let (line_bet, lines) = (game.line_bet(), game.lines());
+                *game = SlotsGame::new(self.machines[self.machine].clone());
+                game.set_line_bet(line_bet);
+                game.set_lines(lines);
+            }
+            KeyCode::Esc => return TableAction::Lobby,
+            _ if ctx.is_deal(code) => {
+                let stake = game.total_bet();
+                if stake > *ctx.balance {
+                    ctx.say(format!(
+                        "${} spin is more than the ${} balance",
+                        stake, ctx.balance
+                    ));
+                    return TableAction::Stay;
+                }
+                game.spin();
+                self.spinning = Some(Instant::now());
+                *ctx.balance -= stake;
+            }
+            _ => {}
+        }
+        TableAction::Stay
+    }
+
+    /// Pays the spin out once the last reel has stopped.
+    fn tick(&mut self, ctx: &mut TableContext) {
+        let reels = self.game.machine().reels.len() as u32;
+        if self
+            .spinning
+            .is_none_or(|started| started.elapsed() < REEL_STOP_INTERVAL * reels)
+        {
+            return;
+        }
+        self.spinning = None;
+        *ctx.balance += self
+            .game
+            .history
+            .last()
+            .map_or(Money::ZERO, |spin| spin.payout());
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.spinning
+            .is_some()
+            .then(|| "Wait for the reels to stop".to_string())
+    }
+}
+
+/// Both hands at a table poker game, named once they are face up, with
+/// only the dealer's first `dealer_shown` cards turned over.
+fn render_poker_hands(
+    f: &mut Frame,
+    area: Rect,
+    view: &TableView,
+    player: (&[Card], &'static str),
+    dealer: (&[Card], &'static str),
+    dealer_shown: usize,
+) {
+    let theme = &view.config.theme;
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let hands = [
+        (player, player.0.len(), "YOU"),
+        (dealer, dealer_shown, "DEALER"),
+    ];
+    for (index, ((cards, name), shown, title)) in hands.into_iter().enumerate() {
+        let score = HandScore::Named(if cards.is_empty() { "No cards" } else { name });
+        f.render_widget(
+            CardRenderer::create_partial_hand_display(
+                cards,
+                title.to_string(),
+                score,
+                shown,
+                view.card_back,
+                panels[index].width,
+                theme,
+            ),
+            panels[index],
+        );
+    }
+}
+
+/// The last hand's result and bets at a table poker game.
+fn poker_report_lines(report: &SettlementReport, result: String) -> Vec<Line<'static>> {
+    let bets: Vec<String> = report.bets.iter().map(|bet| bet.describe()).collect();
+    vec![
+        Line::from(format!("Last hand {:+}: {}", report.net(), result)),
+        Line::from(bets.join(", ")),
+    ]
+}
+
+/// The Caribbean Stud table. The ante and jackpot bet come out of the
+/// bankroll on the deal, the raise when the player raises.
+pub struct CaribbeanTable {
+    game: CaribbeanGame,
+    /// Ante for the next deal in whole dollars.
+    bet: i32,
+    /// The next deal carries the $1 jackpot bet.
+    jackpot_bet: bool,
+    jackpot: Progressive,
+    /// The last hand's bets, once it is settled.
+    report: Option<SettlementReport>,
+}
+
+impl CaribbeanTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        Ok(Box::new(Self {
+            game: CaribbeanGame::new(),
+            bet: ctx.limits.clamp(*ctx.selected_chip),
+            jackpot_bet: false,
+            jackpot: Progressive::new(caribbean::JACKPOT_SEED, caribbean::JACKPOT_SHARE_PCT),
+            report: None,
+        }))
+    }
+
+    /// Takes the ante and jackpot bet from the bankroll and deals, once the
+    /// balance would cover a raise as well.
+    fn deal(&mut self, ctx: &mut TableContext) {
+        let ante = Money::from_dollars(self.bet);
+        let jackpot_bet = if self.jackpot_bet {
+            caribbean::JACKPOT_BET
+        } else {
+            Money::ZERO
+        };
+        if ante * 3 + jackpot_bet > *ctx.balance {
+            ctx.say(format!(
+                "${} ante and raise are more than the ${} balance",
+                ante * 3 + jackpot_bet,
+                ctx.balance
+            ));
+            return;
+        }
+        match self.game.deal(ante, self.jackpot_bet, &mut self.jackpot) {
+            Ok(()) => {
+                self.report = None;
+                *ctx.balance -= ante + jackpot_bet;
+            }
+            Err(e) => ctx.say(format!("Error: {}", e)),
+        }
+    }
+
+    /// Raises from the bankroll or folds, and pays the settled hand out.
+    fn decide(&mut self, raise: bool, ctx: &mut TableContext) {
+        match self.game.decide(raise, &mut self.jackpot) {
+            Ok(report) => {
+                *ctx.balance += report.total_payout() - self.game.raise;
+                self.report = Some(report);
+            }
+            Err(e) => ctx.say(format!("Error: {}", e)),
+        }
+    }
+}
+
+impl CasinoGame for CaribbeanTable {
+    fn game(&self) -> Game {
+        Game::CaribbeanStud
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        format!(
+            "CARIBBEAN STUD - Dealer qualifies with A-K  |  Jackpot ${}  |  ${}-${}",
+            self.jackpot.meter(),
+            view.limits.min_bet,
+            view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let (player, dealer) = (game.player_hand(), game.dealer_hand());
+        let dealer_shown = if game.in_progress() {
+            1
+        } else {
+            game.dealer.len()
+        };
+        render_poker_hands(
+            f,
+            chunks[1],
+            view,
+            (&game.player, player.name()),
+            (&game.dealer, dealer.name()),
+            dealer_shown,
+        );
+
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Ante: "),
+                Span::styled(
+                    format!("${}  (chip ${})", self.bet, view.selected_chip),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw("  Jackpot bet: "),
+                Span::styled(
+                    if self.jackpot_bet {
+                        format!("${}", caribbean::JACKPOT_BET)
+                    } else {
+                        "off".to_string()
+                    },
+                    Style::default().fg(theme.side_bet),
+                ),
+            ]),
+        ];
+        if game.in_progress() {
+            info.push(Line::from(format!(
+                "You hold {}. Raise ${} or fold the ${} ante?",
+                player.name(),
+                game.ante * 2,
+                game.ante
+            )));
+        } else if let Some(report) = &self.report {
+            let result = if game.folded {
+                "folded".to_string()
+            } else if !caribbean::qualifies(&dealer) {
+                format!("the dealer's {} doesn't qualify", dealer.name())
+            } else {
+                format!("{} against the dealer's {}", player.name(), dealer.name())
+            };
+            info.extend(poker_report_lines(report, result));
+        }
+        let muted = Style::default().fg(theme.muted);
+        info.push(Line::from(Span::styled(
+            "Raise pays: pair 1, two pair 2, trips 3, straight 4, flush 5, full house 7, quads 20, straight flush 50, royal 100",
+            muted,
+        )));
+        info.push(Line::from(Span::styled(
+            "Jackpot: royal flush 100% of the meter, straight flush 10%, quads $500, full house $100, flush $50",
+            muted,
+        )));
+        f.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(theme.panel().title(view.panel_title("Caribbean Stud"))),
+            chunks[2],
+        );
+
+        let moves = if game.in_progress() {
+            format!("[R] Raise ${}  [F] Fold", game.ante * 2)
+        } else {
+            format!(
+                "[{}] Deal  [J] Jackpot bet {}",
+                view.key(Action::Deal),
+                if self.jackpot_bet {
+                    "off".to_string()
+                } else {
+                    format!("on (${})", caribbean::JACKPOT_BET)
+                }
+            )
+        };
+        let controls = vec![
+            Line::from(moves),
+            Line::from("[1-5] Select chip  [+/-] Adjust"),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// [R]aise or [F]old a dealt hand; between hands the chip keys, [J] for
+    /// the jackpot bet, and the deal key.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        if self.game.in_progress() {
+            match code {
+                KeyCode::Char('r') => self.decide(true, ctx),
+                KeyCode::Char('f') => self.decide(false, ctx),
+                KeyCode::Esc => ctx.say("Raise or fold first"),
+                _ => {}
+            }
+            return TableAction::Stay;
+        }
+        match code {
+            KeyCode::Esc => return TableAction::Lobby,
+            KeyCode::Char('j') => self.jackpot_bet = !self.jackpot_bet,
+            KeyCode::Char(c @ '1'..='5') => {
+                if let Some(chip) = ctx.select_chip(c) {
+                    self.bet = ctx.limits.clamp(chip);
+                }
+            }
+            KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                self.bet = ctx.limits.step(self.bet, *ctx.selected_chip, c != '-')
+            }
+            _ if ctx.is_deal(code) => self.deal(ctx),
+            _ => {}
+        }
+        TableAction::Stay
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.game
+            .in_progress()
+            .then(|| format!("Finish the {} hand first", self.name()))
+    }
+}
+
+/// The Three Card Poker table. The ante, Pair Plus and jackpot bet come out
+/// of the bankroll on the deal, the play bet when the player plays.
+pub struct ThreeCardPokerTable {
+    game: ThreeCardPokerGame,
+    /// Ante for the next deal in whole dollars.
+    bet: i32,
+    /// Pair Plus for the next deal, or zero.
+    pair_plus: Money,
+    /// The next deal carries the $1 jackpot bet.
+    jackpot_bet: bool,
+    jackpot: Progressive,
+    /// The last hand's bets, once it is settled.
+    report: Option<SettlementReport>,
+}
+
+impl ThreeCardPokerTable {
+    pub fn open(ctx: &mut TableContext) -> Result<Box<dyn CasinoGame>, String> {
+        Ok(Box::new(Self {
+            game: ThreeCardPokerGame::new(),
+            bet: ctx.limits.clamp(*ctx.selected_chip),
+            pair_plus: Money::ZERO,
+            jackpot_bet: false,
+            jackpot: Progressive::new(
+                three_card_poker::JACKPOT_SEED,
+                three_card_poker::JACKPOT_SHARE_PCT,
+            ),
+            report: None,
+        }))
+    }
+
+    /// Takes the ante, Pair Plus and jackpot bet from the bankroll and
+    /// deals, once the balance would cover the play bet as well.
+    fn deal(&mut self, ctx: &mut TableContext) {
+        let ante = Money::from_dollars(self.bet);
+        let jackpot_bet = if self.jackpot_bet {
+            three_card_poker::JACKPOT_BET
+        } else {
+            Money::ZERO
+        };
+        let stake = ante + self.pair_plus + jackpot_bet;
+        if stake + ante > *ctx.balance {
+            ctx.say(format!(
+                "${} in bets with the play bet is more than the ${} balance",
+                stake + ante,
+                ctx.balance
+            ));
+            return;
+        }
+        match self
+            .game
+            .deal(ante, self.pair_plus, self.jackpot_bet, &mut self.jackpot)
+        {
+            Ok(()) => {
+                self.report = None;
+                *ctx.balance -= stake;
+            }
+            Err(e) => ctx.say(format!("Error: {}", e)),
+        }
+    }
+
+    /// Plays from the bankroll or folds, and pays the settled hand out.
+    fn decide(&mut self, play: bool, ctx: &mut TableContext) {
+        match self.game.decide(play, &mut self.jackpot) {
+            Ok(report) => {
+                *ctx.balance += report.total_payout() - self.game.play;
+                self.report = Some(report);
+            }
+            Err(e) => ctx.say(format!("Error: {}", e)),
+        }
+    }
+}
+
+impl CasinoGame for ThreeCardPokerTable {
+    fn game(&self) -> Game {
+        Game::ThreeCardPoker
+    }
+
+    fn title(&self, view: &TableView) -> String {
+        format!(
+            "THREE CARD POKER - Dealer qualifies with Q high  |  Jackpot ${}  |  ${}-${}",
+            self.jackpot.meter(),
+            view.limits.min_bet,
+            view.limits.max_bet
+        )
+    }
+
+    fn render(&self, f: &mut Frame, chunks: &[Rect], view: &TableView) {
+        let theme = &view.config.theme;
+        let game = &self.game;
+        let (player, dealer) = (game.player_hand(), game.dealer_hand());
+        let dealer_shown = if game.in_progress() {
+            0
+        } else {
+            game.dealer.len()
+        };
+        render_poker_hands(
+            f,
+            chunks[1],
+            view,
+            (&game.player, player.name()),
+            (&game.dealer, dealer.name()),
+            dealer_shown,
+        );
+
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", view.balance),
+                    Style::default().fg(theme.balance),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Ante: "),
+                Span::styled(
+                    format!("${}  (chip ${})", self.bet, view.selected_chip),
+                    Style::default().fg(theme.bet),
+                ),
+                Span::raw("  Pair Plus: "),
+                Span::styled(
+                    if self.pair_plus > Money::ZERO {
+                        format!("${}", self.pair_plus)
+                    } else {
+                        "off".to_string()
+                    },
+                    Style::default().fg(theme.side_bet),
+                ),
+                Span::raw("  Jackpot bet: "),
+                Span::styled(
+                    if self.jackpot_bet {
+                        format!("${}", three_card_poker::JACKPOT_BET)
+                    } else {
+                        "off".to_string()
+                    },
+                    Style::default().fg(theme.side_bet),
+                ),
+            ]),
+        ];
+        if game.in_progress() {
+            info.push(Line::from(format!(
+                "You hold {}. Play ${} or fold the ${} ante?",
+                player.name(),
+                game.ante,
+                game.ante
+            )));
+        } else if let Some(report) = &self.report {
+            let result = if game.folded {
+                "folded".to_string()
+            } else if !three_card_poker::qualifies(&dealer) {
+                format!("the dealer's {} doesn't qualify", dealer.name())
+            } else {
+                format!("{} against the dealer's {}", player.name(), dealer.name())
+            };
+            info.extend(poker_report_lines(report, result));
+        }
+        let muted = Style::default().fg(theme.muted);
+        info.push(Line::from(Span::styled(
+            "Pair Plus: pair 1, flush 4, straight 6, trips 30, straight flush 40",
+            muted,
+        )));
+        info.push(Line::from(Span::styled(
+            "Ante bonus: straight 1, trips 4, straight flush 5  |  Jackpot: mini royal 100%, straight flush 10%, trips $30",
+            muted,
+        )));
+        f.render_widget(
+            Paragraph::new(info)
+                .wrap(Wrap { trim: true })
+                .block(theme.panel().title(view.panel_title("Three Card Poker"))),
+            chunks[2],
+        );
+
+        let moves = if game.in_progress() {
+            format!("[P] Play ${}  [F] Fold", game.ante)
+        } else {
+            format!(
+                "[{}] Deal  [T] Pair Plus {}  [J] Jackpot bet {}",
+                view.key(Action::Deal),
+                if self.pair_plus > Money::ZERO {
+                    "off".to_string()
+                } else {
+                    format!("on (${})", view.limits.clamp_side(view.selected_chip))
+                },
+                if self.jackpot_bet {
+                    "off".to_string()
+                } else {
+                    format!("on (${})", three_card_poker::JACKPOT_BET)
+                }
+            )
+        };
+        let controls = vec![
+            Line::from(moves),
+            Line::from("[1-5] Select chip  [+/-] Adjust"),
+            Line::from(format!("[ESC] Lobby  [{}] Quit", view.key(Action::Quit))),
+        ];
+        f.render_widget(
+            Paragraph::new(controls).block(theme.panel().title("Controls")),
+            chunks[3],
+        );
+    }
+
+    /// [P]lay or [F]old a dealt hand; between hands the chip keys, [T] for
+    /// Pair Plus, [J] for the jackpot bet, and the deal key.
+    fn handle_input(&mut self, code: KeyCode, ctx: &mut TableContext) -> TableAction {
+        *ctx.status = None;
+        if self.game.in_progress() {
+            match code {
+                KeyCode::Char('p') => self.decide(true, ctx),
+                KeyCode::Char('f') => self.decide(false, ctx),
+                KeyCode::Esc => ctx.say("Play or fold first"),
+                _ => {}
+            }
+            return TableAction::Stay;
+        }
+        match code {
+            KeyCode::Esc => return TableAction::Lobby,
+            KeyCode::Char('t') => {
+                self.pair_plus = if self.pair_plus > Money::ZERO {
+                    Money::ZERO
+                } else {
+                    ctx.limits.clamp_side(*ctx.selected_chip)
+                };
+            }
+            KeyCode::Char('j') => self.jackpot_bet = !self.jackpot_bet,
+            KeyCode::Char(c @ '1'..='5') => {
+                if let Some(chip) = ctx.select_chip(c) {
+                    self.bet = ctx.limits.clamp(chip);
+                }
+            }
+            KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                self.bet = ctx.limits.step(self.bet, *ctx.selected_chip, c != '-')
+            }
+            _ if ctx.is_deal(code) => self.deal(ctx),
+            _ => {}
+        }
+        TableAction::Stay
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.game
+            .in_progress()
+            .then(|| format!("Finish the {} hand first", self.name()))
+    }
+}
+
+fn blackjack_key(action: blackjack::Action) -> char {
+    match action {
+        blackjack::Action::Hit => 'h',
+        blackjack::Action::Stand => 's',
+        blackjack::Action::Double => 'd',
+        blackjack::Action::Split => 'p',
+        blackjack::Action::Surrender => 'r',
+    }
+}
+
+/// A die face five lines tall.
+fn die_art(face: u8) -> [String; 5] {
+    let rows: [&str; 3] = match face {
+        1 => ["   ", " o ", "   "],
+        2 => ["o  ", "   ", "  o"],
+        3 => ["o  ", " o ", "  o"],
+        4 => ["o o", "   ", "o o"],
+        5 => ["o o", " o ", "o o"],
+        _ => ["o o", "o o", "o o"],
+    };
+    let row = |pips: &str| {
+        let cells: Vec<String> = pips
+            .chars()
+            .map(|pip| {
+                if pip == 'o' {
+                    "●".to_string()
+                } else {
+                    " ".to_string()
+                }
+            })
+            .collect();
+        format!("│ {} │", cells.join(" "))
+    };
+    [
+        "┌───────┐".to_string(),
+        row(rows[0]),
+        row(rows[1]),
+        row(rows[2]),
+        "└───────┘".to_string(),
+    ]
+}
@@ -0,0 +1,169 @@
+//! Fixed deals for regression tests of the drawing rules: a hand dealt
+//! from a chosen run of cards, and the official punto banco tableau to
+//! hold the engine to, for every player total, banker total and player
+//! third card that can come up. Each situation is played as a real hand,
+//! so a slip anywhere between the chart and the dealing order shows up.
+
+use crate::baccarat::{BaccaratGame, Card, GameMode, SPADES};
+
+/// Official banker chart for non-natural hands. Rows are banker totals 0-7;
+/// the first column is "player stood", then player third-card values 0-9.
+/// D = draw, S = stand.
+pub const OFFICIAL_BANKER_CHART: [&str; 8] = [
+    //   -  0123456789
+    "D DDDDDDDDDD", // 0
+    "D DDDDDDDDDD", // 1
+    "D DDDDDDDDDD", // 2
+    "D DDDDDDDDSD", // 3
+    "D SSDDDDDDSS", // 4
+    "D SSSSDDDDSS", // 5
+    "S SSSSSSDDSS", // 6
+    "S SSSSSSSSSS", // 7
+];
+
+/// Which sides took a third card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Draws {
+    pub player: bool,
+    pub banker: bool,
+}
+
+/// What the official tableau does with these two-card totals and the
+/// player's third card, if the player drew one.
+pub fn official_draws(player_total: u8, banker_total: u8, third: Option<u8>) -> Draws {
+    if player_total >= 8 || banker_total >= 8 {
+        return Draws {
+            player: false,
+            banker: false,
+        };
+    }
+    let column = match third {
+        None => 0,
+        Some(value) => 2 + value as usize,
+    };
+    Draws {
+        player: player_total <= 5,
+        banker: OFFICIAL_BANKER_CHART[banker_total as usize].as_bytes()[column] == b'D',
+    }
+}
+
+/// A hand dealt from a fixed run of cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundResult {
+    pub player: Vec<Card>,
+    pub banker: Vec<Card>,
+    pub player_score: u8,
+    pub banker_score: u8,
+    /// 1 player, 2 banker, 3 tie.
+    pub winner: u8,
+}
+
+impl RoundResult {
+    pub fn draws(&self) -> Draws {
+        Draws {
+            player: self.player.len() == 3,
+            banker: self.banker.len() == 3,
+        }
+    }
+}
+
+/// Deals one classic punto banco hand from `cards` in dealing order:
+/// player, banker, player, banker, then whichever third cards the tableau
+/// calls for. Cards left over are not dealt. Panics if they run out.
+pub fn play_scripted_round(cards: &[Card]) -> RoundResult {
+    let mut game = BaccaratGame::with_card_sequence(GameMode::Classic, cards.to_vec());
+    if let Err(e) = game.play_round_unattended() {
+        panic!("scripted round of {} cards: {}", cards.len(), e);
+    }
+    RoundResult {
+        player: game.player_hand.to_vec(),
+        banker: game.banker_hand.to_vec(),
+        player_score: game.state.player_score,
+        banker_score: game.state.banker_score,
+        winner: game.state.winner,
+    }
+}
+
+/// A card worth `value` points: the ace to nine, or a king for zero.
+pub fn card_worth(value: u8) -> Card {
+    Card::new(SPADES, if value == 0 { 13 } else { value })
+}
+
+/// One situation on the tableau: the two-card totals and the value of the
+/// player's third card, `None` when the player stands or a natural ends
+/// the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableauCase {
+    pub player_total: u8,
+    pub banker_total: u8,
+    pub third: Option<u8>,
+}
+
+impl TableauCase {
+    /// Cards that deal this situation, with a card to spare for the
+    /// banker's third.
+    pub fn cards(&self) -> Vec<Card> {
+        let mut cards = vec![
+            card_worth(self.player_total),
+            card_worth(self.banker_total),
+            card_worth(0),
+            card_worth(0),
+        ];
+        cards.extend(self.third.map(card_worth));
+        cards.push(card_worth(5));
+        cards
+    }
+
+    pub fn official(&self) -> Draws {
+        official_draws(self.player_total, self.banker_total, self.third)
+    }
+}
+
+/// Every situation the tableau covers: each pair of two-card totals, and
+/// each third card the player can draw when they draw.
+pub fn tableau_cases() -> Vec<TableauCase> {
+    let mut cases = Vec::new();
+    for player_total in 0..=9u8 {
+        for banker_total in 0..=9u8 {
+            if player_total <= 5 && banker_total < 8 {
+                cases.extend((0..=9).map(|third| TableauCase {
+                    player_total,
+                    banker_total,
+                    third: Some(third),
+                }));
+            } else {
+                cases.push(TableauCase {
+                    player_total,
+                    banker_total,
+                    third: None,
+                });
+            }
+        }
+    }
+    cases
+}
+
+/// A situation the engine dealt differently from the tableau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub case: TableauCase,
+    pub engine: Draws,
+    pub official: Draws,
+}
+
+/// Plays every situation on the tableau and returns those the engine got
+/// wrong; empty when the rules hold.
+pub fn verify_tableau() -> Vec<Mismatch> {
+    tableau_cases()
+        .into_iter()
+        .filter_map(|case| {
+            let engine = play_scripted_round(&case.cards()).draws();
+            let official = case.official();
+            (engine != official).then_some(Mismatch {
+                case,
+                engine,
+                official,
+            })
+        })
+        .collect()
+}
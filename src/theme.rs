@@ -0,0 +1,200 @@
+//! Color themes for ratatui front ends: what the felt, cards, chips, roads
+//! and figures are drawn in, with a few built-in schemes to start from.
+
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Borders},
+};
+
+/// Colors a table draws with. The terminal casino starts from the built-in
+/// theme named in its settings, lets each role be overridden there, and
+/// cycles through the built-in ones with [F12].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// The built-in theme this one starts from.
+    pub name: &'static str,
+    /// Behind the bet boxes and layouts.
+    pub felt: Color,
+    /// Panel borders.
+    pub border: Color,
+    /// Hearts and diamonds.
+    pub red_suit: Color,
+    /// Clubs and spades.
+    pub black_suit: Color,
+    pub card_back: Color,
+    /// The chips under keys 1-5, smallest first.
+    pub chips: [Color; 5],
+    /// Player, Banker and Tie wins on the roads.
+    pub player: Color,
+    pub banker: Color,
+    pub tie: Color,
+    /// Figures that came out ahead or behind.
+    pub win: Color,
+    pub loss: Color,
+    /// Title bar, highlighted seat, headings.
+    pub accent: Color,
+    pub balance: Color,
+    pub bet: Color,
+    pub side_bet: Color,
+    /// Streak alarm banner.
+    pub alert: Color,
+    /// Warnings, unusual results, and search matches.
+    pub highlight: Color,
+    /// Unavailable or unremarkable figures.
+    pub muted: Color,
+}
+
+impl Theme {
+    /// Green baize and the terminal's own colors.
+    pub const CLASSIC: Theme = Theme {
+        name: "classic",
+        felt: Color::Indexed(22),
+        border: Color::Reset,
+        red_suit: Color::Red,
+        black_suit: Color::White,
+        card_back: Color::Reset,
+        chips: [
+            Color::White,
+            Color::Red,
+            Color::Green,
+            Color::Blue,
+            Color::Magenta,
+        ],
+        player: Color::Blue,
+        banker: Color::Red,
+        tie: Color::Green,
+        win: Color::Green,
+        loss: Color::Red,
+        accent: Color::Cyan,
+        balance: Color::Green,
+        bet: Color::Yellow,
+        side_bet: Color::Magenta,
+        alert: Color::Red,
+        highlight: Color::Yellow,
+        muted: Color::DarkGray,
+    };
+
+    /// A near-black table with softened borders.
+    pub const DARK: Theme = Theme {
+        name: "dark",
+        felt: Color::Indexed(234),
+        border: Color::DarkGray,
+        red_suit: Color::LightRed,
+        black_suit: Color::Gray,
+        card_back: Color::DarkGray,
+        chips: [
+            Color::Gray,
+            Color::LightRed,
+            Color::LightGreen,
+            Color::LightBlue,
+            Color::LightMagenta,
+        ],
+        player: Color::LightBlue,
+        banker: Color::LightRed,
+        tie: Color::LightGreen,
+        win: Color::LightGreen,
+        loss: Color::LightRed,
+        accent: Color::LightBlue,
+        balance: Color::LightGreen,
+        bet: Color::LightYellow,
+        side_bet: Color::LightMagenta,
+        alert: Color::LightRed,
+        highlight: Color::LightYellow,
+        muted: Color::DarkGray,
+    };
+
+    /// Bright colors on black, with nothing dimmed.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        name: "high_contrast",
+        felt: Color::Black,
+        border: Color::White,
+        red_suit: Color::LightRed,
+        black_suit: Color::White,
+        card_back: Color::White,
+        chips: [
+            Color::White,
+            Color::LightRed,
+            Color::LightGreen,
+            Color::LightCyan,
+            Color::LightYellow,
+        ],
+        player: Color::LightCyan,
+        banker: Color::LightRed,
+        tie: Color::LightGreen,
+        win: Color::LightGreen,
+        loss: Color::LightRed,
+        accent: Color::LightYellow,
+        balance: Color::LightGreen,
+        bet: Color::LightYellow,
+        side_bet: Color::LightMagenta,
+        alert: Color::LightRed,
+        highlight: Color::LightYellow,
+        muted: Color::Gray,
+    };
+
+    /// Shades of gray only; the suits, chips and roads tell themselves
+    /// apart by their symbols and values.
+    pub const MONOCHROME: Theme = Theme {
+        name: "monochrome",
+        felt: Color::Reset,
+        border: Color::Reset,
+        red_suit: Color::Reset,
+        black_suit: Color::Reset,
+        card_back: Color::DarkGray,
+        chips: [Color::Reset; 5],
+        player: Color::Gray,
+        banker: Color::Reset,
+        tie: Color::DarkGray,
+        win: Color::Reset,
+        loss: Color::Gray,
+        accent: Color::Reset,
+        balance: Color::Reset,
+        bet: Color::Reset,
+        side_bet: Color::Gray,
+        alert: Color::Reset,
+        highlight: Color::Reset,
+        muted: Color::DarkGray,
+    };
+
+    /// The built-in themes, in the order [F12] cycles through them.
+    pub const BUILT_IN: [Theme; 4] = [
+        Theme::CLASSIC,
+        Theme::DARK,
+        Theme::HIGH_CONTRAST,
+        Theme::MONOCHROME,
+    ];
+
+    /// The built-in theme called `name`, taking "high-contrast" or "High
+    /// Contrast" for `high_contrast`.
+    pub fn named(name: &str) -> Option<Theme> {
+        let name = name.trim().to_lowercase().replace(['-', ' '], "_");
+        Self::BUILT_IN.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// The built-in theme after this one's, wrapping around.
+    pub fn next(&self) -> Theme {
+        let index = Self::BUILT_IN
+            .iter()
+            .position(|theme| theme.name == self.name)
+            .unwrap_or(0);
+        Self::BUILT_IN[(index + 1) % Self::BUILT_IN.len()]
+    }
+
+    /// The name as shown at the table, e.g. "high contrast".
+    pub fn label(&self) -> String {
+        self.name.replace('_', " ")
+    }
+
+    /// A panel: a block bordered all round in the border color.
+    pub fn panel<'a>(&self) -> Block<'a> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.border))
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
@@ -0,0 +1,27 @@
+//! Three Card Baccarat, as dealt in Hong Kong and Macau: each side gets
+//! three cards and nobody draws. Three court cards ("3 Faces") beat any
+//! total; otherwise the higher baccarat total wins, and equal totals go to
+//! the hand holding more court cards. It runs on the baccarat engine as
+//! `GameMode::ThreeCard`.
+
+use crate::baccarat::{Card, Hand};
+
+/// Jacks, queens and kings in the hand.
+pub fn face_count(cards: &[Card]) -> usize {
+    cards.iter().filter(|card| card.rank > 10).count()
+}
+
+/// Three court cards, the best hand there is.
+pub fn is_three_faces(cards: &[Card]) -> bool {
+    cards.len() == 3 && face_count(cards) == 3
+}
+
+/// Where the hand ranks; the hand with the greater strength wins and equal
+/// strengths tie.
+pub fn strength(cards: &[Card]) -> (bool, u8, usize) {
+    (
+        is_three_faces(cards),
+        Hand::points(cards),
+        face_count(cards),
+    )
+}
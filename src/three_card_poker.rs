@@ -0,0 +1,213 @@
+//! Three Card Poker: three cards each against the dealer. The player folds
+//! the ante or makes a play bet matching it. The dealer needs queen high or
+//! better to qualify; a dealer who doesn't pays the ante even money and
+//! hands the play bet back. Against a qualifying dealer the better hand
+//! wins both at even money. A played straight or better also collects an
+//! ante bonus whatever the dealer holds. Pair Plus and the $1 jackpot bet
+//! pay on the player's three cards alone, fold or not, the jackpot drawing
+//! on the progressive meter for a mini royal or straight flush.
+
+use crate::baccarat::{Card, Deck, Money};
+pub use crate::poker::{
+    Category, Jackpot, JackpotAward, PokerBet, PokerError, Progressive, ThreeCardValue,
+    evaluate_three,
+};
+use crate::settlement::{SettledBet, SettlementReport, Wager};
+
+pub const HAND_SIZE: usize = 3;
+/// The jackpot bet, the same every hand.
+pub const JACKPOT_BET: Money = Money::from_dollars(1);
+/// Where the meter starts, and goes back to once it is hit.
+pub const JACKPOT_SEED: Money = Money::from_dollars(1_000);
+/// Share of each jackpot bet that goes on the meter.
+pub const JACKPOT_SHARE_PCT: i64 = 20;
+
+/// What the ante bonus pays per unit on a played hand.
+pub fn ante_bonus(hand: &ThreeCardValue) -> i64 {
+    match hand.category {
+        Category::StraightFlush => 5,
+        Category::ThreeOfAKind => 4,
+        Category::Straight => 1,
+        _ => 0,
+    }
+}
+
+/// What Pair Plus wins per unit, or `None` below a pair.
+pub fn pair_plus_pays(hand: &ThreeCardValue) -> Option<i64> {
+    match hand.category {
+        Category::StraightFlush => Some(40),
+        Category::ThreeOfAKind => Some(30),
+        Category::Straight => Some(6),
+        Category::Flush => Some(4),
+        Category::OnePair => Some(1),
+        _ => None,
+    }
+}
+
+/// What the jackpot bet collects on the player's hand, if anything.
+pub fn jackpot_award(hand: &ThreeCardValue) -> Option<JackpotAward> {
+    match hand.category {
+        Category::StraightFlush if hand.is_mini_royal() => Some(JackpotAward::Meter(100)),
+        Category::StraightFlush => Some(JackpotAward::Meter(10)),
+        Category::ThreeOfAKind => Some(JackpotAward::Fixed(Money::from_dollars(30))),
+        _ => None,
+    }
+}
+
+/// Queen high or better.
+pub fn qualifies(hand: &ThreeCardValue) -> bool {
+    hand.category > Category::HighCard || hand.ranks[0] >= 12
+}
+
+/// A Three Card Poker table: both hands and the bets on them.
+pub struct ThreeCardPokerGame {
+    deck: Deck,
+    pub player: Vec<Card>,
+    pub dealer: Vec<Card>,
+    pub ante: Money,
+    /// Matches the ante once the player plays.
+    pub play: Money,
+    pub pair_plus: Money,
+    pub jackpot_bet: Money,
+    /// The hand is dealt and waiting for the player to fold or play.
+    deciding: bool,
+    pub folded: bool,
+}
+
+impl Default for ThreeCardPokerGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThreeCardPokerGame {
+    pub fn new() -> Self {
+        Self {
+            deck: Deck::new(),
+            player: Vec::new(),
+            dealer: Vec::new(),
+            ante: Money::ZERO,
+            play: Money::ZERO,
+            pair_plus: Money::ZERO,
+            jackpot_bet: Money::ZERO,
+            deciding: false,
+            folded: false,
+        }
+    }
+
+    /// The player still has to fold or play.
+    pub fn in_progress(&self) -> bool {
+        self.deciding
+    }
+
+    /// Takes the ante, Pair Plus, and the jackpot bet if `jackpot_bet`, and
+    /// deals from a fresh deck.
+    pub fn deal(
+        &mut self,
+        ante: Money,
+        pair_plus: Money,
+        jackpot_bet: bool,
+        jackpot: &mut dyn Jackpot,
+    ) -> Result<(), PokerError> {
+        if self.deciding {
+            return Err(PokerError::HandInProgress);
+        }
+        self.deck = Deck::new();
+        self.deck.shuffle();
+        self.player = (0..HAND_SIZE).filter_map(|_| self.deck.deal()).collect();
+        self.dealer = (0..HAND_SIZE).filter_map(|_| self.deck.deal()).collect();
+        self.ante = ante;
+        self.play = Money::ZERO;
+        self.pair_plus = pair_plus;
+        self.jackpot_bet = if jackpot_bet {
+            JACKPOT_BET
+        } else {
+            Money::ZERO
+        };
+        if jackpot_bet {
+            jackpot.contribute(JACKPOT_BET);
+        }
+        self.folded = false;
+        self.deciding = true;
+        Ok(())
+    }
+
+    pub fn player_hand(&self) -> ThreeCardValue {
+        evaluate_three(&self.player)
+    }
+
+    pub fn dealer_hand(&self) -> ThreeCardValue {
+        evaluate_three(&self.dealer)
+    }
+
+    /// Plays, matching the ante, or folds, turns the dealer's hand over and
+    /// settles every bet. Payouts include the stake.
+    pub fn decide(
+        &mut self,
+        play: bool,
+        jackpot: &mut dyn Jackpot,
+    ) -> Result<SettlementReport, PokerError> {
+        if !self.deciding {
+            return Err(PokerError::NoHand);
+        }
+        self.deciding = false;
+        if play {
+            self.play = self.ante;
+        } else {
+            self.folded = true;
+        }
+        Ok(self.settle(jackpot))
+    }
+
+    fn settle(&self, jackpot: &mut dyn Jackpot) -> SettlementReport {
+        let player = self.player_hand();
+        let mut bets = Vec::new();
+        if self.folded {
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Ante),
+                self.ante,
+                Money::ZERO,
+            ));
+        } else {
+            let dealer = self.dealer_hand();
+            let (ante, play) = if !qualifies(&dealer) {
+                (self.ante * 2, self.play)
+            } else {
+                match player.cmp(&dealer) {
+                    std::cmp::Ordering::Greater => (self.ante * 2, self.play * 2),
+                    std::cmp::Ordering::Equal => (self.ante, self.play),
+                    std::cmp::Ordering::Less => (Money::ZERO, Money::ZERO),
+                }
+            };
+            let bonus = self.ante * ante_bonus(&player);
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Ante),
+                self.ante,
+                ante + bonus,
+            ));
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Play),
+                self.play,
+                play,
+            ));
+        }
+        if self.pair_plus > Money::ZERO {
+            let won =
+                pair_plus_pays(&player).map_or(Money::ZERO, |pays| self.pair_plus * (pays + 1));
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::PairPlus),
+                self.pair_plus,
+                won,
+            ));
+        }
+        if self.jackpot_bet > Money::ZERO {
+            let won = jackpot_award(&player).map_or(Money::ZERO, |award| jackpot.pay(award));
+            bets.push(SettledBet::new(
+                Wager::Poker(PokerBet::Jackpot),
+                self.jackpot_bet,
+                won,
+            ));
+        }
+        SettlementReport { bets }
+    }
+}
@@ -0,0 +1,382 @@
+//! Ticker mode: a background simulation streams rounds into the TUI, showing
+//! a live outcome ticker, rolling RTP, and the bankroll curve converging on
+//! the house edge.
+
+use crate::baccarat::{
+    self, BaccaratGame, BetType, GameMode, Money, ProcedureProfile, SHOE_DECKS, ShuffleRng,
+};
+use crate::theme::Theme;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Chart, Dataset, GraphType, Paragraph},
+};
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, Receiver},
+    },
+    thread,
+    time::Duration,
+};
+
+const UNIT_BET: Money = Money::from_dollars(10);
+const ROLLING_WINDOW: usize = 1000;
+const TICKER_LEN: usize = 80;
+const CHART_POINTS: usize = 400;
+const SPEEDS: [usize; 6] = [1, 10, 100, 1_000, 10_000, 100_000];
+
+struct SimRound {
+    winner: u8,
+    wagered: Money,
+    returned: Money,
+}
+
+/// Flat-betting simulation loop; `speed` is rounds per 20ms tick. The shoe
+/// shuffles from `rng` when given one.
+fn spawn_simulation(
+    mode: GameMode,
+    bet_type: BetType,
+    rng: Option<ShuffleRng>,
+    speed: Arc<AtomicUsize>,
+    paused: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+) -> Receiver<SimRound> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let procedure = ProcedureProfile::standard(SHOE_DECKS);
+        let mut game = match rng {
+            Some(rng) => BaccaratGame::with_rng(mode, procedure, rng),
+            None => BaccaratGame::with_procedure(mode, procedure),
+        };
+        while !stop.load(Ordering::Relaxed) {
+            if !paused.load(Ordering::Relaxed) {
+                for _ in 0..speed.load(Ordering::Relaxed) {
+                    game.prepare_next_round();
+                    if game.play_round_unattended().is_err() {
+                        return;
+                    }
+                    let round = SimRound {
+                        winner: game.state.winner,
+                        wagered: UNIT_BET,
+                        returned: game.calculate_main_bet_payout(bet_type, UNIT_BET),
+                    };
+                    if tx.send(round).is_err() {
+                        return;
+                    }
+                }
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    });
+    rx
+}
+
+pub struct TickerUI {
+    mode: GameMode,
+    bet_type: BetType,
+    /// Shuffles every shoe from this seed; `None` shuffles at random.
+    seed: Option<u64>,
+    speed_index: usize,
+    speed: Arc<AtomicUsize>,
+    paused: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    rounds: u64,
+    outcome_counts: [u64; 3],
+    total_wagered: Money,
+    total_returned: Money,
+    bankroll: Money,
+    recent: VecDeque<SimRound>,
+    rolling_wagered: Money,
+    rolling_returned: Money,
+    ticker: VecDeque<u8>,
+    bankroll_curve: Vec<(f64, f64)>,
+    rtp_curve: Vec<(f64, f64)>,
+    sample_every: u64,
+    theme: Theme,
+}
+
+impl TickerUI {
+    pub fn new(mode: GameMode, bet_type: BetType, seed: Option<u64>, theme: Theme) -> Self {
+        Self {
+            mode,
+            bet_type,
+            seed,
+            speed_index: 2,
+            speed: Arc::new(AtomicUsize::new(SPEEDS[2])),
+            paused: Arc::new(AtomicBool::new(false)),
+            stop: Arc::new(AtomicBool::new(false)),
+            rounds: 0,
+            outcome_counts: [0; 3],
+            total_wagered: Money::ZERO,
+            total_returned: Money::ZERO,
+            bankroll: Money::ZERO,
+            recent: VecDeque::with_capacity(ROLLING_WINDOW),
+            rolling_wagered: Money::ZERO,
+            rolling_returned: Money::ZERO,
+            ticker: VecDeque::with_capacity(TICKER_LEN),
+            bankroll_curve: Vec::new(),
+            rtp_curve: Vec::new(),
+            sample_every: 1,
+            theme,
+        }
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let rx = spawn_simulation(
+            self.mode,
+            self.bet_type,
+            self.seed.map(baccarat::seeded_rng),
+            Arc::clone(&self.speed),
+            Arc::clone(&self.paused),
+            Arc::clone(&self.stop),
+        );
+        let res = self.run_app(&mut terminal, &rx);
+        self.stop.store(true, Ordering::Relaxed);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        res
+    }
+
+    fn run_app<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        rx: &Receiver<SimRound>,
+    ) -> io::Result<()> {
+        loop {
+            for round in rx.try_iter() {
+                self.record(round);
+            }
+            terminal.draw(|f| self.ui(f))?;
+
+            if event::poll(Duration::from_millis(50))?
+                && let Event::Key(key) = event::read()?
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => {
+                        let paused = !self.paused.load(Ordering::Relaxed);
+                        self.paused.store(paused, Ordering::Relaxed);
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => self.set_speed(self.speed_index + 1),
+                    KeyCode::Char('-') => self.set_speed(self.speed_index.saturating_sub(1)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn set_speed(&mut self, index: usize) {
+        self.speed_index = index.min(SPEEDS.len() - 1);
+        self.speed
+            .store(SPEEDS[self.speed_index], Ordering::Relaxed);
+    }
+
+    fn record(&mut self, round: SimRound) {
+        self.rounds += 1;
+        if (1..=3).contains(&round.winner) {
+            self.outcome_counts[round.winner as usize - 1] += 1;
+        }
+        self.total_wagered += round.wagered;
+        self.total_returned += round.returned;
+        self.bankroll += round.returned - round.wagered;
+
+        if self.ticker.len() == TICKER_LEN {
+            self.ticker.pop_front();
+        }
+        self.ticker.push_back(round.winner);
+
+        self.rolling_wagered += round.wagered;
+        self.rolling_returned += round.returned;
+        if self.recent.len() == ROLLING_WINDOW
+            && let Some(old) = self.recent.pop_front()
+        {
+            self.rolling_wagered -= old.wagered;
+            self.rolling_returned -= old.returned;
+        }
+        self.recent.push_back(round);
+
+        if self.rounds.is_multiple_of(self.sample_every) {
+            let x = self.rounds as f64;
+            self.bankroll_curve.push((x, self.bankroll.as_dollars()));
+            self.rtp_curve
+                .push((x, Self::rtp(self.total_returned, self.total_wagered)));
+            // Halve the resolution whenever the curve fills up so it always
+            // spans the whole run.
+            if self.bankroll_curve.len() >= CHART_POINTS * 2 {
+                self.bankroll_curve = self.bankroll_curve.iter().copied().step_by(2).collect();
+                self.rtp_curve = self.rtp_curve.iter().copied().step_by(2).collect();
+                self.sample_every *= 2;
+            }
+        }
+    }
+
+    fn rtp(returned: Money, wagered: Money) -> f64 {
+        if wagered == Money::ZERO {
+            0.0
+        } else {
+            returned.as_dollars() / wagered.as_dollars() * 100.0
+        }
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(6),
+                Constraint::Min(8),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+
+        let status = if self.paused.load(Ordering::Relaxed) {
+            "PAUSED"
+        } else {
+            "RUNNING"
+        };
+        let title = Paragraph::new(format!(
+            "TICKER - {:?} Mode, flat ${} on {}  |  {} rounds/tick  |  {}",
+            self.mode, UNIT_BET, self.bet_type, SPEEDS[self.speed_index], status
+        ))
+        .style(
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(self.theme.panel());
+        f.render_widget(title, chunks[0]);
+
+        self.render_ticker(f, chunks[1]);
+        self.render_summary(f, chunks[2]);
+
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[3]);
+        Self::render_curve(
+            f,
+            charts[0],
+            "Bankroll",
+            &self.bankroll_curve,
+            self.theme.balance,
+            self.rounds,
+            &self.theme,
+        );
+        Self::render_curve(
+            f,
+            charts[1],
+            "RTP %",
+            &self.rtp_curve,
+            self.theme.highlight,
+            self.rounds,
+            &self.theme,
+        );
+
+        let help = Paragraph::new("[SPACE] Pause  [+/-] Speed  [Q/ESC] Quit")
+            .style(Style::default().fg(self.theme.muted));
+        f.render_widget(help, chunks[4]);
+    }
+
+    fn render_ticker(&self, f: &mut Frame, area: Rect) {
+        let spans: Vec<Span> = self
+            .ticker
+            .iter()
+            .map(|winner| match winner {
+                1 => Span::styled("P", Style::default().fg(self.theme.player)),
+                2 => Span::styled("B", Style::default().fg(self.theme.banker)),
+                _ => Span::styled("T", Style::default().fg(self.theme.tie)),
+            })
+            .collect();
+        let ticker =
+            Paragraph::new(Line::from(spans)).block(self.theme.panel().title("Latest outcomes"));
+        f.render_widget(ticker, area);
+    }
+
+    fn render_summary(&self, f: &mut Frame, area: Rect) {
+        let pct = |count: u64| {
+            if self.rounds == 0 {
+                0.0
+            } else {
+                count as f64 / self.rounds as f64 * 100.0
+            }
+        };
+        let lines = vec![
+            Line::from(format!("Rounds: {}", self.rounds)),
+            Line::from(format!(
+                "Player {:.3}%  Banker {:.3}%  Tie {:.3}%",
+                pct(self.outcome_counts[0]),
+                pct(self.outcome_counts[1]),
+                pct(self.outcome_counts[2])
+            )),
+            Line::from(format!(
+                "RTP overall: {:.3}%   RTP last {}: {:.2}%",
+                Self::rtp(self.total_returned, self.total_wagered),
+                self.recent.len(),
+                Self::rtp(self.rolling_returned, self.rolling_wagered)
+            )),
+            Line::from(format!("Bankroll: {:+}", self.bankroll)),
+        ];
+        let summary = Paragraph::new(lines).block(self.theme.panel().title("Summary"));
+        f.render_widget(summary, area);
+    }
+
+    fn render_curve(
+        f: &mut Frame,
+        area: Rect,
+        title: &str,
+        points: &[(f64, f64)],
+        color: Color,
+        rounds: u64,
+        theme: &Theme,
+    ) {
+        let (min_y, max_y) = points
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &(_, y)| {
+                (lo.min(y), hi.max(y))
+            });
+        let (min_y, max_y) = if points.is_empty() {
+            (0.0, 1.0)
+        } else {
+            (min_y, max_y.max(min_y + 1.0))
+        };
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(points);
+        let chart = Chart::new(vec![dataset])
+            .block(theme.panel().title(title.to_string()))
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, rounds.max(1) as f64])
+                    .labels(vec![Span::raw("0"), Span::raw(rounds.to_string())]),
+            )
+            .y_axis(Axis::default().bounds([min_y, max_y]).labels(vec![
+                Span::raw(format!("{:.1}", min_y)),
+                Span::raw(format!("{:.1}", max_y)),
+            ]));
+        f.render_widget(chart, area);
+    }
+}
@@ -1,156 +1,767 @@
-use crate::baccarat::{BaccaratGame, GameMode, BonusBets, Card};
-use crate::card_renderer::{CardRenderer, CardAnimation};
+use crate::alarms::StreakAlarms;
+use crate::analytics::{self, EdgeInputs, HitRates, ModeOdds, TableEdges};
+use crate::baccarat::{
+    self, BaccaratGame, BetPerformance, BetSlip, BetType, BonusBetType, CardSource, GameEvent,
+    GameMode, GamePhase, GameSession, Money, RngChoice, Shoe, Side,
+};
+use crate::card_renderer::{CardRenderer, HandScore};
+use crate::config::{
+    Action, Config, ConfigWatcher, MAX_AUTOPLAY_DELAY, MIN_AUTOPLAY_DELAY, TableDefaults,
+};
+use crate::events::SeasonalEvent;
+use crate::fairness::Fairness;
+use crate::goal::{BankrollGoal, BetLayout, GoalProjection};
+use crate::history::{
+    self, HandHistory, HandRecord, SeatBets, ShoeHistory, ShoeLedger, ShoeSummary, Trends,
+};
+use crate::holdem;
+use crate::integrity;
+use crate::lifetime::LifetimeStats;
+use crate::lobby::{self, CasinoGame, Game, TABLES, TableAction, TableContext, TableView};
+use crate::log_viewer::LogViewer;
+use crate::pace::PaceTracker;
+use crate::presets::{BetPreset, PresetBook};
+use crate::profiles::Profile;
+use crate::replay::{Replay, ReplayHand, ReplayRules};
+use crate::resume::{self, SavedSeat, SavedSession, SavedShoe};
+use crate::roads::ShoeRoads;
+use crate::rules::RulesBundle;
+use crate::scoreboard::{self, BeadRoad, BigRoad};
+use crate::sessions::{SessionLog, SessionRecorder};
+use crate::settlement::{SettledBet, Wager};
+use crate::significance::{self, SessionTally};
+use crate::storage::{self, DEFAULT_PROFILE};
+use crate::strategy::{Strategy, StrategyKind};
+use crate::theme::Theme;
+use crate::ui::{decision_prompt, natural_text};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use rand::Rng;
 use ratatui::{
+    Frame, Terminal,
     backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
-    Frame, Terminal,
+    widgets::{Axis, Block, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, Wrap},
 };
 use std::{
     io,
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
     time::{Duration, Instant},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum BetType {
-    Player,
-    Banker,
-    Tie,
+/// What a split-view key does to its player's seat.
+#[derive(Debug, Clone, Copy)]
+enum SplitAction {
+    BetOn(BetType),
+    Step(bool),
+    NextChip,
+}
+
+/// A spot on the felt the mouse can bet on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BetZone {
+    Main(BetType),
+    Side(BonusBetType),
+    /// A chip in the rack, by its place in the table's chips.
+    Chip(usize),
 }
 
+/// Table-wide results; money is tracked per seat.
 pub struct GameStats {
     rounds_played: u32,
-    player_wins: u32,
-    banker_wins: u32,
-    ties: u32,
-    total_wagered: i32,
-    total_won: i32,
+    /// Wins, streaks, and chops, fed from each hand's record.
+    trends: Trends,
+    pace: PaceTracker,
 }
 
 impl GameStats {
     fn new() -> Self {
         Self {
             rounds_played: 0,
-            player_wins: 0,
-            banker_wins: 0,
-            ties: 0,
-            total_wagered: 0,
-            total_won: 0,
+            trends: Trends::default(),
+            pace: PaceTracker::new(),
         }
     }
-    
-    fn win_rate(&self) -> f32 {
-        if self.total_wagered == 0 {
-            0.0
-        } else {
-            (self.total_won as f32 / self.total_wagered as f32) * 100.0
+}
+
+/// One player at the table. Hot-seat play gives each player a seat and
+/// passes the keyboard around before every deal.
+struct Seat {
+    name: String,
+    /// Bankroll, bets, and settlement, kept by the engine.
+    session: GameSession,
+    selected_chip: i32,
+    recorder: SessionRecorder,
+    goal: Option<BankrollGoal>,
+    goal_projection: Option<GoalProjection>,
+    /// Each box's stake before a chip went down on it or came off since
+    /// the bets last locked, newest last, for the undo key to put back.
+    undo: Vec<ChipMove>,
+}
+
+/// One box's stake before a chip move changed it.
+#[derive(Debug, Clone, Copy)]
+struct ChipMove {
+    wager: Wager,
+    before: Money,
+}
+
+/// A seat's bets at one moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlacedBets {
+    selected_bet: BetType,
+    slip: BetSlip,
+}
+
+impl PlacedBets {
+    fn of(session: &GameSession) -> Self {
+        Self {
+            selected_bet: session.selected_bet,
+            slip: session.slip,
+        }
+    }
+}
+
+impl Seat {
+    /// A seat sitting down with the table's starting balance and smallest
+    /// chip.
+    fn new(name: &str, table: &TableDefaults) -> Self {
+        Self {
+            name: name.to_string(),
+            session: GameSession::new(table.starting_balance),
+            selected_chip: table.chips[0],
+            recorder: SessionRecorder::new(name, table.starting_balance),
+            goal: None,
+            goal_projection: None,
+            undo: Vec::new(),
         }
     }
 }
 
 pub struct RatatuiUI {
     game: BaccaratGame,
-    balance: i32,
-    current_bet: i32,
-    bet_type: BetType,
-    bonus_bets: BonusBets,
+    game_events: Receiver<GameEvent>,
+    rules: RulesBundle,
+    seats: Vec<Seat>,
+    active_seat: usize,
+    split_view: bool,
+    presets: PresetBook,
+    awaiting_preset_slot: bool,
+    /// The specific-tie bet menu is open.
+    tie_score_menu: bool,
+    /// Side bet waiting for a chip key to set its stake.
+    sizing_bonus_bet: Option<BonusBetType>,
+    /// The spot on the felt under the mouse pointer.
+    hover: Option<BetZone>,
+    status_message: Option<String>,
     game_mode: GameMode,
     stats: GameStats,
     show_stats: bool,
+    /// The stats view shows the profile's lifetime totals rather than this
+    /// session's.
+    stats_lifetime: bool,
+    /// The shoe count panel: what is left of each rank and the edges that
+    /// move with it.
+    show_count: bool,
     animation_state: AnimationState,
-    last_update: Instant,
+    /// Last keypress, for starting the attract loop on an idle table.
+    last_input: Instant,
+    demo: Option<Demo>,
+    autoplay: Option<Autoplay>,
+    event: Option<SeasonalEvent>,
+    show_odds: bool,
+    mode_odds: Option<Vec<ModeOdds>>,
+    show_significance: bool,
+    tally: SessionTally,
+    /// Theoretical rates for the current mode, computed when the
+    /// significance view first opens.
+    hit_rates: Option<HitRates>,
+    /// House edges for the next hand from what is left in the shoe, and the
+    /// mode they were worked out for.
+    edges: Option<(GameMode, TableEdges)>,
+    /// What the latest edges were, or are being, worked out from.
+    edges_inputs: Option<EdgeInputs>,
+    edges_job: Option<Receiver<TableEdges>>,
+    log_viewer: Option<LogViewer>,
+    /// The lobby is open.
+    lobby: bool,
+    /// The table the player is seated at instead of baccarat.
+    table: Option<Box<dyn CasinoGame>>,
+    /// Bots at a hold'em table when it opens.
+    opponents: usize,
+    roads: ShoeRoads,
+    ledger: ShoeLedger,
+    hand_history: HandHistory,
+    /// Lines the hand history panel is scrolled back from the latest hand,
+    /// while it is open.
+    hand_log: Option<usize>,
+    shoe_history: ShoeHistory,
+    session_log: SessionLog,
+    lifetime: LifetimeStats,
+    /// Every hand of the session, for the replay file written on quit.
+    replay_hands: Vec<ReplayHand>,
+    /// Profile the table is saved to on quit, which keeps the bankroll and
+    /// achievements.
+    profile: Profile,
+    shoe_recap: Option<ShoeSummary>,
+    alarms: StreakAlarms,
+    alarm_banner: Option<String>,
+    alarm_bell_pending: bool,
+    config: Config,
+    config_watcher: ConfigWatcher,
+    /// When a Speed table deals the next hand on its own.
+    next_auto_deal: Option<Instant>,
+    /// Shuffles every shoe from this seed; `None` shuffles at random.
+    seed: Option<u64>,
+    /// Commits to each of the player's shoes before it is dealt, unless
+    /// they come from `seed`.
+    fairness: Fairness,
+}
+
+/// Time between card reveals; Speed tables deal four times as fast.
+pub const CARD_REVEAL_INTERVAL: Duration = Duration::from_millis(1000);
+const SPEED_REVEAL_INTERVAL: Duration = Duration::from_millis(250);
+/// Lines moved by [PgUp]/[PgDn] in the history viewer and the hand log.
+const LOG_PAGE: isize = 20;
+/// How long a fresh table sits untouched before the attract loop starts.
+const DEMO_IDLE: Duration = Duration::from_secs(30);
+/// Bots at a new hold'em table unless `--opponents` says otherwise.
+const DEFAULT_OPPONENTS: usize = 3;
+/// Pause between demo hands once the last card is face up.
+const DEMO_DEAL_PAUSE: Duration = Duration::from_secs(3);
+/// Bet kinds the stats view has room for; the least wagered are left off.
+const MAX_BET_ROWS: usize = 5;
+/// Bead plate beside the big road: twelve columns of three-wide beads.
+const BEAD_PLATE_WIDTH: u16 = 12 * 3 + 2;
+/// The felt's main bet boxes, borders included: at least one line of
+/// chips, and a line for each stack of chips when there is room.
+const MIN_BOX_HEIGHT: u16 = 3;
+const MAX_BOX_HEIGHT: u16 = 7;
+/// The boxed two lines of side bet spots under the bet boxes.
+const SIDE_SPOTS_HEIGHT: u16 = 4;
+/// Lines the controls panel needs below the felt; a shorter screen leaves
+/// the felt off and bets from the keyboard.
+const CONTROLS_HEIGHT: u16 = 7;
+const CHIP_GLYPH: &str = "●";
+/// Chips drawn one by one in a stack before it is shown as a count.
+const MAX_STACK_GLYPHS: i32 = 6;
+
+/// The player's table, set aside while the attract loop plays a fresh one.
+struct SavedTable {
+    game: BaccaratGame,
+    seats: Vec<Seat>,
+    active_seat: usize,
+    stats: GameStats,
+    roads: ShoeRoads,
+    ledger: ShoeLedger,
+    hand_history: HandHistory,
+    tally: SessionTally,
+    show_stats: bool,
+    show_count: bool,
+    status_message: Option<String>,
+    alarm_banner: Option<String>,
+}
+
+struct Demo {
+    saved: SavedTable,
+    next_deal: Instant,
 }
 
+/// A betting strategy playing the player's own seat, bankroll and all.
+struct Autoplay {
+    kind: StrategyKind,
+    strategy: Box<dyn Strategy>,
+    next_deal: Instant,
+    /// Pause between hands, from the config and sped up or slowed down
+    /// with the bet keys while it plays.
+    delay: f64,
+    hands: u32,
+    start_balance: Money,
+    /// The main bet, its stake and the hands played before the hand in
+    /// progress, to tell the strategy how it came out.
+    pending: Option<(BetType, Money, u32)>,
+}
+
+/// A hand dealt card by card. Each step either turns up the card just
+/// dealt or deals the next one, so every card lies face down for a moment
+/// before it is revealed.
 #[derive(Debug, Clone)]
 struct AnimationState {
     dealing: bool,
-    cards_to_reveal: Vec<CardAnimation>,
-    current_reveal_index: usize,
-    deal_start_time: Option<Instant>,
+    /// Face-up cards in the player's and banker's hands.
+    revealed: (usize, usize),
+    next_step: Instant,
+    step_interval: Duration,
 }
 
 impl AnimationState {
     fn new() -> Self {
         Self {
             dealing: false,
-            cards_to_reveal: Vec::new(),
-            current_reveal_index: 0,
-            deal_start_time: None,
+            revealed: (0, 0),
+            next_step: Instant::now(),
+            step_interval: CARD_REVEAL_INTERVAL / 2,
         }
     }
-    
-    fn start_dealing(&mut self, cards: Vec<Card>) {
+
+    /// Starts a deal that reveals a card every `reveal_interval`.
+    fn start_dealing(&mut self, reveal_interval: Duration) {
         self.dealing = true;
-        self.cards_to_reveal = cards.into_iter()
-            .enumerate()
-            .map(|(i, card)| CardAnimation::new(card, i))
-            .collect();
-        self.current_reveal_index = 0;
-        self.deal_start_time = Some(Instant::now());
-    }
-    
-    fn update(&mut self) {
-        if !self.dealing {
-            return;
-        }
-        
-        if let Some(start_time) = self.deal_start_time {
-            let elapsed = start_time.elapsed();
-            let reveal_interval = Duration::from_millis(1000); // 1 second per card
-            
-            let cards_to_reveal = (elapsed.as_millis() / reveal_interval.as_millis()) as usize;
-            
-            for i in self.current_reveal_index..cards_to_reveal.min(self.cards_to_reveal.len()) {
-                if i < self.cards_to_reveal.len() {
-                    self.cards_to_reveal[i].reveal();
-                }
-            }
-            
-            self.current_reveal_index = cards_to_reveal.min(self.cards_to_reveal.len());
-            
-            if self.current_reveal_index >= self.cards_to_reveal.len() {
-                self.dealing = false;
-            }
+        self.revealed = (0, 0);
+        self.next_step = Instant::now();
+        self.step_interval = reveal_interval / 2;
+    }
+
+    /// Whether the next step is due, scheduling the one after it.
+    fn step_due(&mut self) -> bool {
+        if !self.dealing || Instant::now() < self.next_step {
+            return false;
+        }
+        self.next_step = Instant::now() + self.step_interval;
+        true
+    }
+
+    /// Turns up the face-down card, if one is showing.
+    fn reveal(&mut self, game: &BaccaratGame) -> bool {
+        let dealt = (game.player_hand.len(), game.banker_hand.len());
+        if self.revealed == dealt {
+            return false;
         }
+        self.revealed = dealt;
+        true
+    }
+
+    fn finish(&mut self) {
+        self.dealing = false;
     }
-    
+
     fn is_complete(&self) -> bool {
         !self.dealing
     }
+
+    /// Face-up cards in each hand; every card once the deal is over.
+    fn revealed(&self, game: &BaccaratGame) -> (usize, usize) {
+        if self.dealing {
+            self.revealed
+        } else {
+            (game.player_hand.len(), game.banker_hand.len())
+        }
+    }
 }
 
 impl RatatuiUI {
-    pub fn new() -> Self {
+    /// Opens with `config`, saying in the status line why the settings file
+    /// couldn't be used if `config_error` says it couldn't.
+    pub fn new(config: Config, config_error: Option<String>) -> Self {
+        let config_error = config_error.map(|e| format!("Settings not loaded, {}", e));
+        let mut game = BaccaratGame::with_shoe(config.table.mode, config.table.decks);
         Self {
-            game: BaccaratGame::new(),
-            balance: 1000,
-            current_bet: 0,
-            bet_type: BetType::Player,
-            bonus_bets: BonusBets::new(),
-            game_mode: GameMode::Classic,
+            game_events: game.subscribe(),
+            game,
+            rules: RulesBundle::classic(),
+            seats: vec![Seat::new("Player", &config.table)],
+            active_seat: 0,
+            split_view: false,
+            presets: PresetBook::empty(DEFAULT_PROFILE),
+            awaiting_preset_slot: false,
+            tie_score_menu: false,
+            sizing_bonus_bet: None,
+            hover: None,
+            status_message: config_error,
+            game_mode: config.table.mode,
             stats: GameStats::new(),
             show_stats: false,
+            stats_lifetime: false,
+            show_count: false,
             animation_state: AnimationState::new(),
-            last_update: Instant::now(),
+            last_input: Instant::now(),
+            demo: None,
+            autoplay: None,
+            event: None,
+            show_odds: false,
+            mode_odds: None,
+            show_significance: false,
+            tally: SessionTally::new(),
+            hit_rates: None,
+            edges: None,
+            edges_inputs: None,
+            edges_job: None,
+            log_viewer: None,
+            lobby: false,
+            table: None,
+            opponents: DEFAULT_OPPONENTS,
+            roads: ShoeRoads::new(),
+            ledger: ShoeLedger::new(),
+            hand_history: HandHistory::new(),
+            hand_log: None,
+            shoe_history: ShoeHistory::new(DEFAULT_PROFILE),
+            session_log: SessionLog::new(DEFAULT_PROFILE),
+            lifetime: LifetimeStats::empty(DEFAULT_PROFILE),
+            replay_hands: Vec::new(),
+            profile: Profile::new(DEFAULT_PROFILE),
+            shoe_recap: None,
+            alarms: StreakAlarms::defaults(),
+            alarm_banner: None,
+            alarm_bell_pending: false,
+            config,
+            config_watcher: ConfigWatcher::new(),
+            next_auto_deal: None,
+            seed: None,
+            fairness: Fairness::new(None),
+        }
+    }
+
+    pub fn set_alarms(&mut self, alarms: StreakAlarms) {
+        self.alarms = alarms;
+    }
+
+    pub fn set_event(&mut self, event: Option<SeasonalEvent>) {
+        self.event = event;
+    }
+
+    /// Opens the table under a house-rules bundle: its mode, shoe,
+    /// limits, side bets, and pay table replace the current ones.
+    pub fn set_rules(&mut self, rules: RulesBundle) {
+        self.game_mode = rules.mode;
+        self.rules = rules;
+        let game = self.committed_game(self.game_mode);
+        self.replace_game(game);
+    }
+
+    /// Deals every shoe from `seed` on, so the same seed brings the same
+    /// cards.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Mixes `client_seed` into every provably fair shoe from the next on.
+    pub fn set_client_seed(&mut self, client_seed: String) {
+        self.fairness = Fairness::new(Some(client_seed));
+    }
+
+    /// A table in `mode` with a freshly shuffled shoe under the rules'
+    /// procedure.
+    fn fresh_game(&self, mode: GameMode) -> BaccaratGame {
+        let procedure = self.rules.procedure.clone();
+        match self.seed {
+            Some(seed) => BaccaratGame::seeded(mode, procedure, seed),
+            None => BaccaratGame::with_procedure(mode, procedure),
+        }
+    }
+
+    /// Like `fresh_game`, for the player's own table: unless the shoes come
+    /// from `seed`, they shuffle from the generator the settings choose, and
+    /// a provably fair shoe is committed to, revealing the one it replaces.
+    fn committed_game(&mut self, mode: GameMode) -> BaccaratGame {
+        let procedure = self.rules.procedure.clone();
+        match self.config.table.rng {
+            _ if self.seed.is_some() => self.fresh_game(mode),
+            RngChoice::ProvablyFair => {
+                let rng = self.fairness.next_shoe(mode, procedure.decks);
+                BaccaratGame::with_rng(mode, procedure, rng)
+            }
+            RngChoice::Os => BaccaratGame::with_rng(mode, procedure, baccarat::os_rng()),
+            RngChoice::Thread => BaccaratGame::with_procedure(mode, procedure),
+        }
+    }
+
+    /// How the shoe in play was shuffled, as the hand history records it.
+    fn shuffle_label(&self) -> String {
+        match self.seed {
+            Some(seed) => format!("seed {}", seed),
+            None if self.demo.is_some() => RngChoice::Thread.name().to_string(),
+            None => self.config.table.rng.name().to_string(),
+        }
+    }
+
+    /// Sits down at a new table, listening to its events instead, and
+    /// returns the old one.
+    fn replace_game(&mut self, mut game: BaccaratGame) -> BaccaratGame {
+        self.game_events = game.subscribe();
+        std::mem::replace(&mut self.game, game)
+    }
+
+    /// Carries on from a saved table: its mode, seats, roads and figures,
+    /// and the shoe where it was left if it was dealt under the procedure
+    /// in force now; otherwise a fresh shoe.
+    pub fn resume(&mut self, saved: &SavedSession) -> Result<(), String> {
+        let mode: GameMode = saved.mode.parse()?;
+        if saved.seats.is_empty() {
+            return Err("the saved table has no seats".to_string());
+        }
+        let procedure = self.rules.procedure.clone();
+        match &saved.shoe {
+            Some(shoe) if saved.procedure == procedure.name => {
+                let (to_come, burned) = shoe.cards()?;
+                let mut shoe =
+                    Shoe::resume(procedure, mode.top_rank(), to_come, shoe.dealt, burned);
+                if self.seed.is_none() && self.config.table.rng == RngChoice::Os {
+                    shoe.set_rng(baccarat::os_rng());
+                }
+                // The saved shoe was revealed when it was left; the one
+                // committed to for the new table is never dealt.
+                self.fairness.reveal();
+                self.replace_game(BaccaratGame::with_card_source(mode, CardSource::Shoe(shoe)));
+                self.roads = ShoeRoads::resume(saved.shoe_number, saved.beads.clone());
+            }
+            _ => {
+                let game = self.committed_game(mode);
+                self.replace_game(game);
+                self.roads = ShoeRoads::resume(saved.shoe_number + 1, Vec::new());
+            }
+        }
+        self.game_mode = mode;
+        self.seats = saved
+            .seats
+            .iter()
+            .map(|seat| Seat {
+                session: seat.session(),
+                selected_chip: seat.selected_chip,
+                recorder: SessionRecorder::new(&seat.name, seat.balance),
+                ..Seat::new(&seat.name, &self.config.table)
+            })
+            .collect();
+        self.active_seat = saved.active_seat.min(self.seats.len() - 1);
+        self.stats.rounds_played = saved.rounds_played;
+        self.stats.trends = saved.trends;
+        self.status_message = Some(format!("Welcome back: {}", saved.summary()));
+        Ok(())
+    }
+
+    /// Writes the table out for `resume` next launch, once anything has
+    /// happened at it. Whatever is on a lobby table is settled back to the
+    /// seat first; a hand still being dealt is void and its stakes stay in
+    /// the bankroll.
+    fn save_session(&mut self) -> io::Result<()> {
+        self.stop_demo();
+        if let Some(mut table) = self.table.take() {
+            let returned = table.settle();
+            self.seat_mut().session.balance += returned;
+        }
+        if self.stats.rounds_played == 0
+            && self
+                .seats
+                .iter()
+                .all(|seat| seat.session.balance == self.config.table.starting_balance)
+        {
+            return Ok(());
+        }
+        let shoe = match &self.game.card_source {
+            CardSource::Shoe(shoe) if !self.game.card_source.needs_reshuffle() => {
+                Some(SavedShoe::new(shoe))
+            }
+            _ => None,
+        };
+        let saved = SavedSession {
+            saved_at: resume::now(),
+            mode: format!("{:?}", self.game_mode),
+            procedure: self.rules.procedure.name.clone(),
+            shoe_number: self.roads.shoe_number(),
+            rounds_played: self.stats.rounds_played,
+            active_seat: self.active_seat,
+            beads: self.roads.bead_plate().beads().to_vec(),
+            shoe,
+            trends: self.stats.trends,
+            seats: self
+                .seats
+                .iter()
+                .map(|seat| SavedSeat::new(&seat.name, &seat.session, seat.selected_chip))
+                .collect(),
+        };
+        saved.save(&self.profile.name)
+    }
+
+    /// Seats one player per name for hot-seat play.
+    pub fn set_players(&mut self, names: &[String]) {
+        if !names.is_empty() {
+            self.seats = names
+                .iter()
+                .map(|name| Seat::new(name, &self.config.table))
+                .collect();
+            self.active_seat = 0;
+        }
+    }
+
+    /// Two players side by side, each with their own key group, betting on
+    /// the same hand. Names come from `set_players` when two were given.
+    /// Gives every seat the same target balance.
+    pub fn set_goal(&mut self, target: i32) {
+        for seat in &mut self.seats {
+            seat.goal = Some(BankrollGoal::new(target));
         }
     }
-    
+
+    /// Opens at `game`'s table instead of baccarat.
+    pub fn set_game(&mut self, game: Game) {
+        self.sit_at(game);
+    }
+
+    /// Opens in the lobby, so the player picks the first table.
+    pub fn open_lobby(&mut self) {
+        self.lobby = true;
+    }
+
+    /// Bots at the hold'em table, 1 to 5.
+    pub fn set_opponents(&mut self, opponents: usize) {
+        self.opponents = opponents.clamp(1, holdem::MAX_OPPONENTS);
+    }
+
+    pub fn set_split_view(&mut self) {
+        if self.seats.len() != 2 {
+            self.seats = vec![
+                Seat::new("Left", &self.config.table),
+                Seat::new("Right", &self.config.table),
+            ];
+        }
+        self.active_seat = 0;
+        self.split_view = true;
+    }
+
+    /// Per-player keys in split view: Z/X/C, A/D, W for the left seat and
+    /// ,/./'/', Left/Right, Up for the right. Returns whether the key was used.
+    fn handle_split_key(&mut self, code: KeyCode) -> bool {
+        let (index, action) = match code {
+            KeyCode::Char('z') => (0, SplitAction::BetOn(BetType::Player)),
+            KeyCode::Char('x') => (0, SplitAction::BetOn(BetType::Banker)),
+            KeyCode::Char('c') => (0, SplitAction::BetOn(BetType::Tie)),
+            KeyCode::Char('a') => (0, SplitAction::Step(false)),
+            KeyCode::Char('d') => (0, SplitAction::Step(true)),
+            KeyCode::Char('w') => (0, SplitAction::NextChip),
+            KeyCode::Char(',') => (1, SplitAction::BetOn(BetType::Player)),
+            KeyCode::Char('.') => (1, SplitAction::BetOn(BetType::Banker)),
+            KeyCode::Char('/') => (1, SplitAction::BetOn(BetType::Tie)),
+            KeyCode::Left => (1, SplitAction::Step(false)),
+            KeyCode::Right => (1, SplitAction::Step(true)),
+            KeyCode::Up => (1, SplitAction::NextChip),
+            _ => return false,
+        };
+        let limits = self.rules.limits;
+        let chips = self.config.table.chips;
+        let seat = &mut self.seats[index];
+        match action {
+            SplitAction::BetOn(bet_type) => {
+                seat.session
+                    .select_main_bet(bet_type, seat.selected_chip, &limits)
+            }
+            SplitAction::Step(increase) => {
+                let bet = seat.session.selected_bet;
+                seat.session
+                    .step_main_bet(bet, seat.selected_chip, increase, &limits);
+            }
+            SplitAction::NextChip => {
+                let next = chips
+                    .iter()
+                    .position(|&chip| chip == seat.selected_chip)
+                    .map_or(0, |i| i + 1);
+                seat.selected_chip = chips[next % chips.len()];
+            }
+        }
+        true
+    }
+
+    /// Runs the Speed-mode deal timer. The clock starts once the last hand is
+    /// face up and holds while an overlay or prompt is open.
+    async fn tick_auto_deal(&mut self) {
+        let waiting = self.animation_state.is_complete()
+            && !self.show_odds
+            && !self.show_significance
+            && self.log_viewer.is_none()
+            && self.demo.is_none()
+            && !self.awaiting_preset_slot
+            && !self.tie_score_menu
+            && self.sizing_bonus_bet.is_none()
+            && self.shoe_recap.is_none()
+            && !self.lobby
+            && self.table.is_none();
+        let Some(interval) = self.game_mode.auto_deal_interval().filter(|_| waiting) else {
+            self.next_auto_deal = None;
+            return;
+        };
+        let deadline = *self
+            .next_auto_deal
+            .get_or_insert_with(|| Instant::now() + interval);
+        if Instant::now() >= deadline {
+            self.next_auto_deal = None;
+            self.active_seat = 0;
+            self.play_round().await;
+        }
+    }
+
+    /// Keeps the house edges in the betting panel in step with the shoe.
+    /// Enumerating every deal takes a while, so it runs on its own thread
+    /// once the hand is face up, and the last figures stay up meanwhile.
+    fn tick_edges(&mut self) {
+        if let Some(job) = &self.edges_job
+            && let Ok(edges) = job.try_recv()
+        {
+            let mode = self
+                .edges_inputs
+                .as_ref()
+                .map_or(self.game_mode, |inputs| inputs.mode);
+            self.edges = Some((mode, edges));
+            self.edges_job = None;
+        }
+        if self.edges_job.is_some() || !self.animation_state.is_complete() {
+            return;
+        }
+        let inputs = EdgeInputs {
+            mode: self.game_mode,
+            over_under: self.rules.over_under,
+            payout_table: self.rules.payout_table(
+                self.game_mode,
+                self.event.as_ref().map(|event| &event.boosts),
+            ),
+            counts: self.game.card_source.composition(),
+        };
+        if self.edges_inputs.as_ref() == Some(&inputs) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let job = inputs.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(job.house_edges());
+        });
+        self.edges_inputs = Some(inputs);
+        self.edges_job = Some(rx);
+    }
+
+    /// Whole seconds until the next automatic deal, rounded up.
+    fn auto_deal_countdown(&self) -> Option<u64> {
+        self.next_auto_deal.map(|deadline| {
+            deadline
+                .saturating_duration_since(Instant::now())
+                .as_millis()
+                .div_ceil(1000) as u64
+        })
+    }
+
+    fn seat(&self) -> &Seat {
+        &self.seats[self.active_seat]
+    }
+
+    fn seat_mut(&mut self) -> &mut Seat {
+        &mut self.seats[self.active_seat]
+    }
+
     pub async fn run(&mut self) -> io::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
-        
+
         let res = self.run_app(&mut terminal).await;
-        
+
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -158,244 +769,2875 @@ impl RatatuiUI {
             DisableMouseCapture
         )?;
         terminal.show_cursor()?;
-        
+
         if let Err(err) = res {
             println!("{err:?}");
         }
-        
+        if let Err(e) = self.save_session() {
+            eprintln!("Table not saved: {}", e);
+        }
+        for record in self.seats.iter().filter_map(|seat| seat.recorder.finish()) {
+            if let Err(e) = self.session_log.append(&record) {
+                eprintln!("Session for {} not saved: {}", record.player, e);
+            }
+        }
+        if let Err(e) = self.lifetime.save() {
+            eprintln!("Lifetime statistics not saved: {}", e);
+        }
+        // Hot-seat players share the profile, so only a table of one carries
+        // its bankroll over.
+        if let [seat] = self.seats.as_slice() {
+            self.profile
+                .remember(seat.session.balance, self.game_mode, seat.selected_chip);
+        }
+        if let Err(e) = self.profile.save() {
+            eprintln!("Profile not saved: {}", e);
+        }
+        if !self.replay_hands.is_empty() {
+            let rules =
+                ReplayRules::new(&self.rules, self.event.as_ref().map(|event| &event.boosts));
+            let replay = Replay::new(
+                &self.profile.name,
+                self.seed,
+                rules,
+                std::mem::take(&mut self.replay_hands),
+            );
+            match replay.save() {
+                Ok(path) => println!("Session replay saved to {}", path.display()),
+                Err(e) => eprintln!("Session replay not saved: {}", e),
+            }
+        }
+        self.fairness.reveal();
+        if let Err(e) = self.fairness.save(&self.profile.name) {
+            eprintln!("Shoe seeds not saved: {}", e);
+        }
+
         Ok(())
     }
-    
+
     async fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
         loop {
             terminal.draw(|f| self.ui(f))?;
-            
-            // Update animation state
-            self.animation_state.update();
-            
-            if event::poll(Duration::from_millis(50))? {
-                if let Event::Key(key) = event::read()? {
+
+            self.tick_deal();
+            self.play_table(|table, ctx| table.tick(ctx));
+            if self.stats.pace.is_round_in_progress() && self.animation_state.is_complete() {
+                self.stats.pace.round_finished();
+            }
+            // Hold the bell until the cards are face up so it can't spoil the deal.
+            if self.alarm_bell_pending && self.animation_state.is_complete() {
+                self.alarm_bell_pending = false;
+                self.alarms.ring_bell();
+            }
+            self.tick_edges();
+            self.tick_auto_deal().await;
+            self.tick_demo().await;
+            self.tick_autoplay().await;
+            self.reload_config();
+
+            if event::poll(Duration::from_millis(50))?
+                && let Some(key) = self.next_key(terminal)?
+            {
+                self.last_input = Instant::now();
+                if self.demo.is_some() {
+                    self.stop_demo();
+                    continue;
+                }
+                if key.code == KeyCode::F(12) {
+                    self.cycle_theme();
+                    continue;
+                }
+                if self.autoplay.is_some() {
                     match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                        KeyCode::Char('p') => self.bet_type = BetType::Player,
-                        KeyCode::Char('b') => self.bet_type = BetType::Banker,
-                        KeyCode::Char('t') => self.bet_type = BetType::Tie,
-                        KeyCode::Char(' ') => {
-                            if self.animation_state.is_complete() {
-                                self.play_round().await;
-                            }
+                        KeyCode::Char('+' | '=') => self.pace_autoplay(0.5),
+                        KeyCode::Char('-') => self.pace_autoplay(2.0),
+                        _ => self.stop_autoplay(None),
+                    }
+                    continue;
+                }
+                if self.lobby {
+                    if self.config.keys.action(key.code) == Some(Action::Quit) {
+                        return Ok(());
+                    }
+                    self.handle_lobby_key(key.code);
+                    continue;
+                }
+                if let Some(table) = &self.table {
+                    if self.config.keys.action(key.code) == Some(Action::Quit) && !table.typing() {
+                        return Ok(());
+                    }
+                    if self.play_table(|table, ctx| table.handle_input(key.code, ctx))
+                        == Some(TableAction::Lobby)
+                    {
+                        self.lobby = true;
+                    }
+                    continue;
+                }
+                if self.shoe_recap.is_some() && self.animation_state.is_complete() {
+                    self.shoe_recap = None;
+                    continue;
+                }
+                if self.awaiting_preset_slot {
+                    self.handle_preset_slot(key.code);
+                    continue;
+                }
+                if self.tie_score_menu {
+                    self.handle_tie_score_key(key.code);
+                    continue;
+                }
+                if self.sizing_bonus_bet.is_some() {
+                    self.handle_bonus_chip(key.code);
+                    continue;
+                }
+                if self.log_viewer.is_some() {
+                    self.handle_log_viewer_key(key.code);
+                    continue;
+                }
+                if decision_prompt(&self.game).is_some() {
+                    if key.code == KeyCode::Esc
+                        || self.config.keys.action(key.code) == Some(Action::Quit)
+                    {
+                        return Ok(());
+                    }
+                    self.handle_decision_key(key.code);
+                    continue;
+                }
+                // Bets and the table stay locked while the cards come out.
+                if !self.animation_state.is_complete()
+                    && key.code != KeyCode::Esc
+                    && !matches!(
+                        self.config.keys.action(key.code),
+                        Some(
+                            Action::Quit
+                                | Action::Stats
+                                | Action::Odds
+                                | Action::Significance
+                                | Action::Count
+                        )
+                    )
+                {
+                    continue;
+                }
+                if self.split_view && self.handle_split_key(key.code) {
+                    continue;
+                }
+                match self.config.keys.action(key.code) {
+                    Some(Action::Quit) => return Ok(()),
+                    Some(Action::Player) => self.select_main_bet(BetType::Player),
+                    Some(Action::Banker) => self.select_main_bet(BetType::Banker),
+                    Some(Action::Tie) => self.select_main_bet(BetType::Tie),
+                    Some(Action::Deal) => {
+                        if self.animation_state.is_complete() {
+                            self.end_turn().await;
                         }
-                        KeyCode::Char('1') => self.current_bet = 10,
-                        KeyCode::Char('2') => self.current_bet = 50,
-                        KeyCode::Char('3') => self.current_bet = 100,
-                        KeyCode::Char('4') => self.current_bet = 500,
-                        KeyCode::Char('5') => self.current_bet = 1000,
-                        KeyCode::Char('m') => self.cycle_game_mode(),
-                        KeyCode::Char('s') => self.show_stats = !self.show_stats,
-                        KeyCode::F(1) => self.toggle_bonus_bet("player_pair"),
-                        KeyCode::F(2) => self.toggle_bonus_bet("banker_pair"),
-                        _ => {}
                     }
+                    Some(Action::SavePreset) => {
+                        self.awaiting_preset_slot = true;
+                        self.status_message = Some("Save preset: press 1-4".to_string());
+                    }
+                    Some(Action::Mode) => self.cycle_game_mode(),
+                    Some(Action::Stats) => self.show_stats = !self.show_stats,
+                    Some(Action::Odds) => self.toggle_odds(),
+                    Some(Action::Significance) => self.toggle_significance(),
+                    Some(Action::Count) => self.show_count = !self.show_count,
+                    Some(Action::History) => self.log_viewer = Some(LogViewer::load()),
+                    Some(Action::Export) => self.export_hands(),
+                    Some(Action::Games) => self.lobby = true,
+                    Some(Action::Autoplay) => {
+                        self.set_autoplay(self.config.autoplay.strategy.clone())
+                    }
+                    Some(Action::Natural) => self.toggle_bonus_bet(BonusBetType::EitherNatural),
+                    Some(Action::SuperSix) => self.toggle_bonus_bet(BonusBetType::SuperSix),
+                    Some(Action::Big) => self.toggle_bonus_bet(BonusBetType::Big),
+                    Some(Action::Small) => self.toggle_bonus_bet(BonusBetType::Small),
+                    Some(Action::MonkeySix) => self.toggle_bonus_bet(BonusBetType::MonkeySix),
+                    Some(Action::MonkeyTie) => self.toggle_bonus_bet(BonusBetType::MonkeyTie),
+                    Some(Action::SuitedTie) => self.toggle_bonus_bet(BonusBetType::SuitedTie),
+                    Some(Action::TieScores) if !self.game_mode.offers_tie_scores() => {
+                        self.status_message = Some(format!(
+                            "Tie score bets are not dealt at {:?} tables",
+                            self.game_mode
+                        ));
+                    }
+                    Some(Action::TieScores) => self.tie_score_menu = true,
+                    None => match key.code {
+                        KeyCode::Esc if self.hand_log.is_some() => self.hand_log = None,
+                        KeyCode::Tab if self.show_stats => {
+                            self.stats_lifetime = !self.stats_lifetime
+                        }
+                        KeyCode::Esc => return Ok(()),
+                        KeyCode::Backspace => self.undo_chip(),
+                        KeyCode::PageUp => self.scroll_hand_log(LOG_PAGE),
+                        KeyCode::PageDown => self.scroll_hand_log(-LOG_PAGE),
+                        KeyCode::Char(c @ '1'..='5') => self.select_chip(c as usize - '1' as usize),
+                        KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                            let limits = self.rules.limits;
+                            let before = PlacedBets::of(&self.seat().session);
+                            let seat = self.seat_mut();
+                            let bet = seat.session.selected_bet;
+                            seat.session
+                                .step_main_bet(bet, seat.selected_chip, c != '-', &limits);
+                            self.remember(before);
+                        }
+                        KeyCode::F(n @ 5..=8) => self.recall_preset(n as usize - 4),
+                        KeyCode::F(1) => self.toggle_bonus_bet(BonusBetType::PlayerPair),
+                        KeyCode::F(2) => self.toggle_bonus_bet(BonusBetType::BankerPair),
+                        KeyCode::F(9) => self.toggle_bonus_bet(BonusBetType::Over),
+                        KeyCode::F(10) => self.toggle_bonus_bet(BonusBetType::Under),
+                        _ => {}
+                    },
                 }
             }
         }
     }
-    
-    fn ui(&self, f: &mut Frame) {
-        let chunks = Layout::default()
+
+    /// Applies edits to the settings files, or says why they were rejected
+    /// (the previous settings stay in effect). The table defaults are what
+    /// it opened with and wait for the next launch.
+    fn reload_config(&mut self) {
+        let Some(reloaded) = self.config_watcher.poll() else {
+            return;
+        };
+        self.status_message = Some(match reloaded {
+            Ok(mut config) => {
+                let table_changed = config.table != self.config.table;
+                config.table = self.config.table;
+                self.config = config;
+                match table_changed {
+                    true => "Settings reloaded; the [table] defaults apply from the next launch"
+                        .to_string(),
+                    false => "Settings reloaded".to_string(),
+                }
+            }
+            Err(e) => format!("Settings not reloaded, {}", e),
+        });
+    }
+
+    /// Switches to the next built-in theme, dropping any colors the
+    /// settings override, until the settings files next change.
+    fn cycle_theme(&mut self) {
+        self.config.theme = self.config.theme.next();
+        self.status_message = Some(format!("Theme: {}", self.config.theme.label()));
+    }
+
+    /// The screen's title, cards, betting info, and stats or controls
+    /// areas.
+    fn screen(&self, area: Rect) -> Rc<[Rect]> {
+        Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([
-                Constraint::Length(3),    // Title
-                Constraint::Length(12),   // Cards display
-                Constraint::Length(5),    // Betting info
-                Constraint::Min(0),       // Stats/Controls
+                Constraint::Length(3),  // Title
+                Constraint::Length(12), // Cards display
+                Constraint::Length(if self.seats.len() > 1 && !self.split_view {
+                    11
+                } else {
+                    10
+                }), // Betting info
+                Constraint::Min(0),     // Stats/Controls
             ])
-            .split(f.area());
-        
+            .split(area)
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let theme = &self.config.theme;
+        let chunks = self.screen(f.area());
+
         // Title
-        let title = Paragraph::new(format!("BACCARAT - {:?} Mode", self.game_mode))
-            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        let mut title_text = match &self.event {
+            Some(event) => format!(
+                "BACCARAT - {:?} Mode  |  {}",
+                self.game_mode,
+                event.banner_text()
+            ),
+            None => format!("BACCARAT - {:?} Mode", self.game_mode),
+        };
+        title_text.push_str(&format!(
+            "  |  {} ${}-${}",
+            self.rules.name, self.rules.limits.min_bet, self.rules.limits.max_bet
+        ));
+        if self.rules.procedure.name != "standard" {
+            title_text.push_str(&format!("  |  {} procedure", self.rules.procedure.name));
+        }
+        if let Some(seconds) = self.auto_deal_countdown() {
+            title_text.push_str(&format!("  |  Next hand in {}s", seconds));
+        }
+        if let Some(table) = &self.table {
+            title_text = table.title(&self.table_view());
+        }
+        if self.lobby {
+            title_text = format!("TERMINAL CASINO - Lobby  |  {} tables", TABLES.len());
+        }
+        let title = Paragraph::new(title_text)
+            .style(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
+            .block(theme.panel());
         f.render_widget(title, chunks[0]);
-        
-        // Cards display
+
+        if self.lobby {
+            self.render_lobby(f, chunks[1].union(chunks[3]));
+            return;
+        }
+        if let Some(table) = &self.table {
+            table.render(f, &chunks, &self.table_view());
+            return;
+        }
+        if self.show_odds {
+            self.render_odds(f, chunks[1].union(chunks[3]));
+            return;
+        }
+        if self.show_significance {
+            self.render_significance(f, chunks[1].union(chunks[3]));
+            return;
+        }
+        if let Some(viewer) = &self.log_viewer {
+            render_log_viewer(f, chunks[1].union(chunks[3]), viewer, &self.config.theme);
+            return;
+        }
+
+        // Cards display, then the felt
         self.render_cards(f, chunks[1]);
-        
-        // Betting info
-        self.render_betting_info(f, chunks[2]);
-        
-        // Stats or Controls
-        if self.show_stats {
-            self.render_stats(f, chunks[3]);
+        let (felt, betting, rest) = self.table_areas(&chunks);
+        if let Some(felt) = felt {
+            self.render_felt(f, felt);
+        }
+
+        // Betting info, one panel per player in split view
+        if self.split_view {
+            let panels = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[2]);
+            for (index, panel) in panels.iter().enumerate() {
+                self.render_betting_info(f, *panel, index);
+            }
+        } else {
+            self.render_betting_info(f, betting, self.active_seat);
+        }
+
+        // Shoe recap, stats, or controls
+        if let Some(recap) = &self.shoe_recap
+            && self.animation_state.is_complete()
+        {
+            self.render_shoe_recap(f, rest, recap);
+        } else if self.tie_score_menu {
+            self.render_tie_score_menu(f, rest);
+        } else if let Some(scroll) = self.hand_log {
+            self.render_hand_log(f, rest, scroll);
+        } else if self.show_count {
+            self.render_count(f, rest);
+        } else if self.show_stats {
+            self.render_stats(f, rest);
         } else {
-            self.render_controls(f, chunks[3]);
+            self.render_controls(f, rest);
         }
     }
-    
-    fn render_cards(&self, f: &mut Frame, area: Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
+
+    /// Where the felt, the betting panel, and the stats or controls go. The
+    /// felt sits between the cards and the betting panel, its bet boxes as
+    /// tall as the screen allows, where there is room for it and the
+    /// controls, and one player bets at a time.
+    fn table_areas(&self, chunks: &[Rect]) -> (Option<Rect>, Rect, Rect) {
+        let room = chunks[3]
+            .height
+            .saturating_sub(SIDE_SPOTS_HEIGHT + CONTROLS_HEIGHT);
+        if self.split_view || room < MIN_BOX_HEIGHT {
+            return (None, chunks[2], chunks[3]);
+        }
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(50),
-                Constraint::Percentage(50),
+                Constraint::Length(room.min(MAX_BOX_HEIGHT) + SIDE_SPOTS_HEIGHT),
+                Constraint::Length(chunks[2].height),
+                Constraint::Min(0),
             ])
-            .split(area);
-        
-        // Player cards
-        let player_display = CardRenderer::create_hand_display(
-            &self.game.player_hand,
-            "PLAYER".to_string(),
-            self.game.state.player_score
-        );
-        f.render_widget(player_display, chunks[0]);
-        
-        // Banker cards
-        let banker_display = CardRenderer::create_hand_display(
-            &self.game.banker_hand,
-            "BANKER".to_string(),
-            self.game.state.banker_score
-        );
-        f.render_widget(banker_display, chunks[1]);
+            .split(chunks[2].union(chunks[3]));
+        (Some(rows[0]), rows[1], rows[2])
     }
-    
-    fn render_betting_info(&self, f: &mut Frame, area: Rect) {
-        let betting_text = vec![
-            Line::from(vec![
-                Span::raw("Balance: "),
-                Span::styled(format!("${}", self.balance), Style::default().fg(Color::Green)),
-            ]),
-            Line::from(vec![
-                Span::raw("Main Bet: "),
-                Span::styled(
-                    format!("${} on {:?}", self.current_bet, self.bet_type),
-                    Style::default().fg(Color::Yellow)
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw("Bonus Bets: "),
-                Span::styled(
-                    format!("${}", self.bonus_bets.total_bet()),
-                    Style::default().fg(Color::Magenta)
-                ),
-            ]),
-        ];
-        
-        let betting_info = Paragraph::new(betting_text)
-            .block(Block::default().borders(Borders::ALL).title("Betting"));
-        f.render_widget(betting_info, area);
+
+    /// Where the felt is on a screen of `area`, while it is drawn.
+    fn felt_area(&self, area: Rect) -> Option<Rect> {
+        if self.lobby
+            || self.table.is_some()
+            || self.show_odds
+            || self.show_significance
+            || self.log_viewer.is_some()
+        {
+            return None;
+        }
+        self.table_areas(&self.screen(area)).0
     }
-    
-    fn render_stats(&self, f: &mut Frame, area: Rect) {
-        let stats_text = vec![
-            Line::from(format!("Rounds Played: {}", self.stats.rounds_played)),
-            Line::from(format!("Win Rate: {:.1}%", self.stats.win_rate())),
-            Line::from(format!(
-                "P: {} | B: {} | T: {}",
-                self.stats.player_wins, self.stats.banker_wins, self.stats.ties
-            )),
-        ];
-        
-        let stats = Paragraph::new(stats_text)
-            .block(Block::default().borders(Borders::ALL).title("Statistics"));
-        f.render_widget(stats, area);
+
+    /// The felt's main bet boxes, chip rack, and side bet row.
+    fn felt_rows(&self, felt: Rect) -> (Rect, Rect, Rect) {
+        let rack_width = self
+            .config
+            .table
+            .chips
+            .iter()
+            .map(|&chip| chip_width(chip))
+            .sum::<u16>()
+            + 2;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(SIDE_SPOTS_HEIGHT)])
+            .split(felt);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(rack_width)])
+            .split(rows[0]);
+        (top[0], top[1], rows[1])
     }
-    
-    fn render_controls(&self, f: &mut Frame, area: Rect) {
-        let controls = vec![
-            "[P] Player  [B] Banker  [T] Tie  [M] Mode",
-            "[1] $10  [2] $50  [3] $100  [4] $500  [5] $1000",
-            "[F1-F2] Bonus Bets  [S] Stats  [SPACE] Deal",
-            "[Q/ESC] Quit",
-        ];
-        
-        let controls_text: Vec<Line> = controls.iter()
-            .map(|&s| Line::from(s))
+
+    /// Every spot on the felt and where it is drawn; side bet spots run
+    /// left to right and onto the next line when one fills up.
+    fn bet_zones(&self, felt: Rect) -> Vec<(BetZone, Rect)> {
+        let (boxes, rack, side) = self.felt_rows(felt);
+        let bets = self.game_mode.main_bets();
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, bets.len() as u32); bets.len()])
+            .split(boxes);
+        let mut zones: Vec<(BetZone, Rect)> = bets
+            .iter()
+            .zip(columns.iter())
+            .map(|(&bet, &rect)| (BetZone::Main(bet), rect))
             .collect();
-        
-        let controls_widget = Paragraph::new(controls_text)
-            .block(Block::default().borders(Borders::ALL).title("Controls"));
-        f.render_widget(controls_widget, area);
+
+        let shelf = rack.inner(Margin::new(1, 1));
+        let mut x = shelf.x;
+        for (index, &chip) in self.config.table.chips.iter().enumerate() {
+            let width = chip_width(chip);
+            zones.push((
+                BetZone::Chip(index),
+                Rect::new(x, shelf.y, width, 1).intersection(shelf),
+            ));
+            x += width;
+        }
+
+        let spots = side.inner(Margin::new(1, 1));
+        let (mut x, mut y) = (spots.x, spots.y);
+        let offered = BonusBetType::ALL
+            .into_iter()
+            .filter(|&bet| self.rules.offers(bet) && self.game_mode.offers_side_bet(bet));
+        for bet in offered {
+            let width = self.spot_line(bet).width() as u16;
+            if x > spots.x && x + width > spots.right() {
+                x = spots.x;
+                y += 1;
+            }
+            if y >= spots.bottom() {
+                break;
+            }
+            zones.push((
+                BetZone::Side(bet),
+                Rect::new(x, y, width, 1).intersection(spots),
+            ));
+            x += width + 1;
+        }
+        zones
+    }
+
+    /// The chips that make up `stake`, biggest first, as glyphs in each
+    /// chip's color: a stack to a line.
+    fn chip_stacks(&self, stake: i32) -> Vec<Line<'static>> {
+        let mut left = stake;
+        let mut stacks = Vec::new();
+        for (index, &chip) in self.config.table.chips.iter().enumerate().rev() {
+            let count = left / chip;
+            if count > 0 {
+                left -= count * chip;
+                stacks.push(Line::from(vec![
+                    Span::styled(
+                        stack_glyphs(count),
+                        Style::default().fg(self.config.theme.chips[index]),
+                    ),
+                    Span::raw(format!(" ${}", chip)),
+                ]));
+            }
+        }
+        stacks
+    }
+
+    /// A side bet spot's label, with the chips and stake on it.
+    fn spot_line(&self, bet: BonusBetType) -> Line<'static> {
+        let stake = self.seat().session.slip.bonus_bets.get(bet);
+        if stake == Money::ZERO {
+            return Line::from(format!(" {} ", spot_label(bet)));
+        }
+        let mut spans = vec![Span::raw(format!(" {} ", spot_label(bet)))];
+        for stack in self.chip_stacks(stake.whole_dollars() as i32) {
+            spans.extend(stack.spans.into_iter().take(1));
+        }
+        spans.push(Span::raw(format!(" ${} ", stake)));
+        Line::from(spans)
+    }
+
+    /// The betting layout seen from above: a box for each main bet with
+    /// the chips on it stacked by value and the total along the bottom,
+    /// the chip rack with the selected chip picked out, and the side bet
+    /// spots. The spot under the mouse is lit up.
+    fn render_felt(&self, f: &mut Frame, felt: Rect) {
+        let theme = &self.config.theme;
+        let seat = self.seat();
+        let (_, rack, side) = self.felt_rows(felt);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.felt)),
+            felt,
+        );
+        f.render_widget(theme.panel().title("Chips"), rack);
+        f.render_widget(theme.panel().title("Side Bets"), side);
+        for (zone, area) in self.bet_zones(felt) {
+            let hovered = self.hover == Some(zone);
+            let lit = |style: Style| {
+                if hovered {
+                    style.add_modifier(Modifier::REVERSED)
+                } else {
+                    style
+                }
+            };
+            let widget = match zone {
+                BetZone::Main(bet) => {
+                    let stake = seat.session.main_bet(bet);
+                    let border = match (hovered, stake > 0) {
+                        (true, _) => Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                        (false, true) => Style::default().fg(theme.bet),
+                        (false, false) => Style::default().fg(theme.border),
+                    };
+                    let mut block = theme
+                        .panel()
+                        .border_style(border)
+                        .title(self.game_mode.bet_label(bet));
+                    if stake > 0 {
+                        block = block.title_bottom(
+                            Line::styled(
+                                format!("${}", stake),
+                                Style::default().fg(theme.bet).add_modifier(Modifier::BOLD),
+                            )
+                            .centered(),
+                        );
+                    }
+                    // Stacks that don't fit share the last line.
+                    let mut stacks = self.chip_stacks(stake);
+                    let lines = area.height.saturating_sub(2).max(1) as usize;
+                    if stacks.len() > lines {
+                        let rest: Vec<Span> = stacks
+                            .split_off(lines - 1)
+                            .into_iter()
+                            .flat_map(|stack| stack.spans.into_iter().chain([Span::raw(" ")]))
+                            .collect();
+                        stacks.push(Line::from(rest));
+                    }
+                    Paragraph::new(stacks)
+                        .alignment(Alignment::Center)
+                        .block(block)
+                }
+                BetZone::Side(bet) => {
+                    let style = match seat.session.slip.bonus_bets.get(bet) {
+                        Money::ZERO => Style::default(),
+                        _ => Style::default()
+                            .fg(theme.side_bet)
+                            .add_modifier(Modifier::BOLD),
+                    };
+                    Paragraph::new(self.spot_line(bet)).style(lit(style))
+                }
+                BetZone::Chip(index) => {
+                    let chip = self.config.table.chips[index];
+                    let style = if self.chip_unavailable(chip).is_some() {
+                        Style::default().fg(theme.muted)
+                    } else if chip == seat.selected_chip {
+                        Style::default()
+                            .fg(theme.bet)
+                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                    } else {
+                        Style::default()
+                    };
+                    let glyph = match self.chip_unavailable(chip) {
+                        Some(_) => Style::default().fg(theme.muted),
+                        None => Style::default().fg(theme.chips[index]),
+                    };
+                    Paragraph::new(Line::from(vec![
+                        Span::raw(" "),
+                        Span::styled(CHIP_GLYPH, glyph),
+                        Span::styled(format!("${}", chip), style),
+                        Span::raw(" "),
+                    ]))
+                    .style(lit(Style::default()))
+                }
+            };
+            f.render_widget(widget, area);
+        }
+    }
+
+    /// The next key pressed, or `None` for anything else; mouse events are
+    /// handled here.
+    fn next_key<B: Backend>(&mut self, terminal: &Terminal<B>) -> io::Result<Option<KeyEvent>> {
+        match event::read()? {
+            Event::Key(key) => Ok(Some(key)),
+            Event::Mouse(mouse) => {
+                let size = terminal.size()?;
+                self.handle_mouse(mouse, Rect::new(0, 0, size.width, size.height));
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Lights up the spot under the pointer. A left click bets on a box or
+    /// spot, or picks up a chip from the rack; a right click takes a chip
+    /// off. Clicks wait, as keys do, while the cards come out or a prompt
+    /// is open.
+    fn handle_mouse(&mut self, mouse: MouseEvent, screen: Rect) {
+        let pointer = Position::new(mouse.column, mouse.row);
+        self.hover = self
+            .felt_area(screen)
+            .and_then(|felt| {
+                self.bet_zones(felt)
+                    .into_iter()
+                    .find(|(_, area)| area.contains(pointer))
+            })
+            .map(|(zone, _)| zone);
+        let add = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => true,
+            MouseEventKind::Down(MouseButton::Right) => false,
+            _ => return,
+        };
+        self.last_input = Instant::now();
+        if self.demo.is_some() {
+            self.stop_demo();
+            return;
+        }
+        if self.autoplay.is_some() {
+            self.stop_autoplay(None);
+            return;
+        }
+        if self.shoe_recap.is_some() && self.animation_state.is_complete() {
+            self.shoe_recap = None;
+            return;
+        }
+        let waiting = self.awaiting_preset_slot
+            || self.tie_score_menu
+            || self.sizing_bonus_bet.is_some()
+            || decision_prompt(&self.game).is_some()
+            || !self.animation_state.is_complete();
+        if let Some(zone) = self.hover
+            && !waiting
+        {
+            self.click_zone(zone, add);
+        }
+    }
+
+    /// A click on `zone`. Each click on a main bet box or a side bet spot
+    /// adds the selected chip to the stake there, up to the limits, so any
+    /// of the boxes can hold a bet at once. A chip picked up from the rack
+    /// goes on the next click.
+    fn click_zone(&mut self, zone: BetZone, add: bool) {
+        let limits = self.rules.limits;
+        match zone {
+            // Picking up a chip leaves the bets down, unlike its key.
+            BetZone::Chip(index) if add => {
+                let chip = self.config.table.chips[index];
+                match self.chip_unavailable(chip) {
+                    Some(reason) => self.status_message = Some(reason),
+                    None => self.seat_mut().selected_chip = chip,
+                }
+            }
+            BetZone::Chip(_) => {}
+            BetZone::Main(bet) => {
+                let before = PlacedBets::of(&self.seat().session);
+                let seat = self.seat_mut();
+                seat.session
+                    .step_main_bet(bet, seat.selected_chip, add, &limits);
+                self.remember(before);
+            }
+            BetZone::Side(bet)
+                if !add && self.seat().session.slip.bonus_bets.get(bet) == Money::ZERO => {}
+            BetZone::Side(bet) => {
+                let before = PlacedBets::of(&self.seat().session);
+                let seat = self.seat_mut();
+                let staked = seat.session.slip.bonus_bets.get(bet).whole_dollars() as i32;
+                let stake = match add {
+                    true => limits.clamp_side(staked + seat.selected_chip),
+                    false if staked - seat.selected_chip < limits.min_side_bet => Money::ZERO,
+                    false => limits.clamp_side(staked - seat.selected_chip),
+                };
+                seat.session.slip.bonus_bets.set(bet, stake);
+                self.remember(before);
+                self.status_message = Some(match stake {
+                    Money::ZERO => format!("{} bet taken down", bet),
+                    stake => format!("{} bet ${}", bet, stake),
+                });
+            }
+        }
+    }
+
+    fn render_cards(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let back_fill = self.event.as_ref().map_or('░', |event| event.card_back);
+        let (player_revealed, banker_revealed) = self.animation_state.revealed(&self.game);
+
+        // Player cards
+        let player_display = CardRenderer::create_partial_hand_display(
+            &self.game.player_hand,
+            self.game_mode.side_name(Side::Player).to_uppercase(),
+            HandScore::of(&self.game, Side::Player),
+            player_revealed,
+            back_fill,
+            chunks[0].width,
+            theme,
+        );
+        f.render_widget(player_display, chunks[0]);
+
+        // Banker cards
+        let banker_display = CardRenderer::create_partial_hand_display(
+            &self.game.banker_hand,
+            self.game_mode.side_name(Side::Banker).to_uppercase(),
+            HandScore::of(&self.game, Side::Banker),
+            banker_revealed,
+            back_fill,
+            chunks[1].width,
+            theme,
+        );
+        f.render_widget(banker_display, chunks[1]);
+    }
+
+    fn render_betting_info(&self, f: &mut Frame, area: Rect, index: usize) {
+        let theme = &self.config.theme;
+        let seat = &self.seats[index];
+        let mut betting_text = Vec::new();
+        if self.seats.len() > 1 && !self.split_view {
+            betting_text.push(self.seats_line());
+        }
+        betting_text.extend([
+            Line::from(vec![
+                Span::raw("Balance: "),
+                Span::styled(
+                    format!("${}", seat.session.balance),
+                    Style::default().fg(theme.balance),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Main Bets: "),
+                Span::styled(
+                    format!(
+                        "{}  (chip ${})",
+                        self.main_bets_text(seat),
+                        seat.selected_chip
+                    ),
+                    Style::default().fg(theme.bet),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Bonus Bets: "),
+                Span::styled(
+                    format!("${}", seat.session.slip.bonus_bets.total_bet()),
+                    Style::default().fg(theme.side_bet),
+                ),
+                Span::styled(self.side_bet_edges(seat), Style::default().fg(theme.muted)),
+            ]),
+            self.house_edge_line(seat),
+        ]);
+        if let Some(report) = &seat.session.last_settlement
+            && self.animation_state.is_complete()
+            && decision_prompt(&self.game).is_none()
+        {
+            let bets: Vec<String> = report.bets.iter().map(SettledBet::describe).collect();
+            betting_text.push(Line::from(format!(
+                "Last round {:+}: {}",
+                report.net(),
+                bets.join(", ")
+            )));
+        }
+
+        if seat.session.slip.bonus_bets.over > Money::ZERO
+            || seat.session.slip.bonus_bets.under > Money::ZERO
+        {
+            let side = if seat.session.slip.bonus_bets.over > Money::ZERO {
+                "Over"
+            } else {
+                "Under"
+            };
+            let mut spans = vec![
+                Span::raw(format!("{} {}.5: ", side, self.rules.over_under.line)),
+                Span::styled(
+                    format!(
+                        "${}",
+                        seat.session.slip.bonus_bets.over + seat.session.slip.bonus_bets.under
+                    ),
+                    Style::default().fg(theme.side_bet),
+                ),
+            ];
+            if self.game.state.round_complete == 1 && self.animation_state.is_complete() {
+                spans.push(Span::raw(format!(
+                    "  (total {})",
+                    self.game.combined_points()
+                )));
+            }
+            betting_text.push(Line::from(spans));
+        }
+
+        if seat.session.slip.bonus_bets.either_natural > Money::ZERO {
+            let mut spans = vec![
+                Span::raw("Either Natural: "),
+                Span::styled(
+                    format!("${}", seat.session.slip.bonus_bets.either_natural),
+                    Style::default().fg(theme.side_bet),
+                ),
+            ];
+            if self.game.state.round_complete == 1 && self.animation_state.is_complete() {
+                spans.push(Span::raw(format!("  ({})", natural_text(&self.game))));
+            }
+            betting_text.push(Line::from(spans));
+        }
+
+        if let Some(banner) = &self.alarm_banner
+            && self.animation_state.is_complete()
+        {
+            betting_text.push(Line::from(Span::styled(
+                format!("🔔 {}", banner),
+                Style::default()
+                    .fg(theme.alert)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let title = if self.split_view {
+            seat.name.clone()
+        } else if self.seats.len() > 1 {
+            format!(
+                "{}'s turn ({}/{})",
+                seat.name,
+                self.active_seat + 1,
+                self.seats.len()
+            )
+        } else {
+            "Betting".to_string()
+        };
+        let betting_info =
+            Paragraph::new(betting_text).block(theme.panel().title(match &self.status_message {
+                Some(message) => format!("{} — {}", title, message),
+                None => title,
+            }));
+        f.render_widget(betting_info, area);
+    }
+
+    /// "$10 on Player, $5 on Tie", the bet chip steps go on marked with a
+    /// star.
+    fn main_bets_text(&self, seat: &Seat) -> String {
+        let bets: Vec<String> = seat
+            .session
+            .slip
+            .main_bets()
+            .map(|(bet, amount)| {
+                let mark = if bet == seat.session.selected_bet {
+                    "*"
+                } else {
+                    ""
+                };
+                format!("${} on {}{}", amount, self.game_mode.bet_label(bet), mark)
+            })
+            .collect();
+        if bets.is_empty() {
+            "none".to_string()
+        } else {
+            bets.join(", ")
+        }
+    }
+
+    /// The house edge of each main bet on the next hand, from what is left
+    /// in the shoe, with the seat's bets picked out.
+    fn house_edge_line(&self, seat: &Seat) -> Line<'static> {
+        let theme = &self.config.theme;
+        let Some((_, edges)) = self
+            .edges
+            .as_ref()
+            .filter(|(mode, _)| *mode == self.game_mode)
+        else {
+            return Line::from(Span::styled(
+                "House edge: working it out...",
+                Style::default().fg(theme.muted),
+            ));
+        };
+        let mut spans = vec![Span::raw("House edge:")];
+        for &(bet, edge) in &edges.main_bets {
+            let text = format!(" {} {:.2}%", self.game_mode.bet_label(bet), edge * 100.0);
+            spans.push(if seat.session.slip.main_bet(bet) > Money::ZERO {
+                Span::styled(
+                    text,
+                    Style::default().fg(theme.bet).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(text)
+            });
+        }
+        spans.push(Span::styled(
+            "  (this shoe)",
+            Style::default().fg(theme.muted),
+        ));
+        Line::from(spans)
+    }
+
+    /// "  (edges: player_pair 10.36%, big 4.32%)" for the side bets the seat
+    /// has up, once the edges are worked out.
+    fn side_bet_edges(&self, seat: &Seat) -> String {
+        let Some((_, edges)) = self
+            .edges
+            .as_ref()
+            .filter(|(mode, _)| *mode == self.game_mode)
+        else {
+            return String::new();
+        };
+        let placed: Vec<String> = BonusBetType::ALL
+            .iter()
+            .filter(|&&bet| seat.session.slip.bonus_bets.get(bet) > Money::ZERO)
+            .filter_map(|&bet| Some(format!("{} {:.2}%", bet, edges.side_bet(bet)? * 100.0)))
+            .collect();
+        if placed.is_empty() {
+            String::new()
+        } else {
+            format!("  (edges: {})", placed.join(", "))
+        }
+    }
+
+    /// Every seat's balance, the active one highlighted, with last round's
+    /// result once the cards are face up.
+    fn seats_line(&self) -> Line<'static> {
+        let theme = &self.config.theme;
+        let mut spans = Vec::new();
+        for (i, seat) in self.seats.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" | "));
+            }
+            let mut text = format!("{} ${}", seat.name, seat.session.balance);
+            if let Some(net) = seat.session.last_net()
+                && self.animation_state.is_complete()
+                && decision_prompt(&self.game).is_none()
+            {
+                text.push_str(&format!(" ({:+})", net));
+            }
+            spans.push(if i == self.active_seat {
+                Span::styled(
+                    format!("▶ {}", text),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(text)
+            });
+        }
+        Line::from(spans)
+    }
+
+    /// This session's figures for the stats view.
+    fn session_lines(&self) -> Vec<Line<'static>> {
+        let theme = &self.config.theme;
+        let mut stats_text = vec![Line::from(format!(
+            "Rounds Played: {}",
+            self.stats.rounds_played
+        ))];
+        for seat in &self.seats {
+            stats_text.push(Line::from(format!(
+                "{}: {} rounds | Win Rate: {:.1}% | Net: {:+}",
+                seat.name,
+                seat.session.rounds_played,
+                seat.session.win_rate(),
+                seat.session.total_won - seat.session.total_wagered
+            )));
+        }
+        stats_text.extend([Line::from(format!(
+            "P: {} | B: {} | T: {}",
+            self.stats.trends.wins[0], self.stats.trends.wins[1], self.stats.trends.wins[2]
+        ))]);
+        stats_text.extend(
+            self.stats
+                .trends
+                .lines(self.game_mode)
+                .into_iter()
+                .map(Line::from),
+        );
+        stats_text.extend([
+            Line::from(format!("Pace: {}", self.stats.pace.summary().describe())),
+            Line::from(format!(
+                "Last round: {}",
+                self.stats
+                    .pace
+                    .last_round_time()
+                    .map_or("-".to_string(), |d| format!("{:.1}s", d.as_secs_f64()))
+            )),
+        ]);
+        if let Some(hash) = self.fairness.commitment().filter(|_| self.demo.is_none()) {
+            stats_text.push(Line::from(format!("Shoe hash: {}", hash)));
+            stats_text.push(Line::from(format!(
+                "Client seed: {}",
+                self.fairness.client_seed()
+            )));
+        }
+
+        for seat in &self.seats {
+            let Some(projection) = &seat.goal_projection else {
+                continue;
+            };
+            let lines = projection.lines();
+            if self.seats.len() == 1 {
+                stats_text.extend(lines.into_iter().map(Line::from));
+            } else {
+                stats_text.push(Line::from(format!("{} {}", seat.name, lines[0])));
+            }
+            if projection.is_out_of_reach() && self.seats.len() > 1 {
+                stats_text.push(Line::from(Span::styled(
+                    "  Warning: out of reach with these bets",
+                    Style::default().fg(theme.highlight),
+                )));
+            }
+        }
+        stats_text
+    }
+
+    /// The profile's totals over every session, at this mode's tables and
+    /// at all of them.
+    fn lifetime_lines(&self) -> Vec<Line<'static>> {
+        let theme = &self.config.theme;
+        let here = self.lifetime.mode(self.game_mode);
+        let all = self.lifetime.total();
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{:?} tables: {} hands | Net: {:+}",
+                self.game_mode,
+                here.hands,
+                here.net()
+            ),
+            Style::default().fg(theme.accent),
+        ))];
+        if here.hands == 0 {
+            lines.push(Line::from(Span::styled(
+                "No hands recorded here yet.",
+                Style::default().fg(theme.muted),
+            )));
+        } else {
+            lines.extend(here.lines().into_iter().map(Line::from));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "All modes ({} played): {} hands | Net: {:+}",
+                self.lifetime.modes_played(),
+                all.hands,
+                all.net()
+            ),
+            Style::default().fg(theme.accent),
+        )));
+        if all.hands > 0 {
+            lines.extend(all.lines().into_iter().map(Line::from));
+        }
+        lines
+    }
+
+    fn render_stats(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let (stats_text, scope, other) = match self.stats_lifetime {
+            true => (self.lifetime_lines(), "lifetime", "Session"),
+            false => (self.session_lines(), "this session", "Lifetime"),
+        };
+
+        let winners = self.roads.winners();
+        let cut_card = match self.game.card_source.hands_until_cut_card() {
+            Some(hands) => format!(", ~{} to cut card", hands),
+            None => String::new(),
+        };
+        let road_title = format!(
+            "Big Road - shoe {}, {} hands{}",
+            self.roads.shoe_number(),
+            winners.len(),
+            cut_card
+        );
+
+        // The roads along the bottom, under the figures: bead plate, then
+        // the big road
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(scoreboard::ROWS as u16 + 2),
+            ])
+            .split(area);
+        let roads = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(BEAD_PLATE_WIDTH), Constraint::Min(0)])
+            .split(rows[1]);
+        // The figures, with the bankroll chart beside them
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(rows[0]);
+        let stats = Paragraph::new(stats_text).block(
+            theme
+                .panel()
+                .title(format!("Statistics, {}  [TAB] {}", scope, other)),
+        );
+        f.render_widget(stats, top[0]);
+        let (bets, bets_title): (Vec<(String, BetPerformance)>, String) = match self.stats_lifetime
+        {
+            true => (
+                self.lifetime
+                    .mode(self.game_mode)
+                    .bets
+                    .into_iter()
+                    .collect(),
+                format!("Lifetime bets at {:?} tables", self.game_mode),
+            ),
+            false => (
+                self.seat()
+                    .session
+                    .round_stats
+                    .by_bet
+                    .iter()
+                    .map(|(wager, performance)| (wager.to_string(), *performance))
+                    .collect(),
+                format!("{}'s bets", self.seat().name),
+            ),
+        };
+        let bet_rows = bets.len().clamp(1, MAX_BET_ROWS) as u16;
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(bet_rows + 3)])
+            .split(top[1]);
+        self.render_bankroll_chart(f, right[0]);
+        self.render_bet_breakdown(f, right[1], bets_title, bets);
+        f.render_widget(
+            BeadRoad::new(self.roads.bead_plate())
+                .theme(theme)
+                .block(theme.panel().title("Bead Plate")),
+            roads[0],
+        );
+        let road = self.roads.roadmap();
+        f.render_widget(
+            BigRoad::new(&road)
+                .theme(theme)
+                .block(theme.panel().title(road_title)),
+            roads[1],
+        );
+    }
+
+    /// The active seat's balance hand by hand against where it started,
+    /// with the session's low and high on the axis and its deepest drawdown
+    /// in the title.
+    fn render_bankroll_chart(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let seat = self.seat();
+        let record = seat.recorder.record();
+        let hands = record.rounds() as f64;
+        let start = record.starting_balance.as_dollars();
+        let curve: Vec<(f64, f64)> = record
+            .balances()
+            .enumerate()
+            .map(|(hand, balance)| (hand as f64, balance.as_dollars()))
+            .collect();
+        let baseline = [(0.0, start), (hands, start)];
+        let low = record.balances().min().unwrap_or(record.starting_balance);
+        let high = record.balances().max().unwrap_or(record.starting_balance);
+        let datasets = vec![
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.muted))
+                .data(&baseline),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.balance))
+                .data(&curve),
+        ];
+        let title = format!(
+            "{}'s bankroll - drawdown ${}",
+            seat.name,
+            record.max_drawdown()
+        );
+        let chart = Chart::new(datasets)
+            .block(theme.panel().title(title))
+            .x_axis(Axis::default().bounds([0.0, hands.max(1.0)]).labels(vec![
+                Span::raw("0"),
+                Span::raw(format!("{} hands", record.rounds())),
+            ]))
+            .y_axis(
+                Axis::default()
+                    .bounds([
+                        low.as_dollars(),
+                        high.as_dollars().max(low.as_dollars() + 1.0),
+                    ])
+                    .labels(vec![
+                        Span::raw(format!("${}", low)),
+                        Span::raw(format!("${}", high)),
+                    ]),
+            );
+        f.render_widget(chart, area);
+    }
+
+    /// Bets by kind, most wagered first: hands played, money staked and
+    /// returned, and the return on it, red when a bet is losing money.
+    fn render_bet_breakdown(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        title: String,
+        mut bets: Vec<(String, BetPerformance)>,
+    ) {
+        let theme = &self.config.theme;
+        bets.sort_by_key(|(_, performance)| std::cmp::Reverse(performance.wagered));
+        let rows: Vec<Row> = bets
+            .iter()
+            .map(|(name, performance)| {
+                let roi = match performance.roi() {
+                    Some(roi) if roi < 0.0 => Cell::from(format!("{:+.1}%", roi * 100.0))
+                        .style(Style::default().fg(theme.loss)),
+                    Some(roi) => Cell::from(format!("{:+.1}%", roi * 100.0))
+                        .style(Style::default().fg(theme.win)),
+                    None => Cell::from("-"),
+                };
+                Row::new(vec![
+                    Cell::from(name.clone()),
+                    Cell::from(performance.bets.to_string()),
+                    Cell::from(format!("${}", performance.wagered)),
+                    Cell::from(format!("${}", performance.won)),
+                    roi,
+                ])
+            })
+            .collect();
+        let header = ["Bet", "Hands", "Wagered", "Won", "ROI"]
+            .map(|h| Cell::from(h).style(Style::default().add_modifier(Modifier::BOLD)));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(5),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(7),
+            ],
+        )
+        .header(Row::new(header).style(Style::default().fg(theme.accent)))
+        .block(theme.panel().title(title));
+        f.render_widget(table, area);
+    }
+
+    /// What is left in the shoe rank by rank, how far each rank is off its
+    /// share of a full shoe, and the Dragon 7 and Panda 8 edges, which a
+    /// count moves further than any other bet.
+    fn render_count(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let (Some(counts), Some((dealt, size))) = (
+            self.game.card_source.composition(),
+            self.game.card_source.penetration(),
+        ) else {
+            let widget = Paragraph::new(Span::styled(
+                "A recorded shoe is dealt in a fixed order, so there is nothing to count.",
+                Style::default().fg(theme.muted),
+            ))
+            .block(theme.panel().title("Shoe Count"));
+            f.render_widget(widget, area);
+            return;
+        };
+        let block = theme.panel().title(format!(
+            "Shoe Count  {} of {} cards left  [{}] Close",
+            size - dealt,
+            size,
+            self.config.keys.label(Action::Count)
+        ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Min(0)])
+            .split(inner);
+
+        let top_rank = self.game_mode.top_rank() as usize;
+        let per_rank = size / top_rank;
+        let left: u32 = counts.iter().sum();
+        let ranks = 0..top_rank;
+        let row = |label: &str, cells: Vec<Cell<'static>>| {
+            Row::new(
+                std::iter::once(
+                    Cell::from(label.to_string()).style(Style::default().fg(theme.accent)),
+                )
+                .chain(cells),
+            )
+        };
+        let header = row(
+            "",
+            ranks
+                .clone()
+                .map(|i| Cell::from(CardRenderer::rank_symbol(i as u8 + 1)))
+                .collect(),
+        );
+        let remaining = row(
+            "Left",
+            ranks
+                .clone()
+                .map(|i| Cell::from(counts[i].to_string()))
+                .collect(),
+        );
+        let seen = row(
+            "Seen",
+            ranks
+                .clone()
+                .map(|i| Cell::from(per_rank.saturating_sub(counts[i] as usize).to_string()))
+                .collect(),
+        );
+        // How rich the rest of the shoe is in each rank against a full one.
+        let rich = row(
+            "Rich",
+            ranks
+                .clone()
+                .map(|i| {
+                    let share = counts[i] as f64 / left.max(1) as f64 * top_rank as f64 - 1.0;
+                    let color = if share < 0.0 { theme.loss } else { theme.win };
+                    Cell::from(format!("{:+.0}%", share * 100.0)).style(Style::default().fg(color))
+                })
+                .collect(),
+        );
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(5))
+            .chain(ranks.map(|_| Constraint::Length(5)))
+            .collect();
+        f.render_widget(
+            Table::new(vec![remaining, seen, rich], widths)
+                .header(header.style(Style::default().add_modifier(Modifier::BOLD))),
+            parts[0],
+        );
+
+        let mut lines = Vec::new();
+        let edges = self.edges.as_ref().filter(|(mode, _)| {
+            *mode == GameMode::EzBaccarat && self.game_mode == GameMode::EzBaccarat
+        });
+        match edges {
+            Some((_, edges)) => {
+                for &(bet, edge) in edges
+                    .main_bets
+                    .iter()
+                    .filter(|(bet, _)| matches!(bet, BetType::Dragon7 | BetType::Panda8))
+                {
+                    let color = if edge < 0.0 { theme.win } else { theme.muted };
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("{:<8}", self.game_mode.bet_label(bet))),
+                        Span::styled(
+                            format!("house edge {:+.2}%", edge * 100.0),
+                            Style::default().fg(color),
+                        ),
+                        Span::styled(
+                            if edge < 0.0 {
+                                "  the shoe favours the bet"
+                            } else {
+                                ""
+                            },
+                            Style::default().fg(theme.win).add_modifier(Modifier::BOLD),
+                        ),
+                    ]));
+                }
+            }
+            None if self.game_mode == GameMode::EzBaccarat => {
+                lines.push(Line::from(Span::styled(
+                    "Working out the Dragon 7 and Panda 8 edges...",
+                    Style::default().fg(theme.muted),
+                )));
+            }
+            None => lines.push(Line::from(Span::styled(
+                format!(
+                    "Dragon 7 and Panda 8 are dealt at EzBaccarat tables; [{}] changes mode.",
+                    self.config.keys.label(Action::Mode)
+                ),
+                Style::default().fg(theme.muted),
+            ))),
+        }
+        f.render_widget(Paragraph::new(lines), parts[1]);
+    }
+
+    /// Opens the hand history at the latest hand, or scrolls it `back`
+    /// lines towards the first.
+    fn scroll_hand_log(&mut self, back: isize) {
+        let last = self.hand_history.lines().len().saturating_sub(1);
+        self.hand_log = Some(match self.hand_log {
+            None => 0,
+            Some(scroll) => scroll.saturating_add_signed(back).min(last),
+        });
+    }
+
+    /// Writes the session's hands to the profile's `exports` directory as
+    /// both CSV and JSON, named for the time of the export.
+    fn export_hands(&mut self) {
+        if self.hand_history.hands().is_empty() {
+            self.status_message = Some("No hands to export yet".to_string());
+            return;
+        }
+        let stem = storage::profile_dir(&self.profile.name)
+            .join("exports")
+            .join(format!("hands-{}", resume::now()));
+        let written = [history::Format::Csv, history::Format::Json]
+            .into_iter()
+            .try_for_each(|format| {
+                self.hand_history
+                    .export(&stem.with_extension(format.extension()), format)
+            });
+        self.status_message = Some(match written {
+            Ok(()) => format!(
+                "Exported {} hands to {}.csv and .json",
+                self.hand_history.hands().len(),
+                stem.display()
+            ),
+            Err(e) => format!("Hands not exported: {}", e),
+        });
+    }
+
+    /// Every hand of the session, latest at the bottom, with the cards,
+    /// each seat's bets, and what they paid.
+    fn render_hand_log(&self, f: &mut Frame, area: Rect, scroll: usize) {
+        let theme = &self.config.theme;
+        let lines = self.hand_history.lines();
+        let height = area.height.saturating_sub(2) as usize;
+        // Scrolled right back, the first hand sits at the top of a full panel.
+        let end = lines
+            .len()
+            .saturating_sub(scroll.min(lines.len().saturating_sub(height)));
+        let start = end.saturating_sub(height);
+        let text: Vec<Line> = if lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "No hands dealt yet.",
+                Style::default().fg(theme.muted),
+            ))]
+        } else {
+            lines[start..end]
+                .iter()
+                .map(|line| match line.starts_with(' ') {
+                    true => Line::from(line.clone()),
+                    false => Line::from(Span::styled(
+                        line.clone(),
+                        Style::default().fg(theme.accent),
+                    )),
+                })
+                .collect()
+        };
+        let title = format!(
+            "Hand History  {} hands, lines {}-{} of {}  [PgUp/PgDn] Scroll  [{}] Export  [ESC] Close",
+            self.hand_history.hands().len(),
+            (start + 1).min(end),
+            end,
+            lines.len(),
+            self.config.keys.label(Action::Export)
+        );
+        let widget = Paragraph::new(text).block(theme.panel().title(title));
+        f.render_widget(widget, area);
+    }
+
+    fn render_shoe_recap(&self, f: &mut Frame, area: Rect, recap: &ShoeSummary) {
+        let theme = &self.config.theme;
+        let mut lines: Vec<Line> = recap.recap_lines().into_iter().map(Line::from).collect();
+        lines.push(Line::from(Span::styled(
+            "Press any key to shuffle a new shoe",
+            Style::default().fg(self.config.theme.accent),
+        )));
+        let widget = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(theme.panel().title("Shoe Recap"));
+        f.render_widget(widget, area);
+    }
+
+    fn render_tie_score_menu(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let bets = &self.seat().session.slip.bonus_bets.tie_score_bets;
+        let mut lines: Vec<Line> = (0..10u8)
+            .map(|score| {
+                let text = format!(
+                    "[{}] Tie on {}  pays {}:1",
+                    score,
+                    score,
+                    self.game.payout_table.tie_score_ratio(score)
+                );
+                match bets[score as usize] {
+                    Money::ZERO => Line::from(text),
+                    stake => Line::from(vec![
+                        Span::raw(text),
+                        Span::styled(format!("  ${}", stake), Style::default().fg(theme.side_bet)),
+                    ]),
+                }
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            "[0-9] Toggle a bet  Any other key closes",
+            Style::default().fg(theme.accent),
+        )));
+        let widget = Paragraph::new(lines).block(theme.panel().title("Tie Scores"));
+        f.render_widget(widget, area);
+    }
+
+    /// Toggles the active seat's bet on a tie at the pressed score, staking
+    /// the selected chip, or closes the menu on any other key.
+    fn handle_tie_score_key(&mut self, code: KeyCode) {
+        let KeyCode::Char(c @ '0'..='9') = code else {
+            self.tie_score_menu = false;
+            return;
+        };
+        let limits = self.rules.limits;
+        let before = PlacedBets::of(&self.seat().session);
+        let seat = self.seat_mut();
+        let chip = limits.clamp_side(seat.selected_chip);
+        let stake = &mut seat.session.slip.bonus_bets.tie_score_bets[c as usize - '0' as usize];
+        *stake = if *stake > Money::ZERO {
+            Money::ZERO
+        } else {
+            chip
+        };
+        self.remember(before);
+    }
+
+    fn toggle_odds(&mut self) {
+        self.show_odds = !self.show_odds;
+        self.show_significance = false;
+        if self.show_odds && self.mode_odds.is_none() {
+            self.mode_odds = Some(analytics::compare_modes());
+        }
+    }
+
+    fn render_odds(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let Some(mode_odds) = &self.mode_odds else {
+            return;
+        };
+
+        let mut header = vec![Cell::from("House edge")];
+        for odds in mode_odds {
+            let style = if odds.mode == self.game_mode {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            header.push(Cell::from(format!("{:?}", odds.mode)).style(style));
+        }
+
+        let mut main_bets: Vec<BetType> = Vec::new();
+        for odds in mode_odds {
+            for (bet, _) in &odds.house_edges {
+                if !main_bets.contains(bet) {
+                    main_bets.push(*bet);
+                }
+            }
+        }
+
+        let mut rows: Vec<Row> = main_bets
+            .iter()
+            .map(|bet| {
+                let mut cells = vec![Cell::from(bet.to_string())];
+                for odds in mode_odds {
+                    cells.push(match odds.house_edge(*bet) {
+                        Some(edge) => edge_cell(edge, theme),
+                        None => Cell::from("—").style(Style::default().fg(theme.muted)),
+                    });
+                }
+                Row::new(cells)
+            })
+            .collect();
+
+        rows.push(Row::new(vec![
+            Cell::from("Side bets").style(Style::default().add_modifier(Modifier::BOLD)),
+        ]));
+        for side_bet in BonusBetType::ALL {
+            let mut cells = vec![Cell::from(side_bet.name())];
+            for odds in mode_odds {
+                cells.push(
+                    match odds.side_bets.iter().find(|(name, _)| *name == side_bet) {
+                        Some((_, Some(edge))) => edge_cell(*edge, theme),
+                        Some((_, None)) => Cell::from("available"),
+                        None => Cell::from("—").style(Style::default().fg(theme.muted)),
+                    },
+                );
+            }
+            rows.push(Row::new(cells));
+        }
+
+        let mut widths = vec![Constraint::Length(16)];
+        widths.extend(mode_odds.iter().map(|_| Constraint::Length(14)));
+        let table = Table::new(rows, widths).header(Row::new(header)).block(
+            theme
+                .panel()
+                .title("Mode comparison (infinite deck)  [O] Close"),
+        );
+        f.render_widget(table, area);
     }
-    
+
+    fn toggle_significance(&mut self) {
+        self.show_significance = !self.show_significance;
+        self.show_odds = false;
+        if self.show_significance && self.hit_rates.is_none() {
+            self.hit_rates = Some(analytics::hit_rates(self.game_mode, self.rules.over_under));
+        }
+    }
+
+    fn render_significance(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let Some(rates) = &self.hit_rates else {
+            return;
+        };
+        let report = significance::analyze(&self.tally, rates);
+        let title = format!(
+            "Session significance: {} hands  |  P/B/T split p = {:.3}  [I] Close",
+            report.hands, report.split_p_value
+        );
+        if report.hands == 0 {
+            let empty = Paragraph::new("No hands dealt yet this session.")
+                .block(theme.panel().title(title));
+            f.render_widget(empty, area);
+            return;
+        }
+
+        let header = ["Bet", "Observed", "Expected", "p-value", "Verdict"]
+            .map(|h| Cell::from(h).style(Style::default().add_modifier(Modifier::BOLD)));
+        let rows: Vec<Row> = report
+            .tests
+            .iter()
+            .map(|test| {
+                let verdict_style = if test.is_significant() {
+                    Style::default().fg(theme.highlight)
+                } else {
+                    Style::default().fg(theme.muted)
+                };
+                Row::new(vec![
+                    Cell::from(test.name),
+                    Cell::from(format!("{:.2}%", test.observed * 100.0)),
+                    Cell::from(format!("{:.2}%", test.expected * 100.0)),
+                    Cell::from(format!("{:.3}", test.p_value)),
+                    Cell::from(test.verdict()).style(verdict_style),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(16),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new(header))
+            .block(theme.panel().title(title));
+        f.render_widget(table, area);
+    }
+
+    fn handle_log_viewer_key(&mut self, code: KeyCode) {
+        let Some(viewer) = &mut self.log_viewer else {
+            return;
+        };
+        if let Some(input) = &mut viewer.search_input {
+            match code {
+                KeyCode::Enter => {
+                    let query = std::mem::take(input);
+                    viewer.search_input = None;
+                    if let Some(open) = &mut viewer.open {
+                        open.search(&query);
+                    }
+                }
+                KeyCode::Esc => viewer.search_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        let mut close = false;
+        match &mut viewer.open {
+            Some(open) => match code {
+                KeyCode::Esc | KeyCode::Backspace => viewer.open = None,
+                KeyCode::Up => open.scroll_by(-1),
+                KeyCode::Down => open.scroll_by(1),
+                KeyCode::PageUp => open.scroll_by(-LOG_PAGE),
+                KeyCode::PageDown => open.scroll_by(LOG_PAGE),
+                KeyCode::Home => open.scroll = 0,
+                KeyCode::End => open.scroll_to_end(),
+                KeyCode::Char('/') => viewer.search_input = Some(String::new()),
+                KeyCode::Char('n') => open.next_match(true),
+                KeyCode::Char('N') => open.next_match(false),
+                KeyCode::Char('h') | KeyCode::Char('q') => close = true,
+                _ => {}
+            },
+            None => match code {
+                KeyCode::Up => viewer.select_by(-1),
+                KeyCode::Down => viewer.select_by(1),
+                KeyCode::Enter => {
+                    if let Err(e) = viewer.open_selected() {
+                        self.status_message = Some(e);
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('h') | KeyCode::Char('q') => close = true,
+                _ => {}
+            },
+        }
+        if close {
+            self.log_viewer = None;
+        }
+    }
+
+    /// The lobby: every table on the floor, the one the player is seated at
+    /// marked, and the bankroll that goes with them from table to table.
+    fn render_lobby(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let current = self.current_game();
+        let seat = self.seat();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw(format!("{}'s bankroll: ", seat.name)),
+                Span::styled(
+                    format!("${}", seat.session.balance),
+                    Style::default().fg(theme.balance),
+                ),
+                Span::styled(
+                    "  carried from table to table",
+                    Style::default().fg(theme.muted),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(TABLES.iter().enumerate().map(|(index, entry)| {
+            let text = format!("[{}] {}", lobby::menu_key(index), entry.game.name());
+            if entry.game == current {
+                Line::from(Span::styled(
+                    format!("{}  (seated)", text),
+                    Style::default().fg(theme.accent),
+                ))
+            } else {
+                Line::from(text)
+            }
+        }));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "[1-9, 0] Sit at a table  [ESC] Back",
+            Style::default().fg(theme.accent),
+        )));
+        let title = match &self.status_message {
+            Some(message) => format!("Lobby — {}", message),
+            None => "Lobby".to_string(),
+        };
+        let widget = Paragraph::new(lines).block(theme.panel().title(title));
+        f.render_widget(widget, area);
+    }
+
+    fn handle_lobby_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.lobby = false,
+            KeyCode::Char(c) => {
+                if let Some(game) = lobby::from_menu_key(c) {
+                    self.sit_at(game);
+                    self.lobby = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn current_game(&self) -> Game {
+        self.table
+            .as_ref()
+            .map_or(Game::Baccarat, |table| table.game())
+    }
+
+    /// What the open table may read while it draws itself.
+    fn table_view(&self) -> TableView<'_> {
+        let seat = self.seat();
+        TableView {
+            balance: seat.session.balance,
+            selected_chip: seat.selected_chip,
+            limits: self.rules.limits,
+            config: &self.config,
+            status: self.status_message.as_deref(),
+            card_back: self.event.as_ref().map_or('░', |event| event.card_back),
+        }
+    }
+
+    /// The active seat's bankroll and chip, lent to a table.
+    fn table_context(&mut self) -> TableContext<'_> {
+        let seat = &mut self.seats[self.active_seat];
+        TableContext {
+            player: &seat.name,
+            balance: &mut seat.session.balance,
+            selected_chip: &mut seat.selected_chip,
+            limits: self.rules.limits,
+            config: &self.config,
+            status: &mut self.status_message,
+            opponents: self.opponents,
+            seed: self.seed,
+        }
+    }
+
+    /// Runs `play` on the open table, if there is one.
+    fn play_table<R>(
+        &mut self,
+        play: impl FnOnce(&mut dyn CasinoGame, &mut TableContext) -> R,
+    ) -> Option<R> {
+        let mut table = self.table.take()?;
+        let result = play(table.as_mut(), &mut self.table_context());
+        self.table = Some(table);
+        Some(result)
+    }
+
+    /// Moves to `game`'s table. Leaving is refused while the open table is
+    /// busy, so no stake is left on the felt; whatever it still holds for
+    /// the player, such as hold'em chips or bets not yet played, goes back
+    /// to the bankroll.
+    fn sit_at(&mut self, game: Game) {
+        if game == self.current_game() {
+            return;
+        }
+        if let Some(reason) = self.table.as_ref().and_then(|table| table.busy()) {
+            self.status_message = Some(reason);
+            return;
+        }
+        if let Some(mut table) = self.table.take() {
+            let returned = table.settle();
+            self.seat_mut().session.balance += returned;
+        }
+        self.status_message = None;
+        let Some(open) = lobby::entry(game).open else {
+            return;
+        };
+        match open(&mut self.table_context()) {
+            Ok(table) => self.table = Some(table),
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    fn render_controls(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.config.theme;
+        let key = |action| self.config.keys.label(action);
+        let views = format!(
+            "[{}] Stats  [{}] Odds  [{}] Significance  [{}] Count  [{}] History  [PgUp] Hands  [{}] Lobby  [F12] Theme  [{}/ESC] Quit",
+            key(Action::Stats),
+            key(Action::Odds),
+            key(Action::Significance),
+            key(Action::Count),
+            key(Action::History),
+            key(Action::Games),
+            key(Action::Quit)
+        );
+        let controls_text: Vec<Line> = if self.split_view {
+            vec![
+                Line::from("Left:  [Z] Player  [X] Banker  [C] Tie  [A/D] -/+ Bet  [W] Chip"),
+                Line::from("Right: [,] Player  [.] Banker  [/] Tie  [←/→] -/+ Bet  [↑] Chip"),
+                Line::from(format!(
+                    "[{}] Deal for both  [{}] Mode",
+                    key(Action::Deal),
+                    key(Action::Mode)
+                )),
+                Line::from(views),
+            ]
+        } else {
+            // Chips the table or the balance can't take are greyed out.
+            let mut chips: Vec<Span> = self
+                .config
+                .table
+                .chips
+                .iter()
+                .enumerate()
+                .map(|(i, &chip)| {
+                    let text = format!("[{}] ${}  ", i + 1, chip);
+                    match self.chip_unavailable(chip) {
+                        Some(_) => Span::styled(text, Style::default().fg(self.config.theme.muted)),
+                        None => Span::raw(text),
+                    }
+                })
+                .collect();
+            chips.push(Span::raw("[+/-] Adjust  [BKSP] Undo  [Mouse] Bet"));
+            vec![
+                Line::from(format!(
+                    "[{}] {}  [{}] {}  [{}] Tie  [{}] Mode{}",
+                    key(Action::Player),
+                    self.game_mode.side_name(Side::Player),
+                    key(Action::Banker),
+                    self.game_mode.side_name(Side::Banker),
+                    key(Action::Tie),
+                    key(Action::Mode),
+                    if self.game_mode == GameMode::EzBaccarat {
+                        format!(
+                            "  [{}] Monkey 6  [{}] Monkey Tie",
+                            key(Action::MonkeySix),
+                            key(Action::MonkeyTie)
+                        )
+                    } else {
+                        String::new()
+                    }
+                )),
+                Line::from(chips),
+                Line::from(match self.game_mode {
+                    GameMode::DragonTiger => format!("[{}] Suited Tie", key(Action::SuitedTie)),
+                    GameMode::ThreeCard => "No side bets at this table".to_string(),
+                    _ => format!(
+                        "[F1-F2] Bonus Bets  [F9/F10] Over/Under  [{}] Natural  [{}] Super 6  [{}] Big  [{}] Small  [{}] Tie Scores",
+                        key(Action::Natural),
+                        key(Action::SuperSix),
+                        key(Action::Big),
+                        key(Action::Small),
+                        key(Action::TieScores)
+                    ),
+                }),
+                Line::from(format!(
+                    "[{}] Deal (hot-seat: next player)  [{}+1-4] Save Preset  [F5-F8] Recall Preset  [{}] Autoplay",
+                    key(Action::Deal),
+                    key(Action::SavePreset),
+                    key(Action::Autoplay)
+                )),
+                Line::from(views),
+            ]
+        };
+
+        let controls_widget = Paragraph::new(controls_text).block(theme.panel().title("Controls"));
+        f.render_widget(controls_widget, area);
+    }
+
+    /// Plays as `profile`: its history, presets and lifetime totals, and
+    /// the chip it last had selected.
+    pub fn set_profile(&mut self, profile: Profile) {
+        if let Some(chip) = profile
+            .preferences()
+            .chip
+            .filter(|chip| self.config.table.chips.contains(chip))
+        {
+            for seat in &mut self.seats {
+                seat.selected_chip = chip;
+            }
+        }
+        self.profile = profile;
+        let profile = self.profile.name.as_str();
+        self.shoe_history = ShoeHistory::new(profile);
+        self.session_log = SessionLog::new(profile);
+        match PresetBook::load(profile) {
+            Ok(presets) => self.presets = presets,
+            Err(e) => self.status_message = Some(format!("Presets not loaded: {}", e)),
+        }
+        match LifetimeStats::load(profile) {
+            Ok(lifetime) => self.lifetime = lifetime,
+            Err(e) => self.status_message = Some(format!("Lifetime statistics not loaded: {}", e)),
+        }
+        let flagged = integrity::check_profile(profile);
+        if !flagged.is_empty() {
+            let files: Vec<String> = flagged
+                .iter()
+                .map(|(name, status)| format!("{} ({})", name, status.label()))
+                .collect();
+            self.status_message = Some(format!(
+                "Save files failed their check: {}",
+                files.join(", ")
+            ));
+        }
+    }
+
+    fn handle_preset_slot(&mut self, code: KeyCode) {
+        self.awaiting_preset_slot = false;
+        let KeyCode::Char(c @ '1'..='4') = code else {
+            self.status_message = Some("Preset save cancelled".to_string());
+            return;
+        };
+        let slot = c as usize - '0' as usize;
+        let seat = self.seat();
+        let preset = BetPreset::new(&seat.session.slip);
+        self.status_message = Some(match self.presets.store(slot, preset) {
+            Ok(()) => format!("Saved preset {}", slot),
+            Err(e) => format!("Could not save preset {}: {}", slot, e),
+        });
+    }
+
+    fn recall_preset(&mut self, slot: usize) {
+        let Some(preset) = self.presets.get(slot) else {
+            self.status_message = Some(format!("Preset {} is empty", slot));
+            return;
+        };
+        let slip = self.rules.restrict_slip(self.game_mode, preset.slip());
+        let before = PlacedBets::of(&self.seat().session);
+        let seat = self.seat_mut();
+        seat.session.slip = slip;
+        if let Some((bet, _)) = slip.main_bets().next() {
+            seat.session.selected_bet = bet;
+        }
+        self.remember(before);
+        self.status_message = Some(format!("Recalled preset {}", slot));
+    }
+
+    /// Keeps the stake of every box that has changed since `before` on the
+    /// active seat's undo history.
+    fn remember(&mut self, before: PlacedBets) {
+        let seat = self.seat_mut();
+        let slip = seat.session.slip;
+        seat.undo.extend(
+            BetSlip::wagers()
+                .filter(|&wager| slip.stake(wager) != before.slip.stake(wager))
+                .map(|wager| ChipMove {
+                    wager,
+                    before: before.slip.stake(wager),
+                }),
+        );
+    }
+
+    /// Puts the box the last chip went down on or came off back as it was,
+    /// leaving the stakes on the other boxes alone.
+    fn undo_chip(&mut self) {
+        let seat = self.seat_mut();
+        let Some(ChipMove { wager, before }) = seat.undo.pop() else {
+            self.status_message = Some("No chips to take back".to_string());
+            return;
+        };
+        seat.session.slip.set_stake(wager, before);
+        if let Wager::Main(bet) = wager {
+            seat.session.selected_bet = bet;
+        }
+        self.status_message = Some(format!("Last chip on {} taken back", box_label(wager)));
+    }
+
+    /// Why the active seat can't bet with `chip`, if it can't.
+    fn chip_unavailable(&self, chip: i32) -> Option<String> {
+        if chip > self.rules.limits.max_bet {
+            Some(format!(
+                "${} chip is over the ${} table maximum",
+                chip, self.rules.limits.max_bet
+            ))
+        } else if Money::from_dollars(chip) > self.seat().session.balance {
+            Some(format!(
+                "${} chip is more than the ${} balance",
+                chip,
+                self.seat().session.balance
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Puts chip steps on `bet_type`, with a first chip down if it is empty.
+    fn select_main_bet(&mut self, bet_type: BetType) {
+        let limits = self.rules.limits;
+        let before = PlacedBets::of(&self.seat().session);
+        let seat = self.seat_mut();
+        seat.session
+            .select_main_bet(bet_type, seat.selected_chip, &limits);
+        self.remember(before);
+    }
+
+    fn select_chip(&mut self, index: usize) {
+        let chip = self.config.table.chips[index];
+        if let Some(reason) = self.chip_unavailable(chip) {
+            self.status_message = Some(reason);
+            return;
+        }
+        let limits = self.rules.limits;
+        let before = PlacedBets::of(&self.seat().session);
+        let seat = self.seat_mut();
+        seat.selected_chip = chip;
+        seat.session.stake_selected_bet(chip, &limits);
+        self.remember(before);
+    }
+
     fn cycle_game_mode(&mut self) {
         self.game_mode = match self.game_mode {
             GameMode::Classic => GameMode::NoCommission,
             GameMode::NoCommission => GameMode::Speed,
             GameMode::Speed => GameMode::EzBaccarat,
-            GameMode::EzBaccarat => GameMode::Classic,
+            GameMode::EzBaccarat => GameMode::SuperPan9,
+            GameMode::SuperPan9 => GameMode::CheminDeFer,
+            GameMode::CheminDeFer => GameMode::DragonTiger,
+            GameMode::DragonTiger => GameMode::ThreeCard,
+            GameMode::ThreeCard => GameMode::Classic,
+        };
+        for seat in &mut self.seats {
+            seat.session.slip.bonus_bets = self.game_mode.restrict(seat.session.slip.bonus_bets);
+            seat.undo.clear();
+        }
+        let game = self.committed_game(self.game_mode);
+        self.replace_game(game);
+        self.roads.new_shoe();
+        self.ledger = ShoeLedger::new();
+        self.tally = SessionTally::new();
+        self.hit_rates = None;
+    }
+
+    /// Takes a placed side bet down; otherwise picks it for sizing, and the
+    /// next chip key sets its stake.
+    fn toggle_bonus_bet(&mut self, bet_type: BonusBetType) {
+        if !self.rules.offers(bet_type) {
+            self.status_message = Some(format!("{} is not offered at this table", bet_type));
+            return;
+        }
+        if !self.game_mode.offers_side_bet(bet_type) {
+            self.status_message = Some(format!(
+                "{} is not dealt at {:?} tables",
+                bet_type, self.game_mode
+            ));
+            return;
+        }
+        let before = PlacedBets::of(&self.seat().session);
+        let bonus_bets = &mut self.seat_mut().session.slip.bonus_bets;
+        if bonus_bets.get(bet_type) > Money::ZERO {
+            bonus_bets.set(bet_type, Money::ZERO);
+            self.remember(before);
+            self.status_message = Some(format!("{} bet taken down", bet_type));
+            return;
+        }
+        self.sizing_bonus_bet = Some(bet_type);
+        self.status_message = Some(format!(
+            "{} bet: press 1-5 for a chip, any other key cancels",
+            bet_type
+        ));
+    }
+
+    /// Stakes the chip pressed on the side bet being sized, within the side
+    /// bet limits.
+    fn handle_bonus_chip(&mut self, code: KeyCode) {
+        let Some(bet_type) = self.sizing_bonus_bet.take() else {
+            return;
+        };
+        let KeyCode::Char(c @ '1'..='5') = code else {
+            self.status_message = Some(format!("{} bet cancelled", bet_type));
+            return;
         };
-        self.game = BaccaratGame::with_mode(self.game_mode);
+        let chip = self.config.table.chips[c as usize - '1' as usize];
+        let stake = self.rules.limits.clamp_side(chip);
+        let before = PlacedBets::of(&self.seat().session);
+        self.seat_mut().session.slip.bonus_bets.set(bet_type, stake);
+        self.remember(before);
+        self.status_message = Some(if stake == Money::from_dollars(chip) {
+            format!("{} bet ${}", bet_type, stake)
+        } else {
+            format!("{} bet ${} (side bet limit)", bet_type, stake)
+        });
+    }
+
+    /// Locks in the active seat's bets. With several players the turn passes
+    /// around the table and the cards come out after the last seat.
+    async fn end_turn(&mut self) {
+        if !self.split_view && self.active_seat + 1 < self.seats.len() {
+            self.active_seat += 1;
+            self.status_message = Some(format!("{} to bet", self.seat().name));
+            return;
+        }
+        self.active_seat = 0;
+        if self.seats.len() > 1 {
+            self.status_message = None;
+        }
+        self.play_round().await;
     }
-    
-    fn toggle_bonus_bet(&mut self, bet_type: &str) {
-        match bet_type {
-            "player_pair" => {
-                self.bonus_bets.player_pair = if self.bonus_bets.player_pair > 0 { 0 } else { 5 };
+
+    async fn play_round(&mut self) {
+        self.game.limits = self.rules.limits;
+        let hot_seat = self.seats.len() > 1;
+        let game = &self.game;
+        let sitting_out: Vec<String> = self
+            .seats
+            .iter_mut()
+            .filter_map(|seat| {
+                let reason = seat.session.lock_bets(game).err()?;
+                Some(if hot_seat {
+                    format!("{}: {}", seat.name, reason)
+                } else {
+                    reason
+                })
+            })
+            .collect();
+        if sitting_out.len() == self.seats.len() {
+            self.status_message = Some(format!("Not dealt: {}", sitting_out.join("; ")));
+            return;
+        }
+        if !sitting_out.is_empty() {
+            self.status_message = Some(format!("Sitting out: {}", sitting_out.join("; ")));
+        }
+        for seat in &mut self.seats {
+            seat.undo.clear();
+        }
+
+        self.stats.pace.round_started();
+        self.game.prepare_next_round();
+        self.handle_game_events();
+        self.game.over_under = self.rules.over_under;
+        self.game.tie_rule = self.rules.tie_rule;
+        self.game.payout_table = self.rules.payout_table(
+            self.game.mode,
+            self.event.as_ref().map(|event| &event.boosts),
+        );
+
+        self.next_auto_deal = None;
+
+        // Deal card by card in Classic mode, and at a quicker pace in Speed mode
+        let reveal_interval = match self.game_mode {
+            GameMode::Classic => Some(CARD_REVEAL_INTERVAL),
+            GameMode::Speed => Some(SPEED_REVEAL_INTERVAL),
+            _ => None,
+        };
+        if let Some(reveal_interval) = reveal_interval {
+            self.animation_state
+                .start_dealing(reveal_interval.div_f64(self.config.animation_speed));
+            return;
+        }
+        let dealt = self.game.play_round();
+        self.handle_game_events();
+        if let Err(e) = dealt {
+            self.status_message = Some(format!("Hand not dealt: {}", e));
+            return;
+        }
+        self.finish_hand();
+    }
+
+    /// Answers the draw decision the hand stopped on with [D] or [S] and
+    /// deals on; other keys wait.
+    fn handle_decision_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('d') => self.decide(true),
+            KeyCode::Char('s') => self.decide(false),
+            _ => {}
+        }
+    }
+
+    fn decide(&mut self, draw: bool) {
+        self.status_message = None;
+        let dealt = self.game.decide(draw).and_then(|_| self.game.play_round());
+        self.handle_game_events();
+        if let Err(e) = dealt {
+            self.status_message = Some(format!("Hand not dealt: {}", e));
+            return;
+        }
+        self.finish_hand();
+    }
+
+    /// Settles the finished hand, or asks for the draw decision it is
+    /// waiting on; robots in the attract loop and autoplay play it by the
+    /// tableau.
+    fn finish_hand(&mut self) {
+        match decision_prompt(&self.game) {
+            Some(_) if self.demo.is_some() || self.autoplay.is_some() => {
+                self.decide(self.game.house_draws())
             }
-            "banker_pair" => {
-                self.bonus_bets.banker_pair = if self.bonus_bets.banker_pair > 0 { 0 } else { 5 };
+            Some(prompt) => self.status_message = Some(prompt),
+            None => self.settle_round(),
+        }
+    }
+
+    /// Moves the deal in progress on by one step: turns up the card just
+    /// dealt or deals the next, and settles the bets once the hand is done.
+    fn tick_deal(&mut self) {
+        if !self.animation_state.step_due() || self.animation_state.reveal(&self.game) {
+            return;
+        }
+        let phase = self.game.advance();
+        self.handle_game_events();
+        match phase {
+            Ok(GamePhase::Settled | GamePhase::DecisionRequired(_)) => {
+                self.animation_state.finish();
+                self.finish_hand();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.animation_state.finish();
+                self.status_message = Some(format!("Hand not dealt: {}", e));
             }
-            _ => {}
         }
     }
-    
-    async fn play_round(&mut self) {
-        if self.current_bet == 0 || self.current_bet > self.balance {
-            return;
-        }
-        
-        let total_bet = self.current_bet + self.bonus_bets.total_bet();
-        if total_bet > self.balance {
-            return;
-        }
-        
-        self.game = BaccaratGame::with_mode(self.game_mode);
-        self.game.set_bonus_bets(self.bonus_bets);
-        
-        // Start animation for Classic mode
-        if self.game_mode == GameMode::Classic {
-            // Collect all cards that will be dealt
-            let mut all_cards = Vec::new();
-            
-            // We need to simulate the dealing to know what cards will be shown
-            // This is a simplified version - in production you'd want to properly
-            // integrate this with the game logic
-            self.game.play_round();
-            
-            for card in &self.game.player_hand {
-                all_cards.push(*card);
-            }
-            for card in &self.game.banker_hand {
-                all_cards.push(*card);
-            }
-            
-            self.animation_state.start_dealing(all_cards);
-        } else {
-            // For other modes, deal immediately
-            self.game.play_round();
-        }
-        
-        let bet_type_str = match self.bet_type {
-            BetType::Player => "player",
-            BetType::Banker => "banker",
-            BetType::Tie => "tie",
-        };
-        
-        let payout = self.game.total_payout(bet_type_str, self.current_bet);
-        
+
+    /// Keeps the roads and the deal animation in step
+    /// with what the engine reports from the table.
+    fn handle_game_events(&mut self) {
+        while let Ok(event) = self.game_events.try_recv() {
+            self.roads.observe(&event);
+            match event {
+                GameEvent::ShoeReshuffled => {
+                    // A hand voided mid-deal starts over face down.
+                    self.animation_state.revealed = (0, 0);
+                    self.roads.new_shoe();
+                    self.status_message =
+                        Some(format!("Shoe {} shuffled", self.roads.shoe_number()));
+                }
+                GameEvent::CardsBurned { revealed, count } => {
+                    let burned =
+                        format!("{} card{} burned", count, if count == 1 { "" } else { "s" });
+                    self.status_message = Some(match revealed {
+                        Some(card) => format!(
+                            "Shoe {}: {} turned up, {}",
+                            self.roads.shoe_number(),
+                            CardRenderer::label(&card),
+                            burned
+                        ),
+                        None => format!("Shoe {}: {}", self.roads.shoe_number(), burned),
+                    });
+                }
+                GameEvent::CardDealt { .. }
+                | GameEvent::NaturalDetected { .. }
+                | GameEvent::ThirdCardDrawn { .. }
+                | GameEvent::RoundSettled { .. } => {}
+            }
+        }
+    }
+
+    /// Pays every seat on the finished hand and records it.
+    fn settle_round(&mut self) {
         self.stats.rounds_played += 1;
-        self.stats.total_wagered += total_bet;
-        self.stats.total_won += payout;
-        
-        match self.game.state.winner {
-            1 => self.stats.player_wins += 1,
-            2 => self.stats.banker_wins += 1,
-            3 => self.stats.ties += 1,
-            _ => {}
+        self.tally.record(&self.game);
+
+        let mut table_net = Money::ZERO;
+        let mut side_bet_hit = false;
+        let mut hand_bets = Vec::new();
+        let hands_per_hour = self.stats.pace.summary().hands_per_hour;
+        for seat in &mut self.seats {
+            let Some(report) = seat.session.settle(&self.game) else {
+                continue;
+            };
+            if !report.bets.is_empty() {
+                hand_bets.push(SeatBets {
+                    seat: seat.name.clone(),
+                    bets: report.bets.clone(),
+                });
+            }
+            side_bet_hit |= report.side_bet_payout() > Money::ZERO;
+            table_net += report.net();
+            if let Some(report) = &seat.session.last_settlement {
+                seat.recorder.record_round(report, seat.session.balance);
+            }
+
+            let Some(goal) = &mut seat.goal else {
+                continue;
+            };
+            let layout = BetLayout {
+                mode: self.game_mode,
+                over_under: self.rules.over_under,
+                payout_table: self.game.payout_table.clone(),
+                slip: seat.session.slip,
+            };
+            let projection = goal.project(layout, seat.session.balance, hands_per_hour);
+            let was_out_of_reach = seat
+                .goal_projection
+                .as_ref()
+                .is_some_and(GoalProjection::is_out_of_reach);
+            if projection.is_out_of_reach() && !was_out_of_reach {
+                self.status_message = Some(format!(
+                    "Goal warning for {}: out of reach with these bets",
+                    seat.name
+                ));
+            }
+            seat.goal_projection = Some(projection);
+        }
+
+        self.ledger.record_round(table_net, side_bet_hit);
+        let hand = HandRecord::new(
+            self.stats.rounds_played,
+            self.roads.shoe_number(),
+            &self.game,
+            hand_bets,
+            self.shuffle_label(),
+        );
+        self.stats.trends.record(&hand);
+        if self.demo.is_none() {
+            self.lifetime.record(&hand);
+            let balance = self
+                .seats
+                .iter()
+                .map(|seat| seat.session.balance)
+                .max()
+                .unwrap_or(Money::ZERO);
+            let earned = self.profile.record(
+                &hand,
+                self.lifetime.total().hands,
+                balance,
+                self.config.table.starting_balance,
+            );
+            if let Some(message) = Profile::announce(&earned) {
+                self.status_message = Some(message);
+            }
+            let balances: Vec<(String, Money)> = self
+                .seats
+                .iter()
+                .map(|seat| (seat.name.clone(), seat.session.balance))
+                .collect();
+            self.replay_hands.push(ReplayHand::new(&hand, &balances));
+        }
+        self.hand_history.record(hand);
+        self.roads.record(self.game.state.winner);
+        self.alarm_banner = self.alarms.check(self.roads.winners());
+        self.alarm_bell_pending = self.alarm_banner.is_some() && self.demo.is_none();
+
+        if self.game.card_source.needs_reshuffle() {
+            self.finish_shoe();
+        }
+    }
+
+    /// The cut card is out: record the shoe and show its recap before the
+    /// next deal reshuffles.
+    fn finish_shoe(&mut self) {
+        let penetration = self.game.card_source.penetration().unwrap_or((0, 0));
+        let summary = self.ledger.finish(&self.roads, penetration);
+        if self.demo.is_some() {
+            return;
+        }
+        if self.seed.is_none()
+            && self.config.table.rng == RngChoice::ProvablyFair
+            && let CardSource::Shoe(shoe) = &mut self.game.card_source
+        {
+            shoe.set_rng(
+                self.fairness
+                    .next_shoe(self.game_mode, self.rules.procedure.decks),
+            );
+            if let Some(hash) = self.fairness.commitment() {
+                self.status_message = Some(format!(
+                    "Shoe seed revealed; the next shoe is committed to sha256 {}",
+                    hash
+                ));
+            }
+        }
+        if let Err(e) = self.shoe_history.append(&summary) {
+            self.status_message = Some(format!("Shoe summary not saved: {}", e));
         }
-        
-        self.balance = self.balance - total_bet + payout;
+        self.shoe_recap = Some(summary);
+    }
+
+    /// Starts the attract loop straight away rather than after the table
+    /// has sat idle.
+    pub fn set_demo(&mut self) {
+        self.start_demo();
     }
-}
\ No newline at end of file
+
+    /// Runs the attract loop: starts it once a fresh table has sat idle,
+    /// then places robot bets and deals a hand every few seconds.
+    async fn tick_demo(&mut self) {
+        let idle = self.animation_state.is_complete()
+            && self.shoe_recap.is_none()
+            && !self.awaiting_preset_slot
+            && !self.tie_score_menu
+            && self.sizing_bonus_bet.is_none()
+            && !self.show_odds
+            && !self.show_significance
+            && self.log_viewer.is_none()
+            && !self.lobby
+            && self.table.is_none()
+            && self.autoplay.is_none()
+            && decision_prompt(&self.game).is_none();
+        let Some(demo) = &mut self.demo else {
+            if idle && self.stats.rounds_played == 0 && self.last_input.elapsed() >= DEMO_IDLE {
+                self.start_demo();
+            }
+            return;
+        };
+        if !self.animation_state.is_complete() || Instant::now() < demo.next_deal {
+            return;
+        }
+        demo.next_deal = Instant::now() + DEMO_DEAL_PAUSE;
+
+        let mut rng = rand::rng();
+        let limits = self.rules.limits;
+        let offered: Vec<BonusBetType> = self
+            .rules
+            .side_bets
+            .iter()
+            .copied()
+            .filter(|&bet| self.game_mode.offers_side_bet(bet))
+            .collect();
+        for seat in &mut self.seats {
+            if seat.session.balance < Money::from_dollars(limits.min_bet * 2) {
+                seat.session.balance = self.config.table.starting_balance;
+            }
+            let bet = match rng.random_range(0..20) {
+                0..=8 => BetType::Banker,
+                9..=17 => BetType::Player,
+                _ => BetType::Tie,
+            };
+            seat.selected_chip = self.config.table.chips[rng.random_range(0..3)];
+            let amount = limits
+                .clamp(seat.selected_chip * rng.random_range(1..=3))
+                .min((seat.session.balance.whole_dollars() / 2) as i32);
+            seat.session.slip = BetSlip::new();
+            seat.session.bet_only(bet, Money::from_dollars(amount));
+            if !offered.is_empty() && rng.random_bool(0.25) {
+                seat.session.slip.bonus_bets.set(
+                    offered[rng.random_range(0..offered.len())],
+                    limits.clamp_side(5),
+                );
+            }
+        }
+        self.play_round().await;
+    }
+
+    fn start_demo(&mut self) {
+        let robots: Vec<Seat> = match self.seats.len() {
+            1 => vec![Seat::new("Robot", &self.config.table)],
+            n => (1..=n)
+                .map(|i| Seat::new(&format!("Robot {}", i), &self.config.table))
+                .collect(),
+        };
+        let saved = SavedTable {
+            game: self.replace_game(self.fresh_game(self.game_mode)),
+            seats: std::mem::replace(&mut self.seats, robots),
+            active_seat: std::mem::take(&mut self.active_seat),
+            stats: std::mem::replace(&mut self.stats, GameStats::new()),
+            roads: std::mem::replace(&mut self.roads, ShoeRoads::new()),
+            ledger: std::mem::take(&mut self.ledger),
+            hand_history: std::mem::take(&mut self.hand_history),
+            tally: std::mem::take(&mut self.tally),
+            show_stats: std::mem::replace(&mut self.show_stats, true),
+            show_count: std::mem::take(&mut self.show_count),
+            status_message: self
+                .status_message
+                .replace("DEMO - press any key to play".to_string()),
+            alarm_banner: self.alarm_banner.take(),
+        };
+        self.demo = Some(Demo {
+            saved,
+            next_deal: Instant::now(),
+        });
+    }
+
+    /// Hands the seat to a betting strategy, which bets the selected main
+    /// bet from the chip on it, with the side bets riding as they are,
+    /// until a key is pressed, the bankroll runs short or one of the
+    /// config's stop conditions is met. The strategy hears only how its
+    /// main bet did.
+    pub fn set_autoplay(&mut self, kind: StrategyKind) {
+        if self.seats.len() > 1 {
+            self.status_message = Some("Autoplay plays a single seat; not started".to_string());
+            return;
+        }
+        let limits = self.rules.limits;
+        let seat = &mut self.seats[self.active_seat];
+        let unit = limits.clamp(
+            seat.session
+                .main_bet(seat.session.selected_bet)
+                .max(seat.selected_chip),
+        );
+        self.status_message = Some(format!(
+            "Autoplay: {} from ${} - press any key to stop",
+            kind.name(),
+            unit
+        ));
+        self.autoplay = Some(Autoplay {
+            strategy: kind.build(seat.session.selected_bet, Money::from_dollars(unit)),
+            kind,
+            next_deal: Instant::now(),
+            delay: self.config.autoplay.delay,
+            hands: 0,
+            start_balance: seat.session.balance,
+            pending: None,
+        });
+    }
+
+    /// Lets the strategy bet and deal the next hand once the last one is
+    /// settled, clearing the shoe recap and drawing decisions on the way.
+    async fn tick_autoplay(&mut self) {
+        let Some(autoplay) = &mut self.autoplay else {
+            return;
+        };
+        if !self.animation_state.is_complete()
+            || Instant::now() < autoplay.next_deal
+            || self.table.is_some()
+            || self.lobby
+        {
+            return;
+        }
+        autoplay.next_deal = Instant::now() + Duration::from_secs_f64(autoplay.delay);
+        self.shoe_recap = None;
+        let seat = &mut self.seats[self.active_seat];
+        let balance = seat.session.balance;
+        if let Some((bet, stake, rounds)) = autoplay.pending.take() {
+            if self.stats.rounds_played == rounds {
+                let reason = self
+                    .status_message
+                    .take()
+                    .unwrap_or_else(|| "the hand was not dealt".to_string());
+                self.stop_autoplay(Some(reason));
+                return;
+            }
+            autoplay
+                .strategy
+                .settle(self.game.calculate_main_bet_payout(bet, stake) - stake);
+            autoplay.hands += 1;
+        }
+        let settings = self.config.autoplay.clone();
+        let net = balance - autoplay.start_balance;
+        let reached = if settings.hands.is_some_and(|hands| autoplay.hands >= hands) {
+            Some(format!("played {} hands", autoplay.hands))
+        } else if let Some(target) = settings.profit_target.filter(|&target| net >= target) {
+            Some(format!("reached the ${} profit target", target))
+        } else {
+            settings
+                .loss_limit
+                .filter(|&limit| net <= -limit)
+                .map(|limit| format!("hit the ${} loss limit", limit))
+        };
+        if let Some(reason) = reached {
+            self.stop_autoplay(Some(reason));
+            return;
+        }
+
+        let decision = match autoplay
+            .strategy
+            .next_bet(self.roads.winners(), Some(balance))
+        {
+            Ok(decision) if self.game_mode.main_bets().contains(&decision.bet) => decision,
+            Ok(decision) => {
+                self.stop_autoplay(Some(format!(
+                    "{} bets are not dealt at this table",
+                    decision.bet
+                )));
+                return;
+            }
+            Err(e) => {
+                self.stop_autoplay(Some(e));
+                return;
+            }
+        };
+        let limits = self.rules.limits;
+        let stake = limits.clamp(decision.stake.whole_dollars().min(i32::MAX as i64) as i32);
+        if Money::from_dollars(stake) > balance {
+            self.stop_autoplay(Some(format!("the bankroll can't cover ${}", stake)));
+            return;
+        }
+        seat.session
+            .bet_only(decision.bet, Money::from_dollars(stake));
+        autoplay.pending = Some((
+            decision.bet,
+            Money::from_dollars(stake),
+            self.stats.rounds_played,
+        ));
+        let of = settings
+            .hands
+            .map_or(String::new(), |hands| format!(" of {}", hands));
+        self.status_message = Some(format!(
+            "Autoplay {}: hand {}{}, ${} on {}, net {:+}, {:.1}s a hand - [+/-] pace, any other key stops",
+            autoplay.kind.name(),
+            autoplay.hands + 1,
+            of,
+            stake,
+            self.game_mode.bet_label(decision.bet),
+            net,
+            autoplay.delay
+        ));
+        self.play_round().await;
+    }
+
+    /// Scales the pause between autoplay hands by `factor`, within the
+    /// config's bounds.
+    fn pace_autoplay(&mut self, factor: f64) {
+        if let Some(autoplay) = &mut self.autoplay {
+            autoplay.delay =
+                (autoplay.delay * factor).clamp(MIN_AUTOPLAY_DELAY, MAX_AUTOPLAY_DELAY);
+            autoplay.next_deal = autoplay
+                .next_deal
+                .min(Instant::now() + Duration::from_secs_f64(autoplay.delay));
+        }
+    }
+
+    /// Takes the seat back from the strategy, saying why if it stopped of
+    /// its own accord.
+    fn stop_autoplay(&mut self, reason: Option<String>) {
+        let Some(autoplay) = self.autoplay.take() else {
+            return;
+        };
+        let net = self.seats[self.active_seat].session.balance - autoplay.start_balance;
+        let reason = reason.map_or(String::new(), |reason| format!(": {}", reason));
+        self.status_message = Some(format!(
+            "Autoplay {} stopped after {} hands, net {:+}{}",
+            autoplay.kind.name(),
+            autoplay.hands,
+            net,
+            reason
+        ));
+    }
+
+    /// Puts the player's table back exactly as it was left.
+    fn stop_demo(&mut self) {
+        let Some(Demo { saved, .. }) = self.demo.take() else {
+            return;
+        };
+        self.replace_game(saved.game);
+        self.seats = saved.seats;
+        self.active_seat = saved.active_seat;
+        self.stats = saved.stats;
+        self.roads = saved.roads;
+        self.ledger = saved.ledger;
+        self.hand_history = saved.hand_history;
+        self.tally = saved.tally;
+        self.show_stats = saved.show_stats;
+        self.show_count = saved.show_count;
+        self.status_message = saved.status_message;
+        self.alarm_banner = saved.alarm_banner;
+        self.animation_state = AnimationState::new();
+        self.alarm_bell_pending = false;
+        self.next_auto_deal = None;
+    }
+}
+
+fn render_log_viewer(f: &mut Frame, area: Rect, viewer: &LogViewer, theme: &Theme) {
+    let visible = area.height.saturating_sub(3) as usize;
+    let Some(open) = &viewer.open else {
+        if viewer.files.is_empty() {
+            let empty = Paragraph::new(
+                "No saved history yet. Shoe and session records appear here once written.",
+            )
+            .block(theme.panel().title("History  [H] Close"));
+            f.render_widget(empty, area);
+            return;
+        }
+        let first = viewer.selected.saturating_sub(visible.saturating_sub(1));
+        let rows: Vec<Row> = viewer
+            .files
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(index, file)| {
+                let style = if index == viewer.selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(file.label.clone()),
+                    Cell::from(format!("{:.1} KB", file.size as f64 / 1024.0)),
+                    Cell::from(file.modified.map_or("-".to_string(), age_text)),
+                    Cell::from(file.integrity.label()),
+                ])
+                .style(style)
+            })
+            .collect();
+        let header = ["File", "Size", "Modified", "Check"]
+            .map(|h| Cell::from(h).style(Style::default().add_modifier(Modifier::BOLD)));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(30),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(9),
+            ],
+        )
+        .header(Row::new(header))
+        .block(
+            theme
+                .panel()
+                .title("History  [↑/↓] Select  [ENTER] Open  [H] Close"),
+        );
+        f.render_widget(table, area);
+        return;
+    };
+
+    let body_height = visible.max(1);
+    let mut lines: Vec<Line> = open
+        .lines
+        .iter()
+        .enumerate()
+        .skip(open.scroll)
+        .take(body_height)
+        .map(|(index, line)| {
+            if open.is_match(index) {
+                Line::from(Span::styled(
+                    line.clone(),
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::REVERSED),
+                ))
+            } else {
+                Line::from(line.clone())
+            }
+        })
+        .collect();
+    lines.resize(body_height, Line::from(""));
+    let footer = match &viewer.search_input {
+        Some(input) => format!("/{}_", input),
+        None => open
+            .match_status()
+            .unwrap_or_else(|| "[/] Search  [N] Next match".to_string()),
+    };
+    lines.push(Line::from(Span::styled(
+        footer,
+        Style::default().fg(theme.accent),
+    )));
+    let last_shown = (open.scroll + body_height).min(open.lines.len());
+    let title = format!(
+        "{}  lines {}-{} of {}  [↑/↓/PgUp/PgDn] Scroll  [ESC] Back",
+        open.label,
+        (open.scroll + 1).min(last_shown),
+        last_shown,
+        open.lines.len()
+    );
+    let widget = Paragraph::new(lines).block(theme.panel().title(title));
+    f.render_widget(widget, area);
+}
+
+/// "just now", "5m ago", "3h ago", "2d ago".
+/// Columns a chip takes in the rack: its glyph and value, spaced.
+fn chip_width(chip: i32) -> u16 {
+    format!(" {}${} ", CHIP_GLYPH, chip).chars().count() as u16
+}
+
+/// `count` chips of one value, piled up: a glyph each, or one glyph and
+/// the count for a tall stack.
+fn stack_glyphs(count: i32) -> String {
+    match count {
+        1..=MAX_STACK_GLYPHS => CHIP_GLYPH.repeat(count as usize),
+        _ => format!("{}×{}", CHIP_GLYPH, count),
+    }
+}
+
+/// A box on the felt, as the status line names it.
+fn box_label(wager: Wager) -> String {
+    match wager {
+        Wager::Main(bet) => format!("{:?}", bet),
+        Wager::Side(bet) => spot_label(bet).to_string(),
+        Wager::TieScore(score) => format!("Tie on {}", score),
+        other => other.to_string(),
+    }
+}
+
+/// A side bet as its spot on the felt is labelled.
+fn spot_label(bet: BonusBetType) -> &'static str {
+    match bet {
+        BonusBetType::PlayerPair => "P Pair",
+        BonusBetType::BankerPair => "B Pair",
+        BonusBetType::EitherPair => "Either Pair",
+        BonusBetType::PerfectPair => "Perfect Pair",
+        BonusBetType::PlayerDragon => "P Dragon",
+        BonusBetType::BankerDragon => "B Dragon",
+        BonusBetType::Lucky6 => "Lucky 6",
+        BonusBetType::Over => "Over",
+        BonusBetType::Under => "Under",
+        BonusBetType::EitherNatural => "Natural",
+        BonusBetType::SuperSix => "Super 6",
+        BonusBetType::Big => "Big",
+        BonusBetType::Small => "Small",
+        BonusBetType::MonkeySix => "Monkey 6",
+        BonusBetType::MonkeyTie => "Monkey Tie",
+        BonusBetType::SuitedTie => "Suited Tie",
+    }
+}
+
+fn age_text(modified: std::time::SystemTime) -> String {
+    let secs = modified.elapsed().map_or(0, |age| age.as_secs());
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86_400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// House-edge cell coloured as a win under 1.5%, highlighted under 5%,
+/// and as a loss above.
+fn edge_cell(edge: f64, theme: &Theme) -> Cell<'static> {
+    Cell::from(format!("{:.2}%", edge * 100.0)).style(Style::default().fg(if edge < 0.015 {
+        theme.win
+    } else if edge < 0.05 {
+        theme.highlight
+    } else {
+        theme.loss
+    }))
+}
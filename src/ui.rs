@@ -1,4 +1,24 @@
-use crate::baccarat::{BaccaratGame, Card, GameMode, BonusBets, HEARTS, DIAMONDS, CLUBS, SPADES};
+use crate::alarms::StreakAlarms;
+use crate::baccarat::{
+    self, BaccaratGame, BetType, BonusBetType, CLUBS, Card, CardSource, DIAMONDS, GameEvent,
+    GameMode, GamePhase, GameSession, HEARTS, Money, RngChoice, SPADES, Side,
+};
+use crate::card_renderer::HandScore;
+use crate::config::{Action, Config};
+use crate::events::SeasonalEvent;
+use crate::fairness::Fairness;
+use crate::goal::{BankrollGoal, BetLayout, GoalProjection};
+use crate::history::{HandRecord, SeatBets, ShoeHistory, ShoeLedger, ShoeSummary, Trends};
+use crate::integrity;
+use crate::lifetime::LifetimeStats;
+use crate::pace::PaceTracker;
+use crate::presets::{BetPreset, PresetBook};
+use crate::profiles::Profile;
+use crate::replay::{Replay, ReplayHand, ReplayRules};
+use crate::roads::{self, ShoeRoads};
+use crate::rules::RulesBundle;
+use crate::sessions::{SessionLog, SessionRecorder};
+use crate::storage::DEFAULT_PROFILE;
 use crossterm::{
     cursor,
     event::{self, Event, KeyCode},
@@ -6,76 +26,173 @@ use crossterm::{
     style::Print,
     terminal::{self, Clear, ClearType},
 };
-use std::io::{self, stdout, Write};
+use std::io::{self, Write, stdout};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+/// Big road columns shown under the statistics.
+const ROAD_COLUMNS: usize = 30;
 
 pub struct TerminalUI {
+    /// Table defaults and key bindings; the colors and animation are the
+    /// ratatui table's own.
+    config: Config,
     game: BaccaratGame,
-    balance: i32,
-    current_bet: i32,
-    bet_type: BetType,
-    bonus_bets: BonusBets,
+    game_events: Receiver<GameEvent>,
+    rules: RulesBundle,
+    session: GameSession,
+    selected_chip: i32,
+    presets: PresetBook,
+    awaiting_preset_slot: bool,
+    status_message: Option<String>,
     game_mode: GameMode,
     statistics: GameStatistics,
     show_statistics: bool,
+    event: Option<SeasonalEvent>,
+    roads: ShoeRoads,
+    ledger: ShoeLedger,
+    shoe_history: ShoeHistory,
+    shoe_recap: Option<ShoeSummary>,
+    recorder: SessionRecorder,
+    session_log: SessionLog,
+    lifetime: LifetimeStats,
+    profile: Profile,
+    /// Every hand of the session, for the replay file written on quit.
+    replay_hands: Vec<ReplayHand>,
+    goal: Option<BankrollGoal>,
+    goal_projection: Option<GoalProjection>,
+    alarms: StreakAlarms,
+    alarm_banner: Option<String>,
+    /// When a Speed table deals the next hand on its own.
+    next_auto_deal: Option<Instant>,
+    /// Shuffles every shoe from this seed; `None` shuffles at random.
+    seed: Option<u64>,
+    /// Commits to each shoe before it is dealt, unless they come from
+    /// `seed`.
+    fairness: Fairness,
 }
 
 pub struct GameStatistics {
-    pub rounds_played: u32,
-    pub player_wins: u32,
-    pub banker_wins: u32,
-    pub ties: u32,
-    pub total_wagered: i32,
-    pub total_won: i32,
-    pub natural_wins: u32,
-    pub pair_hits: u32,
+    /// Wins, streaks, naturals, and pairs, fed from each hand's record.
+    pub trends: Trends,
+    pub pace: PaceTracker,
 }
 
 impl GameStatistics {
     pub fn new() -> Self {
         Self {
-            rounds_played: 0,
-            player_wins: 0,
-            banker_wins: 0,
-            ties: 0,
-            total_wagered: 0,
-            total_won: 0,
-            natural_wins: 0,
-            pair_hits: 0,
-        }
-    }
-    
-    pub fn win_rate(&self) -> f32 {
-        if self.rounds_played == 0 {
-            return 0.0;
+            trends: Trends::default(),
+            pace: PaceTracker::new(),
         }
-        (self.total_won as f32) / (self.total_wagered as f32) * 100.0
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum BetType {
-    Player,
-    Banker,
-    Tie,
-}
-
 impl TerminalUI {
-    pub fn new() -> Self {
+    /// Opens with `config`, saying in the status line why the settings file
+    /// couldn't be used if `config_error` says it couldn't.
+    pub fn new(config: Config, config_error: Option<String>) -> Self {
+        let config_error = config_error.map(|e| format!("Settings not loaded, {}", e));
+        let table = config.table;
+        let mut game = BaccaratGame::with_shoe(table.mode, table.decks);
         Self {
-            game: BaccaratGame::new(),
-            balance: 1000,
-            current_bet: 0,
-            bet_type: BetType::Player,
-            bonus_bets: BonusBets::new(),
-            game_mode: GameMode::Classic,
+            config,
+            game_events: game.subscribe(),
+            game,
+            rules: RulesBundle::classic(),
+            session: GameSession::new(table.starting_balance),
+            selected_chip: table.chips[0],
+            presets: PresetBook::empty(DEFAULT_PROFILE),
+            awaiting_preset_slot: false,
+            status_message: config_error,
+            game_mode: table.mode,
             statistics: GameStatistics::new(),
             show_statistics: false,
+            event: None,
+            roads: ShoeRoads::new(),
+            ledger: ShoeLedger::new(),
+            shoe_history: ShoeHistory::new(DEFAULT_PROFILE),
+            shoe_recap: None,
+            recorder: SessionRecorder::new("Player", table.starting_balance),
+            session_log: SessionLog::new(DEFAULT_PROFILE),
+            lifetime: LifetimeStats::empty(DEFAULT_PROFILE),
+            profile: Profile::new(DEFAULT_PROFILE),
+            replay_hands: Vec::new(),
+            goal: None,
+            goal_projection: None,
+            alarms: StreakAlarms::defaults(),
+            alarm_banner: None,
+            next_auto_deal: None,
+            seed: None,
+            fairness: Fairness::new(None),
+        }
+    }
+
+    pub fn set_alarms(&mut self, alarms: StreakAlarms) {
+        self.alarms = alarms;
+    }
+
+    pub fn set_event(&mut self, event: Option<SeasonalEvent>) {
+        self.event = event;
+    }
+
+    /// Opens the table under a house-rules bundle: its mode, shoe,
+    /// limits, side bets, and pay table replace the current ones.
+    pub fn set_rules(&mut self, rules: RulesBundle) {
+        self.game_mode = rules.mode;
+        self.rules = rules;
+        let game = self.fresh_game(self.game_mode);
+        self.replace_game(game);
+    }
+
+    /// Deals every shoe from `seed` on, so the same seed brings the same
+    /// cards.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Mixes `client_seed` into every provably fair shoe from the next on.
+    pub fn set_client_seed(&mut self, client_seed: String) {
+        self.fairness = Fairness::new(Some(client_seed));
+    }
+
+    /// A table in `mode` with a freshly shuffled shoe under the rules'
+    /// procedure: from `seed` if set, otherwise from the generator the
+    /// settings choose. A provably fair shoe is committed to, revealing the
+    /// one it replaces.
+    fn fresh_game(&mut self, mode: GameMode) -> BaccaratGame {
+        let procedure = self.rules.procedure.clone();
+        match (self.seed, self.config.table.rng) {
+            (Some(seed), _) => BaccaratGame::seeded(mode, procedure, seed),
+            (None, RngChoice::ProvablyFair) => {
+                let rng = self.fairness.next_shoe(mode, procedure.decks);
+                BaccaratGame::with_rng(mode, procedure, rng)
+            }
+            (None, RngChoice::Os) => BaccaratGame::with_rng(mode, procedure, baccarat::os_rng()),
+            (None, RngChoice::Thread) => BaccaratGame::with_procedure(mode, procedure),
         }
     }
 
+    /// How the shoes are shuffled, as the hand history records it.
+    fn shuffle_label(&self) -> String {
+        match self.seed {
+            Some(seed) => format!("seed {}", seed),
+            None => self.config.table.rng.name().to_string(),
+        }
+    }
+
+    /// Sits down at a new table, listening to its events instead.
+    fn replace_game(&mut self, mut game: BaccaratGame) {
+        self.game_events = game.subscribe();
+        self.game = game;
+    }
+
+    pub fn set_goal(&mut self, target: i32) {
+        self.goal = Some(BankrollGoal::new(target));
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
         terminal::enable_raw_mode()?;
-        
+
         // Set panic hook to restore terminal
         let default_panic = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |info| {
@@ -86,124 +203,467 @@ impl TerminalUI {
         loop {
             self.draw_screen()?;
 
-            if let Event::Key(key_event) = event::read()? {
-                match key_event.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Char('p') => self.bet_type = BetType::Player,
-                    KeyCode::Char('b') => self.bet_type = BetType::Banker,
-                    KeyCode::Char('t') => self.bet_type = BetType::Tie,
-                    KeyCode::Char(' ') => self.play_round(),
-                    KeyCode::Char('1') => self.current_bet = 10,
-                    KeyCode::Char('2') => self.current_bet = 50,
-                    KeyCode::Char('3') => self.current_bet = 100,
-                    KeyCode::Char('4') => self.current_bet = 500,
-                    KeyCode::Char('5') => self.current_bet = 1000,
-                    KeyCode::Char('m') => self.cycle_game_mode(),
-                    KeyCode::Char('s') => self.show_statistics = !self.show_statistics,
-                    KeyCode::F(1) => self.toggle_bonus_bet("player_pair"),
-                    KeyCode::F(2) => self.toggle_bonus_bet("banker_pair"),
-                    KeyCode::F(3) => self.toggle_bonus_bet("either_pair"),
-                    KeyCode::F(4) => self.toggle_bonus_bet("perfect_pair"),
-                    _ => {}
+            let Some(event) = self.next_event()? else {
+                continue;
+            };
+            if let Event::Key(key_event) = event {
+                if self.shoe_recap.is_some() {
+                    self.shoe_recap = None;
+                    continue;
+                }
+                if self.awaiting_preset_slot {
+                    self.handle_preset_slot(key_event.code);
+                    continue;
+                }
+                let action = self.config.keys.action(key_event.code);
+                if decision_prompt(&self.game).is_some()
+                    && key_event.code != KeyCode::Esc
+                    && action != Some(Action::Quit)
+                {
+                    self.handle_decision_key(key_event.code);
+                    continue;
+                }
+                match action {
+                    Some(Action::Quit) => break,
+                    Some(Action::Player) => self.select_main_bet(BetType::Player),
+                    Some(Action::Banker) => self.select_main_bet(BetType::Banker),
+                    Some(Action::Tie) => self.select_main_bet(BetType::Tie),
+                    Some(Action::Deal) => self.play_round(),
+                    Some(Action::SavePreset) => {
+                        self.awaiting_preset_slot = true;
+                        self.status_message = Some("Save preset: press 1-4".to_string());
+                    }
+                    Some(Action::Mode) => self.cycle_game_mode(),
+                    Some(Action::Stats) => self.show_statistics = !self.show_statistics,
+                    Some(Action::Natural) => self.toggle_bonus_bet(BonusBetType::EitherNatural),
+                    Some(Action::SuperSix) => self.toggle_bonus_bet(BonusBetType::SuperSix),
+                    Some(Action::Big) => self.toggle_bonus_bet(BonusBetType::Big),
+                    Some(Action::Small) => self.toggle_bonus_bet(BonusBetType::Small),
+                    Some(Action::MonkeySix) => self.toggle_bonus_bet(BonusBetType::MonkeySix),
+                    Some(Action::MonkeyTie) => self.toggle_bonus_bet(BonusBetType::MonkeyTie),
+                    Some(Action::SuitedTie) => self.toggle_bonus_bet(BonusBetType::SuitedTie),
+                    // The odds, count, history and lobby views are the ratatui table's.
+                    Some(_) => {}
+                    None => match key_event.code {
+                        KeyCode::Esc => break,
+                        KeyCode::Char(c @ '1'..='5') => self.select_chip(c as usize - '1' as usize),
+                        KeyCode::Char('+') | KeyCode::Char('=') => self.step_main_bet(true),
+                        KeyCode::Char('-') => self.step_main_bet(false),
+                        KeyCode::F(n @ 5..=8) => self.recall_preset(n as usize - 4),
+                        KeyCode::F(1) => self.toggle_bonus_bet(BonusBetType::PlayerPair),
+                        KeyCode::F(2) => self.toggle_bonus_bet(BonusBetType::BankerPair),
+                        KeyCode::F(3) => self.toggle_bonus_bet(BonusBetType::EitherPair),
+                        KeyCode::F(4) => self.toggle_bonus_bet(BonusBetType::PerfectPair),
+                        KeyCode::F(9) => self.toggle_bonus_bet(BonusBetType::Over),
+                        KeyCode::F(10) => self.toggle_bonus_bet(BonusBetType::Under),
+                        _ => {}
+                    },
                 }
             }
         }
 
         terminal::disable_raw_mode()?;
+        if let Some(record) = self.recorder.finish()
+            && let Err(e) = self.session_log.append(&record)
+        {
+            eprintln!("Session not saved: {}", e);
+        }
+        if let Err(e) = self.lifetime.save() {
+            eprintln!("Lifetime statistics not saved: {}", e);
+        }
+        self.profile
+            .remember(self.session.balance, self.game_mode, self.selected_chip);
+        if let Err(e) = self.profile.save() {
+            eprintln!("Profile not saved: {}", e);
+        }
+        if !self.replay_hands.is_empty() {
+            let rules =
+                ReplayRules::new(&self.rules, self.event.as_ref().map(|event| &event.boosts));
+            let replay = Replay::new(
+                &self.profile.name,
+                self.seed,
+                rules,
+                std::mem::take(&mut self.replay_hands),
+            );
+            match replay.save() {
+                Ok(path) => println!("Session replay saved to {}", path.display()),
+                Err(e) => eprintln!("Session replay not saved: {}", e),
+            }
+        }
+        self.fairness.reveal();
+        if let Err(e) = self.fairness.save(&self.profile.name) {
+            eprintln!("Shoe seeds not saved: {}", e);
+        }
         Ok(())
     }
 
+    /// Blocks for the next input event. Speed tables instead wake every
+    /// second to redraw the countdown, dealing when it runs out; `None`
+    /// means only the screen needs refreshing.
+    fn next_event(&mut self) -> io::Result<Option<Event>> {
+        let interval = self
+            .game_mode
+            .auto_deal_interval()
+            .filter(|_| self.shoe_recap.is_none() && !self.awaiting_preset_slot);
+        let Some(interval) = interval else {
+            self.next_auto_deal = None;
+            return event::read().map(Some);
+        };
+        let deadline = *self
+            .next_auto_deal
+            .get_or_insert_with(|| Instant::now() + interval);
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            self.next_auto_deal = None;
+            self.play_round();
+            return Ok(None);
+        }
+        let until_tick = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos as u64),
+        };
+        if event::poll(until_tick)? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     fn draw_screen(&self) -> io::Result<()> {
         let mut stdout = stdout();
-        
+
         // Clear and reset cursor
-        execute!(
-            stdout,
-            Clear(ClearType::All),
-            cursor::MoveTo(0, 0)
-        )?;
-        
+        execute!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
         // Build screen buffer with \r\n for proper raw mode line endings
         let mut screen = String::new();
-        
+
         screen.push_str("╔════════════════════════════════════════╗\r\n");
-        screen.push_str(&format!("║  BACCARAT - {:?} Mode      ║\r\n", self.game_mode));
-        screen.push_str("╚════════════════════════════════════════╝\r\n\r\n");
-        
-        screen.push_str(&format!("Balance: ${}\r\n", self.balance));
-        screen.push_str(&format!("Main Bet: ${} on {:?}\r\n", self.current_bet, self.bet_type));
-        
-        if self.bonus_bets.total_bet() > 0 {
+        screen.push_str(&format!(
+            "║  BACCARAT - {:?} Mode      ║\r\n",
+            self.game_mode
+        ));
+        screen.push_str("╚════════════════════════════════════════╝\r\n");
+        screen.push_str(&format!(
+            "  Table: {} (${}-${})\r\n",
+            self.rules.name, self.rules.limits.min_bet, self.rules.limits.max_bet
+        ));
+        if self.rules.procedure.name != "standard" {
+            screen.push_str(&format!(
+                "  House procedure: {}\r\n",
+                self.rules.procedure.name
+            ));
+        }
+        if let Some(event) = &self.event {
+            screen.push_str(&format!("  {}\r\n", event.banner_text()));
+        }
+        if let Some(deadline) = self.next_auto_deal {
+            let seconds = deadline
+                .saturating_duration_since(Instant::now())
+                .as_millis()
+                .div_ceil(1000);
+            screen.push_str(&format!("  ⏱ Next hand in {}s\r\n", seconds));
+        }
+        screen.push_str("\r\n");
+
+        screen.push_str(&format!("Balance: ${}\r\n", self.session.balance));
+        if let Some(projection) = &self.goal_projection {
+            for line in projection.lines() {
+                screen.push_str(&format!("  {}\r\n", line));
+            }
+        }
+        let main_bets: Vec<String> = self
+            .session
+            .slip
+            .main_bets()
+            .map(|(bet, amount)| format!("${} on {}", amount, self.game_mode.bet_label(bet)))
+            .collect();
+        screen.push_str(&format!(
+            "Main Bets: {}  (chip ${}, +/- on {})\r\n",
+            if main_bets.is_empty() {
+                "none".to_string()
+            } else {
+                main_bets.join(", ")
+            },
+            self.selected_chip,
+            self.game_mode.bet_label(self.session.selected_bet)
+        ));
+
+        if let Some(message) = &self.status_message {
+            screen.push_str(&format!("» {}\r\n", message));
+        }
+
+        if self.session.slip.bonus_bets.total_bet() > Money::ZERO {
             screen.push_str("Bonus Bets: ");
-            if self.bonus_bets.player_pair > 0 {
-                screen.push_str(&format!("Player Pair ${} ", self.bonus_bets.player_pair));
+            if self.session.slip.bonus_bets.player_pair > Money::ZERO {
+                screen.push_str(&format!(
+                    "Player Pair ${} ",
+                    self.session.slip.bonus_bets.player_pair
+                ));
+            }
+            if self.session.slip.bonus_bets.banker_pair > Money::ZERO {
+                screen.push_str(&format!(
+                    "Banker Pair ${} ",
+                    self.session.slip.bonus_bets.banker_pair
+                ));
+            }
+            if self.session.slip.bonus_bets.either_pair > Money::ZERO {
+                screen.push_str(&format!(
+                    "Either Pair ${} ",
+                    self.session.slip.bonus_bets.either_pair
+                ));
+            }
+            if self.session.slip.bonus_bets.perfect_pair > Money::ZERO {
+                screen.push_str(&format!(
+                    "Perfect Pair ${} ",
+                    self.session.slip.bonus_bets.perfect_pair
+                ));
+            }
+            if self.session.slip.bonus_bets.over > Money::ZERO {
+                screen.push_str(&format!(
+                    "Over {}.5 ${} ",
+                    self.rules.over_under.line, self.session.slip.bonus_bets.over
+                ));
             }
-            if self.bonus_bets.banker_pair > 0 {
-                screen.push_str(&format!("Banker Pair ${} ", self.bonus_bets.banker_pair));
+            if self.session.slip.bonus_bets.under > Money::ZERO {
+                screen.push_str(&format!(
+                    "Under {}.5 ${} ",
+                    self.rules.over_under.line, self.session.slip.bonus_bets.under
+                ));
             }
-            if self.bonus_bets.either_pair > 0 {
-                screen.push_str(&format!("Either Pair ${} ", self.bonus_bets.either_pair));
+            if self.session.slip.bonus_bets.either_natural > Money::ZERO {
+                screen.push_str(&format!(
+                    "Either Natural ${} ",
+                    self.session.slip.bonus_bets.either_natural
+                ));
             }
-            if self.bonus_bets.perfect_pair > 0 {
-                screen.push_str(&format!("Perfect Pair ${} ", self.bonus_bets.perfect_pair));
+            if self.session.slip.bonus_bets.super_6 > Money::ZERO {
+                screen.push_str(&format!(
+                    "Super 6 ${} ",
+                    self.session.slip.bonus_bets.super_6
+                ));
+            }
+            if self.session.slip.bonus_bets.big > Money::ZERO {
+                screen.push_str(&format!("Big ${} ", self.session.slip.bonus_bets.big));
+            }
+            if self.session.slip.bonus_bets.small > Money::ZERO {
+                screen.push_str(&format!("Small ${} ", self.session.slip.bonus_bets.small));
+            }
+            if self.session.slip.bonus_bets.monkey_6 > Money::ZERO {
+                screen.push_str(&format!(
+                    "Monkey 6 ${} ",
+                    self.session.slip.bonus_bets.monkey_6
+                ));
+            }
+            if self.session.slip.bonus_bets.monkey_tie > Money::ZERO {
+                screen.push_str(&format!(
+                    "Monkey Tie ${} ",
+                    self.session.slip.bonus_bets.monkey_tie
+                ));
+            }
+            if self.session.slip.bonus_bets.suited_tie > Money::ZERO {
+                screen.push_str(&format!(
+                    "Suited Tie ${} ",
+                    self.session.slip.bonus_bets.suited_tie
+                ));
             }
             screen.push_str("\r\n");
         }
         screen.push_str("\r\n");
-        
-        if self.game.state.round_complete == 1 {
-            screen.push_str("PLAYER HAND:\r\n");
+
+        let player_name = self.game_mode.side_name(Side::Player).to_uppercase();
+        let banker_name = self.game_mode.side_name(Side::Banker).to_uppercase();
+        if self.game.state.round_complete == 1 || decision_prompt(&self.game).is_some() {
+            screen.push_str(&format!("{} HAND:\r\n", player_name));
             for card in &self.game.player_hand {
                 screen.push_str(&format!("{} ", self.card_display(card)));
             }
-            screen.push_str(&format!(" (Score: {})\r\n", self.game.state.player_score));
-            
-            screen.push_str("\r\nBANKER HAND:\r\n");
+            screen.push_str(&format!(
+                " ({})\r\n",
+                HandScore::of(&self.game, Side::Player)
+            ));
+
+            screen.push_str(&format!("\r\n{} HAND:\r\n", banker_name));
             for card in &self.game.banker_hand {
                 screen.push_str(&format!("{} ", self.card_display(card)));
             }
-            screen.push_str(&format!(" (Score: {})\r\n", self.game.state.banker_score));
-            
+            screen.push_str(&format!(
+                " ({})\r\n",
+                HandScore::of(&self.game, Side::Banker)
+            ));
+
+            if self.game.state.round_complete == 1 {
+                if self.session.slip.bonus_bets.either_natural > Money::ZERO {
+                    screen.push_str(&format!(
+                        "\r\nEither natural: {}\r\n",
+                        natural_text(&self.game)
+                    ));
+                }
+
+                if self.session.slip.bonus_bets.over > Money::ZERO
+                    || self.session.slip.bonus_bets.under > Money::ZERO
+                {
+                    let points = self.game.combined_points();
+                    screen.push_str(&format!(
+                        "\r\nTotal points: {} ({} {}.5)\r\n",
+                        points,
+                        if self.rules.over_under.is_over(points) {
+                            "OVER"
+                        } else {
+                            "UNDER"
+                        },
+                        self.rules.over_under.line
+                    ));
+                }
+
+                screen.push_str("\r\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n");
+
+                match self.game.state.winner {
+                    1 => screen.push_str(&format!(
+                        "🎉 {} WINS! ({})\r\n",
+                        player_name,
+                        HandScore::of(&self.game, Side::Player)
+                    )),
+                    2 => screen.push_str(&format!(
+                        "🎉 {} WINS! ({})\r\n",
+                        banker_name,
+                        HandScore::of(&self.game, Side::Banker)
+                    )),
+                    3 => screen.push_str(&format!(
+                        "🤝 TIE! (Both: {})\r\n",
+                        self.game.state.player_score
+                    )),
+                    _ => {}
+                }
+                if let Some(report) = &self.session.last_settlement {
+                    for bet in &report.bets {
+                        screen.push_str(&format!("  {}\r\n", bet.describe()));
+                    }
+                    screen.push_str(&format!("  Net: {:+}\r\n", report.net()));
+                }
+
+                if let Some(banner) = &self.alarm_banner {
+                    screen.push_str(&format!("🔔 {}\r\n", banner));
+                }
+            }
+        }
+
+        if let Some(recap) = &self.shoe_recap {
             screen.push_str("\r\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n");
-            
-            match self.game.state.winner {
-                1 => screen.push_str(&format!("🎉 PLAYER WINS! (Score: {})\r\n", self.game.state.player_score)),
-                2 => screen.push_str(&format!("🎉 BANKER WINS! (Score: {})\r\n", self.game.state.banker_score)),
-                3 => screen.push_str(&format!("🤝 TIE! (Both: {})\r\n", self.game.state.player_score)),
-                _ => {}
+            for line in recap.recap_lines() {
+                screen.push_str(&format!("{}\r\n", line));
             }
+            screen.push_str("Press any key to shuffle a new shoe\r\n");
         }
-        
-        if self.show_statistics && self.statistics.rounds_played > 0 {
+
+        if self.show_statistics && self.session.rounds_played > 0 {
             screen.push_str("\r\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n");
             screen.push_str("STATISTICS:\r\n");
-            screen.push_str(&format!("  Rounds: {} | Win Rate: {:.1}%\r\n", 
-                self.statistics.rounds_played, 
-                self.statistics.win_rate()));
-            screen.push_str(&format!("  Player Wins: {} | Banker Wins: {} | Ties: {}\r\n",
-                self.statistics.player_wins,
-                self.statistics.banker_wins,
-                self.statistics.ties));
-            screen.push_str(&format!("  Natural Wins: {} | Pair Hits: {}\r\n",
-                self.statistics.natural_wins,
-                self.statistics.pair_hits));
-        }
-        
+            screen.push_str(&format!(
+                "  Rounds: {} | Win Rate: {:.1}%\r\n",
+                self.session.rounds_played,
+                self.session.win_rate()
+            ));
+            screen.push_str(&format!(
+                "  {} Wins: {} | {} Wins: {} | Ties: {}\r\n",
+                self.game_mode.side_name(Side::Player),
+                self.statistics.trends.wins[0],
+                self.game_mode.side_name(Side::Banker),
+                self.statistics.trends.wins[1],
+                self.statistics.trends.wins[2]
+            ));
+            for line in self.statistics.trends.lines(self.game_mode) {
+                screen.push_str(&format!("  {}\r\n", line));
+            }
+            screen.push_str(&format!(
+                "  Pace: {}\r\n",
+                self.statistics.pace.summary().describe()
+            ));
+            if let Some(hash) = self.fairness.commitment() {
+                screen.push_str(&format!(
+                    "  Shoe hash: {}\r\n  Client seed: {}\r\n",
+                    hash,
+                    self.fairness.client_seed()
+                ));
+            }
+            let cut_card = match self.game.card_source.hands_until_cut_card() {
+                Some(hands) => format!(", ~{} to cut card", hands),
+                None => String::new(),
+            };
+            screen.push_str(&format!(
+                "\r\nBIG ROAD (shoe {}, {} hands{}):\r\n",
+                self.roads.shoe_number(),
+                self.roads.winners().len(),
+                cut_card
+            ));
+            for row in roads::last_columns(self.roads.big_road(), ROAD_COLUMNS) {
+                screen.push_str(&format!("  {}\r\n", row));
+            }
+        }
+
         screen.push_str("\r\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n");
         screen.push_str("CONTROLS:\r\n");
-        screen.push_str("  [P] Player  [B] Banker  [T] Tie  [M] Change Mode\r\n");
-        screen.push_str("  [1] $10  [2] $50  [3] $100  [4] $500  [5] $1000\r\n");
-        screen.push_str("  [F1-F4] Toggle Bonus Bets  [S] Stats\r\n");
-        screen.push_str("  [SPACE] Deal Cards  [Q/ESC] Quit\r\n");
-        
+        let key = |action| self.config.keys.label(action);
+        screen.push_str(&format!(
+            "  [{}] {}  [{}] {}  [{}] Tie  [{}] Change Mode\r\n",
+            key(Action::Player),
+            self.game_mode.side_name(Side::Player),
+            key(Action::Banker),
+            self.game_mode.side_name(Side::Banker),
+            key(Action::Tie),
+            key(Action::Mode)
+        ));
+        let chips: Vec<String> = self
+            .config
+            .table
+            .chips
+            .iter()
+            .enumerate()
+            .map(|(i, chip)| format!("[{}] ${}", i + 1, chip))
+            .collect();
+        screen.push_str(&format!("  {}  [+/-] Adjust by chip\r\n", chips.join("  ")));
+        if self.game_mode == GameMode::DragonTiger {
+            screen.push_str(&format!("  [{}] Suited Tie\r\n", key(Action::SuitedTie)));
+        } else if self.game_mode == GameMode::ThreeCard {
+            screen.push_str("  No side bets at this table\r\n");
+        } else {
+            screen.push_str(&format!(
+                "  [F1-F4] Toggle Bonus Bets  [F9/F10] Over/Under  [{}] Natural\r\n",
+                key(Action::Natural)
+            ));
+            screen.push_str(&format!(
+                "  [{}] Super 6  [{}] Big  [{}] Small",
+                key(Action::SuperSix),
+                key(Action::Big),
+                key(Action::Small)
+            ));
+            if self.game_mode == GameMode::EzBaccarat {
+                screen.push_str(&format!(
+                    "  [{}] Monkey 6  [{}] Monkey Tie",
+                    key(Action::MonkeySix),
+                    key(Action::MonkeyTie)
+                ));
+            }
+            screen.push_str("\r\n");
+        }
+        screen.push_str(&format!("  [{}] Stats\r\n", key(Action::Stats)));
+        screen.push_str(&format!(
+            "  [{}+1-4] Save Preset  [F5-F8] Recall Preset\r\n",
+            key(Action::SavePreset)
+        ));
+        screen.push_str(&format!(
+            "  [{}] Deal Cards  [{}/ESC] Quit\r\n",
+            key(Action::Deal),
+            key(Action::Quit)
+        ));
+
         // Single print command
         execute!(stdout, Print(screen))?;
         stdout.flush()?;
-        
+
         Ok(())
     }
 
-
     fn card_display(&self, card: &Card) -> String {
         let suit_symbol = match card.suit {
             HEARTS => "♥",
@@ -223,77 +683,344 @@ impl TerminalUI {
 
         format!("{}{}", rank_str, suit_symbol)
     }
-    
+
+    /// Plays as `profile`: its history, presets and lifetime totals, and
+    /// the chip it last had selected.
+    pub fn set_profile(&mut self, profile: Profile) {
+        if let Some(chip) = profile
+            .preferences()
+            .chip
+            .filter(|chip| self.config.table.chips.contains(chip))
+        {
+            self.selected_chip = chip;
+        }
+        self.profile = profile;
+        let profile = self.profile.name.as_str();
+        self.shoe_history = ShoeHistory::new(profile);
+        self.session_log = SessionLog::new(profile);
+        match PresetBook::load(profile) {
+            Ok(presets) => self.presets = presets,
+            Err(e) => self.status_message = Some(format!("Presets not loaded: {}", e)),
+        }
+        match LifetimeStats::load(profile) {
+            Ok(lifetime) => self.lifetime = lifetime,
+            Err(e) => self.status_message = Some(format!("Lifetime statistics not loaded: {}", e)),
+        }
+        let flagged = integrity::check_profile(profile);
+        if !flagged.is_empty() {
+            let files: Vec<String> = flagged
+                .iter()
+                .map(|(name, status)| format!("{} ({})", name, status.label()))
+                .collect();
+            self.status_message = Some(format!(
+                "Save files failed their check: {}",
+                files.join(", ")
+            ));
+        }
+    }
+
+    fn handle_preset_slot(&mut self, code: KeyCode) {
+        self.awaiting_preset_slot = false;
+        let KeyCode::Char(c @ '1'..='4') = code else {
+            self.status_message = Some("Preset save cancelled".to_string());
+            return;
+        };
+        let slot = c as usize - '0' as usize;
+        let preset = BetPreset::new(&self.session.slip);
+        self.status_message = Some(match self.presets.store(slot, preset) {
+            Ok(()) => format!("Saved preset {}", slot),
+            Err(e) => format!("Could not save preset {}: {}", slot, e),
+        });
+    }
+
+    fn recall_preset(&mut self, slot: usize) {
+        let Some(preset) = self.presets.get(slot) else {
+            self.status_message = Some(format!("Preset {} is empty", slot));
+            return;
+        };
+        self.session.slip = self.rules.restrict_slip(self.game_mode, preset.slip());
+        if let Some((bet, _)) = self.session.slip.main_bets().next() {
+            self.session.selected_bet = bet;
+        }
+        self.status_message = Some(format!("Recalled preset {}", slot));
+    }
+
+    fn select_chip(&mut self, index: usize) {
+        self.selected_chip = self.config.table.chips[index];
+        self.session
+            .stake_selected_bet(self.selected_chip, &self.rules.limits);
+    }
+
+    /// Puts chip steps on `bet_type`, with a first chip down if it is empty.
+    fn select_main_bet(&mut self, bet_type: BetType) {
+        self.session
+            .select_main_bet(bet_type, self.selected_chip, &self.rules.limits);
+    }
+
+    fn step_main_bet(&mut self, increase: bool) {
+        let bet = self.session.selected_bet;
+        self.session
+            .step_main_bet(bet, self.selected_chip, increase, &self.rules.limits);
+    }
+
     fn cycle_game_mode(&mut self) {
         self.game_mode = match self.game_mode {
             GameMode::Classic => GameMode::NoCommission,
             GameMode::NoCommission => GameMode::Speed,
             GameMode::Speed => GameMode::EzBaccarat,
-            GameMode::EzBaccarat => GameMode::Classic,
+            GameMode::EzBaccarat => GameMode::SuperPan9,
+            GameMode::SuperPan9 => GameMode::CheminDeFer,
+            GameMode::CheminDeFer => GameMode::DragonTiger,
+            GameMode::DragonTiger => GameMode::ThreeCard,
+            GameMode::ThreeCard => GameMode::Classic,
         };
-        self.game = BaccaratGame::with_mode(self.game_mode);
+        self.session.slip.bonus_bets = self.game_mode.restrict(self.session.slip.bonus_bets);
+        let game = self.fresh_game(self.game_mode);
+        self.replace_game(game);
+        self.roads.new_shoe();
+        self.ledger = ShoeLedger::new();
     }
-    
-    fn toggle_bonus_bet(&mut self, bet_type: &str) {
-        match bet_type {
-            "player_pair" => {
-                self.bonus_bets.player_pair = if self.bonus_bets.player_pair > 0 { 0 } else { 5 };
-            }
-            "banker_pair" => {
-                self.bonus_bets.banker_pair = if self.bonus_bets.banker_pair > 0 { 0 } else { 5 };
-            }
-            "either_pair" => {
-                self.bonus_bets.either_pair = if self.bonus_bets.either_pair > 0 { 0 } else { 5 };
-            }
-            "perfect_pair" => {
-                self.bonus_bets.perfect_pair = if self.bonus_bets.perfect_pair > 0 { 0 } else { 5 };
-            }
-            _ => {}
+
+    fn toggle_bonus_bet(&mut self, bet_type: BonusBetType) {
+        if !self.rules.offers(bet_type) {
+            self.status_message = Some(format!("{} is not offered at this table", bet_type));
+            return;
         }
+        if !self.game_mode.offers_side_bet(bet_type) {
+            self.status_message = Some(format!(
+                "{} is not dealt at {:?} tables",
+                bet_type, self.game_mode
+            ));
+            return;
+        }
+        let stake = if self.session.slip.bonus_bets.get(bet_type) > Money::ZERO {
+            Money::ZERO
+        } else {
+            self.rules.limits.clamp_side(5)
+        };
+        self.session.slip.bonus_bets.set(bet_type, stake);
     }
 
     fn play_round(&mut self) {
-        if self.current_bet == 0 || self.current_bet > self.balance {
+        self.game.limits = self.rules.limits;
+        if let Err(reason) = self.session.lock_bets(&self.game) {
+            self.status_message = Some(format!("Not dealt: {}", reason));
             return;
         }
 
-        let total_bet = self.current_bet + self.bonus_bets.total_bet();
-        if total_bet > self.balance {
+        self.statistics.pace.round_started();
+        self.next_auto_deal = None;
+        self.game.prepare_next_round();
+        self.game.set_bonus_bets(self.session.slip.bonus_bets);
+        self.game.over_under = self.rules.over_under;
+        self.game.tie_rule = self.rules.tie_rule;
+        self.game.payout_table = self.rules.payout_table(
+            self.game.mode,
+            self.event.as_ref().map(|event| &event.boosts),
+        );
+        let dealt = self.game.play_round();
+        self.handle_game_events();
+        if let Err(e) = dealt {
+            self.status_message = Some(format!("Hand not dealt: {}", e));
             return;
         }
+        self.finish_round();
+    }
 
-        self.game = BaccaratGame::with_mode(self.game_mode);
-        self.game.set_bonus_bets(self.bonus_bets);
-        self.game.play_round();
+    /// Answers the draw decision the hand stopped on with [D] or [S] and
+    /// deals on; other keys wait.
+    fn handle_decision_key(&mut self, code: KeyCode) {
+        let draw = match code {
+            KeyCode::Char('d') => true,
+            KeyCode::Char('s') => false,
+            _ => return,
+        };
+        self.status_message = None;
+        let dealt = self.game.decide(draw).and_then(|_| self.game.play_round());
+        self.handle_game_events();
+        if let Err(e) = dealt {
+            self.status_message = Some(format!("Hand not dealt: {}", e));
+            return;
+        }
+        self.finish_round();
+    }
 
-        let bet_type_str = match self.bet_type {
-            BetType::Player => "player",
-            BetType::Banker => "banker",
-            BetType::Tie => "tie",
+    /// Settles the finished hand, or asks for the draw decision it is
+    /// waiting on.
+    fn finish_round(&mut self) {
+        if let Some(prompt) = decision_prompt(&self.game) {
+            self.status_message = Some(prompt);
+            return;
+        }
+        let Some(report) = self.session.settle(&self.game) else {
+            return;
         };
+        let total_bet = report.total_bet();
+        let payout = report.total_payout();
+        let side_bet_hit = report.side_bet_payout() > Money::ZERO;
+        let seats = if report.bets.is_empty() {
+            Vec::new()
+        } else {
+            vec![SeatBets {
+                seat: "Player".to_string(),
+                bets: report.bets.clone(),
+            }]
+        };
+        let hand = HandRecord::new(
+            self.statistics.trends.hands + 1,
+            self.roads.shoe_number(),
+            &self.game,
+            seats,
+            self.shuffle_label(),
+        );
+        self.statistics.trends.record(&hand);
+        self.lifetime.record(&hand);
+        let earned = self.profile.record(
+            &hand,
+            self.lifetime.total().hands,
+            self.session.balance,
+            self.config.table.starting_balance,
+        );
+        if let Some(message) = Profile::announce(&earned) {
+            self.status_message = Some(message);
+        }
+        self.replay_hands.push(ReplayHand::new(
+            &hand,
+            &[("Player".to_string(), self.session.balance)],
+        ));
+
+        if let Some(report) = &self.session.last_settlement {
+            self.recorder.record_round(report, self.session.balance);
+        }
+        self.statistics.pace.round_finished();
+        self.update_goal();
+
+        if self.session.main_bet_rides() {
+            self.status_message =
+                Some("Tie: your player and banker bets ride to the next hand".to_string());
+        }
+        self.ledger.record_round(payout - total_bet, side_bet_hit);
+        self.roads.record(self.game.state.winner);
+        self.alarm_banner = self.alarms.check(self.roads.winners());
+        if self.alarm_banner.is_some() {
+            self.alarms.ring_bell();
+        }
+
+        if self.game.card_source.needs_reshuffle() {
+            self.finish_shoe();
+        }
+    }
+
+    /// Keeps the shoe roads and statistics in step with what the engine
+    /// reports from the table.
+    fn handle_game_events(&mut self) {
+        while let Ok(event) = self.game_events.try_recv() {
+            match event {
+                GameEvent::ShoeReshuffled => {
+                    self.roads.new_shoe();
+                    self.status_message =
+                        Some(format!("Shoe {} shuffled", self.roads.shoe_number()));
+                }
+                GameEvent::CardsBurned { revealed, count } => {
+                    let burned =
+                        format!("{} card{} burned", count, if count == 1 { "" } else { "s" });
+                    self.status_message = Some(match revealed {
+                        Some(card) => format!(
+                            "Shoe {}: {} turned up, {}",
+                            self.roads.shoe_number(),
+                            self.card_display(&card),
+                            burned
+                        ),
+                        None => format!("Shoe {}: {}", self.roads.shoe_number(), burned),
+                    });
+                }
+                GameEvent::CardDealt { .. }
+                | GameEvent::NaturalDetected { .. }
+                | GameEvent::ThirdCardDrawn { .. }
+                | GameEvent::RoundSettled { .. } => {}
+            }
+        }
+    }
+
+    /// Re-projects the bankroll goal from the layout just played, warning
+    /// when it first drops out of reach.
+    fn update_goal(&mut self) {
+        let Some(goal) = &mut self.goal else {
+            return;
+        };
+        let layout = BetLayout {
+            mode: self.game_mode,
+            over_under: self.rules.over_under,
+            payout_table: self.game.payout_table.clone(),
+            slip: self.session.slip,
+        };
+        let projection = goal.project(
+            layout,
+            self.session.balance,
+            self.statistics.pace.summary().hands_per_hour,
+        );
+        let was_out_of_reach = self
+            .goal_projection
+            .as_ref()
+            .is_some_and(GoalProjection::is_out_of_reach);
+        if projection.is_out_of_reach() && !was_out_of_reach {
+            self.status_message = Some("Goal warning: out of reach with these bets".to_string());
+        }
+        self.goal_projection = Some(projection);
+    }
+
+    /// The cut card is out: record the shoe and show its recap before the
+    /// next deal reshuffles.
+    fn finish_shoe(&mut self) {
+        let penetration = self.game.card_source.penetration().unwrap_or((0, 0));
+        let summary = self.ledger.finish(&self.roads, penetration);
+        if self.seed.is_none()
+            && self.config.table.rng == RngChoice::ProvablyFair
+            && let CardSource::Shoe(shoe) = &mut self.game.card_source
+        {
+            shoe.set_rng(
+                self.fairness
+                    .next_shoe(self.game_mode, self.rules.procedure.decks),
+            );
+            if let Some(hash) = self.fairness.commitment() {
+                self.status_message = Some(format!(
+                    "Shoe seed revealed; the next shoe is committed to sha256 {}",
+                    hash
+                ));
+            }
+        }
+        if let Err(e) = self.shoe_history.append(&summary) {
+            self.status_message = Some(format!("Shoe summary not saved: {}", e));
+        }
+        self.shoe_recap = Some(summary);
+    }
+}
+
+/// Outcome of the either-hand natural bet, e.g. "Player 9, Banker 8".
+/// The draw decision the hand is waiting on, e.g. "Player has 5: [D] Draw
+/// [S] Stand".
+pub fn decision_prompt(game: &BaccaratGame) -> Option<String> {
+    let GamePhase::DecisionRequired(side) = game.phase() else {
+        return None;
+    };
+    let (name, score) = match side {
+        Side::Player => ("Player", game.state.player_score),
+        Side::Banker => ("Banker", game.state.banker_score),
+    };
+    Some(format!("{} has {}: [D] Draw  [S] Stand", name, score))
+}
 
-        let payout = self.game.total_payout(bet_type_str, self.current_bet);
-        
-        self.statistics.rounds_played += 1;
-        self.statistics.total_wagered += total_bet;
-        self.statistics.total_won += payout;
-        
-        match self.game.state.winner {
-            1 => self.statistics.player_wins += 1,
-            2 => self.statistics.banker_wins += 1,
-            3 => self.statistics.ties += 1,
-            _ => {}
-        }
-        
-        if (self.game.state.player_score >= 8 || self.game.state.banker_score >= 8) 
-            && (self.game.player_hand.len() == 2 || self.game.banker_hand.len() == 2) {
-            self.statistics.natural_wins += 1;
-        }
-        
-        if self.game.is_player_pair() || self.game.is_banker_pair() {
-            self.statistics.pair_hits += 1;
-        }
-
-        self.balance = self.balance - total_bet + payout;
+pub fn natural_text(game: &BaccaratGame) -> String {
+    let hands: Vec<String> = [
+        ("Player", game.player_natural()),
+        ("Banker", game.banker_natural()),
+    ]
+    .iter()
+    .filter_map(|(name, natural)| natural.map(|n| format!("{} {}", name, n)))
+    .collect();
+    if hands.is_empty() {
+        "no natural".to_string()
+    } else {
+        hands.join(", ")
     }
 }
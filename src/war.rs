@@ -0,0 +1,234 @@
+//! Casino War: the player and the dealer take one card each and the higher
+//! rank wins, aces high. A tie asks the player to surrender half the ante or
+//! go to war: match the ante with a raise, burn three cards, and deal one
+//! more each. The player wins the war on an equal or higher card; the raise
+//! pays even money and the ante pushes, with a bonus of the ante when the
+//! war cards tie again. The tie side bet pays 10:1 on the first two cards.
+//!
+//! The hand moves through baccarat's `GamePhase` one card at a time, as
+//! `BaccaratGame::advance` does: `DealPlayer1` and `DealBanker1` for the
+//! first cards, `DecisionRequired(Side::Player)` on a tie, `DealPlayer2`
+//! and `DealBanker2` for the war, then `Settled`.
+
+use crate::baccarat::{Card, GamePhase, Money, Shoe, Side};
+
+/// Decks in the war shoe.
+pub const WAR_DECKS: usize = 6;
+/// Cards burned before the war cards are dealt.
+pub const WAR_BURN: usize = 3;
+/// What the tie bet wins per unit staked.
+const TIE_PAYS: i64 = 10;
+
+/// A card's rank for comparing, aces high.
+pub fn rank(card: &Card) -> u8 {
+    if card.rank == 1 { 14 } else { card.rank }
+}
+
+/// "Ace", "King", ... "Two", for naming a card by its rank.
+pub fn rank_name(card: &Card) -> &'static str {
+    const NAMES: [&str; 13] = [
+        "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+        "King", "Ace",
+    ];
+    NAMES[(rank(card) as usize).clamp(2, 14) - 2]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarError {
+    /// The shoe ran out of cards mid-round.
+    ShoeEmpty,
+    /// A round is already being dealt.
+    RoundInProgress,
+}
+
+impl std::fmt::Display for WarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WarError::ShoeEmpty => write!(f, "the shoe ran out of cards mid-round"),
+            WarError::RoundInProgress => write!(f, "a round is already in progress"),
+        }
+    }
+}
+
+impl std::error::Error for WarError {}
+
+/// A Casino War table: the shoe, both hands, and the bets on them.
+pub struct WarGame {
+    shoe: Shoe,
+    pub phase: GamePhase,
+    /// The player's first card, then the war card.
+    pub player: Vec<Card>,
+    pub dealer: Vec<Card>,
+    /// Cards burned before the war.
+    pub burned: Vec<Card>,
+    pub ante: Money,
+    pub tie_bet: Money,
+    /// The raise matching the ante, once the player goes to war.
+    pub raise: Money,
+    pub surrendered: bool,
+}
+
+impl WarGame {
+    pub fn new(num_decks: usize) -> Self {
+        Self::with_shoe(Shoe::new(num_decks))
+    }
+
+    /// A table dealing from `shoe`, such as a seeded or stacked one.
+    pub fn with_shoe(shoe: Shoe) -> Self {
+        Self {
+            shoe,
+            phase: GamePhase::Settled,
+            player: Vec::new(),
+            dealer: Vec::new(),
+            burned: Vec::new(),
+            ante: Money::ZERO,
+            tie_bet: Money::ZERO,
+            raise: Money::ZERO,
+            surrendered: false,
+        }
+    }
+
+    fn draw(&mut self) -> Result<Card, WarError> {
+        self.shoe.deal().ok_or(WarError::ShoeEmpty)
+    }
+
+    /// Cards are still to come or the tie is waiting on the player.
+    pub fn in_progress(&self) -> bool {
+        self.phase != GamePhase::Settled
+    }
+
+    /// Takes the bets for a new round, reshuffling first once the cut card
+    /// is out. No cards come out until `advance`.
+    pub fn deal(&mut self, ante: Money, tie_bet: Money) -> Result<(), WarError> {
+        if self.in_progress() {
+            return Err(WarError::RoundInProgress);
+        }
+        if self.shoe.needs_reshuffle() {
+            self.shoe.reshuffle();
+        }
+        self.player.clear();
+        self.dealer.clear();
+        self.burned.clear();
+        self.ante = ante;
+        self.tie_bet = tie_bet;
+        self.raise = Money::ZERO;
+        self.surrendered = false;
+        self.phase = GamePhase::Betting;
+        Ok(())
+    }
+
+    /// The first two cards are the same rank.
+    pub fn is_tie(&self) -> bool {
+        matches!((self.player.first(), self.dealer.first()), (Some(player), Some(dealer)) if rank(player) == rank(dealer))
+    }
+
+    /// Deals the next card, or settles the hand once none are due, and
+    /// returns the new phase. A tie waits at `DecisionRequired` for
+    /// `decide`.
+    pub fn advance(&mut self) -> Result<GamePhase, WarError> {
+        let next = match self.phase {
+            GamePhase::Betting => GamePhase::DealPlayer1,
+            GamePhase::DealPlayer1 => GamePhase::DealBanker1,
+            GamePhase::DealBanker1 if self.is_tie() => GamePhase::DecisionRequired(Side::Player),
+            GamePhase::DealPlayer2 => GamePhase::DealBanker2,
+            GamePhase::DecisionRequired(_) => return Ok(self.phase),
+            _ => GamePhase::Settled,
+        };
+        self.enter(next)
+    }
+
+    /// Answers the tie: `war` raises the ante and deals the war, otherwise
+    /// the player surrenders half the ante. Does nothing unless a tie is
+    /// waiting.
+    pub fn decide(&mut self, war: bool) -> Result<GamePhase, WarError> {
+        if !matches!(self.phase, GamePhase::DecisionRequired(_)) {
+            return Ok(self.phase);
+        }
+        if war {
+            self.raise = self.ante;
+            self.enter(GamePhase::DealPlayer2)
+        } else {
+            self.surrendered = true;
+            self.enter(GamePhase::Settled)
+        }
+    }
+
+    /// Moves to `phase`, dealing the card it is named for.
+    fn enter(&mut self, phase: GamePhase) -> Result<GamePhase, WarError> {
+        match phase {
+            GamePhase::DealPlayer1 => {
+                let card = self.draw()?;
+                self.player.push(card);
+            }
+            GamePhase::DealBanker1 | GamePhase::DealBanker2 => {
+                let card = self.draw()?;
+                self.dealer.push(card);
+            }
+            GamePhase::DealPlayer2 => {
+                for _ in 0..WAR_BURN {
+                    let card = self.draw()?;
+                    self.burned.push(card);
+                }
+                let card = self.draw()?;
+                self.player.push(card);
+            }
+            _ => {}
+        }
+        self.phase = phase;
+        Ok(phase)
+    }
+
+    /// Everything staked on the round so far.
+    pub fn wagered(&self) -> Money {
+        self.ante + self.tie_bet + self.raise
+    }
+
+    /// The tie bet's return, stake included.
+    pub fn tie_bet_return(&self) -> Money {
+        if self.is_tie() {
+            self.tie_bet * (TIE_PAYS + 1)
+        } else {
+            Money::ZERO
+        }
+    }
+
+    /// Whether the player won, once the hand is settled: the first card
+    /// outranks the dealer's, or the war card matches or beats the dealer's.
+    /// `None` after a surrender or before the settle.
+    pub fn player_wins(&self) -> Option<bool> {
+        if self.phase != GamePhase::Settled || self.surrendered {
+            return None;
+        }
+        let (player, dealer) = (self.player.last()?, self.dealer.last()?);
+        if self.raise > Money::ZERO {
+            Some(rank(player) >= rank(dealer))
+        } else {
+            Some(rank(player) > rank(dealer))
+        }
+    }
+
+    /// Amount the settled round returns, stakes included.
+    pub fn returned(&self) -> Money {
+        if self.phase != GamePhase::Settled {
+            return Money::ZERO;
+        }
+        let main = if self.surrendered {
+            self.ante.percent(50)
+        } else if self.raise > Money::ZERO {
+            match (self.player.last(), self.dealer.last()) {
+                (Some(player), Some(dealer)) if rank(player) == rank(dealer) => {
+                    self.ante * 2 + self.raise * 2
+                }
+                (Some(player), Some(dealer)) if rank(player) > rank(dealer) => {
+                    self.ante + self.raise * 2
+                }
+                _ => Money::ZERO,
+            }
+        } else if self.player_wins() == Some(true) {
+            self.ante * 2
+        } else {
+            Money::ZERO
+        };
+        main + self.tie_bet_return()
+    }
+}
@@ -0,0 +1,266 @@
+//! Compact binary framing for game state, cards, and side bets, for
+//! streams too busy to spell every hand out in JSON: `simulate --binary`
+//! writes one, and anything reading a table's hands can take one in.
+//!
+//! Each frame is a 16-byte header followed by its payload:
+//!
+//! | bytes | field                                           |
+//! |-------|-------------------------------------------------|
+//! | 0-3   | magic, `TCWF`                                   |
+//! | 4-5   | format version, little-endian                   |
+//! | 6-7   | payload kind: 1 state, 2 cards, 3 side bets     |
+//! | 8-11  | payload length in bytes, little-endian          |
+//! | 12-15 | CRC-32 (IEEE) of the payload, little-endian     |
+//!
+//! The payload is the value's `Pod` layout as it sits in memory: four bytes
+//! for a `GameState`, two per `Card` (suit, then rank), and the side bet
+//! stakes as sixteen then ten cents amounts in `BonusBets` field order, in
+//! the byte order of the machine that wrote them (little-endian on every
+//! platform the casino builds for).
+
+use crate::baccarat::{BonusBets, Card, GameState};
+use bytemuck::{Pod, Zeroable};
+use std::io::{self, Read, Write};
+
+/// Format version written in every header; frames from a newer one are
+/// refused.
+pub const WIRE_VERSION: u16 = 1;
+const MAGIC: [u8; 4] = *b"TCWF";
+/// Bytes in a frame header.
+pub const HEADER_LEN: usize = size_of::<Header>();
+/// Longest payload accepted, well past any shoe's worth of cards, so a
+/// corrupt length can't ask for a huge buffer.
+const MAX_PAYLOAD: usize = 1 << 16;
+
+const KIND_STATE: u16 = 1;
+const KIND_CARDS: u16 = 2;
+const KIND_BONUS_BETS: u16 = 3;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct Header {
+    magic: [u8; 4],
+    version: [u8; 2],
+    kind: [u8; 2],
+    length: [u8; 4],
+    checksum: [u8; 4],
+}
+
+/// One decoded frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    State(GameState),
+    Cards(Vec<Card>),
+    BonusBets(BonusBets),
+}
+
+/// Why a frame could not be read.
+#[derive(Debug)]
+pub enum WireError {
+    Io(io::Error),
+    /// The stream ended partway through a frame.
+    Truncated,
+    /// The bytes don't start with a frame header.
+    BadMagic,
+    /// Written by a newer version of the format.
+    UnsupportedVersion(u16),
+    UnknownKind(u16),
+    /// A payload too long, or the wrong length for its kind.
+    BadLength {
+        kind: u16,
+        length: usize,
+    },
+    ChecksumMismatch,
+    /// The payload decoded to a value no table deals, such as a card of
+    /// rank 14.
+    BadValue {
+        kind: u16,
+    },
+}
+
+impl std::fmt::Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WireError::Io(e) => e.fmt(f),
+            WireError::Truncated => write!(f, "the stream ended mid-frame"),
+            WireError::BadMagic => write!(f, "not a frame header"),
+            WireError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "frame version {} is newer than this build's {}",
+                    version, WIRE_VERSION
+                )
+            }
+            WireError::UnknownKind(kind) => write!(f, "unknown frame kind {}", kind),
+            WireError::BadLength { kind, length } => write!(
+                f,
+                "a {}-byte payload is not a valid kind {} frame",
+                length, kind
+            ),
+            WireError::ChecksumMismatch => write!(f, "the payload doesn't match its checksum"),
+            WireError::BadValue { kind } => {
+                write!(f, "a kind {} frame holds a value no table deals", kind)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<io::Error> for WireError {
+    fn from(e: io::Error) -> Self {
+        WireError::Io(e)
+    }
+}
+
+/// CRC-32 with the IEEE polynomial, as zip and PNG use.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn header(kind: u16, payload: &[u8]) -> Header {
+    Header {
+        magic: MAGIC,
+        version: WIRE_VERSION.to_le_bytes(),
+        kind: kind.to_le_bytes(),
+        length: (payload.len() as u32).to_le_bytes(),
+        checksum: crc32(payload).to_le_bytes(),
+    }
+}
+
+fn write_payload(out: &mut impl Write, kind: u16, payload: &[u8]) -> io::Result<()> {
+    out.write_all(bytemuck::bytes_of(&header(kind, payload)))?;
+    out.write_all(payload)
+}
+
+pub fn write_state(out: &mut impl Write, state: &GameState) -> io::Result<()> {
+    write_payload(out, KIND_STATE, bytemuck::bytes_of(state))
+}
+
+pub fn write_cards(out: &mut impl Write, cards: &[Card]) -> io::Result<()> {
+    write_payload(out, KIND_CARDS, bytemuck::cast_slice(cards))
+}
+
+pub fn write_bonus_bets(out: &mut impl Write, bets: &BonusBets) -> io::Result<()> {
+    write_payload(out, KIND_BONUS_BETS, bytemuck::bytes_of(bets))
+}
+
+impl Frame {
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Frame::State(state) => write_state(out, state),
+            Frame::Cards(cards) => write_cards(out, cards),
+            Frame::BonusBets(bets) => write_bonus_bets(out, bets),
+        }
+    }
+
+    /// The frame as bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes).expect("writing to a Vec");
+        bytes
+    }
+
+    /// The frame at the start of `bytes`, and how many bytes it took.
+    pub fn decode(bytes: &[u8]) -> Result<(Frame, usize), WireError> {
+        let header: Header =
+            bytemuck::pod_read_unaligned(bytes.get(..HEADER_LEN).ok_or(WireError::Truncated)?);
+        let (kind, length) = check_header(header)?;
+        let payload = bytes
+            .get(HEADER_LEN..HEADER_LEN + length)
+            .ok_or(WireError::Truncated)?;
+        let frame = payload_frame(kind, payload, u32::from_le_bytes(header.checksum))?;
+        Ok((frame, HEADER_LEN + length))
+    }
+}
+
+/// The kind and payload length a header announces, if it is one this build
+/// reads.
+fn check_header(header: Header) -> Result<(u16, usize), WireError> {
+    if header.magic != MAGIC {
+        return Err(WireError::BadMagic);
+    }
+    let version = u16::from_le_bytes(header.version);
+    if version > WIRE_VERSION {
+        return Err(WireError::UnsupportedVersion(version));
+    }
+    let kind = u16::from_le_bytes(header.kind);
+    let length = u32::from_le_bytes(header.length) as usize;
+    if length > MAX_PAYLOAD {
+        return Err(WireError::BadLength { kind, length });
+    }
+    Ok((kind, length))
+}
+
+fn payload_frame(kind: u16, payload: &[u8], checksum: u32) -> Result<Frame, WireError> {
+    if crc32(payload) != checksum {
+        return Err(WireError::ChecksumMismatch);
+    }
+    let bad_length = || WireError::BadLength {
+        kind,
+        length: payload.len(),
+    };
+    let frame = match kind {
+        KIND_STATE if payload.len() == size_of::<GameState>() => {
+            Frame::State(bytemuck::pod_read_unaligned(payload))
+        }
+        KIND_CARDS if payload.len().is_multiple_of(size_of::<Card>()) => {
+            Frame::Cards(bytemuck::cast_slice(payload).to_vec())
+        }
+        KIND_BONUS_BETS if payload.len() == size_of::<BonusBets>() => {
+            Frame::BonusBets(bytemuck::pod_read_unaligned(payload))
+        }
+        KIND_STATE | KIND_CARDS | KIND_BONUS_BETS => return Err(bad_length()),
+        kind => return Err(WireError::UnknownKind(kind)),
+    };
+    let valid = match &frame {
+        Frame::State(state) => {
+            state.player_score <= 9
+                && state.banker_score <= 9
+                && state.round_complete <= 1
+                && state.winner <= 3
+        }
+        Frame::Cards(cards) => cards.iter().all(Card::is_valid),
+        Frame::BonusBets(_) => true,
+    };
+    if valid {
+        Ok(frame)
+    } else {
+        Err(WireError::BadValue { kind })
+    }
+}
+
+/// The next frame from `input`, or `None` where the stream ends cleanly
+/// between frames.
+pub fn read_frame(input: &mut impl Read) -> Result<Option<Frame>, WireError> {
+    let mut header = [0; HEADER_LEN];
+    let mut filled = 0;
+    while filled < HEADER_LEN {
+        match input.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(WireError::Truncated),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let header: Header = bytemuck::pod_read_unaligned(&header);
+    let (kind, length) = check_header(header)?;
+    let mut payload = vec![0; length];
+    input.read_exact(&mut payload).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => WireError::Truncated,
+        _ => WireError::Io(e),
+    })?;
+    payload_frame(kind, &payload, u32::from_le_bytes(header.checksum)).map(Some)
+}
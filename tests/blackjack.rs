@@ -0,0 +1,105 @@
+use terminal_casino::baccarat::{Card, Money, SPADES, Shoe};
+use terminal_casino::blackjack::{
+    Action, BlackjackError, BlackjackGame, BlackjackRules, Hand, Outcome, Phase,
+};
+
+fn dollars(amount: i32) -> Money {
+    Money::from_dollars(amount)
+}
+
+fn hand(ranks: &[u8]) -> Hand {
+    let mut hand = Hand::new();
+    for &rank in ranks {
+        hand.push(Card::new(SPADES, rank));
+    }
+    hand
+}
+
+/// A table dealing `ranks` in order: player, dealer, player, dealer, then
+/// whatever is drawn.
+fn stacked(rules: BlackjackRules, ranks: &[u8]) -> BlackjackGame {
+    let cards = ranks.iter().map(|&rank| Card::new(SPADES, rank)).collect();
+    BlackjackGame::with_shoe(rules, Shoe::from_cards(cards))
+}
+
+#[test]
+fn a_blackjack_pays_three_to_two() {
+    let mut game = stacked(BlackjackRules::default(), &[1, 9, 13, 7]);
+    game.deal(dollars(10)).unwrap();
+
+    assert_eq!(game.phase, Phase::Settled);
+    assert_eq!(game.hands[0].outcome(&game.dealer), Outcome::Blackjack);
+    assert_eq!(game.wagered(), dollars(10));
+    assert_eq!(game.returned(), dollars(25));
+}
+
+#[test]
+fn soft_17_is_hit_only_under_h17() {
+    let h17 = BlackjackRules::default();
+    let s17 = BlackjackRules {
+        dealer_hits_soft_17: false,
+        ..h17
+    };
+    assert!(h17.dealer_hits(&hand(&[1, 6])));
+    assert!(!s17.dealer_hits(&hand(&[1, 6])));
+    assert!(!h17.dealer_hits(&hand(&[10, 7])));
+    assert!(!h17.dealer_hits(&hand(&[1, 6, 10])));
+    assert!(s17.dealer_hits(&hand(&[10, 6])));
+
+    // Player 19 against a dealer 6 and ace; an H17 dealer draws the 4.
+    let play = |rules| {
+        let mut game = stacked(rules, &[10, 6, 9, 1, 4]);
+        game.deal(dollars(10)).unwrap();
+        game.act(Action::Stand).unwrap();
+        (game.dealer.total(), game.returned())
+    };
+    assert_eq!(play(h17), (21, Money::ZERO));
+    assert_eq!(play(s17), (17, dollars(20)));
+}
+
+#[test]
+fn split_aces_take_one_card_each_and_are_not_blackjacks() {
+    let mut game = stacked(BlackjackRules::default(), &[1, 10, 1, 7, 13, 9, 5]);
+    game.deal(dollars(10)).unwrap();
+    assert!(game.can(Action::Split));
+    assert_eq!(game.cost(Action::Split), dollars(10));
+    game.act(Action::Split).unwrap();
+
+    assert_eq!(game.phase, Phase::Settled);
+    assert_eq!(game.hands.len(), 2);
+    assert_eq!(game.hands[0].hand.total(), 21);
+    assert_eq!(game.hands[1].hand.total(), 20);
+    assert!(game.hands.iter().all(|hand| hand.hand.len() == 2));
+    assert_eq!(game.hands[0].outcome(&game.dealer), Outcome::Win);
+    assert_eq!(game.dealer.total(), 17);
+    assert_eq!(game.wagered(), dollars(20));
+    assert_eq!(game.returned(), dollars(40));
+}
+
+#[test]
+fn insurance_pays_two_to_one_against_a_dealer_blackjack() {
+    let mut game = stacked(BlackjackRules::default(), &[10, 1, 9, 13]);
+    game.deal(dollars(10)).unwrap();
+    assert_eq!(game.phase, Phase::Insurance);
+    assert!(!game.can(Action::Hit));
+    assert_eq!(game.insurance_cost(), dollars(5));
+    game.insure(true).unwrap();
+
+    assert_eq!(game.phase, Phase::Settled);
+    assert_eq!(game.wagered(), dollars(15));
+    assert_eq!(game.returned(), dollars(15));
+    assert_eq!(game.insure(true), Err(BlackjackError::NoInsurance));
+}
+
+#[test]
+fn insurance_is_lost_when_the_dealer_has_no_blackjack() {
+    let mut game = stacked(BlackjackRules::default(), &[10, 1, 9, 5, 2]);
+    game.deal(dollars(10)).unwrap();
+    game.insure(true).unwrap();
+    assert_eq!(game.phase, Phase::PlayerTurn);
+    game.act(Action::Stand).unwrap();
+
+    assert_eq!(game.dealer.total(), 18);
+    assert_eq!(game.wagered(), dollars(15));
+    assert_eq!(game.returned(), dollars(20));
+}
@@ -0,0 +1,132 @@
+use terminal_casino::baccarat::{Money, TableLimits, seeded_rng};
+use terminal_casino::craps::{Bet, CrapsError, CrapsGame, Outcome, Resolution, Roll};
+
+fn dollars(amount: i32) -> Money {
+    Money::from_dollars(amount)
+}
+
+fn table() -> CrapsGame {
+    CrapsGame::new(TableLimits::STANDARD)
+}
+
+fn resolution(resolutions: &[Resolution], bet: Bet) -> Resolution {
+    *resolutions
+        .iter()
+        .find(|resolution| resolution.bet == bet)
+        .unwrap_or_else(|| panic!("{} was not decided", bet))
+}
+
+#[test]
+fn a_come_bet_moves_to_its_number_and_wins_there() {
+    let mut game = table();
+    game.place(Bet::PassLine, dollars(10)).unwrap();
+    assert_eq!(game.place(Bet::Come, dollars(10)), Err(CrapsError::NoPoint));
+    game.resolve(Roll::new(2, 2));
+    assert_eq!(game.point, Some(4));
+
+    game.place(Bet::Come, dollars(10)).unwrap();
+    let moved = game.resolve(Roll::new(3, 3));
+    assert_eq!(resolution(&moved, Bet::Come).outcome, Outcome::Moved(6));
+    assert_eq!(game.stake(Bet::Come), Money::ZERO);
+    assert_eq!(game.stake(Bet::ComePoint(6)), dollars(10));
+    assert_eq!(
+        game.place(Bet::ComePoint(6), dollars(10)),
+        Err(CrapsError::ContractBet(Bet::ComePoint(6)))
+    );
+
+    game.place(Bet::ComeOdds(6), dollars(50)).unwrap();
+    let won = game.resolve(Roll::new(4, 2));
+    let flat = resolution(&won, Bet::ComePoint(6));
+    assert_eq!(flat.outcome, Outcome::Won(dollars(10)));
+    assert_eq!(flat.returned, dollars(20));
+    // Odds on 6 pay 6:5.
+    let odds = resolution(&won, Bet::ComeOdds(6));
+    assert_eq!(odds.outcome, Outcome::Won(dollars(60)));
+    assert_eq!(odds.returned, dollars(110));
+    assert_eq!(game.point, Some(4));
+}
+
+#[test]
+fn dont_pass_is_barred_on_a_come_out_12() {
+    let mut game = table();
+    game.place(Bet::PassLine, dollars(10)).unwrap();
+    game.place(Bet::DontPass, dollars(10)).unwrap();
+    let resolutions = game.resolve(Roll::new(6, 6));
+
+    assert_eq!(
+        resolution(&resolutions, Bet::PassLine).outcome,
+        Outcome::Lost
+    );
+    let barred = resolution(&resolutions, Bet::DontPass);
+    assert_eq!(barred.outcome, Outcome::Push);
+    assert_eq!(barred.returned, Money::ZERO);
+    assert_eq!(game.stake(Bet::DontPass), dollars(10));
+    assert_eq!(game.point, None);
+
+    // A 2 or 3 wins it.
+    let won = game.resolve(Roll::new(1, 2));
+    assert_eq!(
+        resolution(&won, Bet::DontPass).outcome,
+        Outcome::Won(dollars(10))
+    );
+}
+
+#[test]
+fn odds_are_held_to_three_four_five_times_the_flat_bet() {
+    let mut game = table();
+    assert_eq!(
+        game.place(Bet::PassOdds, dollars(10)),
+        Err(CrapsError::NoPoint)
+    );
+    game.place(Bet::PassLine, dollars(10)).unwrap();
+    game.place(Bet::DontPass, dollars(10)).unwrap();
+    game.resolve(Roll::new(4, 5));
+    assert_eq!(game.point, Some(9));
+
+    assert_eq!(game.max_odds(Bet::PassOdds), dollars(40));
+    assert_eq!(game.max_odds(Bet::DontPassOdds), dollars(60));
+    game.place(Bet::PassOdds, dollars(40)).unwrap();
+    assert_eq!(
+        game.place(Bet::PassOdds, dollars(1)),
+        Err(CrapsError::OddsAboveMax(dollars(40)))
+    );
+    game.place(Bet::DontPassOdds, dollars(60)).unwrap();
+    assert_eq!(
+        game.place(Bet::ComeOdds(5), dollars(10)),
+        Err(CrapsError::NoFlatBet(Bet::ComePoint(5)))
+    );
+
+    // The point made: taken odds on 9 pay 3:2, laid odds lose.
+    let made = game.resolve(Roll::new(5, 4));
+    assert_eq!(
+        resolution(&made, Bet::PassOdds).outcome,
+        Outcome::Won(dollars(60))
+    );
+    assert_eq!(resolution(&made, Bet::DontPassOdds).outcome, Outcome::Lost);
+    assert_eq!(game.point, None);
+}
+
+#[test]
+fn laid_odds_win_the_reverse_of_taken_odds() {
+    let mut game = table();
+    game.place(Bet::DontPass, dollars(10)).unwrap();
+    game.resolve(Roll::new(2, 2));
+    game.place(Bet::DontPassOdds, dollars(60)).unwrap();
+    let sevened = game.resolve(Roll::new(3, 4));
+    // Laid against 4 pays 1:2.
+    assert_eq!(
+        resolution(&sevened, Bet::DontPassOdds).outcome,
+        Outcome::Won(dollars(30))
+    );
+}
+
+#[test]
+fn a_seeded_table_rolls_the_same_dice() {
+    let rolls = |seed| {
+        let mut game = table();
+        game.set_rng(seeded_rng(seed));
+        (0..30).map(|_| game.roll().0).collect::<Vec<_>>()
+    };
+    assert_eq!(rolls(5), rolls(5));
+    assert_ne!(rolls(5), rolls(6));
+}
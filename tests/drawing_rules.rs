@@ -0,0 +1,83 @@
+use terminal_casino::test_support::{
+    Draws, card_worth, play_scripted_round, tableau_cases, verify_tableau,
+};
+
+fn cards(values: &[u8]) -> Vec<terminal_casino::baccarat::Card> {
+    values.iter().map(|&value| card_worth(value)).collect()
+}
+
+#[test]
+fn matches_official_tableau_for_every_combination() {
+    assert_eq!(tableau_cases().len(), 532);
+    let mismatches = verify_tableau();
+    assert!(
+        mismatches.is_empty(),
+        "engine differs from the tableau: {:#?}",
+        mismatches
+    );
+}
+
+#[test]
+fn banker_stands_on_3_against_player_third_8() {
+    // Player 2+3 draws an 8; banker 1+2 stands on 3.
+    let round = play_scripted_round(&cards(&[2, 1, 3, 2, 8, 5]));
+    assert_eq!(
+        round.draws(),
+        Draws {
+            player: true,
+            banker: false
+        }
+    );
+    assert_eq!(
+        (round.player_score, round.banker_score, round.winner),
+        (3, 3, 3)
+    );
+}
+
+#[test]
+fn banker_draws_on_6_only_against_player_third_6_or_7() {
+    for third in 0..=9 {
+        let round = play_scripted_round(&cards(&[0, 6, 4, 0, third, 5]));
+        assert_eq!(
+            round.draws().banker,
+            third == 6 || third == 7,
+            "player third {}",
+            third
+        );
+    }
+}
+
+#[test]
+fn naturals_end_the_hand() {
+    let round = play_scripted_round(&cards(&[4, 3, 4, 4, 9, 9]));
+    assert_eq!(
+        round.draws(),
+        Draws {
+            player: false,
+            banker: false
+        }
+    );
+    assert_eq!(
+        (round.player_score, round.banker_score, round.winner),
+        (8, 7, 1)
+    );
+}
+
+#[test]
+fn banker_draws_on_5_when_player_stands_on_6() {
+    let round = play_scripted_round(&cards(&[6, 5, 0, 0, 3]));
+    assert_eq!(
+        round.draws(),
+        Draws {
+            player: false,
+            banker: true
+        }
+    );
+    assert_eq!((round.banker_score, round.winner), (8, 2));
+}
+
+#[test]
+#[should_panic(expected = "ran out")]
+fn runs_out_of_cards_loudly() {
+    play_scripted_round(&cards(&[1, 1, 1]));
+}
@@ -0,0 +1,114 @@
+use terminal_casino::baccarat::{
+    BaccaratGame, BetSlip, BetType, BonusBetType, Card, GameMode, GameSession, Money, SPADES,
+    TieRule,
+};
+use terminal_casino::settlement::{BetOutcome, Wager};
+
+fn card(rank: u8) -> Card {
+    Card::new(SPADES, rank)
+}
+
+fn dollars(amount: i32) -> Money {
+    Money::from_dollars(amount)
+}
+
+#[test]
+fn player_banker_and_tie_settle_side_by_side() {
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    let mut session = GameSession::new(dollars(1000));
+    session
+        .place_main_bet(BetType::Player, dollars(100))
+        .unwrap();
+    session
+        .place_main_bet(BetType::Banker, dollars(50))
+        .unwrap();
+    session.place_main_bet(BetType::Tie, dollars(10)).unwrap();
+    session.lock_bets(&game).unwrap();
+
+    game.set_hands(&[card(9), card(10)], &[card(3), card(4)]);
+    let report = session.settle(&game).unwrap();
+    let outcomes: Vec<BetOutcome> = report.bets.iter().map(|bet| bet.outcome).collect();
+    assert_eq!(
+        outcomes,
+        [BetOutcome::Win, BetOutcome::Lose, BetOutcome::Lose]
+    );
+    assert_eq!(report.net(), dollars(40));
+    assert_eq!(session.balance, dollars(1040));
+}
+
+#[test]
+fn bets_the_balance_cannot_cover_are_refused() {
+    let mut session = GameSession::new(dollars(100));
+    session
+        .place_main_bet(BetType::Player, dollars(60))
+        .unwrap();
+    assert!(
+        session
+            .place_main_bet(BetType::Banker, dollars(50))
+            .is_err()
+    );
+    session
+        .place_main_bet(BetType::Player, dollars(40))
+        .unwrap();
+    session
+        .place_main_bet(BetType::Banker, dollars(50))
+        .unwrap();
+    assert_eq!(session.total_bet(), dollars(90));
+}
+
+#[test]
+fn barged_player_and_banker_bets_ride_while_the_tie_bet_settles() {
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    game.tie_rule = TieRule::BargeToNextHand;
+    let mut session = GameSession::new(dollars(1000));
+    session
+        .place_main_bet(BetType::Player, dollars(20))
+        .unwrap();
+    session
+        .place_main_bet(BetType::Banker, dollars(30))
+        .unwrap();
+    session.place_main_bet(BetType::Tie, dollars(10)).unwrap();
+    session.lock_bets(&game).unwrap();
+
+    game.set_hands(&[card(2), card(5)], &[card(3), card(4)]);
+    session.settle(&game).unwrap();
+    assert!(session.main_bet_rides());
+    assert!(
+        session
+            .place_main_bet(BetType::Banker, dollars(100))
+            .is_err()
+    );
+
+    session.slip.set_main_bet(BetType::Player, Money::ZERO);
+    session.lock_bets(&game).unwrap();
+    assert_eq!(session.slip.main_bet(BetType::Player), dollars(20));
+    assert_eq!(session.slip.main_bet(BetType::Banker), dollars(30));
+}
+
+#[test]
+fn each_box_keeps_its_own_stake_on_the_slip() {
+    let mut slip = BetSlip::new();
+    let boxes = [
+        Wager::Main(BetType::Banker),
+        Wager::Side(BonusBetType::PlayerPair),
+        Wager::TieScore(9),
+    ];
+    for (wager, stake) in boxes.into_iter().zip([25, 5, 1]) {
+        slip.set_stake(wager, dollars(stake)).unwrap();
+    }
+    slip.set_stake(Wager::Main(BetType::Banker), dollars(10))
+        .unwrap();
+    let staked: Vec<_> = BetSlip::wagers()
+        .filter(|&wager| slip.stake(wager) > Money::ZERO)
+        .map(|wager| (wager, slip.stake(wager)))
+        .collect();
+    assert_eq!(
+        staked,
+        vec![
+            (Wager::Main(BetType::Banker), dollars(10)),
+            (Wager::Side(BonusBetType::PlayerPair), dollars(5)),
+            (Wager::TieScore(9), dollars(1)),
+        ]
+    );
+    assert_eq!(slip.set_stake(Wager::TieScore(10), dollars(1)), None);
+}
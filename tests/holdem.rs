@@ -0,0 +1,59 @@
+use terminal_casino::baccarat::{Money, seeded_rng};
+use terminal_casino::holdem::{Action, HoldemGame};
+
+fn dollars(amount: i32) -> Money {
+    Money::from_dollars(amount)
+}
+
+/// You in the big blind, Ada first to act, and Bo in the small blind with
+/// `bo_chips` behind.
+fn three_handed(bo_chips: i32) -> HoldemGame {
+    let mut game = HoldemGame::new("You", 2, dollars(200));
+    game.seats[2].chips = dollars(bo_chips);
+    game.start_hand().unwrap();
+    assert_eq!(game.to_act, Some(1));
+    game
+}
+
+#[test]
+fn a_short_all_in_does_not_reopen_the_betting() {
+    let mut game = three_handed(13);
+    game.act(Action::Raise(dollars(10))).unwrap();
+    // Bo's last $12 make it $13: a raise of $3, short of Ada's $8.
+    game.act(Action::Raise(dollars(13))).unwrap();
+    assert!(game.can(Action::Raise(dollars(21))));
+    game.act(Action::Call).unwrap();
+
+    assert_eq!(game.to_act, Some(1));
+    assert!(game.can(Action::Call));
+    assert!(!game.can(Action::Raise(dollars(21))));
+    assert!(!game.can(Action::Raise(dollars(200))));
+}
+
+#[test]
+fn a_full_raise_reopens_the_betting() {
+    let mut game = three_handed(100);
+    game.act(Action::Raise(dollars(10))).unwrap();
+    game.act(Action::Raise(dollars(18))).unwrap();
+    game.act(Action::Call).unwrap();
+
+    assert_eq!(game.to_act, Some(1));
+    assert!(game.can(Action::Raise(dollars(26))));
+}
+
+#[test]
+fn a_seeded_table_plays_the_same_hand() {
+    let play = || {
+        let mut game = HoldemGame::new("You", 5, dollars(200));
+        game.set_rng(seeded_rng(7));
+        game.start_hand().unwrap();
+        while game.in_progress() {
+            game.play_bots().unwrap();
+            if game.in_progress() {
+                game.act(Action::Fold).unwrap();
+            }
+        }
+        (game.seats, game.board, game.log)
+    };
+    assert_eq!(play(), play());
+}
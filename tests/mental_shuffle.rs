@@ -0,0 +1,45 @@
+use terminal_casino::baccarat::{Card, GameMode};
+use terminal_casino::mental_shuffle::{MentalShoe, Secret};
+use terminal_casino::provably_fair::ServerSeed;
+
+fn secret(byte: &str) -> Secret {
+    Secret::parse(&byte.repeat(32)).unwrap()
+}
+
+/// The first `hands` hands off a fresh shoe dealt with `key`.
+fn deal(key: &ServerSeed, hands: u32) -> Vec<(Vec<Card>, Vec<Card>)> {
+    let mut shoe = MentalShoe::new(GameMode::Classic, 1);
+    (1..=hands)
+        .map(|hand| {
+            let game = shoe.deal(hand, key).unwrap();
+            (
+                game.player_hand.into_iter().copied().collect(),
+                game.banker_hand.into_iter().copied().collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn a_reveal_is_checked_against_its_commitment() {
+    let (alice, bob) = (secret("a1"), secret("b2"));
+    assert!(alice.matches(&alice.commitment()));
+    assert!(alice.matches(&alice.commitment().to_uppercase()));
+    assert!(!bob.matches(&alice.commitment()));
+    assert_eq!(Secret::parse(&alice.to_hex()), Ok(alice));
+
+    assert!(Secret::parse(&"a1".repeat(31)).is_err());
+    assert!(Secret::parse(&"zz".repeat(32)).is_err());
+}
+
+#[test]
+fn both_players_deal_the_same_hands() {
+    let (alice, bob) = (secret("a1"), secret("b2"));
+    let (at_alice, at_bob) = (alice.combine(&bob), bob.combine(&alice));
+    assert_eq!(at_alice.as_str(), at_bob.as_str());
+
+    // Enough hands to run through a one-deck shoe and start another.
+    let dealt = deal(&at_alice, 20);
+    assert_eq!(dealt, deal(&at_bob, 20));
+    assert_ne!(dealt, deal(&alice.combine(&secret("b3")), 20));
+}
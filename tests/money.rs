@@ -0,0 +1,62 @@
+use terminal_casino::baccarat::{BaccaratGame, BetType, Card, GameMode, Money, SPADES};
+
+fn card(rank: u8) -> Card {
+    Card::new(SPADES, rank)
+}
+
+fn parse(json: &str) -> Result<Money, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+#[test]
+fn amounts_show_cents_only_when_there_are_any() {
+    assert_eq!(Money::from_dollars(1000).to_string(), "1000");
+    assert_eq!(Money::from_cents(1950).to_string(), "19.50");
+    assert_eq!(Money::from_cents(-250).to_string(), "-2.50");
+    assert_eq!(Money::from_cents(5).to_string(), "0.05");
+    assert_eq!(format!("{:+}", Money::from_dollars(10)), "+10");
+    assert_eq!(format!("{:>6}", Money::from_cents(950)), "  9.50");
+}
+
+#[test]
+fn saved_amounts_read_back_to_the_cent() {
+    assert_eq!(parse("1000").unwrap(), Money::from_dollars(1000));
+    assert_eq!(parse("19.5").unwrap(), Money::from_cents(1950));
+    assert_eq!(parse("-2.505").unwrap(), Money::from_cents(-251));
+    for amount in [Money::from_dollars(7), Money::from_cents(1999)] {
+        let saved = serde_json::to_string(&amount).unwrap();
+        assert_eq!(parse(&saved).unwrap(), amount);
+    }
+}
+
+#[test]
+fn amounts_past_the_input_limit_are_refused() {
+    assert!(parse("1000000000000").is_ok());
+    assert!(parse("1000000000001").is_err());
+    assert!(parse("-1e13").is_err());
+    assert!(parse("9223372036854775807").is_err());
+    assert!(parse("18446744073709551615").is_err());
+    assert!(parse("1e300").is_err());
+
+    assert_eq!(
+        Money::from_input_cents(Money::MAX_INPUT.cents()),
+        Some(Money::MAX_INPUT)
+    );
+    assert_eq!(Money::from_input_cents(i64::MIN), None);
+}
+
+#[test]
+fn banker_commission_is_five_percent_rounded_down_to_the_cent() {
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    game.set_hands(&[card(10), card(5)], &[card(4), card(3)]);
+    assert_eq!(game.state.winner, 2);
+    let returned =
+        |cents| game.calculate_main_bet_payout(BetType::Banker, Money::from_cents(cents));
+
+    assert_eq!(returned(1000), Money::from_cents(1950));
+    // $10.01 wins $9.5095 after commission, paid as $9.50.
+    assert_eq!(returned(1001), Money::from_cents(1951));
+    // $10.10 wins $9.595, paid as $9.59.
+    assert_eq!(returned(1010), Money::from_cents(1969));
+    assert_eq!(returned(1), Money::from_cents(1));
+}
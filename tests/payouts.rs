@@ -0,0 +1,164 @@
+use terminal_casino::analytics::{self, TableEdges};
+use terminal_casino::baccarat::{
+    BaccaratGame, BetType, BonusBetType, BonusBets, Card, GameMode, Money, OverUnderRule, SPADES,
+};
+use terminal_casino::settlement::{BetOutcome, SettledBet, Wager};
+
+/// House edges at a fresh `decks`-deck `mode` shoe, as `analyze` prints them.
+fn fresh_shoe_edges(mode: GameMode, decks: u32) -> TableEdges {
+    let mut counts = [0; 13];
+    for count in &mut counts[..mode.top_rank() as usize] {
+        *count = decks * 4;
+    }
+    let payout_table = BaccaratGame::with_mode(mode).payout_table;
+    analytics::exact_odds(mode, OverUnderRule::standard(), payout_table, &counts)
+        .unwrap()
+        .edges
+}
+
+fn assert_edge(edges: &TableEdges, bet: BonusBetType, expected: f64) {
+    let edge = edges.side_bet(bet).unwrap();
+    assert!(
+        (edge - expected).abs() < 0.0005,
+        "{} edge {:.4}, expected {:.4}",
+        bet,
+        edge,
+        expected
+    );
+}
+
+#[test]
+fn pairs_pay_11_to_1_at_the_published_edge() {
+    let edges = fresh_shoe_edges(GameMode::Classic, 8);
+    assert_edge(&edges, BonusBetType::PlayerPair, 0.1036);
+    assert_edge(&edges, BonusBetType::BankerPair, 0.1036);
+}
+
+#[test]
+fn winning_side_bets_return_the_stake_with_the_winnings() {
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    let pair = [card(1), card(1)];
+    game.set_hands(&pair, &[card(10), card(5)]);
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::PlayerPair, Money::from_dollars(10));
+    assert_eq!(
+        bets.payout(BonusBetType::PlayerPair, &game),
+        Money::from_dollars(120)
+    );
+}
+
+fn card(rank: u8) -> Card {
+    Card::new(SPADES, rank)
+}
+
+#[test]
+fn dragon_bonus_pays_even_money_on_a_natural_and_30_to_1_by_nine() {
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::PlayerDragon, Money::from_dollars(10));
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+
+    game.set_hands(&[card(4), card(4)], &[card(3), card(3)]);
+    let natural = SettledBet::new(
+        Wager::Side(BonusBetType::PlayerDragon),
+        Money::from_dollars(10),
+        bets.calculate_payouts(&game),
+    );
+    assert_eq!(
+        (natural.outcome, natural.net()),
+        (BetOutcome::Win, Money::from_dollars(10))
+    );
+
+    game.set_hands(
+        &[card(4), card(5), card(10)],
+        &[card(10), card(10), card(10)],
+    );
+    assert_eq!(game.victory_margin(), 9);
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(310));
+
+    game.set_hands(&[card(8), card(10)], &[card(8), card(10)]);
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(10));
+}
+
+#[test]
+fn super_6_pays_12_to_1() {
+    let edges = fresh_shoe_edges(GameMode::Classic, 8);
+    // Banker wins with a 6 on 5.386% of hands from a fresh eight-deck shoe.
+    assert_edge(&edges, BonusBetType::SuperSix, 1.0 - 13.0 * 0.05386);
+
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    game.set_hands(&[card(10), card(5)], &[card(2), card(4)]);
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::SuperSix, Money::from_dollars(10));
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(130));
+}
+
+#[test]
+fn specific_ties_pay_the_published_odds() {
+    const PUBLISHED: [i32; 10] = [150, 215, 225, 200, 120, 110, 45, 45, 80, 80];
+    let mut game = BaccaratGame::with_mode(GameMode::Classic);
+    for (score, odds) in (0..10u8).zip(PUBLISHED) {
+        let mut bets = BonusBets::new();
+        bets.tie_score_bets[score as usize] = Money::from_dollars(1);
+        // The next score along loses.
+        bets.tie_score_bets[(score as usize + 1) % 10] = Money::from_dollars(1);
+        // A ten and a card worth the score; ten for a tie at 0.
+        let hand = [card(10), card(if score == 0 { 10 } else { score })];
+        game.set_hands(&hand, &hand);
+        assert_eq!(
+            bets.calculate_payouts(&game),
+            Money::from_dollars(odds + 1),
+            "tie on {}",
+            score
+        );
+    }
+}
+
+#[test]
+fn ez_dragon_7_and_panda_8_pay_40_and_25_to_1() {
+    let edges = fresh_shoe_edges(GameMode::EzBaccarat, 8);
+    let main_edge = |bet| {
+        edges
+            .main_bets
+            .iter()
+            .find(|(name, _)| *name == bet)
+            .unwrap()
+            .1
+    };
+    assert!((main_edge(BetType::Dragon7) - 0.0761).abs() < 0.0005);
+    assert!((main_edge(BetType::Panda8) - 0.1019).abs() < 0.0005);
+}
+
+#[test]
+fn ez_monkey_bets_return_the_stake_with_the_winnings() {
+    let mut game = BaccaratGame::with_mode(GameMode::EzBaccarat);
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::MonkeySix, Money::from_dollars(1));
+    bets.set(BonusBetType::MonkeyTie, Money::from_dollars(1));
+    game.set_hands(
+        &[card(10), card(5), card(10)],
+        &[card(10), card(3), card(3)],
+    );
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(13));
+    game.set_hands(&[card(10), card(4), card(2)], &[card(10), card(3), card(3)]);
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(151));
+}
+
+#[test]
+fn dragon_tiger_suited_tie_pays_50_to_1() {
+    let mut game = BaccaratGame::with_mode(GameMode::DragonTiger);
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::SuitedTie, Money::from_dollars(1));
+    game.set_hands(&[card(7)], &[card(7)]);
+    assert_eq!(bets.calculate_payouts(&game), Money::from_dollars(51));
+}
+
+#[test]
+fn three_card_tie_pays_25_to_1() {
+    let mut game = BaccaratGame::with_mode(GameMode::ThreeCard);
+    game.set_hands(&[card(1), card(2), card(3)], &[card(3), card(2), card(1)]);
+    assert_eq!(game.state.winner, 3);
+    assert_eq!(
+        game.calculate_main_bet_payout(BetType::Tie, Money::from_dollars(1)),
+        Money::from_dollars(26)
+    );
+}
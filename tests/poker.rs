@@ -0,0 +1,137 @@
+use terminal_casino::baccarat::{CLUBS, Card, DIAMONDS, HEARTS, SPADES};
+use terminal_casino::holdem::{Category, HandValue, evaluate};
+use terminal_casino::three_card_poker::evaluate_three;
+use terminal_casino::{caribbean, three_card_poker};
+
+/// Cards from (rank, suit) pairs, aces as 1.
+fn cards(spec: &[(u8, u8)]) -> Vec<Card> {
+    spec.iter()
+        .map(|&(rank, suit)| Card::new(suit, rank))
+        .collect()
+}
+
+/// Cards of mixed suits from ranks alone.
+fn offsuit(ranks: &[u8]) -> Vec<Card> {
+    ranks
+        .iter()
+        .enumerate()
+        .map(|(i, &rank)| Card::new(i as u8 % 4, rank))
+        .collect()
+}
+
+fn value(category: Category, ranks: [u8; 5]) -> HandValue {
+    HandValue { category, ranks }
+}
+
+#[test]
+fn the_wheel_is_a_five_high_straight() {
+    let wheel = evaluate(&offsuit(&[1, 2, 3, 4, 5]));
+    assert_eq!(wheel, value(Category::Straight, [5, 0, 0, 0, 0]));
+    assert!(evaluate(&offsuit(&[2, 3, 4, 5, 6])) > wheel);
+    assert!(wheel > evaluate(&offsuit(&[1, 1, 1, 13, 12])));
+    // The ace plays high or low, never both.
+    assert_eq!(
+        evaluate(&offsuit(&[12, 13, 1, 2, 3])).category,
+        Category::HighCard
+    );
+
+    let steel = evaluate(&cards(&[
+        (1, SPADES),
+        (2, SPADES),
+        (3, SPADES),
+        (4, SPADES),
+        (5, SPADES),
+        (13, HEARTS),
+        (13, CLUBS),
+    ]));
+    assert_eq!(steel, value(Category::StraightFlush, [5, 0, 0, 0, 0]));
+    assert!(!steel.is_royal());
+}
+
+#[test]
+fn seven_cards_play_their_best_five() {
+    // Six hearts, a straight in other suits, and a pair: the flush plays
+    // its top five hearts.
+    let flush = evaluate(&cards(&[
+        (13, HEARTS),
+        (10, HEARTS),
+        (8, HEARTS),
+        (6, HEARTS),
+        (4, HEARTS),
+        (2, HEARTS),
+        (9, SPADES),
+    ]));
+    assert_eq!(flush, value(Category::Flush, [13, 10, 8, 6, 4]));
+    let over_a_straight = evaluate(&cards(&[
+        (9, HEARTS),
+        (8, HEARTS),
+        (7, HEARTS),
+        (6, CLUBS),
+        (5, HEARTS),
+        (2, HEARTS),
+        (9, DIAMONDS),
+    ]));
+    assert_eq!(over_a_straight, value(Category::Flush, [9, 8, 7, 5, 2]));
+
+    // Two sets of trips make a full house, the lower set as the pair.
+    let full = evaluate(&offsuit(&[9, 9, 9, 4, 4, 4, 13]));
+    assert_eq!(full, value(Category::FullHouse, [9, 4, 0, 0, 0]));
+    assert!(full > flush);
+    assert!(evaluate(&offsuit(&[9, 9, 9, 4, 4, 13, 13])) > full);
+}
+
+#[test]
+fn three_pairs_play_the_best_two_and_the_best_kicker() {
+    assert_eq!(
+        evaluate(&offsuit(&[13, 13, 9, 9, 4, 4, 12])),
+        value(Category::TwoPair, [13, 9, 12, 0, 0])
+    );
+    // The third pair's rank can be the kicker.
+    assert_eq!(
+        evaluate(&offsuit(&[13, 13, 9, 9, 4, 4, 2])),
+        value(Category::TwoPair, [13, 9, 4, 0, 0])
+    );
+    assert_eq!(
+        evaluate(&offsuit(&[7, 7, 1, 13, 9, 3, 2])),
+        value(Category::OnePair, [7, 14, 13, 9, 0])
+    );
+}
+
+#[test]
+fn a_2_3_is_the_lowest_three_card_straight() {
+    let low = evaluate_three(&offsuit(&[1, 2, 3]));
+    assert_eq!(low.category, Category::Straight);
+    assert_eq!(low.ranks, [3, 2, 1]);
+    let next = evaluate_three(&offsuit(&[2, 3, 4]));
+    assert!(next > low);
+    let high = evaluate_three(&offsuit(&[1, 13, 12]));
+    assert_eq!(high.ranks, [14, 13, 12]);
+    assert!(high > next);
+
+    // A straight beats a flush with three cards, and trips beat both.
+    let flush = evaluate_three(&cards(&[(1, HEARTS), (9, HEARTS), (4, HEARTS)]));
+    assert_eq!(flush.category, Category::Flush);
+    assert!(low > flush);
+    assert!(evaluate_three(&offsuit(&[2, 2, 2])) > high);
+
+    let suited = evaluate_three(&cards(&[(1, SPADES), (2, SPADES), (3, SPADES)]));
+    assert_eq!(suited.category, Category::StraightFlush);
+    assert!(!suited.is_mini_royal());
+    let mini = evaluate_three(&cards(&[(1, SPADES), (13, SPADES), (12, SPADES)]));
+    assert!(mini.is_mini_royal());
+    assert_eq!(three_card_poker::pair_plus_pays(&mini), Some(40));
+}
+
+#[test]
+fn dealers_qualify_on_ace_king_and_queen_high() {
+    assert!(caribbean::qualifies(&evaluate(&offsuit(&[1, 13, 4, 3, 2]))));
+    assert!(!caribbean::qualifies(&evaluate(&offsuit(&[
+        1, 12, 4, 3, 2
+    ]))));
+    assert!(three_card_poker::qualifies(&evaluate_three(&offsuit(&[
+        12, 4, 2
+    ]))));
+    assert!(!three_card_poker::qualifies(&evaluate_three(&offsuit(&[
+        11, 9, 2
+    ]))));
+}
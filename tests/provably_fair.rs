@@ -0,0 +1,44 @@
+use terminal_casino::provably_fair::{ServerSeed, hex, parse_hex, shoe_order};
+
+const SEED: &str = "7f3a9c04e1b25d68f0c4a7e93b1d5f2806e9a4c7b3d1f5e2a8c60b4d9e7f1a35";
+
+/// The seed with one hex digit changed.
+fn tampered() -> ServerSeed {
+    ServerSeed::parse(&SEED.replacen('7', "8", 1)).unwrap()
+}
+
+#[test]
+fn a_revealed_seed_matches_its_commitment_and_deals_the_same_shoe() {
+    let seed = ServerSeed::parse(SEED).unwrap();
+    let published = seed.commitment();
+    let dealt = shoe_order(&seed, "player-chosen", 8, 13);
+    assert_eq!(dealt.len(), 8 * 52);
+
+    let revealed = ServerSeed::parse(&SEED.to_uppercase()).unwrap();
+    assert_eq!(revealed.commitment(), published);
+    assert_eq!(shoe_order(&revealed, "player-chosen", 8, 13), dealt);
+    assert_ne!(shoe_order(&seed, "another-client", 8, 13), dealt);
+}
+
+#[test]
+fn a_tampered_seed_fails_the_commitment_and_deals_another_shoe() {
+    let seed = ServerSeed::parse(SEED).unwrap();
+    let tampered = tampered();
+    assert_ne!(tampered.commitment(), seed.commitment());
+    assert_ne!(
+        shoe_order(&tampered, "player-chosen", 8, 13),
+        shoe_order(&seed, "player-chosen", 8, 13)
+    );
+}
+
+#[test]
+fn only_64_hex_digits_are_a_server_seed() {
+    assert!(ServerSeed::parse(&SEED[..62]).is_err());
+    assert!(ServerSeed::parse(&format!("{}00", SEED)).is_err());
+    assert!(ServerSeed::parse(&SEED.replacen('f', "g", 1)).is_err());
+
+    assert_eq!(hex(&[0, 0x7f, 0xff]), "007fff");
+    assert_eq!(parse_hex("007fFF"), Some(vec![0, 0x7f, 0xff]));
+    assert_eq!(parse_hex("abc"), None);
+    assert_eq!(parse_hex("+1"), None);
+}
@@ -0,0 +1,128 @@
+use terminal_casino::baccarat::{Money, TableLimits, seeded_rng};
+use terminal_casino::roulette::{Bet, DOUBLE_ZERO, RouletteError, RouletteGame, Wheel};
+
+#[test]
+fn each_kind_of_bet_pays_by_the_numbers_it_covers() {
+    let table = [
+        (Bet::Straight(17), 1, 35),
+        (Bet::Straight(DOUBLE_ZERO), 1, 35),
+        (Bet::Split(17, 20), 2, 17),
+        (Bet::Split(0, DOUBLE_ZERO), 2, 17),
+        (Bet::Street(13), 3, 11),
+        (Bet::Corner(13), 4, 8),
+        (Bet::SixLine(13), 6, 5),
+        (Bet::Dozen(2), 12, 2),
+        (Bet::Column(3), 12, 2),
+        (Bet::Red, 18, 1),
+        (Bet::Black, 18, 1),
+        (Bet::Odd, 18, 1),
+        (Bet::Even, 18, 1),
+        (Bet::Low, 18, 1),
+        (Bet::High, 18, 1),
+    ];
+    for (bet, covered, pays) in table {
+        assert_eq!(bet.numbers().len(), covered, "{}", bet);
+        assert_eq!(bet.pays(), pays, "{}", bet);
+    }
+    assert_eq!(Bet::Corner(13).numbers(), [13, 14, 16, 17]);
+    assert_eq!(Bet::Column(1).numbers().last(), Some(&34));
+    for bet in [Bet::Red, Bet::Odd, Bet::Low, Bet::Dozen(1), Bet::Column(1)] {
+        assert!(!bet.covers(0) && !bet.covers(DOUBLE_ZERO), "{}", bet);
+    }
+}
+
+#[test]
+fn only_bets_on_the_layout_are_taken() {
+    let on_both = [
+        Bet::Straight(0),
+        Bet::Straight(36),
+        Bet::Split(0, 1),
+        Bet::Split(0, 2),
+        Bet::Split(1, 2),
+        Bet::Split(1, 4),
+        Bet::Split(33, 36),
+        Bet::Street(34),
+        Bet::Corner(32),
+        Bet::SixLine(31),
+        Bet::Dozen(3),
+        Bet::Column(1),
+    ];
+    for bet in on_both {
+        assert!(bet.is_on(Wheel::European), "{}", bet);
+        assert!(bet.is_on(Wheel::American), "{}", bet);
+    }
+
+    let american_only = [
+        Bet::Straight(DOUBLE_ZERO),
+        Bet::Split(0, DOUBLE_ZERO),
+        Bet::Split(3, DOUBLE_ZERO),
+    ];
+    for bet in american_only {
+        assert!(!bet.is_on(Wheel::European), "{}", bet);
+        assert!(bet.is_on(Wheel::American), "{}", bet);
+    }
+    assert!(Bet::Split(0, 3).is_on(Wheel::European));
+    assert!(!Bet::Split(0, 3).is_on(Wheel::American));
+
+    let on_neither = [
+        Bet::Straight(38),
+        Bet::Split(3, 4),
+        Bet::Split(1, 3),
+        Bet::Split(34, 37),
+        Bet::Split(1, DOUBLE_ZERO),
+        Bet::Street(2),
+        Bet::Street(0),
+        Bet::Corner(3),
+        Bet::Corner(34),
+        Bet::SixLine(34),
+        Bet::Dozen(0),
+        Bet::Column(4),
+    ];
+    for bet in on_neither {
+        assert!(!bet.is_on(Wheel::European), "{}", bet);
+        assert!(!bet.is_on(Wheel::American), "{}", bet);
+    }
+
+    let mut game = RouletteGame::new(Wheel::European, TableLimits::STANDARD);
+    assert_eq!(
+        game.place(Bet::Straight(DOUBLE_ZERO), Money::from_dollars(10)),
+        Err(RouletteError::NotOnLayout(
+            Bet::Straight(DOUBLE_ZERO),
+            Wheel::European
+        ))
+    );
+}
+
+#[test]
+fn a_spin_pays_every_covering_bet_and_takes_the_rest() {
+    let dollars = Money::from_dollars;
+    let mut game = RouletteGame::new(Wheel::European, TableLimits::STANDARD);
+    game.place(Bet::Straight(17), dollars(10)).unwrap();
+    game.place(Bet::Corner(13), dollars(10)).unwrap();
+    game.place(Bet::Red, dollars(10)).unwrap();
+    game.place(Bet::Dozen(1), dollars(10)).unwrap();
+
+    let report = game.settle(17);
+    assert_eq!(report.total_bet(), dollars(40));
+    // 17 is black: the straight and the corner win, red and the first
+    // dozen lose.
+    assert_eq!(report.total_payout(), dollars(360 + 90));
+    assert_eq!(game.total_bet(), Money::ZERO);
+    assert_eq!(game.history, [17]);
+}
+
+#[test]
+fn a_seeded_wheel_lands_in_the_same_pockets() {
+    let spins = |seed| {
+        let mut game = RouletteGame::new(Wheel::American, TableLimits::STANDARD);
+        game.set_rng(seeded_rng(seed));
+        (0..30)
+            .map(|_| {
+                game.place(Bet::Red, Money::from_dollars(10)).unwrap();
+                game.spin().unwrap().0
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(spins(5), spins(5));
+    assert_ne!(spins(5), spins(6));
+}
@@ -0,0 +1,92 @@
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// A `serve` process on a free port, stopped when dropped.
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_terminal_casino"))
+            .args(["serve", "--bind", "127.0.0.1:0", "--seed", "1"])
+            .env(
+                "TERMINAL_CASINO_DATA",
+                std::env::temp_dir().join("terminal_casino_server_test"),
+            )
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+        let address = lines
+            .by_ref()
+            .map(Result::unwrap)
+            .find_map(|line| {
+                let rest = line.strip_prefix("Serving on ")?;
+                Some(rest.split_whitespace().next()?.to_string())
+            })
+            .unwrap();
+        // Keep draining what the server prints so it never blocks on it.
+        thread::spawn(move || lines.for_each(drop));
+        Self { child, address }
+    }
+
+    /// Joins as `name`, with `token` if given, and returns the server's
+    /// answer along with the connection.
+    fn join(&self, name: &str, token: Option<&str>) -> (TcpStream, Value) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let join = json!({"type": "join", "name": name, "version": 2, "token": token});
+        writeln!(stream, "{}", join).unwrap();
+        let mut line = String::new();
+        BufReader::new(stream.try_clone().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        (stream, serde_json::from_str(&line).unwrap())
+    }
+
+    /// Joins as `name` once the server has seen them get up.
+    fn rejoin(&self, name: &str, token: Option<&str>) -> (TcpStream, Value) {
+        for _ in 0..50 {
+            let (stream, answer) = self.join(name, token);
+            let reason = answer["reason"].as_str().unwrap_or_default();
+            if !reason.contains("already at the table") {
+                return (stream, answer);
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        panic!("{} never got up", name);
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn a_seat_is_held_for_its_resume_token() {
+    let server = Server::start();
+    let (stream, welcome) = server.join("alice", None);
+    assert_eq!(welcome["type"], "welcome");
+    let token = welcome["token"].as_str().unwrap().to_string();
+    drop(stream);
+
+    let (_, refused) = server.rejoin("alice", Some("not-the-token"));
+    assert_eq!(refused["type"], "rejected");
+    let (_, refused) = server.join("alice", None);
+    assert_eq!(refused["type"], "rejected");
+
+    let (_, welcome) = server.join("alice", Some(&token));
+    assert_eq!(welcome["type"], "welcome");
+    assert_ne!(welcome["token"].as_str(), Some(token.as_str()));
+}
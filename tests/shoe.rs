@@ -0,0 +1,114 @@
+use terminal_casino::baccarat::{
+    BaccaratGame, Card, CardSource, Deck, GameError, GameMode, HEARTS, ProcedureProfile, SPADES,
+    Shoe, ShoeConfig,
+};
+
+fn cards(ranks: &[u8]) -> Vec<Card> {
+    ranks.iter().map(|&rank| Card::new(SPADES, rank)).collect()
+}
+
+fn dealt(game: &BaccaratGame) -> (Vec<Card>, Vec<Card>) {
+    (
+        game.player_hand.into_iter().copied().collect(),
+        game.banker_hand.into_iter().copied().collect(),
+    )
+}
+
+#[test]
+fn a_stacked_shoe_deals_in_the_order_given() {
+    // A natural 8 against 6, then player 2 against banker 5: the player
+    // draws a 3 and the banker stands on 5 against it.
+    let order = cards(&[4, 3, 4, 3, 10, 10, 2, 5, 3]);
+    let mut game = BaccaratGame::with_card_source(
+        GameMode::Classic,
+        CardSource::Shoe(Shoe::from_cards(order.clone())),
+    );
+
+    game.play_round_unattended().unwrap();
+    assert_eq!(
+        dealt(&game),
+        (vec![order[0], order[2]], vec![order[1], order[3]])
+    );
+    assert_eq!(game.state.winner, 1);
+
+    game.prepare_next_round();
+    game.play_round_unattended().unwrap();
+    assert_eq!(
+        dealt(&game),
+        (vec![order[4], order[6], order[8]], vec![order[5], order[7]])
+    );
+    assert_eq!(game.state.winner, 3);
+
+    game.prepare_next_round();
+    assert_eq!(
+        game.play_round_unattended(),
+        Err(GameError::SequenceExhausted)
+    );
+}
+
+#[test]
+fn a_stacked_deck_deals_first_to_last() {
+    let order = vec![Card::new(HEARTS, 1), Card::new(SPADES, 13)];
+    let mut deck = Deck::from_cards(order.clone());
+    assert_eq!(deck.deal(), Some(order[0]));
+    assert_eq!(deck.deal(), Some(order[1]));
+    assert_eq!(deck.deal(), None);
+}
+
+#[test]
+fn a_seeded_shoe_deals_the_same_cards() {
+    let deal = |seed| {
+        let mut shoe = Shoe::seeded(ProcedureProfile::standard(8), 13, seed);
+        (0..60).map(|_| shoe.deal().unwrap()).collect::<Vec<_>>()
+    };
+    assert_eq!(deal(5), deal(5));
+    assert_ne!(deal(5), deal(6));
+}
+
+#[test]
+fn a_seeded_game_plays_the_same_hands() {
+    let play = |seed| {
+        let mut game = BaccaratGame::seeded(GameMode::Classic, ProcedureProfile::standard(8), seed);
+        (0..20)
+            .map(|_| {
+                game.prepare_next_round();
+                game.play_round_unattended().unwrap();
+                dealt(&game)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(play(5), play(5));
+    assert_ne!(play(5), play(6));
+}
+
+#[test]
+fn only_real_cards_are_counted() {
+    assert!(Card::new(HEARTS, 1).is_valid());
+    assert!(Card::new(SPADES, 13).is_valid());
+    assert!(!Card::new(SPADES, 0).is_valid());
+    assert!(!Card::new(SPADES, 14).is_valid());
+    assert!(!Card::new(4, 1).is_valid());
+
+    let deck = Deck::from_cards(vec![
+        Card::new(SPADES, 0),
+        Card::new(SPADES, 14),
+        Card::new(HEARTS, 13),
+    ]);
+    let counts = CardSource::SingleDeck(deck).composition().unwrap();
+    assert_eq!(counts.iter().sum::<u32>(), 1);
+    assert_eq!(counts[12], 1);
+}
+
+#[test]
+fn a_cut_card_offset_stays_within_the_shoe() {
+    assert!(ProcedureProfile::parse("decks=8,offset=-8").is_ok());
+    assert!(ProcedureProfile::parse("decks=8,offset=-2147483648").is_err());
+    assert!(ProcedureProfile::parse("decks=8,offset=500").is_err());
+    assert!(ProcedureProfile::parse("decks=8,cut=18446744073709551615").is_err());
+
+    let size = 52 * 8;
+    for offset in [i32::MIN, -1000, 0, 1000, i32::MAX] {
+        let depth = ShoeConfig::new(8).cut_card_offset(offset).cut_card_depth();
+        assert!(depth <= size - 16, "offset {} cut at {}", offset, depth);
+    }
+}
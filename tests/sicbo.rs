@@ -0,0 +1,122 @@
+use terminal_casino::baccarat::{Money, TableLimits, seeded_rng};
+use terminal_casino::sicbo::{Bet, Dice, SicBoError, SicBoGame};
+
+/// A roll every cell's bet wins on, what it pays there, and a roll it
+/// loses on.
+fn winner_and_loser(bet: Bet) -> (Dice, i64, Dice) {
+    match bet {
+        Bet::Small => (Dice([1, 2, 4]), 1, Dice([2, 2, 2])),
+        Bet::Big => (Dice([5, 6, 6]), 1, Dice([5, 5, 5])),
+        Bet::Total(total) => {
+            let pays = match total {
+                4 | 17 => 60,
+                5 | 16 => 30,
+                6 | 15 => 17,
+                7 | 14 => 12,
+                8 | 13 => 8,
+                _ => 6,
+            };
+            let dice = Dice::all().find(|dice| dice.total() == total).unwrap();
+            let other = Dice::all().find(|dice| dice.total() != total).unwrap();
+            (dice, pays, other)
+        }
+        Bet::Double(face) => (Dice([face, 1 + face % 6, face]), 10, Dice([1, 2, 3])),
+        Bet::Triple(face) => (Dice([face; 3]), 180, Dice([face, face, 1 + face % 6])),
+        Bet::AnyTriple => (Dice([4, 4, 4]), 30, Dice([4, 4, 5])),
+        Bet::Combination(a, b) => (Dice([b, 6 - a % 6, a]), 5, Dice([a, a, a])),
+        Bet::Single(face) => (Dice([face, face, 1 + face % 6]), 2, Dice([7 - face; 3])),
+    }
+}
+
+#[test]
+fn every_cell_on_the_grid_pays_its_odds() {
+    let grid = Bet::grid();
+    assert_eq!(grid.iter().map(Vec::len).sum::<usize>(), 50);
+    for bet in grid.into_iter().flatten() {
+        assert!(bet.is_valid(), "{}", bet);
+        let (winner, pays, loser) = winner_and_loser(bet);
+        assert_eq!(bet.pays(winner), Some(pays), "{} on {:?}", bet, winner);
+        assert_eq!(bet.pays(loser), None, "{} on {:?}", bet, loser);
+    }
+    assert_eq!(Bet::Single(3).pays(Dice([3, 1, 2])), Some(1));
+    assert_eq!(Bet::Single(3).pays(Dice([3, 3, 3])), Some(3));
+}
+
+#[test]
+fn house_edges_match_the_macau_pay_table() {
+    // Units returned over all 216 rolls, stake included.
+    let table = [
+        (Bet::Small, 210),
+        (Bet::Big, 210),
+        (Bet::Total(4), 3 * 61),
+        (Bet::Total(5), 6 * 31),
+        (Bet::Total(6), 10 * 18),
+        (Bet::Total(7), 15 * 13),
+        (Bet::Total(8), 21 * 9),
+        (Bet::Total(9), 25 * 7),
+        (Bet::Total(10), 27 * 7),
+        (Bet::Total(11), 27 * 7),
+        (Bet::Total(17), 3 * 61),
+        (Bet::Double(2), 16 * 11),
+        (Bet::Triple(2), 181),
+        (Bet::AnyTriple, 6 * 31),
+        (Bet::Combination(2, 5), 30 * 6),
+        (Bet::Single(2), 75 * 2 + 15 * 3 + 4),
+    ];
+    for (bet, returned) in table {
+        let edge = 1.0 - returned as f64 / 216.0;
+        assert!((bet.house_edge() - edge).abs() < 1e-12, "{}", bet);
+    }
+    assert!((Bet::Small.house_edge() - 0.0278).abs() < 1e-4);
+    assert!((Bet::Single(6).house_edge() - 0.0787).abs() < 1e-4);
+}
+
+#[test]
+fn bets_off_the_grid_are_refused() {
+    let mut game = SicBoGame::new(TableLimits::STANDARD);
+    for bet in [
+        Bet::Total(3),
+        Bet::Total(18),
+        Bet::Double(0),
+        Bet::Triple(7),
+        Bet::Single(7),
+        Bet::Combination(3, 3),
+        Bet::Combination(5, 2),
+    ] {
+        assert_eq!(
+            game.place(bet, Money::from_dollars(10)),
+            Err(SicBoError::NotOnGrid(bet))
+        );
+    }
+}
+
+#[test]
+fn a_roll_settles_each_bet_with_its_stake_back() {
+    let dollars = Money::from_dollars;
+    let mut game = SicBoGame::new(TableLimits::STANDARD);
+    game.place(Bet::Big, dollars(10)).unwrap();
+    game.place(Bet::Total(14), dollars(10)).unwrap();
+    game.place(Bet::Single(4), dollars(10)).unwrap();
+
+    let report = game.settle(Dice([4, 4, 6]));
+    // Big returns 20, total 14 returns 130, single 4 on two dice returns 30.
+    assert_eq!(report.total_bet(), dollars(30));
+    assert_eq!(report.total_payout(), dollars(180));
+    assert_eq!(game.total_bet(), Money::ZERO);
+}
+
+#[test]
+fn a_seeded_table_rolls_the_same_dice() {
+    let rolls = |seed| {
+        let mut game = SicBoGame::new(TableLimits::STANDARD);
+        game.set_rng(seeded_rng(seed));
+        (0..30)
+            .map(|_| {
+                game.place(Bet::Small, Money::from_dollars(10)).unwrap();
+                game.roll().unwrap().0
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rolls(5), rolls(5));
+    assert_ne!(rolls(5), rolls(6));
+}
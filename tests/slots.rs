@@ -0,0 +1,72 @@
+use terminal_casino::baccarat::Money;
+use terminal_casino::slots::{MachineBook, SlotsGame};
+
+/// Three reels of four stops, two lines, sevens paying only three in a row.
+const TINY: &str = r#"
+[[machine]]
+name = "Tiny"
+rows = 1
+reels = ["SEVEN BAR BAR BAR", "SEVEN SEVEN BAR BAR", "SEVEN BAR BAR BAR"]
+paylines = [[0, 0, 0], [0, 0, 0]]
+pays = { SEVEN = [0, 0, 40], BAR = [0, 1, 2] }
+"#;
+
+#[test]
+fn rtp_is_worked_out_exactly_from_the_strips() {
+    let book = MachineBook::parse(TINY).unwrap();
+    let machine = &book.machines[0];
+    // Three sevens: 1/4 * 2/4 * 1/4 at 40. Two bars and no third: 3/4 *
+    // 2/4 * 1/4 at 1. Three bars: 3/4 * 2/4 * 3/4 at 2.
+    let expected = 1.0 / 32.0 * 40.0 + 3.0 / 32.0 * 1.0 + 9.0 / 32.0 * 2.0;
+    assert!((machine.rtp() - expected).abs() < 1e-12);
+}
+
+#[test]
+fn a_line_pays_its_run_from_the_left_reel() {
+    let book = MachineBook::parse(TINY).unwrap();
+    let machine = &book.machines[0];
+    let line_bet = Money::from_dollars(5);
+
+    let wins = machine.line_wins(&[0, 1, 0], 2, line_bet);
+    assert_eq!(wins.len(), 2);
+    assert!(
+        wins.iter()
+            .all(|win| win.symbol == "SEVEN" && win.count == 3)
+    );
+    assert!(
+        wins.iter()
+            .all(|win| win.payout == Money::from_dollars(200))
+    );
+
+    // Bar, bar, then a seven: two in a row.
+    let wins = machine.line_wins(&[1, 2, 0], 1, line_bet);
+    assert_eq!(wins[0].count, 2);
+    assert_eq!(wins[0].payout, Money::from_dollars(5));
+    assert!(machine.line_wins(&[0, 2, 1], 2, line_bet).is_empty());
+}
+
+#[test]
+fn a_seeded_machine_spins_the_same_reels() {
+    let machine = MachineBook::builtin().machines.remove(0);
+    let spins = |seed| {
+        let mut game = SlotsGame::seeded(machine.clone(), seed);
+        (0..20).map(|_| game.spin().clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(spins(11), spins(11));
+    assert_ne!(spins(11), spins(12));
+}
+
+#[test]
+fn the_builtin_machines_keep_an_edge() {
+    let book = MachineBook::builtin();
+    assert!(!book.machines.is_empty());
+    for machine in &book.machines {
+        let rtp = machine.rtp();
+        assert!(
+            rtp > 0.8 && rtp < 1.0,
+            "{} returns {:.4}",
+            machine.name,
+            rtp
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use terminal_casino::baccarat::{Card, GamePhase, Money, SPADES, Shoe, Side};
+use terminal_casino::war::{WAR_BURN, WarGame};
+
+fn dollars(amount: i32) -> Money {
+    Money::from_dollars(amount)
+}
+
+/// Deals `ranks` in order, player first, on a $10 ante and a `tie_bet`,
+/// answering a tie with `war`.
+fn play(ranks: &[u8], tie_bet: i32, war: bool) -> WarGame {
+    let cards = ranks.iter().map(|&rank| Card::new(SPADES, rank)).collect();
+    let mut game = WarGame::with_shoe(Shoe::from_cards(cards));
+    game.deal(dollars(10), dollars(tie_bet)).unwrap();
+    loop {
+        match game.advance().unwrap() {
+            GamePhase::DecisionRequired(side) => {
+                assert_eq!(side, Side::Player);
+                game.decide(war).unwrap();
+            }
+            GamePhase::Settled => return game,
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn the_higher_card_wins_aces_high() {
+    let game = play(&[1, 13], 5, true);
+    assert_eq!(game.player_wins(), Some(true));
+    assert_eq!(game.returned(), dollars(20));
+
+    let game = play(&[12, 13], 0, true);
+    assert_eq!(game.player_wins(), Some(false));
+    assert_eq!(game.returned(), Money::ZERO);
+}
+
+#[test]
+fn surrendering_a_tie_returns_half_the_ante() {
+    let game = play(&[9, 9, 2, 3, 4, 5, 6], 5, false);
+    assert!(game.surrendered);
+    assert_eq!(game.player_wins(), None);
+    assert_eq!(game.raise, Money::ZERO);
+    assert!(game.burned.is_empty());
+    // Half the $10 ante back, and the $5 tie bet paid 10:1.
+    assert_eq!(game.wagered(), dollars(15));
+    assert_eq!(game.returned(), dollars(5 + 55));
+}
+
+#[test]
+fn winning_the_war_pays_the_raise_and_pushes_the_ante() {
+    let game = play(&[9, 9, 2, 3, 4, 12, 4], 0, true);
+    assert_eq!(game.burned.len(), WAR_BURN);
+    assert_eq!(game.player.len(), 2);
+    assert_eq!(game.wagered(), dollars(20));
+    assert_eq!(game.player_wins(), Some(true));
+    assert_eq!(game.returned(), dollars(30));
+}
+
+#[test]
+fn a_tie_at_war_pays_the_ante_as_a_bonus() {
+    let game = play(&[9, 9, 2, 3, 4, 7, 7], 0, true);
+    assert_eq!(game.player_wins(), Some(true));
+    assert_eq!(game.returned(), dollars(40));
+}
+
+#[test]
+fn losing_the_war_loses_both_stakes() {
+    let game = play(&[9, 9, 2, 3, 4, 3, 13], 5, true);
+    assert_eq!(game.player_wins(), Some(false));
+    assert_eq!(game.wagered(), dollars(25));
+    // Only the tie bet on the first two cards comes back.
+    assert_eq!(game.returned(), dollars(55));
+}
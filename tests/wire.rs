@@ -0,0 +1,80 @@
+use terminal_casino::baccarat::{BonusBetType, BonusBets, Card, GameState, HEARTS, Money, SPADES};
+use terminal_casino::wire::{Frame, HEADER_LEN, WireError, read_frame};
+
+fn frames() -> Vec<Frame> {
+    let mut bets = BonusBets::new();
+    bets.set(BonusBetType::PlayerPair, Money::from_dollars(5));
+    vec![
+        Frame::State(GameState {
+            player_score: 8,
+            banker_score: 3,
+            round_complete: 1,
+            winner: 1,
+        }),
+        Frame::Cards(vec![Card::new(HEARTS, 1), Card::new(SPADES, 13)]),
+        Frame::BonusBets(bets),
+    ]
+}
+
+#[test]
+fn frames_decode_to_what_was_encoded() {
+    for frame in frames() {
+        let bytes = frame.encode();
+        assert_eq!(&bytes[..4], b"TCWF");
+        assert_eq!(Frame::decode(&bytes).unwrap(), (frame.clone(), bytes.len()));
+    }
+
+    let stream: Vec<u8> = frames().iter().flat_map(Frame::encode).collect();
+    let mut input = &stream[..];
+    for frame in frames() {
+        assert_eq!(read_frame(&mut input).unwrap(), Some(frame));
+    }
+    assert_eq!(read_frame(&mut input).unwrap(), None);
+}
+
+#[test]
+fn a_payload_that_fails_its_checksum_is_refused() {
+    let mut bytes = Frame::Cards(vec![Card::new(HEARTS, 4)]).encode();
+    bytes[HEADER_LEN + 1] = 5;
+    assert!(matches!(
+        Frame::decode(&bytes),
+        Err(WireError::ChecksumMismatch)
+    ));
+    assert!(matches!(
+        read_frame(&mut &bytes[..]),
+        Err(WireError::ChecksumMismatch)
+    ));
+}
+
+#[test]
+fn a_frame_cut_short_is_truncated() {
+    let bytes = Frame::Cards(vec![Card::new(HEARTS, 4); 3]).encode();
+    for end in [1, HEADER_LEN - 1, HEADER_LEN, bytes.len() - 1] {
+        let cut = &bytes[..end];
+        assert!(
+            matches!(Frame::decode(cut), Err(WireError::Truncated)),
+            "cut at {}",
+            end
+        );
+        assert!(
+            matches!(read_frame(&mut &cut[..]), Err(WireError::Truncated)),
+            "cut at {}",
+            end
+        );
+    }
+}
+
+#[test]
+fn cards_no_table_deals_are_refused() {
+    for card in [Card::new(SPADES, 0), Card::new(SPADES, 14), Card::new(4, 1)] {
+        let bytes = Frame::Cards(vec![Card::new(HEARTS, 1), card]).encode();
+        assert!(matches!(
+            Frame::decode(&bytes),
+            Err(WireError::BadValue { kind: 2 })
+        ));
+    }
+
+    let mut bytes = frames()[0].encode();
+    bytes[0] = b'X';
+    assert!(matches!(Frame::decode(&bytes), Err(WireError::BadMagic)));
+}